@@ -1,18 +1,26 @@
 use crate::msrvs::Msrv;
-use crate::types::{DisallowedPath, MacroMatcher, MatchLintBehaviour, PubUnderscoreFieldsBehaviour, Rename};
+use crate::profiles::{ProfileLints, PROFILE_ENV_VAR};
+use crate::target_kinds::TargetKind;
+use crate::types::{
+    AllowedOperators, CastCategory, DisallowedAttribute, DisallowedEnvVar, DisallowedPath, DisallowedTypeAcrossRegions,
+    LayeringRule, MacroMatcher, MatchLintBehaviour, MustCallFinalizer, NamePatterns, PointerWidth,
+    PubUnderscoreFieldsBehaviour, Rename, ThresholdOverride,
+};
 use crate::ClippyConfiguration;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::Applicability;
 use rustc_session::Session;
 use rustc_span::edit_distance::edit_distance;
-use rustc_span::{BytePos, Pos, SourceFile, Span, SyntaxContext};
-use serde::de::{IgnoredAny, IntoDeserializer, MapAccess, Visitor};
+use rustc_span::{BytePos, FileName, Pos, SourceFile, Span, SyntaxContext};
+use serde::de::{DeserializeSeed, IgnoredAny, IntoDeserializer, MapAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
+use std::cell::Cell;
+use std::collections::BTreeMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::Range;
 use std::path::PathBuf;
 use std::str::FromStr;
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 use std::{cmp, env, fmt, fs, io};
 
 #[rustfmt::skip]
@@ -40,6 +48,14 @@ const DEFAULT_DOC_VALID_IDENTS: &[&str] = &[
 const DEFAULT_DISALLOWED_NAMES: &[&str] = &["foo", "baz", "quux"];
 const DEFAULT_ALLOWED_IDENTS_BELOW_MIN_CHARS: &[&str] = &["i", "j", "x", "y", "z", "w", "n"];
 const DEFAULT_ALLOWED_PREFIXES: &[&str] = &["to", "as", "into", "from", "try_into", "try_from"];
+const DEFAULT_CLOCK_FUNCTIONS: &[&str] = &["std::time::Instant::now", "std::time::SystemTime::now"];
+const DEFAULT_RANDOMNESS_FUNCTIONS: &[&str] = &[
+    "rand::thread_rng",
+    "rand::rng",
+    "rand::random",
+    "getrandom::getrandom",
+    "getrandom::fill",
+];
 
 /// Conf with parse errors
 #[derive(Default)]
@@ -49,16 +65,6 @@ struct TryConf {
     warnings: Vec<ConfError>,
 }
 
-impl TryConf {
-    fn from_toml_error(file: &SourceFile, error: &toml::de::Error) -> Self {
-        Self {
-            conf: Conf::default(),
-            errors: vec![ConfError::from_toml(file, error)],
-            warnings: vec![],
-        }
-    }
-}
-
 #[derive(Debug)]
 struct ConfError {
     message: String,
@@ -134,15 +140,42 @@ macro_rules! define_Conf {
             }
         }
 
+        /// The values set by a configuration file, or by one of its conditional sections.
+        #[derive(Default)]
+        struct ConfLayer {
+            $($name: Option<$ty>,)*
+        }
+
+        impl ConfLayer {
+            /// Overrides the values of `self` with the ones set in `other`.
+            fn merge(&mut self, other: Self) {
+                $(if other.$name.is_some() {
+                    self.$name = other.$name;
+                })*
+            }
+
+            fn into_conf(self) -> Conf {
+                Conf { $($name: self.$name.unwrap_or_else(defaults::$name),)* }
+            }
+        }
+
         #[derive(Deserialize)]
         #[serde(field_identifier, rename_all = "kebab-case")]
         #[allow(non_camel_case_types)]
-        enum Field { $($name,)* third_party, }
+        enum Field { $($name,)* third_party, cfg, target, target_kind, profile, lints, }
 
-        struct ConfVisitor<'a>(&'a SourceFile);
+        #[derive(Clone, Copy)]
+        struct ConfVisitor<'a> {
+            file: &'a SourceFile,
+            conditions: &'a ConfConditions,
+            /// Whether this is a conditional section, which may not contain other sections.
+            in_section: bool,
+            /// Whether this is a profile or target kind section, which may contain lint levels.
+            allows_lints: bool,
+        }
 
         impl<'de> Visitor<'de> for ConfVisitor<'_> {
-            type Value = TryConf;
+            type Value = TryConfLayer;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
                 formatter.write_str("Conf")
@@ -151,30 +184,31 @@ macro_rules! define_Conf {
             fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error> where V: MapAccess<'de> {
                 let mut errors = Vec::new();
                 let mut warnings = Vec::new();
+                let mut sections = Vec::new();
                 $(let mut $name = None;)*
                 // could get `Field` here directly, but get `String` first for diagnostics
                 while let Some(name) = map.next_key::<toml::Spanned<String>>()? {
                     match Field::deserialize(name.get_ref().as_str().into_deserializer()) {
                         Err(e) => {
                             let e: FieldError = e;
-                            errors.push(ConfError::spanned(self.0, e.error, e.suggestion, name.span()));
+                            errors.push(ConfError::spanned(self.file, e.error, e.suggestion, name.span()));
                         }
                         $(Ok(Field::$name) => {
-                            $(warnings.push(ConfError::spanned(self.0, format!("deprecated field `{}`. {}", name.get_ref(), $dep), None, name.span()));)?
+                            $(warnings.push(ConfError::spanned(self.file, format!("deprecated field `{}`. {}", name.get_ref(), $dep), None, name.span()));)?
                             let raw_value = map.next_value::<toml::Spanned<toml::Value>>()?;
                             let value_span = raw_value.span();
                             match <$ty>::deserialize(raw_value.into_inner()) {
-                                Err(e) => errors.push(ConfError::spanned(self.0, e.to_string().replace('\n', " ").trim(), None, value_span)),
+                                Err(e) => errors.push(ConfError::spanned(self.file, e.to_string().replace('\n', " ").trim(), None, value_span)),
                                 Ok(value) => match $name {
                                     Some(_) => {
-                                        errors.push(ConfError::spanned(self.0, format!("duplicate field `{}`", name.get_ref()), None, name.span()));
+                                        errors.push(ConfError::spanned(self.file, format!("duplicate field `{}`", name.get_ref()), None, name.span()));
                                     }
                                     None => {
                                         $name = Some(value);
                                         // $new_conf is the same as one of the defined `$name`s, so
                                         // this variable is defined in line 2 of this function.
                                         $(match $new_conf {
-                                            Some(_) => errors.push(ConfError::spanned(self.0, concat!(
+                                            Some(_) => errors.push(ConfError::spanned(self.file, concat!(
                                                 "duplicate field `", stringify!($new_conf),
                                                 "` (provided as `", stringify!($name), "`)"
                                             ), None, name.span())),
@@ -185,11 +219,43 @@ macro_rules! define_Conf {
                             }
                         })*
                         // ignore contents of the third_party key
-                        Ok(Field::third_party) => drop(map.next_value::<IgnoredAny>())
+                        Ok(Field::third_party) => drop(map.next_value::<IgnoredAny>()),
+                        Ok(field @ (Field::cfg | Field::target | Field::target_kind | Field::profile)) => {
+                            if self.in_section {
+                                errors.push(ConfError::spanned(self.file, "conditional sections cannot be nested", None, name.span()));
+                                drop(map.next_value::<IgnoredAny>());
+                            } else {
+                                let kind = match field {
+                                    Field::cfg => SectionKind::Cfg,
+                                    Field::target => SectionKind::Target,
+                                    Field::target_kind => SectionKind::TargetKind,
+                                    _ => SectionKind::Profile,
+                                };
+                                sections.extend(map.next_value_seed(SectionsVisitor { conf: self, kind })?);
+                            }
+                        },
+                        // the lint levels are read by `profile_lint_flags` and `target_kind_lint_flags`,
+                        // only check them here
+                        Ok(Field::lints) => {
+                            // `Spanned` can't be used for tables written as `[profile.<name>.lints.clippy]`
+                            let value = map.next_value::<toml::Table>()?;
+                            if !self.allows_lints {
+                                errors.push(ConfError::spanned(self.file, "lint levels can only be set in a `profile` or `target-kind` section", None, name.span()));
+                            } else if let Err(e) = ProfileLints::deserialize(toml::Value::Table(value)) {
+                                errors.push(ConfError::spanned(self.file, e.to_string().replace('\n', " ").trim(), None, name.span()));
+                            }
+                        },
                     }
                 }
-                let conf = Conf { $($name: $name.unwrap_or_else(defaults::$name),)* };
-                Ok(TryConf { conf, errors, warnings })
+                let mut layer = ConfLayer { $($name,)* };
+                for section in sections {
+                    errors.extend(section.errors);
+                    warnings.extend(section.warnings);
+                    if let Some(section_layer) = section.layer {
+                        layer.merge(section_layer);
+                    }
+                }
+                Ok(TryConfLayer { layer: Some(layer), errors, warnings })
             }
         }
 
@@ -201,6 +267,7 @@ macro_rules! define_Conf {
 
                         ClippyConfiguration::new(
                             stringify!($name),
+                            stringify!($ty),
                             default_text!(defaults::$name() $(, $default_text)?),
                             concat!($($doc, '\n',)*),
                             deprecation_reason,
@@ -259,11 +326,42 @@ define_Conf! {
     /// arithmetic-side-effects-allowed-unary = ["SomeType", "AnotherType"]
     /// ```
     (arithmetic_side_effects_allowed_unary: FxHashSet<String> = <_>::default()),
-    /// Lint: ENUM_VARIANT_NAMES, LARGE_TYPES_PASSED_BY_VALUE, TRIVIALLY_COPY_PASS_BY_REF, UNNECESSARY_WRAPS, UNUSED_SELF, UPPER_CASE_ACRONYMS, WRONG_SELF_CONVENTION, BOX_COLLECTION, REDUNDANT_ALLOCATION, RC_BUFFER, VEC_BOX, OPTION_OPTION, LINKEDLIST, RC_MUTEX, UNNECESSARY_BOX_RETURNS, SINGLE_CALL_FN.
+    /// Lint: ARITHMETIC_SIDE_EFFECTS.
+    ///
+    /// Suppress checking of the given operators on the passed type names. `rhs` restricts binary
+    /// operations to a right-hand side type and defaults to any type. The operators are `+`, `-`,
+    /// `*`, `/`, `%`, `<<`, `>>` and `neg` (the unary `-`), and include their assigning variants.
+    /// The operators configured for a type take precedence over `arithmetic-side-effects-allowed`,
+    /// `arithmetic-side-effects-allowed-unary` and `arithmetic-side-effects-wrapper-types`, so the
+    /// other operators are checked even if the type is listed there.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// arithmetic-side-effects-allowed-operators = [
+    ///     { type = "std::time::Duration", operators = ["+", "-"] },
+    ///     { type = "Meters", rhs = "f64", operators = ["*", "/"] },
+    /// ]
+    /// ```
+    (arithmetic_side_effects_allowed_operators: Vec<AllowedOperators> = Vec::new()),
+    /// Lint: ARITHMETIC_SIDE_EFFECTS.
+    ///
+    /// The paths of types, such as saturating or wrapping newtypes, whose operations can't have
+    /// side effects. Operations involving them are never checked. Unlike the names in
+    /// `arithmetic-side-effects-allowed`, the paths are resolved, so they also match through
+    /// type aliases and re-exports.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// arithmetic-side-effects-wrapper-types = ["my_crate::num::Saturated", "fixed::types::U16F16"]
+    /// ```
+    (arithmetic_side_effects_wrapper_types: Vec<String> = Vec::new()),
+    /// Lint: ENUM_VARIANT_NAMES, LARGE_TYPES_PASSED_BY_VALUE, TRIVIALLY_COPY_PASS_BY_REF, UNNECESSARY_WRAPS, UNUSED_SELF, UPPER_CASE_ACRONYMS, WRONG_SELF_CONVENTION, BOX_COLLECTION, REDUNDANT_ALLOCATION, RC_BUFFER, VEC_BOX, OPTION_OPTION, LINKEDLIST, RC_MUTEX, UNNECESSARY_BOX_RETURNS, SINGLE_CALL_FN, IMPL_TRAIT_IN_PARAMS, NEEDLESS_PASS_BY_REF_MUT, EXTRA_UNUSED_TYPE_PARAMETERS.
     ///
     /// Suppress lints whenever the suggested change would cause breakage for other crates.
     (avoid_breaking_exported_api: bool = true),
-    /// Lint: MANUAL_SPLIT_ONCE, MANUAL_STR_REPEAT, CLONED_INSTEAD_OF_COPIED, REDUNDANT_FIELD_NAMES, OPTION_MAP_UNWRAP_OR, REDUNDANT_STATIC_LIFETIMES, FILTER_MAP_NEXT, CHECKED_CONVERSIONS, MANUAL_RANGE_CONTAINS, USE_SELF, MEM_REPLACE_WITH_DEFAULT, MANUAL_NON_EXHAUSTIVE, OPTION_AS_REF_DEREF, MAP_UNWRAP_OR, MATCH_LIKE_MATCHES_MACRO, MANUAL_STRIP, MISSING_CONST_FOR_FN, UNNESTED_OR_PATTERNS, FROM_OVER_INTO, PTR_AS_PTR, IF_THEN_SOME_ELSE_NONE, APPROX_CONSTANT, DEPRECATED_CFG_ATTR, INDEX_REFUTABLE_SLICE, MAP_CLONE, BORROW_AS_PTR, MANUAL_BITS, ERR_EXPECT, CAST_ABS_TO_UNSIGNED, UNINLINED_FORMAT_ARGS, MANUAL_CLAMP, MANUAL_LET_ELSE, UNCHECKED_DURATION_SUBTRACTION, COLLAPSIBLE_STR_REPLACE, SEEK_FROM_CURRENT, SEEK_REWIND, UNNECESSARY_LAZY_EVALUATIONS, TRANSMUTE_PTR_TO_REF, ALMOST_COMPLETE_RANGE, NEEDLESS_BORROW, DERIVABLE_IMPLS, MANUAL_IS_ASCII_CHECK, MANUAL_REM_EUCLID, MANUAL_RETAIN, TYPE_REPETITION_IN_BOUNDS, TUPLE_ARRAY_CONVERSIONS, MANUAL_TRY_FOLD, MANUAL_HASH_ONE, ITER_KV_MAP, MANUAL_C_STR_LITERALS, ASSIGNING_CLONES, LEGACY_NUMERIC_CONSTANTS.
+    /// Lint: MANUAL_SPLIT_ONCE, MANUAL_STR_REPEAT, CLONED_INSTEAD_OF_COPIED, REDUNDANT_FIELD_NAMES, OPTION_MAP_UNWRAP_OR, REDUNDANT_STATIC_LIFETIMES, FILTER_MAP_NEXT, CHECKED_CONVERSIONS, MANUAL_RANGE_CONTAINS, USE_SELF, MEM_REPLACE_WITH_DEFAULT, MANUAL_NON_EXHAUSTIVE, OPTION_AS_REF_DEREF, MAP_UNWRAP_OR, MATCH_LIKE_MATCHES_MACRO, MANUAL_STRIP, MISSING_CONST_FOR_FN, MISSING_REQUIRED_CONST_FN, UNNESTED_OR_PATTERNS, FROM_OVER_INTO, PTR_AS_PTR, IF_THEN_SOME_ELSE_NONE, APPROX_CONSTANT, DEPRECATED_CFG_ATTR, INDEX_REFUTABLE_SLICE, MAP_CLONE, BORROW_AS_PTR, MANUAL_BITS, ERR_EXPECT, CAST_ABS_TO_UNSIGNED, UNINLINED_FORMAT_ARGS, MANUAL_CLAMP, MANUAL_LET_ELSE, UNCHECKED_DURATION_SUBTRACTION, COLLAPSIBLE_STR_REPLACE, SEEK_FROM_CURRENT, SEEK_REWIND, UNNECESSARY_LAZY_EVALUATIONS, TRANSMUTE_PTR_TO_REF, ALMOST_COMPLETE_RANGE, NEEDLESS_BORROW, DERIVABLE_IMPLS, MANUAL_IS_ASCII_CHECK, MANUAL_REM_EUCLID, MANUAL_RETAIN, TYPE_REPETITION_IN_BOUNDS, TUPLE_ARRAY_CONVERSIONS, MANUAL_TRY_FOLD, MANUAL_HASH_ONE, ITER_KV_MAP, MANUAL_C_STR_LITERALS, ASSIGNING_CLONES, LEGACY_NUMERIC_CONSTANTS.
     ///
     /// The minimum rust version that the project supports. Defaults to the `rust-version` field in `Cargo.toml`
     #[default_text = ""]
@@ -358,6 +456,21 @@ define_Conf! {
     ///
     /// The maximum number of lines a function or method can have
     (too_many_lines_threshold: u64 = 100),
+    /// Lint: COGNITIVE_COMPLEXITY, TOO_MANY_ARGUMENTS, TOO_MANY_LINES, TYPE_COMPLEXITY, STRUCT_EXCESSIVE_BOOLS, FN_PARAMS_EXCESSIVE_BOOLS.
+    ///
+    /// Thresholds which replace the global ones in the source files matching a path glob. Paths are
+    /// relative to the package root; `*` matches within a path component and `**` matches any number
+    /// of components. The first matching entry applies. A `#[clippy::limit(..)]` or
+    /// `#[clippy::max_*(..)]` attribute takes precedence over these.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// threshold-overrides = [
+    ///     { path = "src/parser/**", too-many-lines-threshold = 300, cognitive-complexity-threshold = 50 },
+    /// ]
+    /// ```
+    (threshold_overrides: Vec<ThresholdOverride> = Vec::new()),
     /// Lint: LARGE_STACK_ARRAYS, LARGE_CONST_ARRAYS.
     ///
     /// The maximum allowed size for arrays on the stack
@@ -382,6 +495,15 @@ define_Conf! {
     ///
     /// The maximum number of bool parameters a function can have
     (max_fn_params_bools: u64 = 3),
+    /// Lint: MATCH_BOOL_TUPLE.
+    ///
+    /// The maximum number of booleans in the tuples matched by a `match` for it to be linted
+    (max_match_bool_tuple_arity: u64 = 3),
+    /// Lint: MATCH_SAME_ARMS.
+    ///
+    /// Whether to consider the paths resolving to the same item as equal however they are
+    /// qualified, e.g. `mem::take(x)` and `std::mem::take(x)`
+    (match_same_arms_ignore_path_prefixes: bool = false),
     /// Lint: WILDCARD_IMPORTS.
     ///
     /// Whether to allow certain wildcard imports (prelude, super in tests).
@@ -398,6 +520,51 @@ define_Conf! {
     ///
     /// The list of disallowed types, written as fully qualified paths.
     (disallowed_types: Vec<DisallowedPath> = Vec::new()),
+    /// Lint: MUST_USE_TYPES.
+    ///
+    /// The list of types which should be treated as if they were `#[must_use]`, written as fully
+    /// qualified paths. Paths can start with `crate`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// must-use-types = [
+    ///     "crate::Guard",
+    ///     { path = "db::Transaction", reason = "the transaction is rolled back when dropped" },
+    /// ]
+    /// ```
+    (must_use_types: Vec<DisallowedPath> = Vec::new()),
+    /// Lint: DISALLOWED_IMPORTS.
+    ///
+    /// The list of disallowed imports, written as fully qualified paths. A path ending in `::*`
+    /// disallows the module and everything inside of it. Paths may start with `crate`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// disallowed-imports = [
+    ///     "openssl::*",
+    ///     { path = "crate::legacy::*", reason = "use `crate::storage` instead" },
+    /// ]
+    /// ```
+    (disallowed_imports: Vec<DisallowedPath> = Vec::new()),
+    /// Lint: DISALLOWED_ATTRIBUTES.
+    ///
+    /// The list of disallowed attributes. An attribute written with arguments, like `inline(always)`,
+    /// only matches if one of the arguments is used. Derive and attribute macros are written with
+    /// their fully qualified path, like `derive(serde::Deserialize)`. An attribute can be disallowed
+    /// only inside of a module with `scope`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// disallowed-attributes = [
+    ///     "inline(always)",
+    ///     { attribute = "allow(dead_code)", reason = "remove the dead code instead" },
+    ///     { attribute = "no_mangle", scope = "crate::domain" },
+    /// ]
+    /// ```
+    (disallowed_attributes: Vec<DisallowedAttribute> = Vec::new()),
     /// Lint: UNREADABLE_LITERAL.
     ///
     /// Should the fraction of a decimal be linted to include separators.
@@ -435,7 +602,7 @@ define_Conf! {
     ///
     /// Whether to apply the raw pointer heuristic to determine if a type is `Send`.
     (enable_raw_pointer_heuristic_for_send: bool = true),
-    /// Lint: INDEX_REFUTABLE_SLICE.
+    /// Lint: INDEX_REFUTABLE_SLICE, MANUAL_SLICE_PATTERN.
     ///
     /// When Clippy suggests using a slice pattern, this is the maximum number of elements allowed in
     /// the slice pattern that is suggested. If more elements are necessary, the lint is suppressed.
@@ -443,6 +610,181 @@ define_Conf! {
     (max_suggested_slice_pattern_length: u64 = 3),
     /// Lint: AWAIT_HOLDING_INVALID_TYPE.
     (await_holding_invalid_types: Vec<DisallowedPath> = Vec::new()),
+    /// Lint: LET_UNDERSCORE_LOCK, MEM_FORGET.
+    ///
+    /// The paths of additional types whose drop has effects other code can observe, such as
+    /// releasing a lock or a connection. Like the lock guards, files and channels of `std`, values
+    /// of these types, and of types containing them, are treated as having a significant drop.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// significant-drop-types = ["my_crate::pool::Connection"]
+    /// ```
+    (significant_drop_types: Vec<String> = Vec::new()),
+    /// Lint: DISALLOWED_TYPES_ACROSS_REGIONS.
+    ///
+    /// The list of types which may not be live across the given regions. A region is either
+    /// `"await"` (an `await` point), `"ffi"` (a call to a foreign function), or
+    /// `{ call = "path::to::function" }` (a call to the given function).
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// disallowed-types-across-regions = [
+    ///     { path = "db::TransactionGuard", across = ["await", { call = "app::render" }] },
+    ///     { path = "std::cell::RefMut", across = ["ffi"], reason = "callbacks may borrow again" },
+    /// ]
+    /// ```
+    (disallowed_types_across_regions: Vec<DisallowedTypeAcrossRegions> = Vec::new()),
+    /// Lint: MISSING_FINALIZER.
+    ///
+    /// The list of constructors whose result must be passed to one of the `finalizers` on every
+    /// path before it is dropped. If `finalizers` is empty, the result only needs to be bound
+    /// instead of being dropped right away.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// must-call-finalizers = [
+    ///     { constructor = "db::Transaction::begin", finalizers = ["db::Transaction::commit", "db::Transaction::rollback"] },
+    ///     { constructor = "http::RequestBuilder::new", finalizers = ["http::RequestBuilder::build"] },
+    ///     { constructor = "tracing::Span::enter", reason = "the span is exited when the guard is dropped" },
+    /// ]
+    /// ```
+    (must_call_finalizers: Vec<MustCallFinalizer> = Vec::new()),
+    /// Lint: NAMING_CONVENTIONS.
+    ///
+    /// The regexes which the whole name of each kind of item must match: `types` (structs, enums,
+    /// unions, type aliases and associated types), `traits`, `functions` (including methods),
+    /// `constants` (including statics), `features` (the features in `Cargo.toml`) and `tests`
+    /// (`#[test]` functions, which are checked against `functions` if this is not set).
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// naming-conventions = { types = "[A-Z][A-Za-z0-9]*", tests = "test_[a-z0-9_]+", features = "[a-z0-9-]+" }
+    /// ```
+    (naming_conventions: NamePatterns = NamePatterns::default()),
+    /// Lint: MODULE_LAYERING.
+    ///
+    /// The dependencies between the modules of the crate which are not allowed. The module `from`,
+    /// including its submodules, may not use any item of the module `to`, unless `allow-in-tests`
+    /// is set and the use is in a test or in `#[cfg(test)]` code.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// module-layering = [
+    ///     { from = "crate::domain", to = "crate::http", reason = "the domain must not depend on the transport" },
+    ///     { from = "crate::*", to = "crate::test_support", allow-in-tests = true },
+    /// ]
+    /// ```
+    (module_layering: Vec<LayeringRule> = Vec::new()),
+    /// Lint: DISALLOWED_ENV_VARS.
+    ///
+    /// The environment variables which may only be read in the `allowed-in` modules, by
+    /// `std::env::var`, `std::env::var_os`, `env!` or `option_env!`. Names may contain the wildcards
+    /// `*` and `?`, and modules are paths starting with `crate`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// disallowed-env-vars = [
+    ///     { name = "SECRET_KEY", allowed-in = ["crate::config"], reason = "secrets are loaded by the config" },
+    ///     { name = "AWS_*" },
+    /// ]
+    /// ```
+    (disallowed_env_vars: Vec<DisallowedEnvVar> = Vec::new()),
+    /// Lint: ENV_ACCESS_OUTSIDE_CONFIG.
+    ///
+    /// The modules, including their submodules, which may read the environment. Modules are paths
+    /// starting with `crate`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// env-config-modules = ["crate::config"]
+    /// ```
+    (env_config_modules: Vec<String> = Vec::new()),
+    /// Lint: ENV_ACCESS_OUTSIDE_CONFIG.
+    ///
+    /// The environment variables which may be read outside of the `env-config-modules`. Names may
+    /// contain the wildcards `*` and `?`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// allowed-env-vars = ["CARGO_PKG_*", "RUST_LOG"]
+    /// ```
+    (allowed_env_vars: Vec<String> = Vec::new()),
+    /// Lint: DIRECT_CLOCK_ACCESS.
+    ///
+    /// The functions reading the clock. Use `".."` as part of the list to add to the default
+    /// `std::time::Instant::now` and `std::time::SystemTime::now`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// clock-functions = ["..", "chrono::Utc::now", "chrono::Local::now"]
+    /// ```
+    (clock_functions: Vec<String> = DEFAULT_CLOCK_FUNCTIONS.iter().map(ToString::to_string).collect()),
+    /// Lint: DIRECT_CLOCK_ACCESS.
+    ///
+    /// The modules, including their submodules, which may read the clock. Modules are paths
+    /// starting with `crate`.
+    (clock_modules: Vec<String> = Vec::new()),
+    /// Lint: DIRECT_CLOCK_ACCESS.
+    ///
+    /// The path of the abstraction which the code should read the time from, e.g.
+    /// `crate::time::Clock`.
+    #[default_text = ""]
+    (clock_abstraction: Option<String> = None),
+    /// Lint: AMBIENT_RANDOMNESS.
+    ///
+    /// The functions returning an ambient source of randomness, or values drawn from it. Use
+    /// `".."` as part of the list to add to the default `rand::thread_rng`, `rand::rng`,
+    /// `rand::random`, `getrandom::getrandom` and `getrandom::fill`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// randomness-functions = ["..", "fastrand::u64", "uuid::Uuid::new_v4"]
+    /// ```
+    (randomness_functions: Vec<String> = DEFAULT_RANDOMNESS_FUNCTIONS.iter().map(ToString::to_string).collect()),
+    /// Lint: AMBIENT_RANDOMNESS.
+    ///
+    /// The modules, including their submodules, which may use an ambient source of randomness,
+    /// e.g. to create the generator passed to the rest of the code. Modules are paths starting with
+    /// `crate`.
+    (randomness_modules: Vec<String> = Vec::new()),
+    /// Lint: PANIC_IN_PRODUCTION.
+    ///
+    /// Whether `unwrap`, `expect`, `panic!`, `todo!`, `unimplemented!` and indexing are forbidden
+    /// in the code of libraries and binaries. Tests, benchmarks, examples and build scripts may
+    /// still panic.
+    (forbid_panics_in_production: bool = false),
+    /// Lint: IMPLICIT_FIELD_INIT.
+    ///
+    /// The structs whose literals must initialize all the fields explicitly, without
+    /// `..Default::default()` or another base, e.g. `crate::config::TlsConfig`.
+    (exhaustive_init_types: Vec<String> = Vec::new()),
+    /// Lint: MISSING_REQUIRED_CONST_FN.
+    ///
+    /// The modules, including their submodules, whose functions must be `const` when they can be.
+    /// Modules are paths starting with `crate`, and may contain `*` wildcards, e.g.
+    /// `crate::*::consts`. If empty, the functions of all the modules are linted.
+    (required_const_fn_modules: Vec<String> = Vec::new()),
+    /// Lint: RECURSION.
+    ///
+    /// The recursive functions whose depth was audited, e.g. `crate::parser::parse_expr`. They
+    /// aren't linted, nor are the cycles of calls going through them.
+    (allowed_recursive_functions: Vec<String> = Vec::new()),
+    /// Lint: ALLOC_IN_NO_ALLOC.
+    ///
+    /// The modules, including their submodules, whose functions mustn't allocate, like the
+    /// functions marked with `#[clippy::no_alloc]`. Modules are paths starting with `crate`.
+    (no_alloc_modules: Vec<String> = Vec::new()),
     /// Lint: LARGE_INCLUDE_FILE.
     ///
     /// The maximum size of a file included via `include_bytes!()` or `include_str!()`, in bytes
@@ -459,14 +801,36 @@ define_Conf! {
     ///
     /// Whether `dbg!` should be allowed in test functions or `#[cfg(test)]`
     (allow_dbg_in_tests: bool = false),
+    /// Lint: DISPLAY_FORWARDING_TO_DEBUG.
+    ///
+    /// Whether `Display` implementations printing the `Debug` output should be allowed for types
+    /// implementing `std::error::Error`
+    (allow_debug_display_for_errors: bool = false),
     /// Lint: PRINT_STDOUT, PRINT_STDERR.
     ///
     /// Whether print macros (ex. `println!`) should be allowed in test functions or `#[cfg(test)]`
     (allow_print_in_tests: bool = false),
+    /// Lint: PRINT_IN_LIBRARY.
+    ///
+    /// The path of the logging facade whose `info!` and `warn!` macros replace the prints, e.g.
+    /// `log`, `tracing` or `crate::logging`.
+    (logging_facade: String = String::from("log")),
     /// Lint: USELESS_VEC.
     ///
     /// Whether `useless_vec` should ignore test functions or `#[cfg(test)]`
     (allow_useless_vec_in_tests: bool = false),
+    /// Lint: DBG_MACRO, EXPECT_USED, UNWRAP_USED, PRINT_STDOUT, PRINT_STDERR, USELESS_VEC, MISSING_ASSERT_MESSAGE, MODULE_LAYERING, PANIC_IN_PRODUCTION.
+    ///
+    /// The paths of the attributes and macros of the test frameworks which mark test code, in
+    /// addition to `#[test]`, `#[bench]` and `#[cfg(test)]`. A path with a single segment also
+    /// matches the paths ending with it, e.g. `rstest` matches `#[rstest::rstest]`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// test-attributes = [ "rstest", "test_case", "tokio::test" ]
+    /// ```
+    (test_attributes: Vec<String> = Vec::new()),
     /// Lint: RESULT_LARGE_ERR.
     ///
     /// The maximum size of the `Err`-variant in a `Result` returned from a function
@@ -523,7 +887,7 @@ define_Conf! {
     ///
     /// Whether to accept a safety comment to be placed above the attributes for the `unsafe` block
     (accept_comment_above_attributes: bool = true),
-    /// Lint: UNNECESSARY_RAW_STRING_HASHES.
+    /// Lint: NEEDLESS_RAW_STRING_HASHES.
     ///
     /// Whether to allow `r#""#` when `r""` can be used
     (allow_one_hash_in_raw_strings: bool = false),
@@ -544,6 +908,31 @@ define_Conf! {
     ///
     /// A list of crate names to allow duplicates of
     (allowed_duplicate_crates: FxHashSet<String> = FxHashSet::default()),
+    /// Lint: GIT_DEPENDENCIES.
+    ///
+    /// The git repositories which dependencies are allowed to come from. A dependency is allowed if
+    /// its `git` URL is one of these, or is under one of these, compared by path segments:
+    /// `https://github.com/my-org` allows `https://github.com/my-org/regex`, but not
+    /// `https://github.com/my-org-fork/regex`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// allowed-git-sources = ["https://github.com/my-org/"]
+    /// ```
+    (allowed_git_sources: Vec<String> = Vec::new()),
+    /// Lint: UNAPPROVED_LICENSES.
+    ///
+    /// The SPDX identifiers of the licenses dependencies are allowed to use. A dependency whose
+    /// license expression can't be satisfied with these licenses is linted. Nothing is linted if
+    /// this is empty.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// allowed-licenses = ["MIT", "Apache-2.0", "BSD-3-Clause"]
+    /// ```
+    (allowed_licenses: Vec<String> = Vec::new()),
     /// Lint: EXPLICIT_ITER_LOOP.
     ///
     /// Whether to recommend using implicit into iter for reborrowed values.
@@ -613,6 +1002,68 @@ define_Conf! {
     /// - Use `".."` as part of the list to indicate that the configured values should be appended to the
     /// default configuration of Clippy. By default, any configuration will replace the default value
     (allowed_prefixes: Vec<String> = DEFAULT_ALLOWED_PREFIXES.iter().map(ToString::to_string).collect()),
+    /// Lint: OR_FUN_CALL, UNNECESSARY_LAZY_EVALUATIONS.
+    ///
+    /// The functions and methods which are expensive to call, written as fully qualified paths.
+    /// Their calls are always evaluated lazily, even when they are `const`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// expensive-calls = [ "crate::tables::build_lookup_table", "regex::Regex::new" ]
+    /// ```
+    (expensive_calls: Vec<String> = Vec::new()),
+    /// Lint: POINTER_WIDTH_DEPENDENT_CAST.
+    ///
+    /// The pointer widths of the targets the crate is built for, out of 16, 32 and 64. A cast which
+    /// doesn't lose information on the current target is linted if it does on one of them.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// target-pointer-widths = [ 16, 32, 64 ]
+    /// ```
+    (target_pointer_widths: Vec<PointerWidth> = vec![PointerWidth::W32, PointerWidth::W64]),
+    /// Lint: CAST_POLICY.
+    ///
+    /// The categories of `as` casts which are allowed, out of `widening`, `sign-change`,
+    /// `truncation`, `float-int` and `pointer`. The casts of the other categories are linted.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// allowed-cast-categories = [ "widening", "pointer" ]
+    /// ```
+    (allowed_cast_categories: Vec<CastCategory> = Vec::new()),
+    /// The maximum number of warnings of each lint in a crate. Clippy fails when a lint is emitted
+    /// more often than its budget allows, which allows adopting a lint gradually. The lints still
+    /// have to be enabled. `--warn-budget` on the command line takes precedence over these.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// warn-budget = { "clippy::todo" = 5, "clippy::unwrap_used" = 0 }
+    /// ```
+    (warn_budget: BTreeMap<String, u64> = BTreeMap::new()),
+    /// The lints which `cargo clippy --doctests` runs on the Rust code blocks of the documentation.
+    /// All the enabled lints are run if this is empty.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// doctest-lints = [ "clippy::needless_return", "clippy::redundant_clone" ]
+    /// ```
+    (doctest_lints: Vec<String> = Vec::new()),
+    /// **Unstable**: the dynamic libraries of external lint passes to load, relative to the
+    /// directory of the configuration file. They are only loaded with `CLIPPY_ALLOW_PLUGINS=1` set.
+    /// See the "Plugins" section of the documentation.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// plugins = [ "target/release/libcompany_lints.so" ]
+    /// ```
+    (plugins: Vec<String> = Vec::new()),
 }
 
 /// Search for the configuration file.
@@ -668,25 +1119,213 @@ pub fn lookup_conf_file() -> io::Result<(Option<PathBuf>, Vec<String>)> {
     }
 }
 
-fn deserialize(file: &SourceFile) -> TryConf {
-    match toml::de::Deserializer::new(file.src.as_ref().unwrap()).deserialize_map(ConfVisitor(file)) {
-        Ok(mut conf) => {
-            extend_vec_if_indicator_present(&mut conf.conf.doc_valid_idents, DEFAULT_DOC_VALID_IDENTS);
-            extend_vec_if_indicator_present(&mut conf.conf.disallowed_names, DEFAULT_DISALLOWED_NAMES);
-            extend_vec_if_indicator_present(&mut conf.conf.allowed_prefixes, DEFAULT_ALLOWED_PREFIXES);
-            // TODO: THIS SHOULD BE TESTED, this comment will be gone soon
-            if conf.conf.allowed_idents_below_min_chars.contains("..") {
-                conf.conf
-                    .allowed_idents_below_min_chars
-                    .extend(DEFAULT_ALLOWED_IDENTS_BELOW_MIN_CHARS.iter().map(ToString::to_string));
+/// A configuration file or one of its conditional sections, with parse errors. `layer` is `None`
+/// for the sections which don't apply to the current crate.
+struct TryConfLayer {
+    layer: Option<ConfLayer>,
+    errors: Vec<ConfError>,
+    warnings: Vec<ConfError>,
+}
+
+impl<'de> DeserializeSeed<'de> for ConfVisitor<'_> {
+    type Value = TryConfLayer;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+/// The `cfg`s, target and target kind of the crate being checked, and the selected profile, which
+/// select the conditional sections of the configuration file that apply.
+#[derive(Default)]
+struct ConfConditions {
+    cfgs: FxHashSet<(String, Option<String>)>,
+    target: String,
+    target_kind: TargetKind,
+    profile: Option<String>,
+    /// Whether a section for the selected profile was found.
+    profile_found: Cell<bool>,
+}
+
+impl ConfConditions {
+    fn new(sess: &Session) -> Self {
+        Self {
+            profile: env::var(PROFILE_ENV_VAR).ok().filter(|profile| !profile.is_empty()),
+            profile_found: Cell::new(false),
+            cfgs: sess
+                .psess
+                .config
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.map(|value| value.to_string())))
+                .collect(),
+            target: sess.opts.target_triple.triple().to_string(),
+            target_kind: TargetKind::from_session(sess),
+        }
+    }
+
+    /// Parses a condition of the form `name` or `name = "value"` and checks whether it is set.
+    fn is_cfg_set(&self, cfg: &str) -> Option<bool> {
+        let (name, value) = match cfg.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(value.trim().strip_prefix('"')?.strip_suffix('"')?)),
+            None => (cfg.trim(), None),
+        };
+        if name.is_empty() || !name.chars().all(|c| c == '_' || c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        Some(self.cfgs.contains(&(name.to_string(), value.map(ToString::to_string))))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SectionKind {
+    Cfg,
+    Target,
+    TargetKind,
+    Profile,
+}
+
+/// Visits the `[cfg.'..']`, `[target.'..']`, `[target-kind.<kind>]` or `[profile.<name>]` sections
+/// of a configuration file.
+struct SectionsVisitor<'a> {
+    conf: ConfVisitor<'a>,
+    kind: SectionKind,
+}
+
+impl<'de> Visitor<'de> for SectionsVisitor<'_> {
+    type Value = Vec<TryConfLayer>;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a table of sections")
+    }
+
+    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let mut sections = Vec::new();
+        while let Some(condition) = map.next_key::<toml::Spanned<String>>()? {
+            let mut section = map.next_value_seed(ConfVisitor {
+                in_section: true,
+                allows_lints: matches!(self.kind, SectionKind::TargetKind | SectionKind::Profile),
+                ..self.conf
+            })?;
+            let conditions = self.conf.conditions;
+            let is_active = match self.kind {
+                SectionKind::Cfg => conditions.is_cfg_set(condition.get_ref()),
+                SectionKind::Target => Some(*condition.get_ref() == conditions.target),
+                SectionKind::TargetKind => {
+                    TargetKind::from_name(condition.get_ref()).map(|kind| kind == conditions.target_kind)
+                },
+                SectionKind::Profile => {
+                    let is_selected = conditions.profile.as_ref() == Some(condition.get_ref());
+                    if is_selected {
+                        conditions.profile_found.set(true);
+                    }
+                    Some(is_selected)
+                },
+            };
+            match is_active {
+                Some(true) => {},
+                Some(false) => section.layer = None,
+                None => {
+                    section.layer = None;
+                    let message = if self.kind == SectionKind::TargetKind {
+                        format!(
+                            "invalid target kind `{}`, expected one of {}",
+                            condition.get_ref(),
+                            TargetKind::names()
+                        )
+                    } else {
+                        format!(
+                            "invalid cfg `{}`, expected `name` or `name = \"value\"`",
+                            condition.get_ref()
+                        )
+                    };
+                    section
+                        .errors
+                        .push(ConfError::spanned(self.conf.file, message, None, condition.span()));
+                },
             }
+            sections.push(section);
+        }
+        Ok(sections)
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for SectionsVisitor<'_> {
+    type Value = Vec<TryConfLayer>;
 
-            conf
-        },
-        Err(e) => TryConf::from_toml_error(file, &e),
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
     }
 }
 
+/// Deserializes the configuration files, the values of the later files overriding the ones of the
+/// earlier files.
+fn deserialize(files: &[&SourceFile], conditions: &ConfConditions) -> TryConf {
+    let mut layer = ConfLayer::default();
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    for &file in files {
+        let visitor = ConfVisitor {
+            file,
+            conditions,
+            in_section: false,
+            allows_lints: false,
+        };
+        match toml::de::Deserializer::new(file.src.as_ref().unwrap()).deserialize_map(visitor) {
+            Ok(file_layer) => {
+                errors.extend(file_layer.errors);
+                warnings.extend(file_layer.warnings);
+                if let Some(file_layer) = file_layer.layer {
+                    layer.merge(file_layer);
+                }
+            },
+            Err(e) => errors.push(ConfError::from_toml(file, &e)),
+        }
+    }
+
+    let mut conf = TryConf {
+        conf: layer.into_conf(),
+        errors,
+        warnings,
+    };
+    extend_vec_if_indicator_present(&mut conf.conf.doc_valid_idents, DEFAULT_DOC_VALID_IDENTS);
+    extend_vec_if_indicator_present(&mut conf.conf.disallowed_names, DEFAULT_DISALLOWED_NAMES);
+    extend_vec_if_indicator_present(&mut conf.conf.allowed_prefixes, DEFAULT_ALLOWED_PREFIXES);
+    extend_vec_if_indicator_present(&mut conf.conf.clock_functions, DEFAULT_CLOCK_FUNCTIONS);
+    extend_vec_if_indicator_present(&mut conf.conf.randomness_functions, DEFAULT_RANDOMNESS_FUNCTIONS);
+    // TODO: THIS SHOULD BE TESTED, this comment will be gone soon
+    if conf.conf.allowed_idents_below_min_chars.contains("..") {
+        conf.conf
+            .allowed_idents_below_min_chars
+            .extend(DEFAULT_ALLOWED_IDENTS_BELOW_MIN_CHARS.iter().map(ToString::to_string));
+    }
+
+    conf
+}
+
+/// Converts a `key=value` pair passed to `--clippy-config` to a TOML document, so that it is
+/// validated like a configuration file. The values which aren't valid TOML, and the floats like
+/// `1.70` which would lose their trailing zero, are read as strings, as no configuration is a
+/// float.
+fn override_toml(sess: &Session, item: &str) -> Option<String> {
+    let Some((key, value)) = item.split_once('=') else {
+        sess.dcx()
+            .err(format!("invalid `--clippy-config {item}`, expected `key=value`"));
+        return None;
+    };
+    let value = value.trim();
+    let is_toml = toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .is_ok_and(|table| !matches!(table["value"], toml::Value::Float(_)));
+    let value = if is_toml {
+        value.to_string()
+    } else {
+        toml::Value::String(value.to_string()).to_string()
+    };
+    Some(format!("{} = {value}\n", key.trim()))
+}
+
 fn extend_vec_if_indicator_present(vec: &mut Vec<String>, default: &[&str]) {
     if vec.contains(&"..".to_string()) {
         vec.extend(default.iter().map(ToString::to_string));
@@ -694,12 +1333,32 @@ fn extend_vec_if_indicator_present(vec: &mut Vec<String>, default: &[&str]) {
 }
 
 impl Conf {
-    pub fn read(sess: &Session, path: &io::Result<(Option<PathBuf>, Vec<String>)>) -> &'static Conf {
-        static CONF: OnceLock<Conf> = OnceLock::new();
-        CONF.get_or_init(|| Conf::read_inner(sess, path))
+    /// Reads the configuration file found by [`lookup_conf_file`], with the `key=value` pairs
+    /// passed to `--clippy-config` overriding its values.
+    pub fn read(
+        sess: &Session,
+        path: &io::Result<(Option<PathBuf>, Vec<String>)>,
+        overrides: &[String],
+    ) -> &'static Arc<Conf> {
+        static CONF: OnceLock<Arc<Conf>> = OnceLock::new();
+        CONF.get_or_init(|| Arc::new(Conf::read_inner(sess, path, overrides)))
     }
 
-    fn read_inner(sess: &Session, path: &io::Result<(Option<PathBuf>, Vec<String>)>) -> Conf {
+    /// Parses the configuration from the contents of a `clippy.toml` file, for the tools running
+    /// Clippy's lint passes in their own compiler sessions. Unlike [`Conf::read`], no configuration
+    /// file is looked up, and each call returns a new configuration. The errors are reported to
+    /// `sess`, and the default values are used for the invalid keys.
+    ///
+    /// The configuration can also be built directly, e.g.
+    /// `Conf { too_many_lines_threshold: 50, ..Conf::default() }`.
+    pub fn from_toml(sess: &Session, toml: &str) -> Conf {
+        let file = sess
+            .source_map()
+            .new_source_file(FileName::Custom("clippy.toml".into()), toml.to_string());
+        Conf::from_files(sess, &[&file])
+    }
+
+    fn read_inner(sess: &Session, path: &io::Result<(Option<PathBuf>, Vec<String>)>, overrides: &[String]) -> Conf {
         match path {
             Ok((_, warnings)) => {
                 for warning in warnings {
@@ -712,20 +1371,46 @@ impl Conf {
             },
         }
 
-        let TryConf {
-            mut conf,
-            errors,
-            warnings,
-        } = match path {
+        let file = match path {
             Ok((Some(path), _)) => match sess.source_map().load_file(path) {
-                Ok(file) => deserialize(&file),
+                Ok(file) => Some(file),
                 Err(error) => {
                     sess.dcx().err(format!("failed to read `{}`: {error}", path.display()));
-                    TryConf::default()
+                    None
                 },
             },
-            _ => TryConf::default(),
+            _ => None,
         };
+        // Each override is a separate file, so that the later ones override the earlier ones
+        let overrides: Vec<_> = overrides
+            .iter()
+            .filter_map(|item| {
+                let toml = override_toml(sess, item)?;
+                Some(
+                    sess.source_map()
+                        .new_source_file(FileName::Custom(format!("--clippy-config {item}")), toml),
+                )
+            })
+            .collect();
+        let files: Vec<_> = file.iter().chain(&overrides).map(|file| &**file).collect();
+        Conf::from_files(sess, &files)
+    }
+
+    fn from_files(sess: &Session, files: &[&SourceFile]) -> Conf {
+        let conditions = ConfConditions::new(sess);
+        let TryConf {
+            mut conf,
+            errors,
+            warnings,
+        } = deserialize(files, &conditions);
+
+        if let Some(profile) = &conditions.profile
+            && !conditions.profile_found.get()
+        {
+            sess.dcx().warn(format!(
+                "the Clippy profile `{profile}` is not defined in the configuration file"
+            ));
+        }
 
         conf.msrv.read_cargo(sess);
 
@@ -737,7 +1422,11 @@ impl Conf {
             );
 
             if let Some(sugg) = error.suggestion {
-                diag.span_suggestion(error.span, sugg.message, sugg.suggestion, Applicability::MaybeIncorrect);
+                diag.span_suggestion(error.span, sugg.message, sugg.field, Applicability::MaybeIncorrect);
+                if !sugg.lints.is_empty() {
+                    let lints: Vec<_> = sugg.lints.iter().map(|lint| format!("`{lint}`")).collect();
+                    diag.note(format!("`{}` affects the lints {}", sugg.field, lints.join(", ")));
+                }
             }
 
             diag.emit();
@@ -765,7 +1454,9 @@ struct FieldError {
 #[derive(Debug)]
 struct Suggestion {
     message: &'static str,
-    suggestion: &'static str,
+    field: &'static str,
+    /// The lints whose behavior the suggested field affects.
+    lints: Vec<String>,
 }
 
 impl std::error::Error for FieldError {}
@@ -811,9 +1502,14 @@ impl serde::de::Error for FieldError {
                 Some((dist, expected))
             })
             .min_by_key(|&(dist, _)| dist)
-            .map(|(_, suggestion)| Suggestion {
+            .map(|(_, &field)| Suggestion {
                 message: "perhaps you meant",
-                suggestion,
+                field,
+                lints: get_configuration_metadata()
+                    .into_iter()
+                    .find(|conf| conf.name == field)
+                    .map(|conf| conf.lints)
+                    .unwrap_or_default(),
             });
 
         Self { error: msg, suggestion }
@@ -25,7 +25,11 @@ extern crate rustc_span;
 mod conf;
 mod metadata;
 pub mod msrvs;
+mod profiles;
+mod target_kinds;
 pub mod types;
 
 pub use conf::{get_configuration_metadata, lookup_conf_file, Conf};
-pub use metadata::ClippyConfiguration;
+pub use metadata::{get_configuration_schema, ClippyConfiguration};
+pub use profiles::{profile_lint_flags, PROFILE_ENV_VAR};
+pub use target_kinds::{target_kind_lint_flags, TargetKind};
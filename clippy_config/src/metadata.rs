@@ -1,8 +1,11 @@
+use serde_json::{json, Map, Value};
 use std::fmt::{self, Write};
 
 #[derive(Debug, Clone, Default)]
 pub struct ClippyConfiguration {
     pub name: String,
+    /// The Rust type of the value.
+    pub ty: &'static str,
     pub default: String,
     pub lints: Vec<String>,
     pub doc: String,
@@ -22,17 +25,18 @@ impl fmt::Display for ClippyConfiguration {
 impl ClippyConfiguration {
     pub fn new(
         name: &'static str,
+        ty: &'static str,
         default: String,
         doc_comment: &'static str,
         deprecation_reason: Option<&'static str>,
     ) -> Self {
-        let (mut lints, doc) = parse_config_field_doc(doc_comment)
-            .unwrap_or_else(|| (vec![], "[ERROR] MALFORMED DOC COMMENT".to_string()));
+        let (mut lints, doc) = parse_config_field_doc(doc_comment);
 
         lints.sort();
 
         Self {
             name: to_kebab(name),
+            ty,
             lints,
             doc,
             default,
@@ -55,6 +59,9 @@ impl ClippyConfiguration {
             write!(out, "**Default Value:** `{}`\n\n", self.default).unwrap();
         }
 
+        if self.lints.is_empty() {
+            return out;
+        }
         write!(
             out,
             "---\n**Affected lints:**\n{}\n\n",
@@ -74,6 +81,115 @@ impl ClippyConfiguration {
         const BOOK_CONFIGS_PATH: &str = "https://doc.rust-lang.org/clippy/lint_configuration.html";
         format!("[`{}`]: {BOOK_CONFIGS_PATH}#{}", self.name, self.name)
     }
+
+    /// Returns the JSON schema of the value of this configuration, with its documentation, default
+    /// and affected lints.
+    pub fn to_json_schema(&self) -> Value {
+        let mut schema = Map::new();
+        schema.insert("description".into(), self.doc.replace("\n    ", "\n").into());
+        if let Value::Object(ty) = type_schema(self.ty) {
+            schema.extend(ty);
+        }
+        // The default is rendered as TOML, convert it to JSON.
+        if let Ok(mut table) = toml::from_str::<toml::Table>(&format!("default = {}", self.default))
+            && let Some(default) = table.remove("default")
+            && let Ok(default) = serde_json::to_value(default)
+        {
+            schema.insert("default".into(), default);
+        }
+        schema.insert("x-rust-type".into(), self.ty.into());
+        schema.insert("x-lints".into(), self.lints.clone().into());
+        if let Some(reason) = self.deprecation_reason {
+            schema.insert("deprecated".into(), true.into());
+            schema.insert("x-deprecation-reason".into(), reason.into());
+        }
+        Value::Object(schema)
+    }
+}
+
+/// Returns the JSON schema of the configuration file, for editors and validators.
+pub fn get_configuration_schema() -> Value {
+    let mut properties: Map<_, _> = crate::get_configuration_metadata()
+        .iter()
+        .map(|conf| (conf.name.clone(), conf.to_json_schema()))
+        .collect();
+    for (name, description) in [
+        (
+            "cfg",
+            "Configuration which applies when the crate is compiled with a cfg, e.g. `[cfg.'feature = \"ffi\"']`.",
+        ),
+        (
+            "target",
+            "Configuration which applies when compiling for a target, e.g. `[target.'wasm32-unknown-unknown']`.",
+        ),
+        (
+            "profile",
+            "Configuration and lint levels which apply when a profile is selected with `--clippy-profile`.",
+        ),
+    ] {
+        properties.insert(
+            name.into(),
+            json!({
+                "description": description,
+                "type": "object",
+                "additionalProperties": { "$ref": "#" },
+            }),
+        );
+    }
+    properties.insert(
+        "lints".into(),
+        json!({
+            "description": "The lint levels of a profile, with the layout of the `[lints]` table of `Cargo.toml`.",
+            "type": "object",
+        }),
+    );
+    properties.insert(
+        "third-party".into(),
+        json!({
+            "description": "Ignored by Clippy, for use by third party tools.",
+        }),
+    );
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "clippy.toml",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": properties,
+    })
+}
+
+/// Returns the schema of the values of the Rust type `ty`, as written by `stringify!`. Types
+/// without a known TOML representation have no constraints.
+fn type_schema(ty: &str) -> Value {
+    let ty: String = ty.chars().filter(|c| !c.is_whitespace()).collect();
+    if let Some(item) = ty
+        .strip_prefix("Vec<")
+        .or_else(|| ty.strip_prefix("FxHashSet<"))
+        .and_then(|ty| ty.strip_suffix('>'))
+    {
+        return json!({ "type": "array", "items": type_schema(item) });
+    }
+    if let Some(value) = ty.strip_prefix("BTreeMap<String,").and_then(|ty| ty.strip_suffix('>')) {
+        return json!({ "type": "object", "additionalProperties": type_schema(value) });
+    }
+    if let Some(inner) = ty.strip_prefix("Option<").and_then(|ty| ty.strip_suffix('>')) {
+        return type_schema(inner);
+    }
+    if let Some((item, len)) = ty
+        .strip_prefix('[')
+        .and_then(|ty| ty.strip_suffix(']'))
+        .and_then(|ty| ty.split_once(';'))
+        && let Ok(len) = len.parse::<u64>()
+    {
+        return json!({ "type": "array", "items": type_schema(item), "minItems": len, "maxItems": len });
+    }
+    match ty.as_str() {
+        "bool" => json!({ "type": "boolean" }),
+        "u8" | "u16" | "u32" | "u64" | "usize" => json!({ "type": "integer", "minimum": 0 }),
+        "i8" | "i16" | "i32" | "i64" | "isize" => json!({ "type": "integer" }),
+        "String" | "Msrv" => json!({ "type": "string" }),
+        _ => json!({}),
+    }
 }
 
 /// This parses the field documentation of the config struct.
@@ -84,9 +200,11 @@ impl ClippyConfiguration {
 ///
 /// Would yield:
 /// ```rust, ignore
-/// Some(["lint_name_1", "lint_name_2"], "Papa penguin, papa penguin")
+/// (["lint_name_1", "lint_name_2"], "Papa penguin, papa penguin")
 /// ```
-fn parse_config_field_doc(doc_comment: &str) -> Option<(Vec<String>, String)> {
+///
+/// The documentation of configurations which don't belong to specific lints has no `Lint:` line.
+fn parse_config_field_doc(doc_comment: &str) -> (Vec<String>, String) {
     const DOC_START: &str = " Lint: ";
     if doc_comment.starts_with(DOC_START)
         && let Some(split_pos) = doc_comment.find('.')
@@ -109,9 +227,9 @@ fn parse_config_field_doc(doc_comment: &str) -> Option<(Vec<String>, String)> {
         // split off leading `.` from lint name list and indent for correct formatting
         documentation = documentation.trim_start_matches('.').trim().replace("\n ", "\n    ");
 
-        Some((lints, documentation))
+        (lints, documentation)
     } else {
-        None
+        (vec![], doc_comment.trim().replace("\n ", "\n    "))
     }
 }
 
@@ -0,0 +1,105 @@
+//! Lint levels set by the `[profile.<name>.lints]` tables of the configuration file.
+//!
+//! The levels have to be known before the compiler session is created, so they are read separately
+//! from the rest of the configuration and passed to the compiler as command line flags.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::{fs, io};
+
+/// The environment variable selecting the profile, set by `cargo clippy --clippy-profile <name>`.
+pub const PROFILE_ENV_VAR: &str = "CLIPPY_PROFILE";
+
+/// The `lints` table of a profile, with the same layout as the `[lints]` table of `Cargo.toml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ProfileLints {
+    #[serde(default)]
+    clippy: BTreeMap<String, LintConfig>,
+    #[serde(default)]
+    rust: BTreeMap<String, LintConfig>,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+    Forbid,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LintConfig {
+    Level(LintLevel),
+    Table(LintConfigTable),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LintConfigTable {
+    level: LintLevel,
+    #[serde(default)]
+    priority: i64,
+}
+
+impl ProfileLints {
+    /// Converts the lint levels to command line flags. Like Cargo does, they are ordered by
+    /// priority and then by name, so that the lints with a higher priority override the others.
+    pub(crate) fn into_flags(self) -> Vec<String> {
+        let mut lints: Vec<_> = self
+            .clippy
+            .into_iter()
+            .map(|(name, config)| (format!("clippy::{name}"), config))
+            .chain(self.rust)
+            .map(|(name, config)| match config {
+                LintConfig::Level(level) => (0, name, level),
+                LintConfig::Table(table) => (table.priority, name, table.level),
+            })
+            .collect();
+        lints.sort_by(|(a_priority, a_name, _), (b_priority, b_name, _)| {
+            a_priority.cmp(b_priority).then_with(|| a_name.cmp(b_name))
+        });
+        lints
+            .into_iter()
+            .map(|(_, name, level)| {
+                let flag = match level {
+                    LintLevel::Allow => "allow",
+                    LintLevel::Warn => "warn",
+                    LintLevel::Deny => "deny",
+                    LintLevel::Forbid => "forbid",
+                };
+                format!("--{flag}={name}")
+            })
+            .collect()
+    }
+}
+
+/// Returns the command line flags, such as `--deny=clippy::unwrap_used`, setting the lint levels of
+/// the profile named `profile` in the configuration file.
+///
+/// Errors are ignored here, they are reported when the configuration is read.
+pub fn profile_lint_flags(path: &io::Result<(Option<PathBuf>, Vec<String>)>, profile: &str) -> Vec<String> {
+    #[derive(Deserialize)]
+    struct ConfFile {
+        #[serde(default)]
+        profile: BTreeMap<String, Profile>,
+    }
+
+    #[derive(Deserialize)]
+    struct Profile {
+        lints: Option<ProfileLints>,
+    }
+
+    if let Ok((Some(path), _)) = path
+        && let Ok(src) = fs::read_to_string(path)
+        && let Ok(mut file) = toml::from_str::<ConfFile>(&src)
+        && let Some(Profile { lints: Some(lints) }) = file.profile.remove(profile)
+    {
+        lints.into_flags()
+    } else {
+        Vec::new()
+    }
+}
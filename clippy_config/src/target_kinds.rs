@@ -0,0 +1,145 @@
+//! The kind of Cargo target being checked, which selects the `[target-kind.<kind>]` sections of the
+//! configuration file.
+//!
+//! Cargo doesn't tell the compiler the kind of the target, so it's detected from the way Cargo
+//! invokes the compiler: tests are compiled with `--test`, build scripts are binaries named
+//! `build_script_*`, and examples and benchmarks are in the `examples` and `benches` directories.
+
+use crate::profiles::ProfileLints;
+use rustc_session::config::{CrateType, Input};
+use rustc_session::Session;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum TargetKind {
+    Lib,
+    /// The default of the compiler when no `--crate-type` is given.
+    #[default]
+    Bin,
+    /// Unit tests, compiled with `--test`, and integration tests.
+    Test,
+    Bench,
+    Example,
+    BuildScript,
+}
+
+impl TargetKind {
+    const ALL: [Self; 6] = [
+        Self::Lib,
+        Self::Bin,
+        Self::Test,
+        Self::Bench,
+        Self::Example,
+        Self::BuildScript,
+    ];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Lib => "lib",
+            Self::Bin => "bin",
+            Self::Test => "test",
+            Self::Bench => "bench",
+            Self::Example => "example",
+            Self::BuildScript => "build-script",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    /// The names of all the kinds, for diagnostics.
+    pub(crate) fn names() -> String {
+        Self::ALL.map(|kind| format!("`{}`", kind.name())).join(", ")
+    }
+
+    fn detect(crate_name: Option<&str>, is_bin: bool, is_test: bool, path: Option<&Path>) -> Self {
+        let in_dir = |dir: &str| path.is_some_and(|path| path.components().any(|c| c.as_os_str() == dir));
+        if is_test {
+            if in_dir("benches") { Self::Bench } else { Self::Test }
+        } else if is_bin && crate_name.is_some_and(|name| name.starts_with("build_script_")) {
+            Self::BuildScript
+        } else if in_dir("examples") {
+            Self::Example
+        } else if is_bin {
+            Self::Bin
+        } else {
+            Self::Lib
+        }
+    }
+
+    /// Detects the kind of target from the arguments of the compiler.
+    pub fn from_args(args: &[String]) -> Self {
+        let mut crate_name = None;
+        let mut crate_types = Vec::new();
+        let mut iter = args.iter().map(String::as_str);
+        while let Some(arg) = iter.next() {
+            if arg == "--crate-name" {
+                crate_name = iter.next();
+            } else if let Some(name) = arg.strip_prefix("--crate-name=") {
+                crate_name = Some(name);
+            } else if arg == "--crate-type" {
+                crate_types.extend(iter.next().into_iter().flat_map(|types| types.split(',')));
+            } else if let Some(types) = arg.strip_prefix("--crate-type=") {
+                crate_types.extend(types.split(','));
+            }
+        }
+        Self::detect(
+            crate_name,
+            crate_types.is_empty() || crate_types.contains(&"bin"),
+            args.iter().any(|arg| arg == "--test"),
+            args.iter()
+                .map(Path::new)
+                .find(|path| path.extension().is_some_and(|ext| ext == "rs")),
+        )
+    }
+
+    /// Detects the kind of target from the options of the compiler session, like
+    /// [`TargetKind::from_args`] does.
+    // `Session::crate_types` isn't known yet when the configuration is read, and would include the
+    // `#![crate_type]` attributes which Cargo doesn't use
+    #[allow(rustc::bad_opt_access)]
+    pub(crate) fn from_session(sess: &Session) -> Self {
+        let crate_types = &sess.opts.crate_types;
+        let path = match &sess.io.input {
+            Input::File(path) => Some(path.as_path()),
+            Input::Str { .. } => None,
+        };
+        Self::detect(
+            sess.opts.crate_name.as_deref(),
+            crate_types.is_empty() || crate_types.contains(&CrateType::Executable),
+            sess.opts.test,
+            path,
+        )
+    }
+}
+
+/// Returns the command line flags, such as `--allow=clippy::unwrap_used`, setting the lint levels
+/// of the `[target-kind.<kind>]` section of the configuration file.
+///
+/// Errors are ignored here, they are reported when the configuration is read.
+pub fn target_kind_lint_flags(path: &io::Result<(Option<PathBuf>, Vec<String>)>, kind: TargetKind) -> Vec<String> {
+    #[derive(Deserialize)]
+    struct ConfFile {
+        #[serde(default, rename = "target-kind")]
+        target_kind: BTreeMap<String, Section>,
+    }
+
+    #[derive(Deserialize)]
+    struct Section {
+        lints: Option<ProfileLints>,
+    }
+
+    if let Ok((Some(path), _)) = path
+        && let Ok(src) = fs::read_to_string(path)
+        && let Ok(mut file) = toml::from_str::<ConfFile>(&src)
+        && let Some(Section { lints: Some(lints) }) = file.target_kind.remove(kind.name())
+    {
+        lints.into_flags()
+    } else {
+        Vec::new()
+    }
+}
@@ -1,3 +1,4 @@
+use regex::Regex;
 use serde::de::{self, Deserializer, Visitor};
 use serde::{ser, Deserialize, Serialize};
 use std::fmt;
@@ -32,6 +33,250 @@ impl DisallowedPath {
     }
 }
 
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum DisallowedAttribute {
+    Simple(String),
+    WithReason {
+        attribute: String,
+        reason: Option<String>,
+        /// The module, starting with `crate`, outside of which the attribute is allowed.
+        scope: Option<String>,
+    },
+}
+
+impl DisallowedAttribute {
+    pub fn attribute(&self) -> &str {
+        let (Self::Simple(attribute) | Self::WithReason { attribute, .. }) = self;
+
+        attribute
+    }
+
+    pub fn reason(&self) -> Option<String> {
+        match self {
+            Self::WithReason {
+                reason: Some(reason), ..
+            } => Some(format!("{reason} (from clippy.toml)")),
+            _ => None,
+        }
+    }
+
+    pub fn scope(&self) -> Option<&str> {
+        match self {
+            Self::WithReason { scope, .. } => scope.as_deref(),
+            Self::Simple(_) => None,
+        }
+    }
+}
+
+/// The arithmetic operators which are allowed on a type.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AllowedOperators {
+    /// The type of the operand, or of the left-hand side of a binary operation.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The type of the right-hand side of a binary operation, any type if `None`.
+    pub rhs: Option<String>,
+    pub operators: Vec<ArithmeticOperator>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum ArithmeticOperator {
+    #[serde(rename = "+")]
+    Add,
+    #[serde(rename = "-")]
+    Sub,
+    #[serde(rename = "*")]
+    Mul,
+    #[serde(rename = "/")]
+    Div,
+    #[serde(rename = "%")]
+    Rem,
+    #[serde(rename = "<<")]
+    Shl,
+    #[serde(rename = ">>")]
+    Shr,
+    /// The unary `-`.
+    #[serde(rename = "neg")]
+    Neg,
+}
+
+/// A type which may not be live across the configured regions.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DisallowedTypeAcrossRegions {
+    pub path: String,
+    pub reason: Option<String>,
+    pub across: Vec<Region>,
+}
+
+impl DisallowedTypeAcrossRegions {
+    pub fn reason(&self) -> Option<String> {
+        self.reason
+            .as_ref()
+            .map(|reason| format!("{reason} (from clippy.toml)"))
+    }
+}
+
+/// A region of code a value may be held across.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Region {
+    /// An `await` point.
+    Await,
+    /// A call to a foreign function declared in an `extern` block.
+    Ffi,
+    /// A call to the function with the given path.
+    Call(String),
+}
+
+/// A category of `as` casts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CastCategory {
+    /// A cast which can't lose information, e.g. `u8` to `u32` or `f32` to `f64`.
+    Widening,
+    /// A cast between integers which can change the sign of the value, but not truncate it, e.g.
+    /// `i32` to `u32` or `i8` to `u64`.
+    SignChange,
+    /// A cast to a smaller type, e.g. `u64` to `u32` or `f64` to `f32`.
+    Truncation,
+    /// A cast between an integer and a float.
+    FloatInt,
+    /// A cast from or to a pointer, or from a function.
+    Pointer,
+}
+
+/// A constructor whose result must be passed to one of the finalizers before it is dropped.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MustCallFinalizer {
+    pub constructor: String,
+    #[serde(default)]
+    pub finalizers: Vec<String>,
+    pub reason: Option<String>,
+}
+
+impl MustCallFinalizer {
+    pub fn reason(&self) -> Option<String> {
+        self.reason
+            .as_ref()
+            .map(|reason| format!("{reason} (from clippy.toml)"))
+    }
+}
+
+/// A dependency of the module `from` on the module `to` which is not allowed. Both are paths
+/// starting with `crate`, where a trailing `::*` is ignored.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct LayeringRule {
+    pub from: String,
+    pub to: String,
+    /// Whether the dependency is allowed in tests and `#[cfg(test)]` code.
+    #[serde(default)]
+    pub allow_in_tests: bool,
+    pub reason: Option<String>,
+}
+
+impl LayeringRule {
+    pub fn reason(&self) -> Option<String> {
+        self.reason
+            .as_ref()
+            .map(|reason| format!("{reason} (from clippy.toml)"))
+    }
+}
+
+/// An environment variable which may only be read in the `allowed-in` modules. The name may
+/// contain the wildcards `*` and `?`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct DisallowedEnvVar {
+    pub name: String,
+    /// The modules, including their submodules, which may read the variable.
+    #[serde(default)]
+    pub allowed_in: Vec<String>,
+    pub reason: Option<String>,
+}
+
+impl DisallowedEnvVar {
+    pub fn reason(&self) -> Option<String> {
+        self.reason
+            .as_ref()
+            .map(|reason| format!("{reason} (from clippy.toml)"))
+    }
+}
+
+/// Thresholds which apply to the source files matching a path glob.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ThresholdOverride {
+    pub path: String,
+    pub cognitive_complexity_threshold: Option<u64>,
+    pub too_many_arguments_threshold: Option<u64>,
+    pub too_many_lines_threshold: Option<u64>,
+    pub type_complexity_threshold: Option<u64>,
+    pub max_struct_bools: Option<u64>,
+    pub max_fn_params_bools: Option<u64>,
+}
+
+/// The patterns which the names of each kind of item must match.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NamePatterns {
+    pub types: Option<NamePattern>,
+    pub traits: Option<NamePattern>,
+    pub functions: Option<NamePattern>,
+    pub constants: Option<NamePattern>,
+    pub features: Option<NamePattern>,
+    pub tests: Option<NamePattern>,
+}
+
+/// A regex which has to match a whole name.
+#[derive(Clone, Debug)]
+pub struct NamePattern(String);
+
+impl NamePattern {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Compiles the pattern, anchored at both ends of the name.
+    pub fn to_regex(&self) -> Regex {
+        Self::compile(&self.0).expect("the pattern was checked when reading the configuration")
+    }
+
+    fn compile(pattern: &str) -> Result<Regex, regex::Error> {
+        // Check the pattern on its own first so errors don't mention the added anchors.
+        Regex::new(pattern).and_then(|_| Regex::new(&format!("^(?:{pattern})$")))
+    }
+}
+
+impl<'de> Deserialize<'de> for NamePattern {
+    fn deserialize<D>(deser: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pattern = String::deserialize(deser)?;
+        if let Err(error) = Self::compile(&pattern) {
+            // Syntax errors point at the pattern over several lines, only keep the reason.
+            let error = error.to_string();
+            let reason = error.lines().last().unwrap_or_default().trim_start_matches("error: ");
+            return Err(de::Error::custom(format!("invalid regex `{pattern}`: {reason}")));
+        }
+        Ok(Self(pattern))
+    }
+}
+
+impl Serialize for NamePattern {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum MatchLintBehaviour {
     AllTypes,
@@ -104,6 +349,44 @@ impl<'de> Deserialize<'de> for MacroMatcher {
     }
 }
 
+/// The width in bits of the pointers of a target: 16, 32 or 64.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PointerWidth(u64);
+
+impl PointerWidth {
+    pub const W32: Self = Self(32);
+    pub const W64: Self = Self(64);
+
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for PointerWidth {
+    fn deserialize<D>(deser: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bits = u64::deserialize(deser)?;
+        if matches!(bits, 16 | 32 | 64) {
+            Ok(Self(bits))
+        } else {
+            Err(de::Error::custom(format!(
+                "invalid pointer width `{bits}`, expected one of 16, 32 or 64"
+            )))
+        }
+    }
+}
+
+impl Serialize for PointerWidth {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_u64(self.0)
+    }
+}
+
 // these impls are never actually called but are used by the various config options that default to
 // empty lists
 macro_rules! unimplemented_serialize {
@@ -122,9 +405,16 @@ macro_rules! unimplemented_serialize {
 }
 
 unimplemented_serialize! {
+    AllowedOperators,
+    DisallowedAttribute,
+    DisallowedEnvVar,
     DisallowedPath,
+    DisallowedTypeAcrossRegions,
+    LayeringRule,
     Rename,
     MacroMatcher,
+    MustCallFinalizer,
+    ThresholdOverride,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
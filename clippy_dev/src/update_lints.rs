@@ -106,7 +106,7 @@ fn generate_lint_files(
     process_file(
         "clippy_lints/src/declared_lints.rs",
         update_mode,
-        &gen_declared_lints(internal_lints.iter(), usable_lints.iter()),
+        &gen_declared_lints(internal_lints.iter(), usable_lints.iter(), &gather_lint_passes()),
     );
     process_file(
         "clippy_lints/src/lib.deprecated.rs",
@@ -555,6 +555,15 @@ fn exit_with_failure() {
     std::process::exit(1);
 }
 
+/// A lint pass and the lints it declares.
+struct LintPass {
+    name: String,
+    lints: Vec<String>,
+    /// Whether this is a late pass without state, i.e. it's declared with `declare_lint_pass!` in a
+    /// file without the `check_crate` methods, so that it can run on each module separately.
+    stateless: bool,
+}
+
 /// Lint data parsed from the Clippy source code.
 #[derive(Clone, PartialEq, Eq, Debug)]
 struct Lint {
@@ -656,6 +665,7 @@ fn gen_deprecated(lints: &[DeprecatedLint]) -> String {
 fn gen_declared_lints<'a>(
     internal_lints: impl Iterator<Item = &'a Lint>,
     usable_lints: impl Iterator<Item = &'a Lint>,
+    lint_passes: &[LintPass],
 ) -> String {
     let mut details: Vec<_> = internal_lints
         .map(|l| (false, &l.module, l.name.to_uppercase()))
@@ -674,6 +684,26 @@ fn gen_declared_lints<'a>(
     }
     output.push_str("];\n");
 
+    output.push_str("\n/// The lints declared by each lint pass, by the name of the pass.\n");
+    output.push_str("#[rustfmt::skip]\n");
+    output.push_str("pub(crate) static LINT_PASSES: &[(&str, &[&str])] = &[\n");
+    for pass in lint_passes.iter().sorted_by(|a, b| a.name.cmp(&b.name)) {
+        let lints = pass.lints.iter().map(|lint| format!("\"{lint}\"")).join(", ");
+        let _: fmt::Result = writeln!(output, "    (\"{}\", &[{lints}]),", pass.name);
+    }
+    output.push_str("];\n");
+
+    output.push_str("\n/// The late passes without state, which can run on each module separately.\n");
+    output.push_str("pub(crate) static STATELESS_PASSES: &[&str] = &[\n");
+    for pass in lint_passes
+        .iter()
+        .filter(|pass| pass.stateless)
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+    {
+        let _: fmt::Result = writeln!(output, "    \"{}\",", pass.name);
+    }
+    output.push_str("];\n");
+
     output
 }
 
@@ -753,6 +783,18 @@ fn gather_all() -> (Vec<Lint>, Vec<DeprecatedLint>, Vec<RenamedLint>) {
     (lints, deprecated_lints, renamed_lints)
 }
 
+/// Gathers the lint passes defined in `clippy_lints/src`
+fn gather_lint_passes() -> Vec<LintPass> {
+    let mut passes = Vec::with_capacity(400);
+    for (_, file) in clippy_lints_src_files() {
+        let path = file.path();
+        let contents =
+            fs::read_to_string(path).unwrap_or_else(|e| panic!("Cannot read from `{}`: {e}", path.display()));
+        parse_lint_passes(&contents, &mut passes);
+    }
+    passes
+}
+
 fn clippy_lints_src_files() -> impl Iterator<Item = (PathBuf, DirEntry)> {
     let root_path = clippy_project_root().join("clippy_lints/src");
     let iter = WalkDir::new(&root_path).into_iter();
@@ -847,6 +889,71 @@ fn parse_contents(contents: &str, module: &str, lints: &mut Vec<Lint>) {
     }
 }
 
+/// Parse a source file looking for `impl_lint_pass` and `declare_lint_pass` macro invocations.
+fn parse_lint_passes(contents: &str, passes: &mut Vec<LintPass>) {
+    let mut offset = 0usize;
+    let mut iter = tokenize(contents)
+        .map(|t| {
+            let content = &contents[offset..offset + t.len as usize];
+            offset += t.len as usize;
+            (t.kind, content)
+        })
+        .filter(|(kind, _)| {
+            !matches!(
+                kind,
+                TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. }
+            )
+        });
+
+    let has_crate_methods = contents.contains("fn check_crate");
+    while let Some((_, macro_name)) = iter
+        .by_ref()
+        .find(|&(kind, content)| kind == TokenKind::Ident && matches!(content, "impl_lint_pass" | "declare_lint_pass"))
+    {
+        // matches `!(Name`
+        let (Some((TokenKind::Bang, _)), Some((TokenKind::OpenParen, _)), Some((TokenKind::Ident, name))) =
+            (iter.next(), iter.next(), iter.next())
+        else {
+            continue;
+        };
+        // skips the generic arguments of the pass up to `=> [`
+        let mut after_eq = false;
+        if !iter.by_ref().any(|(kind, _)| {
+            let arrow = after_eq && kind == TokenKind::Gt;
+            after_eq = kind == TokenKind::Eq;
+            arrow
+        }) || !matches!(iter.next(), Some((TokenKind::OpenBracket, _)))
+        {
+            continue;
+        }
+        // the last segment of the path of each lint, up to the `]` which isn't part of an attribute
+        let mut depth = 0;
+        let lints = iter
+            .by_ref()
+            .take_while(|(kind, _)| {
+                match kind {
+                    TokenKind::OpenBracket => depth += 1,
+                    TokenKind::CloseBracket if depth == 0 => return false,
+                    TokenKind::CloseBracket => depth -= 1,
+                    _ => {},
+                }
+                true
+            })
+            .filter(|&(kind, content)| kind == TokenKind::Ident && !content.contains(|c: char| c.is_ascii_lowercase()))
+            .map(|(_, content)| content.to_lowercase())
+            .collect();
+        passes.push(LintPass {
+            name: name.to_string(),
+            lints,
+            stateless: macro_name == "declare_lint_pass"
+                && !has_crate_methods
+                && contents
+                    .lines()
+                    .any(|line| line.contains("LateLintPass") && line.trim_end().ends_with(&format!(" for {name} {{"))),
+        });
+    }
+}
+
 /// Parse a source file looking for `declare_deprecated_lint` macro invocations.
 fn parse_deprecated_contents(contents: &str, lints: &mut Vec<DeprecatedLint>) {
     let mut offset = 0usize;
@@ -1084,6 +1191,49 @@ mod tests {
         assert_eq!(expected, result);
     }
 
+    #[test]
+    fn test_parse_lint_passes() {
+        static CONTENTS: &str = r#"
+            impl_lint_pass!(SignificantDropTightening<'_> => [SIGNIFICANT_DROP_TIGHTENING]);
+
+            declare_lint_pass!(
+                /// Some doc comment
+                Attributes => [
+                    INLINE_ALWAYS,
+                    #[cfg(feature = "internal")]
+                    crate::utils::DEPRECATED_SEMVER,
+                ]
+            );
+
+            impl_lint_pass!(FormatArgsCollector => []);
+
+            impl LateLintPass<'_> for Attributes {
+            }
+        "#;
+        let mut result = Vec::new();
+        parse_lint_passes(CONTENTS, &mut result);
+        let stateless: Vec<_> = result
+            .iter()
+            .filter(|pass| pass.stateless)
+            .map(|pass| &*pass.name)
+            .collect();
+        assert_eq!(stateless, ["Attributes"]);
+        let result: Vec<_> = result.iter().map(|pass| (&*pass.name, pass.lints.clone())).collect();
+
+        let expected = vec![
+            (
+                "SignificantDropTightening",
+                vec!["significant_drop_tightening".to_string()],
+            ),
+            (
+                "Attributes",
+                vec!["inline_always".to_string(), "deprecated_semver".to_string()],
+            ),
+            ("FormatArgsCollector", vec![]),
+        ];
+        assert_eq!(expected, result);
+    }
+
     #[test]
     fn test_parse_deprecated_contents() {
         static DEPRECATED_CONTENTS: &str = r#"
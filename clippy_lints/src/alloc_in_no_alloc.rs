@@ -0,0 +1,382 @@
+use clippy_utils::attrs::get_attr;
+use clippy_utils::call_graph::call_graph;
+use clippy_utils::diagnostics::{span_lint_hir, span_lint_hir_and_then};
+use clippy_utils::ty::{is_type_diagnostic_item, is_type_lang_item};
+use clippy_utils::{is_lint_allowed, local_def_path_def_ids};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::{walk_expr, FnKind, Visitor};
+use rustc_hir::{Body, Expr, ExprKind, FnDecl, HirId, LangItem};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::hir::nested_filter;
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, Instance, Ty, TypeckResults};
+use rustc_session::impl_lint_pass;
+use rustc_span::{sym, Span, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for heap allocations in the functions marked with `#[clippy::no_alloc]`, in the
+    /// impls and modules marked with it, and in the modules listed in `no-alloc-modules`.
+    ///
+    /// The allocations are the calls to the functions of other crates returning a heap-allocated
+    /// container, like `Box::new`, `String::from`, `format!` or `vec!`, the calls to `collect`
+    /// into such a container, and the calls to the methods which can grow one, like `push`.
+    /// The calls to the functions of the crate which allocate, directly or through the functions
+    /// they call, are linted too, and the chain of calls leading to the allocation is shown.
+    ///
+    /// The allocations allowed with `#[allow(clippy::alloc_in_no_alloc)]` aren't linted, nor
+    /// counted as allocations of the functions containing them.
+    ///
+    /// ### Why is this bad?
+    /// Some code mustn't allocate, e.g. interrupt handlers, the real-time path of an audio
+    /// callback or code running before the allocator is set up. Allocations are easy to
+    /// introduce unknowingly, through a helper function or a `format!` in an error path.
+    ///
+    /// ### Known problems
+    /// The calls through function pointers, `dyn Trait` and closures aren't followed, and
+    /// the allocations inside the functions of other crates aren't known, unless they return a
+    /// heap-allocated container.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// #[clippy::no_alloc]
+    /// fn on_sample(buffer: &mut Vec<f32>, sample: f32) {
+    ///     buffer.push(sample);
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```no_run
+    /// #[clippy::no_alloc]
+    /// fn on_sample(buffer: &mut [f32], len: &mut usize, sample: f32) {
+    ///     if let Some(slot) = buffer.get_mut(*len) {
+    ///         *slot = sample;
+    ///         *len += 1;
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub ALLOC_IN_NO_ALLOC,
+    style,
+    "heap allocation in code marked with `#[clippy::no_alloc]`"
+}
+
+impl_lint_pass!(AllocInNoAlloc => [ALLOC_IN_NO_ALLOC]);
+
+/// The heap-allocated containers, with `Box` and `String`
+const CONTAINERS: &[Symbol] = &[
+    sym::Vec,
+    sym::VecDeque,
+    sym::BinaryHeap,
+    sym::LinkedList,
+    sym::BTreeMap,
+    sym::BTreeSet,
+    sym::HashMap,
+    sym::HashSet,
+    sym::Rc,
+    sym::Arc,
+];
+
+/// The methods which can grow a container, and so reallocate it
+const GROWING_METHODS: &[&str] = &[
+    "append",
+    "extend",
+    "extend_from_slice",
+    "extend_from_within",
+    "insert",
+    "insert_str",
+    "push",
+    "push_back",
+    "push_front",
+    "push_str",
+    "reserve",
+    "reserve_exact",
+    "resize",
+    "resize_with",
+];
+
+pub struct AllocInNoAlloc {
+    modules: Vec<String>,
+    /// The functions marked with `#[clippy::no_alloc]`, directly or through their parents, or in
+    /// one of `modules`
+    no_alloc_fns: FxHashSet<LocalDefId>,
+    /// The first allocation of each function of the crate which was looked for
+    allocations: FxHashMap<LocalDefId, Option<Allocation>>,
+}
+
+impl AllocInNoAlloc {
+    pub fn new(modules: Vec<String>) -> Self {
+        Self {
+            modules,
+            no_alloc_fns: FxHashSet::default(),
+            allocations: FxHashMap::default(),
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for AllocInNoAlloc {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        let module_ids: FxHashSet<DefId> = self
+            .modules
+            .iter()
+            .flat_map(|path| local_def_path_def_ids(cx, path))
+            .collect();
+        // Whether each item or one of its parents is marked
+        let mut marked: FxHashMap<DefId, bool> = FxHashMap::default();
+        let mut is_marked = |def_id: DefId| {
+            *marked.entry(def_id).or_insert_with(|| {
+                module_ids.contains(&def_id)
+                    || get_attr(cx.sess(), cx.tcx.get_attrs_unchecked(def_id), "no_alloc")
+                        .next()
+                        .is_some()
+            })
+        };
+        self.no_alloc_fns = cx
+            .tcx
+            .hir()
+            .body_owners()
+            .filter(|&def_id| {
+                matches!(cx.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+                    && std::iter::successors(Some(def_id.to_def_id()), |&id| cx.tcx.opt_parent(id)).any(&mut is_marked)
+            })
+            .collect();
+    }
+
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'_>,
+        _: &'tcx Body<'_>,
+        span: Span,
+        def_id: LocalDefId,
+    ) {
+        if matches!(kind, FnKind::Closure) || !self.no_alloc_fns.contains(&def_id) || in_external_macro(cx.sess(), span)
+        {
+            return;
+        }
+
+        for allocation in allocations(cx, def_id, false) {
+            span_lint_hir(
+                cx,
+                ALLOC_IN_NO_ALLOC,
+                allocation.hir_id,
+                allocation.span,
+                format!("{} in `no_alloc` code", allocation.description),
+            );
+        }
+
+        let Self {
+            no_alloc_fns,
+            allocations: first_allocations,
+            ..
+        } = self;
+        let mut first_allocation = |def_id: LocalDefId| {
+            first_allocations
+                .entry(def_id)
+                .or_insert_with(|| allocations(cx, def_id, true).into_iter().next())
+                .clone()
+        };
+        let call_graph = call_graph(cx);
+        for call in call_graph.calls(def_id) {
+            // The functions marked `no_alloc` are checked on their own
+            if no_alloc_fns.contains(&call.callee) || in_external_macro(cx.sess(), call.span) {
+                continue;
+            }
+            let path = if first_allocation(call.callee).is_some() {
+                Vec::new()
+            } else if let Some(path) = call_graph.find_path_through(
+                call.callee,
+                |callee| !no_alloc_fns.contains(&callee) && first_allocation(callee).is_some(),
+                |callee| !no_alloc_fns.contains(&callee),
+            ) {
+                path
+            } else {
+                continue;
+            };
+            let allocating_fn = path.last().map_or(call.callee, |last| last.callee);
+            let Some(allocation) = first_allocation(allocating_fn) else {
+                continue;
+            };
+            span_lint_hir_and_then(
+                cx,
+                ALLOC_IN_NO_ALLOC,
+                call.hir_id,
+                call.span,
+                format!(
+                    "call to `{}`, which can allocate, in `no_alloc` code",
+                    cx.tcx.def_path_str(call.callee)
+                ),
+                |diag| {
+                    for call in &path {
+                        diag.span_note(
+                            call.span,
+                            format!(
+                                "`{}` calls `{}` here",
+                                cx.tcx.def_path_str(call.caller),
+                                cx.tcx.def_path_str(call.callee)
+                            ),
+                        );
+                    }
+                    diag.span_note(
+                        allocation.span,
+                        format!(
+                            "{} in `{}` here",
+                            allocation.description,
+                            cx.tcx.def_path_str(allocating_fn)
+                        ),
+                    );
+                },
+            );
+        }
+    }
+}
+
+/// An expression of a function which allocates
+#[derive(Clone)]
+struct Allocation {
+    hir_id: HirId,
+    /// The span of the expression, or of the macro call it comes from
+    span: Span,
+    description: String,
+}
+
+/// Returns the allocations of the body of `def_id`, including its closures, but not the calls to
+/// the functions of the crate. The allocations where the lint is allowed are skipped.
+fn allocations(cx: &LateContext<'_>, def_id: LocalDefId, first_only: bool) -> Vec<Allocation> {
+    let typeck_results = cx.tcx.typeck(def_id);
+    if typeck_results.tainted_by_errors.is_some() {
+        return Vec::new();
+    }
+    let mut visitor = AllocationVisitor {
+        cx,
+        typeck_results,
+        param_env: cx.tcx.param_env(def_id),
+        first_only,
+        spans: FxHashSet::default(),
+        allocations: Vec::new(),
+    };
+    visitor.visit_expr(cx.tcx.hir().body(cx.tcx.hir().body_owned_by(def_id)).value);
+    visitor.allocations
+}
+
+struct AllocationVisitor<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    first_only: bool,
+    /// The spans already reported, as a macro like `vec!` can expand to several allocations
+    spans: FxHashSet<Span>,
+    allocations: Vec<Allocation>,
+}
+
+impl<'a, 'tcx> AllocationVisitor<'a, 'tcx> {
+    /// Describes the allocation made by the expression, if any
+    fn allocation(&self, expr: &Expr<'tcx>) -> Option<String> {
+        let (def_id, args) = match expr.kind {
+            ExprKind::Call(callee, _) => match callee.kind {
+                ExprKind::Path(ref qpath) => match self.typeck_results.qpath_res(qpath, callee.hir_id) {
+                    Res::Def(DefKind::Fn | DefKind::AssocFn, def_id) => {
+                        (def_id, self.typeck_results.node_args(callee.hir_id))
+                    },
+                    _ => return None,
+                },
+                _ => return None,
+            },
+            ExprKind::MethodCall(..) => (
+                self.typeck_results.type_dependent_def_id(expr.hir_id)?,
+                self.typeck_results.node_args(expr.hir_id),
+            ),
+            _ => return None,
+        };
+        // The calls to the functions of the crate are followed through the call graph, and
+        // `const fn`s can't allocate
+        let callee = match Instance::resolve(self.cx.tcx, self.param_env, def_id, args) {
+            Ok(Some(instance)) if matches!(instance.def, ty::InstanceDef::Item(_)) => instance.def_id(),
+            _ => def_id,
+        };
+        if def_id.is_local() || callee.is_local() || self.cx.tcx.is_const_fn_raw(callee) {
+            return None;
+        }
+
+        if self.cx.tcx.is_diagnostic_item(sym::iterator_collect_fn, def_id) {
+            let container = container_in(self.cx, self.typeck_results.expr_ty(expr))?;
+            return Some(format!("collecting into a `{container}`"));
+        }
+        if let ExprKind::MethodCall(method, receiver, ..) = expr.kind
+            && GROWING_METHODS.contains(&method.ident.as_str())
+            && let Some(container) = container(self.cx, self.typeck_results.expr_ty_adjusted(receiver).peel_refs())
+            && !matches!(container, "Rc" | "Arc")
+        {
+            return Some(format!("`{}` can reallocate the `{container}`", method.ident));
+        }
+        // The declared return type, so that e.g. `Option::unwrap` on an `Option<Vec<_>>` isn't
+        // taken for an allocation
+        let output = self.cx.tcx.fn_sig(callee).instantiate_identity().skip_binder().output();
+        let container = container_in(self.cx, output)?;
+        // Cloning an `Rc` only increments its counter
+        if matches!(container, "Rc" | "Arc")
+            && self.cx.tcx.trait_of_item(def_id) == self.cx.tcx.lang_items().clone_trait()
+        {
+            return None;
+        }
+        Some(format!("allocation of a `{container}`"))
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for AllocationVisitor<'a, 'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.cx.tcx.hir()
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        if self.first_only && !self.allocations.is_empty() {
+            return;
+        }
+        if let Some(description) = self.allocation(expr)
+            && !is_lint_allowed(self.cx, ALLOC_IN_NO_ALLOC, expr.hir_id)
+        {
+            let span = expr.span.source_callsite();
+            if self.spans.insert(span) {
+                self.allocations.push(Allocation {
+                    hir_id: expr.hir_id,
+                    span,
+                    description,
+                });
+            }
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Returns the name of the heap-allocated container `ty` is, if any
+fn container(cx: &LateContext<'_>, ty: Ty<'_>) -> Option<&'static str> {
+    if ty.is_box() {
+        return Some("Box");
+    }
+    if is_type_lang_item(cx, ty, LangItem::String) {
+        return Some("String");
+    }
+    CONTAINERS
+        .iter()
+        .find(|&&container| is_type_diagnostic_item(cx, ty, container))
+        .map(Symbol::as_str)
+}
+
+/// Returns the name of a heap-allocated container owned by a value of type `ty`, if any. The
+/// targets of references and pointers aren't owned.
+fn container_in(cx: &LateContext<'_>, ty: Ty<'_>) -> Option<&'static str> {
+    if let Some(container) = container(cx, ty) {
+        return Some(container);
+    }
+    match ty.kind() {
+        ty::Adt(_, args) => args.types().find_map(|ty| container_in(cx, ty)),
+        ty::Tuple(tys) => tys.iter().find_map(|ty| container_in(cx, ty)),
+        ty::Array(ty, _) | ty::Slice(ty) => container_in(cx, *ty),
+        _ => None,
+    }
+}
@@ -0,0 +1,121 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::{fn_def_id, get_parent_expr, local_def_path_def_ids, path_def_id};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::impl_lint_pass;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for uses of a random number generator which isn't passed to the code, by
+    /// `rand::thread_rng`, `rand::rng`, `rand::random`, `getrandom::getrandom`,
+    /// `getrandom::fill` or the functions configured in `randomness-functions`, outside of the
+    /// modules configured in `randomness-modules`.
+    ///
+    /// ### Why is this bad?
+    /// Code drawing from an ambient source of randomness gives different results on each run, so
+    /// its tests and simulations can't be reproduced. Taking the generator as a parameter lets
+    /// them use a seeded one.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// fn shuffle_deck(deck: &mut [Card]) {
+    ///     deck.shuffle(&mut rand::thread_rng());
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// fn shuffle_deck(deck: &mut [Card], rng: &mut impl rand::Rng) {
+    ///     deck.shuffle(rng);
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub AMBIENT_RANDOMNESS,
+    restriction,
+    "use of an ambient source of randomness instead of a generator passed to the code"
+}
+
+impl_lint_pass!(AmbientRandomness => [AMBIENT_RANDOMNESS]);
+
+pub struct AmbientRandomness {
+    functions: Vec<String>,
+    modules: Vec<String>,
+    /// The `DefId`s of `functions`, with their index.
+    function_ids: FxHashMap<DefId, usize>,
+    /// The `DefId`s of `modules`.
+    module_ids: Vec<DefId>,
+}
+
+impl AmbientRandomness {
+    pub fn new(functions: Vec<String>, modules: Vec<String>) -> Self {
+        Self {
+            functions,
+            modules,
+            function_ids: FxHashMap::default(),
+            module_ids: Vec::new(),
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for AmbientRandomness {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for (index, function) in self.functions.iter().enumerate() {
+            for id in local_def_path_def_ids(cx, function) {
+                self.function_ids.insert(id, index);
+            }
+        }
+        self.module_ids = self
+            .modules
+            .iter()
+            .flat_map(|module| local_def_path_def_ids(cx, module))
+            .collect();
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        // The path of a call is checked with the call.
+        let uncalled_path = if let Some(parent) = get_parent_expr(cx, expr)
+            && let ExprKind::Call(callee, _) = parent.kind
+            && callee.hir_id == expr.hir_id
+        {
+            None
+        } else {
+            path_def_id(cx, expr)
+        };
+        let Some(&index) = uncalled_path
+            .or_else(|| fn_def_id(cx, expr))
+            .and_then(|def_id| self.function_ids.get(&def_id))
+        else {
+            return;
+        };
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+        let module = cx.tcx.parent_module(expr.hir_id).to_def_id();
+        if self
+            .module_ids
+            .iter()
+            .any(|&allowed| cx.tcx.is_descendant_of(module, allowed))
+        {
+            return;
+        }
+        let help = if self.modules.is_empty() {
+            String::from("take an `impl Rng` parameter instead, so that callers can pass a seeded generator")
+        } else {
+            format!(
+                "take an `impl Rng` parameter instead, or create the generator in `{}`",
+                self.modules.join("`, `")
+            )
+        };
+        span_lint_and_help(
+            cx,
+            AMBIENT_RANDOMNESS,
+            expr.span,
+            format!("use of the ambient source of randomness `{}`", self.functions[index]),
+            None,
+            help,
+        );
+    }
+}
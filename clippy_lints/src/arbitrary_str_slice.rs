@@ -0,0 +1,272 @@
+use std::ops::ControlFlow;
+
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::higher::{ForLoop, Range};
+use clippy_utils::macros::{find_assert_args, is_assert_macro, root_macro_call_first_node};
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::ty::is_type_lang_item;
+use clippy_utils::visitors::{for_each_expr, Descend};
+use clippy_utils::{eq_expr_value, get_parent_expr, is_never_expr, path_to_local};
+use rustc_ast::RangeLimits;
+use rustc_errors::Applicability;
+use rustc_hir::{
+    BinOpKind, BorrowKind, Expr, ExprKind, HirId, LangItem, MatchSource, Mutability, Node, Stmt, StmtKind, UnOp,
+};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::declare_lint_pass;
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for slices of strings whose bounds aren't known to be on a char boundary.
+    ///
+    /// Unlike `string_slice`, this lint accepts the bounds which are:
+    /// * `0` or the length of the string,
+    /// * constants within the bounds of a string literal which are on a char boundary,
+    /// * indices returned by `find`, `rfind`, `match_indices` or `char_indices` on the same
+    ///   string, possibly followed by the length of the pattern which was found,
+    ///
+    /// and the slices of strings which are checked with `is_ascii()` by an enclosing `if`, an
+    /// `assert!`, or an early return.
+    ///
+    /// ### Why is this bad?
+    /// Slicing a string panics if a bound is within a multi-byte UTF-8 character, which is easy
+    /// to miss when testing with ASCII strings only.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn initials(name: &str) -> &str {
+    ///     &name[..2]
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// fn initials(name: &str) -> Option<&str> {
+    ///     name.get(..2)
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub ARBITRARY_STR_SLICE,
+    restriction,
+    "slicing a string with bounds which may not be on a char boundary"
+}
+
+declare_lint_pass!(ArbitraryStrSlice => [ARBITRARY_STR_SLICE]);
+
+impl<'tcx> LateLintPass<'tcx> for ArbitraryStrSlice {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::Index(string, index, _) = expr.kind
+            && !in_external_macro(cx.sess(), expr.span)
+            && let ty = cx.typeck_results().expr_ty(string).peel_refs()
+            && (ty.is_str() || is_type_lang_item(cx, ty, LangItem::String))
+            && let Some(range) = Range::hir(index)
+            && !(range.start.map_or(true, |start| is_boundary(cx, string, start, 0))
+                && range.end.map_or(true, |end| {
+                    is_boundary(cx, string, end, u128::from(range.limits == RangeLimits::Closed))
+                }))
+            && !is_ascii_checked(cx, expr, string)
+        {
+            span_lint_and_then(
+                cx,
+                ARBITRARY_STR_SLICE,
+                expr.span,
+                "slicing a string with bounds which may not be on a char boundary",
+                |diag| {
+                    let mut app = Applicability::MaybeIncorrect;
+                    let string = snippet_with_applicability(cx, string.span, "..", &mut app);
+                    let index = snippet_with_applicability(cx, index.span, "..", &mut app);
+                    // `&s[..]` becomes `s.get(..)`
+                    let span = match get_parent_expr(cx, expr) {
+                        Some(parent) if let ExprKind::AddrOf(BorrowKind::Ref, Mutability::Not, _) = parent.kind => {
+                            parent.span
+                        },
+                        _ => expr.span,
+                    };
+                    diag.span_suggestion(
+                        span,
+                        "use `get` to handle the bounds which aren't on a char boundary",
+                        format!("{string}.get({index})"),
+                        app,
+                    );
+                    diag.help(
+                        "or compute the bounds with `char_indices`, or slice the bytes returned by `as_bytes` instead",
+                    );
+                },
+            );
+        }
+    }
+}
+
+/// Checks if `bound + offset` is known to be on a char boundary of `string`
+fn is_boundary<'tcx>(cx: &LateContext<'tcx>, string: &'tcx Expr<'tcx>, bound: &'tcx Expr<'tcx>, offset: u128) -> bool {
+    if let Some(Constant::Int(value)) = constant(cx, cx.typeck_results(), bound) {
+        let value = value.saturating_add(offset);
+        return value == 0
+            || matches!(
+                (constant(cx, cx.typeck_results(), string), usize::try_from(value)),
+                (Some(Constant::Str(literal)), Ok(value)) if literal.is_char_boundary(value)
+            );
+    }
+    if offset != 0 {
+        return false;
+    }
+    match bound.kind {
+        ExprKind::MethodCall(path, recv, [], _) if path.ident.name == sym::len => eq_expr_value(cx, recv, string),
+        ExprKind::Binary(op, index, len) if op.node == BinOpKind::Add => {
+            matches!(found_in(cx, string, index), Some([pat]) if is_len_of(cx, len, pat))
+        },
+        _ => found_in(cx, string, bound).is_some(),
+    }
+}
+
+/// If `index` is a local bound to the result of `find`, `rfind`, `match_indices`, `rmatch_indices`
+/// or `char_indices` on `string`, returns the arguments of the call
+fn found_in<'tcx>(
+    cx: &LateContext<'tcx>,
+    string: &'tcx Expr<'tcx>,
+    index: &'tcx Expr<'tcx>,
+) -> Option<&'tcx [Expr<'tcx>]> {
+    let mut source = binding_source(cx, path_to_local(index)?)?;
+    loop {
+        source = match source.kind {
+            ExprKind::MethodCall(path, recv, _, _)
+                if matches!(path.ident.name.as_str(), "unwrap" | "expect" | "unwrap_unchecked") =>
+            {
+                recv
+            },
+            ExprKind::Match(scrutinee, _, MatchSource::TryDesugar(_))
+                if let ExprKind::Call(_, [inner]) = scrutinee.kind =>
+            {
+                inner
+            },
+            ExprKind::MethodCall(path, recv, args, _)
+                if matches!(
+                    path.ident.name.as_str(),
+                    "find" | "rfind" | "match_indices" | "rmatch_indices" | "char_indices"
+                ) && eq_expr_value(cx, recv, string) =>
+            {
+                return Some(args);
+            },
+            _ => return None,
+        };
+    }
+}
+
+/// Returns the expression matched by the pattern which binds `local`, i.e. the initializer of a
+/// `let`, the scrutinee of a `match`, or the iterator of a `for` loop
+fn binding_source<'tcx>(cx: &LateContext<'tcx>, local: HirId) -> Option<&'tcx Expr<'tcx>> {
+    for (_, node) in cx.tcx.hir().parent_iter(local) {
+        match node {
+            Node::Pat(_) | Node::PatField(_) | Node::Arm(_) => {},
+            Node::LetStmt(local) => return local.init,
+            Node::Expr(expr) => {
+                return match expr.kind {
+                    ExprKind::Let(let_expr) => Some(let_expr.init),
+                    ExprKind::Match(scrutinee, _, MatchSource::Normal) => Some(scrutinee),
+                    ExprKind::Match(_, _, MatchSource::ForLoopDesugar) => cx
+                        .tcx
+                        .hir()
+                        .parent_iter(expr.hir_id)
+                        .find_map(|(_, node)| match node {
+                            Node::Expr(expr) => ForLoop::hir(expr),
+                            _ => None,
+                        })
+                        .map(|for_loop| for_loop.arg),
+                    _ => None,
+                };
+            },
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Checks if `len` is the length of the pattern `pat`
+fn is_len_of<'tcx>(cx: &LateContext<'tcx>, len: &'tcx Expr<'tcx>, pat: &'tcx Expr<'tcx>) -> bool {
+    if let ExprKind::MethodCall(path, recv, [], _) = len.kind
+        && matches!(path.ident.name.as_str(), "len" | "len_utf8")
+        && eq_expr_value(cx, recv, pat)
+    {
+        return true;
+    }
+    let pat_len = match constant(cx, cx.typeck_results(), pat) {
+        Some(Constant::Str(pat)) => pat.len(),
+        Some(Constant::Char(pat)) => pat.len_utf8(),
+        _ => return false,
+    };
+    matches!(constant(cx, cx.typeck_results(), len), Some(Constant::Int(len)) if len == pat_len as u128)
+}
+
+/// Checks if `string` is known to be ASCII where `expr` is evaluated
+fn is_ascii_checked<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>, string: &'tcx Expr<'tcx>) -> bool {
+    let mut child = expr.hir_id;
+    for (parent_id, node) in cx.tcx.hir().parent_iter(expr.hir_id) {
+        match node {
+            Node::Expr(parent) => match parent.kind {
+                ExprKind::If(cond, then, _) if then.hir_id == child && holds(cx, cond, string) => return true,
+                ExprKind::Binary(op, left, right)
+                    if op.node == BinOpKind::And && right.hir_id == child && holds(cx, left, string) =>
+                {
+                    return true;
+                },
+                _ => {},
+            },
+            Node::Block(block) => {
+                if block
+                    .stmts
+                    .iter()
+                    .take_while(|stmt| stmt.hir_id != child)
+                    .any(|stmt| checks_ascii(cx, stmt, string))
+                {
+                    return true;
+                }
+            },
+            Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_) => break,
+            _ => {},
+        }
+        child = parent_id;
+    }
+    false
+}
+
+/// Checks if the statement is an `assert!(string.is_ascii())`, or an early return if
+/// `!string.is_ascii()`
+fn checks_ascii<'tcx>(cx: &LateContext<'tcx>, stmt: &'tcx Stmt<'tcx>, string: &'tcx Expr<'tcx>) -> bool {
+    let (StmtKind::Expr(expr) | StmtKind::Semi(expr)) = stmt.kind else {
+        return false;
+    };
+    if let Some(macro_call) = root_macro_call_first_node(cx, expr) {
+        is_assert_macro(cx, macro_call.def_id)
+            && find_assert_args(cx, expr, macro_call.expn).is_some_and(|(cond, _)| holds(cx, cond, string))
+    } else if let ExprKind::If(cond, then, None) = expr.kind
+        && let ExprKind::Unary(UnOp::Not, check) = cond.peel_drop_temps().kind
+    {
+        is_ascii_call(cx, check, string) && is_never_expr(cx, then).is_some()
+    } else {
+        false
+    }
+}
+
+/// Checks if `string.is_ascii()` holds when `cond` is `true`
+fn holds<'tcx>(cx: &LateContext<'tcx>, cond: &'tcx Expr<'tcx>, string: &'tcx Expr<'tcx>) -> bool {
+    for_each_expr(cond, |e| {
+        if is_ascii_call(cx, e, string) {
+            ControlFlow::Break(())
+        } else {
+            let descend = match e.kind {
+                ExprKind::Binary(op, ..) => op.node == BinOpKind::And,
+                ExprKind::DropTemps(_) => true,
+                _ => false,
+            };
+            ControlFlow::Continue(Descend::from(descend))
+        }
+    })
+    .is_some()
+}
+
+fn is_ascii_call<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>, string: &'tcx Expr<'tcx>) -> bool {
+    matches!(expr.kind, ExprKind::MethodCall(path, recv, [], _)
+        if path.ident.name.as_str() == "is_ascii" && eq_expr_value(cx, recv, string))
+}
@@ -1,13 +1,13 @@
 use clippy_config::types::DisallowedPath;
 use clippy_utils::diagnostics::span_lint_and_then;
-use clippy_utils::{match_def_path, paths};
+use clippy_utils::ty::{own_significant_drop_kind, SignificantDropKind};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir as hir;
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, DefIdSet};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::mir::CoroutineLayout;
 use rustc_session::impl_lint_pass;
-use rustc_span::{sym, Span};
+use rustc_span::Span;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -223,7 +223,8 @@ impl AwaitHolding {
                         })
                         .collect::<Vec<_>>()
                 };
-                if is_mutex_guard(cx, adt.did()) {
+                let kind = own_significant_drop_kind(cx, adt.did(), &DefIdSet::default());
+                if kind == Some(SignificantDropKind::Lock) {
                     span_lint_and_then(
                         cx,
                         AWAIT_HOLDING_LOCK,
@@ -240,7 +241,7 @@ impl AwaitHolding {
                             );
                         },
                     );
-                } else if is_refcell_ref(cx, adt.did()) {
+                } else if kind == Some(SignificantDropKind::RefCellBorrow) {
                     span_lint_and_then(
                         cx,
                         AWAIT_HOLDING_REFCELL_REF,
@@ -278,19 +279,3 @@ fn emit_invalid_type(cx: &LateContext<'_>, span: Span, disallowed: &DisallowedPa
         },
     );
 }
-
-fn is_mutex_guard(cx: &LateContext<'_>, def_id: DefId) -> bool {
-    cx.tcx.is_diagnostic_item(sym::MutexGuard, def_id)
-        || cx.tcx.is_diagnostic_item(sym::RwLockReadGuard, def_id)
-        || cx.tcx.is_diagnostic_item(sym::RwLockWriteGuard, def_id)
-        || match_def_path(cx, def_id, &paths::PARKING_LOT_MUTEX_GUARD)
-        || match_def_path(cx, def_id, &paths::PARKING_LOT_RWLOCK_READ_GUARD)
-        || match_def_path(cx, def_id, &paths::PARKING_LOT_RWLOCK_WRITE_GUARD)
-}
-
-fn is_refcell_ref(cx: &LateContext<'_>, def_id: DefId) -> bool {
-    matches!(
-        cx.tcx.get_diagnostic_name(def_id),
-        Some(sym::RefCellRef | sym::RefCellRefMut)
-    )
-}
@@ -0,0 +1,30 @@
+use clippy_utils::cargo::Manifest;
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_lint::LateContext;
+
+use super::GIT_DEPENDENCIES;
+
+pub(super) fn check(cx: &LateContext<'_>, manifest: &Manifest, allowed_git_sources: &[String]) {
+    for dependency in &manifest.toml.dependencies {
+        if let Some(git) = &dependency.git
+            && !allowed_git_sources.iter().any(|source| is_from_source(git, source))
+        {
+            span_lint_and_help(
+                cx,
+                GIT_DEPENDENCIES,
+                manifest.span(dependency.key.clone()),
+                format!("git dependency for `{}` from `{git}`", dependency.package),
+                None,
+                "depend on a published version, or add the repository to `allowed-git-sources` in `clippy.toml`",
+            );
+        }
+    }
+}
+
+/// Checks if `git` is the repository `source`, or a repository under it, comparing whole path
+/// segments: `https://github.com/org` allows `https://github.com/org/repo` but not
+/// `https://github.com/org-fork/repo`.
+fn is_from_source(git: &str, source: &str) -> bool {
+    git.strip_prefix(source.trim_end_matches('/'))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
@@ -1,10 +1,12 @@
 mod common_metadata;
 mod feature_name;
+mod git_dependencies;
 mod lint_groups_priority;
 mod multiple_crate_versions;
+mod unapproved_licenses;
 mod wildcard_dependencies;
 
-use cargo_metadata::MetadataCommand;
+use clippy_utils::cargo::{manifest, metadata};
 use clippy_utils::diagnostics::span_lint;
 use clippy_utils::is_lint_allowed;
 use rustc_data_structures::fx::FxHashSet;
@@ -203,9 +205,66 @@ declare_clippy_lint! {
     "a lint group in `Cargo.toml` at the same priority as a lint"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for dependencies in the `Cargo.toml` which are fetched from a git repository
+    /// which isn't listed in the `allowed-git-sources` configuration.
+    ///
+    /// ### Why is this bad?
+    /// A git dependency can't be published to crates.io, and the code it resolves to isn't
+    /// reviewed as a release. Projects often only allow forks from their own organization.
+    ///
+    /// ### Example
+    /// ```toml
+    /// [dependencies]
+    /// regex = { git = "https://github.com/someone/regex" }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```toml
+    /// [dependencies]
+    /// regex = "1.10"
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub GIT_DEPENDENCIES,
+    cargo,
+    "dependencies fetched from a git repository which isn't allowed"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for dependencies, including the indirect ones, whose license isn't allowed by
+    /// the `allowed-licenses` configuration. The license of a dependency is allowed if its
+    /// SPDX expression, like `MIT OR Apache-2.0`, can be satisfied with the allowed licenses.
+    ///
+    /// Nothing is linted if `allowed-licenses` isn't set.
+    ///
+    /// ### Why is this bad?
+    /// Using code under a license which the project can't comply with is a legal risk.
+    ///
+    /// ### Example
+    /// ```toml
+    /// # clippy.toml
+    /// allowed-licenses = ["MIT", "Apache-2.0"]
+    /// ```
+    ///
+    /// ```toml
+    /// # Cargo.toml
+    /// [dependencies]
+    /// # licensed under `GPL-3.0`
+    /// some-gpl-crate = "1.0"
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub UNAPPROVED_LICENSES,
+    cargo,
+    "dependencies whose license isn't allowed"
+}
+
 pub struct Cargo {
     pub allowed_duplicate_crates: FxHashSet<String>,
     pub ignore_publish: bool,
+    pub allowed_git_sources: Vec<String>,
+    pub allowed_licenses: Vec<String>,
 }
 
 impl_lint_pass!(Cargo => [
@@ -215,6 +274,8 @@ impl_lint_pass!(Cargo => [
     MULTIPLE_CRATE_VERSIONS,
     WILDCARD_DEPENDENCIES,
     LINT_GROUPS_PRIORITY,
+    GIT_DEPENDENCIES,
+    UNAPPROVED_LICENSES,
 ]);
 
 impl LateLintPass<'_> for Cargo {
@@ -225,15 +286,26 @@ impl LateLintPass<'_> for Cargo {
             NEGATIVE_FEATURE_NAMES,
             WILDCARD_DEPENDENCIES,
         ];
-        static WITH_DEPS_LINTS: &[&Lint] = &[MULTIPLE_CRATE_VERSIONS];
+        static WITH_DEPS_LINTS: &[&Lint] = &[MULTIPLE_CRATE_VERSIONS, UNAPPROVED_LICENSES];
 
         lint_groups_priority::check(cx);
 
+        let check_licenses =
+            !self.allowed_licenses.is_empty() && !is_lint_allowed(cx, UNAPPROVED_LICENSES, CRATE_HIR_ID);
+        let manifest = if check_licenses || !is_lint_allowed(cx, GIT_DEPENDENCIES, CRATE_HIR_ID) {
+            manifest(cx)
+        } else {
+            None
+        };
+        if let Some(manifest) = &manifest {
+            git_dependencies::check(cx, manifest, &self.allowed_git_sources);
+        }
+
         if !NO_DEPS_LINTS
             .iter()
             .all(|&lint| is_lint_allowed(cx, lint, CRATE_HIR_ID))
         {
-            match MetadataCommand::new().no_deps().exec() {
+            match metadata(cx, false) {
                 Ok(metadata) => {
                     common_metadata::check(cx, &metadata, self.ignore_publish);
                     feature_name::check(cx, &metadata);
@@ -251,9 +323,12 @@ impl LateLintPass<'_> for Cargo {
             .iter()
             .all(|&lint| is_lint_allowed(cx, lint, CRATE_HIR_ID))
         {
-            match MetadataCommand::new().exec() {
+            match metadata(cx, true) {
                 Ok(metadata) => {
                     multiple_crate_versions::check(cx, &metadata, &self.allowed_duplicate_crates);
+                    if check_licenses && let Some(manifest) = &manifest {
+                        unapproved_licenses::check(cx, &metadata, manifest, &self.allowed_licenses);
+                    }
                 },
                 Err(e) => {
                     for lint in WITH_DEPS_LINTS {
@@ -0,0 +1,135 @@
+use cargo_metadata::{Metadata, Package, PackageId};
+use clippy_utils::cargo::Manifest;
+use clippy_utils::diagnostics::span_lint_and_then;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_lint::LateContext;
+use std::collections::VecDeque;
+
+use super::UNAPPROVED_LICENSES;
+
+pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata, manifest: &Manifest, allowed_licenses: &[String]) {
+    let local_name = cx.tcx.crate_name(LOCAL_CRATE);
+    let packages: FxHashMap<&PackageId, &Package> = metadata.packages.iter().map(|p| (&p.id, p)).collect();
+    let Some(resolve) = &metadata.resolve else {
+        return;
+    };
+    let nodes: FxHashMap<_, _> = resolve.nodes.iter().map(|node| (&node.id, node)).collect();
+    let Some(local) = metadata
+        .packages
+        .iter()
+        .find(|p| p.name.replace('-', "_") == local_name.as_str())
+        .and_then(|p| nodes.get(&p.id))
+    else {
+        return;
+    };
+
+    // Visit the dependencies breadth first, remembering which direct dependency each one comes from.
+    let mut seen = FxHashSet::from_iter([&local.id]);
+    let mut queue: VecDeque<_> = local.dependencies.iter().map(|id| (id, id)).collect();
+    while let Some((id, direct)) = queue.pop_front() {
+        if !seen.insert(id) {
+            continue;
+        }
+        let (Some(package), Some(direct_package)) = (packages.get(id), packages.get(direct)) else {
+            continue;
+        };
+        if let Some(node) = nodes.get(id) {
+            queue.extend(node.dependencies.iter().map(|dep| (dep, direct)));
+        }
+
+        let msg = match &package.license {
+            Some(license) if is_allowed(license, allowed_licenses) => continue,
+            Some(license) => format!(
+                "the license `{license}` of `{} v{}` is not allowed",
+                package.name, package.version
+            ),
+            None => format!("`{} v{}` does not declare a license", package.name, package.version),
+        };
+        let Some(dependency) = manifest
+            .toml
+            .dependencies
+            .iter()
+            .find(|dependency| dependency.package == direct_package.name)
+        else {
+            continue;
+        };
+        span_lint_and_then(
+            cx,
+            UNAPPROVED_LICENSES,
+            manifest.span(dependency.key.clone()),
+            msg,
+            |diag| {
+                if id != direct {
+                    diag.note(format!(
+                        "`{}` is a dependency of `{}`",
+                        package.name, direct_package.name
+                    ));
+                }
+                diag.help("the allowed licenses are set by `allowed-licenses` in `clippy.toml`");
+            },
+        );
+    }
+}
+
+/// Checks whether the SPDX license expression can be satisfied with the allowed licenses.
+fn is_allowed(expression: &str, allowed_licenses: &[String]) -> bool {
+    let expression = expression.replace('(', " ( ").replace(')', " ) ").replace('/', " OR ");
+    let mut tokens = expression.split_whitespace().peekable();
+    let allowed = parse_or(&mut tokens, allowed_licenses);
+    allowed == Some(true) && tokens.next().is_none()
+}
+
+type Tokens<'a> = std::iter::Peekable<std::str::SplitWhitespace<'a>>;
+
+fn parse_or(tokens: &mut Tokens<'_>, allowed_licenses: &[String]) -> Option<bool> {
+    let mut allowed = parse_and(tokens, allowed_licenses)?;
+    while tokens.next_if_eq(&"OR").is_some() {
+        allowed |= parse_and(tokens, allowed_licenses)?;
+    }
+    Some(allowed)
+}
+
+fn parse_and(tokens: &mut Tokens<'_>, allowed_licenses: &[String]) -> Option<bool> {
+    let mut allowed = parse_license(tokens, allowed_licenses)?;
+    while tokens.next_if_eq(&"AND").is_some() {
+        allowed &= parse_license(tokens, allowed_licenses)?;
+    }
+    Some(allowed)
+}
+
+fn parse_license(tokens: &mut Tokens<'_>, allowed_licenses: &[String]) -> Option<bool> {
+    match tokens.next()? {
+        "(" => {
+            let allowed = parse_or(tokens, allowed_licenses)?;
+            tokens.next_if_eq(&")").map(|_| allowed)
+        },
+        ")" | "AND" | "OR" | "WITH" => None,
+        license => {
+            // The exception doesn't change whether the license itself is allowed.
+            if tokens.next_if_eq(&"WITH").is_some() {
+                tokens.next()?;
+            }
+            Some(allowed_licenses.iter().any(|allowed| allowed == license))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_allowed;
+
+    #[test]
+    fn license_expressions() {
+        let allowed = ["MIT".to_string(), "Apache-2.0".to_string()];
+        assert!(is_allowed("MIT", &allowed));
+        assert!(is_allowed("MIT OR GPL-3.0", &allowed));
+        assert!(is_allowed("MIT/Apache-2.0", &allowed));
+        assert!(is_allowed("(MIT OR GPL-3.0) AND Apache-2.0", &allowed));
+        assert!(is_allowed("Apache-2.0 WITH LLVM-exception", &allowed));
+        assert!(!is_allowed("GPL-3.0", &allowed));
+        assert!(!is_allowed("MIT AND GPL-3.0", &allowed));
+        assert!(!is_allowed("MIT OR", &allowed));
+        assert!(!is_allowed("(MIT", &allowed));
+    }
+}
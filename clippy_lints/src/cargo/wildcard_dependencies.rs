@@ -1,4 +1,5 @@
 use cargo_metadata::Metadata;
+use clippy_utils::cargo::current_package;
 use clippy_utils::diagnostics::span_lint;
 use rustc_lint::LateContext;
 use rustc_span::DUMMY_SP;
@@ -6,7 +7,10 @@ use rustc_span::DUMMY_SP;
 use super::WILDCARD_DEPENDENCIES;
 
 pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
-    for dep in &metadata.packages[0].dependencies {
+    let Some(package) = current_package(metadata) else {
+        return;
+    };
+    for dep in &package.dependencies {
         // VersionReq::any() does not work
         if let Ok(wildcard_ver) = semver::VersionReq::parse("*")
             && let Some(ref source) = dep.source
@@ -0,0 +1,150 @@
+use clippy_config::types::{CastCategory, PointerWidth};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::in_constant;
+use clippy_utils::source::snippet;
+use clippy_utils::sugg::Sugg;
+use clippy_utils::ty::implements_trait;
+use rustc_errors::{Applicability, SuggestionStyle};
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, Ty};
+use rustc_span::sym;
+
+use super::pointer_width_dependent_cast::{is_lossless, nbits};
+use super::CAST_POLICY;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &Expr<'tcx>,
+    cast_from: Ty<'tcx>,
+    cast_to: Ty<'tcx>,
+    target_pointer_widths: &[PointerWidth],
+    allowed_cast_categories: &[CastCategory],
+) {
+    let ExprKind::Cast(cast_expr, cast_to_hir) = expr.kind else {
+        return;
+    };
+    let Some(category) = category(cx, cast_from, cast_to, target_pointer_widths) else {
+        return;
+    };
+    if allowed_cast_categories.contains(&category) {
+        return;
+    }
+
+    let (description, name) = match category {
+        CastCategory::Widening => ("widening cast", "widening"),
+        CastCategory::SignChange => ("sign-changing cast", "sign-change"),
+        CastCategory::Truncation => ("truncating cast", "truncation"),
+        CastCategory::FloatInt => ("cast between a float and an integer", "float-int"),
+        CastCategory::Pointer => ("pointer cast", "pointer"),
+    };
+    span_lint_and_then(
+        cx,
+        CAST_POLICY,
+        expr.span,
+        format!("{description} from `{cast_from}` to `{cast_to}`"),
+        |diag| {
+            diag.note(format!("`{name}` isn't one of the `allowed-cast-categories`"));
+
+            let implements = |trait_name| {
+                !in_constant(cx, expr.hir_id)
+                    && cx
+                        .tcx
+                        .get_diagnostic_item(trait_name)
+                        .is_some_and(|trait_id| implements_trait(cx, cast_to, trait_id, &[cast_from.into()]))
+            };
+            let cast_to_snip = snippet(cx, cast_to_hir.span, "..");
+            let operand = Sugg::hir(cx, cast_expr, "..");
+            if implements(sym::From) {
+                let (sugg, app) = if cast_to_snip == "_" {
+                    (format!("{}.into()", operand.maybe_par()), Applicability::MaybeIncorrect)
+                } else {
+                    (
+                        format!("{cast_to_snip}::from({operand})"),
+                        Applicability::MachineApplicable,
+                    )
+                };
+                diag.span_suggestion(expr.span, "use `From` instead", sugg, app);
+            } else if matches!(category, CastCategory::SignChange | CastCategory::Truncation)
+                && implements(sym::TryFrom)
+            {
+                let sugg = if cast_to_snip == "_" {
+                    format!("{}.try_into()", operand.maybe_par())
+                } else {
+                    format!("{cast_to_snip}::try_from({operand})")
+                };
+                diag.span_suggestion_with_style(
+                    expr.span,
+                    "use `TryFrom` and handle the error instead",
+                    sugg,
+                    Applicability::Unspecified,
+                    SuggestionStyle::ShowAlways,
+                );
+            } else if category == CastCategory::FloatInt && cast_from.is_floating_point() {
+                diag.help(
+                    "use `to_bits` to get the representation of the float, or check the range of the rounded \
+                     value before converting it",
+                );
+            } else if category == CastCategory::Pointer && cast_from.is_unsafe_ptr() && cast_to.is_unsafe_ptr() {
+                diag.help("use `cast`, `cast_const` or `cast_mut` instead");
+            } else if category == CastCategory::Pointer && cast_from.is_ref() {
+                diag.help("use `std::ptr::from_ref` or `std::ptr::from_mut` instead");
+            }
+        },
+    );
+}
+
+/// Returns the category of the cast, or `None` if it isn't one of the categorized casts, e.g. the
+/// cast of an enum to an integer.
+fn category<'tcx>(
+    cx: &LateContext<'tcx>,
+    cast_from: Ty<'tcx>,
+    cast_to: Ty<'tcx>,
+    target_pointer_widths: &[PointerWidth],
+) -> Option<CastCategory> {
+    if cast_from == cast_to {
+        return None;
+    }
+    if cast_from.is_unsafe_ptr() || cast_from.is_fn() || cast_to.is_unsafe_ptr() {
+        return Some(CastCategory::Pointer);
+    }
+    let widths: Vec<_> = if target_pointer_widths.is_empty() {
+        vec![cx.tcx.data_layout.pointer_size.bits()]
+    } else {
+        target_pointer_widths.iter().map(|width| width.bits()).collect()
+    };
+    match (cast_from.kind(), cast_to.kind()) {
+        (ty::Float(from), ty::Float(to)) => {
+            if from.bit_width() < to.bit_width() {
+                Some(CastCategory::Widening)
+            } else {
+                Some(CastCategory::Truncation)
+            }
+        },
+        (ty::Float(_), ty::Int(_) | ty::Uint(_)) | (ty::Int(_) | ty::Uint(_), ty::Float(_)) => {
+            Some(CastCategory::FloatInt)
+        },
+        (ty::Bool, _) | (ty::Uint(ty::UintTy::U8), ty::Char) => Some(CastCategory::Widening),
+        // The code points fit in 21 bits.
+        (ty::Char, ty::Int(_) | ty::Uint(_)) => {
+            if widths.iter().all(|&width| nbits(cast_to, width) >= 32) {
+                Some(CastCategory::Widening)
+            } else {
+                Some(CastCategory::Truncation)
+            }
+        },
+        (ty::Int(_) | ty::Uint(_), ty::Int(_) | ty::Uint(_)) => {
+            if widths.iter().all(|&width| is_lossless(cast_from, cast_to, width)) {
+                Some(CastCategory::Widening)
+            } else if widths
+                .iter()
+                .any(|&width| nbits(cast_from, width) > nbits(cast_to, width))
+            {
+                Some(CastCategory::Truncation)
+            } else {
+                Some(CastCategory::SignChange)
+            }
+        },
+        _ => None,
+    }
+}
@@ -1,6 +1,7 @@
 use clippy_utils::consts::{constant, Constant};
 use clippy_utils::diagnostics::{span_lint, span_lint_and_then};
 use clippy_utils::expr_or_init;
+use clippy_utils::intervals::{expr_interval, Interval};
 use clippy_utils::source::snippet;
 use clippy_utils::sugg::Sugg;
 use clippy_utils::ty::{get_discriminant_value, is_isize_or_usize};
@@ -83,16 +84,34 @@ fn apply_reductions(cx: &LateContext<'_>, nbits: u64, expr: &Expr<'_>, signed: b
     }
 }
 
-pub(super) fn check(
-    cx: &LateContext<'_>,
+/// The interval of the values of an integer type on every target, i.e. assuming 32-bit wide
+/// pointers for `isize` and `usize`.
+fn target_independent_interval(cx: &LateContext<'_>, ty: Ty<'_>) -> Option<Interval> {
+    match ty.kind() {
+        ty::Int(ty::IntTy::Isize) => Some(Interval::new(i32::MIN.into(), i32::MAX.into())),
+        ty::Uint(ty::UintTy::Usize) => Some(Interval::new(0, u32::MAX.into())),
+        _ => Interval::of_ty(cx.tcx, ty),
+    }
+}
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
     expr: &Expr<'_>,
-    cast_expr: &Expr<'_>,
+    cast_expr: &'tcx Expr<'tcx>,
     cast_from: Ty<'_>,
     cast_to: Ty<'_>,
     cast_to_span: Span,
 ) {
     let msg = match (cast_from.kind(), cast_to.is_integral()) {
         (ty::Int(_) | ty::Uint(_), true) => {
+            // Constants are still linted, as their value can change without the cast being revisited.
+            if constant_int(cx, cast_expr).is_none()
+                && let Some(to_interval) = target_independent_interval(cx, cast_to)
+                && expr_interval(cx, cast_expr).is_some_and(|i| i.is_subset_of(to_interval))
+            {
+                return;
+            }
+
             let from_nbits = apply_reductions(
                 cx,
                 utils::int_ty_to_nbits(cast_from, cx.tcx),
@@ -5,6 +5,7 @@ mod cast_abs_to_unsigned;
 mod cast_enum_constructor;
 mod cast_lossless;
 mod cast_nan_to_int;
+mod cast_policy;
 mod cast_possible_truncation;
 mod cast_possible_wrap;
 mod cast_precision_loss;
@@ -16,6 +17,7 @@ mod char_lit_as_u8;
 mod fn_to_numeric_cast;
 mod fn_to_numeric_cast_any;
 mod fn_to_numeric_cast_with_truncation;
+mod pointer_width_dependent_cast;
 mod ptr_as_ptr;
 mod ptr_cast_constness;
 mod ref_as_ptr;
@@ -24,6 +26,7 @@ mod utils;
 mod zero_ptr;
 
 use clippy_config::msrvs::{self, Msrv};
+use clippy_config::types::{CastCategory, PointerWidth};
 use clippy_utils::is_hir_ty_cfg_dependant;
 use rustc_hir::{Expr, ExprKind};
 use rustc_lint::{LateContext, LateLintPass, LintContext};
@@ -714,14 +717,97 @@ declare_clippy_lint! {
     "using `as` to cast a reference to pointer"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for casts between integer types, one of them `usize` or `isize`, which don't lose
+    /// information on the current target, but truncate or wrap around the value on a target with
+    /// another pointer width, out of those of the `target-pointer-widths` configuration.
+    ///
+    /// ### Why is this bad?
+    /// The cast is correct where the code is written and tested, and silently wrong where it is
+    /// ported to, e.g. `u64 as usize` on a 32-bit target. Unlike `cast_possible_truncation`, this
+    /// is about the casts which are lossless today.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn offset(pos: u64) -> usize {
+    ///     pos as usize
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// fn offset(pos: u64) -> usize {
+    ///     usize::try_from(pos).expect("offset fits in the address space")
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub POINTER_WIDTH_DEPENDENT_CAST,
+    restriction,
+    "casts involving `usize` or `isize` which are lossless on the current target only"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `as` casts whose category isn't allowed by the `allowed-cast-categories`
+    /// configuration. The categories are:
+    /// - `widening`: casts which can't lose information, e.g. `u8` to `u32`, `bool` to `u8` or
+    ///   `f32` to `f64`
+    /// - `sign-change`: casts between integers which can change the sign of the value, e.g. `i32`
+    ///   to `u32`
+    /// - `truncation`: casts to a smaller type, e.g. `u64` to `u32` or `f64` to `f32`
+    /// - `float-int`: casts between an integer and a float
+    /// - `pointer`: casts from or to a pointer, and from a function
+    ///
+    /// A cast involving `usize` or `isize` is a widening only if it is on each of the
+    /// `target-pointer-widths`.
+    ///
+    /// ### Why is this bad?
+    /// `as` silently truncates, wraps around, rounds or reinterprets values depending on the
+    /// types. A single policy of the casts allowed in a codebase is easier to follow than the
+    /// configuration of each of the cast lints, and `From`, `TryFrom` or the methods of the
+    /// types state the intent of the other conversions.
+    ///
+    /// ### Example
+    /// ```toml
+    /// allowed-cast-categories = [ "widening" ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// fn checksum(len: u64, flags: u8) -> u32 {
+    ///     len as u32 + flags as u32
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// fn checksum(len: u64, flags: u8) -> Result<u32, TryFromIntError> {
+    ///     Ok(u32::try_from(len)? + flags as u32)
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub CAST_POLICY,
+    restriction,
+    "`as` casts whose category isn't allowed by the configuration"
+}
+
 pub struct Casts {
     msrv: Msrv,
+    target_pointer_widths: Vec<PointerWidth>,
+    allowed_cast_categories: Vec<CastCategory>,
 }
 
 impl Casts {
     #[must_use]
-    pub fn new(msrv: Msrv) -> Self {
-        Self { msrv }
+    pub fn new(
+        msrv: Msrv,
+        target_pointer_widths: Vec<PointerWidth>,
+        allowed_cast_categories: Vec<CastCategory>,
+    ) -> Self {
+        Self {
+            msrv,
+            target_pointer_widths,
+            allowed_cast_categories,
+        }
     }
 }
 
@@ -750,10 +836,12 @@ impl_lint_pass!(Casts => [
     CAST_NAN_TO_INT,
     ZERO_PTR,
     REF_AS_PTR,
+    POINTER_WIDTH_DEPENDENT_CAST,
+    CAST_POLICY,
 ]);
 
 impl<'tcx> LateLintPass<'tcx> for Casts {
-    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
         if in_external_macro(cx.sess(), expr.span) {
             return;
         }
@@ -782,6 +870,15 @@ impl<'tcx> LateLintPass<'tcx> for Casts {
                 cast_possible_truncation::check(cx, expr, cast_expr, cast_from, cast_to, cast_to_hir.span);
                 if cast_from.is_numeric() {
                     cast_possible_wrap::check(cx, expr, cast_from, cast_to);
+                    pointer_width_dependent_cast::check(
+                        cx,
+                        expr,
+                        cast_expr,
+                        cast_from,
+                        cast_to,
+                        cast_to_hir.span,
+                        &self.target_pointer_widths,
+                    );
                     cast_precision_loss::check(cx, expr, cast_from, cast_to);
                     cast_sign_loss::check(cx, expr, cast_expr, cast_from, cast_to);
                     cast_abs_to_unsigned::check(cx, expr, cast_expr, cast_from, cast_to, &self.msrv);
@@ -792,6 +889,14 @@ impl<'tcx> LateLintPass<'tcx> for Casts {
             }
 
             as_underscore::check(cx, expr, cast_to_hir);
+            cast_policy::check(
+                cx,
+                expr,
+                cast_from,
+                cast_to,
+                &self.target_pointer_widths,
+                &self.allowed_cast_categories,
+            );
 
             if self.msrv.meets(msrvs::PTR_FROM_REF) {
                 ref_as_ptr::check(cx, expr, cast_expr, cast_to_hir);
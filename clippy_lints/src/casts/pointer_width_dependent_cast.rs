@@ -0,0 +1,109 @@
+use clippy_config::types::PointerWidth;
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::intervals::{expr_interval, Interval};
+use clippy_utils::source::snippet;
+use clippy_utils::sugg::Sugg;
+use rustc_errors::{Applicability, SuggestionStyle};
+use rustc_hir::Expr;
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, Ty};
+use rustc_span::Span;
+
+use super::POINTER_WIDTH_DEPENDENT_CAST;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &Expr<'_>,
+    cast_expr: &'tcx Expr<'tcx>,
+    cast_from: Ty<'_>,
+    cast_to: Ty<'_>,
+    cast_to_span: Span,
+    target_pointer_widths: &[PointerWidth],
+) {
+    if !(cast_from.is_integral() && cast_to.is_integral())
+        || !(cast_from.is_ptr_sized_integral() || cast_to.is_ptr_sized_integral())
+        || !is_lossless(cast_from, cast_to, cx.tcx.data_layout.pointer_size.bits())
+    {
+        return;
+    }
+
+    // The values of the casted expression are those on the current target, which is fine as the
+    // cast is lossless on it. Constants are still linted, as their value can change without the
+    // cast being revisited.
+    let from_interval = if matches!(constant(cx, cx.typeck_results(), cast_expr), Some(Constant::Int(_))) {
+        None
+    } else {
+        expr_interval(cx, cast_expr)
+    };
+    let Some(ptr_width) = target_pointer_widths
+        .iter()
+        .map(|width| width.bits())
+        .filter(|&ptr_width| {
+            !is_lossless(cast_from, cast_to, ptr_width)
+                && !from_interval.is_some_and(|i| i.is_subset_of(ty_interval(cast_to, ptr_width)))
+        })
+        .min()
+    else {
+        return;
+    };
+
+    let effect = if nbits(cast_from, ptr_width) > nbits(cast_to, ptr_width) {
+        "truncate"
+    } else {
+        "wrap around"
+    };
+    span_lint_and_then(
+        cx,
+        POINTER_WIDTH_DEPENDENT_CAST,
+        expr.span,
+        format!(
+            "casting `{cast_from}` to `{cast_to}` may {effect} the value on targets with {ptr_width}-bit wide pointers"
+        ),
+        |diag| {
+            let cast_to_snip = snippet(cx, cast_to_span, "..");
+            let suggestion = if cast_to_snip == "_" {
+                format!("{}.try_into()", Sugg::hir(cx, cast_expr, "..").maybe_par())
+            } else {
+                format!("{cast_to_snip}::try_from({})", Sugg::hir(cx, cast_expr, ".."))
+            };
+            diag.span_suggestion_with_style(
+                expr.span,
+                "use `try_from` and handle the error accordingly, or a type of the same width on every target",
+                suggestion,
+                Applicability::Unspecified,
+                SuggestionStyle::ShowAlways,
+            );
+        },
+    );
+}
+
+/// The size in bits of an integer type, with pointers of `ptr_width` bits.
+pub(super) fn nbits(ty: Ty<'_>, ptr_width: u64) -> u64 {
+    match ty.kind() {
+        ty::Int(ty::IntTy::Isize) | ty::Uint(ty::UintTy::Usize) => ptr_width,
+        ty::Int(int_ty) => int_ty.bit_width().unwrap_or_default(),
+        ty::Uint(uint_ty) => uint_ty.bit_width().unwrap_or_default(),
+        _ => 0,
+    }
+}
+
+/// The interval of the values of an integer type, with pointers of `ptr_width` bits.
+fn ty_interval(ty: Ty<'_>, ptr_width: u64) -> Interval {
+    let bits = nbits(ty, ptr_width);
+    if ty.is_signed() {
+        Interval::new(-(1 << (bits - 1)), (1 << (bits - 1)) - 1)
+    } else {
+        Interval::new(0, (1 << bits) - 1)
+    }
+}
+
+/// Checks if every value of `from` is a value of `to`, with pointers of `ptr_width` bits.
+pub(super) fn is_lossless(from: Ty<'_>, to: Ty<'_>, ptr_width: u64) -> bool {
+    let (from_nbits, to_nbits) = (nbits(from, ptr_width), nbits(to, ptr_width));
+    match (from.is_signed(), to.is_signed()) {
+        (true, false) => false,
+        (false, true) => from_nbits < to_nbits,
+        _ => from_nbits <= to_nbits,
+    }
+}
@@ -50,7 +50,7 @@ impl_lint_pass!(CheckedConversions => [CHECKED_CONVERSIONS]);
 
 impl<'tcx> LateLintPass<'tcx> for CheckedConversions {
     fn check_expr(&mut self, cx: &LateContext<'_>, item: &Expr<'_>) {
-        if !self.msrv.meets(msrvs::TRY_FROM) {
+        if !self.msrv.meets(msrvs::TRY_FROM) || in_external_macro(cx.sess(), item.span) {
             return;
         }
 
@@ -0,0 +1,184 @@
+use clippy_utils::crate_cache;
+use clippy_utils::diagnostics::span_lint_and_then;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::{walk_param_bound, walk_ty, Visitor};
+use rustc_hir::{GenericBound, Item, ItemKind, Ty, TyKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::hir::nested_filter;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_session::declare_lint_pass;
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for traits private to the crate which are only used as trait objects, e.g.
+    /// `Box<dyn Trait>` or `&dyn Trait`, and are implemented by a few types without generics.
+    ///
+    /// ### Why is this bad?
+    /// The set of implementors is known, so an enum with a variant for each of them can replace
+    /// the trait object. Calls through the enum are static and can be inlined, the values don't
+    /// need to be boxed, and matching on the enum is checked for exhaustiveness.
+    ///
+    /// ### Known problems
+    /// Only traits with at most 8 implementors are linted. The enum is larger than a pointer to a
+    /// trait object when the sizes of the implementors differ a lot.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// trait Shape {
+    ///     fn area(&self) -> f64;
+    /// }
+    ///
+    /// struct Circle(f64);
+    /// struct Square(f64);
+    ///
+    /// impl Shape for Circle {
+    ///     fn area(&self) -> f64 {
+    ///         3.14 * self.0 * self.0
+    ///     }
+    /// }
+    ///
+    /// impl Shape for Square {
+    ///     fn area(&self) -> f64 {
+    ///         self.0 * self.0
+    ///     }
+    /// }
+    ///
+    /// fn total_area(shapes: &[Box<dyn Shape>]) -> f64 {
+    ///     shapes.iter().map(|shape| shape.area()).sum()
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// enum Shape {
+    ///     Circle(f64),
+    ///     Square(f64),
+    /// }
+    ///
+    /// impl Shape {
+    ///     fn area(&self) -> f64 {
+    ///         match self {
+    ///             Shape::Circle(r) => 3.14 * r * r,
+    ///             Shape::Square(side) => side * side,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// fn total_area(shapes: &[Shape]) -> f64 {
+    ///     shapes.iter().map(Shape::area).sum()
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub CLOSED_DYN_TRAIT,
+    nursery,
+    "traits with a few known implementors only used as trait objects"
+}
+
+declare_lint_pass!(ClosedDynTrait => [CLOSED_DYN_TRAIT]);
+
+/// The maximum number of implementors for an enum to be suggested
+const MAX_IMPLEMENTORS: usize = 8;
+
+impl<'tcx> LateLintPass<'tcx> for ClosedDynTrait {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        let def_id = item.owner_id.def_id;
+        if let ItemKind::Trait(_, _, generics, ..) = item.kind
+            && !item.span.from_expansion()
+            && generics.params.is_empty()
+            && !cx.effective_visibilities.is_exported(def_id)
+            && let Some(implementors) = implementors(cx.tcx, def_id)
+            && (2..=MAX_IMPLEMENTORS).contains(&implementors.len())
+            && let Some(TraitUse::Dyn(dyn_uses)) = crate_cache::get_or_compute(cx, || TraitUses::collect(cx.tcx))
+                .0
+                .get(&def_id)
+        {
+            span_lint_and_then(
+                cx,
+                CLOSED_DYN_TRAIT,
+                item.ident.span,
+                "this trait is only used as a trait object, and has a few known implementors",
+                |diag| {
+                    diag.span_note(dyn_uses.clone(), "the trait objects are here");
+                    let names: Vec<_> = implementors
+                        .iter()
+                        .map(|&did| format!("`{}`", cx.tcx.item_name(did)))
+                        .collect();
+                    diag.help(format!(
+                        "consider an enum with a variant for each of {}",
+                        names.join(", ")
+                    ));
+                },
+            );
+        }
+    }
+}
+
+/// The types implementing the trait, if they are all types without generics
+fn implementors(tcx: TyCtxt<'_>, trait_id: LocalDefId) -> Option<Vec<DefId>> {
+    tcx.all_impls(trait_id.to_def_id())
+        .map(|impl_id| {
+            let self_ty = tcx.impl_trait_ref(impl_id)?.instantiate_identity().self_ty();
+            match self_ty.kind() {
+                ty::Adt(adt, args) if args.is_empty() && tcx.generics_of(impl_id).count() == 0 => Some(adt.did()),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+enum TraitUse {
+    /// The spans of the trait objects
+    Dyn(Vec<Span>),
+    /// The trait is a bound, e.g. `T: Trait` or `impl Trait`
+    Bound,
+}
+
+/// How each trait of the crate is used in types and bounds
+struct TraitUses(FxHashMap<LocalDefId, TraitUse>);
+
+impl TraitUses {
+    fn collect(tcx: TyCtxt<'_>) -> Self {
+        let mut visitor = TraitUseVisitor {
+            tcx,
+            uses: FxHashMap::default(),
+        };
+        tcx.hir().visit_all_item_likes_in_crate(&mut visitor);
+        Self(visitor.uses)
+    }
+}
+
+struct TraitUseVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    uses: FxHashMap<LocalDefId, TraitUse>,
+}
+
+impl<'tcx> Visitor<'tcx> for TraitUseVisitor<'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_ty(&mut self, ty: &'tcx Ty<'tcx>) {
+        if let TyKind::TraitObject(bounds, ..) = ty.kind {
+            for bound in bounds {
+                if let Some(trait_id) = bound.trait_ref.trait_def_id().and_then(DefId::as_local)
+                    && let TraitUse::Dyn(spans) = self.uses.entry(trait_id).or_insert(TraitUse::Dyn(Vec::new()))
+                {
+                    spans.push(ty.span);
+                }
+            }
+        }
+        walk_ty(self, ty);
+    }
+
+    fn visit_param_bound(&mut self, bound: &'tcx GenericBound<'tcx>) {
+        if let GenericBound::Trait(poly_trait_ref, _) = bound
+            && let Some(trait_id) = poly_trait_ref.trait_ref.trait_def_id().and_then(DefId::as_local)
+        {
+            self.uses.insert(trait_id, TraitUse::Bound);
+        }
+        walk_param_bound(self, bound);
+    }
+}
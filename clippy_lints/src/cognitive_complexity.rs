@@ -1,15 +1,16 @@
 //! calculate cognitive complexity and warn about overly complex functions
 
+use clippy_config::types::ThresholdOverride;
 use clippy_utils::diagnostics::span_lint_and_help;
 use clippy_utils::source::snippet_opt;
+use clippy_utils::thresholds::{Threshold, ThresholdKind};
 use clippy_utils::ty::is_type_diagnostic_item;
 use clippy_utils::visitors::for_each_expr;
-use clippy_utils::{get_async_fn_body, is_async_fn, LimitStack};
+use clippy_utils::{get_async_fn_body, is_async_fn};
 use core::ops::ControlFlow;
-use rustc_ast::ast::Attribute;
 use rustc_hir::intravisit::FnKind;
-use rustc_hir::{Body, Expr, ExprKind, FnDecl};
-use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_hir::{Body, Expr, ExprKind, FnDecl, HirId};
+use rustc_lint::{LateContext, LateLintPass};
 use rustc_session::impl_lint_pass;
 use rustc_span::def_id::LocalDefId;
 use rustc_span::{sym, BytePos, Span};
@@ -35,14 +36,14 @@ declare_clippy_lint! {
 }
 
 pub struct CognitiveComplexity {
-    limit: LimitStack,
+    limit: Threshold,
 }
 
 impl CognitiveComplexity {
     #[must_use]
-    pub fn new(limit: u64) -> Self {
+    pub fn new(limit: u64, threshold_overrides: &[ThresholdOverride]) -> Self {
         Self {
-            limit: LimitStack::new(limit),
+            limit: Threshold::new(ThresholdKind::CognitiveComplexity, limit, threshold_overrides),
         }
     }
 }
@@ -58,6 +59,7 @@ impl CognitiveComplexity {
         decl: &'tcx FnDecl<'_>,
         expr: &'tcx Expr<'_>,
         body_span: Span,
+        hir_id: HirId,
     ) {
         if body_span.from_expansion() {
             return;
@@ -95,7 +97,8 @@ impl CognitiveComplexity {
             cc -= ret_adjust;
         }
 
-        if cc > self.limit.limit() {
+        let limit = self.limit.resolve(cx, hir_id, body_span);
+        if cc > limit {
             let fn_span = match kind {
                 FnKind::ItemFn(ident, _, _) | FnKind::Method(ident, _) => ident.span,
                 FnKind::Closure => {
@@ -121,10 +124,7 @@ impl CognitiveComplexity {
                 cx,
                 COGNITIVE_COMPLEXITY,
                 fn_span,
-                format!(
-                    "the function has a cognitive complexity of ({cc}/{})",
-                    self.limit.limit()
-                ),
+                format!("the function has a cognitive complexity of ({cc}/{limit})"),
                 None,
                 "you could split it up into multiple smaller functions",
             );
@@ -154,14 +154,7 @@ impl<'tcx> LateLintPass<'tcx> for CognitiveComplexity {
                 body.value
             };
 
-            self.check(cx, kind, decl, expr, span);
+            self.check(cx, kind, decl, expr, span, cx.tcx.local_def_id_to_hir_id(def_id));
         }
     }
-
-    fn check_attributes(&mut self, cx: &LateContext<'tcx>, attrs: &'tcx [Attribute]) {
-        self.limit.push_attrs(cx.sess(), attrs, "cognitive_complexity");
-    }
-    fn check_attributes_post(&mut self, cx: &LateContext<'tcx>, attrs: &'tcx [Attribute]) {
-        self.limit.pop_attrs(cx.sess(), attrs, "cognitive_complexity");
-    }
 }
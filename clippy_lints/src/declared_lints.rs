@@ -10,6 +10,10 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     #[cfg(feature = "internal")]
     crate::utils::internal_lints::compiler_lint_functions::COMPILER_LINT_FUNCTIONS_INFO,
     #[cfg(feature = "internal")]
+    crate::utils::internal_lints::configuration_lints::INCONSISTENT_CONFIGURATION_LINTS_INFO,
+    #[cfg(feature = "internal")]
+    crate::utils::internal_lints::crate_walk_in_node_check::CRATE_WALK_IN_NODE_CHECK_INFO,
+    #[cfg(feature = "internal")]
     crate::utils::internal_lints::interning_defined_symbol::INTERNING_DEFINED_SYMBOL_INFO,
     #[cfg(feature = "internal")]
     crate::utils::internal_lints::interning_defined_symbol::UNNECESSARY_SYMBOL_STR_INFO,
@@ -34,13 +38,18 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     #[cfg(feature = "internal")]
     crate::utils::internal_lints::produce_ice::PRODUCE_ICE_INFO,
     #[cfg(feature = "internal")]
+    crate::utils::internal_lints::unguarded_span_lint::UNGUARDED_SPAN_LINT_INFO,
+    #[cfg(feature = "internal")]
     crate::utils::internal_lints::unnecessary_def_path::UNNECESSARY_DEF_PATH_INFO,
     #[cfg(feature = "internal")]
     crate::utils::internal_lints::unsorted_clippy_utils_paths::UNSORTED_CLIPPY_UTILS_PATHS_INFO,
     crate::absolute_paths::ABSOLUTE_PATHS_INFO,
+    crate::alloc_in_no_alloc::ALLOC_IN_NO_ALLOC_INFO,
     crate::allow_attributes::ALLOW_ATTRIBUTES_INFO,
     crate::almost_complete_range::ALMOST_COMPLETE_RANGE_INFO,
+    crate::ambient_randomness::AMBIENT_RANDOMNESS_INFO,
     crate::approx_const::APPROX_CONSTANT_INFO,
+    crate::arbitrary_str_slice::ARBITRARY_STR_SLICE_INFO,
     crate::arc_with_non_send_sync::ARC_WITH_NON_SEND_SYNC_INFO,
     crate::as_conversions::AS_CONVERSIONS_INFO,
     crate::asm_syntax::INLINE_ASM_X86_ATT_SYNTAX_INFO,
@@ -76,10 +85,12 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::borrow_deref_ref::BORROW_DEREF_REF_INFO,
     crate::box_default::BOX_DEFAULT_INFO,
     crate::cargo::CARGO_COMMON_METADATA_INFO,
+    crate::cargo::GIT_DEPENDENCIES_INFO,
     crate::cargo::LINT_GROUPS_PRIORITY_INFO,
     crate::cargo::MULTIPLE_CRATE_VERSIONS_INFO,
     crate::cargo::NEGATIVE_FEATURE_NAMES_INFO,
     crate::cargo::REDUNDANT_FEATURE_NAMES_INFO,
+    crate::cargo::UNAPPROVED_LICENSES_INFO,
     crate::cargo::WILDCARD_DEPENDENCIES_INFO,
     crate::casts::AS_PTR_CAST_MUT_INFO,
     crate::casts::AS_UNDERSCORE_INFO,
@@ -89,6 +100,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::casts::CAST_ENUM_TRUNCATION_INFO,
     crate::casts::CAST_LOSSLESS_INFO,
     crate::casts::CAST_NAN_TO_INT_INFO,
+    crate::casts::CAST_POLICY_INFO,
     crate::casts::CAST_POSSIBLE_TRUNCATION_INFO,
     crate::casts::CAST_POSSIBLE_WRAP_INFO,
     crate::casts::CAST_PRECISION_LOSS_INFO,
@@ -100,12 +112,14 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::casts::FN_TO_NUMERIC_CAST_INFO,
     crate::casts::FN_TO_NUMERIC_CAST_ANY_INFO,
     crate::casts::FN_TO_NUMERIC_CAST_WITH_TRUNCATION_INFO,
+    crate::casts::POINTER_WIDTH_DEPENDENT_CAST_INFO,
     crate::casts::PTR_AS_PTR_INFO,
     crate::casts::PTR_CAST_CONSTNESS_INFO,
     crate::casts::REF_AS_PTR_INFO,
     crate::casts::UNNECESSARY_CAST_INFO,
     crate::casts::ZERO_PTR_INFO,
     crate::checked_conversions::CHECKED_CONVERSIONS_INFO,
+    crate::closed_dyn_trait::CLOSED_DYN_TRAIT_INFO,
     crate::cognitive_complexity::COGNITIVE_COMPLEXITY_INFO,
     crate::collapsible_if::COLLAPSIBLE_ELSE_IF_INFO,
     crate::collapsible_if::COLLAPSIBLE_IF_INFO,
@@ -135,11 +149,17 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::derive::DERIVE_PARTIAL_EQ_WITHOUT_EQ_INFO,
     crate::derive::EXPL_IMPL_CLONE_ON_COPY_INFO,
     crate::derive::UNSAFE_DERIVE_DESERIALIZE_INFO,
+    crate::direct_clock_access::DIRECT_CLOCK_ACCESS_INFO,
+    crate::disallowed_attributes::DISALLOWED_ATTRIBUTES_INFO,
+    crate::disallowed_env_vars::DISALLOWED_ENV_VARS_INFO,
+    crate::disallowed_imports::DISALLOWED_IMPORTS_INFO,
     crate::disallowed_macros::DISALLOWED_MACROS_INFO,
     crate::disallowed_methods::DISALLOWED_METHODS_INFO,
     crate::disallowed_names::DISALLOWED_NAMES_INFO,
     crate::disallowed_script_idents::DISALLOWED_SCRIPT_IDENTS_INFO,
     crate::disallowed_types::DISALLOWED_TYPES_INFO,
+    crate::disallowed_types_across_regions::DISALLOWED_TYPES_ACROSS_REGIONS_INFO,
+    crate::display_forwarding_to_debug::DISPLAY_FORWARDING_TO_DEBUG_INFO,
     crate::doc::DOC_LAZY_CONTINUATION_INFO,
     crate::doc::DOC_LINK_WITH_QUOTES_INFO,
     crate::doc::DOC_MARKDOWN_INFO,
@@ -155,6 +175,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::drop_forget_ref::DROP_NON_DROP_INFO,
     crate::drop_forget_ref::FORGET_NON_DROP_INFO,
     crate::drop_forget_ref::MEM_FORGET_INFO,
+    crate::dropped_error_source::DROPPED_ERROR_SOURCE_INFO,
     crate::duplicate_mod::DUPLICATE_MOD_INFO,
     crate::else_if_without_else::ELSE_IF_WITHOUT_ELSE_INFO,
     crate::empty_drop::EMPTY_DROP_INFO,
@@ -166,6 +187,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::endian_bytes::LITTLE_ENDIAN_BYTES_INFO,
     crate::entry::MAP_ENTRY_INFO,
     crate::enum_clike::ENUM_CLIKE_UNPORTABLE_VARIANT_INFO,
+    crate::env_access_outside_config::ENV_ACCESS_OUTSIDE_CONFIG_INFO,
     crate::equatable_if_let::EQUATABLE_IF_LET_INFO,
     crate::error_impl_error::ERROR_IMPL_ERROR_INFO,
     crate::escape::BOXED_LOCAL_INFO,
@@ -216,12 +238,16 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::if_then_some_else_none::IF_THEN_SOME_ELSE_NONE_INFO,
     crate::ignored_unit_patterns::IGNORED_UNIT_PATTERNS_INFO,
     crate::impl_hash_with_borrow_str_and_bytes::IMPL_HASH_BORROW_WITH_STR_AND_BYTES_INFO,
+    crate::implicit_field_init::IMPLICIT_FIELD_INIT_INFO,
     crate::implicit_hasher::IMPLICIT_HASHER_INFO,
     crate::implicit_return::IMPLICIT_RETURN_INFO,
     crate::implicit_saturating_add::IMPLICIT_SATURATING_ADD_INFO,
     crate::implicit_saturating_sub::IMPLICIT_SATURATING_SUB_INFO,
     crate::implied_bounds_in_impls::IMPLIED_BOUNDS_IN_IMPLS_INFO,
     crate::incompatible_msrv::INCOMPATIBLE_MSRV_INFO,
+    crate::inconsistent_borrow::INCONSISTENT_BORROW_INFO,
+    crate::inconsistent_hash_and_eq::HASH_EQ_FIELD_MISMATCH_INFO,
+    crate::inconsistent_hash_and_eq::INCONSISTENT_HASH_AND_EQ_INFO,
     crate::inconsistent_struct_constructor::INCONSISTENT_STRUCT_CONSTRUCTOR_INFO,
     crate::index_refutable_slice::INDEX_REFUTABLE_SLICE_INFO,
     crate::indexing_slicing::INDEXING_SLICING_INFO,
@@ -236,6 +262,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::inline_fn_without_body::INLINE_FN_WITHOUT_BODY_INFO,
     crate::instant_subtraction::MANUAL_INSTANT_ELAPSED_INFO,
     crate::instant_subtraction::UNCHECKED_DURATION_SUBTRACTION_INFO,
+    crate::instant_subtraction::UNGUARDED_TIME_SUBTRACTION_INFO,
     crate::int_plus_one::INT_PLUS_ONE_INFO,
     crate::integer_division_remainder_used::INTEGER_DIVISION_REMAINDER_USED_INFO,
     crate::invalid_upcast_comparisons::INVALID_UPCAST_COMPARISONS_INFO,
@@ -256,6 +283,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::large_stack_arrays::LARGE_STACK_ARRAYS_INFO,
     crate::large_stack_frames::LARGE_STACK_FRAMES_INFO,
     crate::legacy_numeric_constants::LEGACY_NUMERIC_CONSTANTS_INFO,
+    crate::legacy_ref_pattern::LEGACY_REF_PATTERN_INFO,
     crate::len_zero::COMPARISON_TO_EMPTY_INFO,
     crate::len_zero::LEN_WITHOUT_IS_EMPTY_INFO,
     crate::len_zero::LEN_ZERO_INFO,
@@ -295,6 +323,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::loops::WHILE_IMMUTABLE_CONDITION_INFO,
     crate::loops::WHILE_LET_LOOP_INFO,
     crate::loops::WHILE_LET_ON_ITERATOR_INFO,
+    crate::lossy_from::LOSSY_FROM_INFO,
     crate::macro_use::MACRO_USE_IMPORTS_INFO,
     crate::main_recursion::MAIN_RECURSION_INFO,
     crate::manual_assert::MANUAL_ASSERT_INFO,
@@ -311,6 +340,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::manual_range_patterns::MANUAL_RANGE_PATTERNS_INFO,
     crate::manual_rem_euclid::MANUAL_REM_EUCLID_INFO,
     crate::manual_retain::MANUAL_RETAIN_INFO,
+    crate::manual_slice_pattern::MANUAL_SLICE_PATTERN_INFO,
     crate::manual_slice_size_calculation::MANUAL_SLICE_SIZE_CALCULATION_INFO,
     crate::manual_string_new::MANUAL_STRING_NEW_INFO,
     crate::manual_strip::MANUAL_STRIP_INFO,
@@ -325,6 +355,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::matches::MANUAL_UNWRAP_OR_INFO,
     crate::matches::MATCH_AS_REF_INFO,
     crate::matches::MATCH_BOOL_INFO,
+    crate::matches::MATCH_BOOL_TUPLE_INFO,
     crate::matches::MATCH_LIKE_MATCHES_MACRO_INFO,
     crate::matches::MATCH_ON_VEC_ITEMS_INFO,
     crate::matches::MATCH_OVERLAPPING_ARM_INFO,
@@ -344,6 +375,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::matches::TRY_ERR_INFO,
     crate::matches::WILDCARD_ENUM_MATCH_ARM_INFO,
     crate::matches::WILDCARD_IN_OR_PATTERNS_INFO,
+    crate::matches_then_destructure::MATCHES_THEN_DESTRUCTURE_INFO,
     crate::mem_replace::MEM_REPLACE_OPTION_WITH_NONE_INFO,
     crate::mem_replace::MEM_REPLACE_WITH_DEFAULT_INFO,
     crate::mem_replace::MEM_REPLACE_WITH_UNINIT_INFO,
@@ -353,6 +385,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::methods::CASE_SENSITIVE_FILE_EXTENSION_COMPARISONS_INFO,
     crate::methods::CHARS_LAST_CMP_INFO,
     crate::methods::CHARS_NEXT_CMP_INFO,
+    crate::methods::CHECKED_ARITHMETIC_UNWRAP_INFO,
     crate::methods::CLEAR_WITH_DRAIN_INFO,
     crate::methods::CLONED_INSTEAD_OF_COPIED_INFO,
     crate::methods::CLONE_ON_COPY_INFO,
@@ -457,6 +490,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::methods::SUSPICIOUS_OPEN_OPTIONS_INFO,
     crate::methods::SUSPICIOUS_SPLITN_INFO,
     crate::methods::SUSPICIOUS_TO_OWNED_INFO,
+    crate::methods::TRIM_MATCHES_INSTEAD_OF_STRIP_INFO,
     crate::methods::TYPE_ID_ON_BOX_INFO,
     crate::methods::UNINIT_ASSUMED_INIT_INFO,
     crate::methods::UNIT_HASH_INFO,
@@ -499,24 +533,31 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::missing_assert_message::MISSING_ASSERT_MESSAGE_INFO,
     crate::missing_asserts_for_indexing::MISSING_ASSERTS_FOR_INDEXING_INFO,
     crate::missing_const_for_fn::MISSING_CONST_FOR_FN_INFO,
+    crate::missing_const_for_fn::MISSING_REQUIRED_CONST_FN_INFO,
     crate::missing_doc::MISSING_DOCS_IN_PRIVATE_ITEMS_INFO,
     crate::missing_enforced_import_rename::MISSING_ENFORCED_IMPORT_RENAMES_INFO,
+    crate::missing_error_impls::MISSING_ERROR_IMPLS_INFO,
     crate::missing_fields_in_debug::MISSING_FIELDS_IN_DEBUG_INFO,
+    crate::missing_finalizer::MISSING_FINALIZER_INFO,
     crate::missing_inline::MISSING_INLINE_IN_PUBLIC_ITEMS_INFO,
+    crate::missing_track_caller::MISSING_TRACK_CALLER_INFO,
     crate::missing_trait_methods::MISSING_TRAIT_METHODS_INFO,
     crate::mixed_read_write_in_expression::DIVERGING_SUB_EXPRESSION_INFO,
     crate::mixed_read_write_in_expression::MIXED_READ_WRITE_IN_EXPRESSION_INFO,
+    crate::module_layering::MODULE_LAYERING_INFO,
     crate::module_style::MOD_MODULE_FILES_INFO,
     crate::module_style::SELF_NAMED_MODULE_FILES_INFO,
     crate::multi_assignments::MULTI_ASSIGNMENTS_INFO,
     crate::multiple_bound_locations::MULTIPLE_BOUND_LOCATIONS_INFO,
     crate::multiple_unsafe_ops_per_block::MULTIPLE_UNSAFE_OPS_PER_BLOCK_INFO,
+    crate::must_use_types::MUST_USE_TYPES_INFO,
     crate::mut_key::MUTABLE_KEY_TYPE_INFO,
     crate::mut_mut::MUT_MUT_INFO,
     crate::mut_reference::UNNECESSARY_MUT_PASSED_INFO,
     crate::mutable_debug_assertion::DEBUG_ASSERT_WITH_MUT_CALL_INFO,
     crate::mutex_atomic::MUTEX_ATOMIC_INFO,
     crate::mutex_atomic::MUTEX_INTEGER_INFO,
+    crate::naming_conventions::NAMING_CONVENTIONS_INFO,
     crate::needless_arbitrary_self_type::NEEDLESS_ARBITRARY_SELF_TYPE_INFO,
     crate::needless_bool::BOOL_COMPARISON_INFO,
     crate::needless_bool::NEEDLESS_BOOL_INFO,
@@ -535,6 +576,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::needless_update::NEEDLESS_UPDATE_INFO,
     crate::neg_cmp_op_on_partial_ord::NEG_CMP_OP_ON_PARTIAL_ORD_INFO,
     crate::neg_multiply::NEG_MULTIPLY_INFO,
+    crate::nested_match_pyramid::NESTED_MATCH_PYRAMID_INFO,
     crate::new_without_default::NEW_WITHOUT_DEFAULT_INFO,
     crate::no_effect::NO_EFFECT_INFO,
     crate::no_effect::NO_EFFECT_UNDERSCORE_BINDING_INFO,
@@ -564,6 +606,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::operators::FLOAT_ARITHMETIC_INFO,
     crate::operators::FLOAT_CMP_INFO,
     crate::operators::FLOAT_CMP_CONST_INFO,
+    crate::operators::FLOAT_CMP_IN_GUARD_OR_ASSERT_INFO,
     crate::operators::FLOAT_EQUALITY_WITHOUT_ABS_INFO,
     crate::operators::IDENTITY_OP_INFO,
     crate::operators::IMPOSSIBLE_COMPARISONS_INFO,
@@ -577,15 +620,18 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::operators::PTR_EQ_INFO,
     crate::operators::REDUNDANT_COMPARISONS_INFO,
     crate::operators::SELF_ASSIGNMENT_INFO,
+    crate::operators::SHIFT_AMOUNT_OVERFLOW_INFO,
     crate::operators::VERBOSE_BIT_MASK_INFO,
     crate::option_env_unwrap::OPTION_ENV_UNWRAP_INFO,
     crate::option_if_let_else::OPTION_IF_LET_ELSE_INFO,
     crate::overflow_check_conditional::OVERFLOW_CHECK_CONDITIONAL_INFO,
+    crate::panic_in_production::PANIC_IN_PRODUCTION_INFO,
     crate::panic_in_result_fn::PANIC_IN_RESULT_FN_INFO,
     crate::panic_unimplemented::PANIC_INFO,
     crate::panic_unimplemented::TODO_INFO,
     crate::panic_unimplemented::UNIMPLEMENTED_INFO,
     crate::panic_unimplemented::UNREACHABLE_INFO,
+    crate::panicking_iterator_next::PANICKING_ITERATOR_NEXT_INFO,
     crate::partial_pub_fields::PARTIAL_PUB_FIELDS_INFO,
     crate::partialeq_ne_impl::PARTIALEQ_NE_IMPL_INFO,
     crate::partialeq_to_none::PARTIALEQ_TO_NONE_INFO,
@@ -597,6 +643,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::ptr::CMP_NULL_INFO,
     crate::ptr::INVALID_NULL_PTR_USAGE_INFO,
     crate::ptr::MUT_FROM_REF_INFO,
+    crate::ptr::OWNED_PARAM_ONLY_BORROWED_INFO,
     crate::ptr::PTR_ARG_INFO,
     crate::ptr_offset_with_cast::PTR_OFFSET_WITH_CAST_INFO,
     crate::pub_underscore_fields::PUB_UNDERSCORE_FIELDS_INFO,
@@ -611,6 +658,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::raw_strings::NEEDLESS_RAW_STRING_HASHES_INFO,
     crate::rc_clone_in_vec_init::RC_CLONE_IN_VEC_INIT_INFO,
     crate::read_zero_byte_vec::READ_ZERO_BYTE_VEC_INFO,
+    crate::recursion::RECURSION_INFO,
     crate::redundant_async_block::REDUNDANT_ASYNC_BLOCK_INFO,
     crate::redundant_clone::REDUNDANT_CLONE_INFO,
     crate::redundant_closure_call::REDUNDANT_CLOSURE_CALL_INFO,
@@ -630,6 +678,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::repeat_vec_with_capacity::REPEAT_VEC_WITH_CAPACITY_INFO,
     crate::reserve_after_initialization::RESERVE_AFTER_INITIALIZATION_INFO,
     crate::return_self_not_must_use::RETURN_SELF_NOT_MUST_USE_INFO,
+    crate::returned_vec_only_iterated::RETURNED_VEC_ONLY_ITERATED_INFO,
     crate::returns::LET_AND_RETURN_INFO,
     crate::returns::NEEDLESS_RETURN_INFO,
     crate::returns::NEEDLESS_RETURN_WITH_QUESTION_MARK_INFO,
@@ -733,6 +782,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::unused_peekable::UNUSED_PEEKABLE_INFO,
     crate::unused_rounding::UNUSED_ROUNDING_INFO,
     crate::unused_self::UNUSED_SELF_INFO,
+    crate::unused_trait_bound::UNUSED_TRAIT_BOUND_INFO,
     crate::unused_unit::UNUSED_UNIT_INFO,
     crate::unwrap::PANICKING_UNWRAP_INFO,
     crate::unwrap::UNNECESSARY_UNWRAP_INFO,
@@ -748,6 +798,7 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::wildcard_imports::ENUM_GLOB_USE_INFO,
     crate::wildcard_imports::WILDCARD_IMPORTS_INFO,
     crate::write::PRINTLN_EMPTY_STRING_INFO,
+    crate::write::PRINT_IN_LIBRARY_INFO,
     crate::write::PRINT_LITERAL_INFO,
     crate::write::PRINT_STDERR_INFO,
     crate::write::PRINT_STDOUT_INFO,
@@ -760,3 +811,562 @@ pub(crate) static LINTS: &[&crate::LintInfo] = &[
     crate::zero_repeat_side_effects::ZERO_REPEAT_SIDE_EFFECTS_INFO,
     crate::zero_sized_map_values::ZERO_SIZED_MAP_VALUES_INFO,
 ];
+
+/// The lints declared by each lint pass, by the name of the pass.
+#[rustfmt::skip]
+pub(crate) static LINT_PASSES: &[(&str, &[&str])] = &[
+    ("AbsolutePaths", &["absolute_paths"]),
+    ("AllocInNoAlloc", &["alloc_in_no_alloc"]),
+    ("AllowAttribute", &["allow_attributes"]),
+    ("AlmostCompleteRange", &["almost_complete_range"]),
+    ("AlmostStandardFormulation", &["almost_standard_lint_formulation"]),
+    ("AmbientRandomness", &["ambient_randomness"]),
+    ("ApproxConstant", &["approx_constant"]),
+    ("ArbitraryStrSlice", &["arbitrary_str_slice"]),
+    ("ArcWithNonSendSync", &["arc_with_non_send_sync"]),
+    ("ArithmeticSideEffects", &["arithmetic_side_effects"]),
+    ("AsConversions", &["as_conversions"]),
+    ("AssertionsOnConstants", &["assertions_on_constants"]),
+    ("AssertionsOnResultStates", &["assertions_on_result_states"]),
+    ("AssigningClones", &["assigning_clones"]),
+    ("AsyncYieldsAsync", &["async_yields_async"]),
+    ("Attributes", &["allow_attributes_without_reason", "inline_always", "deprecated_semver", "useless_attribute", "blanket_clippy_restriction_lints", "should_panic_without_expect", "mixed_attributes_style", "duplicated_attributes"]),
+    ("Author", &[]),
+    ("AwaitHolding", &["await_holding_lock", "await_holding_refcell_ref", "await_holding_invalid_type"]),
+    ("BlocksInConditions", &["blocks_in_conditions"]),
+    ("BoolAssertComparison", &["bool_assert_comparison"]),
+    ("BoolComparison", &["bool_comparison"]),
+    ("BoolToIntWithIf", &["bool_to_int_with_if"]),
+    ("BorrowDerefRef", &["borrow_deref_ref"]),
+    ("BoxDefault", &["box_default"]),
+    ("BoxedLocal", &["boxed_local"]),
+    ("Cargo", &["cargo_common_metadata", "redundant_feature_names", "negative_feature_names", "multiple_crate_versions", "wildcard_dependencies", "lint_groups_priority", "git_dependencies", "unapproved_licenses"]),
+    ("Casts", &["cast_precision_loss", "cast_sign_loss", "cast_possible_truncation", "cast_possible_wrap", "cast_lossless", "cast_ptr_alignment", "cast_slice_different_sizes", "unnecessary_cast", "fn_to_numeric_cast_any", "fn_to_numeric_cast", "fn_to_numeric_cast_with_truncation", "char_lit_as_u8", "ptr_as_ptr", "ptr_cast_constness", "cast_enum_truncation", "cast_enum_constructor", "cast_abs_to_unsigned", "as_underscore", "borrow_as_ptr", "cast_slice_from_raw_parts", "as_ptr_cast_mut", "cast_nan_to_int", "zero_ptr", "ref_as_ptr", "pointer_width_dependent_cast", "cast_policy"]),
+    ("CheckedConversions", &["checked_conversions"]),
+    ("ClosedDynTrait", &["closed_dyn_trait"]),
+    ("CognitiveComplexity", &["cognitive_complexity"]),
+    ("CollapsibleCalls", &["collapsible_span_lint_calls"]),
+    ("CollapsibleIf", &["collapsible_if", "collapsible_else_if"]),
+    ("CollectionIsNeverRead", &["collection_is_never_read"]),
+    ("ComparisonChain", &["comparison_chain"]),
+    ("CompilerLintFunctions", &["compiler_lint_functions"]),
+    ("ConfigurationLints", &["inconsistent_configuration_lints"]),
+    ("ConfusingXorAndPow", &["suspicious_xor_used_as_pow"]),
+    ("CopyAndPaste", &["ifs_same_cond", "same_functions_in_if_condition", "if_same_then_else", "branches_sharing_code"]),
+    ("CopyIterator", &["copy_iterator"]),
+    ("CrateInMacroDef", &["crate_in_macro_def"]),
+    ("CrateWalkInNodeCheck", &["crate_walk_in_node_check"]),
+    ("CreateDir", &["create_dir"]),
+    ("DbgMacro", &["dbg_macro"]),
+    ("DebugAssertWithMutCall", &["debug_assert_with_mut_call"]),
+    ("DecimalLiteralRepresentation", &["decimal_literal_representation"]),
+    ("Default", &["default_trait_access", "field_reassign_with_default"]),
+    ("DefaultConstructedUnitStructs", &["default_constructed_unit_structs"]),
+    ("DefaultIterEmpty", &["default_instead_of_iter_empty"]),
+    ("DefaultNumericFallback", &["default_numeric_fallback"]),
+    ("DefaultUnionRepresentation", &["default_union_representation"]),
+    ("DerefAddrOf", &["deref_addrof"]),
+    ("Dereferencing", &["explicit_deref_methods", "needless_borrow", "ref_binding_to_reference", "explicit_auto_deref"]),
+    ("DerivableImpls", &["derivable_impls"]),
+    ("Derive", &["expl_impl_clone_on_copy", "derived_hash_with_manual_eq", "derive_ord_xor_partial_ord", "unsafe_derive_deserialize", "derive_partial_eq_without_eq"]),
+    ("DirectClockAccess", &["direct_clock_access"]),
+    ("DisallowedAttributes", &["disallowed_attributes"]),
+    ("DisallowedEnvVars", &["disallowed_env_vars"]),
+    ("DisallowedImports", &["disallowed_imports"]),
+    ("DisallowedMacros", &["disallowed_macros"]),
+    ("DisallowedMethods", &["disallowed_methods"]),
+    ("DisallowedNames", &["disallowed_names"]),
+    ("DisallowedScriptIdents", &["disallowed_script_idents"]),
+    ("DisallowedTypes", &["disallowed_types"]),
+    ("DisallowedTypesAcrossRegions", &["disallowed_types_across_regions"]),
+    ("DisplayForwardingToDebug", &["display_forwarding_to_debug"]),
+    ("Documentation", &["doc_link_with_quotes", "doc_markdown", "missing_safety_doc", "missing_errors_doc", "missing_panics_doc", "needless_doctest_main", "test_attr_in_doctest", "unnecessary_safety_doc", "suspicious_doc_comments", "empty_docs", "doc_lazy_continuation"]),
+    ("DoubleParens", &["double_parens"]),
+    ("DropForgetRef", &["drop_non_drop", "forget_non_drop", "mem_forget"]),
+    ("DroppedErrorSource", &["dropped_error_source"]),
+    ("DuplicateMod", &["duplicate_mod"]),
+    ("EarlyAttributes", &["deprecated_cfg_attr", "mismatched_target_os", "empty_line_after_outer_attr", "empty_line_after_doc_comments", "non_minimal_cfg", "maybe_misused_cfg", "deprecated_clippy_cfg_attr", "unnecessary_clippy_cfg"]),
+    ("ElseIfWithoutElse", &["else_if_without_else"]),
+    ("EmptyDrop", &["empty_drop"]),
+    ("EmptyEnum", &["empty_enum"]),
+    ("EmptyWithBrackets", &["empty_structs_with_brackets", "empty_enum_variants_with_brackets"]),
+    ("EndianBytes", &["host_endian_bytes", "little_endian_bytes", "big_endian_bytes"]),
+    ("EnvAccessOutsideConfig", &["env_access_outside_config"]),
+    ("ErrorImplError", &["error_impl_error"]),
+    ("EtaReduction", &["redundant_closure", "redundant_closure_for_method_calls"]),
+    ("EvalOrderDependence", &["mixed_read_write_in_expression", "diverging_sub_expression"]),
+    ("ExcessiveBools", &["struct_excessive_bools", "fn_params_excessive_bools"]),
+    ("ExcessiveNesting", &["excessive_nesting"]),
+    ("ExhaustiveItems", &["exhaustive_enums", "exhaustive_structs"]),
+    ("Exit", &["exit"]),
+    ("ExplicitWrite", &["explicit_write"]),
+    ("ExtraUnusedTypeParameters", &["extra_unused_type_parameters"]),
+    ("FallibleImplFrom", &["fallible_impl_from"]),
+    ("FloatLiteral", &["excessive_precision", "lossy_float_literal"]),
+    ("FloatingPointArithmetic", &["imprecise_flops", "suboptimal_flops"]),
+    ("FormatArgs", &["format_in_format_args", "to_string_in_format_args", "uninlined_format_args", "unused_format_specs"]),
+    ("FormatArgsCollector", &[]),
+    ("FormatImpl", &["recursive_format_impl", "print_in_format_impl"]),
+    ("FormatPushString", &["format_push_string"]),
+    ("Formatting", &["suspicious_assignment_formatting", "suspicious_unary_op_formatting", "suspicious_else_formatting", "possible_missing_comma"]),
+    ("FourForwardSlashes", &["four_forward_slashes"]),
+    ("FromOverInto", &["from_over_into"]),
+    ("FromRawWithVoidPtr", &["from_raw_with_void_ptr"]),
+    ("FromStrRadix10", &["from_str_radix_10"]),
+    ("Functions", &["too_many_arguments", "too_many_lines", "not_unsafe_ptr_arg_deref", "must_use_unit", "double_must_use", "must_use_candidate", "result_unit_err", "result_large_err", "misnamed_getters", "impl_trait_in_params"]),
+    ("FutureNotSend", &["future_not_send"]),
+    ("HashMapPass", &["map_entry"]),
+    ("IfLetMutex", &["if_let_mutex"]),
+    ("IfNotElse", &["if_not_else"]),
+    ("IfThenSomeElseNone", &["if_then_some_else_none"]),
+    ("IgnoredUnitPatterns", &["ignored_unit_patterns"]),
+    ("ImplHashWithBorrowStrBytes", &["impl_hash_borrow_with_str_and_bytes"]),
+    ("ImplicitFieldInit", &["implicit_field_init"]),
+    ("ImplicitHasher", &["implicit_hasher"]),
+    ("ImplicitReturn", &["implicit_return"]),
+    ("ImplicitSaturatingAdd", &["implicit_saturating_add"]),
+    ("ImplicitSaturatingSub", &["implicit_saturating_sub"]),
+    ("ImpliedBoundsInImpls", &["implied_bounds_in_impls"]),
+    ("ImportRename", &["missing_enforced_import_renames"]),
+    ("IncompatibleMsrv", &["incompatible_msrv"]),
+    ("InconsistentBorrow", &["inconsistent_borrow"]),
+    ("InconsistentHashAndEq", &["inconsistent_hash_and_eq", "hash_eq_field_mismatch"]),
+    ("InconsistentStructConstructor", &["inconsistent_struct_constructor"]),
+    ("IndexRefutableSlice", &["index_refutable_slice"]),
+    ("IndexingSlicing", &["indexing_slicing", "out_of_bounds_indexing"]),
+    ("IneffectiveOpenOptions", &["ineffective_open_options"]),
+    ("InfiniteIter", &["infinite_iter", "maybe_infinite_iter"]),
+    ("InherentToString", &["inherent_to_string", "inherent_to_string_shadow_display"]),
+    ("InlineAsmX86AttSyntax", &["inline_asm_x86_att_syntax"]),
+    ("InlineAsmX86IntelSyntax", &["inline_asm_x86_intel_syntax"]),
+    ("InlineFnWithoutBody", &["inline_fn_without_body"]),
+    ("InstantSubtraction", &["manual_instant_elapsed", "unchecked_duration_subtraction", "unguarded_time_subtraction"]),
+    ("IntPlusOne", &["int_plus_one"]),
+    ("IntegerDivisionRemainderUsed", &["integer_division_remainder_used"]),
+    ("InterningDefinedSymbol", &["interning_defined_symbol", "unnecessary_symbol_str"]),
+    ("InvalidPaths", &["invalid_paths"]),
+    ("InvalidUpcastComparisons", &["invalid_upcast_comparisons"]),
+    ("ItemNameRepetitions", &["enum_variant_names", "struct_field_names", "module_name_repetitions", "module_inception"]),
+    ("ItemsAfterStatements", &["items_after_statements"]),
+    ("ItemsAfterTestModule", &["items_after_test_module"]),
+    ("IterNotReturningIterator", &["iter_not_returning_iterator"]),
+    ("IterOverHashType", &["iter_over_hash_type"]),
+    ("IterWithoutIntoIter", &["iter_without_into_iter", "into_iter_without_iter"]),
+    ("LargeConstArrays", &["large_const_arrays"]),
+    ("LargeEnumVariant", &["large_enum_variant"]),
+    ("LargeFuture", &["large_futures"]),
+    ("LargeIncludeFile", &["large_include_file"]),
+    ("LargeStackArrays", &["large_stack_arrays"]),
+    ("LargeStackFrames", &["large_stack_frames"]),
+    ("LegacyNumericConstants", &["legacy_numeric_constants"]),
+    ("LegacyRefPattern", &["legacy_ref_pattern"]),
+    ("LenZero", &["len_zero", "len_without_is_empty", "comparison_to_empty"]),
+    ("LetIfSeq", &["useless_let_if_seq"]),
+    ("LetUnderscore", &["let_underscore_must_use", "let_underscore_lock", "let_underscore_future", "let_underscore_untyped"]),
+    ("Lifetimes", &["needless_lifetimes", "extra_unused_lifetimes"]),
+    ("LinesFilterMapOk", &["lines_filter_map_ok"]),
+    ("LintPass", &["toplevel_ref_arg", "used_underscore_binding", "short_circuit_statement"]),
+    ("LintWithoutLintPass", &["default_lint", "lint_without_lint_pass", "invalid_clippy_version_attribute", "missing_clippy_version_attribute", "default_deprecation_reason"]),
+    ("LiteralDigitGrouping", &["unreadable_literal", "inconsistent_digit_grouping", "large_digit_groups", "mistyped_literal_suffixes", "unusual_byte_groupings"]),
+    ("Loops", &["manual_memcpy", "manual_flatten", "needless_range_loop", "explicit_iter_loop", "explicit_into_iter_loop", "iter_next_loop", "while_let_loop", "explicit_counter_loop", "empty_loop", "while_let_on_iterator", "for_kv_map", "never_loop", "mut_range_bound", "while_immutable_condition", "same_item_push", "single_element_loop", "missing_spin_loop", "manual_find", "manual_while_let_some", "unused_enumerate_index", "infinite_loop"]),
+    ("LossyFrom", &["lossy_from"]),
+    ("MacroBraces", &["nonstandard_macro_braces"]),
+    ("MacroUseImports", &["macro_use_imports"]),
+    ("MainRecursion", &["main_recursion"]),
+    ("ManualAssert", &["manual_assert"]),
+    ("ManualAsyncFn", &["manual_async_fn"]),
+    ("ManualBits", &["manual_bits"]),
+    ("ManualClamp", &["manual_clamp"]),
+    ("ManualFloatMethods", &["manual_is_infinite", "manual_is_finite"]),
+    ("ManualHashOne", &["manual_hash_one"]),
+    ("ManualIsAsciiCheck", &["manual_is_ascii_check"]),
+    ("ManualMainSeparatorStr", &["manual_main_separator_str"]),
+    ("ManualNonExhaustiveEnum", &["manual_non_exhaustive"]),
+    ("ManualNonExhaustiveStruct", &["manual_non_exhaustive"]),
+    ("ManualRangePatterns", &["manual_range_patterns"]),
+    ("ManualRemEuclid", &["manual_rem_euclid"]),
+    ("ManualRetain", &["manual_retain"]),
+    ("ManualSlicePattern", &["manual_slice_pattern"]),
+    ("ManualSliceSizeCalculation", &["manual_slice_size_calculation"]),
+    ("ManualStringNew", &["manual_string_new"]),
+    ("ManualStrip", &["manual_strip"]),
+    ("ManualUnwrapOrDefault", &["manual_unwrap_or_default"]),
+    ("MapUnit", &["option_map_unit_fn", "result_map_unit_fn"]),
+    ("MatchResultOk", &["match_result_ok"]),
+    ("Matches", &["single_match", "match_ref_pats", "match_bool", "single_match_else", "match_overlapping_arm", "match_wild_err_arm", "match_as_ref", "wildcard_enum_match_arm", "match_wildcard_for_single_variants", "wildcard_in_or_patterns", "match_single_binding", "infallible_destructuring_match", "rest_pat_in_fully_bound_structs", "redundant_pattern_matching", "match_like_matches_macro", "match_same_arms", "needless_match", "collapsible_match", "manual_unwrap_or", "match_on_vec_items", "match_str_case_mismatch", "significant_drop_in_scrutinee", "try_err", "manual_map", "manual_filter", "redundant_guards", "match_bool_tuple"]),
+    ("MatchesThenDestructure", &["matches_then_destructure"]),
+    ("MemReplace", &["mem_replace_option_with_none", "mem_replace_with_uninit", "mem_replace_with_default"]),
+    ("MetadataCollector", &["metadata_collector"]),
+    ("Methods", &["unwrap_used", "expect_used", "should_implement_trait", "wrong_self_convention", "ok_expect", "unwrap_or_default", "map_unwrap_or", "result_map_or_into_option", "option_map_or_none", "bind_instead_of_map", "or_fun_call", "or_then_unwrap", "expect_fun_call", "chars_next_cmp", "chars_last_cmp", "clone_on_copy", "clone_on_ref_ptr", "collapsible_str_replace", "const_is_empty", "iter_overeager_cloned", "cloned_instead_of_copied", "flat_map_option", "inefficient_to_string", "new_ret_no_self", "single_char_pattern", "single_char_add_str", "search_is_some", "filter_next", "skip_while_next", "filter_map_identity", "map_identity", "manual_filter_map", "manual_find_map", "option_filter_map", "filter_map_next", "flat_map_identity", "map_flatten", "iterator_step_by_zero", "iter_next_slice", "iter_count", "iter_nth", "iter_nth_zero", "bytes_nth", "iter_skip_next", "get_unwrap", "get_last_with_len", "string_extend_chars", "iter_cloned_collect", "iter_with_drain", "type_id_on_box", "useless_asref", "unnecessary_fold", "unnecessary_filter_map", "unnecessary_find_map", "into_iter_on_ref", "suspicious_map", "uninit_assumed_init", "manual_saturating_arithmetic", "zst_offset", "filetype_is_file", "option_as_ref_deref", "unnecessary_lazy_evaluations", "map_collect_result_unit", "from_iter_instead_of_collect", "inspect_for_each", "implicit_clone", "suspicious_to_owned", "suspicious_splitn", "manual_str_repeat", "extend_with_drain", "manual_split_once", "needless_splitn", "unnecessary_to_owned", "unnecessary_join", "err_expect", "needless_option_as_deref", "is_digit_ascii_radix", "needless_option_take", "no_effect_replace", "obfuscated_if_else", "iter_on_single_items", "iter_on_empty_collections", "naive_bytecount", "bytes_count_to_len", "case_sensitive_file_extension_comparisons", "get_first", "manual_ok_or", "map_clone", "map_err_ignore", "mut_mutex_lock", "nonsensical_open_options", "suspicious_open_options", "path_buf_push_overwrite", "range_zip_with_len", "repeat_once", "stable_sort_primitive", "unit_hash", "read_line_without_trim", "unnecessary_sort_by", "vec_resize_to_zero", "verbose_file_reads", "iter_kv_map", "seek_from_current", "seek_to_start_instead_of_rewind", "needless_collect", "suspicious_command_arg_space", "clear_with_drain", "manual_next_back", "unnecessary_literal_unwrap", "drain_collect", "manual_try_fold", "format_collect", "string_lit_chars_any", "iter_skip_zero", "filter_map_bool_then", "readonly_write_lock", "iter_out_of_bounds", "path_ends_with_ext", "redundant_as_str", "waker_clone_wake", "unnecessary_fallible_conversions", "join_absolute_paths", "option_map_or_err_ok", "result_filter_map", "iter_filter_is_some", "iter_filter_is_ok", "manual_is_variant_and", "str_split_at_newline", "option_as_ref_cloned", "unnecessary_result_map_or_else", "manual_c_str_literals", "unnecessary_get_then_check", "trim_matches_instead_of_strip", "checked_arithmetic_unwrap"]),
+    ("MinIdentChars", &["min_ident_chars"]),
+    ("MinMaxPass", &["min_max"]),
+    ("MiscEarlyLints", &["unneeded_field_pattern", "duplicate_underscore_argument", "double_neg", "mixed_case_hex_literals", "unseparated_literal_suffix", "separated_literal_suffix", "zero_prefixed_literal", "builtin_type_shadow", "redundant_pattern", "unneeded_wildcard_pattern", "redundant_at_rest_pattern"]),
+    ("MissingAssertMessage", &["missing_assert_message"]),
+    ("MissingAssertsForIndexing", &["missing_asserts_for_indexing"]),
+    ("MissingConstForFn", &["missing_const_for_fn", "missing_required_const_fn"]),
+    ("MissingDoc", &["missing_docs_in_private_items"]),
+    ("MissingErrorImpls", &["missing_error_impls"]),
+    ("MissingFieldsInDebug", &["missing_fields_in_debug"]),
+    ("MissingFinalizer", &["missing_finalizer"]),
+    ("MissingInline", &["missing_inline_in_public_items"]),
+    ("MissingTrackCaller", &["missing_track_caller"]),
+    ("MissingTraitMethods", &["missing_trait_methods"]),
+    ("ModStyle", &["mod_module_files", "self_named_module_files"]),
+    ("ModuleLayering", &["module_layering"]),
+    ("MsrvAttrImpl", &["missing_msrv_attr_impl"]),
+    ("MultiAssignments", &["multi_assignments"]),
+    ("MultipleBoundLocations", &["multiple_bound_locations"]),
+    ("MultipleInherentImpl", &["multiple_inherent_impl"]),
+    ("MultipleUnsafeOpsPerBlock", &["multiple_unsafe_ops_per_block"]),
+    ("MustUseTypes", &["must_use_types"]),
+    ("MutMut", &["mut_mut"]),
+    ("MutableKeyType", &["mutable_key_type"]),
+    ("Mutex", &["mutex_atomic", "mutex_integer"]),
+    ("NamingConventions", &["naming_conventions"]),
+    ("NeedlessArbitrarySelfType", &["needless_arbitrary_self_type"]),
+    ("NeedlessBool", &["needless_bool", "needless_bool_assign"]),
+    ("NeedlessBorrowedRef", &["needless_borrowed_reference"]),
+    ("NeedlessBorrowsForGenericArgs", &["needless_borrows_for_generic_args"]),
+    ("NeedlessContinue", &["needless_continue"]),
+    ("NeedlessElse", &["needless_else"]),
+    ("NeedlessForEach", &["needless_for_each"]),
+    ("NeedlessIf", &["needless_if"]),
+    ("NeedlessLateInit", &["needless_late_init"]),
+    ("NeedlessParensOnRangeLiterals", &["needless_parens_on_range_literals"]),
+    ("NeedlessPassByRefMut", &["needless_pass_by_ref_mut"]),
+    ("NeedlessPassByValue", &["needless_pass_by_value"]),
+    ("NeedlessQuestionMark", &["needless_question_mark"]),
+    ("NeedlessUpdate", &["needless_update"]),
+    ("NegMultiply", &["neg_multiply"]),
+    ("NestedMatchPyramid", &["nested_match_pyramid"]),
+    ("NewWithoutDefault", &["new_without_default"]),
+    ("NoEffect", &["no_effect", "unnecessary_operation", "no_effect_underscore_binding"]),
+    ("NoMangleWithRustAbi", &["no_mangle_with_rust_abi"]),
+    ("NoNegCompOpForPartialOrd", &["neg_cmp_op_on_partial_ord"]),
+    ("NonCanonicalImpls", &["non_canonical_clone_impl", "non_canonical_partial_ord_impl"]),
+    ("NonCopyConst", &["declare_interior_mutable_const", "borrow_interior_mutable_const"]),
+    ("NonExpressiveNames", &["similar_names", "many_single_char_names", "just_underscores_and_digits"]),
+    ("NonOctalUnixPermissions", &["non_octal_unix_permissions"]),
+    ("NonSendFieldInSendTy", &["non_send_fields_in_send_ty"]),
+    ("NonminimalBool", &["nonminimal_bool", "overly_complex_bool_expr"]),
+    ("NumberedFields", &["init_numbered_fields"]),
+    ("OctalEscapes", &["octal_escapes"]),
+    ("OnlyUsedInRecursion", &["only_used_in_recursion"]),
+    ("Operators", &["absurd_extreme_comparisons", "arithmetic_side_effects", "float_arithmetic", "assign_op_pattern", "misrefactored_assign_op", "bad_bit_mask", "ineffective_bit_mask", "verbose_bit_mask", "double_comparisons", "impossible_comparisons", "redundant_comparisons", "duration_subsec", "eq_op", "op_ref", "erasing_op", "float_equality_without_abs", "identity_op", "integer_division", "cmp_owned", "float_cmp", "float_cmp_const", "float_cmp_in_guard_or_assert", "modulo_one", "modulo_arithmetic", "needless_bitwise_bool", "ptr_eq", "self_assignment", "shift_amount_overflow"]),
+    ("OptionEnvUnwrap", &["option_env_unwrap"]),
+    ("OptionIfLetElse", &["option_if_let_else"]),
+    ("OuterExpnDataPass", &["outer_expn_expn_data"]),
+    ("OverflowCheckConditional", &["overflow_check_conditional"]),
+    ("PanicInProduction", &["panic_in_production"]),
+    ("PanicInResultFn", &["panic_in_result_fn"]),
+    ("PanicUnimplemented", &["unimplemented", "unreachable", "todo", "panic"]),
+    ("PanickingIteratorNext", &["panicking_iterator_next"]),
+    ("PartialEqNeImpl", &["partialeq_ne_impl"]),
+    ("PartialPubFields", &["partial_pub_fields"]),
+    ("PartialeqToNone", &["partialeq_to_none"]),
+    ("PassByRefOrValue", &["trivially_copy_pass_by_ref", "large_types_passed_by_value"]),
+    ("PatternEquality", &["equatable_if_let"]),
+    ("PatternTypeMismatch", &["pattern_type_mismatch"]),
+    ("PermissionsSetReadonlyFalse", &["permissions_set_readonly_false"]),
+    ("Precedence", &["precedence"]),
+    ("ProduceIce", &["produce_ice"]),
+    ("Ptr", &["ptr_arg", "cmp_null", "mut_from_ref", "invalid_null_ptr_usage", "owned_param_only_borrowed"]),
+    ("PtrOffsetWithCast", &["ptr_offset_with_cast"]),
+    ("PubUnderscoreFields", &["pub_underscore_fields"]),
+    ("PubUse", &["pub_use"]),
+    ("QuestionMark", &["question_mark", "manual_let_else"]),
+    ("QuestionMarkUsed", &["question_mark_used"]),
+    ("Ranges", &["range_plus_one", "range_minus_one", "reversed_empty_ranges", "manual_range_contains"]),
+    ("RawStrings", &["needless_raw_strings", "needless_raw_string_hashes"]),
+    ("RcCloneInVecInit", &["rc_clone_in_vec_init"]),
+    ("ReadZeroByteVec", &["read_zero_byte_vec"]),
+    ("Recursion", &["recursion"]),
+    ("RedundantAsyncBlock", &["redundant_async_block"]),
+    ("RedundantClone", &["redundant_clone"]),
+    ("RedundantClosureCall", &["redundant_closure_call"]),
+    ("RedundantElse", &["redundant_else"]),
+    ("RedundantFieldNames", &["redundant_field_names"]),
+    ("RedundantLocals", &["redundant_locals"]),
+    ("RedundantPubCrate", &["redundant_pub_crate"]),
+    ("RedundantSlicing", &["redundant_slicing", "deref_by_slicing"]),
+    ("RedundantStaticLifetimes", &["redundant_static_lifetimes"]),
+    ("RedundantTypeAnnotations", &["redundant_type_annotations"]),
+    ("RefOptionRef", &["ref_option_ref"]),
+    ("RefPatterns", &["ref_patterns"]),
+    ("Regex", &["invalid_regex", "trivial_regex"]),
+    ("RepeatVecWithCapacity", &["repeat_vec_with_capacity"]),
+    ("ReserveAfterInitialization", &["reserve_after_initialization"]),
+    ("Return", &["let_and_return", "needless_return", "needless_return_with_question_mark"]),
+    ("ReturnSelfNotMustUse", &["return_self_not_must_use"]),
+    ("ReturnedVecOnlyIterated", &["returned_vec_only_iterated"]),
+    ("SameNameMethod", &["same_name_method"]),
+    ("SelfNamedConstructors", &["self_named_constructors"]),
+    ("SemicolonBlock", &["semicolon_inside_block", "semicolon_outside_block"]),
+    ("SemicolonIfNothingReturned", &["semicolon_if_nothing_returned"]),
+    ("SerdeApi", &["serde_api_misuse"]),
+    ("Shadow", &["shadow_same", "shadow_reuse", "shadow_unrelated"]),
+    ("SignificantDropTightening", &["significant_drop_tightening"]),
+    ("SingleCallFn", &["single_call_fn"]),
+    ("SingleCharLifetimeNames", &["single_char_lifetime_names"]),
+    ("SingleComponentPathImports", &["single_component_path_imports"]),
+    ("SingleRangeInVecInit", &["single_range_in_vec_init"]),
+    ("SizeOfInElementCount", &["size_of_in_element_count"]),
+    ("SizeOfRef", &["size_of_ref"]),
+    ("SkippedPass", &[]),
+    ("SlowVectorInit", &["slow_vector_initialization"]),
+    ("StdReexports", &["std_instead_of_core", "std_instead_of_alloc", "alloc_instead_of_core"]),
+    ("StrToString", &["str_to_string"]),
+    ("StringAdd", &["string_add", "string_add_assign", "string_slice"]),
+    ("StringLitAsBytes", &["string_lit_as_bytes", "string_from_utf8_as_bytes"]),
+    ("StringToString", &["string_to_string"]),
+    ("StrlenOnCStrings", &["strlen_on_c_strings"]),
+    ("SuspiciousImpl", &["suspicious_arithmetic_impl", "suspicious_op_assign_impl"]),
+    ("SuspiciousOperationGroupings", &["suspicious_operation_groupings"]),
+    ("Swap", &["manual_swap", "almost_swapped"]),
+    ("SwapPtrToRef", &["swap_ptr_to_ref"]),
+    ("TabsInDocComments", &["tabs_in_doc_comments"]),
+    ("TemporaryAssignment", &["temporary_assignment"]),
+    ("TestsOutsideTestModule", &["tests_outside_test_module"]),
+    ("ThreadLocalInitializerCanBeMadeConst", &["thread_local_initializer_can_be_made_const"]),
+    ("ToDigitIsSome", &["to_digit_is_some"]),
+    ("ToStringTraitImpl", &["to_string_trait_impl"]),
+    ("TrailingEmptyArray", &["trailing_empty_array"]),
+    ("TraitBounds", &["type_repetition_in_bounds", "trait_duplication_in_bounds"]),
+    ("Transmute", &["crosspointer_transmute", "transmute_ptr_to_ref", "transmute_ptr_to_ptr", "useless_transmute", "wrong_transmute", "transmute_int_to_char", "transmute_bytes_to_str", "transmute_int_to_bool", "transmute_int_to_float", "transmute_int_to_non_zero", "transmute_float_to_int", "transmute_num_to_bytes", "unsound_collection_transmute", "transmutes_expressible_as_ptr_casts", "transmute_undefined_repr", "transmuting_null", "transmute_null_to_fn", "eager_transmute", "missing_transmute_annotations"]),
+    ("TrimSplitWhitespace", &["trim_split_whitespace"]),
+    ("TupleArrayConversions", &["tuple_array_conversions"]),
+    ("TypeParamMismatch", &["mismatching_type_param_order"]),
+    ("Types", &["box_collection", "vec_box", "option_option", "linkedlist", "borrowed_box", "redundant_allocation", "rc_buffer", "rc_mutex", "type_complexity"]),
+    ("UnconditionalRecursion", &["unconditional_recursion"]),
+    ("UnderscoreTyped", &["let_with_type_underscore"]),
+    ("UndocumentedUnsafeBlocks", &["undocumented_unsafe_blocks", "unnecessary_safety_comment"]),
+    ("UnguardedSpanLint", &["unguarded_span_lint"]),
+    ("Unicode", &["invisible_characters", "non_ascii_literal", "unicode_not_nfc"]),
+    ("UninhabitedReferences", &["uninhabited_references"]),
+    ("UninitVec", &["uninit_vec"]),
+    ("UnitReturnExpectingOrd", &["unit_return_expecting_ord"]),
+    ("UnitTypes", &["let_unit_value", "unit_cmp", "unit_arg"]),
+    ("UnnamedAddress", &["fn_address_comparisons"]),
+    ("UnnecessaryBoxReturns", &["unnecessary_box_returns"]),
+    ("UnnecessaryDefPath", &["unnecessary_def_path"]),
+    ("UnnecessaryMapOnConstructor", &["unnecessary_map_on_constructor"]),
+    ("UnnecessaryMutPassed", &["unnecessary_mut_passed"]),
+    ("UnnecessaryOwnedEmptyStrings", &["unnecessary_owned_empty_strings"]),
+    ("UnnecessarySelfImports", &["unnecessary_self_imports"]),
+    ("UnnecessaryStruct", &["unnecessary_struct_initialization"]),
+    ("UnnecessaryWraps", &["unnecessary_wraps"]),
+    ("UnnestedOrPatterns", &["unnested_or_patterns"]),
+    ("UnportableVariant", &["enum_clike_unportable_variant"]),
+    ("UnsafeNameRemoval", &["unsafe_removed_from_name"]),
+    ("UnsortedClippyUtilsPaths", &["unsorted_clippy_utils_paths"]),
+    ("UnusedAsync", &["unused_async"]),
+    ("UnusedIoAmount", &["unused_io_amount"]),
+    ("UnusedPeekable", &["unused_peekable"]),
+    ("UnusedRounding", &["unused_rounding"]),
+    ("UnusedSelf", &["unused_self"]),
+    ("UnusedTraitBound", &["unused_trait_bound"]),
+    ("UnusedUnit", &["unused_unit"]),
+    ("Unwrap", &["panicking_unwrap", "unnecessary_unwrap"]),
+    ("UnwrapInResult", &["unwrap_in_result"]),
+    ("UpperCaseAcronyms", &["upper_case_acronyms"]),
+    ("UseSelf", &["use_self"]),
+    ("UselessConversion", &["useless_conversion"]),
+    ("UselessFormat", &["useless_format"]),
+    ("UselessVec", &["useless_vec"]),
+    ("VecInitThenPush", &["vec_init_then_push"]),
+    ("Visibility", &["needless_pub_self", "pub_with_shorthand", "pub_without_shorthand"]),
+    ("WildcardImports", &["enum_glob_use", "wildcard_imports"]),
+    ("Write", &["print_with_newline", "println_empty_string", "print_stdout", "print_stderr", "print_in_library", "use_debug", "print_literal", "write_with_newline", "writeln_empty_string", "write_literal"]),
+    ("ZeroDiv", &["zero_divided_by_zero"]),
+    ("ZeroRepeatSideEffects", &["zero_repeat_side_effects"]),
+    ("ZeroSizedMapValues", &["zero_sized_map_values"]),
+];
+
+/// The late passes without state, which can run on each module separately.
+pub(crate) static STATELESS_PASSES: &[&str] = &[
+    "AllowAttribute",
+    "ArbitraryStrSlice",
+    "ArcWithNonSendSync",
+    "AsConversions",
+    "AssertionsOnConstants",
+    "AssertionsOnResultStates",
+    "AsyncYieldsAsync",
+    "Author",
+    "BlocksInConditions",
+    "BoolAssertComparison",
+    "BoolComparison",
+    "BoolToIntWithIf",
+    "BorrowDerefRef",
+    "BoxDefault",
+    "ClosedDynTrait",
+    "CollapsibleCalls",
+    "CollectionIsNeverRead",
+    "ComparisonChain",
+    "ConfusingXorAndPow",
+    "CopyIterator",
+    "CrateWalkInNodeCheck",
+    "CreateDir",
+    "DebugAssertWithMutCall",
+    "DefaultConstructedUnitStructs",
+    "DefaultIterEmpty",
+    "DefaultNumericFallback",
+    "DefaultUnionRepresentation",
+    "Derive",
+    "DroppedErrorSource",
+    "EmptyDrop",
+    "EmptyEnum",
+    "EndianBytes",
+    "ErrorImplError",
+    "EtaReduction",
+    "EvalOrderDependence",
+    "ExhaustiveItems",
+    "Exit",
+    "FallibleImplFrom",
+    "FloatLiteral",
+    "FloatingPointArithmetic",
+    "FormatPushString",
+    "FourForwardSlashes",
+    "FromRawWithVoidPtr",
+    "FromStrRadix10",
+    "FutureNotSend",
+    "HashMapPass",
+    "IfLetMutex",
+    "IfNotElse",
+    "IgnoredUnitPatterns",
+    "ImplHashWithBorrowStrBytes",
+    "ImplicitHasher",
+    "ImplicitReturn",
+    "ImplicitSaturatingAdd",
+    "ImplicitSaturatingSub",
+    "ImpliedBoundsInImpls",
+    "InconsistentBorrow",
+    "InconsistentHashAndEq",
+    "InconsistentStructConstructor",
+    "IneffectiveOpenOptions",
+    "InfiniteIter",
+    "InherentToString",
+    "InlineFnWithoutBody",
+    "IntegerDivisionRemainderUsed",
+    "InvalidPaths",
+    "InvalidUpcastComparisons",
+    "ItemsAfterStatements",
+    "ItemsAfterTestModule",
+    "IterNotReturningIterator",
+    "IterOverHashType",
+    "IterWithoutIntoIter",
+    "LegacyRefPattern",
+    "LenZero",
+    "LetIfSeq",
+    "Lifetimes",
+    "LinesFilterMapOk",
+    "LintPass",
+    "LossyFrom",
+    "ManualAssert",
+    "ManualAsyncFn",
+    "ManualFloatMethods",
+    "ManualRangePatterns",
+    "ManualSliceSizeCalculation",
+    "ManualStringNew",
+    "ManualUnwrapOrDefault",
+    "MapUnit",
+    "MatchResultOk",
+    "MatchesThenDestructure",
+    "MinMaxPass",
+    "MissingAssertMessage",
+    "MissingAssertsForIndexing",
+    "MissingFieldsInDebug",
+    "MissingInline",
+    "MissingTrackCaller",
+    "MissingTraitMethods",
+    "MsrvAttrImpl",
+    "MultipleUnsafeOpsPerBlock",
+    "MutMut",
+    "Mutex",
+    "NeedlessBool",
+    "NeedlessBorrowedRef",
+    "NeedlessForEach",
+    "NeedlessIf",
+    "NeedlessLateInit",
+    "NeedlessParensOnRangeLiterals",
+    "NeedlessPassByValue",
+    "NeedlessQuestionMark",
+    "NeedlessUpdate",
+    "NegMultiply",
+    "NoMangleWithRustAbi",
+    "NoNegCompOpForPartialOrd",
+    "NonCanonicalImpls",
+    "NonOctalUnixPermissions",
+    "NonminimalBool",
+    "NumberedFields",
+    "OptionIfLetElse",
+    "OuterExpnDataPass",
+    "OverflowCheckConditional",
+    "PanicInResultFn",
+    "PanicUnimplemented",
+    "PanickingIteratorNext",
+    "PartialEqNeImpl",
+    "PartialeqToNone",
+    "PatternEquality",
+    "PatternTypeMismatch",
+    "PermissionsSetReadonlyFalse",
+    "Ptr",
+    "PtrOffsetWithCast",
+    "QuestionMarkUsed",
+    "RcCloneInVecInit",
+    "ReadZeroByteVec",
+    "RedundantAsyncBlock",
+    "RedundantClone",
+    "RedundantClosureCall",
+    "RedundantLocals",
+    "RedundantSlicing",
+    "RedundantTypeAnnotations",
+    "RefOptionRef",
+    "RepeatVecWithCapacity",
+    "Return",
+    "ReturnSelfNotMustUse",
+    "ReturnedVecOnlyIterated",
+    "SelfNamedConstructors",
+    "SemicolonIfNothingReturned",
+    "SerdeApi",
+    "SingleRangeInVecInit",
+    "SizeOfInElementCount",
+    "SizeOfRef",
+    "SlowVectorInit",
+    "StrToString",
+    "StringAdd",
+    "StringLitAsBytes",
+    "StringToString",
+    "StrlenOnCStrings",
+    "SuspiciousImpl",
+    "Swap",
+    "SwapPtrToRef",
+    "TemporaryAssignment",
+    "TestsOutsideTestModule",
+    "ToDigitIsSome",
+    "ToStringTraitImpl",
+    "TrailingEmptyArray",
+    "TrimSplitWhitespace",
+    "TypeParamMismatch",
+    "UnconditionalRecursion",
+    "UnderscoreTyped",
+    "UnguardedSpanLint",
+    "Unicode",
+    "UninhabitedReferences",
+    "UninitVec",
+    "UnitReturnExpectingOrd",
+    "UnitTypes",
+    "UnnamedAddress",
+    "UnnecessaryMapOnConstructor",
+    "UnnecessaryMutPassed",
+    "UnnecessaryOwnedEmptyStrings",
+    "UnnecessaryStruct",
+    "UnportableVariant",
+    "UnusedIoAmount",
+    "UnusedPeekable",
+    "UnusedTraitBound",
+    "Unwrap",
+    "UnwrapInResult",
+    "ZeroDiv",
+    "ZeroRepeatSideEffects",
+    "ZeroSizedMapValues",
+];
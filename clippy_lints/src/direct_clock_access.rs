@@ -0,0 +1,126 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::{fn_def_id, get_parent_expr, local_def_path_def_ids, path_def_id};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::impl_lint_pass;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for reads of the clock, by `std::time::Instant::now`, `std::time::SystemTime::now`
+    /// or the functions configured in `clock-functions`, e.g. `chrono::Utc::now`, outside of the
+    /// modules configured in `clock-modules`.
+    ///
+    /// ### Why is this bad?
+    /// Code reading the clock directly can't be tested with a fixed or simulated time, e.g. to
+    /// check what happens when a token expires. Reading it through an abstraction, which tests can
+    /// replace, keeps the time injectable.
+    ///
+    /// ### Example
+    /// ```toml
+    /// clock-modules = ["crate::time"]
+    /// clock-abstraction = "crate::time::Clock"
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// fn is_expired(token: &Token) -> bool {
+    ///     token.expires_at < std::time::SystemTime::now()
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// fn is_expired(token: &Token, clock: &impl crate::time::Clock) -> bool {
+    ///     token.expires_at < clock.now()
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub DIRECT_CLOCK_ACCESS,
+    restriction,
+    "read of the clock outside of the time source abstraction"
+}
+
+impl_lint_pass!(DirectClockAccess => [DIRECT_CLOCK_ACCESS]);
+
+pub struct DirectClockAccess {
+    functions: Vec<String>,
+    modules: Vec<String>,
+    abstraction: Option<String>,
+    /// The `DefId`s of `functions`, with their index.
+    function_ids: FxHashMap<DefId, usize>,
+    /// The `DefId`s of `modules`.
+    module_ids: Vec<DefId>,
+}
+
+impl DirectClockAccess {
+    pub fn new(functions: Vec<String>, modules: Vec<String>, abstraction: Option<String>) -> Self {
+        Self {
+            functions,
+            modules,
+            abstraction,
+            function_ids: FxHashMap::default(),
+            module_ids: Vec::new(),
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for DirectClockAccess {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for (index, function) in self.functions.iter().enumerate() {
+            for id in local_def_path_def_ids(cx, function) {
+                self.function_ids.insert(id, index);
+            }
+        }
+        self.module_ids = self
+            .modules
+            .iter()
+            .flat_map(|module| local_def_path_def_ids(cx, module))
+            .collect();
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        // The path of a call is checked with the call.
+        let uncalled_path = if let Some(parent) = get_parent_expr(cx, expr)
+            && let ExprKind::Call(callee, _) = parent.kind
+            && callee.hir_id == expr.hir_id
+        {
+            None
+        } else {
+            path_def_id(cx, expr)
+        };
+        let Some(&index) = uncalled_path
+            .or_else(|| fn_def_id(cx, expr))
+            .and_then(|def_id| self.function_ids.get(&def_id))
+        else {
+            return;
+        };
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+        let module = cx.tcx.parent_module(expr.hir_id).to_def_id();
+        if self
+            .module_ids
+            .iter()
+            .any(|&allowed| cx.tcx.is_descendant_of(module, allowed))
+        {
+            return;
+        }
+        let help = if let Some(abstraction) = &self.abstraction {
+            format!("use `{abstraction}` instead")
+        } else if !self.modules.is_empty() {
+            format!("read the clock in `{}`", self.modules.join("`, `"))
+        } else {
+            String::from("read the clock through an abstraction which tests can replace")
+        };
+        span_lint_and_help(
+            cx,
+            DIRECT_CLOCK_ACCESS,
+            expr.span,
+            format!("direct read of the clock with `{}`", self.functions[index]),
+            None,
+            help,
+        );
+    }
+}
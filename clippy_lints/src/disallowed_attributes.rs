@@ -0,0 +1,226 @@
+use clippy_config::types::DisallowedAttribute;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::{def_path_def_ids, local_def_path_def_ids};
+use rustc_ast::{ast, AttrKind};
+use rustc_ast_pretty::pprust;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{HirId, ImplItem, Item, TraitItem};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::impl_lint_pass;
+use rustc_span::hygiene::{ExpnId, ExpnKind, MacroKind};
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Denies the configured attributes in clippy.toml, including derive and attribute
+    /// macros.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// attributes are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Some attributes are undesirable in certain contexts, e.g. `#[inline(always)]` which
+    /// should be justified by a benchmark, `#[no_mangle]` outside of the FFI layer, or a
+    /// derive macro which a codebase is migrating away from.
+    ///
+    /// ### Example
+    /// An example clippy.toml configuration:
+    /// ```toml
+    /// # clippy.toml
+    /// disallowed-attributes = [
+    ///     # Arguments only match if the attribute uses one of them.
+    ///     "inline(always)",
+    ///     # Derive and attribute macros are written with their fully qualified path.
+    ///     { attribute = "derive(serde::Deserialize)", reason = "use `crate::codec` instead" },
+    ///     # The attribute is only disallowed inside of the `scope` module.
+    ///     { attribute = "no_mangle", scope = "crate::domain" },
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// #[inline(always)]
+    /// fn hash(key: &[u8]) -> u64 { .. }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// #[inline]
+    /// fn hash(key: &[u8]) -> u64 { .. }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub DISALLOWED_ATTRIBUTES,
+    style,
+    "use of a disallowed attribute"
+}
+
+/// A configured attribute, parsed from `path` or `path(arg, ..)`.
+struct Matcher {
+    path: Vec<String>,
+    /// The arguments of the attribute, with the whitespace removed.
+    args: Vec<String>,
+    /// The derive or attribute macro which the path resolves to.
+    macro_ids: Vec<DefId>,
+    macro_kind: MacroKind,
+    scope: Option<Vec<DefId>>,
+}
+
+impl Matcher {
+    fn new(attribute: &str) -> Self {
+        let (name, args) = match attribute.split_once('(') {
+            Some((name, args)) => {
+                let args = args.strip_suffix(')').unwrap_or(args);
+                (
+                    name,
+                    args.split(',').map(normalize).filter(|arg| !arg.is_empty()).collect(),
+                )
+            },
+            None => (attribute, Vec::new()),
+        };
+        let (name, args, macro_kind) = match (name.trim(), args) {
+            ("derive", args) => (
+                args.into_iter().next().unwrap_or_default(),
+                Vec::new(),
+                MacroKind::Derive,
+            ),
+            (name, args) => (normalize(name), args, MacroKind::Attr),
+        };
+        Self {
+            path: name.split("::").map(String::from).collect(),
+            args,
+            macro_ids: Vec::new(),
+            macro_kind,
+            scope: None,
+        }
+    }
+
+    fn matches_attr(&self, attr: &ast::Attribute) -> bool {
+        let AttrKind::Normal(normal) = &attr.kind else {
+            return false;
+        };
+        let segments = &normal.item.path.segments;
+        self.macro_kind == MacroKind::Attr
+            && segments.len() == self.path.len()
+            && segments
+                .iter()
+                .zip(&self.path)
+                .all(|(segment, name)| segment.ident.name.as_str() == name)
+            && (self.args.is_empty()
+                || attr.meta_item_list().is_some_and(|items| {
+                    items
+                        .iter()
+                        .any(|item| self.args.contains(&normalize(&pprust::meta_list_item_to_string(item))))
+                }))
+    }
+
+    fn matches_macro(&self, kind: MacroKind, name: &str, def_id: DefId) -> bool {
+        kind == self.macro_kind && (self.macro_ids.contains(&def_id) || matches!(&*self.path, [path] if path == name))
+    }
+}
+
+fn normalize(s: &str) -> String {
+    s.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+pub struct DisallowedAttributes {
+    conf_disallowed: Vec<DisallowedAttribute>,
+    matchers: Vec<Matcher>,
+    /// The expansions of derive and attribute macros which were already checked.
+    seen_expansions: FxHashSet<ExpnId>,
+}
+
+impl DisallowedAttributes {
+    pub fn new(conf_disallowed: Vec<DisallowedAttribute>) -> Self {
+        Self {
+            matchers: conf_disallowed
+                .iter()
+                .map(|conf| Matcher::new(conf.attribute()))
+                .collect(),
+            conf_disallowed,
+            seen_expansions: FxHashSet::default(),
+        }
+    }
+
+    fn is_in_scope(cx: &LateContext<'_>, matcher: &Matcher, hir_id: HirId) -> bool {
+        matcher.scope.as_ref().map_or(true, |scope| {
+            scope
+                .iter()
+                .any(|&module| cx.tcx.is_descendant_of(hir_id.owner.to_def_id(), module))
+        })
+    }
+
+    fn emit(&self, cx: &LateContext<'_>, index: usize, span: Span) {
+        let conf = &self.conf_disallowed[index];
+        span_lint_and_then(
+            cx,
+            DISALLOWED_ATTRIBUTES,
+            span,
+            format!("use of a disallowed attribute `{}`", conf.attribute()),
+            |diag| {
+                if let Some(reason) = conf.reason() {
+                    diag.note(reason);
+                }
+            },
+        );
+    }
+
+    /// Checks the item `hir_id` if it was generated by a derive or attribute macro.
+    fn check_expansion(&mut self, cx: &LateContext<'_>, hir_id: HirId, span: Span) {
+        let expn_id = span.ctxt().outer_expn();
+        let expn_data = expn_id.expn_data();
+        if let ExpnKind::Macro(kind @ (MacroKind::Derive | MacroKind::Attr), name) = expn_data.kind
+            && let Some(def_id) = expn_data.macro_def_id
+            && !in_external_macro(cx.sess(), expn_data.call_site)
+            && self.seen_expansions.insert(expn_id)
+            && let Some(index) = self.matchers.iter().position(|matcher| {
+                matcher.matches_macro(kind, name.as_str(), def_id) && Self::is_in_scope(cx, matcher, hir_id)
+            })
+        {
+            self.emit(cx, index, expn_data.call_site);
+        }
+    }
+}
+
+impl_lint_pass!(DisallowedAttributes => [DISALLOWED_ATTRIBUTES]);
+
+impl<'tcx> LateLintPass<'tcx> for DisallowedAttributes {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for (matcher, conf) in self.matchers.iter_mut().zip(&self.conf_disallowed) {
+            let segs: Vec<_> = matcher.path.iter().map(String::as_str).collect();
+            matcher.macro_ids = def_path_def_ids(cx, &segs).collect();
+            matcher.scope = conf.scope().map(|scope| local_def_path_def_ids(cx, scope));
+        }
+    }
+
+    fn check_attribute(&mut self, cx: &LateContext<'tcx>, attr: &'tcx ast::Attribute) {
+        if self.matchers.is_empty() || in_external_macro(cx.sess(), attr.span) {
+            return;
+        }
+        let hir_id = cx.last_node_with_lint_attrs;
+        if let Some(index) = self
+            .matchers
+            .iter()
+            .position(|matcher| matcher.matches_attr(attr) && Self::is_in_scope(cx, matcher, hir_id))
+        {
+            self.emit(cx, index, attr.span);
+        }
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if !self.matchers.is_empty() {
+            self.check_expansion(cx, item.hir_id(), item.span);
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx ImplItem<'tcx>) {
+        if !self.matchers.is_empty() {
+            self.check_expansion(cx, item.hir_id(), item.span);
+        }
+    }
+
+    fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx TraitItem<'tcx>) {
+        if !self.matchers.is_empty() {
+            self.check_expansion(cx, item.hir_id(), item.span);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use clippy_config::types::DisallowedEnvVar;
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::macros::macro_backtrace;
+use clippy_utils::source::snippet_opt;
+use clippy_utils::str_utils::matches_wildcard;
+use clippy_utils::{fn_def_id, local_def_path_def_ids, match_def_path, paths};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind, HirId};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::impl_lint_pass;
+use rustc_span::{ExpnId, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for reads of the environment variables configured in clippy.toml outside of the
+    /// modules which are allowed to read them, by `std::env::var`, `std::env::var_os`, `env!`
+    /// or `option_env!`.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// environment variables are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Reading secrets or settings all over a codebase makes it hard to know which variables
+    /// a program depends on, and easy to leak them. Keeping the reads in one module, e.g. the
+    /// one loading the configuration, avoids this.
+    ///
+    /// ### Known problems
+    /// The name of the variable is only known if it is a literal or a constant.
+    ///
+    /// ### Example
+    /// ```toml
+    /// disallowed-env-vars = [{ name = "SECRET_KEY", allowed-in = ["crate::config"] }]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// mod handlers {
+    ///     fn sign(body: &[u8]) -> Signature {
+    ///         let key = std::env::var("SECRET_KEY").unwrap();
+    ///         // ..
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// mod handlers {
+    ///     fn sign(config: &crate::config::Config, body: &[u8]) -> Signature {
+    ///         let key = &config.secret_key;
+    ///         // ..
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub DISALLOWED_ENV_VARS,
+    style,
+    "read of an environment variable outside of the modules which may read it as per the configuration"
+}
+
+impl_lint_pass!(DisallowedEnvVars => [DISALLOWED_ENV_VARS]);
+
+pub struct DisallowedEnvVars {
+    conf: Vec<DisallowedEnvVar>,
+    /// The `DefId`s of the `allowed-in` modules of each entry of `conf`.
+    allowed_in: Vec<Vec<DefId>>,
+    /// The expansions of `env!` and `option_env!` which were already checked.
+    seen_expansions: FxHashSet<ExpnId>,
+}
+
+impl DisallowedEnvVars {
+    pub fn new(conf: Vec<DisallowedEnvVar>) -> Self {
+        Self {
+            conf,
+            allowed_in: Vec::new(),
+            seen_expansions: FxHashSet::default(),
+        }
+    }
+
+    fn check_var(&self, cx: &LateContext<'_>, hir_id: HirId, span: Span, name: &str) {
+        let module = cx.tcx.parent_module(hir_id).to_def_id();
+        let Some((conf, _)) = self.conf.iter().zip(&self.allowed_in).find(|(conf, allowed_in)| {
+            matches_wildcard(&conf.name, name)
+                && !allowed_in
+                    .iter()
+                    .any(|&allowed| cx.tcx.is_descendant_of(module, allowed))
+        }) else {
+            return;
+        };
+        span_lint_and_then(
+            cx,
+            DISALLOWED_ENV_VARS,
+            span,
+            format!("read of the environment variable `{name}`"),
+            |diag| {
+                if let Some(reason) = conf.reason() {
+                    diag.note(reason);
+                }
+                if !conf.allowed_in.is_empty() {
+                    diag.help(format!("it may only be read in `{}`", conf.allowed_in.join("`, `")));
+                }
+            },
+        );
+    }
+}
+
+/// Returns the contents of the first string literal of the `env!` or `option_env!` call.
+pub(crate) fn macro_var_name(call: &str) -> Option<&str> {
+    let (_, rest) = call.split_once('"')?;
+    let (name, _) = rest.split_once('"')?;
+    Some(name)
+}
+
+impl<'tcx> LateLintPass<'tcx> for DisallowedEnvVars {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        self.allowed_in = self
+            .conf
+            .iter()
+            .map(|conf| {
+                conf.allowed_in
+                    .iter()
+                    .flat_map(|module| local_def_path_def_ids(cx, module))
+                    .collect()
+            })
+            .collect();
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if self.conf.is_empty() {
+            return;
+        }
+        if let Some(mac) = macro_backtrace(expr.span).find(|mac| {
+            cx.tcx
+                .get_diagnostic_name(mac.def_id)
+                .is_some_and(|name| name == sym!(env_macro) || name == sym!(option_env_macro))
+        }) {
+            if self.seen_expansions.insert(mac.expn)
+                && !in_external_macro(cx.sess(), mac.span)
+                && let Some(call) = snippet_opt(cx, mac.span)
+                && let Some(name) = macro_var_name(&call)
+            {
+                self.check_var(cx, expr.hir_id, mac.span, name);
+            }
+        } else if let ExprKind::Call(_, [arg]) = expr.kind
+            && let Some(def_id) = fn_def_id(cx, expr)
+            && (match_def_path(cx, def_id, &paths::ENV_VAR) || match_def_path(cx, def_id, &paths::ENV_VAR_OS))
+            && !in_external_macro(cx.sess(), expr.span)
+            && let Some(Constant::Str(name)) = constant(cx, cx.typeck_results(), arg)
+        {
+            self.check_var(cx, expr.hir_id, expr.span, &name);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use clippy_config::types::DisallowedPath;
+use clippy_utils::def_path_def_ids;
+use clippy_utils::diagnostics::span_lint_and_then;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def::Res;
+use rustc_hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_hir::{Item, ItemKind, UseKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::impl_lint_pass;
+use std::iter;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Denies importing the configured modules, crates or items in clippy.toml.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// imports are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Some modules are undesirable in certain contexts, e.g. a deprecated internal
+    /// module that a codebase is migrating away from. Unlike `disallowed_types`, this
+    /// lint fires once on the import instead of on every usage.
+    ///
+    /// ### Example:
+    /// An example clippy.toml configuration:
+    /// ```toml
+    /// # clippy.toml
+    /// disallowed-imports = [
+    ///     # A path ending in `::*` disallows the module and everything inside of it.
+    ///     "openssl::*",
+    ///     # Paths can start with `crate`, and have a `reason` when using an inline table.
+    ///     { path = "crate::legacy::*", reason = "use `crate::storage` instead" },
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// use openssl::ssl::SslConnector;
+    /// use crate::legacy::Store;
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// use rustls::ClientConfig;
+    /// use crate::storage::Store;
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub DISALLOWED_IMPORTS,
+    style,
+    "use of disallowed imports"
+}
+
+pub struct DisallowedImports {
+    conf_disallowed: Vec<DisallowedPath>,
+    /// Maps the `DefId` of a disallowed path to its index in `conf_disallowed`, and whether the
+    /// items inside of it are disallowed too.
+    def_ids: FxHashMap<DefId, (usize, bool)>,
+}
+
+impl DisallowedImports {
+    pub fn new(conf_disallowed: Vec<DisallowedPath>) -> Self {
+        Self {
+            conf_disallowed,
+            def_ids: FxHashMap::default(),
+        }
+    }
+
+    /// Returns the index of the configuration disallowing `def_id`. Only the modules ending in
+    /// `::*` are considered if `exact` is false.
+    fn find(&self, cx: &LateContext<'_>, def_id: DefId, exact: bool) -> Option<usize> {
+        if let Some(&(index, glob)) = self.def_ids.get(&def_id)
+            && (exact || glob)
+        {
+            return Some(index);
+        }
+        iter::successors(cx.tcx.opt_parent(def_id), |&id| cx.tcx.opt_parent(id)).find_map(|id| {
+            match self.def_ids.get(&id) {
+                Some(&(index, true)) => Some(index),
+                _ => None,
+            }
+        })
+    }
+}
+
+impl_lint_pass!(DisallowedImports => [DISALLOWED_IMPORTS]);
+
+impl<'tcx> LateLintPass<'tcx> for DisallowedImports {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        let local_crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+        for (index, conf) in self.conf_disallowed.iter().enumerate() {
+            let (path, glob) = match conf.path().strip_suffix("::*") {
+                Some(path) => (path, true),
+                None => (conf.path(), false),
+            };
+            let mut segs: Vec<_> = path.split("::").collect();
+            if segs[0] == "crate" {
+                segs[0] = local_crate_name.as_str();
+            }
+            let ids: Vec<_> = if let [name] = *segs {
+                // `def_path_res` resolves single segments to primitive types, look for a crate instead.
+                cx.tcx
+                    .crates(())
+                    .iter()
+                    .copied()
+                    .chain(iter::once(LOCAL_CRATE))
+                    .filter(|&num| cx.tcx.crate_name(num).as_str() == name)
+                    .map(CrateNum::as_def_id)
+                    .collect()
+            } else {
+                def_path_def_ids(cx, &segs).collect()
+            };
+            for id in ids {
+                self.def_ids
+                    .entry(id)
+                    .and_modify(|entry| {
+                        if glob {
+                            *entry = (index, true);
+                        }
+                    })
+                    .or_insert((index, glob));
+            }
+        }
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if self.def_ids.is_empty() || in_external_macro(cx.sess(), item.span) {
+            return;
+        }
+        let index = match &item.kind {
+            ItemKind::Use(path, UseKind::Single | UseKind::Glob) => {
+                // The prefix of the path may name a disallowed module even if the imported item was
+                // re-exported from elsewhere.
+                let [prefix @ .., _] = path.segments else {
+                    return;
+                };
+                path.res
+                    .iter()
+                    .filter_map(Res::opt_def_id)
+                    .find_map(|id| self.find(cx, id, true))
+                    .or_else(|| {
+                        prefix
+                            .iter()
+                            .filter_map(|segment| segment.res.opt_def_id())
+                            .find_map(|id| self.find(cx, id, false))
+                    })
+            },
+            ItemKind::ExternCrate(_) => cx
+                .tcx
+                .extern_mod_stmt_cnum(item.owner_id.def_id)
+                .and_then(|num| self.find(cx, num.as_def_id(), true)),
+            _ => None,
+        };
+        if let Some(index) = index {
+            let conf = &self.conf_disallowed[index];
+            span_lint_and_then(
+                cx,
+                DISALLOWED_IMPORTS,
+                item.span,
+                format!("importing `{}` is not allowed according to config", conf.path()),
+                |diag| {
+                    if let Some(reason) = conf.reason() {
+                        diag.note(reason);
+                    }
+                },
+            );
+        }
+    }
+}
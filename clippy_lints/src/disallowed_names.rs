@@ -2,7 +2,8 @@ use clippy_utils::diagnostics::span_lint;
 use clippy_utils::is_test_module_or_function;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_hir::{Item, Pat, PatKind};
-use rustc_lint::{LateContext, LateLintPass};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
 use rustc_session::impl_lint_pass;
 
 declare_clippy_lint! {
@@ -54,7 +55,7 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedNames {
 
     fn check_pat(&mut self, cx: &LateContext<'tcx>, pat: &'tcx Pat<'_>) {
         // Check whether we are under the `test` attribute.
-        if self.in_test_module() {
+        if self.in_test_module() || in_external_macro(cx.sess(), pat.span) {
             return;
         }
 
@@ -0,0 +1,238 @@
+use clippy_config::types::{DisallowedTypeAcrossRegions, Region};
+use clippy_utils::def_path_def_ids;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::mir::dataflow::{self, MaybeInitializedLocals};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::{self as hir, Body, ClosureKind, CoroutineDesugaring, CoroutineKind, Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::mir::{self, CoroutineLayout, Local, TerminatorKind};
+use rustc_middle::ty::{self, Ty};
+use rustc_mir_dataflow::impls::MaybeLiveLocals;
+use rustc_session::impl_lint_pass;
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Allows users to configure types which may not be live across certain regions of
+    /// code: `await` points, calls to foreign functions, or calls to specific functions.
+    ///
+    /// ### Why is this bad?
+    /// Some values guard a resource which must not be held while control leaves the
+    /// current function. For example, a database transaction guard held across a call
+    /// to a slow rendering function keeps the transaction open for far longer than needed,
+    /// and a `RefCell` borrow held across an FFI call will panic if a callback borrows
+    /// the cell again.
+    ///
+    /// ### Known problems
+    /// Inside `async` bodies, only `await` regions are checked.
+    ///
+    /// ### Example
+    /// ```toml
+    /// disallowed-types-across-regions = [
+    ///     { path = "db::TransactionGuard", across = ["await", { call = "app::render" }] },
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// fn handle(db: &Db) {
+    ///     let tx = db.begin();
+    ///     tx.insert(1);
+    ///     app::render(); // Lint violation
+    ///     tx.commit();
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// fn handle(db: &Db) {
+    ///     let tx = db.begin();
+    ///     tx.insert(1);
+    ///     tx.commit();
+    ///     app::render();
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub DISALLOWED_TYPES_ACROSS_REGIONS,
+    suspicious,
+    "holding a type across a region of code which is not allowed to be held across as per the configuration"
+}
+
+impl_lint_pass!(DisallowedTypesAcrossRegions => [DISALLOWED_TYPES_ACROSS_REGIONS]);
+
+pub struct DisallowedTypesAcrossRegions {
+    conf_disallowed: Vec<DisallowedTypeAcrossRegions>,
+    /// Maps the `DefId` of a disallowed type to its indices in `conf_disallowed`, a type may be
+    /// configured more than once.
+    def_ids: FxHashMap<DefId, Vec<usize>>,
+    /// Maps the `DefId` of a configured function to the indices of the types which may not be
+    /// held across calls to it.
+    calls: FxHashMap<DefId, Vec<usize>>,
+}
+
+impl DisallowedTypesAcrossRegions {
+    pub fn new(conf_disallowed: Vec<DisallowedTypeAcrossRegions>) -> Self {
+        Self {
+            conf_disallowed,
+            def_ids: FxHashMap::default(),
+            calls: FxHashMap::default(),
+        }
+    }
+
+    /// Returns the index of the first rule for the type which disallows it across a matching
+    /// region.
+    fn disallowed_across(&self, ty: Ty<'_>, region: impl Fn(usize, &Region) -> bool) -> Option<usize> {
+        if let ty::Adt(adt, _) = ty.kind()
+            && let Some(indices) = self.def_ids.get(&adt.did())
+        {
+            indices.iter().copied().find(|&index| {
+                self.conf_disallowed[index]
+                    .across
+                    .iter()
+                    .any(|across| region(index, across))
+            })
+        } else {
+            None
+        }
+    }
+
+    fn check_await_points(&self, cx: &LateContext<'_>, coroutine: &CoroutineLayout<'_>) {
+        for (ty_index, ty_cause) in coroutine.field_tys.iter_enumerated() {
+            if let Some(index) = self.disallowed_across(ty_cause.ty, |_, region| *region == Region::Await) {
+                let await_points = coroutine
+                    .variant_source_info
+                    .iter_enumerated()
+                    .filter_map(|(variant, source_info)| {
+                        coroutine.variant_fields[variant]
+                            .raw
+                            .contains(&ty_index)
+                            .then_some(source_info.span)
+                    })
+                    .collect::<Vec<_>>();
+                self.emit(cx, index, ty_cause.source_info.span, "an `await` point", await_points);
+            }
+        }
+    }
+
+    fn check_calls<'tcx>(&self, cx: &LateContext<'tcx>, mir: &mir::Body<'tcx>) {
+        let mut liveness = dataflow::run(cx.tcx, mir, MaybeLiveLocals);
+        // A local may be live only because it's dropped later on another path, after it was moved
+        // on this one.
+        let locals: Vec<_> = mir.local_decls.indices().collect();
+        let mut initialized = dataflow::run(cx.tcx, mir, MaybeInitializedLocals::new(mir, &locals));
+        // The calls each local is held across, keyed by the local, the disallowed type and the region.
+        let mut held: FxIndexMap<(Local, usize, String), Vec<Span>> = FxIndexMap::default();
+
+        for data in mir.basic_blocks.iter() {
+            let TerminatorKind::Call {
+                func,
+                destination,
+                target: Some(target),
+                fn_span,
+                ..
+            } = &data.terminator().kind
+            else {
+                continue;
+            };
+            let Some((callee, _)) = func.const_fn_def() else {
+                continue;
+            };
+            let is_ffi = cx.tcx.is_foreign_item(callee);
+            let disallowed = self.calls.get(&callee);
+            if (!is_ffi && disallowed.is_none()) || in_external_macro(cx.sess(), *fn_span) {
+                continue;
+            }
+
+            // Locals which are live and may be initialized on entry of the return block are held
+            // across the call.
+            liveness.seek_to_block_start(*target);
+            initialized.seek_to_block_start(*target);
+            for local in liveness.get().iter() {
+                if local == mir::RETURN_PLACE || local == destination.local || !initialized.get().contains(local) {
+                    continue;
+                }
+                let ty = mir.local_decls[local].ty;
+                if is_ffi && let Some(index) = self.disallowed_across(ty, |_, region| *region == Region::Ffi) {
+                    held.entry((local, index, "a call to a foreign function".into()))
+                        .or_default()
+                        .push(*fn_span);
+                } else if let Some(disallowed) = disallowed
+                    && let Some(index) = self.disallowed_across(ty, |index, _| disallowed.contains(&index))
+                {
+                    held.entry((local, index, format!("a call to `{}`", cx.tcx.def_path_str(callee))))
+                        .or_default()
+                        .push(*fn_span);
+                }
+            }
+        }
+
+        for ((local, index, what), calls) in held {
+            let span = mir.local_decls[local].source_info.span;
+            if !in_external_macro(cx.sess(), span) {
+                self.emit(cx, index, span, &what, calls);
+            }
+        }
+    }
+
+    fn emit(&self, cx: &LateContext<'_>, index: usize, span: Span, what: &str, points: Vec<Span>) {
+        let disallowed = &self.conf_disallowed[index];
+        span_lint_and_then(
+            cx,
+            DISALLOWED_TYPES_ACROSS_REGIONS,
+            span,
+            format!("`{}` may not be held across {what} per `clippy.toml`", disallowed.path),
+            |diag| {
+                diag.span_note(points, "the value is held across this point");
+                if let Some(reason) = disallowed.reason() {
+                    diag.note(reason);
+                }
+            },
+        );
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for DisallowedTypesAcrossRegions {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for (index, conf) in self.conf_disallowed.iter().enumerate() {
+            let segs: Vec<_> = conf.path.split("::").collect();
+            for id in def_path_def_ids(cx, &segs) {
+                self.def_ids.entry(id).or_default().push(index);
+            }
+            let mut seen = FxHashSet::default();
+            for region in &conf.across {
+                if let Region::Call(path) = region
+                    && seen.insert(path)
+                {
+                    let segs: Vec<_> = path.split("::").collect();
+                    for id in def_path_def_ids(cx, &segs) {
+                        self.calls.entry(id).or_default().push(index);
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_body(&mut self, cx: &LateContext<'tcx>, body: &'tcx Body<'tcx>) {
+        if self.def_ids.is_empty() {
+            return;
+        }
+        let def_id: LocalDefId = cx.tcx.hir().body_owner_def_id(body.id());
+        if cx.tcx.hir().body_owner_kind(def_id).is_fn_or_closure() && cx.tcx.coroutine_kind(def_id).is_none() {
+            self.check_calls(cx, cx.tcx.optimized_mir(def_id));
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::Closure(hir::Closure {
+            kind: ClosureKind::Coroutine(CoroutineKind::Desugared(CoroutineDesugaring::Async, _)),
+            def_id,
+            ..
+        }) = expr.kind
+            && !self.def_ids.is_empty()
+            && let Some(coroutine_layout) = cx.tcx.mir_coroutine_witnesses(*def_id)
+        {
+            self.check_await_points(cx, coroutine_layout);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::format_string::FormatString;
+use clippy_utils::macros::{root_macro_call_first_node, FormatArgsStorage};
+use clippy_utils::ty::implements_trait;
+use clippy_utils::{get_parent_as_impl, is_diag_trait_item, path_to_local};
+use rustc_hir::{Expr, ExprKind, Impl, ImplItem, ImplItemKind, UnOp};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{Ty, TypeckResults};
+use rustc_session::impl_lint_pass;
+use rustc_span::sym;
+use rustc_span::symbol::kw;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `Display` implementations which only forward to the `Debug` implementation, e.g.
+    /// with `write!(f, "{:?}", self)`, or which format every field with `{:?}`.
+    ///
+    /// ### Why is this bad?
+    /// `Display` is meant for a human-readable output, while `Debug` shows the structure of the
+    /// value for programmers. A `Display` implementation printing the `Debug` output provides
+    /// neither, and makes `{}` and `{:?}` look the same.
+    ///
+    /// ### Configuration
+    /// This lint has the following configuration variables:
+    ///
+    /// - `allow-debug-display-for-errors`: Whether to allow the pattern for types implementing
+    ///   `std::error::Error`.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// impl fmt::Display for Point {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "{:?}", self)
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// impl fmt::Display for Point {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "({}, {})", self.x, self.y)
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub DISPLAY_FORWARDING_TO_DEBUG,
+    pedantic,
+    "`Display` implementation printing the `Debug` output"
+}
+
+pub struct DisplayForwardingToDebug {
+    format_args: FormatArgsStorage,
+    allow_for_errors: bool,
+}
+
+impl DisplayForwardingToDebug {
+    pub fn new(format_args: FormatArgsStorage, allow_for_errors: bool) -> Self {
+        Self {
+            format_args,
+            allow_for_errors,
+        }
+    }
+}
+
+impl_lint_pass!(DisplayForwardingToDebug => [DISPLAY_FORWARDING_TO_DEBUG]);
+
+impl<'tcx> LateLintPass<'tcx> for DisplayForwardingToDebug {
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, impl_item: &'tcx ImplItem<'tcx>) {
+        if impl_item.ident.name == sym::fmt
+            && let ImplItemKind::Fn(_, body_id) = impl_item.kind
+            && !impl_item.span.from_expansion()
+            && let Some(Impl {
+                of_trait: Some(trait_ref),
+                ..
+            }) = get_parent_as_impl(cx.tcx, impl_item.hir_id())
+            && let Some(trait_id) = trait_ref.trait_def_id()
+            && cx.tcx.is_diagnostic_item(sym::Display, trait_id)
+            && let ExprKind::Block(block, _) = cx.tcx.hir().body(body_id).value.kind
+            && block.stmts.is_empty()
+            && let Some(tail) = block.expr
+            && let typeck = cx.tcx.typeck_body(body_id)
+            && let Some(msg) = self.forwarding(cx, typeck, tail)
+            && !(self.allow_for_errors
+                && is_error(
+                    cx,
+                    cx.tcx
+                        .type_of(cx.tcx.local_parent(impl_item.owner_id.def_id))
+                        .instantiate_identity(),
+                ))
+        {
+            span_lint_and_help(
+                cx,
+                DISPLAY_FORWARDING_TO_DEBUG,
+                impl_item.span,
+                msg,
+                None,
+                "write a human-readable format, or format the value with `{:?}` where it's displayed",
+            );
+        }
+    }
+}
+
+impl DisplayForwardingToDebug {
+    /// Checks if the body of `fmt` only forwards to `Debug`, returning the message of the lint.
+    fn forwarding<'tcx>(
+        &self,
+        cx: &LateContext<'tcx>,
+        typeck: &TypeckResults<'tcx>,
+        tail: &'tcx Expr<'tcx>,
+    ) -> Option<&'static str> {
+        const FORWARDS: &str = "this `Display` implementation forwards to `Debug`";
+
+        if let Some(macro_call) = root_macro_call_first_node(cx, tail) {
+            if !matches!(
+                cx.tcx.get_diagnostic_name(macro_call.def_id),
+                Some(sym::write_macro | sym::writeln_macro)
+            ) {
+                return None;
+            }
+            let format_args = self.format_args.get(cx, tail, macro_call.expn)?;
+            let format_string = FormatString::new(format_args);
+            let mut args = Vec::new();
+            for placeholder in format_string.placeholders() {
+                if placeholder.format_trait_name() != sym::Debug {
+                    return None;
+                }
+                args.push(placeholder.arg?.hir_expr(tail).ok()?);
+            }
+            return match *args {
+                [] => None,
+                [arg] if is_self(cx, peel_refs(arg)) => Some(FORWARDS),
+                _ if args.iter().all(
+                    |arg| matches!(peel_refs(arg).kind, ExprKind::Field(base, _) if is_self(cx, peel_refs(base))),
+                ) =>
+                {
+                    Some("this `Display` implementation formats every field with `Debug`")
+                },
+                _ => None,
+            };
+        }
+
+        // `fmt::Debug::fmt(self, f)`, or `self.fmt(f)` with `Debug` in scope
+        let (fn_id, arg) = match tail.kind {
+            ExprKind::Call(callee, [arg, _]) => match callee.kind {
+                ExprKind::Path(ref qpath) => (typeck.qpath_res(qpath, callee.hir_id).opt_def_id()?, arg),
+                _ => return None,
+            },
+            ExprKind::MethodCall(_, receiver, [_], _) => (typeck.type_dependent_def_id(tail.hir_id)?, receiver),
+            _ => return None,
+        };
+        (is_diag_trait_item(cx, fn_id, sym::Debug) && is_self(cx, peel_refs(arg))).then_some(FORWARDS)
+    }
+}
+
+fn peel_refs<'tcx>(mut e: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    while let ExprKind::AddrOf(_, _, inner) | ExprKind::Unary(UnOp::Deref, inner) = e.kind {
+        e = inner;
+    }
+    e
+}
+
+fn is_self(cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
+    path_to_local(e).is_some_and(|id| cx.tcx.hir().name(id) == kw::SelfLower)
+}
+
+fn is_error<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> bool {
+    cx.tcx
+        .get_diagnostic_item(sym::Error)
+        .is_some_and(|error_trait| implements_trait(cx, ty, error_trait, &[]))
+}
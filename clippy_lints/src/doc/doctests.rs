@@ -0,0 +1,41 @@
+//! Collection of the Rust code blocks of the documentation for `cargo clippy --doctests`, which
+//! lints them after the crate the same way `rustdoc --test` compiles them.
+
+use super::Fragments;
+use crate::Doctest;
+use rustc_lint::{LateContext, LintContext};
+use rustc_span::edition::Edition;
+use std::ops::Range;
+use std::sync::Mutex;
+
+/// The collected code blocks, if enabled.
+static DOCTESTS: Mutex<Option<Vec<Doctest>>> = Mutex::new(None);
+
+pub fn enable() {
+    *DOCTESTS.lock().unwrap() = Some(Vec::new());
+}
+
+pub fn take() -> Vec<Doctest> {
+    DOCTESTS.lock().unwrap().take().unwrap_or_default()
+}
+
+pub(super) fn collect(
+    cx: &LateContext<'_>,
+    text: &str,
+    edition: Edition,
+    range: Range<usize>,
+    fragments: Fragments<'_>,
+) {
+    if let Some(doctests) = &mut *DOCTESTS.lock().unwrap()
+        && let Some(span) = fragments.span(cx, range)
+    {
+        let loc = cx.sess().source_map().lookup_char_pos(span.lo());
+        doctests.push(Doctest {
+            code: text.to_string(),
+            edition: edition.to_string(),
+            file: loc.file.name.prefer_local().to_string(),
+            line: loc.line,
+            column: loc.col_display,
+        });
+    }
+}
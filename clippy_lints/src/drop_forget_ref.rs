@@ -1,9 +1,12 @@
 use clippy_utils::diagnostics::span_lint_and_note;
-use clippy_utils::is_must_use_func_call;
-use clippy_utils::ty::{is_copy, is_must_use_ty, is_type_lang_item};
+use clippy_utils::ty::{is_copy, is_must_use_ty, is_type_lang_item, significant_drop};
+use clippy_utils::{def_path_def_ids, is_must_use_func_call};
+use rustc_hir::def_id::DefIdSet;
 use rustc_hir::{Arm, Expr, ExprKind, LangItem, Node};
-use rustc_lint::{LateContext, LateLintPass};
-use rustc_session::declare_lint_pass;
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty;
+use rustc_session::impl_lint_pass;
 use rustc_span::sym;
 use std::borrow::Cow;
 
@@ -54,7 +57,9 @@ declare_clippy_lint! {
     ///
     /// ### Why is this bad?
     /// `std::mem::forget(t)` prevents `t` from running its
-    /// destructor, possibly causing leaks.
+    /// destructor, possibly causing leaks. Forgetting a value with a
+    /// significant drop, e.g. a lock guard or a file, also means the
+    /// lock is never released or the file never closed.
     ///
     /// ### Example
     /// ```no_run
@@ -73,14 +78,39 @@ const DROP_NON_DROP_SUMMARY: &str = "call to `std::mem::drop` with a value that
 const FORGET_NON_DROP_SUMMARY: &str = "call to `std::mem::forget` with a value that does not implement `Drop`. \
                                    Forgetting such a type is the same as dropping it";
 
-declare_lint_pass!(DropForgetRef => [
+pub struct DropForgetRef {
+    significant_drop_types: Vec<String>,
+    significant_drop_def_ids: DefIdSet,
+}
+
+impl DropForgetRef {
+    pub fn new(significant_drop_types: Vec<String>) -> Self {
+        Self {
+            significant_drop_types,
+            significant_drop_def_ids: DefIdSet::default(),
+        }
+    }
+}
+
+impl_lint_pass!(DropForgetRef => [
     DROP_NON_DROP,
     FORGET_NON_DROP,
     MEM_FORGET,
 ]);
 
 impl<'tcx> LateLintPass<'tcx> for DropForgetRef {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for path in &self.significant_drop_types {
+            let segs: Vec<_> = path.split("::").collect();
+            self.significant_drop_def_ids.extend(def_path_def_ids(cx, &segs));
+        }
+    }
+
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+
         if let ExprKind::Call(path, [arg]) = expr.kind
             && let ExprKind::Path(ref qpath) = path.kind
             && let Some(def_id) = cx.qpath_res(qpath, path.hir_id).opt_def_id()
@@ -106,7 +136,18 @@ impl<'tcx> LateLintPass<'tcx> for DropForgetRef {
                     (DROP_NON_DROP, DROP_NON_DROP_SUMMARY.into(), Some(arg.span))
                 },
                 sym::mem_forget => {
-                    if arg_ty.needs_drop(cx.tcx, cx.param_env) {
+                    if let Some(sig_drop) = significant_drop(cx, arg_ty, &self.significant_drop_def_ids) {
+                        let outer = matches!(arg_ty.kind(), ty::Adt(adt, _) if adt.did() == sig_drop.def_id);
+                        (
+                            MEM_FORGET,
+                            Cow::Owned(format!(
+                                "usage of `mem::forget` on {}{}, whose drop is never run",
+                                if outer { "" } else { "a type containing " },
+                                sig_drop.kind.describe(),
+                            )),
+                            None,
+                        )
+                    } else if arg_ty.needs_drop(cx.tcx, cx.param_env) {
                         (
                             MEM_FORGET,
                             Cow::Owned(format!(
@@ -0,0 +1,186 @@
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_then};
+use clippy_utils::macros::{is_format_macro, root_macro_call};
+use clippy_utils::ty::implements_trait;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{get_parent_expr, path_to_local_id};
+use core::ops::ControlFlow;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind, Impl, ImplItemKind, Item, ItemKind, Node, PatKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, Ty};
+use rustc_session::declare_lint_pass;
+use rustc_span::{sym, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for error types which lose the error causing them:
+    /// - an `Error` implementation which doesn't override `source`, when the type has a field
+    ///   holding another error,
+    /// - a `From` implementation converting another error into the error type, which only keeps
+    ///   the message of the error, or nothing of it.
+    ///
+    /// ### Why is this bad?
+    /// The chain of errors returned by `source` is how error reporters, e.g. `anyhow` and loggers,
+    /// show the root cause of a failure. Once the cause is dropped, it can't be inspected nor
+    /// downcast by the callers.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use std::{error::Error, fmt, io};
+    /// #[derive(Debug)]
+    /// struct ConfigError(io::Error);
+    ///
+    /// impl fmt::Display for ConfigError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.write_str("can't read the configuration")
+    ///     }
+    /// }
+    ///
+    /// impl Error for ConfigError {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # use std::{error::Error, fmt, io};
+    /// # #[derive(Debug)]
+    /// # struct ConfigError(io::Error);
+    /// # impl fmt::Display for ConfigError {
+    /// #     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// #         f.write_str("can't read the configuration")
+    /// #     }
+    /// # }
+    /// impl Error for ConfigError {
+    ///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+    ///         Some(&self.0)
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub DROPPED_ERROR_SOURCE,
+    pedantic,
+    "error types dropping the error which caused them"
+}
+
+declare_lint_pass!(DroppedErrorSource => [DROPPED_ERROR_SOURCE]);
+
+impl<'tcx> LateLintPass<'tcx> for DroppedErrorSource {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if let ItemKind::Impl(impl_) = item.kind
+            && let Some(trait_ref) = impl_.of_trait
+            && !item.span.from_expansion()
+            && let Some(trait_id) = trait_ref.trait_def_id()
+            && let Some(error_trait) = cx.tcx.get_diagnostic_item(sym::Error)
+        {
+            let self_ty = cx.tcx.type_of(item.owner_id).instantiate_identity();
+            if trait_id == error_trait {
+                check_error_impl(cx, impl_, self_ty, error_trait);
+            } else if cx.tcx.is_diagnostic_item(sym::From, trait_id)
+                && let Some(trait_ref) = cx.tcx.impl_trait_ref(item.owner_id)
+                && let source_ty = trait_ref.instantiate_identity().args.type_at(1)
+                && source_ty != self_ty
+                && is_error(cx, source_ty, error_trait)
+                && is_error(cx, self_ty, error_trait)
+            {
+                check_from_impl(cx, impl_, source_ty);
+            }
+        }
+    }
+}
+
+/// Lints the `Error` implementation if it doesn't override `source` while a field holds an error.
+fn check_error_impl<'tcx>(cx: &LateContext<'tcx>, impl_: &Impl<'_>, self_ty: Ty<'tcx>, error_trait: DefId) {
+    if impl_
+        .items
+        .iter()
+        .any(|item| matches!(item.ident.as_str(), "source" | "cause"))
+    {
+        return;
+    }
+    let ty::Adt(adt, args) = self_ty.kind() else {
+        return;
+    };
+    let error_fields: Vec<Span> = adt
+        .all_fields()
+        .filter(|field| is_error(cx, field.ty(cx.tcx, args), error_trait))
+        .map(|field| cx.tcx.def_span(field.did))
+        .collect();
+    if error_fields.is_empty() {
+        return;
+    }
+    span_lint_and_then(
+        cx,
+        DROPPED_ERROR_SOURCE,
+        impl_.self_ty.span,
+        "this error type holds another error, but doesn't return it from `source`",
+        |diag| {
+            diag.span_note(error_fields, "the underlying error is stored here");
+            diag.help("implement `source` to return the underlying error");
+        },
+    );
+}
+
+/// Lints the `From` implementation if `from` doesn't keep the converted error.
+fn check_from_impl<'tcx>(cx: &LateContext<'tcx>, impl_: &Impl<'_>, source_ty: Ty<'tcx>) {
+    let Some(from) = impl_.items.iter().find(|item| item.ident.name == sym::from) else {
+        return;
+    };
+    let Node::ImplItem(from) = cx.tcx.hir_node(from.id.hir_id()) else {
+        return;
+    };
+    let ImplItemKind::Fn(_, body_id) = from.kind else {
+        return;
+    };
+    let body = cx.tcx.hir().body(body_id);
+    let [param] = body.params else {
+        return;
+    };
+    let dropped = match param.pat.kind {
+        PatKind::Wild => true,
+        PatKind::Binding(_, id, _, None) => for_each_expr(body.value, |e| {
+            if path_to_local_id(e, id) && !is_only_described(cx, e) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .is_none(),
+        _ => false,
+    };
+    if dropped {
+        span_lint_and_help(
+            cx,
+            DROPPED_ERROR_SOURCE,
+            from.span,
+            "this conversion drops the error it converts",
+            None,
+            format!("store the `{source_ty}` in the error, and return it from `source`"),
+        );
+    }
+}
+
+/// Checks if the use of the error `e` only reads its message, with `to_string` or by formatting
+/// it.
+fn is_only_described(cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
+    let Some(parent) = get_parent_expr(cx, e) else {
+        return false;
+    };
+    match parent.kind {
+        ExprKind::MethodCall(method, receiver, [], _) => {
+            receiver.hir_id == e.hir_id && matches!(method.ident.as_str(), "to_string" | "description")
+        },
+        _ => {
+            parent.span.from_expansion()
+                && root_macro_call(parent.span).is_some_and(|macro_call| is_format_macro(cx, macro_call.def_id))
+        },
+    }
+}
+
+/// Checks if `ty` implements `Error`, or is a boxed `dyn Error`.
+fn is_error<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>, error_trait: DefId) -> bool {
+    if ty.is_box()
+        && let ty::Dynamic(preds, ..) = ty.boxed_ty().kind()
+    {
+        preds.principal_def_id() == Some(error_trait)
+    } else {
+        implements_trait(cx, ty, error_trait, &[])
+    }
+}
@@ -0,0 +1,154 @@
+use crate::disallowed_env_vars::macro_var_name;
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::macros::macro_backtrace;
+use clippy_utils::source::snippet_opt;
+use clippy_utils::str_utils::matches_wildcard;
+use clippy_utils::{fn_def_id, local_def_path_def_ids, match_def_path, paths};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind, HirId};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::impl_lint_pass;
+use rustc_span::{ExpnId, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for reads of the environment, by `std::env::var`, `std::env::var_os`,
+    /// `std::env::vars`, `std::env::vars_os`, `env!` or `option_env!`, outside of the modules
+    /// configured in `env-config-modules`.
+    ///
+    /// The variables listed in `allowed-env-vars` may be read anywhere.
+    ///
+    /// ### Why is this bad?
+    /// Settings read from the environment all over a codebase make it hard to know which variables
+    /// a program depends on, and to test the code reading them. Loading them in one module, and
+    /// passing the values around, keeps the configuration in one place.
+    ///
+    /// ### Known problems
+    /// The name of the variable is only known if it is a literal or a constant. Reads of variables
+    /// with other names are linted even if they are in `allowed-env-vars`.
+    ///
+    /// ### Example
+    /// ```toml
+    /// env-config-modules = ["crate::config"]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// mod server {
+    ///     pub fn bind() -> std::io::Result<std::net::TcpListener> {
+    ///         let port = std::env::var("PORT").unwrap_or_else(|_| "8080".into());
+    ///         std::net::TcpListener::bind(format!("0.0.0.0:{port}"))
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// mod server {
+    ///     pub fn bind(config: &crate::config::Config) -> std::io::Result<std::net::TcpListener> {
+    ///         std::net::TcpListener::bind(("0.0.0.0", config.port))
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub ENV_ACCESS_OUTSIDE_CONFIG,
+    restriction,
+    "read of the environment outside of the configuration modules"
+}
+
+impl_lint_pass!(EnvAccessOutsideConfig => [ENV_ACCESS_OUTSIDE_CONFIG]);
+
+pub struct EnvAccessOutsideConfig {
+    modules: Vec<String>,
+    allowed_vars: Vec<String>,
+    /// The `DefId`s of `modules`.
+    module_ids: Vec<DefId>,
+    /// The expansions of `env!` and `option_env!` which were already checked.
+    seen_expansions: FxHashSet<ExpnId>,
+}
+
+impl EnvAccessOutsideConfig {
+    pub fn new(modules: Vec<String>, allowed_vars: Vec<String>) -> Self {
+        Self {
+            modules,
+            allowed_vars,
+            module_ids: Vec::new(),
+            seen_expansions: FxHashSet::default(),
+        }
+    }
+
+    /// Lints the read, unless it's in a configuration module or of an allowed variable. `name` is
+    /// `None` if the name of the variable isn't known, or if the whole environment is read.
+    fn check_read(&self, cx: &LateContext<'_>, hir_id: HirId, span: Span, name: Option<&str>, what: &str) {
+        if name.is_some_and(|name| self.allowed_vars.iter().any(|allowed| matches_wildcard(allowed, name))) {
+            return;
+        }
+        let module = cx.tcx.parent_module(hir_id).to_def_id();
+        if self
+            .module_ids
+            .iter()
+            .any(|&config| cx.tcx.is_descendant_of(module, config))
+        {
+            return;
+        }
+        let help = if self.modules.is_empty() {
+            String::from("read it in a configuration module, and list the module in `env-config-modules`")
+        } else {
+            format!("read it in `{}`", self.modules.join("`, `"))
+        };
+        span_lint_and_help(
+            cx,
+            ENV_ACCESS_OUTSIDE_CONFIG,
+            span,
+            format!("{what} outside of the configuration modules"),
+            None,
+            help,
+        );
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for EnvAccessOutsideConfig {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        self.module_ids = self
+            .modules
+            .iter()
+            .flat_map(|module| local_def_path_def_ids(cx, module))
+            .collect();
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let Some(mac) = macro_backtrace(expr.span).find(|mac| {
+            cx.tcx
+                .get_diagnostic_name(mac.def_id)
+                .is_some_and(|name| name == sym!(env_macro) || name == sym!(option_env_macro))
+        }) {
+            if self.seen_expansions.insert(mac.expn) && !in_external_macro(cx.sess(), mac.span) {
+                let call = snippet_opt(cx, mac.span);
+                let name = call.as_deref().and_then(macro_var_name);
+                let what = name.map_or_else(
+                    || String::from("read of an environment variable"),
+                    |name| format!("read of the environment variable `{name}`"),
+                );
+                self.check_read(cx, expr.hir_id, mac.span, name, &what);
+            }
+        } else if let ExprKind::Call(_, args) = expr.kind
+            && let Some(def_id) = fn_def_id(cx, expr)
+            && !in_external_macro(cx.sess(), expr.span)
+        {
+            if let [arg] = args
+                && (match_def_path(cx, def_id, &paths::ENV_VAR) || match_def_path(cx, def_id, &paths::ENV_VAR_OS))
+            {
+                if let Some(Constant::Str(name)) = constant(cx, cx.typeck_results(), arg) {
+                    let what = format!("read of the environment variable `{name}`");
+                    self.check_read(cx, expr.hir_id, expr.span, Some(&name), &what);
+                } else {
+                    self.check_read(cx, expr.hir_id, expr.span, None, "read of an environment variable");
+                }
+            } else if match_def_path(cx, def_id, &paths::ENV_VARS) || match_def_path(cx, def_id, &paths::ENV_VARS_OS) {
+                self.check_read(cx, expr.hir_id, expr.span, None, "read of the whole environment");
+            }
+        }
+    }
+}
@@ -1,7 +1,9 @@
+use clippy_config::types::ThresholdOverride;
 use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::thresholds::{Threshold, ThresholdKind};
 use clippy_utils::{get_parent_as_impl, has_repr_attr, is_bool};
 use rustc_hir::intravisit::FnKind;
-use rustc_hir::{Body, FnDecl, Item, ItemKind, TraitFn, TraitItem, TraitItemKind, Ty};
+use rustc_hir::{Body, FnDecl, HirId, Item, ItemKind, TraitFn, TraitItem, TraitItemKind, Ty};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_session::impl_lint_pass;
 use rustc_span::def_id::LocalDefId;
@@ -83,44 +85,46 @@ declare_clippy_lint! {
 }
 
 pub struct ExcessiveBools {
-    max_struct_bools: u64,
-    max_fn_params_bools: u64,
-}
-
-#[derive(Eq, PartialEq, Debug, Copy, Clone)]
-enum Kind {
-    Struct,
-    Fn,
+    max_struct_bools: Threshold,
+    max_fn_params_bools: Threshold,
 }
 
 impl ExcessiveBools {
     #[must_use]
-    pub fn new(max_struct_bools: u64, max_fn_params_bools: u64) -> Self {
+    pub fn new(max_struct_bools: u64, max_fn_params_bools: u64, threshold_overrides: &[ThresholdOverride]) -> Self {
         Self {
-            max_struct_bools,
-            max_fn_params_bools,
+            max_struct_bools: Threshold::new(
+                ThresholdKind::StructExcessiveBools,
+                max_struct_bools,
+                threshold_overrides,
+            ),
+            max_fn_params_bools: Threshold::new(
+                ThresholdKind::FnParamsExcessiveBools,
+                max_fn_params_bools,
+                threshold_overrides,
+            ),
         }
     }
 
-    fn too_many_bools<'tcx>(&self, tys: impl Iterator<Item = &'tcx Ty<'tcx>>, kind: Kind) -> bool {
+    fn too_many_bools<'tcx>(tys: impl Iterator<Item = &'tcx Ty<'tcx>>, max: u64) -> bool {
         if let Ok(bools) = tys.filter(|ty| is_bool(ty)).count().try_into() {
-            (if Kind::Fn == kind {
-                self.max_fn_params_bools
-            } else {
-                self.max_struct_bools
-            }) < bools
+            max < bools
         } else {
             false
         }
     }
 
-    fn check_fn_sig(&self, cx: &LateContext<'_>, fn_decl: &FnDecl<'_>, span: Span) {
-        if !span.from_expansion() && self.too_many_bools(fn_decl.inputs.iter(), Kind::Fn) {
+    fn check_fn_sig(&self, cx: &LateContext<'_>, fn_decl: &FnDecl<'_>, span: Span, hir_id: HirId) {
+        if span.from_expansion() {
+            return;
+        }
+        let max_fn_params_bools = self.max_fn_params_bools.resolve(cx, hir_id, span);
+        if Self::too_many_bools(fn_decl.inputs.iter(), max_fn_params_bools) {
             span_lint_and_help(
                 cx,
                 FN_PARAMS_EXCESSIVE_BOOLS,
                 span,
-                format!("more than {} bools in function parameters", self.max_fn_params_bools),
+                format!("more than {max_fn_params_bools} bools in function parameters"),
                 None,
                 "consider refactoring bools into two-variant enums",
             );
@@ -140,12 +144,13 @@ impl<'tcx> LateLintPass<'tcx> for ExcessiveBools {
                 return;
             }
 
-            if self.too_many_bools(variant_data.fields().iter().map(|field| field.ty), Kind::Struct) {
+            let max_struct_bools = self.max_struct_bools.resolve(cx, item.hir_id(), item.span);
+            if Self::too_many_bools(variant_data.fields().iter().map(|field| field.ty), max_struct_bools) {
                 span_lint_and_help(
                     cx,
                     STRUCT_EXCESSIVE_BOOLS,
                     item.span,
-                    format!("more than {} bools in a struct", self.max_struct_bools),
+                    format!("more than {max_struct_bools} bools in a struct"),
                     None,
                     "consider using a state machine or refactoring bools into two-variant enums",
                 );
@@ -158,7 +163,7 @@ impl<'tcx> LateLintPass<'tcx> for ExcessiveBools {
         if let TraitItemKind::Fn(fn_sig, TraitFn::Required(_)) = &trait_item.kind
             && fn_sig.header.abi == Abi::Rust
         {
-            self.check_fn_sig(cx, fn_sig.decl, fn_sig.span);
+            self.check_fn_sig(cx, fn_sig.decl, fn_sig.span, trait_item.hir_id());
         }
     }
 
@@ -176,7 +181,7 @@ impl<'tcx> LateLintPass<'tcx> for ExcessiveBools {
             && fn_header.abi == Abi::Rust
             && get_parent_as_impl(cx.tcx, hir_id).map_or(true, |impl_item| impl_item.of_trait.is_none())
         {
-            self.check_fn_sig(cx, fn_decl, span);
+            self.check_fn_sig(cx, fn_decl, span, hir_id);
         }
     }
 }
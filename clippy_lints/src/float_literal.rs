@@ -3,7 +3,8 @@ use clippy_utils::numeric_literal;
 use rustc_ast::ast::{self, LitFloatType, LitKind};
 use rustc_errors::Applicability;
 use rustc_hir as hir;
-use rustc_lint::{LateContext, LateLintPass};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
 use rustc_middle::ty::{self, FloatTy};
 use rustc_session::declare_lint_pass;
 use std::fmt;
@@ -62,6 +63,10 @@ declare_lint_pass!(FloatLiteral => [EXCESSIVE_PRECISION, LOSSY_FLOAT_LITERAL]);
 
 impl<'tcx> LateLintPass<'tcx> for FloatLiteral {
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'_>) {
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+
         let ty = cx.typeck_results().expr_ty(expr);
         if let ty::Float(fty) = *ty.kind()
             && let hir::ExprKind::Lit(lit) = expr.kind
@@ -1,8 +1,8 @@
 use clippy_utils::diagnostics::span_lint_and_sugg;
-use clippy_utils::macros::{find_format_arg_expr, root_macro_call_first_node, FormatArgsStorage};
+use clippy_utils::format_string::{FormatString, Piece};
+use clippy_utils::macros::{root_macro_call_first_node, FormatArgsStorage};
 use clippy_utils::source::{snippet_opt, snippet_with_context};
 use clippy_utils::sugg::Sugg;
-use rustc_ast::{FormatArgsPiece, FormatOptions, FormatTrait};
 use rustc_errors::Applicability;
 use rustc_hir::{Expr, ExprKind};
 use rustc_lint::{LateContext, LateLintPass};
@@ -61,22 +61,23 @@ impl<'tcx> LateLintPass<'tcx> for UselessFormat {
             let mut applicability = Applicability::MachineApplicable;
             let call_site = macro_call.span;
 
-            match (format_args.arguments.all_args(), &format_args.template[..]) {
+            let format_string = FormatString::new(format_args);
+            let args = format_string.args().collect::<Vec<_>>();
+            let pieces = format_string.pieces().collect::<Vec<_>>();
+            match (&args[..], &pieces[..]) {
                 ([], []) => span_useless_format_empty(cx, call_site, "String::new()".to_owned(), applicability),
                 ([], [_]) => {
                     // Simulate macro expansion, converting {{ and }} to { and }.
-                    let Some(snippet) = snippet_opt(cx, format_args.span) else {
+                    let Some(snippet) = snippet_opt(cx, format_string.span()) else {
                         return;
                     };
                     let s_expand = snippet.replace("{{", "{").replace("}}", "}");
                     let sugg = format!("{s_expand}.to_string()");
                     span_useless_format(cx, call_site, sugg, applicability);
                 },
-                ([arg], [piece]) => {
-                    if let Ok(value) = find_format_arg_expr(expr, arg)
-                        && let FormatArgsPiece::Placeholder(placeholder) = piece
-                        && placeholder.format_trait == FormatTrait::Display
-                        && placeholder.format_options == FormatOptions::default()
+                ([arg], [Piece::Placeholder(placeholder)]) => {
+                    if let Ok(value) = arg.hir_expr(expr)
+                        && placeholder.is_plain_display()
                         && match cx.typeck_results().expr_ty(value).peel_refs().kind() {
                             ty::Adt(adt, _) => Some(adt.did()) == cx.tcx.lang_items().string(),
                             ty::Str => true,
@@ -1,18 +1,14 @@
-use arrayvec::ArrayVec;
 use clippy_config::msrvs::{self, Msrv};
 use clippy_utils::diagnostics::{span_lint_and_sugg, span_lint_and_then};
+use clippy_utils::format_string::{FormatString, Param, Placeholder};
 use clippy_utils::is_diag_trait_item;
 use clippy_utils::macros::{
-    find_format_arg_expr, format_arg_removal_span, format_placeholder_format_span, is_assert_macro, is_format_macro,
-    is_panic, matching_root_macro_call, root_macro_call_first_node, FormatArgsStorage, FormatParamUsage, MacroCall,
+    is_assert_macro, is_format_macro, is_panic, matching_root_macro_call, root_macro_call_first_node,
+    FormatArgsStorage, FormatParamUsage, MacroCall,
 };
 use clippy_utils::source::snippet_opt;
 use clippy_utils::ty::{implements_trait, is_type_lang_item};
-use itertools::Itertools;
-use rustc_ast::{
-    FormatArgPosition, FormatArgPositionKind, FormatArgsPiece, FormatArgumentKind, FormatCount, FormatOptions,
-    FormatPlaceholder, FormatTrait,
-};
+use rustc_ast::FormatOptions;
 use rustc_errors::Applicability;
 use rustc_errors::SuggestionStyle::{CompletelyHidden, ShowCode};
 use rustc_hir::{Expr, ExprKind, LangItem};
@@ -195,7 +191,7 @@ impl<'tcx> LateLintPass<'tcx> for FormatArgs {
                 cx,
                 expr,
                 macro_call: &macro_call,
-                format_args,
+                format_string: FormatString::new(format_args),
                 ignore_mixed: self.ignore_mixed,
             };
 
@@ -214,25 +210,21 @@ struct FormatArgsExpr<'a, 'tcx> {
     cx: &'a LateContext<'tcx>,
     expr: &'tcx Expr<'tcx>,
     macro_call: &'a MacroCall,
-    format_args: &'a rustc_ast::FormatArgs,
+    format_string: FormatString<'a>,
     ignore_mixed: bool,
 }
 
 impl<'a, 'tcx> FormatArgsExpr<'a, 'tcx> {
     fn check_templates(&self) {
-        for piece in &self.format_args.template {
-            if let FormatArgsPiece::Placeholder(placeholder) = piece
-                && let Ok(index) = placeholder.argument.index
-                && let Some(arg) = self.format_args.arguments.all_args().get(index)
-            {
-                let arg_expr = find_format_arg_expr(self.expr, arg);
+        for placeholder in self.format_string.placeholders() {
+            if let Some(arg) = placeholder.arg {
+                let arg_expr = arg.hir_expr(self.expr);
 
                 self.check_unused_format_specifier(placeholder, arg_expr);
 
                 if let Ok(arg_expr) = arg_expr
-                    && placeholder.format_trait == FormatTrait::Display
-                    && placeholder.format_options == FormatOptions::default()
-                    && !self.is_aliased(index)
+                    && placeholder.is_plain_display()
+                    && !self.format_string.is_aliased(arg.index)
                 {
                     let name = self.cx.tcx.item_name(self.macro_call.def_id);
                     self.check_format_in_format_args(name, arg_expr);
@@ -244,7 +236,7 @@ impl<'a, 'tcx> FormatArgsExpr<'a, 'tcx> {
 
     fn check_unused_format_specifier(
         &self,
-        placeholder: &FormatPlaceholder,
+        placeholder: Placeholder<'_>,
         arg_expr: Result<&Expr<'_>, &rustc_ast::Expr>,
     ) {
         let ty_or_ast_expr = arg_expr.map(|expr| self.cx.typeck_results().expr_ty(expr).peel_refs());
@@ -254,14 +246,14 @@ impl<'a, 'tcx> FormatArgsExpr<'a, 'tcx> {
             Err(expr) => matches!(expr.peel_parens_and_refs().kind, rustc_ast::ExprKind::FormatArgs(_)),
         };
 
-        let options = &placeholder.format_options;
+        let options = placeholder.format_options();
 
         let arg_span = match arg_expr {
             Ok(expr) => expr.span,
             Err(expr) => expr.span,
         };
 
-        if let Some(placeholder_span) = placeholder.span
+        if let Some(placeholder_span) = placeholder.span()
             && is_format_args
             && *options != FormatOptions::default()
         {
@@ -294,7 +286,7 @@ impl<'a, 'tcx> FormatArgsExpr<'a, 'tcx> {
                         suggest_format("precision");
                     }
 
-                    if let Some(format_span) = format_placeholder_format_span(placeholder) {
+                    if let Some(format_span) = placeholder.format_span() {
                         diag.span_suggestion_verbose(
                             format_span,
                             "if the current behavior is intentional, remove the format specifiers",
@@ -308,7 +300,7 @@ impl<'a, 'tcx> FormatArgsExpr<'a, 'tcx> {
     }
 
     fn check_uninlined_args(&self) {
-        if self.format_args.span.from_expansion() {
+        if self.format_string.span().from_expansion() {
             return;
         }
         if self.macro_call.span.edition() < Edition2021
@@ -325,8 +317,8 @@ impl<'a, 'tcx> FormatArgsExpr<'a, 'tcx> {
         // we cannot remove any other arguments in the format string,
         // because the index numbers might be wrong after inlining.
         // Example of an un-inlinable format:  print!("{}{1}", foo, 2)
-        for (pos, usage) in self.format_arg_positions() {
-            if !self.check_one_arg(pos, usage, &mut fixes) {
+        for param in self.format_string.params() {
+            if !self.check_one_arg(param, &mut fixes) {
                 return;
             }
         }
@@ -361,18 +353,18 @@ impl<'a, 'tcx> FormatArgsExpr<'a, 'tcx> {
         );
     }
 
-    fn check_one_arg(&self, pos: &FormatArgPosition, usage: FormatParamUsage, fixes: &mut Vec<(Span, String)>) -> bool {
-        let index = pos.index.unwrap();
-        let arg = &self.format_args.arguments.all_args()[index];
+    fn check_one_arg(&self, param: Param<'_>, fixes: &mut Vec<(Span, String)>) -> bool {
+        let index = param.index().unwrap();
+        let arg = self.format_string.arg(index).unwrap();
 
-        if !matches!(arg.kind, FormatArgumentKind::Captured(_))
-            && let rustc_ast::ExprKind::Path(None, path) = &arg.expr.kind
+        if !arg.is_inline()
+            && let rustc_ast::ExprKind::Path(None, path) = &arg.ast_expr().kind
             && let [segment] = path.segments.as_slice()
             && segment.args.is_none()
-            && let Some(arg_span) = format_arg_removal_span(self.format_args, index)
-            && let Some(pos_span) = pos.span
+            && let Some(arg_span) = self.format_string.arg_removal_span(index)
+            && let Some(pos_span) = param.span()
         {
-            let replacement = match usage {
+            let replacement = match param.usage {
                 FormatParamUsage::Argument => segment.ident.name.to_string(),
                 FormatParamUsage::Width => format!("{}$", segment.ident.name),
                 FormatParamUsage::Precision => format!(".{}$", segment.ident.name),
@@ -384,8 +376,7 @@ impl<'a, 'tcx> FormatArgsExpr<'a, 'tcx> {
             // Do not continue inlining (return false) in case
             // * if we can't inline a numbered argument, e.g. `print!("{0} ...", foo.bar, ...)`
             // * if allow_mixed_uninlined_format_args is false and this arg hasn't been inlined already
-            pos.kind != FormatArgPositionKind::Number
-                && (!self.ignore_mixed || matches!(arg.kind, FormatArgumentKind::Captured(_)))
+            !param.is_numbered() && (!self.ignore_mixed || arg.is_inline())
         }
     }
 
@@ -454,33 +445,6 @@ impl<'a, 'tcx> FormatArgsExpr<'a, 'tcx> {
             }
         }
     }
-
-    fn format_arg_positions(&self) -> impl Iterator<Item = (&FormatArgPosition, FormatParamUsage)> {
-        self.format_args.template.iter().flat_map(|piece| match piece {
-            FormatArgsPiece::Placeholder(placeholder) => {
-                let mut positions = ArrayVec::<_, 3>::new();
-
-                positions.push((&placeholder.argument, FormatParamUsage::Argument));
-                if let Some(FormatCount::Argument(position)) = &placeholder.format_options.width {
-                    positions.push((position, FormatParamUsage::Width));
-                }
-                if let Some(FormatCount::Argument(position)) = &placeholder.format_options.precision {
-                    positions.push((position, FormatParamUsage::Precision));
-                }
-
-                positions
-            },
-            FormatArgsPiece::Literal(_) => ArrayVec::new(),
-        })
-    }
-
-    /// Returns true if the format argument at `index` is referred to by multiple format params
-    fn is_aliased(&self, index: usize) -> bool {
-        self.format_arg_positions()
-            .filter(|(position, _)| position.index == Ok(index))
-            .at_most_one()
-            .is_err()
-    }
 }
 
 fn count_needed_derefs<'tcx, I>(mut ty: Ty<'tcx>, mut iter: I) -> (usize, Ty<'tcx>)
@@ -6,6 +6,8 @@ mod result;
 mod too_many_arguments;
 mod too_many_lines;
 
+use clippy_config::types::ThresholdOverride;
+use clippy_utils::thresholds::{Threshold, ThresholdKind};
 use rustc_hir as hir;
 use rustc_hir::intravisit;
 use rustc_lint::{LateContext, LateLintPass};
@@ -359,11 +361,10 @@ declare_clippy_lint! {
     "`impl Trait` is used in the function's parameters"
 }
 
-#[derive(Copy, Clone)]
 #[allow(clippy::struct_field_names)]
 pub struct Functions {
-    too_many_arguments_threshold: u64,
-    too_many_lines_threshold: u64,
+    too_many_arguments_threshold: Threshold,
+    too_many_lines_threshold: Threshold,
     large_error_threshold: u64,
     avoid_breaking_exported_api: bool,
 }
@@ -374,10 +375,19 @@ impl Functions {
         too_many_lines_threshold: u64,
         large_error_threshold: u64,
         avoid_breaking_exported_api: bool,
+        threshold_overrides: &[ThresholdOverride],
     ) -> Self {
         Self {
-            too_many_arguments_threshold,
-            too_many_lines_threshold,
+            too_many_arguments_threshold: Threshold::new(
+                ThresholdKind::TooManyArguments,
+                too_many_arguments_threshold,
+                threshold_overrides,
+            ),
+            too_many_lines_threshold: Threshold::new(
+                ThresholdKind::TooManyLines,
+                too_many_lines_threshold,
+                threshold_overrides,
+            ),
             large_error_threshold,
             avoid_breaking_exported_api,
         }
@@ -408,8 +418,8 @@ impl<'tcx> LateLintPass<'tcx> for Functions {
         def_id: LocalDefId,
     ) {
         let hir_id = cx.tcx.local_def_id_to_hir_id(def_id);
-        too_many_arguments::check_fn(cx, kind, decl, span, hir_id, self.too_many_arguments_threshold);
-        too_many_lines::check_fn(cx, kind, span, body, self.too_many_lines_threshold);
+        too_many_arguments::check_fn(cx, kind, decl, span, hir_id, &self.too_many_arguments_threshold);
+        too_many_lines::check_fn(cx, kind, span, body, hir_id, &self.too_many_lines_threshold);
         not_unsafe_ptr_arg_deref::check_fn(cx, kind, decl, body, def_id);
         misnamed_getters::check_fn(cx, kind, decl, body, span);
         impl_trait_in_params::check_fn(cx, &kind, body, hir_id);
@@ -427,7 +437,7 @@ impl<'tcx> LateLintPass<'tcx> for Functions {
     }
 
     fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::TraitItem<'_>) {
-        too_many_arguments::check_trait_item(cx, item, self.too_many_arguments_threshold);
+        too_many_arguments::check_trait_item(cx, item, &self.too_many_arguments_threshold);
         not_unsafe_ptr_arg_deref::check_trait_item(cx, item);
         must_use::check_trait_item(cx, item);
         result::check_trait_item(cx, item, self.large_error_threshold);
@@ -7,7 +7,7 @@ use rustc_span::{sym, Span};
 
 use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_then};
 use clippy_utils::trait_ref_of_method;
-use clippy_utils::ty::{approx_ty_size, is_type_diagnostic_item, AdtVariantInfo};
+use clippy_utils::ty::{is_type_diagnostic_item, layout_of_or_estimate, AdtVariantInfo};
 
 use super::{RESULT_LARGE_ERR, RESULT_UNIT_ERR};
 
@@ -128,7 +128,7 @@ fn check_result_large_err<'tcx>(cx: &LateContext<'tcx>, err_ty: Ty<'tcx>, hir_ty
             );
         }
     } else {
-        let ty_size = approx_ty_size(cx, err_ty);
+        let ty_size = layout_of_or_estimate(cx, err_ty).size;
         if ty_size >= large_err_threshold {
             span_lint_and_then(
                 cx,
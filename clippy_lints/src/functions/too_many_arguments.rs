@@ -5,6 +5,7 @@ use rustc_target::spec::abi::Abi;
 
 use clippy_utils::diagnostics::span_lint;
 use clippy_utils::is_trait_impl_item;
+use clippy_utils::thresholds::Threshold;
 
 use super::TOO_MANY_ARGUMENTS;
 
@@ -14,7 +15,7 @@ pub(super) fn check_fn(
     decl: &hir::FnDecl<'_>,
     span: Span,
     hir_id: hir::HirId,
-    too_many_arguments_threshold: u64,
+    too_many_arguments_threshold: &Threshold,
 ) {
     // don't warn for implementations, it's not their fault
     if !is_trait_impl_item(cx, hir_id) {
@@ -31,6 +32,7 @@ pub(super) fn check_fn(
                 cx,
                 decl,
                 span.with_hi(decl.output.span().hi()),
+                hir_id,
                 too_many_arguments_threshold,
             ),
             _ => {},
@@ -38,7 +40,11 @@ pub(super) fn check_fn(
     }
 }
 
-pub(super) fn check_trait_item(cx: &LateContext<'_>, item: &hir::TraitItem<'_>, too_many_arguments_threshold: u64) {
+pub(super) fn check_trait_item(
+    cx: &LateContext<'_>,
+    item: &hir::TraitItem<'_>,
+    too_many_arguments_threshold: &Threshold,
+) {
     if let hir::TraitItemKind::Fn(ref sig, _) = item.kind {
         // don't lint extern functions decls, it's not their fault
         if sig.header.abi == Abi::Rust {
@@ -46,14 +52,22 @@ pub(super) fn check_trait_item(cx: &LateContext<'_>, item: &hir::TraitItem<'_>,
                 cx,
                 sig.decl,
                 item.span.with_hi(sig.decl.output.span().hi()),
+                item.hir_id(),
                 too_many_arguments_threshold,
             );
         }
     }
 }
 
-fn check_arg_number(cx: &LateContext<'_>, decl: &hir::FnDecl<'_>, fn_span: Span, too_many_arguments_threshold: u64) {
+fn check_arg_number(
+    cx: &LateContext<'_>,
+    decl: &hir::FnDecl<'_>,
+    fn_span: Span,
+    hir_id: hir::HirId,
+    too_many_arguments_threshold: &Threshold,
+) {
     let args = decl.inputs.len() as u64;
+    let too_many_arguments_threshold = too_many_arguments_threshold.resolve(cx, hir_id, fn_span);
     if args > too_many_arguments_threshold {
         span_lint(
             cx,
@@ -6,6 +6,7 @@ use rustc_span::Span;
 
 use clippy_utils::diagnostics::span_lint;
 use clippy_utils::source::snippet_opt;
+use clippy_utils::thresholds::Threshold;
 
 use super::TOO_MANY_LINES;
 
@@ -14,7 +15,8 @@ pub(super) fn check_fn(
     kind: FnKind<'_>,
     span: Span,
     body: &hir::Body<'_>,
-    too_many_lines_threshold: u64,
+    hir_id: hir::HirId,
+    too_many_lines_threshold: &Threshold,
 ) {
     // Closures must be contained in a parent body, which will be checked for `too_many_lines`.
     // Don't check closures for `too_many_lines` to avoid duplicated lints.
@@ -72,6 +74,7 @@ pub(super) fn check_fn(
         }
     }
 
+    let too_many_lines_threshold = too_many_lines_threshold.resolve(cx, hir_id, span);
     if line_count > too_many_lines_threshold {
         span_lint(
             cx,
@@ -0,0 +1,113 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::local_def_path_def_ids;
+use itertools::Itertools;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty;
+use rustc_session::impl_lint_pass;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for struct literals of the types configured in `exhaustive-init-types` which take
+    /// some of their fields from a base, with the struct update syntax, e.g.
+    /// `..Default::default()`.
+    ///
+    /// ### Why is this bad?
+    /// When a field is added to such a type, e.g. a security setting of a configuration struct,
+    /// the literals taking the other fields from a base still compile, and silently give it the
+    /// value of the base. Initializing all the fields explicitly makes the compiler point to each
+    /// literal to revisit.
+    ///
+    /// ### Example
+    /// ```toml
+    /// exhaustive-init-types = ["crate::TlsConfig"]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// let config = TlsConfig {
+    ///     verify_certificates: true,
+    ///     ..Default::default()
+    /// };
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// let config = TlsConfig {
+    ///     verify_certificates: true,
+    ///     min_version: TlsVersion::V1_2,
+    /// };
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub IMPLICIT_FIELD_INIT,
+    restriction,
+    "struct update syntax on a type whose fields must all be initialized explicitly"
+}
+
+impl_lint_pass!(ImplicitFieldInit => [IMPLICIT_FIELD_INIT]);
+
+pub struct ImplicitFieldInit {
+    types: Vec<String>,
+    /// The `DefId`s of `types`.
+    type_ids: FxHashSet<DefId>,
+}
+
+impl ImplicitFieldInit {
+    pub fn new(types: Vec<String>) -> Self {
+        Self {
+            types,
+            type_ids: FxHashSet::default(),
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for ImplicitFieldInit {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        self.type_ids = self
+            .types
+            .iter()
+            .flat_map(|path| local_def_path_def_ids(cx, path))
+            .collect();
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::Struct(_, fields, Some(base)) = expr.kind
+            && !expr.span.from_expansion()
+            && let ty::Adt(def, _) = cx.typeck_results().expr_ty(expr).kind()
+            && self.type_ids.contains(&def.did())
+        {
+            let implicit: Vec<_> = def
+                .non_enum_variant()
+                .fields
+                .iter()
+                .filter(|field| !fields.iter().any(|init| init.ident.name == field.name))
+                .map(|field| format!("`{}`", field.name))
+                .collect();
+            let Some((last, rest)) = implicit.split_last() else {
+                return;
+            };
+            let note = if rest.is_empty() {
+                format!("the field {last} is initialized implicitly")
+            } else {
+                format!(
+                    "the fields {} and {last} are initialized implicitly",
+                    rest.iter().join(", ")
+                )
+            };
+            span_lint_and_then(
+                cx,
+                IMPLICIT_FIELD_INIT,
+                base.span,
+                format!(
+                    "struct update of `{}`, whose fields must all be initialized explicitly",
+                    cx.tcx.item_name(def.did())
+                ),
+                |diag| {
+                    diag.note(note);
+                    diag.help("initialize every field in the literal, without a base");
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::impl_consistency::{fields_used, paired_impls, TraitPair};
+use rustc_hir::{Impl, Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty;
+use rustc_session::declare_lint_pass;
+use rustc_span::{sym, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `Borrow` implementations returning a part of the value, when the `Hash` or
+    /// `PartialEq` implementation of the type also reads other fields.
+    ///
+    /// ### Why is this bad?
+    /// `HashMap` and `HashSet` look up owned keys by their borrowed form, which requires that a
+    /// value and what it borrows as hash and compare the same way. If `Hash` or `PartialEq` read
+    /// fields which the borrowed value doesn't contain, lookups through the borrowed form fail
+    /// or find the wrong entry.
+    ///
+    /// ### Known problems
+    /// Only structs are checked, and only when all the fields `borrow` reads can be determined.
+    /// The borrowed field is assumed to be hashed and compared the same way as the borrowed type.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use std::borrow::Borrow;
+    /// #[derive(PartialEq, Eq, Hash)]
+    /// struct User {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// impl Borrow<str> for User {
+    ///     fn borrow(&self) -> &str {
+    ///         &self.name
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # use std::borrow::Borrow;
+    /// # use std::hash::{Hash, Hasher};
+    /// struct User {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// impl PartialEq for User {
+    ///     fn eq(&self, other: &Self) -> bool {
+    ///         self.name == other.name
+    ///     }
+    /// }
+    ///
+    /// impl Eq for User {}
+    ///
+    /// impl Hash for User {
+    ///     fn hash<H: Hasher>(&self, state: &mut H) {
+    ///         self.name.hash(state);
+    ///     }
+    /// }
+    ///
+    /// impl Borrow<str> for User {
+    ///     fn borrow(&self) -> &str {
+    ///         &self.name
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub INCONSISTENT_BORROW,
+    suspicious,
+    "`Borrow` implementation returning fewer fields than `Hash` or `PartialEq` read"
+}
+
+declare_lint_pass!(InconsistentBorrow => [INCONSISTENT_BORROW]);
+
+impl LateLintPass<'_> for InconsistentBorrow {
+    fn check_item(&mut self, cx: &LateContext<'_>, item: &Item<'_>) {
+        if let ItemKind::Impl(Impl {
+            of_trait: Some(trait_ref),
+            ..
+        }) = item.kind
+            && !item.span.from_expansion()
+            && let Some(trait_id) = trait_ref.trait_def_id()
+            && cx.tcx.is_diagnostic_item(sym::Borrow, trait_id)
+            && let impl_id = item.owner_id.to_def_id()
+            && let Some(borrowed_ty) = cx.tcx.impl_trait_ref(impl_id)
+            && let self_ty = cx.tcx.type_of(impl_id).instantiate_identity()
+            && borrowed_ty.instantiate_identity().args.type_at(1) != self_ty
+            && let ty::Adt(adt, _) = self_ty.kind()
+            && let Some(borrow) = fields_used(cx, impl_id, Symbol::intern("borrow"))
+            && borrow.complete
+        {
+            let mut notes = Vec::new();
+            let impls = paired_impls(cx, impl_id, TraitPair::BorrowHash)
+                .into_iter()
+                .map(|hash_impl| (hash_impl, sym::hash, "Hash"))
+                .chain(
+                    paired_impls(cx, impl_id, TraitPair::PartialEqHash)
+                        .into_iter()
+                        .map(|eq_impl| (eq_impl, sym::eq, "PartialEq")),
+                );
+            for (other_impl, method, trait_name) in impls {
+                let Some(other) = fields_used(cx, other_impl, method) else {
+                    continue;
+                };
+                let fields: Vec<_> = other
+                    .fields
+                    .iter()
+                    .filter(|&idx| !borrow.uses(idx))
+                    .map(|idx| format!("`{}`", adt.non_enum_variant().fields[idx].name))
+                    .collect();
+                if !fields.is_empty() {
+                    notes.push((other_impl, trait_name, fields.join(", ")));
+                }
+            }
+            if notes.is_empty() {
+                return;
+            }
+            span_lint_and_then(
+                cx,
+                INCONSISTENT_BORROW,
+                item.span,
+                "this `Borrow` implementation doesn't return fields which `Hash` or `PartialEq` read",
+                |diag| {
+                    for (other_impl, trait_name, fields) in notes {
+                        let msg = format!("{fields} also read by this `{trait_name}` implementation");
+                        if let Some(other_impl) = other_impl.as_local() {
+                            diag.span_note(cx.tcx.def_span(other_impl), msg);
+                        } else {
+                            diag.note(msg);
+                        }
+                    }
+                    diag.help("a value and what it borrows as must hash and compare the same way");
+                },
+            );
+        }
+    }
+}
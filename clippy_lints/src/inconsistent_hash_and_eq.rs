@@ -0,0 +1,188 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::impl_consistency::{fields_used, paired_impls, TraitPair};
+use rustc_hir::{Impl, Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty;
+use rustc_session::declare_lint_pass;
+use rustc_span::sym;
+use rustc_target::abi::FieldIdx;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for manual `Hash` implementations which hash a field that the `PartialEq`
+    /// implementation of the same type doesn't compare.
+    ///
+    /// ### Why is this bad?
+    /// Values which are equal must have the same hash. If the hash depends on a field that
+    /// equality ignores, two equal values can hash differently, which breaks `HashMap` and
+    /// `HashSet`.
+    ///
+    /// ### Known problems
+    /// Only structs are checked, and only when all the fields `PartialEq` reads can be determined.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use std::hash::{Hash, Hasher};
+    /// struct Entry {
+    ///     id: u32,
+    ///     hits: u32,
+    /// }
+    ///
+    /// impl PartialEq for Entry {
+    ///     fn eq(&self, other: &Self) -> bool {
+    ///         self.id == other.id
+    ///     }
+    /// }
+    ///
+    /// impl Hash for Entry {
+    ///     fn hash<H: Hasher>(&self, state: &mut H) {
+    ///         self.id.hash(state);
+    ///         self.hits.hash(state);
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # use std::hash::{Hash, Hasher};
+    /// # struct Entry {
+    /// #     id: u32,
+    /// #     hits: u32,
+    /// # }
+    /// #
+    /// # impl PartialEq for Entry {
+    /// #     fn eq(&self, other: &Self) -> bool {
+    /// #         self.id == other.id
+    /// #     }
+    /// # }
+    /// #
+    /// impl Hash for Entry {
+    ///     fn hash<H: Hasher>(&self, state: &mut H) {
+    ///         self.id.hash(state);
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub INCONSISTENT_HASH_AND_EQ,
+    suspicious,
+    "manual `Hash` implementation hashing a field `PartialEq` doesn't compare"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for manual `Hash` implementations which don't hash some of the fields that the
+    /// `PartialEq` implementation of the same type compares. The opposite case, hashing fields
+    /// which aren't compared, is linted by `inconsistent_hash_and_eq`.
+    ///
+    /// ### Why is this bad?
+    /// It's allowed by the `Hash` contract, but values which differ only by the fields which
+    /// aren't hashed all have the same hash, and collide in a `HashMap`. It's also often a sign
+    /// that a field was added to one implementation and forgotten in the other.
+    ///
+    /// ### Known problems
+    /// Only structs are checked, and only when all the fields `Hash` reads can be determined.
+    /// Hashing fewer fields is sometimes deliberate, e.g. when the other fields are expensive to
+    /// hash and rarely differ.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use std::hash::{Hash, Hasher};
+    /// #[derive(PartialEq)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// impl Hash for Point {
+    ///     fn hash<H: Hasher>(&self, state: &mut H) {
+    ///         self.x.hash(state);
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # use std::hash::{Hash, Hasher};
+    /// # #[derive(PartialEq)]
+    /// # struct Point {
+    /// #     x: i32,
+    /// #     y: i32,
+    /// # }
+    /// impl Hash for Point {
+    ///     fn hash<H: Hasher>(&self, state: &mut H) {
+    ///         self.x.hash(state);
+    ///         self.y.hash(state);
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub HASH_EQ_FIELD_MISMATCH,
+    pedantic,
+    "manual `Hash` implementation not hashing a field `PartialEq` compares"
+}
+
+declare_lint_pass!(InconsistentHashAndEq => [INCONSISTENT_HASH_AND_EQ, HASH_EQ_FIELD_MISMATCH]);
+
+impl LateLintPass<'_> for InconsistentHashAndEq {
+    fn check_item(&mut self, cx: &LateContext<'_>, item: &Item<'_>) {
+        if let ItemKind::Impl(Impl {
+            of_trait: Some(trait_ref),
+            ..
+        }) = item.kind
+            && !item.span.from_expansion()
+            && let Some(trait_id) = trait_ref.trait_def_id()
+            && cx.tcx.is_diagnostic_item(sym::Hash, trait_id)
+            && let impl_id = item.owner_id.to_def_id()
+            && let ty::Adt(adt, _) = cx.tcx.type_of(impl_id).instantiate_identity().kind()
+            && let Some(hash) = fields_used(cx, impl_id, sym::hash)
+        {
+            for eq_impl in paired_impls(cx, impl_id, TraitPair::PartialEqHash) {
+                let Some(eq) = fields_used(cx, eq_impl, sym::eq) else {
+                    continue;
+                };
+                let field_names = |fields: &[FieldIdx]| -> String {
+                    let names: Vec<_> = fields
+                        .iter()
+                        .map(|&idx| format!("`{}`", adt.non_enum_variant().fields[idx].name))
+                        .collect();
+                    names.join(", ")
+                };
+                let not_compared: Vec<_> = hash.fields.iter().filter(|&idx| !eq.uses(idx)).collect();
+                let not_hashed: Vec<_> = eq.fields.iter().filter(|&idx| !hash.uses(idx)).collect();
+                if eq.complete && !not_compared.is_empty() {
+                    let fields = field_names(&not_compared);
+                    span_lint_and_then(
+                        cx,
+                        INCONSISTENT_HASH_AND_EQ,
+                        item.span,
+                        "this `Hash` implementation hashes fields which `PartialEq` doesn't compare",
+                        |diag| {
+                            if let Some(eq_impl) = eq_impl.as_local() {
+                                diag.span_note(
+                                    cx.tcx.def_span(eq_impl),
+                                    format!("{fields} not compared by this `PartialEq` implementation"),
+                                );
+                            }
+                            diag.help("equal values must have the same hash");
+                        },
+                    );
+                } else if hash.complete && !not_hashed.is_empty() {
+                    let fields = field_names(&not_hashed);
+                    span_lint_and_then(
+                        cx,
+                        HASH_EQ_FIELD_MISMATCH,
+                        item.span,
+                        "this `Hash` implementation doesn't hash fields which `PartialEq` compares",
+                        |diag| {
+                            if let Some(eq_impl) = eq_impl.as_local() {
+                                diag.span_note(
+                                    cx.tcx.def_span(eq_impl),
+                                    format!("{fields} compared by this `PartialEq` implementation, but not hashed"),
+                                );
+                            }
+                            diag.help("values which only differ by these fields have the same hash");
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,12 +1,14 @@
 //! lint on indexing and slicing operations
 
-use clippy_utils::consts::{constant, Constant};
+use clippy_utils::consts::{constant, constant_len, Constant};
 use clippy_utils::diagnostics::{span_lint, span_lint_and_then};
 use clippy_utils::higher;
+use clippy_utils::intervals::expr_interval;
 use rustc_ast::ast::RangeLimits;
 use rustc_hir::{Expr, ExprKind};
-use rustc_lint::{LateContext, LateLintPass};
-use rustc_middle::ty;
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, Ty};
 use rustc_session::impl_lint_pass;
 
 declare_clippy_lint! {
@@ -99,17 +101,20 @@ impl IndexingSlicing {
 
 impl<'tcx> LateLintPass<'tcx> for IndexingSlicing {
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
-        if self.suppress_restriction_lint_in_const && cx.tcx.hir().is_inside_const_context(expr.hir_id) {
+        if self.suppress_restriction_lint_in_const && cx.tcx.hir().is_inside_const_context(expr.hir_id)
+            || in_external_macro(cx.sess(), expr.span)
+        {
             return;
         }
 
         if let ExprKind::Index(array, index, _) = &expr.kind {
             let note = "the suggestion might not be applicable in constant blocks";
             let ty = cx.typeck_results().expr_ty(array).peel_refs();
+            let len = known_len(cx, array, ty);
             if let Some(range) = higher::Range::hir(index) {
                 // Ranged indexes, i.e., &x[n..m], &x[n..], &x[..n] and &x[..]
-                if let ty::Array(_, s) = ty.kind() {
-                    let size: u128 = if let Some(size) = s.try_eval_target_usize(cx.tcx, cx.param_env) {
+                if ty.is_array() || len.is_some() {
+                    let size: u128 = if let Some(size) = len {
                         size.into()
                     } else {
                         return;
@@ -164,7 +169,7 @@ impl<'tcx> LateLintPass<'tcx> for IndexingSlicing {
                 });
             } else {
                 // Catchall non-range index, i.e., [n] or [n << m]
-                if let ty::Array(..) = ty.kind() {
+                if ty.is_array() || len.is_some() {
                     // Index is a const block.
                     if let ExprKind::ConstBlock(..) = index.kind {
                         return;
@@ -177,8 +182,7 @@ impl<'tcx> LateLintPass<'tcx> for IndexingSlicing {
                             && off <= usize::MAX as u128
                             && let ty::Uint(utype) = cx.typeck_results().expr_ty(index).kind()
                             && *utype == ty::UintTy::Usize
-                            && let ty::Array(_, s) = ty.kind()
-                            && let Some(size) = s.try_eval_target_usize(cx.tcx, cx.param_env)
+                            && let Some(size) = len
                         {
                             // get constant offset and check whether it is in bounds
                             let off = usize::try_from(off).unwrap();
@@ -191,6 +195,12 @@ impl<'tcx> LateLintPass<'tcx> for IndexingSlicing {
                         // Let rustc's `const_err` lint handle constant `usize` indexing on arrays.
                         return;
                     }
+                    // Index is bounded by its type, a mask, a modulo or the conditions it's used in.
+                    if let Some(len) = len
+                        && expr_interval(cx, index).is_some_and(|i| i.is_index_of(len.into()))
+                    {
+                        return;
+                    }
                 }
 
                 span_lint_and_then(cx, INDEXING_SLICING, expr.span, "indexing may panic", |diag| {
@@ -205,6 +215,16 @@ impl<'tcx> LateLintPass<'tcx> for IndexingSlicing {
     }
 }
 
+/// Returns the length of the indexed value if it's an array, or a slice whose length is known from
+/// the constant it comes from.
+fn known_len<'tcx>(cx: &LateContext<'tcx>, array: &Expr<'_>, ty: Ty<'tcx>) -> Option<u64> {
+    match ty.kind() {
+        ty::Array(_, s) => s.try_eval_target_usize(cx.tcx, cx.param_env),
+        ty::Slice(_) | ty::Str => constant_len(cx, array),
+        _ => None,
+    }
+}
+
 /// Returns a tuple of options with the start and end (exclusive) values of
 /// the range. If the start or end is not constant, None is returned.
 fn to_const_range(cx: &LateContext<'_>, range: higher::Range<'_>, array_size: u128) -> (Option<u128>, Option<u128>) {
@@ -1,10 +1,12 @@
 use clippy_config::msrvs::{self, Msrv};
-use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::consts::constant;
+use clippy_utils::diagnostics::{span_lint_and_sugg, span_lint_and_then};
+use clippy_utils::macros::{find_assert_args, root_macro_call_first_node};
 use clippy_utils::source::snippet_with_context;
 use clippy_utils::sugg::Sugg;
-use clippy_utils::ty;
+use clippy_utils::{eq_expr_value, higher, ty};
 use rustc_errors::Applicability;
-use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_hir::{BinOpKind, Expr, ExprKind, Node, StmtKind, UnOp};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_session::impl_lint_pass;
 use rustc_span::source_map::Spanned;
@@ -63,6 +65,46 @@ declare_clippy_lint! {
     "finds unchecked subtraction of a 'Duration' from an 'Instant'"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Lints subtractions which can underflow between two `Instant`s or two `Duration`s, and the
+    /// underflowing integer subtractions passed to the constructors of `Duration`, unless a
+    /// comparison of the operands guards the subtraction.
+    ///
+    /// ### Why is this bad?
+    /// The subtraction of two `Duration`s panics when the result would be negative, and so does
+    /// the integer subtraction in debug builds, while it wraps around to a huge `Duration` in
+    /// release builds. The subtraction of two `Instant`s currently saturates to zero, but this
+    /// is documented to possibly panic in the future. As instants come from an unspecified clock,
+    /// their order often isn't what it looks like.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use std::time::{Duration, Instant};
+    /// # let (start, end) = (Instant::now(), Instant::now());
+    /// # let (timeout, elapsed) = (Duration::from_secs(5), Duration::from_secs(1));
+    /// # let (deadline, now) = (10_u64, 5_u64);
+    /// let taken = end - start;
+    /// let remaining = timeout - elapsed;
+    /// let left = Duration::from_secs(deadline - now);
+    /// ```
+    ///
+    /// Use instead:
+    /// ```no_run
+    /// # use std::time::{Duration, Instant};
+    /// # let (start, end) = (Instant::now(), Instant::now());
+    /// # let (timeout, elapsed) = (Duration::from_secs(5), Duration::from_secs(1));
+    /// # let (deadline, now) = (10_u64, 5_u64);
+    /// let taken = end.saturating_duration_since(start);
+    /// let remaining = timeout.saturating_sub(elapsed);
+    /// let left = Duration::from_secs(deadline.saturating_sub(now));
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub UNGUARDED_TIME_SUBTRACTION,
+    pedantic,
+    "subtraction of `Instant`s or `Duration`s, or of the integers of a `Duration`, which can underflow"
+}
+
 pub struct InstantSubtraction {
     msrv: Msrv,
 }
@@ -74,7 +116,11 @@ impl InstantSubtraction {
     }
 }
 
-impl_lint_pass!(InstantSubtraction => [MANUAL_INSTANT_ELAPSED, UNCHECKED_DURATION_SUBTRACTION]);
+impl_lint_pass!(InstantSubtraction => [
+    MANUAL_INSTANT_ELAPSED,
+    UNCHECKED_DURATION_SUBTRACTION,
+    UNGUARDED_TIME_SUBTRACTION,
+]);
 
 impl LateLintPass<'_> for InstantSubtraction {
     fn check_expr(&mut self, cx: &LateContext<'_>, expr: &'_ Expr<'_>) {
@@ -97,6 +143,62 @@ impl LateLintPass<'_> for InstantSubtraction {
                 && is_a_duration(cx, rhs)
             {
                 print_unchecked_duration_subtraction_sugg(cx, lhs, rhs, expr);
+            } else if !expr.span.from_expansion()
+                && (is_an_instant(cx, lhs) && is_an_instant(cx, rhs)
+                    || is_a_duration(cx, lhs) && is_a_duration(cx, rhs))
+                && !is_guarded(cx, expr, lhs, rhs)
+            {
+                print_unguarded_time_subtraction_sugg(cx, expr, lhs, rhs);
+            }
+        } else if let ExprKind::AssignOp(
+            Spanned {
+                node: BinOpKind::Sub, ..
+            },
+            lhs,
+            rhs,
+        ) = expr.kind
+            && !expr.span.from_expansion()
+            && is_a_duration(cx, lhs)
+            && is_a_duration(cx, rhs)
+            && !is_guarded(cx, expr, lhs, rhs)
+        {
+            span_lint_and_then(
+                cx,
+                UNGUARDED_TIME_SUBTRACTION,
+                expr.span,
+                "this subtraction of `Duration`s can underflow",
+                |diag| {
+                    let mut applicability = Applicability::MaybeIncorrect;
+                    let ctxt = expr.span.ctxt();
+                    let lhs = snippet_with_context(cx, lhs.span, ctxt, "..", &mut applicability).0;
+                    let rhs = snippet_with_context(cx, rhs.span, ctxt, "..", &mut applicability).0;
+                    diag.span_suggestion(
+                        expr.span,
+                        "use `saturating_sub`, or `checked_sub` to handle the underflow",
+                        format!("{lhs} = {lhs}.saturating_sub({rhs})"),
+                        applicability,
+                    );
+                },
+            );
+        } else if let ExprKind::Call(func, args) = expr.kind
+            && !expr.span.from_expansion()
+            && is_duration_constructor(cx, func)
+        {
+            for arg in args {
+                if let ExprKind::Binary(
+                    Spanned {
+                        node: BinOpKind::Sub, ..
+                    },
+                    lhs,
+                    rhs,
+                ) = arg.kind
+                    && cx.typeck_results().expr_ty(arg).is_integral()
+                    && !cx.typeck_results().expr_ty(arg).is_signed()
+                    && constant(cx, cx.typeck_results(), arg).is_none()
+                    && !is_guarded(cx, arg, lhs, rhs)
+                {
+                    print_unguarded_time_subtraction_sugg(cx, arg, lhs, rhs);
+                }
             }
         }
     }
@@ -159,3 +261,124 @@ fn print_unchecked_duration_subtraction_sugg(
         applicability,
     );
 }
+
+fn is_duration_constructor(cx: &LateContext<'_>, func: &Expr<'_>) -> bool {
+    if let Some(fn_id) = clippy_utils::path_def_id(cx, func)
+        && let Some(impl_id) = cx.tcx.impl_of_method(fn_id)
+        && ty::is_type_diagnostic_item(cx, cx.tcx.type_of(impl_id).instantiate_identity(), sym::Duration)
+    {
+        matches!(
+            cx.tcx.item_name(fn_id).as_str(),
+            "new" | "from_secs" | "from_millis" | "from_micros" | "from_nanos"
+        )
+    } else {
+        false
+    }
+}
+
+/// Checks if the subtraction `lhs - rhs` only runs when `lhs` is at least `rhs`: in the `then`
+/// branch of an `if` comparing them, or after an `if` leaving the block or an `assert!` when `lhs`
+/// is less than `rhs`.
+fn is_guarded(cx: &LateContext<'_>, expr: &Expr<'_>, lhs: &Expr<'_>, rhs: &Expr<'_>) -> bool {
+    let mut child_id = expr.hir_id;
+    for (parent_id, node) in cx.tcx.hir().parent_iter(expr.hir_id) {
+        match node {
+            Node::Expr(parent) => {
+                if let Some(higher::If { cond, then, .. }) = higher::If::hir(parent)
+                    && then.hir_id == child_id
+                    && implies_ge(cx, cond, lhs, rhs, true)
+                {
+                    return true;
+                }
+            },
+            Node::Block(block) => {
+                let guards = block
+                    .stmts
+                    .iter()
+                    .take_while(|stmt| stmt.hir_id != child_id)
+                    .filter_map(|stmt| match stmt.kind {
+                        StmtKind::Expr(e) | StmtKind::Semi(e) => Some(e),
+                        _ => None,
+                    });
+                for e in guards {
+                    if let Some(higher::If {
+                        cond,
+                        then,
+                        r#else: None,
+                    }) = higher::If::hir(e)
+                        && cx.typeck_results().expr_ty(then).is_never()
+                        && implies_ge(cx, cond, lhs, rhs, false)
+                    {
+                        return true;
+                    }
+                    if let Some(macro_call) = root_macro_call_first_node(cx, e)
+                        && cx.tcx.is_diagnostic_item(sym::assert_macro, macro_call.def_id)
+                        && let Some((cond, _)) = find_assert_args(cx, e, macro_call.expn)
+                        && implies_ge(cx, cond, lhs, rhs, true)
+                    {
+                        return true;
+                    }
+                }
+            },
+            Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_) => break,
+            _ => {},
+        }
+        child_id = parent_id;
+    }
+    false
+}
+
+/// Checks if `cond` evaluating to `value` implies `lhs >= rhs`.
+fn implies_ge(cx: &LateContext<'_>, cond: &Expr<'_>, lhs: &Expr<'_>, rhs: &Expr<'_>, value: bool) -> bool {
+    match cond.kind {
+        ExprKind::Unary(UnOp::Not, inner) => implies_ge(cx, inner, lhs, rhs, !value),
+        ExprKind::DropTemps(inner) => implies_ge(cx, inner, lhs, rhs, value),
+        ExprKind::Binary(op, a, b) => match (op.node, value) {
+            (BinOpKind::And, true) | (BinOpKind::Or, false) => {
+                implies_ge(cx, a, lhs, rhs, value) || implies_ge(cx, b, lhs, rhs, value)
+            },
+            (BinOpKind::Gt | BinOpKind::Ge, true) | (BinOpKind::Lt | BinOpKind::Le, false) => {
+                eq_expr_value(cx, a, lhs) && eq_expr_value(cx, b, rhs)
+            },
+            (BinOpKind::Lt | BinOpKind::Le, true) | (BinOpKind::Gt | BinOpKind::Ge, false) => {
+                eq_expr_value(cx, a, rhs) && eq_expr_value(cx, b, lhs)
+            },
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn print_unguarded_time_subtraction_sugg(cx: &LateContext<'_>, expr: &Expr<'_>, lhs: &Expr<'_>, rhs: &Expr<'_>) {
+    let (msg, saturating, checked) = if is_an_instant(cx, lhs) {
+        (
+            "this subtraction of `Instant`s assumes their order",
+            "saturating_duration_since",
+            "checked_duration_since",
+        )
+    } else if is_a_duration(cx, lhs) {
+        (
+            "this subtraction of `Duration`s can underflow",
+            "saturating_sub",
+            "checked_sub",
+        )
+    } else {
+        (
+            "this subtraction can underflow, creating a huge `Duration` in release builds",
+            "saturating_sub",
+            "checked_sub",
+        )
+    };
+    span_lint_and_then(cx, UNGUARDED_TIME_SUBTRACTION, expr.span, msg, |diag| {
+        let mut applicability = Applicability::MaybeIncorrect;
+        let ctxt = expr.span.ctxt();
+        let lhs = Sugg::hir_with_context(cx, lhs, ctxt, "..", &mut applicability);
+        let rhs = snippet_with_context(cx, rhs.span, ctxt, "..", &mut applicability).0;
+        diag.span_suggestion(
+            expr.span,
+            format!("use `{saturating}`, or `{checked}` to handle the underflow"),
+            format!("{}.{saturating}({rhs})", lhs.maybe_par()),
+            applicability,
+        );
+    });
+}
@@ -1,7 +1,8 @@
 use clippy_utils::diagnostics::span_lint;
 use rustc_ast::BinOpKind;
 use rustc_hir::{Expr, ExprKind};
-use rustc_lint::{LateContext, LateLintPass};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
 use rustc_middle::ty::{self};
 use rustc_session::declare_lint_pass;
 
@@ -32,6 +33,10 @@ declare_lint_pass!(IntegerDivisionRemainderUsed => [INTEGER_DIVISION_REMAINDER_U
 
 impl LateLintPass<'_> for IntegerDivisionRemainderUsed {
     fn check_expr(&mut self, cx: &LateContext<'_>, expr: &Expr<'_>) {
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+
         if let ExprKind::Binary(op, lhs, rhs) = &expr.kind
             && let BinOpKind::Div | BinOpKind::Rem = op.node
             && let lhs_ty = cx.typeck_results().expr_ty(lhs)
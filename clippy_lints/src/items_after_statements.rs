@@ -1,9 +1,9 @@
 //! lint when items are used after statements
 
 use clippy_utils::diagnostics::span_lint_hir;
+use clippy_utils::macros::{lintability, MacroPolicy};
 use rustc_hir::{Block, ItemKind, StmtKind};
-use rustc_lint::{LateContext, LateLintPass, LintContext};
-use rustc_middle::lint::in_external_macro;
+use rustc_lint::{LateContext, LateLintPass};
 use rustc_session::declare_lint_pass;
 
 declare_clippy_lint! {
@@ -54,7 +54,7 @@ declare_lint_pass!(ItemsAfterStatements => [ITEMS_AFTER_STATEMENTS]);
 
 impl LateLintPass<'_> for ItemsAfterStatements {
     fn check_block(&mut self, cx: &LateContext<'_>, block: &Block<'_>) {
-        if in_external_macro(cx.sess(), block.span) {
+        if !lintability(cx, block.span, MacroPolicy::LOCAL_MACROS).should_lint() {
             return;
         }
 
@@ -68,7 +68,9 @@ impl LateLintPass<'_> for ItemsAfterStatements {
         for stmt in stmts {
             if let StmtKind::Item(item_id) = stmt.kind {
                 let item = cx.tcx.hir().item(item_id);
-                if in_external_macro(cx.sess(), item.span) || !item.span.eq_ctxt(block.span) {
+                if !lintability(cx, item.span, MacroPolicy::LOCAL_MACROS).should_lint()
+                    || !item.span.eq_ctxt(block.span)
+                {
                     return;
                 }
                 if let ItemKind::Macro(..) = item.kind {
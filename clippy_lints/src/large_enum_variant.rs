@@ -2,7 +2,7 @@
 
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::source::snippet_with_applicability;
-use clippy_utils::ty::{approx_ty_size, is_copy, AdtVariantInfo};
+use clippy_utils::ty::{is_copy, layout_of_or_estimate, AdtVariantInfo};
 use rustc_errors::Applicability;
 use rustc_hir::{Item, ItemKind};
 use rustc_lint::{LateContext, LateLintPass};
@@ -101,7 +101,10 @@ impl<'tcx> LateLintPass<'tcx> for LargeEnumVariant {
                     |diag| {
                         diag.span_label(
                             item.span,
-                            format!("the entire enum is at least {} bytes", approx_ty_size(cx, ty)),
+                            format!(
+                                "the entire enum is at least {} bytes",
+                                layout_of_or_estimate(cx, ty).size
+                            ),
                         );
                         diag.span_label(
                             def.variants[variants_size[0].ind].span,
@@ -0,0 +1,237 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use rustc_errors::Applicability;
+use rustc_hir::{BindingMode, LangItem, Mutability, Node, Pat, PatKind, QPath};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, TypeckResults};
+use rustc_session::declare_lint_pass;
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `ref` bindings which are redundant with the default binding modes of Rust 2018,
+    /// e.g. `Some(ref y)` matched against a reference, and for `&` patterns whose bindings all
+    /// take a reference, possibly nested, e.g. `&Some((ref a, ref b))`.
+    ///
+    /// ### Why is this bad?
+    /// When a pattern without `&` is matched against a reference, its bindings already borrow the
+    /// matched value. The explicit `&` and `ref` are leftovers of older editions which add noise.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// let x = Some((1, String::new()));
+    /// match &x {
+    ///     Some((_, ref s)) => println!("{s}"),
+    ///     None => {},
+    /// }
+    /// if let &Some((ref n, ref s)) = &x {
+    ///     println!("{n} {s}");
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// let x = Some((1, String::new()));
+    /// match &x {
+    ///     Some((_, s)) => println!("{s}"),
+    ///     None => {},
+    /// }
+    /// if let Some((n, s)) = &x {
+    ///     println!("{n} {s}");
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub LEGACY_REF_PATTERN,
+    restriction,
+    "`ref` bindings and `&` patterns made redundant by the default binding modes"
+}
+
+declare_lint_pass!(LegacyRefPattern => [LEGACY_REF_PATTERN]);
+
+impl<'tcx> LateLintPass<'tcx> for LegacyRefPattern {
+    fn check_pat(&mut self, cx: &LateContext<'tcx>, pat: &'tcx Pat<'_>) {
+        // Only the whole patterns are checked, their subpatterns are checked with them. The
+        // patterns of `for` loops are in the `Some(..)` pattern of the desugaring.
+        if pat.span.from_expansion() {
+            return;
+        }
+        let parent = match cx.tcx.parent_hir_node(pat.hir_id) {
+            Node::PatField(_) => cx.tcx.parent_hir_node(cx.tcx.parent_hir_id(pat.hir_id)),
+            parent => parent,
+        };
+        if let Node::Pat(parent) = parent
+            && !matches!(
+                parent.kind,
+                PatKind::Struct(QPath::LangItem(LangItem::OptionSome, _), ..)
+            )
+        {
+            return;
+        }
+        let mut finder = RedundantRefs {
+            cx,
+            typeck_results: cx.typeck_results(),
+            removable: Vec::new(),
+        };
+        finder.check(pat, DefaultMode::Move, false);
+        if let Some(&first) = finder.removable.first() {
+            span_lint_and_then(
+                cx,
+                LEGACY_REF_PATTERN,
+                first,
+                "`ref` is redundant when the value is matched through a reference",
+                |diag| {
+                    diag.multipart_suggestion(
+                        "remove the `ref`",
+                        finder.removable.into_iter().map(|span| (span, String::new())).collect(),
+                        Applicability::MachineApplicable,
+                    );
+                },
+            );
+        }
+    }
+}
+
+/// The binding mode applying to the bindings without `ref` or `ref mut`
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DefaultMode {
+    Move,
+    Ref,
+    RefMut,
+}
+
+struct RedundantRefs<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    /// The spans of the `ref ` of the bindings which are already borrowing
+    removable: Vec<Span>,
+}
+
+impl<'a, 'tcx> RedundantRefs<'a, 'tcx> {
+    fn check(&mut self, pat: &Pat<'_>, mut mode: DefaultMode, in_or: bool) {
+        if pat.span.from_expansion() {
+            return;
+        }
+        // The references implicitly dereferenced to match `pat`
+        for ty in self
+            .typeck_results
+            .pat_adjustments()
+            .get(pat.hir_id)
+            .into_iter()
+            .flatten()
+        {
+            match ty.kind() {
+                ty::Ref(_, _, Mutability::Not) => mode = DefaultMode::Ref,
+                ty::Ref(_, _, Mutability::Mut) if mode == DefaultMode::Move => mode = DefaultMode::RefMut,
+                _ => {},
+            }
+        }
+        match pat.kind {
+            // The bindings of the alternatives must have the same binding modes, which is left to
+            // the user.
+            PatKind::Binding(BindingMode::REF, _, ident, sub) => {
+                if mode == DefaultMode::Ref && !in_or {
+                    self.removable.push(pat.span.until(ident.span));
+                }
+                if let Some(sub) = sub {
+                    self.check(sub, mode, in_or);
+                }
+            },
+            PatKind::Binding(_, _, _, Some(sub)) => self.check(sub, mode, in_or),
+            PatKind::Ref(sub, Mutability::Not) => {
+                if mode == DefaultMode::Move
+                    && !in_or
+                    && !is_needless_borrowed_ref(sub)
+                    && let Some(refs) = ref_bindings(sub)
+                {
+                    span_lint_and_then(
+                        self.cx,
+                        LEGACY_REF_PATTERN,
+                        pat.span,
+                        "dereferencing a pattern whose bindings all take a reference",
+                        |diag| {
+                            let mut suggestions = vec![(pat.span.until(sub.span), String::new())];
+                            suggestions.extend(refs.into_iter().map(|span| (span, String::new())));
+                            diag.multipart_suggestion(
+                                "remove the `&` and `ref` parts",
+                                suggestions,
+                                Applicability::MachineApplicable,
+                            );
+                        },
+                    );
+                } else {
+                    self.check(sub, DefaultMode::Move, in_or);
+                }
+            },
+            PatKind::Ref(sub, Mutability::Mut) | PatKind::Box(sub) | PatKind::Deref(sub) => {
+                self.check(sub, DefaultMode::Move, in_or);
+            },
+            PatKind::Or(pats) => {
+                for pat in pats {
+                    self.check(pat, mode, true);
+                }
+            },
+            PatKind::Struct(_, fields, _) => {
+                for field in fields {
+                    self.check(field.pat, mode, in_or);
+                }
+            },
+            PatKind::TupleStruct(_, pats, _) | PatKind::Tuple(pats, _) => {
+                for pat in pats {
+                    self.check(pat, mode, in_or);
+                }
+            },
+            PatKind::Slice(before, middle, after) => {
+                for pat in before.iter().chain(middle).chain(after) {
+                    self.check(pat, mode, in_or);
+                }
+            },
+            PatKind::Wild
+            | PatKind::Binding(..)
+            | PatKind::Never
+            | PatKind::Path(_)
+            | PatKind::Lit(_)
+            | PatKind::Range(..)
+            | PatKind::Err(_) => {},
+        }
+    }
+}
+
+/// Returns the spans of the `ref ` of the bindings of `pat` if it has some, and they all take a
+/// shared reference, so that they would be the same without the `&` of the parent pattern.
+fn ref_bindings(pat: &Pat<'_>) -> Option<Vec<Span>> {
+    let mut refs = Vec::new();
+    let mut removable = true;
+    pat.walk(|pat| {
+        if pat.span.from_expansion() {
+            removable = false;
+        }
+        match pat.kind {
+            PatKind::Binding(BindingMode::REF, _, ident, _) => refs.push(pat.span.until(ident.span)),
+            // A binding by value or by `ref mut`, or a reference or box which would be matched with
+            // another default binding mode.
+            PatKind::Binding(..) | PatKind::Ref(..) | PatKind::Box(_) | PatKind::Deref(_) => removable = false,
+            _ => {},
+        }
+        removable
+    });
+    (removable && !refs.is_empty()).then_some(refs)
+}
+
+/// Checks if `needless_borrowed_reference` lints the `&` pattern around `pat`: `&ref a` or a
+/// slice, tuple or struct pattern whose subpatterns are all `ref` bindings or wildcards.
+fn is_needless_borrowed_ref(pat: &Pat<'_>) -> bool {
+    let is_ref_or_wild =
+        |pat: &Pat<'_>| matches!(pat.kind, PatKind::Binding(BindingMode::REF, _, _, None) | PatKind::Wild);
+    match pat.kind {
+        PatKind::Binding(BindingMode::REF, _, _, None) => true,
+        PatKind::Slice(
+            before,
+            None
+            | Some(Pat {
+                kind: PatKind::Wild, ..
+            }),
+            after,
+        ) => before.iter().chain(after).all(is_ref_or_wild),
+        PatKind::Tuple(pats, _) | PatKind::TupleStruct(_, pats, _) => pats.iter().all(is_ref_or_wild),
+        PatKind::Struct(_, fields, _) => fields.iter().all(|field| is_ref_or_wild(field.pat)),
+        _ => false,
+    }
+}
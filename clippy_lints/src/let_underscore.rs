@@ -1,12 +1,13 @@
 use clippy_utils::diagnostics::span_lint_and_help;
-use clippy_utils::ty::{implements_trait, is_must_use_ty, match_type};
-use clippy_utils::{is_from_proc_macro, is_must_use_func_call, paths};
+use clippy_utils::ty::{implements_trait, is_must_use_ty, significant_drop, SignificantDropKind};
+use clippy_utils::{def_path_def_ids, is_from_proc_macro, is_must_use_func_call};
+use rustc_hir::def_id::DefIdSet;
 use rustc_hir::{LetStmt, LocalSource, PatKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::lint::in_external_macro;
-use rustc_middle::ty::{GenericArgKind, IsSuggestable};
-use rustc_session::declare_lint_pass;
-use rustc_span::{BytePos, Span};
+use rustc_middle::ty::IsSuggestable;
+use rustc_session::impl_lint_pass;
+use rustc_span::{sym, BytePos, Span};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -38,6 +39,9 @@ declare_clippy_lint! {
     /// `parking_lot`. For `std` locks see the `rustc` lint
     /// [`let_underscore_lock`](https://doc.rust-lang.org/nightly/rustc/lints/listing/deny-by-default.html#let-underscore-lock)
     ///
+    /// Types marked `#[clippy::has_significant_drop]` or listed in the `significant-drop-types`
+    /// configuration are checked as well.
+    ///
     /// ### Why is this bad?
     /// This statement immediately drops the lock instead of
     /// extending its lifetime to the end of the scope, which is often not intended.
@@ -129,15 +133,30 @@ declare_clippy_lint! {
     "non-binding `let` without a type annotation"
 }
 
-declare_lint_pass!(LetUnderscore => [LET_UNDERSCORE_MUST_USE, LET_UNDERSCORE_LOCK, LET_UNDERSCORE_FUTURE, LET_UNDERSCORE_UNTYPED]);
+pub struct LetUnderscore {
+    significant_drop_types: Vec<String>,
+    significant_drop_def_ids: DefIdSet,
+}
+
+impl LetUnderscore {
+    pub fn new(significant_drop_types: Vec<String>) -> Self {
+        Self {
+            significant_drop_types,
+            significant_drop_def_ids: DefIdSet::default(),
+        }
+    }
+}
 
-const SYNC_GUARD_PATHS: [&[&str]; 3] = [
-    &paths::PARKING_LOT_MUTEX_GUARD,
-    &paths::PARKING_LOT_RWLOCK_READ_GUARD,
-    &paths::PARKING_LOT_RWLOCK_WRITE_GUARD,
-];
+impl_lint_pass!(LetUnderscore => [LET_UNDERSCORE_MUST_USE, LET_UNDERSCORE_LOCK, LET_UNDERSCORE_FUTURE, LET_UNDERSCORE_UNTYPED]);
 
 impl<'tcx> LateLintPass<'tcx> for LetUnderscore {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for path in &self.significant_drop_types {
+            let segs: Vec<_> = path.split("::").collect();
+            self.significant_drop_def_ids.extend(def_path_def_ids(cx, &segs));
+        }
+    }
+
     fn check_local(&mut self, cx: &LateContext<'tcx>, local: &LetStmt<'tcx>) {
         if matches!(local.source, LocalSource::Normal)
             && !in_external_macro(cx.tcx.sess, local.span)
@@ -145,16 +164,24 @@ impl<'tcx> LateLintPass<'tcx> for LetUnderscore {
             && let Some(init) = local.init
         {
             let init_ty = cx.typeck_results().expr_ty(init);
-            let contains_sync_guard = init_ty.walk().any(|inner| match inner.unpack() {
-                GenericArgKind::Type(inner_ty) => SYNC_GUARD_PATHS.iter().any(|path| match_type(cx, inner_ty, path)),
-                GenericArgKind::Lifetime(_) | GenericArgKind::Const(_) => false,
+            let sig_drop_msg = significant_drop(cx, init_ty, &self.significant_drop_def_ids).and_then(|sig_drop| {
+                match sig_drop.kind {
+                    // `std` locks are linted by rustc's `let_underscore_lock`
+                    SignificantDropKind::Lock if cx.tcx.crate_name(sig_drop.def_id.krate) != sym::std => {
+                        Some("non-binding `let` on a synchronization lock")
+                    },
+                    SignificantDropKind::Attribute | SignificantDropKind::Configured => {
+                        Some("non-binding `let` on a type with a significant drop")
+                    },
+                    _ => None,
+                }
             });
-            if contains_sync_guard {
+            if let Some(msg) = sig_drop_msg {
                 span_lint_and_help(
                     cx,
                     LET_UNDERSCORE_LOCK,
                     local.span,
-                    "non-binding `let` on a synchronization lock",
+                    msg,
                     None,
                     "consider using an underscore-prefixed named \
                             binding or dropping explicitly with `std::mem::drop`",
@@ -47,6 +47,7 @@ extern crate rustc_infer;
 extern crate rustc_lexer;
 extern crate rustc_lint;
 extern crate rustc_middle;
+extern crate rustc_mir_dataflow;
 extern crate rustc_parse;
 extern crate rustc_resolve;
 extern crate rustc_session;
@@ -70,9 +71,12 @@ mod renamed_lints;
 
 // begin lints modules, do not remove this comment, it’s used in `update_lints`
 mod absolute_paths;
+mod alloc_in_no_alloc;
 mod allow_attributes;
 mod almost_complete_range;
+mod ambient_randomness;
 mod approx_const;
+mod arbitrary_str_slice;
 mod arc_with_non_send_sync;
 mod as_conversions;
 mod asm_syntax;
@@ -91,6 +95,7 @@ mod box_default;
 mod cargo;
 mod casts;
 mod checked_conversions;
+mod closed_dyn_trait;
 mod cognitive_complexity;
 mod collapsible_if;
 mod collection_is_never_read;
@@ -108,14 +113,21 @@ mod default_union_representation;
 mod dereference;
 mod derivable_impls;
 mod derive;
+mod direct_clock_access;
+mod disallowed_attributes;
+mod disallowed_env_vars;
+mod disallowed_imports;
 mod disallowed_macros;
 mod disallowed_methods;
 mod disallowed_names;
 mod disallowed_script_idents;
 mod disallowed_types;
+mod disallowed_types_across_regions;
+mod display_forwarding_to_debug;
 mod doc;
 mod double_parens;
 mod drop_forget_ref;
+mod dropped_error_source;
 mod duplicate_mod;
 mod else_if_without_else;
 mod empty_drop;
@@ -124,6 +136,7 @@ mod empty_with_brackets;
 mod endian_bytes;
 mod entry;
 mod enum_clike;
+mod env_access_outside_config;
 mod equatable_if_let;
 mod error_impl_error;
 mod escape;
@@ -153,12 +166,15 @@ mod if_not_else;
 mod if_then_some_else_none;
 mod ignored_unit_patterns;
 mod impl_hash_with_borrow_str_and_bytes;
+mod implicit_field_init;
 mod implicit_hasher;
 mod implicit_return;
 mod implicit_saturating_add;
 mod implicit_saturating_sub;
 mod implied_bounds_in_impls;
 mod incompatible_msrv;
+mod inconsistent_borrow;
+mod inconsistent_hash_and_eq;
 mod inconsistent_struct_constructor;
 mod index_refutable_slice;
 mod indexing_slicing;
@@ -185,6 +201,7 @@ mod large_include_file;
 mod large_stack_arrays;
 mod large_stack_frames;
 mod legacy_numeric_constants;
+mod legacy_ref_pattern;
 mod len_zero;
 mod let_if_seq;
 mod let_underscore;
@@ -193,6 +210,7 @@ mod lifetimes;
 mod lines_filter_map_ok;
 mod literal_representation;
 mod loops;
+mod lossy_from;
 mod macro_use;
 mod main_recursion;
 mod manual_assert;
@@ -208,6 +226,7 @@ mod manual_non_exhaustive;
 mod manual_range_patterns;
 mod manual_rem_euclid;
 mod manual_retain;
+mod manual_slice_pattern;
 mod manual_slice_size_calculation;
 mod manual_string_new;
 mod manual_strip;
@@ -215,6 +234,7 @@ mod manual_unwrap_or_default;
 mod map_unit_fn;
 mod match_result_ok;
 mod matches;
+mod matches_then_destructure;
 mod mem_replace;
 mod methods;
 mod min_ident_chars;
@@ -227,19 +247,25 @@ mod missing_asserts_for_indexing;
 mod missing_const_for_fn;
 mod missing_doc;
 mod missing_enforced_import_rename;
+mod missing_error_impls;
 mod missing_fields_in_debug;
+mod missing_finalizer;
 mod missing_inline;
+mod missing_track_caller;
 mod missing_trait_methods;
 mod mixed_read_write_in_expression;
+mod module_layering;
 mod module_style;
 mod multi_assignments;
 mod multiple_bound_locations;
 mod multiple_unsafe_ops_per_block;
+mod must_use_types;
 mod mut_key;
 mod mut_mut;
 mod mut_reference;
 mod mutable_debug_assertion;
 mod mutex_atomic;
+mod naming_conventions;
 mod needless_arbitrary_self_type;
 mod needless_bool;
 mod needless_borrowed_ref;
@@ -256,6 +282,7 @@ mod needless_question_mark;
 mod needless_update;
 mod neg_cmp_op_on_partial_ord;
 mod neg_multiply;
+mod nested_match_pyramid;
 mod new_without_default;
 mod no_effect;
 mod no_mangle_with_rust_abi;
@@ -271,8 +298,10 @@ mod operators;
 mod option_env_unwrap;
 mod option_if_let_else;
 mod overflow_check_conditional;
+mod panic_in_production;
 mod panic_in_result_fn;
 mod panic_unimplemented;
+mod panicking_iterator_next;
 mod partial_pub_fields;
 mod partialeq_ne_impl;
 mod partialeq_to_none;
@@ -290,6 +319,7 @@ mod ranges;
 mod raw_strings;
 mod rc_clone_in_vec_init;
 mod read_zero_byte_vec;
+mod recursion;
 mod redundant_async_block;
 mod redundant_clone;
 mod redundant_closure_call;
@@ -307,6 +337,7 @@ mod regex;
 mod repeat_vec_with_capacity;
 mod reserve_after_initialization;
 mod return_self_not_must_use;
+mod returned_vec_only_iterated;
 mod returns;
 mod same_name_method;
 mod self_named_constructors;
@@ -362,6 +393,7 @@ mod unused_io_amount;
 mod unused_peekable;
 mod unused_rounding;
 mod unused_self;
+mod unused_trait_bound;
 mod unused_unit;
 mod unwrap;
 mod unwrap_in_result;
@@ -378,11 +410,15 @@ mod zero_repeat_side_effects;
 mod zero_sized_map_values;
 // end lints modules, do not remove this comment, it’s used in `update_lints`
 
-use clippy_config::{get_configuration_metadata, Conf};
+use clippy_config::{get_configuration_metadata, ClippyConfiguration, Conf};
 use clippy_utils::macros::FormatArgsStorage;
 use rustc_data_structures::fx::FxHashSet;
-use rustc_lint::{Lint, LintId};
+use rustc_data_structures::sync::{DynSend, DynSync};
+use rustc_lint::{LateLintPass, Lint, LintId};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::impl_lint_pass;
 use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
 
 /// Register all pre expansion lints
 ///
@@ -391,7 +427,7 @@ use std::collections::BTreeMap;
 /// Note that due to the architecture of the compiler, currently `cfg_attr` attributes on crate
 /// level (i.e `#![cfg_attr(...)]`) will still be expanded even when using a pre-expansion pass.
 ///
-/// Used in `./src/driver.rs`.
+/// Used in [`register_with_config`].
 pub fn register_pre_expansion_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
     // NOTE: Do not add any more pre-expansion passes. These should be removed eventually.
     let msrv = || conf.msrv.clone();
@@ -455,6 +491,22 @@ impl LintCategory {
         matches!(self, Correctness | Suspicious | Style | Complexity | Perf)
     }
 
+    fn name(self) -> &'static str {
+        match self {
+            Cargo => "cargo",
+            Complexity => "complexity",
+            Correctness => "correctness",
+            Nursery => "nursery",
+            Pedantic => "pedantic",
+            Perf => "perf",
+            Restriction => "restriction",
+            Style => "style",
+            Suspicious => "suspicious",
+            #[cfg(feature = "internal")]
+            Internal => "internal",
+        }
+    }
+
     fn group(self, groups: &mut RegistrationGroups) -> &mut Vec<LintId> {
         match self {
             Cargo => &mut groups.cargo,
@@ -477,16 +529,29 @@ pub(crate) struct LintInfo {
     lint: &'static &'static Lint,
     category: LintCategory,
     explanation: &'static str,
+    /// The version from `#[clippy::version]`, which is missing for some internal lints.
+    version: Option<&'static str>,
 }
 
+#[allow(clippy::print_in_library)]
 pub fn explain(name: &str) -> i32 {
     let target = format!("clippy::{}", name.to_ascii_uppercase());
     if let Some(info) = declared_lints::LINTS.iter().find(|info| info.lint.name == target) {
-        println!("{}", info.explanation);
         // Check if the lint has configuration
         let mut mdconf = get_configuration_metadata();
         let name = name.to_ascii_lowercase();
         mdconf.retain(|cconf| cconf.lints.contains(&name));
+
+        println!("Group: `{}`", info.category.name());
+        println!("Default level: `{}`", info.lint.default_level.as_str());
+        if let Some(version) = info.version {
+            println!("Added in: `{version}`");
+        }
+        if mdconf.iter().any(|cconf| cconf.name == "msrv") {
+            println!("Respects the minimum supported Rust version (`msrv`)");
+        }
+        println!();
+        println!("{}", info.explanation);
         if !mdconf.is_empty() {
             println!("### Configuration for {}:\n", info.lint.name_lower());
             for conf in mdconf {
@@ -500,6 +565,56 @@ pub fn explain(name: &str) -> i32 {
     }
 }
 
+/// The documentation of a lint, for `cargo clippy --dump-lints`.
+pub struct LintMetadata {
+    /// The name without the `clippy::` prefix, e.g. `needless_return`.
+    pub name: String,
+    pub group: &'static str,
+    pub default_level: &'static str,
+    pub version: Option<&'static str>,
+    pub description: &'static str,
+    pub explanation: &'static str,
+    /// The configuration keys which the lint reads.
+    pub configuration: Vec<ClippyConfiguration>,
+}
+
+/// Returns the documentation of all the lints, sorted by name.
+pub fn lints_metadata() -> Vec<LintMetadata> {
+    let configuration = get_configuration_metadata();
+    let mut lints: Vec<_> = declared_lints::LINTS
+        .iter()
+        .map(|info| {
+            let name = info.lint.name_lower();
+            let name = name.strip_prefix("clippy::").unwrap_or(&name).to_string();
+            LintMetadata {
+                configuration: configuration
+                    .iter()
+                    .filter(|conf| conf.lints.contains(&name))
+                    .cloned()
+                    .collect(),
+                name,
+                group: info.category.name(),
+                default_level: info.lint.default_level.as_str(),
+                version: info.version,
+                description: info.lint.desc,
+                explanation: info.explanation,
+            }
+        })
+        .collect();
+    lints.sort_by(|a, b| a.name.cmp(&b.name));
+    lints
+}
+
+/// Returns the group and the description of the lint `name`, e.g. `("style", "..")` for
+/// `needless_return`.
+pub fn lint_group_and_description(name: &str) -> Option<(&'static str, &'static str)> {
+    let target = format!("clippy::{}", name.to_ascii_uppercase());
+    declared_lints::LINTS
+        .iter()
+        .find(|info| info.lint.name == target)
+        .map(|info| (info.category.name(), info.lint.desc))
+}
+
 fn register_categories(store: &mut rustc_lint::LintStore) {
     let mut groups = RegistrationGroups::default();
 
@@ -519,7 +634,7 @@ fn register_categories(store: &mut rustc_lint::LintStore) {
 
 /// Register all lints and lint groups with the rustc lint store
 ///
-/// Used in `./src/driver.rs`.
+/// Used in [`register_with_config`].
 #[expect(clippy::too_many_lines)]
 pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
     let Conf {
@@ -528,6 +643,7 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
         accept_comment_above_attributes,
         accept_comment_above_statement,
         allow_dbg_in_tests,
+        allow_debug_display_for_errors,
         allow_expect_in_tests,
         allow_mixed_uninlined_format_args,
         allow_one_hash_in_raw_strings,
@@ -542,15 +658,21 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
         ref arithmetic_side_effects_allowed_binary,
         ref arithmetic_side_effects_allowed_unary,
         ref arithmetic_side_effects_allowed,
+        ref arithmetic_side_effects_allowed_operators,
+        ref arithmetic_side_effects_wrapper_types,
         array_size_threshold,
         avoid_breaking_exported_api,
         ref await_holding_invalid_types,
         cargo_ignore_publish,
         cognitive_complexity_threshold,
+        ref disallowed_imports,
+        ref disallowed_attributes,
         ref disallowed_macros,
         ref disallowed_methods,
         ref disallowed_names,
         ref disallowed_types,
+        ref must_use_types,
+        ref disallowed_types_across_regions,
         ref doc_valid_idents,
         enable_raw_pointer_heuristic_for_send,
         enforce_iter_loop_reborrow,
@@ -558,6 +680,7 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
         enum_variant_name_threshold,
         enum_variant_size_threshold,
         excessive_nesting_threshold,
+        ref expensive_calls,
         future_size_threshold,
         ref ignore_interior_mutability,
         large_error_threshold,
@@ -565,20 +688,44 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
         matches_for_let_else,
         max_fn_params_bools,
         max_include_file_size,
+        max_match_bool_tuple_arity,
+        match_same_arms_ignore_path_prefixes,
         max_struct_bools,
         max_suggested_slice_pattern_length,
         max_trait_bounds,
         min_ident_chars_threshold,
         missing_docs_in_crate_items,
         ref msrv,
+        ref must_call_finalizers,
+        ref naming_conventions,
+        ref logging_facade,
+        ref module_layering,
+        ref disallowed_env_vars,
+        ref env_config_modules,
+        ref allowed_env_vars,
+        ref clock_functions,
+        ref clock_modules,
+        ref clock_abstraction,
+        ref randomness_functions,
+        ref randomness_modules,
+        forbid_panics_in_production,
+        ref exhaustive_init_types,
+        ref required_const_fn_modules,
+        ref allowed_recursive_functions,
+        ref no_alloc_modules,
         pass_by_value_size_limit,
         semicolon_inside_block_ignore_singleline,
         semicolon_outside_block_ignore_multiline,
+        ref significant_drop_types,
         single_char_binding_names_threshold,
         stack_size_threshold,
         ref standard_macro_braces,
         struct_field_name_threshold,
         suppress_restriction_lint_in_const,
+        ref target_pointer_widths,
+        ref allowed_cast_categories,
+        ref threshold_overrides,
+        ref test_attributes,
         too_large_for_stack,
         too_many_arguments_threshold,
         too_many_lines_threshold,
@@ -593,14 +740,24 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
         check_private_items,
         pub_underscore_fields_behavior,
         ref allowed_duplicate_crates,
+        ref allowed_git_sources,
+        ref allowed_licenses,
         allow_comparison_to_zero,
         ref allowed_prefixes,
+        // read by `enable_warn_budget`
+        warn_budget: _,
+        // read by `collect_doctests`
+        doctest_lints: _,
+        // read by the driver, which loads the plugins
+        plugins: _,
 
         blacklisted_names: _,
         cyclomatic_complexity_threshold: _,
     } = *conf;
     let msrv = || msrv.clone();
 
+    clippy_utils::set_test_attributes(test_attributes.clone());
+
     register_removed_non_tool_lints(store);
     register_categories(store);
 
@@ -621,6 +778,7 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
             format_args.clone(),
         ))
     });
+    store.register_late_pass(|_| Box::new(utils::threshold_attrs::ThresholdAttrs));
 
     // all the internal lints
     #[cfg(feature = "internal")]
@@ -646,6 +804,9 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
         store.register_late_pass(|_| {
             Box::new(utils::internal_lints::almost_standard_lint_formulation::AlmostStandardFormulation::new())
         });
+        store.register_late_pass(|_| Box::new(utils::internal_lints::unguarded_span_lint::UnguardedSpanLint));
+        store.register_late_pass(|_| Box::new(utils::internal_lints::configuration_lints::ConfigurationLints::new()));
+        store.register_late_pass(|_| Box::new(utils::internal_lints::crate_walk_in_node_check::CrateWalkInNodeCheck));
     }
 
     store.register_late_pass(move |_| {
@@ -660,6 +821,8 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
                 .chain(arithmetic_side_effects_allowed_unary.iter())
                 .cloned()
                 .collect(),
+            arithmetic_side_effects_allowed_operators.clone(),
+            arithmetic_side_effects_wrapper_types.clone(),
         ))
     });
     store.register_late_pass(|_| Box::new(utils::dump_hir::DumpHir));
@@ -675,6 +838,7 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
             vec_box_size_threshold,
             type_complexity_threshold,
             avoid_breaking_exported_api,
+            threshold_overrides,
         ))
     });
     store.register_late_pass(|_| Box::new(booleans::NonminimalBool));
@@ -711,10 +875,17 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
             allow_expect_in_tests,
             allow_unwrap_in_tests,
             allowed_dotfiles.clone(),
+            expensive_calls.clone(),
             format_args.clone(),
         ))
     });
-    store.register_late_pass(move |_| Box::new(matches::Matches::new(msrv())));
+    store.register_late_pass(move |_| {
+        Box::new(matches::Matches::new(
+            msrv(),
+            max_match_bool_tuple_arity,
+            match_same_arms_ignore_path_prefixes,
+        ))
+    });
     store.register_early_pass(move || Box::new(manual_non_exhaustive::ManualNonExhaustiveStruct::new(msrv())));
     store.register_late_pass(move |_| Box::new(manual_non_exhaustive::ManualNonExhaustiveEnum::new(msrv())));
     store.register_late_pass(move |_| Box::new(manual_strip::ManualStrip::new(msrv())));
@@ -725,9 +896,20 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
     store.register_late_pass(move |_| Box::new(ranges::Ranges::new(msrv())));
     store.register_late_pass(move |_| Box::new(from_over_into::FromOverInto::new(msrv())));
     store.register_late_pass(move |_| Box::new(use_self::UseSelf::new(msrv())));
-    store.register_late_pass(move |_| Box::new(missing_const_for_fn::MissingConstForFn::new(msrv())));
+    store.register_late_pass(move |_| {
+        Box::new(missing_const_for_fn::MissingConstForFn::new(
+            msrv(),
+            required_const_fn_modules.clone(),
+        ))
+    });
     store.register_late_pass(move |_| Box::new(needless_question_mark::NeedlessQuestionMark));
-    store.register_late_pass(move |_| Box::new(casts::Casts::new(msrv())));
+    store.register_late_pass(move |_| {
+        Box::new(casts::Casts::new(
+            msrv(),
+            target_pointer_widths.clone(),
+            allowed_cast_categories.clone(),
+        ))
+    });
     store.register_early_pass(move || Box::new(unnested_or_patterns::UnnestedOrPatterns::new(msrv())));
     store.register_late_pass(|_| Box::new(size_of_in_element_count::SizeOfInElementCount));
     store.register_late_pass(|_| Box::new(same_name_method::SameNameMethod));
@@ -755,6 +937,7 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
     store.register_late_pass(move |_| {
         Box::new(cognitive_complexity::CognitiveComplexity::new(
             cognitive_complexity_threshold,
+            threshold_overrides,
         ))
     });
     store.register_late_pass(move |_| Box::new(escape::BoxedLocal { too_large_for_stack }));
@@ -770,7 +953,7 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
     store.register_late_pass(|_| Box::new(strings::StringLitAsBytes));
     store.register_late_pass(|_| Box::new(derive::Derive));
     store.register_late_pass(move |_| Box::new(derivable_impls::DerivableImpls::new(msrv())));
-    store.register_late_pass(|_| Box::new(drop_forget_ref::DropForgetRef));
+    store.register_late_pass(move |_| Box::new(drop_forget_ref::DropForgetRef::new(significant_drop_types.clone())));
     store.register_late_pass(|_| Box::new(empty_enum::EmptyEnum));
     store.register_late_pass(|_| Box::new(invalid_upcast_comparisons::InvalidUpcastComparisons));
     store.register_late_pass(|_| Box::<regex::Regex>::default());
@@ -788,6 +971,7 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
             too_many_lines_threshold,
             large_error_threshold,
             avoid_breaking_exported_api,
+            threshold_overrides,
         ))
     });
     store.register_late_pass(move |_| Box::new(doc::Documentation::new(doc_valid_idents, check_private_items)));
@@ -897,12 +1081,13 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
     store.register_late_pass(move |_| Box::new(large_const_arrays::LargeConstArrays::new(array_size_threshold.into())));
     store.register_late_pass(|_| Box::new(floating_point_arithmetic::FloatingPointArithmetic));
     store.register_late_pass(|_| Box::new(as_conversions::AsConversions));
-    store.register_late_pass(|_| Box::new(let_underscore::LetUnderscore));
+    store.register_late_pass(move |_| Box::new(let_underscore::LetUnderscore::new(significant_drop_types.clone())));
     store.register_early_pass(|| Box::<single_component_path_imports::SingleComponentPathImports>::default());
     store.register_late_pass(move |_| {
         Box::new(excessive_bools::ExcessiveBools::new(
             max_struct_bools,
             max_fn_params_bools,
+            threshold_overrides,
         ))
     });
     store.register_early_pass(|| Box::new(option_env_unwrap::OptionEnvUnwrap));
@@ -990,11 +1175,19 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
     store.register_late_pass(|_| Box::<only_used_in_recursion::OnlyUsedInRecursion>::default());
     store.register_late_pass(move |_| Box::new(dbg_macro::DbgMacro::new(allow_dbg_in_tests)));
     let format_args = format_args_storage.clone();
-    store.register_late_pass(move |_| Box::new(write::Write::new(format_args.clone(), allow_print_in_tests)));
+    store.register_late_pass(move |_| {
+        Box::new(write::Write::new(
+            format_args.clone(),
+            allow_print_in_tests,
+            logging_facade.clone(),
+        ))
+    });
     store.register_late_pass(move |_| {
         Box::new(cargo::Cargo {
             ignore_publish: cargo_ignore_publish,
             allowed_duplicate_crates: allowed_duplicate_crates.clone(),
+            allowed_git_sources: allowed_git_sources.clone(),
+            allowed_licenses: allowed_licenses.clone(),
         })
     });
     store.register_early_pass(|| Box::new(crate_in_macro_def::CrateInMacroDef));
@@ -1137,7 +1330,7 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
     store.register_late_pass(|_| Box::new(repeat_vec_with_capacity::RepeatVecWithCapacity));
     store.register_late_pass(|_| Box::new(uninhabited_references::UninhabitedReferences));
     store.register_late_pass(|_| Box::new(ineffective_open_options::IneffectiveOpenOptions));
-    store.register_late_pass(|_| Box::<unconditional_recursion::UnconditionalRecursion>::default());
+    store.register_late_pass(|_| Box::new(unconditional_recursion::UnconditionalRecursion));
     store.register_late_pass(move |_| {
         Box::new(pub_underscore_fields::PubUnderscoreFields {
             behavior: pub_underscore_fields_behavior,
@@ -1153,6 +1346,79 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
     store.register_late_pass(|_| Box::new(zero_repeat_side_effects::ZeroRepeatSideEffects));
     store.register_late_pass(|_| Box::new(manual_unwrap_or_default::ManualUnwrapOrDefault));
     store.register_late_pass(|_| Box::new(integer_division_remainder_used::IntegerDivisionRemainderUsed));
+    store.register_late_pass(move |_| {
+        Box::new(disallowed_types_across_regions::DisallowedTypesAcrossRegions::new(
+            disallowed_types_across_regions.clone(),
+        ))
+    });
+    store.register_late_pass(move |_| Box::new(disallowed_imports::DisallowedImports::new(disallowed_imports.clone())));
+    store.register_late_pass(move |_| Box::new(missing_finalizer::MissingFinalizer::new(must_call_finalizers.clone())));
+    store.register_late_pass(move |_| Box::new(naming_conventions::NamingConventions::new(naming_conventions.clone())));
+    store.register_late_pass(move |_| Box::new(module_layering::ModuleLayering::new(module_layering.clone())));
+    store.register_late_pass(move |_| {
+        Box::new(disallowed_attributes::DisallowedAttributes::new(
+            disallowed_attributes.clone(),
+        ))
+    });
+    store.register_late_pass(move |_| Box::new(must_use_types::MustUseTypes::new(must_use_types.clone())));
+    store.register_late_pass(move |_| {
+        Box::new(disallowed_env_vars::DisallowedEnvVars::new(disallowed_env_vars.clone()))
+    });
+    store.register_late_pass(|_| Box::new(inconsistent_hash_and_eq::InconsistentHashAndEq));
+    store.register_late_pass(|_| Box::new(legacy_ref_pattern::LegacyRefPattern));
+    store.register_late_pass(move |_| {
+        Box::new(manual_slice_pattern::ManualSlicePattern::new(
+            max_suggested_slice_pattern_length,
+        ))
+    });
+    store.register_late_pass(move |_| Box::new(nested_match_pyramid::NestedMatchPyramid::new(msrv())));
+    store.register_late_pass(|_| Box::new(matches_then_destructure::MatchesThenDestructure));
+    store.register_late_pass(|_| Box::new(arbitrary_str_slice::ArbitraryStrSlice));
+    store.register_late_pass(|_| Box::new(returned_vec_only_iterated::ReturnedVecOnlyIterated));
+    store.register_late_pass(|_| Box::new(unused_trait_bound::UnusedTraitBound));
+    store.register_late_pass(|_| Box::new(closed_dyn_trait::ClosedDynTrait));
+    store.register_late_pass(|_| Box::new(inconsistent_borrow::InconsistentBorrow));
+    store.register_late_pass(|_| Box::new(panicking_iterator_next::PanickingIteratorNext));
+    let format_args = format_args_storage.clone();
+    store.register_late_pass(move |_| {
+        Box::new(display_forwarding_to_debug::DisplayForwardingToDebug::new(
+            format_args.clone(),
+            allow_debug_display_for_errors,
+        ))
+    });
+    store.register_late_pass(|_| Box::new(dropped_error_source::DroppedErrorSource));
+    store.register_late_pass(|_| Box::new(lossy_from::LossyFrom));
+    store.register_late_pass(move |_| {
+        Box::new(env_access_outside_config::EnvAccessOutsideConfig::new(
+            env_config_modules.clone(),
+            allowed_env_vars.clone(),
+        ))
+    });
+    store.register_late_pass(|_| Box::new(missing_track_caller::MissingTrackCaller));
+    store.register_late_pass(move |_| {
+        Box::new(direct_clock_access::DirectClockAccess::new(
+            clock_functions.clone(),
+            clock_modules.clone(),
+            clock_abstraction.clone(),
+        ))
+    });
+    store.register_late_pass(move |_| {
+        Box::new(ambient_randomness::AmbientRandomness::new(
+            randomness_functions.clone(),
+            randomness_modules.clone(),
+        ))
+    });
+    store.register_late_pass(|_| Box::<missing_error_impls::MissingErrorImpls>::default());
+    store.register_late_pass(move |_| {
+        Box::new(panic_in_production::PanicInProduction::new(forbid_panics_in_production))
+    });
+    store.register_late_pass(move |_| {
+        Box::new(implicit_field_init::ImplicitFieldInit::new(
+            exhaustive_init_types.clone(),
+        ))
+    });
+    store.register_late_pass(move |_| Box::new(recursion::Recursion::new(allowed_recursive_functions.clone())));
+    store.register_late_pass(move |_| Box::new(alloc_in_no_alloc::AllocInNoAlloc::new(no_alloc_modules.clone())));
     // add lints here, do not remove this comment, it's used in `new_lint`
 }
 
@@ -1200,9 +1466,306 @@ fn register_removed_non_tool_lints(store: &mut rustc_lint::LintStore) {
     );
 }
 
-/// Register renamed lints.
+/// Replaces the late passes registered by [`register_lints`] from the index `first_late_pass` on
+/// by a pass which replays their diagnostics on the functions that didn't change since the last
+/// run, see `clippy_utils::lint_cache`.
 ///
 /// Used in `./src/driver.rs`.
+pub fn enable_lint_cache(store: &mut rustc_lint::LintStore, first_late_pass: usize) {
+    clippy_utils::lint_cache::enable(store, first_late_pass);
+}
+
+/// Times the late passes registered by [`register_lints`] from the index `first_late_pass` on,
+/// and reports the slowest of them at the end of the crate, see `clippy_utils::lint_profile`.
+///
+/// Used in `./src/driver.rs`.
+pub fn enable_lint_profile(store: &mut rustc_lint::LintStore, first_late_pass: usize) {
+    clippy_utils::lint_profile::enable(store, first_late_pass);
+}
+
+/// Makes `cargo fix` apply the `MaybeIncorrect` suggestions of `lints`, given by their names
+/// without the `clippy::` prefix.
+///
+/// Used in `./src/driver.rs` for `cargo clippy --fix --fix-maybe-incorrect`.
+pub fn set_fix_maybe_incorrect(lints: Vec<String>) {
+    clippy_utils::diagnostics::set_fix_maybe_incorrect(lints);
+}
+
+/// Fails the crate when a lint is emitted more often than its budget in `budgets` or the
+/// `warn-budget` configuration allows, see `clippy_utils::warn_budget`. This has to be called
+/// after all the passes are registered.
+///
+/// Used in `./src/driver.rs` for `cargo clippy --warn-budget`.
+pub fn enable_warn_budget(
+    store: &mut rustc_lint::LintStore,
+    sess: &rustc_session::Session,
+    conf: &Conf,
+    budgets: &[(String, u64)],
+) {
+    let mut merged = BTreeMap::new();
+    for (name, &budget) in conf
+        .warn_budget
+        .iter()
+        .chain(budgets.iter().map(|(name, budget)| (name, budget)))
+    {
+        let name = name.to_ascii_lowercase().replace('-', "_");
+        let name = name.strip_prefix("clippy::").unwrap_or(&name);
+        if lint_group_and_description(name).is_some() {
+            merged.insert(name.to_string(), budget);
+        } else {
+            sess.dcx().warn(format!(
+                "unknown lint `clippy::{name}` in the `warn-budget` configuration"
+            ));
+        }
+    }
+    if !merged.is_empty() {
+        clippy_utils::warn_budget::enable(store, merged);
+    }
+}
+
+/// Collapses the warnings of a lint in the expansions of the same macro into the first of them,
+/// see `clippy_utils::macro_diagnostics`. This has to be called after all the passes are
+/// registered.
+///
+/// Used in `./src/driver.rs` for `cargo clippy --collapse-macro-diagnostics`.
+pub fn collapse_macro_diagnostics(store: &mut rustc_lint::LintStore) {
+    clippy_utils::macro_diagnostics::enable(store);
+}
+
+/// Counts the warnings of each lint and reports them in a note at the end of the crate, see
+/// `clippy_utils::summary`. This has to be called after all the passes are registered.
+///
+/// Used in `./src/driver.rs` for `cargo clippy --summary`.
+pub fn enable_summary(store: &mut rustc_lint::LintStore) {
+    clippy_utils::summary::enable(store);
+}
+
+/// Replaces the suggestions of the warnings of `lints`, given without the `clippy::` prefix, by
+/// the insertion of `#[allow]` attributes on their items, see `clippy_utils::annotate_allows`.
+///
+/// Used in `./src/driver.rs` for `cargo clippy --annotate-allows`.
+pub fn annotate_allows(store: &mut rustc_lint::LintStore, lints: &[String]) {
+    clippy_utils::annotate_allows::enable(store, lints);
+}
+
+/// Notes the cfgs of the crate on its warnings and errors, see `clippy_utils::cfg_notes`.
+///
+/// Used in `./src/driver.rs` for `cargo clippy --aggregate`.
+pub fn note_cfgs(cfgs: &[String]) {
+    clippy_utils::cfg_notes::enable(cfgs);
+}
+
+/// A Rust code block of the documentation, for `cargo clippy --doctests`.
+pub struct Doctest {
+    pub code: String,
+    pub edition: String,
+    /// The path of the file as passed to the compiler, e.g. `src/lib.rs`.
+    pub file: String,
+    /// The line and column of the start of the code in the file, starting at 1 and 0.
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Makes the documentation lints collect the Rust code blocks of the crate, which are returned by
+/// [`take_doctests`]. Returns the lints which should be run on them, from the `doctest-lints`
+/// configuration.
+///
+/// Used in `./src/driver.rs` for `cargo clippy --doctests`.
+pub fn collect_doctests(conf: &Conf) -> Vec<String> {
+    doc::doctests::enable();
+    conf.doctest_lints
+        .iter()
+        .map(|name| {
+            let name = name.to_ascii_lowercase().replace('-', "_");
+            name.strip_prefix("clippy::").unwrap_or(&name).to_string()
+        })
+        .collect()
+}
+
+/// Returns the code blocks collected since [`collect_doctests`] was called.
+pub fn take_doctests() -> Vec<Doctest> {
+    doc::doctests::take()
+}
+
+/// Keeps only the lint passes registered by [`register_lints`] from the indices
+/// `first_early_pass` and `first_late_pass` on which declare one of `lints`, or no lint at all.
+/// `lints` are the names without the `clippy::` prefix.
+///
+/// Used in `./src/driver.rs` for `cargo clippy --only`.
+pub fn retain_lint_passes(
+    store: &mut rustc_lint::LintStore,
+    first_early_pass: usize,
+    first_late_pass: usize,
+    lints: &[String],
+) {
+    let skipped: Arc<FxHashSet<&str>> = Arc::new(
+        declared_lints::LINT_PASSES
+            .iter()
+            .filter(|(_, pass_lints)| {
+                !pass_lints.is_empty() && !pass_lints.iter().any(|lint| lints.iter().any(|name| name == lint))
+            })
+            .map(|&(pass, _)| pass)
+            .collect(),
+    );
+    let is_skipped = |skipped: &FxHashSet<&str>, name: &str| {
+        // `impl_lint_pass!(Pass<'_> => [..])` names the pass `Pass<'_>`
+        skipped.contains(name.split('<').next().unwrap_or(name))
+    };
+
+    let early_passes: Vec<_> = store.early_passes.drain(first_early_pass..).collect();
+    store.early_passes.extend(
+        early_passes
+            .into_iter()
+            .filter(|pass| !is_skipped(&skipped, pass().name())),
+    );
+
+    let late_passes: Vec<_> = store.late_passes.drain(first_late_pass..).collect();
+    for pass in late_passes {
+        let skipped = Arc::clone(&skipped);
+        store.register_late_pass(move |tcx| {
+            let pass = pass(tcx);
+            if is_skipped(&skipped, pass.name()) {
+                Box::new(SkippedPass)
+            } else {
+                pass
+            }
+        });
+    }
+}
+
+/// Runs the late passes registered by [`register_lints`] from the index `first_late_pass` on
+/// which have no state on each module separately, so that the modules are linted in parallel
+/// when the compiler runs with several threads. The other passes still run once on the whole
+/// crate, on one thread but alongside the modules.
+///
+/// The passes which report at the end of the crate, like the ones of
+/// [`enable_lint_profile`] and [`enable_warn_budget`], must not be registered when this is used,
+/// as the crate can be done before the modules.
+///
+/// Used in `./src/driver.rs` for `cargo clippy --lint-threads`.
+pub fn run_stateless_passes_per_module(store: &mut rustc_lint::LintStore, first_late_pass: usize) {
+    let late_passes: Vec<_> = store.late_passes.drain(first_late_pass..).collect();
+    for pass in late_passes {
+        let pass: Arc<LateLintPassFactory> = pass.into();
+        // The passes can only be told apart once created, and creating the passes with state for
+        // each module would be wasteful
+        let stateless = Arc::new(OnceLock::new());
+        let is_stateless = |stateless: &OnceLock<bool>, pass: &dyn LateLintPass<'_>| {
+            let name = pass.name();
+            *stateless
+                .get_or_init(|| declared_lints::STATELESS_PASSES.contains(&name.split('<').next().unwrap_or(name)))
+        };
+
+        let (module_pass, module_stateless) = (Arc::clone(&pass), Arc::clone(&stateless));
+        store.late_module_passes.push(Box::new(move |tcx| {
+            if module_stateless.get() == Some(&false) {
+                return Box::new(SkippedPass);
+            }
+            let pass = module_pass(tcx);
+            if is_stateless(&module_stateless, &*pass) {
+                pass
+            } else {
+                Box::new(SkippedPass)
+            }
+        }));
+        store.register_late_pass(move |tcx| {
+            let pass = pass(tcx);
+            if is_stateless(&stateless, &*pass) {
+                Box::new(SkippedPass)
+            } else {
+                pass
+            }
+        });
+    }
+}
+
+type LateLintPassFactory = dyn for<'tcx> Fn(TyCtxt<'tcx>) -> Box<dyn LateLintPass<'tcx> + 'tcx> + DynSend + DynSync;
+
+/// Stands in for the late passes which are skipped, as these can only be told apart once created.
+struct SkippedPass;
+
+impl_lint_pass!(SkippedPass => []);
+
+impl LateLintPass<'_> for SkippedPass {}
+
+/// Returns the names of all the lints except `lints`, without the `clippy::` prefix.
+///
+/// Used in `./src/driver.rs` to allow the other lints of the passes kept by `cargo clippy --only`.
+pub fn lint_names_except(lints: &[String]) -> Vec<String> {
+    declared_lints::LINTS
+        .iter()
+        .map(|info| info.lint.name_lower())
+        .map(|name| name.strip_prefix("clippy::").map(String::from).unwrap_or(name))
+        .filter(|name| !lints.contains(name))
+        .collect()
+}
+
+/// The configurations passed to [`register_with_config`]. The lint store may create the lint
+/// passes borrowing them until the end of the process, so they are never dropped, and a
+/// configuration registered for several sessions is only stored once.
+static REGISTERED_CONFS: Mutex<Vec<&'static Arc<Conf>>> = Mutex::new(Vec::new());
+
+/// Registers all the lints, lint groups and lint passes of Clippy, configured by `conf`, for the
+/// tools running them in their own compiler sessions, e.g. custom drivers. The configuration can be
+/// read with [`Conf::from_toml`], or built from [`Conf::default`]:
+///
+/// ```ignore
+/// config.register_lints = Some(Box::new(|sess, store| {
+///     let conf = Conf::from_toml(sess, "too-many-lines-threshold = 50");
+///     clippy_lints::register_with_config(store, Arc::new(conf));
+/// }));
+/// clippy_lints::set_compiler_options(&mut config.opts);
+/// ```
+///
+/// The configuration is kept until the end of the process, so the tools running several sessions
+/// should register the same `Arc` for all of them.
+///
+/// Used in `./src/driver.rs`.
+pub fn register_with_config(store: &mut rustc_lint::LintStore, conf: Arc<Conf>) {
+    let conf: &'static Conf = {
+        let mut confs = REGISTERED_CONFS.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(registered) = confs.iter().find(|registered| Arc::ptr_eq(registered, &conf)) {
+            registered
+        } else {
+            let registered = Box::leak(Box::new(conf));
+            confs.push(registered);
+            registered
+        }
+    };
+    register_lints(store, conf);
+    register_pre_expansion_lints(store, conf);
+    register_renamed(store);
+}
+
+/// Sets the options of the compiler which the lint passes of Clippy rely on, see
+/// [`register_with_config`]. `clippy-driver` also sets `--cfg clippy`, which the tools may set too.
+///
+/// Used in `./src/driver.rs`.
+// JUSTIFICATION: the lint passes need these options like they need the lint store
+#[allow(rustc::bad_opt_access)]
+pub fn set_compiler_options(opts: &mut rustc_session::config::Options) {
+    // FIXME: #4825; This is required, because Clippy lints that are based on MIR have to be
+    // run on the unoptimized MIR. On the other hand this results in some false negatives. If
+    // MIR passes can be enabled / disabled separately, we should figure out, what passes to
+    // use for Clippy.
+    opts.unstable_opts.mir_opt_level = Some(0);
+
+    // Disable flattening and inlining of format_args!(), so the HIR matches with the AST.
+    opts.unstable_opts.flatten_format_args = false;
+}
+
+/// Runs the compiler on `threads` threads, which lets [`run_stateless_passes_per_module`] lint
+/// the modules in parallel.
+///
+/// Used in `./src/driver.rs` for `cargo clippy --lint-threads`.
+#[allow(rustc::bad_opt_access)]
+pub fn set_threads(opts: &mut rustc_session::config::Options, threads: usize) {
+    opts.unstable_opts.threads = threads;
+}
+
+/// Register renamed lints.
+///
+/// Used in [`register_with_config`].
 pub fn register_renamed(ls: &mut rustc_lint::LintStore) {
     for (old_name, new_name) in renamed_lints::RENAMED_LINTS {
         ls.register_renamed(old_name, new_name);
@@ -13,6 +13,7 @@ pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, pat: &'tcx Pat<'_>, arg: &'tcx
     let pat_span = pat.span;
 
     if let PatKind::Tuple(pat, _) = pat.kind {
+        #[expect(clippy::manual_slice_pattern)]
         if pat.len() == 2 {
             let arg_span = arg.span;
             let (new_pat_span, kind, ty, mutbl) = match *cx.typeck_results().expr_ty(arg).kind() {
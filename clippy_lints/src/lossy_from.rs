@@ -0,0 +1,307 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{get_parent_expr, is_wild, path_to_local_id};
+use core::ops::ControlFlow;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::{Arm, ExprKind, ImplItemKind, Item, ItemKind, LetStmt, MatchSource, Node, Pat, PatKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, AdtDef, TypeckResults, VariantDef};
+use rustc_session::declare_lint_pass;
+use rustc_span::{sym, Span, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `From` implementations converting a struct or an enum while ignoring a part of
+    /// it: fields which are never read, payloads of variants matched with `_` or `..`, or several
+    /// variants converted by the same wildcard arm.
+    ///
+    /// ### Why is this bad?
+    /// `From` is expected to be a lossless conversion, and `into()` calls don't show that
+    /// information is lost. When the loss is deliberate, a named method makes it visible, or the
+    /// lint can be allowed on the implementation to acknowledge it.
+    ///
+    /// ### Known problems
+    /// The conversion is only checked when its argument is matched, destructured or has its
+    /// fields read directly in `from`.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// enum Event {
+    ///     Click { x: i32, y: i32 },
+    ///     Key(char),
+    ///     Scroll(i32),
+    /// }
+    ///
+    /// enum Kind {
+    ///     Click,
+    ///     Other,
+    /// }
+    ///
+    /// impl From<Event> for Kind {
+    ///     fn from(event: Event) -> Self {
+    ///         match event {
+    ///             Event::Click { .. } => Kind::Click,
+    ///             _ => Kind::Other,
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # enum Event {
+    /// #     Click { x: i32, y: i32 },
+    /// #     Key(char),
+    /// #     Scroll(i32),
+    /// # }
+    /// # enum Kind {
+    /// #     Click,
+    /// #     Other,
+    /// # }
+    /// impl Event {
+    ///     fn kind(&self) -> Kind {
+    ///         match self {
+    ///             Event::Click { .. } => Kind::Click,
+    ///             Event::Key(_) | Event::Scroll(_) => Kind::Other,
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub LOSSY_FROM,
+    pedantic,
+    "`From` implementations ignoring a part of the converted value"
+}
+
+declare_lint_pass!(LossyFrom => [LOSSY_FROM]);
+
+impl<'tcx> LateLintPass<'tcx> for LossyFrom {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if let ItemKind::Impl(impl_) = item.kind
+            && let Some(trait_ref) = impl_.of_trait
+            && !item.span.from_expansion()
+            && let Some(trait_id) = trait_ref.trait_def_id()
+            && cx.tcx.is_diagnostic_item(sym::From, trait_id)
+            && let Some(trait_ref) = cx.tcx.impl_trait_ref(item.owner_id)
+            && let ty::Adt(adt, _) = trait_ref.instantiate_identity().args.type_at(1).kind()
+            && let Some(from) = impl_.items.iter().find(|item| item.ident.name == sym::from)
+            && let Node::ImplItem(from) = cx.tcx.hir_node(from.id.hir_id())
+            && let ImplItemKind::Fn(sig, body_id) = from.kind
+            && let body = cx.tcx.hir().body(body_id)
+            && let [param] = body.params
+        {
+            let mut finder = LossFinder {
+                cx,
+                typeck: cx.tcx.typeck_body(body_id),
+                adt: *adt,
+                losses: Vec::new(),
+            };
+            if finder.param(param.pat, body.value).is_some() && !finder.losses.is_empty() {
+                span_lint_and_then(
+                    cx,
+                    LOSSY_FROM,
+                    sig.span,
+                    format!(
+                        "this conversion ignores a part of the `{}`",
+                        cx.tcx.item_name(adt.did())
+                    ),
+                    |diag| {
+                        for (span, msg) in finder.losses {
+                            diag.span_note(span, msg);
+                        }
+                        diag.help("use a named method for a lossy conversion, or allow the lint to acknowledge it");
+                    },
+                );
+            }
+        }
+    }
+}
+
+struct LossFinder<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    adt: AdtDef<'tcx>,
+    losses: Vec<(Span, String)>,
+}
+
+impl<'tcx> LossFinder<'_, 'tcx> {
+    /// Looks for the parts of the argument of `from` which are ignored. Returns `None` if it's
+    /// used in a way which isn't understood.
+    fn param(&mut self, pat: &'tcx Pat<'tcx>, body: &'tcx rustc_hir::Expr<'tcx>) -> Option<()> {
+        let PatKind::Binding(_, id, _, None) = pat.kind else {
+            return self.pat(pat);
+        };
+        let mut fields_read = FxHashSet::default();
+        let mut destructured = false;
+        let unknown_use = for_each_expr(body, |e| {
+            if !path_to_local_id(e, id) {
+                return ControlFlow::Continue(());
+            }
+            if let Some(parent) = get_parent_expr(self.cx, e) {
+                match parent.kind {
+                    ExprKind::Field(..) if self.adt.is_struct() => {
+                        if let Some(idx) = self.typeck.opt_field_index(parent.hir_id) {
+                            fields_read.insert(idx);
+                        }
+                        return ControlFlow::Continue(());
+                    },
+                    ExprKind::Match(_, arms, MatchSource::Normal) if !destructured => {
+                        destructured = true;
+                        return match self.arms(arms) {
+                            Some(()) => ControlFlow::Continue(()),
+                            None => ControlFlow::Break(()),
+                        };
+                    },
+                    _ => {},
+                }
+            } else if let Node::LetStmt(LetStmt { pat, .. }) = self.cx.tcx.parent_hir_node(e.hir_id)
+                && !destructured
+            {
+                destructured = true;
+                return match self.pat(pat) {
+                    Some(()) => ControlFlow::Continue(()),
+                    None => ControlFlow::Break(()),
+                };
+            }
+            ControlFlow::Break(())
+        });
+        if unknown_use.is_some() {
+            return None;
+        }
+        if !destructured && self.adt.is_enum() {
+            self.losses.push((pat.span, String::from("the value is never read")));
+        } else if !destructured {
+            let variant = self.adt.non_enum_variant();
+            let unread: Vec<_> = variant
+                .fields
+                .iter_enumerated()
+                .filter(|(idx, _)| !fields_read.contains(idx))
+                .map(|(_, field)| field.name)
+                .collect();
+            if !unread.is_empty() {
+                self.losses.push((pat.span, fields_msg(&unread, "never read")));
+            }
+        }
+        Some(())
+    }
+
+    /// Looks for the variants merged by a wildcard arm, and the fields ignored by the patterns.
+    fn arms(&mut self, arms: &'tcx [Arm<'tcx>]) -> Option<()> {
+        let mut covered = FxHashSet::default();
+        for arm in arms {
+            if arm.guard.is_some() {
+                return None;
+            }
+            match arm.pat.kind {
+                PatKind::Wild if self.adt.is_enum() => {
+                    let merged: Vec<_> = self
+                        .adt
+                        .variants()
+                        .iter_enumerated()
+                        .filter(|(idx, _)| !covered.contains(idx))
+                        .map(|(_, variant)| format!("`{}`", variant.name))
+                        .collect();
+                    if merged.len() > 1 {
+                        self.losses.push((
+                            arm.pat.span,
+                            format!("{} are all converted by this arm", merged.join(", ")),
+                        ));
+                    }
+                },
+                _ => {
+                    for pat in or_pats(arm.pat) {
+                        let variant = self.variant(pat)?;
+                        covered.insert(self.adt.variant_index_with_id(variant.def_id));
+                        self.pat(pat)?;
+                    }
+                },
+            }
+        }
+        Some(())
+    }
+
+    /// Looks for the fields ignored by a pattern of the converted type.
+    fn pat(&mut self, pat: &'tcx Pat<'tcx>) -> Option<()> {
+        let variant = self.variant(pat)?;
+        let ignored: Vec<Symbol> = match pat.kind {
+            PatKind::Struct(_, fields, has_rest) => {
+                let listed: FxHashSet<_> = fields.iter().map(|field| field.ident.name).collect();
+                variant
+                    .fields
+                    .iter()
+                    .filter(|field| {
+                        if listed.contains(&field.name) {
+                            fields
+                                .iter()
+                                .any(|pat_field| pat_field.ident.name == field.name && is_wild(pat_field.pat))
+                        } else {
+                            has_rest
+                        }
+                    })
+                    .map(|field| field.name)
+                    .collect()
+            },
+            PatKind::TupleStruct(_, pats, dotdot) => {
+                let skipped = variant.fields.len().saturating_sub(pats.len());
+                variant
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| match dotdot.as_opt_usize() {
+                        Some(pos) if i >= pos && i < pos + skipped => true,
+                        Some(pos) if i >= pos => is_wild(&pats[i - skipped]),
+                        _ => is_wild(&pats[i]),
+                    })
+                    .map(|(_, field)| field.name)
+                    .collect()
+            },
+            PatKind::Path(_) => Vec::new(),
+            _ => return None,
+        };
+        if !ignored.is_empty() {
+            let what = if self.adt.is_enum() {
+                format!("ignored in `{}`", variant.name)
+            } else {
+                String::from("ignored")
+            };
+            self.losses.push((pat.span, fields_msg(&ignored, &what)));
+        }
+        Some(())
+    }
+
+    fn variant(&self, pat: &Pat<'_>) -> Option<&'tcx VariantDef> {
+        let (PatKind::Struct(ref qpath, ..) | PatKind::TupleStruct(ref qpath, ..) | PatKind::Path(ref qpath)) =
+            pat.kind
+        else {
+            return None;
+        };
+        let res = self.typeck.qpath_res(qpath, pat.hir_id);
+        let is_adt = matches!(self.typeck.pat_ty(pat).kind(), ty::Adt(adt, _) if *adt == self.adt);
+        let is_variant = matches!(
+            res,
+            Res::Def(
+                DefKind::Variant | DefKind::Ctor(..) | DefKind::Struct | DefKind::TyAlias | DefKind::AssocTy,
+                _
+            ) | Res::SelfTyParam { .. }
+                | Res::SelfTyAlias { .. }
+                | Res::SelfCtor(..)
+        );
+        (is_adt && is_variant).then(|| self.adt.variant_of_res(res))
+    }
+}
+
+fn or_pats<'tcx>(pat: &'tcx Pat<'tcx>) -> &'tcx [Pat<'tcx>] {
+    match pat.kind {
+        PatKind::Or(pats) => pats,
+        _ => std::slice::from_ref(pat),
+    }
+}
+
+fn fields_msg(fields: &[Symbol], what: &str) -> String {
+    let names: Vec<_> = fields.iter().map(|name| format!("`{name}`")).collect();
+    if let [name] = &*names {
+        format!("the field {name} is {what}")
+    } else {
+        format!("the fields {} are {what}", names.join(", "))
+    }
+}
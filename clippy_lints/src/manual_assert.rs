@@ -1,7 +1,8 @@
 use crate::rustc_lint::LintContext;
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::macros::{is_panic, root_macro_call};
-use clippy_utils::{is_else_clause, is_parent_stmt, peel_blocks_with_stmt, span_extract_comment, sugg};
+use clippy_utils::source::{removed_comments, removed_source_contains_cfg, with_leading_comments};
+use clippy_utils::{is_else_clause, is_parent_stmt, peel_blocks_with_stmt, sugg};
 use rustc_errors::Applicability;
 use rustc_hir::{Expr, ExprKind, UnOp};
 use rustc_lint::{LateContext, LateLintPass};
@@ -50,13 +51,12 @@ impl<'tcx> LateLintPass<'tcx> for ManualAssert {
             // shuffles the condition around.
             // Should this have a config value?
             && !is_else_clause(cx.tcx, expr)
+            // Statements removed by `#[cfg]` aren't in the block, but would still be deleted.
+            && !removed_source_contains_cfg(cx, expr.span, &[cond.span, macro_call.span])
+            && let Some(comments) = removed_comments(cx, expr.span, &[cond.span, macro_call.span])
         {
             let mut applicability = Applicability::MachineApplicable;
             let cond = cond.peel_drop_temps();
-            let mut comments = span_extract_comment(cx.sess().source_map(), expr.span);
-            if !comments.is_empty() {
-                comments += "\n";
-            }
             let (cond, not) = match cond.kind {
                 ExprKind::Unary(UnOp::Not, e) => (e, ""),
                 _ => (cond, "!"),
@@ -77,7 +77,7 @@ impl<'tcx> LateLintPass<'tcx> for ManualAssert {
                         diag.tool_only_span_suggestion(
                             expr.span.shrink_to_lo(),
                             "add comments back",
-                            comments,
+                            with_leading_comments(cx, &comments, "", expr.span),
                             applicability,
                         );
                     }
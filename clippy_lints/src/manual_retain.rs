@@ -1,17 +1,22 @@
 use clippy_config::msrvs::{self, Msrv};
 use clippy_utils::diagnostics::span_lint_and_sugg;
 use clippy_utils::source::snippet;
-use clippy_utils::ty::{is_type_diagnostic_item, is_type_lang_item};
-use clippy_utils::{match_def_path, paths, SpanlessEq};
+use clippy_utils::ty::{is_copy, is_type_diagnostic_item, is_type_lang_item};
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{get_parent_expr, is_trait_method, match_def_path, path_to_local_id, paths, SpanlessEq};
 use rustc_errors::Applicability;
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
 use rustc_hir::ExprKind::Assign;
+use rustc_hir::Mutability;
 use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::adjustment::{Adjust, Adjustment};
+use rustc_middle::ty::{self, Ty};
 use rustc_semver::RustcVersion;
 use rustc_session::impl_lint_pass;
-use rustc_span::symbol::sym;
-use rustc_span::Span;
+use rustc_span::symbol::{sym, Ident};
+use rustc_span::{Pos, Span, SyntaxContext};
+use std::ops::ControlFlow;
 
 const ACCEPTABLE_METHODS: [&[&str]; 5] = [
     &paths::BINARYHEAP_ITER,
@@ -34,6 +39,11 @@ const MAP_TYPES: [rustc_span::Symbol; 2] = [sym::BTreeMap, sym::HashMap];
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for code to be replaced by `.retain()`.
+    ///
+    /// This includes the maps rebuilt from their cloned entries, e.g.
+    /// `map = map.iter().filter(..).map(|(k, v)| (k.clone(), v.clone())).collect()`, and the
+    /// strings rebuilt from their filtered chars. The predicate is adapted to the parameters of
+    /// `retain`.
     /// ### Why is this bad?
     /// `.retain()` is simpler and avoids needless allocation.
     /// ### Example
@@ -79,6 +89,8 @@ impl<'tcx> LateLintPass<'tcx> for ManualRetain {
             check_into_iter(cx, left_expr, target_expr, expr.span, &self.msrv);
             check_iter(cx, left_expr, target_expr, expr.span, &self.msrv);
             check_to_owned(cx, left_expr, target_expr, expr.span, &self.msrv);
+            check_map_iter(cx, left_expr, target_expr, expr.span, &self.msrv);
+            check_chars(cx, left_expr, target_expr, expr.span, &self.msrv);
         }
     }
 
@@ -153,10 +165,27 @@ fn check_iter(
         && let [filter_params] = filter_body.params
     {
         match filter_params.pat.kind {
-            // hir::PatKind::Binding(_, _, _, None) => {
-            //     // Be conservative now. Do nothing here.
-            //     // TODO: Ideally, we can rewrite the lambda by stripping one level of reference
-            // },
+            hir::PatKind::Binding(_, _, _, None) => {
+                // `filter` passes `&&T` and `retain` passes `&T`
+                if let ty::Ref(_, elem_ref_ty, _) = cx.typeck_results().node_type(filter_params.hir_id).kind()
+                    && let ty::Ref(_, elem_ty, _) = elem_ref_ty.kind()
+                    && let Some(closure) = adapt_closure(
+                        cx,
+                        &[RetainParam {
+                            pat: filter_params.pat,
+                            ty: *elem_ty,
+                            by_ref: Some(Mutability::Not),
+                        }],
+                        filter_body,
+                    )
+                {
+                    make_span_lint_and_sugg(
+                        cx,
+                        parent_expr_span,
+                        format!("{}.retain({closure})", snippet(cx, left_expr.span, "..")),
+                    );
+                }
+            },
             hir::PatKind::Tuple([_, _], _) => {
                 // the `&&` reference for the `filter` method will be auto derefed to `ref`
                 // so, we can directly use the lambda
@@ -193,11 +222,74 @@ fn check_to_owned(
     parent_expr_span: Span,
     msrv: &Msrv,
 ) {
-    if msrv.meets(msrvs::STRING_RETAIN)
-        && let hir::ExprKind::MethodCall(_, filter_expr, [], _) = &target_expr.kind
+    if let hir::ExprKind::MethodCall(_, filter_expr, [], _) = &target_expr.kind
         && let Some(to_owned_def_id) = cx.typeck_results().type_dependent_def_id(target_expr.hir_id)
         && cx.tcx.is_diagnostic_item(sym::to_owned_method, to_owned_def_id)
-        && let hir::ExprKind::MethodCall(_, chars_expr, [_], _) = &filter_expr.kind
+    {
+        check_chars(cx, left_expr, filter_expr, parent_expr_span, msrv);
+    }
+}
+
+fn check_map_iter(
+    cx: &LateContext<'_>,
+    left_expr: &hir::Expr<'_>,
+    target_expr: &hir::Expr<'_>,
+    parent_expr_span: Span,
+    msrv: &Msrv,
+) {
+    if let hir::ExprKind::MethodCall(map_seg, filter_expr, [map_closure], _) = &target_expr.kind
+        && map_seg.ident.name == sym::map
+        && is_trait_method(cx, target_expr, sym::Iterator)
+        && is_clone_pair(cx, map_closure)
+        && let hir::ExprKind::MethodCall(_, iter_expr, [closure_expr], _) = &filter_expr.kind
+        && let Some(filter_def_id) = cx.typeck_results().type_dependent_def_id(filter_expr.hir_id)
+        && match_def_path(cx, filter_def_id, &paths::CORE_ITER_FILTER)
+        && let hir::ExprKind::MethodCall(iter_seg, struct_expr, [], _) = &iter_expr.kind
+        && iter_seg.ident.name == sym::iter
+        && match_map_type(cx, struct_expr)
+        && match_acceptable_type(cx, left_expr, msrv)
+        && let left_expr = peel_deref(left_expr)
+        && SpanlessEq::new(cx).eq_expr(left_expr, struct_expr)
+        && let ty::Adt(_, args) = cx.typeck_results().expr_ty(struct_expr).peel_refs().kind()
+        && let hir::ExprKind::Closure(closure) = closure_expr.kind
+        && let filter_body = cx.tcx.hir().body(closure.body)
+        && let [filter_params] = filter_body.params
+        && let hir::PatKind::Tuple([key_pat, value_pat], dotdot) = peel_ref_pats(filter_params.pat).kind
+        && dotdot.as_opt_usize().is_none()
+        && let Some(closure) = adapt_closure(
+            cx,
+            &[
+                RetainParam {
+                    pat: key_pat,
+                    ty: args.type_at(0),
+                    by_ref: Some(Mutability::Not),
+                },
+                RetainParam {
+                    pat: value_pat,
+                    ty: args.type_at(1),
+                    by_ref: Some(Mutability::Mut),
+                },
+            ],
+            filter_body,
+        )
+    {
+        make_span_lint_and_sugg(
+            cx,
+            parent_expr_span,
+            format!("{}.retain({closure})", snippet(cx, left_expr.span, "..")),
+        );
+    }
+}
+
+fn check_chars(
+    cx: &LateContext<'_>,
+    left_expr: &hir::Expr<'_>,
+    filter_expr: &hir::Expr<'_>,
+    parent_expr_span: Span,
+    msrv: &Msrv,
+) {
+    if msrv.meets(msrvs::STRING_RETAIN)
+        && let hir::ExprKind::MethodCall(_, chars_expr, [closure_expr], _) = &filter_expr.kind
         && let Some(filter_def_id) = cx.typeck_results().type_dependent_def_id(filter_expr.hir_id)
         && match_def_path(cx, filter_def_id, &paths::CORE_ITER_FILTER)
         && let hir::ExprKind::MethodCall(_, str_expr, [], _) = &chars_expr.kind
@@ -205,26 +297,220 @@ fn check_to_owned(
         && match_def_path(cx, chars_expr_def_id, &paths::STR_CHARS)
         && let ty = cx.typeck_results().expr_ty(str_expr).peel_refs()
         && is_type_lang_item(cx, ty, hir::LangItem::String)
+        && let left_expr = peel_deref(left_expr)
         && SpanlessEq::new(cx).eq_expr(left_expr, str_expr)
-        && let hir::ExprKind::MethodCall(_, _, [closure_expr], _) = filter_expr.kind
         && let hir::ExprKind::Closure(closure) = closure_expr.kind
         && let filter_body = cx.tcx.hir().body(closure.body)
         && let [filter_params] = filter_body.params
+        && let Some(closure) = adapt_closure(
+            cx,
+            &[RetainParam {
+                pat: filter_params.pat,
+                ty: cx.tcx.types.char,
+                by_ref: None,
+            }],
+            filter_body,
+        )
     {
-        if let hir::PatKind::Ref(pat, _) = filter_params.pat.kind {
-            make_span_lint_and_sugg(
-                cx,
-                parent_expr_span,
-                format!(
-                    "{}.retain(|{}| {})",
-                    snippet(cx, left_expr.span, ".."),
-                    snippet(cx, pat.span, ".."),
-                    snippet(cx, filter_body.value.span, "..")
-                ),
-            );
+        make_span_lint_and_sugg(
+            cx,
+            parent_expr_span,
+            format!("{}.retain({closure})", snippet(cx, left_expr.span, "..")),
+        );
+    }
+}
+
+/// Checks if `expr` is a closure cloning the key and the value of a map entry, e.g.
+/// `|(k, v)| (k.clone(), v.clone())` or `|(&k, &v)| (k, v)`
+fn is_clone_pair(cx: &LateContext<'_>, expr: &hir::Expr<'_>) -> bool {
+    fn is_clone_of(mut expr: &hir::Expr<'_>, pat: &hir::Pat<'_>) -> bool {
+        let hir::PatKind::Binding(_, id, _, None) = peel_ref_pats(pat).kind else {
+            return false;
+        };
+        loop {
+            expr = match expr.kind {
+                hir::ExprKind::MethodCall(seg, recv, [], _)
+                    if matches!(seg.ident.name.as_str(), "clone" | "to_owned") =>
+                {
+                    recv
+                },
+                hir::ExprKind::Unary(hir::UnOp::Deref, inner) => inner,
+                _ => return path_to_local_id(expr, id),
+            };
+        }
+    }
+
+    if let hir::ExprKind::Closure(closure) = expr.kind
+        && let body = cx.tcx.hir().body(closure.body)
+        && let [param] = body.params
+        && let hir::PatKind::Tuple([key_pat, value_pat], dotdot) = peel_ref_pats(param.pat).kind
+        && dotdot.as_opt_usize().is_none()
+        && let hir::ExprKind::Tup([key, value]) = body.value.kind
+    {
+        is_clone_of(key, key_pat) && is_clone_of(value, value_pat)
+    } else {
+        false
+    }
+}
+
+/// A parameter of the closure passed to `retain`, and the pattern of the `filter` closure which
+/// binds the same value
+struct RetainParam<'tcx> {
+    pat: &'tcx hir::Pat<'tcx>,
+    /// The type of the value, without the reference passed by `retain`
+    ty: Ty<'tcx>,
+    /// Whether `retain` passes the value by reference
+    by_ref: Option<Mutability>,
+}
+
+/// Rewrites the `filter` closure whose body is `body` into a closure taking `params`, by adjusting
+/// the references to the bindings of the patterns. Copy values are bound by value.
+fn adapt_closure<'tcx>(cx: &LateContext<'tcx>, params: &[RetainParam<'tcx>], body: &hir::Body<'tcx>) -> Option<String> {
+    let body_span = body.value.span;
+    let mut param_snippets = Vec::with_capacity(params.len());
+    let mut replacements = Vec::new();
+    for param in params {
+        match peel_ref_pats(param.pat).kind {
+            hir::PatKind::Wild => param_snippets.push("_".to_owned()),
+            hir::PatKind::Binding(hir::BindingMode::NONE, id, ident, None) => {
+                let depth = ref_depth(cx.typeck_results().node_type(id), param.ty)?;
+                let by_value = param.by_ref.is_none() || is_copy(cx, param.ty);
+                param_snippets.push(match (by_value, param.by_ref) {
+                    (true, Some(Mutability::Not)) => format!("&{ident}"),
+                    (true, Some(Mutability::Mut)) => format!("&mut {ident}"),
+                    _ => ident.to_string(),
+                });
+                let binding = AdaptedBinding {
+                    ident,
+                    ty: param.ty,
+                    depth,
+                    by_value,
+                    by_mut_ref: param.by_ref == Some(Mutability::Mut),
+                };
+                for_each_expr(body.value, |e| {
+                    if !path_to_local_id(e, id) {
+                        return ControlFlow::Continue(());
+                    }
+                    match binding.adapt_use(cx, e, body_span.ctxt()) {
+                        Some(replacement) => {
+                            replacements.push(replacement);
+                            ControlFlow::Continue(())
+                        },
+                        None => ControlFlow::Break(()),
+                    }
+                })
+                .is_none()
+                .then_some(())?;
+            },
+            _ => return None,
         }
-        // Be conservative now. Do nothing for the `Binding` case.
-        // TODO: Ideally, we can rewrite the lambda by stripping one level of reference
+    }
+
+    let mut adapted_body = snippet(cx, body_span, "..").into_owned();
+    replacements.sort_by_key(|(span, _)| span.lo());
+    for (span, replacement) in replacements.into_iter().rev() {
+        let start = (span.lo() - body_span.lo()).to_usize();
+        let end = (span.hi() - body_span.lo()).to_usize();
+        adapted_body.replace_range(start..end, &replacement);
+    }
+    Some(format!("|{}| {adapted_body}", param_snippets.join(", ")))
+}
+
+/// A binding of the `filter` closure, bound by `retain` to a value with a different number of
+/// references
+struct AdaptedBinding<'tcx> {
+    ident: Ident,
+    ty: Ty<'tcx>,
+    /// The number of references to the value in the binding of the `filter` closure
+    depth: usize,
+    /// Whether the binding is bound to the value itself in the `retain` closure, instead of the
+    /// reference passed by `retain`
+    by_value: bool,
+    by_mut_ref: bool,
+}
+
+impl<'tcx> AdaptedBinding<'tcx> {
+    /// Returns the replacement of the use `expr` of the binding, or of the dereferences of it,
+    /// which gives an expression of the same type in the `retain` closure
+    fn adapt_use(&self, cx: &LateContext<'tcx>, expr: &hir::Expr<'_>, ctxt: SyntaxContext) -> Option<(Span, String)> {
+        let ident = self.ident;
+        let mut expr = expr;
+        let mut derefs = 0;
+        let mut parent = get_parent_expr(cx, expr);
+        while let Some(deref) = parent
+            && let hir::ExprKind::Unary(hir::UnOp::Deref, _) = deref.kind
+        {
+            expr = deref;
+            derefs += 1;
+            parent = get_parent_expr(cx, deref);
+        }
+        if expr.span.ctxt() != ctxt || parent.is_some_and(|parent| parent.span.ctxt() != ctxt) {
+            return None;
+        }
+        let mut depth = self.depth.checked_sub(derefs)?;
+        // The arguments which are dereferenced by a coercion only need the references of the
+        // coerced type.
+        let mut coerced = false;
+        if let Some(Adjustment {
+            kind: Adjust::Deref(_), ..
+        }) = cx.typeck_results().expr_adjustments(expr).first()
+            && let Some(coerced_depth) = ref_depth(cx.typeck_results().expr_ty_adjusted(expr), self.ty)
+        {
+            depth = coerced_depth;
+            coerced = true;
+        }
+        // The receivers of methods and the bases of fields and indexes are dereferenced automatically.
+        let auto_deref = parent.is_some_and(|parent| match parent.kind {
+            hir::ExprKind::MethodCall(_, recv, ..) => recv.hir_id == expr.hir_id,
+            hir::ExprKind::Field(..) => true,
+            hir::ExprKind::Index(base, ..) => base.hir_id == expr.hir_id,
+            _ => false,
+        });
+        let replacement = if auto_deref {
+            ident.to_string()
+        } else if self.by_value {
+            format!("{}{ident}", "&".repeat(depth))
+        } else if depth == 0 {
+            format!("*{ident}")
+        } else if self.by_mut_ref {
+            if coerced && depth == 1 {
+                // `&mut V` is coerced to `&V` as well
+                ident.to_string()
+            } else {
+                format!("{}*{ident}", "&".repeat(depth))
+            }
+        } else {
+            format!("{}{ident}", "&".repeat(depth - 1))
+        };
+        Some((expr.span, replacement))
+    }
+}
+
+/// Returns the number of references to `target` in `ty`
+fn ref_depth<'tcx>(mut ty: Ty<'tcx>, target: Ty<'tcx>) -> Option<usize> {
+    let mut depth = 0;
+    while ty != target {
+        let ty::Ref(_, inner, _) = ty.kind() else {
+            return None;
+        };
+        ty = *inner;
+        depth += 1;
+    }
+    Some(depth)
+}
+
+fn peel_ref_pats<'a, 'tcx>(mut pat: &'a hir::Pat<'tcx>) -> &'a hir::Pat<'tcx> {
+    while let hir::PatKind::Ref(inner, _) = pat.kind {
+        pat = inner;
+    }
+    pat
+}
+
+/// `*map = map.iter()...` assigns to the map behind a reference.
+fn peel_deref<'a, 'tcx>(expr: &'a hir::Expr<'tcx>) -> &'a hir::Expr<'tcx> {
+    match expr.kind {
+        hir::ExprKind::Unary(hir::UnOp::Deref, inner) => inner,
+        _ => expr,
     }
 }
 
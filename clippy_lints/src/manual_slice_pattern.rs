@@ -0,0 +1,223 @@
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet_with_context;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::visitors::for_each_expr_with_closures;
+use clippy_utils::{get_parent_expr, path_to_local, path_to_local_id};
+use rustc_ast::{BinOpKind, Mutability};
+use rustc_errors::Applicability;
+use rustc_hir::{Expr, ExprKind, HirId, LetExpr, PatKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty;
+use rustc_middle::ty::adjustment::{Adjust, AutoBorrow, AutoBorrowMutability};
+use rustc_session::impl_lint_pass;
+use rustc_span::{sym, Span};
+use std::ops::ControlFlow;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `if` expressions checking the length of a slice or a `Vec` against a constant,
+    /// e.g. `v.len() == 2` or `let 2 = v.len()`, whose body only uses it by indexing it with
+    /// constants. Lengths above `max-suggested-slice-pattern-length` aren't linted.
+    ///
+    /// ### Why is this bad?
+    /// The indexing can panic if the length check is changed, and the elements have no names. A
+    /// slice pattern checks the length and binds the elements at once.
+    ///
+    /// ### Limitations
+    /// Only the bodies which only read the elements through constant indices are linted.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # fn f(_: u32, _: u32) {}
+    /// let v = vec![1, 2];
+    /// if v.len() == 2 {
+    ///     f(v[0], v[1]);
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # fn f(_: u32, _: u32) {}
+    /// let v = vec![1, 2];
+    /// if let [v_0, v_1] = v.as_slice() {
+    ///     f(*v_0, *v_1);
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub MANUAL_SLICE_PATTERN,
+    pedantic,
+    "checking the length of a slice before indexing it instead of using a slice pattern"
+}
+
+pub struct ManualSlicePattern {
+    max_suggested_slice: u64,
+}
+
+impl ManualSlicePattern {
+    pub fn new(max_suggested_slice_pattern_length: u64) -> Self {
+        Self {
+            max_suggested_slice: max_suggested_slice_pattern_length,
+        }
+    }
+}
+
+impl_lint_pass!(ManualSlicePattern => [MANUAL_SLICE_PATTERN]);
+
+impl<'tcx> LateLintPass<'tcx> for ManualSlicePattern {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::If(cond, then, _) = expr.kind
+            && !expr.span.from_expansion()
+            && let cond = cond.peel_drop_temps()
+            && let Some((slice, len)) = checked_len(cx, cond)
+            && (1..=self.max_suggested_slice).contains(&len)
+            && let Some(local) = path_to_local(slice)
+            && let Some(uses) = index_uses(cx, then, local, len)
+            && !uses.is_empty()
+        {
+            span_lint_and_then(
+                cx,
+                MANUAL_SLICE_PATTERN,
+                cond.span,
+                "this length check is followed by indexing with constant indices",
+                |diag| {
+                    let mut app = Applicability::MaybeIncorrect;
+                    let (snip, _) = snippet_with_context(cx, slice.span, cond.span.ctxt(), "..", &mut app);
+                    let scrutinee = if let ty::Ref(_, ty, _) = cx.typeck_results().expr_ty(slice).kind()
+                        && ty.is_slice()
+                    {
+                        snip.into_owned()
+                    } else {
+                        format!("{snip}.as_slice()")
+                    };
+                    let name = cx.tcx.hir().name(local);
+                    let value_name = |index| format!("{name}_{index}");
+                    let pat = (0..len)
+                        .map(|index| {
+                            if uses.iter().any(|index_use| index_use.index == index) {
+                                value_name(index)
+                            } else {
+                                "_".to_string()
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let mut suggestions = vec![(cond.span, format!("let [{pat}] = {scrutinee}"))];
+                    suggestions.extend(uses.iter().map(|index_use| {
+                        let deref = if index_use.needs_deref { "*" } else { "" };
+                        (index_use.span, format!("{deref}{}", value_name(index_use.index)))
+                    }));
+                    diag.multipart_suggestion("use a slice pattern", suggestions, app);
+                },
+            );
+        }
+    }
+}
+
+/// Returns the slice or `Vec` whose length is checked by `cond`, and the length it's checked
+/// against.
+fn checked_len<'tcx>(cx: &LateContext<'tcx>, cond: &'tcx Expr<'tcx>) -> Option<(&'tcx Expr<'tcx>, u64)> {
+    let (len_call, len) = match cond.kind {
+        ExprKind::Binary(op, left, right) if op.node == BinOpKind::Eq => {
+            if let Some(Constant::Int(len)) = constant(cx, cx.typeck_results(), right) {
+                (left, len)
+            } else if let Some(Constant::Int(len)) = constant(cx, cx.typeck_results(), left) {
+                (right, len)
+            } else {
+                return None;
+            }
+        },
+        ExprKind::Let(LetExpr { pat, init, .. }) if let PatKind::Lit(lit) = pat.kind => {
+            let Some(Constant::Int(len)) = constant(cx, cx.typeck_results(), lit) else {
+                return None;
+            };
+            (*init, len)
+        },
+        _ => return None,
+    };
+    if let ExprKind::MethodCall(path, receiver, [], _) = len_call.kind
+        && path.ident.name == sym::len
+        && !len_call.span.from_expansion()
+        && let ty = cx.typeck_results().expr_ty(receiver).peel_refs()
+        && (ty.is_slice() || is_type_diagnostic_item(cx, ty, sym::Vec))
+    {
+        Some((receiver, len.try_into().ok()?))
+    } else {
+        None
+    }
+}
+
+struct IndexUse {
+    index: u64,
+    /// The span to replace with the name of the element
+    span: Span,
+    /// Whether the element is used by value, rather than borrowed or auto-dereferenced
+    needs_deref: bool,
+}
+
+/// Returns the uses of the local `slice` in `body`, if they are all reads of an element through a
+/// constant index lower than `len`.
+fn index_uses<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Expr<'tcx>, slice: HirId, len: u64) -> Option<Vec<IndexUse>> {
+    let mut uses = Vec::new();
+    let other_use = for_each_expr_with_closures(cx, body, |e| {
+        if !path_to_local_id(e, slice) {
+            return ControlFlow::Continue(());
+        }
+        if let Some(index_expr) = get_parent_expr(cx, e)
+            && let ExprKind::Index(base, index, _) = index_expr.kind
+            && base.hir_id == e.hir_id
+            && !index_expr.span.from_expansion()
+            && let Some(Constant::Int(index)) = constant(cx, cx.typeck_results(), index)
+            && let Ok(index) = u64::try_from(index)
+            && index < len
+            && !is_borrowed_mutably(cx, index_expr)
+        {
+            let parent = get_parent_expr(cx, index_expr).filter(|parent| !parent.span.from_expansion());
+            uses.push(match parent {
+                Some(
+                    addr_of @ Expr {
+                        kind: ExprKind::AddrOf(_, Mutability::Not, _),
+                        ..
+                    },
+                ) => IndexUse {
+                    index,
+                    span: addr_of.span,
+                    needs_deref: false,
+                },
+                // The element is auto-dereferenced.
+                Some(Expr {
+                    kind: ExprKind::MethodCall(_, receiver, ..) | ExprKind::Field(receiver, _),
+                    ..
+                }) if receiver.hir_id == index_expr.hir_id => IndexUse {
+                    index,
+                    span: index_expr.span,
+                    needs_deref: false,
+                },
+                _ => IndexUse {
+                    index,
+                    span: index_expr.span,
+                    needs_deref: true,
+                },
+            });
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    });
+    other_use.is_none().then_some(uses)
+}
+
+/// Checks if the element `index_expr` is assigned, or borrowed mutably, explicitly or not.
+fn is_borrowed_mutably(cx: &LateContext<'_>, index_expr: &Expr<'_>) -> bool {
+    let auto_borrowed_mutably = cx.typeck_results().expr_adjustments(index_expr).iter().any(|adjust| {
+        matches!(
+            adjust.kind,
+            Adjust::Borrow(AutoBorrow::Ref(_, AutoBorrowMutability::Mut { .. }))
+        )
+    });
+    auto_borrowed_mutably
+        || get_parent_expr(cx, index_expr).is_some_and(|parent| match parent.kind {
+            ExprKind::AddrOf(_, Mutability::Mut, _) => true,
+            ExprKind::Assign(lhs, ..) | ExprKind::AssignOp(_, lhs, _) => lhs.hir_id == index_expr.hir_id,
+            _ => false,
+        })
+}
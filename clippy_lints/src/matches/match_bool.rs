@@ -19,6 +19,7 @@ pub(crate) fn check(cx: &LateContext<'_>, scrutinee: &Expr<'_>, arms: &[Arm<'_>]
             expr.span,
             "you seem to be trying to match on a boolean expression",
             move |diag| {
+                #[expect(clippy::manual_slice_pattern)]
                 if arms.len() == 2 {
                     // no guards
                     let exprs = if let PatKind::Lit(arm_bool) = arms[0].pat.kind {
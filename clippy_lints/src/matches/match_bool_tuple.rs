@@ -0,0 +1,243 @@
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::source::expr_block;
+use clippy_utils::sugg::{make_binop, Sugg};
+use clippy_utils::{get_parent_expr, is_unit_expr, path_to_local, peel_blocks};
+use rustc_ast::{BinOpKind, LitKind};
+use rustc_errors::Applicability;
+use rustc_hir::{Arm, Expr, ExprKind, PatKind};
+use rustc_lint::LateContext;
+use std::iter;
+
+use super::MATCH_BOOL_TUPLE;
+
+/// The values of the booleans matched by a tuple pattern, `None` for the wildcards
+type Cube = Vec<Option<bool>>;
+
+pub(crate) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    scrutinee: &'tcx Expr<'tcx>,
+    arms: &'tcx [Arm<'tcx>],
+    expr: &'tcx Expr<'tcx>,
+    max_arity: u64,
+) {
+    if let ExprKind::Tup(elems) = scrutinee.kind
+        && arms.len() > 1
+        && u64::try_from(elems.len()).is_ok_and(|arity| (2..=max_arity).contains(&arity))
+        && elems.iter().all(|elem| cx.typeck_results().expr_ty(elem).is_bool())
+        && let Some(conds) = arms
+            .iter()
+            .map(|arm| arm_cubes(arm, elems.len()))
+            .collect::<Option<Vec<_>>>()
+    {
+        // The booleans may be evaluated several times, or not at all.
+        let mut app = if elems.iter().all(|elem| is_simple(elem)) {
+            Applicability::MachineApplicable
+        } else {
+            Applicability::MaybeIncorrect
+        };
+        let ctxt = expr.span.ctxt();
+        let elems: Vec<Sugg<'_>> = elems
+            .iter()
+            .map(|elem| Sugg::hir_with_context(cx, elem, ctxt, "..", &mut app))
+            .collect();
+        let needs_par = get_parent_expr(cx, expr).is_some_and(|parent| {
+            matches!(
+                parent.kind,
+                ExprKind::Unary(..)
+                    | ExprKind::Binary(..)
+                    | ExprKind::Cast(..)
+                    | ExprKind::MethodCall(..)
+                    | ExprKind::Field(..)
+                    | ExprKind::Index(..)
+            )
+        });
+        // A `match` with more arms which aren't booleans is a decision table, which is clearer
+        // than the equivalent `if`/`else` chain.
+        let (help, sugg) = if let Some(values) = arms
+            .iter()
+            .map(|arm| bool_lit(peel_blocks(arm.body)))
+            .collect::<Option<Vec<_>>>()
+        {
+            let Some(sugg) = bool_expr(&elems, &conds, &values) else {
+                return;
+            };
+            let sugg = if needs_par { sugg.maybe_par() } else { sugg };
+            ("use a boolean expression", sugg.to_string())
+        } else if let [first, last] = arms
+            && let Some(cond) = arm_cond(&elems, &conds[0])
+        {
+            let mut sugg = format!(
+                "if {cond} {}",
+                expr_block(cx, first.body, ctxt, "..", Some(expr.span), &mut app)
+            );
+            if !is_unit_expr(last.body) {
+                sugg.push_str(" else ");
+                sugg.push_str(&expr_block(cx, last.body, ctxt, "..", Some(expr.span), &mut app));
+            }
+            let sugg = if needs_par { format!("({sugg})") } else { sugg };
+            ("use an `if`/`else` expression", sugg)
+        } else {
+            return;
+        };
+        span_lint_and_sugg(
+            cx,
+            MATCH_BOOL_TUPLE,
+            expr.span,
+            "you seem to be trying to match on a tuple of booleans",
+            help,
+            sugg,
+            app,
+        );
+    }
+}
+
+/// Returns the tuple patterns matched by `arm`, which can be an or-pattern
+fn arm_cubes(arm: &Arm<'_>, arity: usize) -> Option<Vec<Cube>> {
+    if arm.guard.is_some() {
+        return None;
+    }
+    let pats = match arm.pat.kind {
+        PatKind::Or(pats) => pats,
+        _ => std::slice::from_ref(arm.pat),
+    };
+    pats.iter()
+        .map(|pat| match pat.kind {
+            PatKind::Wild => Some(vec![None; arity]),
+            PatKind::Tuple(elems, rest) => {
+                let mut cube = Vec::with_capacity(arity);
+                for (index, elem) in elems.iter().enumerate() {
+                    if rest.as_opt_usize() == Some(index) {
+                        cube.extend(iter::repeat(None).take(arity - elems.len()));
+                    }
+                    cube.push(match elem.kind {
+                        PatKind::Wild => None,
+                        PatKind::Lit(lit) => Some(bool_lit(lit)?),
+                        _ => return None,
+                    });
+                }
+                if rest.as_opt_usize() == Some(elems.len()) {
+                    cube.extend(iter::repeat(None).take(arity - elems.len()));
+                }
+                Some(cube)
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn bool_lit(expr: &Expr<'_>) -> Option<bool> {
+    if let ExprKind::Lit(lit) = expr.kind
+        && let LitKind::Bool(value) = lit.node
+        && !expr.span.from_expansion()
+    {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Locals and their fields, which can be evaluated several times
+fn is_simple(expr: &Expr<'_>) -> bool {
+    match expr.kind {
+        ExprKind::Field(base, _) => is_simple(base),
+        ExprKind::Lit(_) => true,
+        _ => path_to_local(expr).is_some(),
+    }
+}
+
+fn cube_matches(cube: &[Option<bool>], row: u64) -> bool {
+    cube.iter()
+        .enumerate()
+        .all(|(index, value)| value.map_or(true, |value| value == (row & (1 << index) != 0)))
+}
+
+/// The conjunction of the booleans of `cube`, `None` if it's a wildcard
+fn cube_sugg<'a>(elems: &[Sugg<'a>], cube: &[Option<bool>]) -> Option<Sugg<'a>> {
+    cube.iter()
+        .zip(elems)
+        .filter_map(|(value, elem)| {
+            value.map(|value| {
+                let elem = elem.clone();
+                if value { elem } else { !elem }
+            })
+        })
+        .reduce(|acc, elem| acc.and(&elem))
+}
+
+/// The disjunction of the patterns of an arm, `None` if one of them is a wildcard
+fn arm_cond<'a>(elems: &[Sugg<'a>], cubes: &[Cube]) -> Option<Sugg<'a>> {
+    cubes
+        .iter()
+        .map(|cube| cube_sugg(elems, cube))
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .reduce(|acc, cond| make_binop(BinOpKind::Or, &acc, &cond))
+}
+
+/// Returns a boolean expression equivalent to the `match` whose arms evaluate to `values`,
+/// preferably a conjunction or a disjunction of the booleans, or a comparison of two booleans.
+fn bool_expr<'a>(elems: &[Sugg<'a>], conds: &[Vec<Cube>], values: &[bool]) -> Option<Sugg<'a>> {
+    let arity = elems.len();
+    // The value of the `match` for each combination of the booleans
+    let table: Vec<bool> = (0..1u64 << arity)
+        .map(|row| {
+            conds
+                .iter()
+                .position(|cubes| cubes.iter().any(|cube| cube_matches(cube, row)))
+                .map_or(false, |arm| values[arm])
+        })
+        .collect();
+    let rows = || (0..1u64 << arity).zip(table.iter().copied());
+    // The booleans which have the same value in all the rows for which the table has `value`
+    let common = |value: bool| -> Cube {
+        (0..arity)
+            .map(|index| {
+                let mut bits = rows()
+                    .filter(|&(_, v)| v == value)
+                    .map(|(row, _)| row & (1 << index) != 0);
+                let first = bits.next()?;
+                bits.all(|bit| bit == first).then_some(first)
+            })
+            .collect()
+    };
+    if table.iter().all(|&v| v == table[0]) {
+        return None;
+    }
+    let when_true = common(true);
+    if rows().all(|(row, v)| v == cube_matches(&when_true, row)) {
+        return cube_sugg(elems, &when_true);
+    }
+    let when_false = common(false);
+    if rows().all(|(row, v)| v != cube_matches(&when_false, row)) {
+        return when_false
+            .iter()
+            .zip(elems)
+            .filter_map(|(value, elem)| {
+                value.map(|value| {
+                    let elem = elem.clone();
+                    if value { !elem } else { elem }
+                })
+            })
+            .reduce(|acc, elem| make_binop(BinOpKind::Or, &acc, &elem));
+    }
+    if let [left, right] = elems {
+        if table == [true, false, false, true] {
+            return Some(make_binop(BinOpKind::Eq, left, right));
+        } else if table == [false, true, true, false] {
+            return Some(make_binop(BinOpKind::Ne, left, right));
+        }
+    }
+    // Each arm is `cond || rest` or `!cond && rest`, where `rest` are the following arms.
+    let mut rest = Err(*values.last()?);
+    for (cubes, &value) in conds.iter().zip(values).rev().skip(1) {
+        let cond = arm_cond(elems, cubes)?;
+        rest = match (value, rest) {
+            (true, Err(true)) | (false, Err(false)) => Err(value),
+            (true, Err(false)) => Ok(cond),
+            (false, Err(true)) => Ok(!cond),
+            (true, Ok(rest)) => Ok(make_binop(BinOpKind::Or, &cond, &rest)),
+            (false, Ok(rest)) => Ok((!cond).and(&rest)),
+        };
+    }
+    rest.ok()
+}
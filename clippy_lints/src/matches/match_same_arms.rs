@@ -16,9 +16,12 @@ use rustc_span::{ErrorGuaranteed, Symbol};
 use super::MATCH_SAME_ARMS;
 
 #[expect(clippy::too_many_lines)]
-pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, arms: &'tcx [Arm<'_>]) {
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, arms: &'tcx [Arm<'_>], ignore_path_prefixes: bool) {
     let hash = |&(_, arm): &(usize, &Arm<'_>)| -> u64 {
         let mut h = SpanlessHash::new(cx);
+        if ignore_path_prefixes {
+            h = h.ignore_path_prefixes();
+        }
         h.hash_expr(arm.body);
         h.finish()
     };
@@ -87,9 +90,15 @@ pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, arms: &'tcx [Arm<'_>]) {
                 }
             };
 
-            SpanlessEq::new(cx)
-                .expr_fallback(eq_fallback)
-                .eq_expr(expr_a, expr_b)
+            let same = {
+                let mut spanless_eq = SpanlessEq::new(cx).expr_fallback(eq_fallback);
+                if ignore_path_prefixes {
+                    spanless_eq = spanless_eq.ignore_path_prefixes();
+                }
+                spanless_eq.eq_expr(expr_a, expr_b)
+            };
+
+            same
                 // these checks could be removed to allow unused bindings
                 && bindings_eq(lhs.pat, local_map.keys().copied().collect())
                 && bindings_eq(rhs.pat, local_map.values().copied().collect())
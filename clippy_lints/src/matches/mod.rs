@@ -6,6 +6,7 @@ mod manual_unwrap_or;
 mod manual_utils;
 mod match_as_ref;
 mod match_bool;
+mod match_bool_tuple;
 mod match_like_matches;
 mod match_on_vec_items;
 mod match_ref_pats;
@@ -974,16 +975,64 @@ declare_clippy_lint! {
     "checks for unnecessary guards in match expressions"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for matches on a tuple of booleans, e.g. `match (a, b)`, whose arms only match
+    /// `true`, `false` or anything, and which either evaluate to booleans or only have two arms.
+    /// Matches with more arms are decision tables, which are clearer than `if`/`else` chains.
+    ///
+    /// The maximum number of booleans in the tuple can be configured with
+    /// `max-match-bool-tuple-arity`.
+    ///
+    /// ### Why is this bad?
+    /// The logic is hidden in the combinations of the patterns, when it's a simple condition.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # fn foo() {}
+    /// # fn bar() {}
+    /// # let (a, b) = (true, false);
+    /// let both = match (a, b) {
+    ///     (true, true) => true,
+    ///     _ => false,
+    /// };
+    /// match (a, b) {
+    ///     (true, false) => foo(),
+    ///     _ => bar(),
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # fn foo() {}
+    /// # fn bar() {}
+    /// # let (a, b) = (true, false);
+    /// let both = a && b;
+    /// if a && !b {
+    ///     foo();
+    /// } else {
+    ///     bar();
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub MATCH_BOOL_TUPLE,
+    pedantic,
+    "a `match` on a tuple of booleans instead of a boolean expression or an `if`/`else` chain"
+}
+
 pub struct Matches {
     msrv: Msrv,
+    max_match_bool_tuple_arity: u64,
+    match_same_arms_ignore_path_prefixes: bool,
     infallible_destructuring_match_linted: bool,
 }
 
 impl Matches {
     #[must_use]
-    pub fn new(msrv: Msrv) -> Self {
+    pub fn new(msrv: Msrv, max_match_bool_tuple_arity: u64, match_same_arms_ignore_path_prefixes: bool) -> Self {
         Self {
             msrv,
+            max_match_bool_tuple_arity,
+            match_same_arms_ignore_path_prefixes,
             infallible_destructuring_match_linted: false,
         }
     }
@@ -1016,6 +1065,7 @@ impl_lint_pass!(Matches => [
     MANUAL_MAP,
     MANUAL_FILTER,
     REDUNDANT_GUARDS,
+    MATCH_BOOL_TUPLE,
 ]);
 
 impl<'tcx> LateLintPass<'tcx> for Matches {
@@ -1054,12 +1104,13 @@ impl<'tcx> LateLintPass<'tcx> for Matches {
             if !from_expansion && !contains_cfg_arm(cx, expr, ex, arms) {
                 if source == MatchSource::Normal {
                     if !(self.msrv.meets(msrvs::MATCHES_MACRO) && match_like_matches::check_match(cx, expr, ex, arms)) {
-                        match_same_arms::check(cx, arms);
+                        match_same_arms::check(cx, arms, self.match_same_arms_ignore_path_prefixes);
                     }
 
                     redundant_pattern_match::check_match(cx, expr, ex, arms);
                     single_match::check(cx, ex, arms, expr);
                     match_bool::check(cx, ex, arms, expr);
+                    match_bool_tuple::check(cx, ex, arms, expr, self.max_match_bool_tuple_arity);
                     overlapping_arms::check(cx, ex, arms);
                     match_wild_enum::check(cx, ex, arms);
                     match_as_ref::check(cx, ex, arms, expr);
@@ -0,0 +1,250 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::higher::IfLetOrMatch;
+use clippy_utils::macros::{is_panic, matching_root_macro_call, root_macro_call};
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::{eq_expr_value, is_refutable, over, path_to_local, peel_blocks, peel_blocks_with_stmt};
+use rustc_errors::Applicability;
+use rustc_hir::{BindingMode, Block, DotDotPos, Expr, ExprKind, LetStmt, MatchSource, Pat, PatKind, StmtKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty;
+use rustc_session::declare_lint_pass;
+use rustc_span::{sym, BytePos, Span};
+use std::iter;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `if matches!(x, pattern)` whose body starts by destructuring `x` again with the
+    /// same pattern, and panics if it doesn't match, e.g. with
+    /// `let Some(y) = x else { unreachable!() };`.
+    ///
+    /// ### Why is this bad?
+    /// The pattern is checked twice, and the two checks have to be kept in sync. An `if let`
+    /// checks it once and binds the values at the same time.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let x = Some(1);
+    /// if matches!(x, Some(_)) {
+    ///     let Some(y) = x else { unreachable!() };
+    ///     println!("{y}");
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let x = Some(1);
+    /// if let Some(y) = x {
+    ///     println!("{y}");
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub MATCHES_THEN_DESTRUCTURE,
+    complexity,
+    "checking a pattern with `matches!` before destructuring the value with the same pattern"
+}
+
+declare_lint_pass!(MatchesThenDestructure => [MATCHES_THEN_DESTRUCTURE]);
+
+impl<'tcx> LateLintPass<'tcx> for MatchesThenDestructure {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::If(cond, then, _) = expr.kind
+            && !expr.span.from_expansion()
+            && let Some(macro_call) = matching_root_macro_call(cx, cond.span, sym::matches_macro)
+            && let ExprKind::Match(scrutinee, [arm, _], _) = cond.peel_drop_temps().kind
+            && arm.guard.is_none()
+            && let ExprKind::Block(block, _) = then.kind
+            && let Some(stmt) = block.stmts.first()
+            && let StmtKind::Let(local) = stmt.kind
+            && !stmt.span.from_expansion()
+            && let Some(destructuring) = Destructuring::new(cx, local)
+            && eq_expr_value(cx, scrutinee, destructuring.scrutinee)
+            && refines(cx, arm.pat, destructuring.pat)
+        {
+            span_lint_and_then(
+                cx,
+                MATCHES_THEN_DESTRUCTURE,
+                macro_call.span,
+                "this `matches!` is followed by a destructuring of the same value",
+                |diag| {
+                    diag.span_note(stmt.span, "the value is destructured with the same pattern here");
+                    let mut app = Applicability::MachineApplicable;
+                    let pat = snippet_with_applicability(cx, destructuring.pat.span, "..", &mut app);
+                    let scrutinee = snippet_with_applicability(cx, scrutinee.span, "..", &mut app);
+                    let mut suggestions = vec![(macro_call.span, format!("let {pat} = {scrutinee}"))];
+                    match destructuring.value {
+                        Some(value) if !is_same_binding(cx, local.pat, value) => {
+                            // The bindings of the pattern are now in scope in the whole block.
+                            app = Applicability::MaybeIncorrect;
+                            let value = snippet_with_applicability(cx, value.span, "..", &mut app);
+                            suggestions.push((local.init.unwrap().span, value.into_owned()));
+                        },
+                        _ => {
+                            let next = next_span(block, 1).source_callsite();
+                            suggestions.push((stmt.span.with_hi(next.lo()), String::new()));
+                        },
+                    }
+                    diag.multipart_suggestion("use `if let`", suggestions, app);
+                },
+            );
+        }
+    }
+}
+
+/// The destructuring of a value with a pattern which panics if it doesn't match
+struct Destructuring<'tcx> {
+    scrutinee: &'tcx Expr<'tcx>,
+    pat: &'tcx Pat<'tcx>,
+    /// The value assigned to the pattern of the `let` statement when the destructuring is an
+    /// `if let` or a `match`, `None` for a `let ... else`
+    value: Option<&'tcx Expr<'tcx>>,
+}
+
+impl<'tcx> Destructuring<'tcx> {
+    fn new(cx: &LateContext<'tcx>, local: &'tcx LetStmt<'tcx>) -> Option<Self> {
+        let init = local.init?;
+        if let Some(els) = local.els {
+            return is_panic_block(cx, els).then_some(Self {
+                scrutinee: init,
+                pat: local.pat,
+                value: None,
+            });
+        }
+        let (scrutinee, pat, value, other) = match IfLetOrMatch::parse(cx, init)? {
+            IfLetOrMatch::IfLet(scrutinee, pat, then, Some(els), _) => (scrutinee, pat, then, els),
+            IfLetOrMatch::Match(scrutinee, [arm, other], MatchSource::Normal)
+                if arm.guard.is_none() && other.guard.is_none() && matches!(other.pat.kind, PatKind::Wild) =>
+            {
+                (scrutinee, arm.pat, arm.body, other.body)
+            },
+            _ => return None,
+        };
+        is_panic_expr(cx, other).then_some(Self {
+            scrutinee,
+            pat,
+            value: Some(peel_blocks(value)),
+        })
+    }
+}
+
+/// Checks if `expr` is a `panic!` or an `unreachable!`, possibly in a block
+fn is_panic_expr(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    root_macro_call(peel_blocks_with_stmt(expr).span)
+        .is_some_and(|mac| is_panic(cx, mac.def_id) || cx.tcx.item_name(mac.def_id) == sym::unreachable)
+}
+
+fn is_panic_block(cx: &LateContext<'_>, block: &Block<'_>) -> bool {
+    match (block.stmts, block.expr) {
+        ([], Some(expr)) => is_panic_expr(cx, expr),
+        ([stmt], None) if let StmtKind::Expr(expr) | StmtKind::Semi(expr) = stmt.kind => is_panic_expr(cx, expr),
+        _ => false,
+    }
+}
+
+/// Checks if `pat` is a binding to the local `value` of the same name, e.g. `let y = y`
+fn is_same_binding(cx: &LateContext<'_>, pat: &Pat<'_>, value: &Expr<'_>) -> bool {
+    if let PatKind::Binding(BindingMode::NONE, _, ident, None) = pat.kind
+        && let Some(local) = path_to_local(value)
+    {
+        cx.tcx.hir().name(local) == ident.name
+    } else {
+        false
+    }
+}
+
+/// The span of the statement or expression at `index` in `block`, or of its closing brace
+fn next_span(block: &Block<'_>, index: usize) -> Span {
+    match block.stmts.get(index) {
+        Some(stmt) => stmt.span,
+        None => block
+            .expr
+            .map_or(block.span.with_lo(block.span.hi() - BytePos(1)), |expr| expr.span),
+    }
+}
+
+/// Checks if `destructuring` matches the same values as `check`, possibly with bindings where
+/// `check` has wildcards.
+fn refines(cx: &LateContext<'_>, check: &Pat<'_>, destructuring: &Pat<'_>) -> bool {
+    if check.span.from_expansion() || destructuring.span.from_expansion() {
+        return false;
+    }
+    match (check.kind, destructuring.kind) {
+        (PatKind::Wild | PatKind::Binding(.., None), _) => !is_refutable(cx, destructuring),
+        (_, PatKind::Binding(.., Some(sub))) => refines(cx, check, sub),
+        (PatKind::Path(ref check_qpath), PatKind::Path(ref qpath)) => {
+            cx.qpath_res(check_qpath, check.hir_id) == cx.qpath_res(qpath, destructuring.hir_id)
+        },
+        (
+            PatKind::TupleStruct(ref check_qpath, check_pats, check_rest),
+            PatKind::TupleStruct(ref qpath, pats, rest),
+        ) => {
+            let res = cx.qpath_res(qpath, destructuring.hir_id);
+            if cx.qpath_res(check_qpath, check.hir_id) == res
+                && let ty::Adt(adt, _) = cx.typeck_results().pat_ty(destructuring).kind()
+            {
+                let len = adt.variant_of_res(res).fields.len();
+                refines_all(cx, &with_rest(check_pats, check_rest, len), &with_rest(pats, rest, len))
+            } else {
+                false
+            }
+        },
+        (PatKind::Struct(ref check_qpath, check_fields, _), PatKind::Struct(ref qpath, fields, _)) => {
+            cx.qpath_res(check_qpath, check.hir_id) == cx.qpath_res(qpath, destructuring.hir_id)
+                && check_fields.iter().all(|check_field| {
+                    fields
+                        .iter()
+                        .find(|field| field.ident.name == check_field.ident.name)
+                        .map_or(!is_refutable(cx, check_field.pat), |field| {
+                            refines(cx, check_field.pat, field.pat)
+                        })
+                })
+                && fields.iter().all(|field| {
+                    check_fields
+                        .iter()
+                        .any(|check_field| check_field.ident.name == field.ident.name)
+                        || !is_refutable(cx, field.pat)
+                })
+        },
+        (PatKind::Tuple(check_pats, check_rest), PatKind::Tuple(pats, rest)) => {
+            let len = cx.typeck_results().pat_ty(destructuring).tuple_fields().len();
+            refines_all(cx, &with_rest(check_pats, check_rest, len), &with_rest(pats, rest, len))
+        },
+        (PatKind::Or(check_pats), PatKind::Or(pats)) => over(check_pats, pats, |check, pat| refines(cx, check, pat)),
+        (PatKind::Ref(check, check_mutbl), PatKind::Ref(pat, mutbl)) => check_mutbl == mutbl && refines(cx, check, pat),
+        (PatKind::Box(check), PatKind::Box(pat)) | (PatKind::Deref(check), PatKind::Deref(pat)) => {
+            refines(cx, check, pat)
+        },
+        (PatKind::Lit(check), PatKind::Lit(lit)) => eq_expr_value(cx, check, lit),
+        (PatKind::Slice(check_before, check_mid, check_after), PatKind::Slice(before, mid, after)) => {
+            over(check_before, before, |check, pat| refines(cx, check, pat))
+                && over(check_after, after, |check, pat| refines(cx, check, pat))
+                && match (check_mid, mid) {
+                    (Some(check), Some(pat)) => refines(cx, check, pat),
+                    (None, None) => true,
+                    _ => false,
+                }
+        },
+        _ => false,
+    }
+}
+
+/// The subpatterns of a tuple or tuple struct pattern of `len` fields, with `None` for the fields
+/// elided by `..`
+fn with_rest<'a, 'tcx>(pats: &'a [Pat<'tcx>], rest: DotDotPos, len: usize) -> Vec<Option<&'a Pat<'tcx>>> {
+    match rest.as_opt_usize() {
+        Some(pos) => pats[..pos]
+            .iter()
+            .map(Some)
+            .chain(iter::repeat(None).take(len.saturating_sub(pats.len())))
+            .chain(pats[pos..].iter().map(Some))
+            .collect(),
+        None => pats.iter().map(Some).collect(),
+    }
+}
+
+fn refines_all(cx: &LateContext<'_>, checks: &[Option<&Pat<'_>>], pats: &[Option<&Pat<'_>>]) -> bool {
+    over(checks, pats, |check, pat| match (check, pat) {
+        (Some(check), Some(pat)) => refines(cx, check, pat),
+        (None, Some(pat)) => !is_refutable(cx, pat),
+        (Some(check), None) => !is_refutable(cx, check),
+        (None, None) => true,
+    })
+}
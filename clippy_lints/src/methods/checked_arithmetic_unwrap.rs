@@ -0,0 +1,76 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet_with_context;
+use clippy_utils::sugg::{make_binop, Sugg};
+use clippy_utils::ty::is_type_diagnostic_item;
+use rustc_ast::ast::BinOpKind;
+use rustc_errors::Applicability;
+use rustc_hir::Expr;
+use rustc_lint::LateContext;
+use rustc_middle::ty::Ty;
+use rustc_span::sym;
+
+use super::CHECKED_ARITHMETIC_UNWRAP;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    recv: &'tcx Expr<'_>,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+    arith: &str,
+) {
+    let ty = cx.typeck_results().expr_ty(lhs).peel_refs();
+    if !ty.is_integral() || expr.span.from_expansion() {
+        return;
+    }
+    let op = match arith {
+        "add" => BinOpKind::Add,
+        "sub" => BinOpKind::Sub,
+        "mul" => BinOpKind::Mul,
+        "div" => BinOpKind::Div,
+        _ => BinOpKind::Rem,
+    };
+
+    span_lint_and_then(
+        cx,
+        CHECKED_ARITHMETIC_UNWRAP,
+        expr.span,
+        format!(
+            "`checked_{arith}` is immediately unwrapped, which panics like the `{}` operator",
+            op.as_str()
+        ),
+        |diag| {
+            let mut app = Applicability::MaybeIncorrect;
+            let ctxt = expr.span.ctxt();
+            let returned = returned_ty(cx);
+            if returned.is_some_and(|ty| is_type_diagnostic_item(cx, ty, sym::Option)) {
+                let recv = snippet_with_context(cx, recv.span, ctxt, "..", &mut app).0;
+                diag.span_suggestion(expr.span, "propagate the overflow", format!("{recv}?"), app);
+            } else {
+                let lhs = Sugg::hir_with_context(cx, lhs, ctxt, "..", &mut app);
+                let rhs = Sugg::hir_with_context(cx, rhs, ctxt, "..", &mut app);
+                diag.span_suggestion(
+                    expr.span,
+                    "if the overflow can't happen, use the operator",
+                    make_binop(op, &lhs, &rhs).to_string(),
+                    app,
+                );
+                if matches!(op, BinOpKind::Add | BinOpKind::Sub | BinOpKind::Mul) {
+                    diag.note(format!(
+                        "unlike `checked_{arith}`, the operator wraps around in release builds"
+                    ));
+                }
+                if returned.is_some_and(|ty| is_type_diagnostic_item(cx, ty, sym::Result)) {
+                    diag.help("if it can, propagate the overflow as an error with `.ok_or(..)?`");
+                }
+            }
+            diag.help("otherwise, use `expect` with a message explaining why the overflow can't happen");
+        },
+    );
+}
+
+/// The type returned by the enclosing function or closure.
+fn returned_ty<'tcx>(cx: &LateContext<'tcx>) -> Option<Ty<'tcx>> {
+    let body = cx.tcx.hir().body(cx.enclosing_body?);
+    Some(cx.typeck_results().expr_ty(body.value))
+}
@@ -1,9 +1,9 @@
 use clippy_utils::consts::constant_is_empty;
 use clippy_utils::diagnostics::span_lint;
+use clippy_utils::macros::{lintability, MacroPolicy};
 use clippy_utils::{find_binding_init, path_to_local};
 use rustc_hir::{Expr, HirId};
-use rustc_lint::{LateContext, LintContext};
-use rustc_middle::lint::in_external_macro;
+use rustc_lint::LateContext;
 use rustc_span::sym;
 
 use super::CONST_IS_EMPTY;
@@ -11,7 +11,7 @@ use super::CONST_IS_EMPTY;
 /// Expression whose initialization depend on a constant conditioned by a `#[cfg(…)]` directive will
 /// not trigger the lint.
 pub(super) fn check(cx: &LateContext<'_>, expr: &'_ Expr<'_>, receiver: &Expr<'_>) {
-    if in_external_macro(cx.sess(), expr.span) || !receiver.span.eq_ctxt(expr.span) {
+    if !lintability(cx, expr.span, MacroPolicy::LOCAL_MACROS).should_lint() || !receiver.span.eq_ctxt(expr.span) {
         return;
     }
     let init_expr = expr_or_init(cx, receiver);
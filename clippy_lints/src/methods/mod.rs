@@ -9,6 +9,7 @@ mod chars_last_cmp;
 mod chars_last_cmp_with_unwrap;
 mod chars_next_cmp;
 mod chars_next_cmp_with_unwrap;
+mod checked_arithmetic_unwrap;
 mod clear_with_drain;
 mod clone_on_copy;
 mod clone_on_ref_ptr;
@@ -105,6 +106,7 @@ mod suspicious_command_arg_space;
 mod suspicious_map;
 mod suspicious_splitn;
 mod suspicious_to_owned;
+mod trim_matches_instead_of_strip;
 mod type_id_on_box;
 mod uninit_assumed_init;
 mod unit_hash;
@@ -133,12 +135,14 @@ use bind_instead_of_map::BindInsteadOfMap;
 use clippy_config::msrvs::{self, Msrv};
 use clippy_utils::consts::{constant, Constant};
 use clippy_utils::diagnostics::{span_lint, span_lint_and_help};
+use clippy_utils::eager_or_lazy::expensive_call_ids;
 use clippy_utils::macros::FormatArgsStorage;
 use clippy_utils::ty::{contains_ty_adt_constructor_opaque, implements_trait, is_copy, is_type_diagnostic_item};
 use clippy_utils::{contains_return, is_bool, is_trait_method, iter_input_pats, peel_blocks, return_ty};
 pub use path_ends_with_ext::DEFAULT_ALLOWED_DOTFILES;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_hir as hir;
+use rustc_hir::def_id::DefIdSet;
 use rustc_hir::{Expr, ExprKind, Node, Stmt, StmtKind, TraitItem, TraitItemKind};
 use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_middle::lint::in_external_macro;
@@ -4089,12 +4093,73 @@ declare_clippy_lint! {
     "is_empty() called on strings known at compile time"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls to `trim_start_matches` or `trim_end_matches` with a string literal of
+    /// several characters, which are guarded by a `starts_with` or `ends_with` check of the same
+    /// pattern, or whose result is parsed, unless the pattern repeats a single character like
+    /// `"00"`.
+    ///
+    /// ### Why is this bad?
+    /// These methods remove all the repetitions of the pattern, e.g.
+    /// `"0x0x1".trim_start_matches("0x")` is `"1"`. When the pattern is expected at most once,
+    /// like a prefix before a number, `strip_prefix` and `strip_suffix` only remove it once, so
+    /// that a malformed input isn't accepted.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let s = "0x1f";
+    /// let n = u32::from_str_radix(s.trim_start_matches("0x"), 16);
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let s = "0x1f";
+    /// let n = u32::from_str_radix(s.strip_prefix("0x").unwrap_or(s), 16);
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub TRIM_MATCHES_INSTEAD_OF_STRIP,
+    suspicious,
+    "using `trim_start_matches` or `trim_end_matches` to remove a prefix or a suffix expected once"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `checked_add`, `checked_sub`, `checked_mul`, `checked_div` and `checked_rem`
+    /// calls on integers which are immediately unwrapped.
+    ///
+    /// ### Why is this bad?
+    /// `a.checked_add(b).unwrap()` panics on overflow, just like `a + b` in debug builds, but reads
+    /// as if the overflow was handled. Either the overflow can't happen, and the operator says so,
+    /// or it can, and it should be propagated or explained with `expect`.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn total(a: u32, b: u32) -> Option<u32> {
+    ///     let sum = a.checked_add(b).unwrap();
+    ///     Some(sum)
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// fn total(a: u32, b: u32) -> Option<u32> {
+    ///     let sum = a.checked_add(b)?;
+    ///     Some(sum)
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub CHECKED_ARITHMETIC_UNWRAP,
+    pedantic,
+    "checked integer arithmetic which is immediately unwrapped"
+}
+
 pub struct Methods {
     avoid_breaking_exported_api: bool,
     msrv: Msrv,
     allow_expect_in_tests: bool,
     allow_unwrap_in_tests: bool,
     allowed_dotfiles: FxHashSet<String>,
+    expensive_calls: Vec<String>,
+    expensive_call_ids: DefIdSet,
     format_args: FormatArgsStorage,
 }
 
@@ -4106,6 +4171,7 @@ impl Methods {
         allow_expect_in_tests: bool,
         allow_unwrap_in_tests: bool,
         mut allowed_dotfiles: FxHashSet<String>,
+        expensive_calls: Vec<String>,
         format_args: FormatArgsStorage,
     ) -> Self {
         allowed_dotfiles.extend(DEFAULT_ALLOWED_DOTFILES.iter().map(ToString::to_string));
@@ -4116,6 +4182,8 @@ impl Methods {
             allow_expect_in_tests,
             allow_unwrap_in_tests,
             allowed_dotfiles,
+            expensive_calls,
+            expensive_call_ids: DefIdSet::default(),
             format_args,
         }
     }
@@ -4254,6 +4322,8 @@ impl_lint_pass!(Methods => [
     UNNECESSARY_RESULT_MAP_OR_ELSE,
     MANUAL_C_STR_LITERALS,
     UNNECESSARY_GET_THEN_CHECK,
+    TRIM_MATCHES_INSTEAD_OF_STRIP,
+    CHECKED_ARITHMETIC_UNWRAP,
 ]);
 
 /// Extracts a method call name, args, and `Span` of the method name.
@@ -4270,6 +4340,10 @@ pub fn method_call<'tcx>(
 }
 
 impl<'tcx> LateLintPass<'tcx> for Methods {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        self.expensive_call_ids = expensive_call_ids(cx, &self.expensive_calls);
+    }
+
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
         if expr.span.from_expansion() {
             return;
@@ -4285,7 +4359,15 @@ impl<'tcx> LateLintPass<'tcx> for Methods {
             },
             ExprKind::MethodCall(method_call, receiver, args, _) => {
                 let method_span = method_call.ident.span;
-                or_fun_call::check(cx, expr, method_span, method_call.ident.as_str(), receiver, args);
+                or_fun_call::check(
+                    cx,
+                    expr,
+                    method_span,
+                    method_call.ident.as_str(),
+                    receiver,
+                    args,
+                    &self.expensive_call_ids,
+                );
                 expect_fun_call::check(
                     cx,
                     &self.format_args,
@@ -4476,7 +4558,7 @@ impl Methods {
                     let biom_option_linted = bind_instead_of_map::OptionAndThenSome::check(cx, expr, recv, arg);
                     let biom_result_linted = bind_instead_of_map::ResultAndThenOk::check(cx, expr, recv, arg);
                     if !biom_option_linted && !biom_result_linted {
-                        unnecessary_lazy_eval::check(cx, expr, recv, arg, "and");
+                        unnecessary_lazy_eval::check(cx, expr, recv, arg, "and", &self.expensive_call_ids);
                     }
                 },
                 ("any", [arg]) => {
@@ -4682,7 +4764,9 @@ impl Methods {
                     get_first::check(cx, expr, recv, arg);
                     get_last_with_len::check(cx, expr, recv, arg);
                 },
-                ("get_or_insert_with", [arg]) => unnecessary_lazy_eval::check(cx, expr, recv, arg, "get_or_insert"),
+                ("get_or_insert_with", [arg]) => {
+                    unnecessary_lazy_eval::check(cx, expr, recv, arg, "get_or_insert", &self.expensive_call_ids);
+                },
                 ("hash", [arg]) => {
                     unit_hash::check(cx, expr, recv, arg);
                 },
@@ -4804,13 +4888,15 @@ impl Methods {
                     },
                     _ => iter_nth_zero::check(cx, expr, recv, n_arg),
                 },
-                ("ok_or_else", [arg]) => unnecessary_lazy_eval::check(cx, expr, recv, arg, "ok_or"),
+                ("ok_or_else", [arg]) => {
+                    unnecessary_lazy_eval::check(cx, expr, recv, arg, "ok_or", &self.expensive_call_ids);
+                },
                 ("open", [_]) => {
                     open_options::check(cx, expr, recv);
                 },
                 ("or_else", [arg]) => {
                     if !bind_instead_of_map::ResultOrElseErrInfo::check(cx, expr, recv, arg) {
-                        unnecessary_lazy_eval::check(cx, expr, recv, arg, "or");
+                        unnecessary_lazy_eval::check(cx, expr, recv, arg, "or", &self.expensive_call_ids);
                     }
                 },
                 ("push", [arg]) => {
@@ -4907,7 +4993,7 @@ impl Methods {
                     if !self.msrv.meets(msrvs::BOOL_THEN_SOME) {
                         return;
                     }
-                    unnecessary_lazy_eval::check(cx, expr, recv, arg, "then_some");
+                    unnecessary_lazy_eval::check(cx, expr, recv, arg, "then_some", &self.expensive_call_ids);
                 },
                 ("try_into", []) if is_trait_method(cx, expr, sym::TryInto) => {
                     unnecessary_fallible_conversions::check_method(cx, expr);
@@ -4917,6 +5003,9 @@ impl Methods {
                         implicit_clone::check(cx, name, expr, recv);
                     }
                 },
+                ("trim_start_matches" | "trim_end_matches", [arg]) => {
+                    trim_matches_instead_of_strip::check(cx, expr, name, recv, arg, &self.msrv);
+                },
                 ("to_os_string" | "to_path_buf" | "to_vec", []) => {
                     implicit_clone::check(cx, name, expr, recv);
                 },
@@ -4934,6 +5023,15 @@ impl Methods {
                         Some(("or", recv, [or_arg], or_span, _)) => {
                             or_then_unwrap::check(cx, expr, recv, or_arg, or_span);
                         },
+                        Some((
+                            arith @ ("checked_add" | "checked_sub" | "checked_mul" | "checked_div" | "checked_rem"),
+                            lhs,
+                            [rhs],
+                            _,
+                            _,
+                        )) => {
+                            checked_arithmetic_unwrap::check(cx, expr, recv, lhs, rhs, &arith["checked_".len()..]);
+                        },
                         _ => {},
                     }
                     unnecessary_literal_unwrap::check(cx, expr, recv, name, args);
@@ -4986,7 +5084,7 @@ impl Methods {
                         Some(("map", recv, [map_arg], _, _))
                             if map_unwrap_or::check(cx, expr, recv, map_arg, u_arg, &self.msrv) => {},
                         _ => {
-                            unnecessary_lazy_eval::check(cx, expr, recv, u_arg, "unwrap_or");
+                            unnecessary_lazy_eval::check(cx, expr, recv, u_arg, "unwrap_or", &self.expensive_call_ids);
                         },
                     }
                     unnecessary_literal_unwrap::check(cx, expr, recv, name, args);
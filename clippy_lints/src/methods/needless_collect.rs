@@ -2,7 +2,10 @@ use super::NEEDLESS_COLLECT;
 use clippy_utils::diagnostics::{span_lint_and_sugg, span_lint_hir_and_then};
 use clippy_utils::source::{snippet, snippet_with_applicability};
 use clippy_utils::sugg::Sugg;
-use clippy_utils::ty::{is_type_diagnostic_item, make_normalized_projection, make_projection};
+use clippy_utils::ty::{
+    contains_non_static_region, is_type_diagnostic_item, make_normalized_projection, make_projection,
+};
+use clippy_utils::usage::borrow_escape;
 use clippy_utils::{
     can_move_expr_to_closure, fn_def_id, get_enclosing_block, higher, is_trait_method, path_to_local, path_to_local_id,
     CaptureKind,
@@ -95,6 +98,7 @@ pub(super) fn check<'tcx>(
                 && let Some(block) = get_enclosing_block(cx, l.hir_id)
                 && let Some(iter_calls) = detect_iter_and_into_iters(block, id, cx, get_captured_ids(cx, iter_ty))
                 && let [iter_call] = &*iter_calls
+                && !escapes_borrowing(cx, iter_call, iter_ty)
             {
                 let mut used_count_visitor = UsedCountVisitor { cx, id, count: 0 };
                 walk_block(&mut used_count_visitor, block);
@@ -247,6 +251,7 @@ fn is_contains_sig(cx: &LateContext<'_>, call_id: HirId, iter_expr: &Expr<'_>) -
 struct IterFunction {
     func: IterFunctionKind,
     span: Span,
+    hir_id: HirId,
 }
 impl IterFunction {
     fn get_iter_method(&self, cx: &LateContext<'_>) -> String {
@@ -340,18 +345,22 @@ impl<'tcx> Visitor<'tcx> for IterFunctionVisitor<'_, 'tcx> {
                         "into_iter" => self.uses.push(Some(IterFunction {
                             func: IterFunctionKind::IntoIter,
                             span: expr.span,
+                            hir_id: expr.hir_id,
                         })),
                         "len" => self.uses.push(Some(IterFunction {
                             func: IterFunctionKind::Len,
                             span: expr.span,
+                            hir_id: expr.hir_id,
                         })),
                         "is_empty" => self.uses.push(Some(IterFunction {
                             func: IterFunctionKind::IsEmpty,
                             span: expr.span,
+                            hir_id: expr.hir_id,
                         })),
                         "contains" => self.uses.push(Some(IterFunction {
                             func: IterFunctionKind::Contains(args[0].span),
                             span: expr.span,
+                            hir_id: expr.hir_id,
                         })),
                         _ => {
                             self.seen_other = true;
@@ -482,6 +491,15 @@ fn detect_iter_and_into_iters<'tcx: 'a, 'a>(
     }
 }
 
+/// Checks if replacing the `into_iter` call with the original iterator would make a borrowing
+/// iterator escape the function, e.g. by returning it, which wouldn't compile if it borrows a
+/// local.
+fn escapes_borrowing(cx: &LateContext<'_>, iter_call: &IterFunction, iter_ty: Ty<'_>) -> bool {
+    matches!(iter_call.func, IterFunctionKind::IntoIter)
+        && contains_non_static_region(iter_ty)
+        && borrow_escape(cx, cx.tcx.hir().expect_expr(iter_call.hir_id)).is_some()
+}
+
 fn get_captured_ids(cx: &LateContext<'_>, ty: Ty<'_>) -> HirIdSet {
     fn get_captured_ids_recursive(cx: &LateContext<'_>, ty: Ty<'_>, set: &mut HirIdSet) {
         match ty.kind() {
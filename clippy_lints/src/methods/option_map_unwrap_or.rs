@@ -1,6 +1,7 @@
 use clippy_config::msrvs::{self, Msrv};
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::sugg::MultipartSugg;
 use clippy_utils::ty::{is_copy, is_type_diagnostic_item};
 use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::Applicability;
@@ -100,27 +101,25 @@ pub(super) fn check<'tcx>(
         let msg = format!("called `map(<f>).unwrap_or({arg})` on an `Option` value");
 
         span_lint_and_then(cx, MAP_UNWRAP_OR, expr.span, msg, |diag| {
-            let map_arg_span = map_arg.span;
-
-            let mut suggestion = vec![
-                (
+            let mut suggestion = MultipartSugg::new(cx, expr.span.ctxt());
+            suggestion
+                .replace(
                     map_span,
-                    String::from(if unwrap_snippet_none {
+                    if unwrap_snippet_none {
                         "and_then"
                     } else if suggest_is_some_and {
                         "is_some_and"
                     } else {
                         "map_or"
-                    }),
-                ),
-                (expr.span.with_lo(unwrap_recv.span.hi()), String::new()),
-            ];
+                    },
+                )
+                .remove(expr.span.with_lo(unwrap_recv.span.hi()));
 
             if !unwrap_snippet_none && !suggest_is_some_and {
-                suggestion.push((map_arg_span.with_hi(map_arg_span.lo()), format!("{unwrap_snippet}, ")));
+                suggestion.insert_before(map_arg.span, format!("{unwrap_snippet}, "));
             }
 
-            diag.multipart_suggestion(format!("use `{suggest}` instead"), suggestion, applicability);
+            suggestion.emit(diag, format!("use `{suggest}` instead"), applicability);
         });
     }
 }
@@ -1,9 +1,10 @@
 use clippy_utils::diagnostics::span_lint_and_sugg;
-use clippy_utils::eager_or_lazy::switch_to_lazy_eval;
+use clippy_utils::eager_or_lazy::switch_to_lazy_eval_with_costs;
 use clippy_utils::source::snippet_with_context;
 use clippy_utils::ty::{expr_type_is_certain, implements_trait, is_type_diagnostic_item};
 use clippy_utils::{contains_return, is_default_equivalent, is_default_equivalent_call, last_path_segment};
 use rustc_errors::Applicability;
+use rustc_hir::def_id::DefIdSet;
 use rustc_lint::LateContext;
 use rustc_middle::ty;
 use rustc_span::symbol::{self, sym, Symbol};
@@ -21,6 +22,7 @@ pub(super) fn check<'tcx>(
     name: &str,
     receiver: &'tcx hir::Expr<'_>,
     args: &'tcx [hir::Expr<'_>],
+    expensive_calls: &DefIdSet,
 ) {
     /// Checks for `unwrap_or(T::new())`, `unwrap_or(T::default())`,
     /// `or_insert(T::new())` or `or_insert(T::default())`.
@@ -122,22 +124,24 @@ pub(super) fn check<'tcx>(
         span: Span,
         // None if lambda is required
         fun_span: Option<Span>,
+        expensive_calls: &DefIdSet,
     ) {
         // (path, fn_has_argument, methods, suffix)
-        const KNOW_TYPES: [(Symbol, bool, &[&str], &str); 4] = [
+        const KNOW_TYPES: [(Symbol, bool, &[&str], &str); 5] = [
             (sym::BTreeEntry, false, &["or_insert"], "with"),
             (sym::HashMapEntry, false, &["or_insert"], "with"),
+            (sym::Option, false, &["get_or_insert"], "with"),
             (sym::Option, false, &["map_or", "ok_or", "or", "unwrap_or"], "else"),
             (sym::Result, true, &["or", "unwrap_or"], "else"),
         ];
 
         if KNOW_TYPES.iter().any(|k| k.2.contains(&name))
-            && switch_to_lazy_eval(cx, arg)
+            && switch_to_lazy_eval_with_costs(cx, arg, expensive_calls)
             && !contains_return(arg)
             && let self_ty = cx.typeck_results().expr_ty(self_expr)
-            && let Some(&(_, fn_has_arguments, poss, suffix)) =
-                KNOW_TYPES.iter().find(|&&i| is_type_diagnostic_item(cx, self_ty, i.0))
-            && poss.contains(&name)
+            && let Some(&(_, fn_has_arguments, _, suffix)) = KNOW_TYPES
+                .iter()
+                .find(|&&i| i.2.contains(&name) && is_type_diagnostic_item(cx, self_ty, i.0))
         {
             let ctxt = span.ctxt();
             let mut app = Applicability::HasPlaceholders;
@@ -200,14 +204,34 @@ pub(super) fn check<'tcx>(
                     || !check_unwrap_or_default(cx, name, receiver, fun, Some(inner_arg), expr.span, method_span)
                 {
                     let fun_span = if or_has_args { None } else { Some(fun.span) };
-                    check_general_case(cx, name, method_span, receiver, arg, None, expr.span, fun_span);
+                    check_general_case(
+                        cx,
+                        name,
+                        method_span,
+                        receiver,
+                        arg,
+                        None,
+                        expr.span,
+                        fun_span,
+                        expensive_calls,
+                    );
                 }
             },
             hir::ExprKind::Path(..) | hir::ExprKind::Closure(..) => {
                 check_unwrap_or_default(cx, name, receiver, inner_arg, None, expr.span, method_span);
             },
             hir::ExprKind::Index(..) | hir::ExprKind::MethodCall(..) => {
-                check_general_case(cx, name, method_span, receiver, arg, None, expr.span, None);
+                check_general_case(
+                    cx,
+                    name,
+                    method_span,
+                    receiver,
+                    arg,
+                    None,
+                    expr.span,
+                    None,
+                    expensive_calls,
+                );
             },
             _ => (),
         }
@@ -218,7 +242,17 @@ pub(super) fn check<'tcx>(
         let inner_arg = extract_inner_arg(arg);
         if let hir::ExprKind::Call(fun, or_args) = inner_arg.kind {
             let fun_span = if or_args.is_empty() { Some(fun.span) } else { None };
-            check_general_case(cx, name, method_span, receiver, arg, Some(lambda), expr.span, fun_span);
+            check_general_case(
+                cx,
+                name,
+                method_span,
+                receiver,
+                arg,
+                Some(lambda),
+                expr.span,
+                fun_span,
+                expensive_calls,
+            );
         }
     }
 }
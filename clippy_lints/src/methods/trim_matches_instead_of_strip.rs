@@ -0,0 +1,168 @@
+use std::ops::ControlFlow;
+
+use clippy_config::msrvs::{self, Msrv};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet_with_context;
+use clippy_utils::visitors::{for_each_expr, for_each_local_use_after_expr, Descend};
+use clippy_utils::{eq_expr_value, get_parent_expr, path_def_id};
+use rustc_ast::LitKind;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind, Node, PatKind, UnOp};
+use rustc_lint::LateContext;
+use rustc_span::{Span, Symbol};
+
+use super::TRIM_MATCHES_INSTEAD_OF_STRIP;
+
+/// Why the pattern is probably meant to be removed at most once
+enum Hint {
+    /// The trimmed string is parsed.
+    Parsed(Span),
+    /// The code is guarded by a `starts_with` or `ends_with` check.
+    Checked(Span),
+}
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    name: &str,
+    recv: &'tcx Expr<'tcx>,
+    arg: &'tcx Expr<'tcx>,
+    msrv: &Msrv,
+) {
+    let (strip, check) = match name {
+        "trim_start_matches" => ("strip_prefix", "starts_with"),
+        "trim_end_matches" => ("strip_suffix", "ends_with"),
+        _ => return,
+    };
+    if let ExprKind::Lit(lit) = arg.kind
+        && let LitKind::Str(pat, _) = lit.node
+        && pat.as_str().chars().nth(1).is_some()
+        && !expr.span.from_expansion()
+        && cx.typeck_results().expr_ty_adjusted(recv).peel_refs().is_str()
+        && let Some(hint) = parsed(cx, expr)
+            .filter(|_| !is_repeated_char(pat))
+            .map(Hint::Parsed)
+            .or_else(|| checked(cx, expr, recv, pat, check).map(Hint::Checked))
+        && msrv.meets(msrvs::STR_STRIP_PREFIX)
+    {
+        span_lint_and_then(
+            cx,
+            TRIM_MATCHES_INSTEAD_OF_STRIP,
+            expr.span,
+            format!("`{name}` removes all the repetitions of the pattern, not only the first one"),
+            |diag| {
+                match hint {
+                    Hint::Parsed(span) => diag.span_note(span, "the result is parsed here"),
+                    Hint::Checked(span) => {
+                        diag.span_note(span, format!("the pattern is checked once with `{check}` here"))
+                    },
+                };
+                let mut app = Applicability::MaybeIncorrect;
+                let ctxt = expr.span.ctxt();
+                let (recv_snip, _) = snippet_with_context(cx, recv.span, ctxt, "..", &mut app);
+                let (pat_snip, _) = snippet_with_context(cx, arg.span, ctxt, "..", &mut app);
+                let default = if cx.typeck_results().expr_ty(recv).is_ref() {
+                    recv_snip.to_string()
+                } else {
+                    format!("&{recv_snip}")
+                };
+                diag.span_suggestion(
+                    expr.span,
+                    format!("if it must be removed at most once, use `{strip}`"),
+                    format!("{recv_snip}.{strip}({pat_snip}).unwrap_or({default})"),
+                    app,
+                );
+            },
+        );
+    }
+}
+
+/// Patterns repeating a single character, e.g. leading zeros, are usually meant to be trimmed
+/// repeatedly before parsing.
+fn is_repeated_char(pat: Symbol) -> bool {
+    let mut chars = pat.as_str().chars();
+    chars.next().is_some_and(|first| chars.all(|c| c == first))
+}
+
+/// Returns the span of the parsing of `expr`, or of the local it's assigned to.
+fn parsed<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> Option<Span> {
+    if let Some(span) = parsing(cx, expr) {
+        return Some(span);
+    }
+    if let Node::LetStmt(local) = cx.tcx.parent_hir_node(expr.hir_id)
+        && let PatKind::Binding(_, local_id, _, None) = local.pat.kind
+    {
+        match for_each_local_use_after_expr(cx, local_id, expr.hir_id, |e| match parsing(cx, e) {
+            Some(span) => ControlFlow::Break(span),
+            None => ControlFlow::Continue(()),
+        }) {
+            ControlFlow::Break(span) => Some(span),
+            ControlFlow::Continue(()) => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Returns the span of the parsing of `expr` if it's the receiver of `parse`, or the first
+/// argument of a `from_str` or `from_str_radix` function.
+fn parsing<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> Option<Span> {
+    let parent = get_parent_expr(cx, expr)?;
+    match parent.kind {
+        ExprKind::MethodCall(path, recv, ..) if recv.hir_id == expr.hir_id && path.ident.name == sym!(parse) => {
+            Some(parent.span)
+        },
+        ExprKind::Call(func, [first, ..])
+            if first.hir_id == expr.hir_id
+                && let Some(def_id) = path_def_id(cx, func)
+                && matches!(cx.tcx.item_name(def_id).as_str(), "from_str" | "from_str_radix") =>
+        {
+            Some(parent.span)
+        },
+        _ => None,
+    }
+}
+
+/// Returns the span of a `starts_with` or `ends_with` check of the same pattern on the same string
+/// in the condition of an enclosing `if`.
+fn checked<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    recv: &'tcx Expr<'tcx>,
+    pat: Symbol,
+    check: &str,
+) -> Option<Span> {
+    let mut child = expr.hir_id;
+    for (parent_id, node) in cx.tcx.hir().parent_iter(expr.hir_id) {
+        if let Node::Expr(parent) = node
+            && let ExprKind::If(cond, then, _) = parent.kind
+            && then.hir_id == child
+            && let Some(span) = for_each_expr(cond, |e| {
+                if let ExprKind::MethodCall(path, check_recv, [check_arg], _) = e.kind
+                    && path.ident.as_str() == check
+                    && let ExprKind::Lit(lit) = check_arg.kind
+                    && let LitKind::Str(check_pat, _) = lit.node
+                    && check_pat == pat
+                    && eq_expr_value(cx, check_recv, recv)
+                {
+                    ControlFlow::Break(e.span)
+                } else {
+                    // The check must hold in the `then` branch.
+                    let holds = match e.kind {
+                        ExprKind::Binary(op, ..) => op.node == BinOpKind::And,
+                        ExprKind::Unary(UnOp::Not, _) => false,
+                        _ => true,
+                    };
+                    ControlFlow::Continue(Descend::from(holds))
+                }
+            })
+        {
+            return Some(span);
+        }
+        if matches!(node, Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_)) {
+            break;
+        }
+        child = parent_id;
+    }
+    None
+}
@@ -5,6 +5,7 @@ use clippy_utils::{eager_or_lazy, is_from_proc_macro, usage};
 use hir::FnRetTy;
 use rustc_errors::Applicability;
 use rustc_hir as hir;
+use rustc_hir::def_id::DefIdSet;
 use rustc_lint::LateContext;
 use rustc_span::sym;
 
@@ -18,6 +19,7 @@ pub(super) fn check<'tcx>(
     recv: &'tcx hir::Expr<'_>,
     arg: &'tcx hir::Expr<'_>,
     simplify_using: &str,
+    expensive_calls: &DefIdSet,
 ) {
     let is_option = is_type_diagnostic_item(cx, cx.typeck_results().expr_ty(recv), sym::Option);
     let is_result = is_type_diagnostic_item(cx, cx.typeck_results().expr_ty(recv), sym::Result);
@@ -32,7 +34,7 @@ pub(super) fn check<'tcx>(
                 return;
             }
 
-            if eager_or_lazy::switch_to_eager_eval(cx, body_expr) {
+            if eager_or_lazy::switch_to_eager_eval_with_costs(cx, body_expr, expensive_calls) {
                 let msg = if is_option {
                     "unnecessary closure used to substitute value for `Option::None`"
                 } else if is_result {
@@ -1,17 +1,22 @@
 use clippy_config::msrvs::{self, Msrv};
-use clippy_utils::diagnostics::span_lint;
+use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::qualify_min_const_fn::is_min_const_fn;
+use clippy_utils::source::snippet_opt;
+use clippy_utils::str_utils::matches_wildcard;
 use clippy_utils::ty::has_drop;
-use clippy_utils::{fn_has_unsatisfiable_preds, is_entrypoint_fn, is_from_proc_macro, trait_ref_of_method};
+use clippy_utils::{
+    fn_has_unsatisfiable_preds, is_entrypoint_fn, is_from_proc_macro, is_lint_allowed, trait_ref_of_method,
+};
+use rustc_errors::Applicability;
 use rustc_hir as hir;
-use rustc_hir::def_id::CRATE_DEF_ID;
+use rustc_hir::def_id::{DefId, CRATE_DEF_ID};
 use rustc_hir::intravisit::FnKind;
 use rustc_hir::{Body, Constness, FnDecl, GenericParamKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::lint::in_external_macro;
 use rustc_session::impl_lint_pass;
 use rustc_span::def_id::LocalDefId;
-use rustc_span::Span;
+use rustc_span::{BytePos, Span};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -71,16 +76,83 @@ declare_clippy_lint! {
     "Lint functions definitions that could be made `const fn`"
 }
 
-impl_lint_pass!(MissingConstForFn => [MISSING_CONST_FOR_FN]);
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for functions which could be `const fn`, like `missing_const_for_fn`, in the
+    /// modules matching one of the `required-const-fn-modules`, or in all the modules if none is
+    /// configured.
+    ///
+    /// The functions called must be stable as `const fn`, and the language features used stable
+    /// in `const fn`, at the MSRV configured by `msrv`.
+    ///
+    /// ### Why is this bad?
+    /// Some modules, e.g. the ones computing lookup tables or holding the constructors of the
+    /// types of an API, are meant to be usable in constants. Requiring their functions to be
+    /// `const` as soon as they can be avoids discovering it when a caller needs them in a `const`
+    /// context, after the function was published without it.
+    ///
+    /// ### Known problems
+    /// Removing `const` from a public function later on is a breaking change.
+    ///
+    /// ### Example
+    /// ```toml
+    /// required-const-fn-modules = ["crate::tables", "crate::*::consts"]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// pub fn crc_entry(index: u32) -> u32 {
+    ///     index.rotate_left(8) ^ 0xEDB8_8320
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// pub const fn crc_entry(index: u32) -> u32 {
+    ///     index.rotate_left(8) ^ 0xEDB8_8320
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub MISSING_REQUIRED_CONST_FN,
+    restriction,
+    "function not marked `const` in a module requiring `const fn` where possible"
+}
+
+impl_lint_pass!(MissingConstForFn => [MISSING_CONST_FOR_FN, MISSING_REQUIRED_CONST_FN]);
 
 pub struct MissingConstForFn {
     msrv: Msrv,
+    required_modules: Vec<String>,
 }
 
 impl MissingConstForFn {
     #[must_use]
-    pub fn new(msrv: Msrv) -> Self {
-        Self { msrv }
+    pub fn new(msrv: Msrv, required_modules: Vec<String>) -> Self {
+        Self { msrv, required_modules }
+    }
+
+    /// Returns the pattern of `required-const-fn-modules` matching the module of the function, or
+    /// its ancestors, or `Some("")` if no module is configured.
+    fn required_module(&self, cx: &LateContext<'_>, def_id: LocalDefId) -> Option<&str> {
+        if self.required_modules.is_empty() {
+            return Some("");
+        }
+        let mut module: DefId = cx.tcx.parent_module_from_def_id(def_id).to_def_id();
+        loop {
+            let path = cx
+                .tcx
+                .def_path(module)
+                .data
+                .iter()
+                .fold(String::from("crate"), |path, segment| format!("{path}::{segment}"));
+            if let Some(pattern) = self
+                .required_modules
+                .iter()
+                .find(|pattern| matches_wildcard(pattern, &path))
+            {
+                return Some(pattern);
+            }
+            module = cx.tcx.opt_parent(module)?;
+        }
     }
 }
 
@@ -156,7 +228,34 @@ impl<'tcx> LateLintPass<'tcx> for MissingConstForFn {
                 cx.tcx.dcx().span_err(span, err);
             }
         } else {
-            span_lint(cx, MISSING_CONST_FOR_FN, span, "this could be a `const fn`");
+            let required_module = if is_lint_allowed(cx, MISSING_REQUIRED_CONST_FN, hir_id) {
+                None
+            } else {
+                self.required_module(cx, def_id)
+            };
+            let lint = if required_module.is_some() {
+                MISSING_REQUIRED_CONST_FN
+            } else {
+                MISSING_CONST_FOR_FN
+            };
+            span_lint_and_then(cx, lint, span, "this could be a `const fn`", |diag| {
+                if let Some(pattern) = required_module
+                    && !pattern.is_empty()
+                {
+                    diag.note(format!("the function is in a module matching `{pattern}`"));
+                }
+                if let Some(sig) = cx.tcx.hir_node_by_def_id(def_id).fn_sig()
+                    && !sig.span.from_expansion()
+                    && let Some(const_span) = const_insertion_span(cx, sig.span)
+                {
+                    diag.span_suggestion_verbose(
+                        const_span,
+                        "make the function `const`",
+                        "const ",
+                        Applicability::MachineApplicable,
+                    );
+                }
+            });
         }
     }
     extract_msrv_attr!(LateContext);
@@ -171,6 +270,24 @@ fn method_accepts_droppable(cx: &LateContext<'_>, def_id: LocalDefId) -> bool {
     sig.inputs().iter().any(|&ty| has_drop(cx, ty))
 }
 
+/// Returns the position of `const` in the signature, after the visibility and `default`, and before
+/// the other qualifiers, e.g. `unsafe`. The signature of methods starts with their visibility.
+fn const_insertion_span(cx: &LateContext<'_>, sig_span: Span) -> Option<Span> {
+    let snip = snippet_opt(cx, sig_span)?;
+    let mut rest = snip.as_str();
+    if let Some(after_pub) = rest.strip_prefix("pub") {
+        rest = after_pub.trim_start();
+        if let Some(restriction) = rest.strip_prefix('(') {
+            rest = restriction.split_once(')')?.1.trim_start();
+        }
+    }
+    if let Some(after_default) = rest.strip_prefix("default ") {
+        rest = after_default.trim_start();
+    }
+    let offset = u32::try_from(snip.len() - rest.len()).ok()?;
+    Some(sig_span.with_lo(sig_span.lo() + BytePos(offset)).shrink_to_lo())
+}
+
 // We don't have to lint on something that's already `const`
 #[must_use]
 fn already_const(header: hir::FnHeader) -> bool {
@@ -0,0 +1,149 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::trait_ref_of_method;
+use clippy_utils::ty::is_type_diagnostic_item;
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::{FnDecl, FnRetTy, ImplItem, ImplItemKind, Item, ItemKind, TraitItem, TraitItemKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty;
+use rustc_session::impl_lint_pass;
+use rustc_span::{sym, Span, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for public types used as the error of the `Result` returned by a public function,
+    /// which don't implement all of `Debug`, `Display` and `std::error::Error`.
+    ///
+    /// ### Why is this bad?
+    /// Callers expect errors to be printable, and to be usable with `?` in functions returning
+    /// `Box<dyn Error>` or the error types of crates like `anyhow`, which all need these traits.
+    /// They can't be implemented outside of the crate defining the type.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// #[derive(Debug)]
+    /// pub struct ParseError;
+    ///
+    /// pub fn parse(input: &str) -> Result<u32, ParseError> {
+    ///     input.parse().map_err(|_| ParseError)
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```no_run
+    /// #[derive(Debug)]
+    /// pub struct ParseError;
+    ///
+    /// impl std::fmt::Display for ParseError {
+    ///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         f.write_str("invalid number")
+    ///     }
+    /// }
+    ///
+    /// impl std::error::Error for ParseError {}
+    ///
+    /// pub fn parse(input: &str) -> Result<u32, ParseError> {
+    ///     input.parse().map_err(|_| ParseError)
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub MISSING_ERROR_IMPLS,
+    pedantic,
+    "public error type not implementing `Debug`, `Display` and `std::error::Error`"
+}
+
+impl_lint_pass!(MissingErrorImpls => [MISSING_ERROR_IMPLS]);
+
+#[derive(Default)]
+pub struct MissingErrorImpls {
+    /// The local error types returned by public functions, with the signature of the first
+    /// function returning them.
+    error_types: FxIndexMap<LocalDefId, Span>,
+}
+
+impl MissingErrorImpls {
+    /// Records the error type of the `Result` returned by the function, if it's a local type.
+    fn check_fn_decl(&mut self, cx: &LateContext<'_>, decl: &FnDecl<'_>, def_id: LocalDefId, span: Span) {
+        if let FnRetTy::Return(_) = decl.output
+            && cx.effective_visibilities.is_exported(def_id)
+            && !in_external_macro(cx.sess(), span)
+            && let ret_ty = cx
+                .tcx
+                .instantiate_bound_regions_with_erased(cx.tcx.fn_sig(def_id).instantiate_identity().output())
+            && is_type_diagnostic_item(cx, ret_ty, sym::Result)
+            && let ty::Adt(_, args) = ret_ty.kind()
+            && let ty::Adt(err_def, _) = args.type_at(1).kind()
+            && let Some(err_id) = err_def.did().as_local()
+        {
+            self.error_types
+                .entry(err_id)
+                .or_insert_with(|| span.with_hi(decl.output.span().hi()));
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for MissingErrorImpls {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if let ItemKind::Fn(sig, ..) = item.kind {
+            self.check_fn_decl(cx, sig.decl, item.owner_id.def_id, item.span);
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx ImplItem<'tcx>) {
+        // The signatures of trait impls are given by the trait
+        if let ImplItemKind::Fn(sig, _) = item.kind
+            && trait_ref_of_method(cx, item.owner_id.def_id).is_none()
+        {
+            self.check_fn_decl(cx, sig.decl, item.owner_id.def_id, item.span);
+        }
+    }
+
+    fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx TraitItem<'tcx>) {
+        if let TraitItemKind::Fn(sig, _) = item.kind {
+            self.check_fn_decl(cx, sig.decl, item.owner_id.def_id, item.span);
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        let traits: Vec<_> = [
+            (sym::Debug, "Debug"),
+            (sym::Display, "Display"),
+            (sym::Error, "std::error::Error"),
+        ]
+        .into_iter()
+        .filter_map(|(name, path): (Symbol, &str)| Some((cx.tcx.get_diagnostic_item(name)?, path)))
+        .collect();
+        for (&def_id, &fn_span) in &self.error_types {
+            if !cx.effective_visibilities.is_exported(def_id) || in_external_macro(cx.sess(), cx.tcx.def_span(def_id)) {
+                continue;
+            }
+            let ty = cx.tcx.type_of(def_id).instantiate_identity();
+            let missing: Vec<_> = traits
+                .iter()
+                .filter(|&&(trait_id, _)| cx.tcx.non_blanket_impls_for_ty(trait_id, ty).next().is_none())
+                .map(|&(_, path)| format!("`{path}`"))
+                .collect();
+            let Some((last, rest)) = missing.split_last() else {
+                continue;
+            };
+            let missing = if rest.is_empty() {
+                last.clone()
+            } else {
+                format!("{} and {last}", rest.join(", "))
+            };
+            span_lint_and_then(
+                cx,
+                MISSING_ERROR_IMPLS,
+                cx.tcx.def_span(def_id),
+                format!(
+                    "public error type `{}` doesn't implement {missing}",
+                    cx.tcx.item_name(def_id.to_def_id())
+                ),
+                |diag| {
+                    diag.span_note(fn_span, "returned as an error here");
+                },
+            );
+        }
+    }
+}
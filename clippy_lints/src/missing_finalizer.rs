@@ -0,0 +1,284 @@
+use clippy_config::types::MustCallFinalizer;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::{def_path_def_ids, fn_def_id};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::{Body, Expr, LetStmt, Node, PatKind, Stmt, StmtKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::mir::{self, BasicBlock, Local, Operand, Rvalue, StatementKind, TerminatorKind};
+use rustc_session::impl_lint_pass;
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Allows users to configure constructors whose result must be passed to one of a set
+    /// of finalizers, and checks that this happens on every path through the function.
+    ///
+    /// If a constructor has no finalizers, its result only needs to be bound instead of
+    /// being dropped right away.
+    ///
+    /// ### Why is this bad?
+    /// Some values need to be explicitly finished, e.g. a transaction must be committed
+    /// or rolled back and a builder is useless unless `build` is called. Dropping such
+    /// a value silently discards the work done with it.
+    ///
+    /// ### Known problems
+    /// A value which is moved into a function other than a finalizer, returned, or stored
+    /// in another value is assumed to be finalized elsewhere. `async` bodies are not checked.
+    ///
+    /// ### Example
+    /// ```toml
+    /// must-call-finalizers = [
+    ///     { constructor = "db::Transaction::begin", finalizers = ["db::Transaction::commit", "db::Transaction::rollback"] },
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// fn transfer(db: &Db, amount: u64) -> Result<(), Error> {
+    ///     let tx = db::Transaction::begin(db);
+    ///     if amount == 0 {
+    ///         return Ok(()); // `tx` is dropped without being committed or rolled back
+    ///     }
+    ///     tx.execute("...")?;
+    ///     tx.commit()
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// fn transfer(db: &Db, amount: u64) -> Result<(), Error> {
+    ///     if amount == 0 {
+    ///         return Ok(());
+    ///     }
+    ///     let tx = db::Transaction::begin(db);
+    ///     tx.execute("...")?;
+    ///     tx.commit()
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub MISSING_FINALIZER,
+    suspicious,
+    "a value which must be finalized as per the configuration is dropped without being finalized"
+}
+
+impl_lint_pass!(MissingFinalizer => [MISSING_FINALIZER]);
+
+pub struct MissingFinalizer {
+    conf: Vec<MustCallFinalizer>,
+    /// Maps the `DefId` of a constructor to its index in `conf`.
+    constructors: FxHashMap<DefId, usize>,
+    /// The `DefId`s of the finalizers of each entry of `conf`.
+    finalizers: Vec<FxHashSet<DefId>>,
+}
+
+impl MissingFinalizer {
+    pub fn new(conf: Vec<MustCallFinalizer>) -> Self {
+        Self {
+            conf,
+            constructors: FxHashMap::default(),
+            finalizers: Vec::new(),
+        }
+    }
+
+    fn check_mir<'tcx>(&self, cx: &LateContext<'tcx>, mir: &mir::Body<'tcx>) {
+        for data in mir.basic_blocks.iter() {
+            if data.is_cleanup {
+                continue;
+            }
+            if let TerminatorKind::Call {
+                func,
+                destination,
+                target: Some(target),
+                fn_span,
+                ..
+            } = &data.terminator().kind
+                && let Some((callee, _)) = func.const_fn_def()
+                && let Some(&index) = self.constructors.get(&callee)
+                && !self.finalizers[index].is_empty()
+                && destination.projection.is_empty()
+                && !fn_span.from_expansion()
+                && let Some(unfinalized) = self.find_unfinalized_path(mir, index, destination.local, *target)
+            {
+                self.emit(cx, index, *fn_span, |diag| {
+                    diag.span_note(unfinalized, "the value is dropped here without being finalized");
+                });
+            }
+        }
+    }
+
+    /// Follows the paths starting at `start` on which `value` is not finalized. Returns the span of
+    /// the end of the function if one of them reaches it.
+    fn find_unfinalized_path(
+        &self,
+        mir: &mir::Body<'_>,
+        index: usize,
+        value: Local,
+        start: BasicBlock,
+    ) -> Option<Span> {
+        // The locals holding the value, or a reference to it, for each block which was reached.
+        let mut visited: FxHashMap<BasicBlock, FxHashSet<Local>> = FxHashMap::default();
+        let mut stack = vec![(start, FxHashSet::from_iter([value]))];
+
+        'paths: while let Some((block, mut tracked)) = stack.pop() {
+            let seen = visited.entry(block).or_default();
+            if tracked.is_subset(seen) {
+                continue;
+            }
+            seen.extend(tracked.iter().copied());
+            tracked.clone_from(seen);
+
+            let data = &mir.basic_blocks[block];
+            for statement in &data.statements {
+                if let StatementKind::Assign(box (place, rvalue)) = &statement.kind {
+                    let uses_value = match rvalue {
+                        Rvalue::Use(operand) | Rvalue::Cast(_, operand, _) => {
+                            operand.place().is_some_and(|p| tracked.contains(&p.local))
+                        },
+                        Rvalue::Ref(_, _, p) | Rvalue::AddressOf(_, p) => tracked.contains(&p.local),
+                        Rvalue::Aggregate(_, operands) => {
+                            if operands
+                                .iter()
+                                .any(|op| op.place().is_some_and(|p| tracked.contains(&p.local)))
+                            {
+                                // Stored in another value, assume it is finalized elsewhere.
+                                continue 'paths;
+                            }
+                            false
+                        },
+                        _ => false,
+                    };
+                    if uses_value {
+                        if !place.projection.is_empty() {
+                            continue 'paths;
+                        }
+                        tracked.insert(place.local);
+                    }
+                }
+            }
+
+            let terminator = data.terminator();
+            match &terminator.kind {
+                TerminatorKind::Call {
+                    func,
+                    args,
+                    destination,
+                    target,
+                    ..
+                } => {
+                    let uses_value = |op: &Operand<'_>| op.place().is_some_and(|p| tracked.contains(&p.local));
+                    if args.iter().any(|arg| uses_value(&arg.node)) {
+                        if let Some((callee, _)) = func.const_fn_def()
+                            && self.finalizers[index].contains(&callee)
+                        {
+                            continue;
+                        }
+                        // Moving a reference to the value doesn't move the value itself.
+                        let ty = mir.local_decls[value].ty;
+                        let moves_value = |op: &Operand<'_>| {
+                            matches!(op, Operand::Move(p) if p.projection.is_empty()
+                                && tracked.contains(&p.local)
+                                && mir.local_decls[p.local].ty == ty)
+                        };
+                        // Builder methods take the value and return it, keep following the result.
+                        let dest_ty = mir.local_decls[destination.local].ty;
+                        if dest_ty == ty || dest_ty.peel_refs() == ty {
+                            tracked.insert(destination.local);
+                        } else if args.iter().any(|arg| moves_value(&arg.node)) {
+                            // Moved into another function, assume it is finalized there.
+                            continue;
+                        }
+                    }
+                    if let Some(target) = target {
+                        stack.push((*target, tracked));
+                    }
+                },
+                TerminatorKind::Return => {
+                    if !tracked.contains(&mir::RETURN_PLACE) {
+                        return Some(terminator.source_info.span);
+                    }
+                },
+                _ => {
+                    for succ in terminator.successors() {
+                        if !mir.basic_blocks[succ].is_cleanup {
+                            stack.push((succ, tracked.clone()));
+                        }
+                    }
+                },
+            }
+        }
+        None
+    }
+
+    fn emit(&self, cx: &LateContext<'_>, index: usize, span: Span, f: impl FnOnce(&mut rustc_errors::Diag<'_, ()>)) {
+        if in_external_macro(cx.sess(), span) {
+            return;
+        }
+        let conf = &self.conf[index];
+        let msg = if conf.finalizers.is_empty() {
+            format!("the result of `{}` is dropped right away", conf.constructor)
+        } else {
+            let finalizers: Vec<_> = conf.finalizers.iter().map(|path| format!("`{path}`")).collect();
+            format!(
+                "the result of `{}` may be dropped without calling {}",
+                conf.constructor,
+                finalizers.join(" or ")
+            )
+        };
+        span_lint_and_then(cx, MISSING_FINALIZER, span, msg, |diag| {
+            f(diag);
+            if let Some(reason) = conf.reason() {
+                diag.note(reason);
+            }
+        });
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for MissingFinalizer {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for (index, conf) in self.conf.iter().enumerate() {
+            let segs: Vec<_> = conf.constructor.split("::").collect();
+            for id in def_path_def_ids(cx, &segs) {
+                self.constructors.insert(id, index);
+            }
+            self.finalizers.push(
+                conf.finalizers
+                    .iter()
+                    .flat_map(|path| {
+                        let segs: Vec<_> = path.split("::").collect();
+                        def_path_def_ids(cx, &segs)
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    fn check_body(&mut self, cx: &LateContext<'tcx>, body: &'tcx Body<'tcx>) {
+        if self.constructors.is_empty() {
+            return;
+        }
+        let def_id: LocalDefId = cx.tcx.hir().body_owner_def_id(body.id());
+        if cx.tcx.hir().body_owner_kind(def_id).is_fn_or_closure() && cx.tcx.coroutine_kind(def_id).is_none() {
+            self.check_mir(cx, cx.tcx.optimized_mir(def_id));
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let Some(callee) = fn_def_id(cx, expr)
+            && let Some(&index) = self.constructors.get(&callee)
+            && self.conf[index].finalizers.is_empty()
+        {
+            let dropped = match cx.tcx.parent_hir_node(expr.hir_id) {
+                Node::Stmt(Stmt {
+                    kind: StmtKind::Semi(_),
+                    ..
+                }) => true,
+                Node::LetStmt(LetStmt { pat, .. }) => matches!(pat.kind, PatKind::Wild),
+                _ => false,
+            };
+            if dropped {
+                self.emit(cx, index, expr.span, |_| {});
+            }
+        }
+    }
+}
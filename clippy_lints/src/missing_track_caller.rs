@@ -0,0 +1,225 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::macros::{find_assert_args, find_assert_eq_args, is_panic, root_macro_call_first_node};
+use clippy_utils::panics::{panic_sites, PanicConfig, PanicKind};
+use clippy_utils::source::indent_of;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{is_entrypoint_fn, is_in_test, is_trait_impl_item, path_to_local};
+use core::ops::ControlFlow;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::Applicability;
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{Body, Expr, ExprKind, FnDecl, HirId, StmtKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::TypeckResults;
+use rustc_session::declare_lint_pass;
+use rustc_span::def_id::LocalDefId;
+use rustc_span::{sym, Span};
+use rustc_target::spec::abi::Abi;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for small functions and inherent methods whose only panics check their arguments,
+    /// with `assert!`, `assert_eq!`, `assert_ne!`, an `if` calling `panic!`, or by unwrapping an
+    /// `Option` argument, and which aren't marked `#[track_caller]`.
+    ///
+    /// ### Why is this bad?
+    /// Such a panic is caused by the caller passing an invalid argument, but its message points to
+    /// the line of the check in the helper. With `#[track_caller]`, it points to the call instead,
+    /// as for the methods of the standard library, e.g. `Option::unwrap`.
+    ///
+    /// ### Known problems
+    /// Only the body of the function is looked at: the panics of the functions it calls aren't
+    /// taken into account, and neither are indexing or integer division.
+    ///
+    /// Trait methods are ignored, since the caller isn't tracked through a trait object unless the
+    /// method of the trait is also `#[track_caller]`.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn percent(value: u32) -> f64 {
+    ///     assert!(value <= 100, "not a percentage: {value}");
+    ///     f64::from(value) / 100.0
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// #[track_caller]
+    /// fn percent(value: u32) -> f64 {
+    ///     assert!(value <= 100, "not a percentage: {value}");
+    ///     f64::from(value) / 100.0
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub MISSING_TRACK_CALLER,
+    pedantic,
+    "function checking its arguments with panics without being `#[track_caller]`"
+}
+
+declare_lint_pass!(MissingTrackCaller => [MISSING_TRACK_CALLER]);
+
+/// The number of lines of the body above which a function isn't considered to be a helper.
+const MAX_LINES: usize = 10;
+
+/// `todo!`, `unimplemented!` and `unreachable!` are looked for to exclude the functions using
+/// them, they aren't argument checks.
+const PANICS: PanicConfig = PanicConfig::new(
+    &[
+        PanicKind::Explicit,
+        PanicKind::Assert,
+        PanicKind::Unreachable,
+        PanicKind::Todo,
+        PanicKind::Unimplemented,
+        PanicKind::Unwrap,
+    ],
+    0,
+);
+
+impl<'tcx> LateLintPass<'tcx> for MissingTrackCaller {
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'tcx>,
+        body: &'tcx Body<'tcx>,
+        span: Span,
+        def_id: LocalDefId,
+    ) {
+        let header = match kind {
+            FnKind::ItemFn(_, _, header) => header,
+            FnKind::Method(_, sig) => sig.header,
+            FnKind::Closure => return,
+        };
+        let hir_id = cx.tcx.local_def_id_to_hir_id(def_id);
+        if header.abi != Abi::Rust
+            || header.is_async()
+            || span.from_expansion()
+            || in_external_macro(cx.sess(), span)
+            || cx.tcx.has_attr(def_id, sym::track_caller)
+            || cx.tcx.has_attr(def_id, sym::naked)
+            || is_entrypoint_fn(cx, def_id.to_def_id())
+            || is_in_test(cx.tcx, hir_id)
+            || is_trait_impl_item(cx, hir_id)
+            || cx.tcx.trait_of_item(def_id.to_def_id()).is_some()
+        {
+            return;
+        }
+        let source_map = cx.sess().source_map();
+        let lines = source_map.lookup_char_pos(body.value.span.hi()).line
+            - source_map.lookup_char_pos(body.value.span.lo()).line
+            + 1;
+        if lines > MAX_LINES {
+            return;
+        }
+
+        let typeck = cx.tcx.typeck_body(body.id());
+        let panics = panic_sites(cx, typeck, body.value, PANICS);
+        if panics.is_empty() {
+            return;
+        }
+        let mut params = FxHashSet::default();
+        for param in body.params {
+            param.pat.each_binding(|_, id, _, _| {
+                params.insert(id);
+            });
+        }
+        let checks = argument_checks(cx, typeck, body.value, &params);
+        if !panics.iter().all(|panic| checks.contains(&panic.span)) {
+            return;
+        }
+
+        let fn_span = cx.tcx.def_span(def_id);
+        span_lint_and_then(
+            cx,
+            MISSING_TRACK_CALLER,
+            fn_span,
+            "this function panics on invalid arguments, but isn't `#[track_caller]`",
+            |diag| {
+                diag.span_note(
+                    panics.iter().map(|panic| panic.span).collect::<Vec<_>>(),
+                    "the panic messages point here, instead of to the caller",
+                );
+                let indent = " ".repeat(indent_of(cx, fn_span).unwrap_or(0));
+                diag.span_suggestion(
+                    fn_span.shrink_to_lo(),
+                    "add the attribute",
+                    format!("#[track_caller]\n{indent}"),
+                    Applicability::MachineApplicable,
+                );
+            },
+        );
+    }
+}
+
+/// Returns the spans of the panics of `body` which check the arguments of the function: the
+/// assertions and the `if` calling `panic!` whose conditions only read arguments, and the calls to
+/// `unwrap` or `expect` on an `Option` argument.
+fn argument_checks<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck: &TypeckResults<'tcx>,
+    body: &'tcx Expr<'tcx>,
+    params: &FxHashSet<HirId>,
+) -> FxHashSet<Span> {
+    let mut checks = FxHashSet::default();
+    for_each_expr(body, |e| {
+        if let Some(macro_call) = root_macro_call_first_node(cx, e) {
+            let is_check = match cx.tcx.get_diagnostic_name(macro_call.def_id) {
+                Some(sym::assert_macro) => {
+                    find_assert_args(cx, e, macro_call.expn).is_some_and(|(cond, _)| reads_only(&[cond], params))
+                },
+                Some(sym::assert_eq_macro | sym::assert_ne_macro) => find_assert_eq_args(cx, e, macro_call.expn)
+                    .is_some_and(|(left, right, _)| reads_only(&[left, right], params)),
+                _ => false,
+            };
+            if is_check {
+                checks.insert(macro_call.span);
+            }
+            return ControlFlow::Continue(());
+        }
+        match e.kind {
+            ExprKind::If(cond, then, None) if reads_only(&[cond], params) => {
+                if let ExprKind::Block(block, _) = then.kind
+                    && let Some(panic) = match (block.stmts, block.expr) {
+                        ([stmt], None) => match stmt.kind {
+                            StmtKind::Expr(e) | StmtKind::Semi(e) => Some(e),
+                            _ => None,
+                        },
+                        ([], Some(e)) => Some(e),
+                        _ => None,
+                    }
+                    && let Some(macro_call) = root_macro_call_first_node(cx, panic)
+                    && is_panic(cx, macro_call.def_id)
+                {
+                    checks.insert(macro_call.span);
+                }
+            },
+            ExprKind::MethodCall(method, receiver, ..)
+                if matches!(method.ident.as_str(), "unwrap" | "expect")
+                    && path_to_local(receiver).is_some_and(|id| params.contains(&id))
+                    && is_type_diagnostic_item(cx, typeck.expr_ty(receiver), sym::Option) =>
+            {
+                checks.insert(e.span);
+            },
+            _ => {},
+        }
+        ControlFlow::<()>::Continue(())
+    });
+    checks
+}
+
+/// Checks if `exprs` read at least one of the arguments, and no other local variable.
+fn reads_only(exprs: &[&Expr<'_>], params: &FxHashSet<HirId>) -> bool {
+    let mut reads_param = false;
+    exprs.iter().all(|e| {
+        for_each_expr(*e, |e| match path_to_local(e) {
+            Some(id) if params.contains(&id) => {
+                reads_param = true;
+                ControlFlow::Continue(())
+            },
+            Some(_) => ControlFlow::Break(()),
+            None => ControlFlow::Continue(()),
+        })
+        .is_none()
+    }) && reads_param
+}
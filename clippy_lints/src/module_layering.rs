@@ -0,0 +1,147 @@
+use clippy_config::types::LayeringRule;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::{is_in_test_context, local_def_path_def_ids};
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::{HirId, Item, ItemKind, Node, Path};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::impl_lint_pass;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for uses of the items of a module by another module of the same crate when
+    /// this dependency is disallowed in clippy.toml.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// layering rules are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Many crates are organized in layers, e.g. the domain logic must not depend on the
+    /// HTTP handlers, and helpers for tests must not be used by the rest of the code. This
+    /// lint enforces such an architecture without an external tool.
+    ///
+    /// With `allow-in-tests`, the uses in tests, including the code marked by one of the
+    /// `test-attributes`, are allowed.
+    ///
+    /// ### Known problems
+    /// A name brought into scope by a `use` item is only linted at the `use` item.
+    ///
+    /// ### Example
+    /// ```toml
+    /// module-layering = [
+    ///     { from = "crate::domain", to = "crate::http" },
+    ///     { from = "crate::*", to = "crate::test_support", allow-in-tests = true },
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// mod domain {
+    ///     use crate::http::StatusCode;
+    ///
+    ///     pub fn validate(order: &Order) -> Result<(), StatusCode> { /* .. */ }
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// mod domain {
+    ///     pub fn validate(order: &Order) -> Result<(), ValidationError> { /* .. */ }
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub MODULE_LAYERING,
+    style,
+    "use of an item of a module which the current module may not depend on as per the configuration"
+}
+
+impl_lint_pass!(ModuleLayering => [MODULE_LAYERING]);
+
+pub struct ModuleLayering {
+    conf: Vec<LayeringRule>,
+    /// The `DefId`s of the `from` and `to` modules of each entry of `conf`.
+    modules: Vec<(Vec<DefId>, Vec<DefId>)>,
+}
+
+impl ModuleLayering {
+    pub fn new(conf: Vec<LayeringRule>) -> Self {
+        Self {
+            conf,
+            modules: Vec::new(),
+        }
+    }
+}
+
+fn module_def_ids(cx: &LateContext<'_>, path: &str) -> Vec<DefId> {
+    local_def_path_def_ids(cx, path.strip_suffix("::*").unwrap_or(path))
+}
+
+/// Checks if the path starts with a name brought into scope by a `use` item, which is linted
+/// itself, rather than with a keyword like `crate` or an item declared in `module`.
+fn is_imported(cx: &LateContext<'_>, path: &Path<'_>, module: DefId) -> bool {
+    if let Some(first) = path.segments.first()
+        && !first.ident.is_path_segment_keyword()
+        && let Some(id) = first.res.opt_def_id()
+    {
+        cx.tcx.opt_parent(id) != Some(module)
+    } else {
+        false
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for ModuleLayering {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        self.modules = self
+            .conf
+            .iter()
+            .map(|rule| (module_def_ids(cx, &rule.from), module_def_ids(cx, &rule.to)))
+            .collect();
+    }
+
+    fn check_path(&mut self, cx: &LateContext<'tcx>, path: &Path<'tcx>, hir_id: HirId) {
+        let Some(target) = path.res.opt_def_id() else {
+            return;
+        };
+        if !target.is_local() || self.conf.is_empty() || in_external_macro(cx.sess(), path.span) {
+            return;
+        }
+        let module = cx.tcx.parent_module(hir_id).to_def_id();
+        let is_use = matches!(
+            cx.tcx.hir_node(hir_id),
+            Node::Item(Item {
+                kind: ItemKind::Use(..),
+                ..
+            })
+        );
+        if is_use {
+            // A `use` of a tuple struct also imports its constructor, which is the same dependency.
+            if matches!(path.res, Res::Def(DefKind::Ctor(..), _)) {
+                return;
+            }
+        } else if is_imported(cx, path, module) {
+            return;
+        }
+
+        let is_in = |id: DefId, modules: &[DefId]| modules.iter().any(|&module| cx.tcx.is_descendant_of(id, module));
+        for (rule, (from, to)) in self.conf.iter().zip(&self.modules) {
+            if is_in(module, from)
+                && is_in(target, to)
+                && !is_in(module, to)
+                && !(rule.allow_in_tests && is_in_test_context(cx, hir_id))
+            {
+                span_lint_and_then(
+                    cx,
+                    MODULE_LAYERING,
+                    path.span,
+                    format!("`{}` may not depend on `{}` per `clippy.toml`", rule.from, rule.to),
+                    |diag| {
+                        if let Some(reason) = rule.reason() {
+                            diag.note(reason);
+                        }
+                    },
+                );
+                return;
+            }
+        }
+    }
+}
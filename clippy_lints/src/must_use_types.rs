@@ -0,0 +1,182 @@
+use clippy_config::types::DisallowedPath;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::{is_must_use_func_call, local_def_path_def_ids};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::{
+    Expr, ExprKind, ImplItem, ImplItemKind, Item, ItemKind, LetStmt, PatKind, Stmt, StmtKind, TraitItem, TraitItemKind,
+};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, Ty};
+use rustc_session::impl_lint_pass;
+use rustc_span::{sym, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Treats the types configured in clippy.toml as if they were `#[must_use]`. Checks for
+    /// functions returning one of them without being `#[must_use]`, and for calls whose
+    /// result of such a type is discarded.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// types are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Some values are useless, or even harmful, when they are dropped right away, e.g. a
+    /// transaction or a guard. Adding `#[must_use]` to the type makes the compiler check this,
+    /// but this isn't possible for the types of other crates, or not yet done in a large
+    /// codebase.
+    ///
+    /// ### Example
+    /// ```toml
+    /// must-use-types = ["db::Transaction"]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// fn begin(db: &Db) -> db::Transaction {
+    ///     db.transaction()
+    /// }
+    ///
+    /// begin(&db);
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// #[must_use]
+    /// fn begin(db: &Db) -> db::Transaction {
+    ///     db.transaction()
+    /// }
+    ///
+    /// let tx = begin(&db);
+    /// tx.commit();
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub MUST_USE_TYPES,
+    style,
+    "a value of a type configured as must use is discarded, or returned by a function which isn't `#[must_use]`"
+}
+
+impl_lint_pass!(MustUseTypes => [MUST_USE_TYPES]);
+
+pub struct MustUseTypes {
+    conf: Vec<DisallowedPath>,
+    /// Maps the `DefId` of a configured type to its index in `conf`.
+    def_ids: FxHashMap<DefId, usize>,
+}
+
+impl MustUseTypes {
+    pub fn new(conf: Vec<DisallowedPath>) -> Self {
+        Self {
+            conf,
+            def_ids: FxHashMap::default(),
+        }
+    }
+
+    /// Returns the index in `conf` of the type, unless it is already `#[must_use]`.
+    fn configured_index(&self, cx: &LateContext<'_>, ty: Ty<'_>) -> Option<usize> {
+        if let ty::Adt(adt, _) = ty.kind()
+            && let Some(&index) = self.def_ids.get(&adt.did())
+            && !cx.tcx.has_attr(adt.did(), sym::must_use)
+        {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    fn check_fn_output(&self, cx: &LateContext<'_>, def_id: LocalDefId) {
+        let span = cx.tcx.def_span(def_id);
+        let output = cx.tcx.fn_sig(def_id).instantiate_identity().output().skip_binder();
+        if let Some(index) = self.configured_index(cx, output)
+            && !cx.tcx.has_attr(def_id, sym::must_use)
+            && !in_external_macro(cx.sess(), span)
+        {
+            self.emit(
+                cx,
+                index,
+                span,
+                format!(
+                    "this function returns `{}` but isn't `#[must_use]`",
+                    self.conf[index].path()
+                ),
+                "add `#[must_use]` to the function",
+            );
+        }
+    }
+
+    /// Checks the discarded value `expr`. Calls to `#[must_use]` functions are already linted by
+    /// the compiler unless `let _ = ..` is used.
+    fn check_discarded(&self, cx: &LateContext<'_>, expr: &Expr<'_>, is_let: bool) {
+        if matches!(expr.kind, ExprKind::Call(..) | ExprKind::MethodCall(..))
+            && (is_let || !is_must_use_func_call(cx, expr))
+            && let Some(index) = self.configured_index(cx, cx.typeck_results().expr_ty(expr))
+            && !in_external_macro(cx.sess(), expr.span)
+        {
+            self.emit(
+                cx,
+                index,
+                expr.span,
+                format!("unused value of type `{}`", self.conf[index].path()),
+                "the type is configured as must use in `clippy.toml`",
+            );
+        }
+    }
+
+    fn emit(&self, cx: &LateContext<'_>, index: usize, span: Span, msg: String, help: &'static str) {
+        span_lint_and_then(cx, MUST_USE_TYPES, span, msg, |diag| {
+            if let Some(reason) = self.conf[index].reason() {
+                diag.note(reason);
+            }
+            diag.help(help);
+        });
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for MustUseTypes {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for (index, conf) in self.conf.iter().enumerate() {
+            for id in local_def_path_def_ids(cx, conf.path()) {
+                self.def_ids.insert(id, index);
+            }
+        }
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if let ItemKind::Fn(..) = item.kind
+            && !self.def_ids.is_empty()
+        {
+            self.check_fn_output(cx, item.owner_id.def_id);
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx ImplItem<'tcx>) {
+        // The items of trait impls are checked at the trait.
+        if let ImplItemKind::Fn(..) = item.kind
+            && !self.def_ids.is_empty()
+            && cx.tcx.associated_item(item.owner_id).trait_item_def_id.is_none()
+        {
+            self.check_fn_output(cx, item.owner_id.def_id);
+        }
+    }
+
+    fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx TraitItem<'tcx>) {
+        if let TraitItemKind::Fn(..) = item.kind
+            && !self.def_ids.is_empty()
+        {
+            self.check_fn_output(cx, item.owner_id.def_id);
+        }
+    }
+
+    fn check_stmt(&mut self, cx: &LateContext<'tcx>, stmt: &'tcx Stmt<'tcx>) {
+        if self.def_ids.is_empty() {
+            return;
+        }
+        match stmt.kind {
+            StmtKind::Semi(expr) => self.check_discarded(cx, expr, false),
+            StmtKind::Let(LetStmt {
+                pat, init: Some(init), ..
+            }) if matches!(pat.kind, PatKind::Wild) => self.check_discarded(cx, init, true),
+            _ => {},
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use clippy_config::types::{NamePattern, NamePatterns};
+use clippy_utils::cargo::manifest;
+use clippy_utils::diagnostics::span_lint;
+use clippy_utils::is_in_test_function;
+use regex::Regex;
+use rustc_hir::def::DefKind;
+use rustc_hir::{ImplItem, ImplItemKind, Item, ItemKind, Node, TraitItem, TraitItemKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::impl_lint_pass;
+use rustc_span::symbol::{kw, Ident};
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks that the names of items match the regexes configured for their kind in
+    /// clippy.toml.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// patterns are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Many projects have naming policies which go beyond the case checks done by rustc,
+    /// e.g. a prefix for test functions or a suffix for error types.
+    ///
+    /// ### Example
+    /// ```toml
+    /// naming-conventions = { types = "[A-Z][A-Za-z0-9]*", tests = "test_[a-z0-9_]+" }
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// struct HTTP_Client;
+    ///
+    /// #[test]
+    /// fn connects() {}
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// struct HttpClient;
+    ///
+    /// #[test]
+    /// fn test_connects() {}
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub NAMING_CONVENTIONS,
+    style,
+    "a name which does not match the naming convention from the configuration"
+}
+
+impl_lint_pass!(NamingConventions => [NAMING_CONVENTIONS]);
+
+struct Convention {
+    pattern: NamePattern,
+    regex: Regex,
+}
+
+impl Convention {
+    fn check(&self, cx: &LateContext<'_>, kind: &str, name: &str, span: Span) {
+        if !self.regex.is_match(name) {
+            span_lint(
+                cx,
+                NAMING_CONVENTIONS,
+                span,
+                format!(
+                    "{kind} name `{name}` does not match the configured pattern `{}`",
+                    self.pattern.as_str()
+                ),
+            );
+        }
+    }
+}
+
+pub struct NamingConventions {
+    types: Option<Convention>,
+    traits: Option<Convention>,
+    functions: Option<Convention>,
+    constants: Option<Convention>,
+    features: Option<Convention>,
+    tests: Option<Convention>,
+}
+
+impl NamingConventions {
+    pub fn new(patterns: NamePatterns) -> Self {
+        let convention = |pattern: Option<NamePattern>| {
+            pattern.map(|pattern| Convention {
+                regex: pattern.to_regex(),
+                pattern,
+            })
+        };
+        Self {
+            types: convention(patterns.types),
+            traits: convention(patterns.traits),
+            functions: convention(patterns.functions),
+            constants: convention(patterns.constants),
+            features: convention(patterns.features),
+            tests: convention(patterns.tests),
+        }
+    }
+
+    fn check_name(cx: &LateContext<'_>, kind: &str, convention: Option<&Convention>, ident: Ident, span: Span) {
+        if let Some(convention) = convention
+            && ident.name != kw::Underscore
+            && !span.from_expansion()
+            && !in_external_macro(cx.sess(), span)
+        {
+            convention.check(cx, kind, ident.name.as_str(), ident.span);
+        }
+    }
+
+    fn check_features(&self, cx: &LateContext<'_>) {
+        if let Some(convention) = &self.features
+            && let Some(manifest) = manifest(cx)
+        {
+            for feature in &manifest.toml.features {
+                convention.check(cx, "feature", feature.get_ref(), manifest.span(feature.span()));
+            }
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for NamingConventions {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        self.check_features(cx);
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        let (kind, convention) = match item.kind {
+            ItemKind::Struct(..) | ItemKind::Enum(..) | ItemKind::Union(..) | ItemKind::TyAlias(..) => {
+                ("type", self.types.as_ref())
+            },
+            ItemKind::Trait(..) | ItemKind::TraitAlias(..) => ("trait", self.traits.as_ref()),
+            ItemKind::Fn(..)
+                if self.tests.is_some()
+                    && cx.tcx.def_kind(cx.tcx.local_parent(item.owner_id.def_id)) == DefKind::Mod
+                    && is_in_test_function(cx.tcx, item.hir_id()) =>
+            {
+                ("test function", self.tests.as_ref())
+            },
+            ItemKind::Fn(..) => ("function", self.functions.as_ref()),
+            ItemKind::Const(..) | ItemKind::Static(..) => ("constant", self.constants.as_ref()),
+            _ => return,
+        };
+        Self::check_name(cx, kind, convention, item.ident, item.span);
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx ImplItem<'tcx>) {
+        // The names of the items of trait impls are chosen by the trait.
+        if let Node::Item(parent) = cx.tcx.parent_hir_node(item.hir_id())
+            && let ItemKind::Impl(impl_) = parent.kind
+            && impl_.of_trait.is_none()
+        {
+            let (kind, convention) = match item.kind {
+                ImplItemKind::Fn(..) => ("function", self.functions.as_ref()),
+                ImplItemKind::Const(..) => ("constant", self.constants.as_ref()),
+                ImplItemKind::Type(..) => return,
+            };
+            Self::check_name(cx, kind, convention, item.ident, item.span);
+        }
+    }
+
+    fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx TraitItem<'tcx>) {
+        let (kind, convention) = match item.kind {
+            TraitItemKind::Fn(..) => ("function", self.functions.as_ref()),
+            TraitItemKind::Const(..) => ("constant", self.constants.as_ref()),
+            TraitItemKind::Type(..) => ("type", self.types.as_ref()),
+        };
+        Self::check_name(cx, kind, convention, item.ident, item.span);
+    }
+}
@@ -1,10 +1,10 @@
 use clippy_utils::diagnostics::{multispan_sugg, span_lint_and_then};
-use clippy_utils::is_self;
 use clippy_utils::ptr::get_spans;
 use clippy_utils::source::{snippet, snippet_opt};
 use clippy_utils::ty::{
     implements_trait, implements_trait_with_env_from_iter, is_copy, is_type_diagnostic_item, is_type_lang_item,
 };
+use clippy_utils::{is_lint_allowed, is_self};
 use rustc_ast::ast::Attribute;
 use rustc_errors::{Applicability, Diag};
 use rustc_hir::intravisit::FnKind;
@@ -25,6 +25,8 @@ use rustc_target::spec::abi::Abi;
 use rustc_trait_selection::traits;
 use rustc_trait_selection::traits::misc::type_allowed_to_implement_copy;
 
+use crate::ptr::{is_owned_param_only_borrowed, OWNED_PARAM_ONLY_BORROWED};
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for functions taking arguments by value, but not
@@ -194,6 +196,10 @@ impl<'tcx> LateLintPass<'tcx> for NeedlessPassByValue {
                 && !all_borrowable_trait
                 && let PatKind::Binding(BindingMode(_, Mutability::Not), canonical_id, ..) = arg.pat.kind
                 && !moved_vars.contains(&canonical_id)
+                // The more precise lint is emitted instead.
+                && (kind.asyncness().is_async()
+                    || is_lint_allowed(cx, OWNED_PARAM_ONLY_BORROWED, arg.hir_id)
+                    || !is_owned_param_only_borrowed(cx, decl, fn_sig, body, idx))
             {
                 // Dereference suggestion
                 let sugg = |diag: &mut Diag<'_, ()>| {
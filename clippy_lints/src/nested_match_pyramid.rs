@@ -0,0 +1,344 @@
+use clippy_config::msrvs::{self, Msrv};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::{higher, is_else_clause, is_res_lang_ctor, path_res};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::{Applicability, MultiSpan};
+use rustc_hir::LangItem::{OptionNone, OptionSome, ResultErr, ResultOk};
+use rustc_hir::{Arm, Expr, ExprKind, HirId, MatchSource, Node, Pat, PatKind, StmtKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::impl_lint_pass;
+use rustc_span::{BytePos, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for two or more nested `match` or `if let` expressions on `Option` or `Result`
+    /// values, where only the `Some` or `Ok` case continues to the next level and all the other
+    /// cases return, `continue` or `break` with a trivial value.
+    ///
+    /// ### Why is this bad?
+    /// Each level adds an indentation level and moves the handling of the failure cases away from
+    /// the check which causes them. A sequence of `let ... else` statements keeps the happy path
+    /// flat, with each failure case next to its check.
+    ///
+    /// ### Limitations
+    /// Only the pyramids which are the last expression of their block are linted. When a failure
+    /// case evaluates to a value instead of diverging, the pyramid must be the value of the
+    /// function, so that the value can be returned instead.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn first_even_len(v: &[Option<&str>]) -> Option<usize> {
+    ///     if let Some(first) = v.first() {
+    ///         match first {
+    ///             Some(s) => {
+    ///                 let len = s.len();
+    ///                 (len % 2 == 0).then_some(len)
+    ///             },
+    ///             None => None,
+    ///         }
+    ///     } else {
+    ///         None
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// fn first_even_len(v: &[Option<&str>]) -> Option<usize> {
+    ///     let Some(first) = v.first() else { return None };
+    ///     let Some(s) = first else { return None };
+    ///     let len = s.len();
+    ///     (len % 2 == 0).then_some(len)
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub NESTED_MATCH_PYRAMID,
+    pedantic,
+    "nested matches on `Option` or `Result` which only continue on the happy path"
+}
+
+pub struct NestedMatchPyramid {
+    msrv: Msrv,
+    /// The inner levels of the pyramids which were already linted
+    linted: FxHashSet<HirId>,
+}
+
+impl NestedMatchPyramid {
+    #[must_use]
+    pub fn new(msrv: Msrv) -> Self {
+        Self {
+            msrv,
+            linted: FxHashSet::default(),
+        }
+    }
+}
+
+impl_lint_pass!(NestedMatchPyramid => [NESTED_MATCH_PYRAMID]);
+
+impl<'tcx> LateLintPass<'tcx> for NestedMatchPyramid {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if self.linted.contains(&expr.hir_id)
+            || !self.msrv.meets(msrvs::LET_ELSE)
+            || in_external_macro(cx.sess(), expr.span)
+            || is_else_clause(cx.tcx, expr)
+        {
+            return;
+        }
+        let Some(end) = pyramid_end(cx, expr) else {
+            return;
+        };
+        let Some(first) = Level::new(cx, expr, end) else {
+            return;
+        };
+        let mut levels = vec![first];
+        while let Some(next) = levels.last().and_then(|level| next_level(cx, level.happy)) {
+            levels.push(next);
+        }
+        if levels.len() < 2 {
+            return;
+        }
+        // The trivial values can only be returned if the pyramid is the value of the function.
+        if levels.iter().any(|level| !level.diverges) && !is_fn_value(cx, expr) {
+            return;
+        }
+        self.linted.extend(levels.iter().map(|level| level.expr.hir_id));
+
+        let headers: Vec<Span> = levels
+            .iter()
+            .map(|level| level.expr.span.with_hi(level.scrutinee.span.hi()))
+            .collect();
+        span_lint_and_then(
+            cx,
+            NESTED_MATCH_PYRAMID,
+            MultiSpan::from_spans(headers),
+            "these nested matches only continue on the happy path",
+            |diag| {
+                let mut app = Applicability::MaybeIncorrect;
+                let mut suggestions = Vec::new();
+                for level in &levels {
+                    let pat = snippet_with_applicability(cx, level.pat.span, "..", &mut app);
+                    let scrutinee = snippet_with_applicability(cx, level.scrutinee.span, "..", &mut app);
+                    let other = snippet_with_applicability(cx, level.other.span, "..", &mut app);
+                    let other = if level.diverges {
+                        other.into_owned()
+                    } else {
+                        format!("return {other}")
+                    };
+                    // The braces of the happy path are removed with the rest of the level.
+                    let is_block = matches!(level.happy.kind, ExprKind::Block(..));
+                    let brace = BytePos(u32::from(is_block));
+                    let sep = if is_block { "" } else { " " };
+                    let open = level.expr.span.with_hi(level.happy.span.lo() + brace);
+                    suggestions.push((open, format!("let {pat} = {scrutinee} else {{ {other} }};{sep}")));
+                    let close = level.end.with_lo(level.happy.span.hi() - brace);
+                    suggestions.push((close, String::new()));
+                }
+                diag.multipart_suggestion("flatten them with `let ... else`", suggestions, app);
+            },
+        );
+    }
+
+    extract_msrv_attr!(LateContext);
+}
+
+/// A `match` or `if let` on an `Option` or a `Result` which only continues in its `Some` or `Ok`
+/// case
+struct Level<'tcx> {
+    expr: &'tcx Expr<'tcx>,
+    /// The end of the level, including the `;` of its statement
+    end: Span,
+    /// The `Some(..)` or `Ok(..)` pattern
+    pat: &'tcx Pat<'tcx>,
+    scrutinee: &'tcx Expr<'tcx>,
+    /// The `Some` or `Ok` case
+    happy: &'tcx Expr<'tcx>,
+    /// The value of the other cases
+    other: &'tcx Expr<'tcx>,
+    /// Whether `other` returns, `continue`s or `break`s rather than being a value
+    diverges: bool,
+}
+
+impl<'tcx> Level<'tcx> {
+    fn new(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>, end: Span) -> Option<Self> {
+        if expr.span.from_expansion() {
+            return None;
+        }
+        let (pat, scrutinee, happy, other) = if let Some(higher::IfLet {
+            let_pat,
+            let_expr,
+            if_then,
+            if_else: Some(if_else),
+            ..
+        }) = higher::IfLet::hir(cx, expr)
+        {
+            (let_pat, let_expr, if_then, if_else)
+        } else if let ExprKind::Match(scrutinee, [first, second], MatchSource::Normal) = expr.kind
+            && first.guard.is_none()
+            && second.guard.is_none()
+        {
+            let (happy, other) = if is_unhappy_pat(cx, second.pat) {
+                (first, second)
+            } else if is_unhappy_pat(cx, first.pat) {
+                (second, first)
+            } else {
+                return None;
+            };
+            let Arm { pat, body, .. } = happy;
+            (*pat, scrutinee, *body, other.body)
+        } else {
+            return None;
+        };
+        if is_happy_pat(cx, pat)
+            && !matches!(happy.kind, ExprKind::Block(_, Some(_)))
+            && !happy.span.from_expansion()
+            && let other = peel_other(other)
+            && let Some(diverges) = trivial_kind(cx, other)
+        {
+            Some(Self {
+                expr,
+                end,
+                pat,
+                scrutinee,
+                happy,
+                other,
+                diverges,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the next level of the pyramid, which must be `happy` or the last expression of its
+/// block
+fn next_level<'tcx>(cx: &LateContext<'tcx>, happy: &'tcx Expr<'tcx>) -> Option<Level<'tcx>> {
+    let ExprKind::Block(happy, None) = happy.kind else {
+        return Level::new(cx, happy, happy.span);
+    };
+    match (happy.expr, happy.stmts.last()) {
+        (Some(expr), _) => Level::new(cx, expr, expr.span),
+        (None, Some(stmt)) if let StmtKind::Expr(expr) | StmtKind::Semi(expr) = stmt.kind => {
+            if cx.typeck_results().expr_ty(expr).is_unit() {
+                Level::new(cx, expr, stmt.span)
+            } else {
+                None
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Returns the end of the pyramid `expr` if it's the last expression of its block, including the
+/// `;` of its statement
+fn pyramid_end(cx: &LateContext<'_>, expr: &Expr<'_>) -> Option<Span> {
+    match cx.tcx.parent_hir_node(expr.hir_id) {
+        Node::Block(block) if block.expr.is_some_and(|e| e.hir_id == expr.hir_id) => Some(expr.span),
+        Node::Stmt(stmt)
+            if let Node::Block(block) = cx.tcx.parent_hir_node(stmt.hir_id)
+                && block.expr.is_none()
+                && block.stmts.last().is_some_and(|last| last.hir_id == stmt.hir_id)
+                && cx.typeck_results().expr_ty(expr).is_unit() =>
+        {
+            Some(stmt.span)
+        },
+        _ => None,
+    }
+}
+
+/// Checks if `expr` is the value of the body of a function
+fn is_fn_value(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    if let Some(body_id) = cx.enclosing_body
+        && cx
+            .tcx
+            .hir()
+            .body_owner_kind(cx.tcx.hir().body_owner_def_id(body_id))
+            .is_fn_or_closure()
+        && let ExprKind::Block(block, _) = cx.tcx.hir().body(body_id).value.kind
+    {
+        block.expr.is_some_and(|e| e.hir_id == expr.hir_id)
+    } else {
+        false
+    }
+}
+
+/// Removes the blocks around the value of a failure case, and the ones around a single diverging
+/// statement, e.g. `{ return; }`
+fn peel_other<'tcx>(expr: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    match expr.kind {
+        ExprKind::Block(block, None) if !expr.span.from_expansion() => match (block.stmts, block.expr) {
+            ([], Some(inner)) => peel_other(inner),
+            ([stmt], None)
+                if let StmtKind::Expr(inner) | StmtKind::Semi(inner) = stmt.kind
+                    && matches!(
+                        inner.kind,
+                        ExprKind::Ret(_) | ExprKind::Break(..) | ExprKind::Continue(_)
+                    ) =>
+            {
+                inner
+            },
+            _ => expr,
+        },
+        _ => expr,
+    }
+}
+
+/// `Some(..)` or `Ok(..)`
+fn is_happy_pat(cx: &LateContext<'_>, pat: &Pat<'_>) -> bool {
+    if let PatKind::TupleStruct(ref qpath, [_], _) = pat.kind {
+        let res = cx.qpath_res(qpath, pat.hir_id);
+        is_res_lang_ctor(cx, res, OptionSome) || is_res_lang_ctor(cx, res, ResultOk)
+    } else {
+        false
+    }
+}
+
+/// `None`, `Err(_)` or `_`
+fn is_unhappy_pat(cx: &LateContext<'_>, pat: &Pat<'_>) -> bool {
+    match pat.kind {
+        PatKind::Wild => true,
+        PatKind::Path(ref qpath) => is_res_lang_ctor(cx, cx.qpath_res(qpath, pat.hir_id), OptionNone),
+        PatKind::TupleStruct(
+            ref qpath,
+            [
+                Pat {
+                    kind: PatKind::Wild, ..
+                },
+            ],
+            _,
+        ) => is_res_lang_ctor(cx, cx.qpath_res(qpath, pat.hir_id), ResultErr),
+        _ => false,
+    }
+}
+
+/// Returns whether `expr` diverges if it's a trivial value, or a `return`, `continue` or `break`
+/// of one.
+fn trivial_kind(cx: &LateContext<'_>, expr: &Expr<'_>) -> Option<bool> {
+    match expr.kind {
+        ExprKind::Ret(value) | ExprKind::Break(_, value) if !expr.span.from_expansion() => {
+            value.map_or(true, |value| is_trivial(cx, value)).then_some(true)
+        },
+        ExprKind::Continue(_) if !expr.span.from_expansion() => Some(true),
+        _ => is_trivial(cx, expr).then_some(false),
+    }
+}
+
+/// Literals, paths, `()`, calls without arguments like `Vec::new()`, and `Some`, `Ok` or `Err` of
+/// a trivial value
+fn is_trivial(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    if expr.span.from_expansion() {
+        return false;
+    }
+    match expr.kind {
+        ExprKind::Lit(_) | ExprKind::Path(_) | ExprKind::Tup([]) => true,
+        ExprKind::Call(callee, []) => matches!(callee.kind, ExprKind::Path(_)),
+        ExprKind::Call(callee, [arg]) => {
+            let res = path_res(cx, callee);
+            (is_res_lang_ctor(cx, res, OptionSome)
+                || is_res_lang_ctor(cx, res, ResultOk)
+                || is_res_lang_ctor(cx, res, ResultErr))
+                && is_trivial(cx, arg)
+        },
+        _ => false,
+    }
+}
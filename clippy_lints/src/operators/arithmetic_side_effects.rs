@@ -1,9 +1,13 @@
 use super::ARITHMETIC_SIDE_EFFECTS;
+use clippy_config::types::{AllowedOperators, ArithmeticOperator};
 use clippy_utils::consts::{constant, constant_simple, Constant};
 use clippy_utils::diagnostics::span_lint;
 use clippy_utils::ty::is_type_diagnostic_item;
-use clippy_utils::{expr_or_init, is_from_proc_macro, is_lint_allowed, peel_hir_expr_refs, peel_hir_expr_unary};
+use clippy_utils::{
+    def_path_def_ids, expr_or_init, is_from_proc_macro, is_lint_allowed, peel_hir_expr_refs, peel_hir_expr_unary,
+};
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def_id::DefId;
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::{self, Ty};
 use rustc_session::impl_lint_pass;
@@ -23,18 +27,26 @@ const DISALLOWED_INT_METHODS: &[Symbol] = &[
 #[derive(Debug)]
 pub struct ArithmeticSideEffects {
     allowed_binary: FxHashMap<String, FxHashSet<String>>,
+    allowed_operators: Vec<AllowedOperators>,
     allowed_unary: FxHashSet<String>,
     // Used to check whether expressions are constants, such as in enum discriminants and consts
     const_span: Option<Span>,
     disallowed_int_methods: FxHashSet<Symbol>,
     expr_span: Option<Span>,
+    wrapper_types: Vec<String>,
+    wrapper_def_ids: FxHashSet<DefId>,
 }
 
 impl_lint_pass!(ArithmeticSideEffects => [ARITHMETIC_SIDE_EFFECTS]);
 
 impl ArithmeticSideEffects {
     #[must_use]
-    pub fn new(user_allowed_binary: Vec<[String; 2]>, user_allowed_unary: Vec<String>) -> Self {
+    pub fn new(
+        user_allowed_binary: Vec<[String; 2]>,
+        user_allowed_unary: Vec<String>,
+        allowed_operators: Vec<AllowedOperators>,
+        wrapper_types: Vec<String>,
+    ) -> Self {
         let mut allowed_binary: FxHashMap<String, FxHashSet<String>> = <_>::default();
         for [lhs, rhs] in user_allowed_binary.into_iter().chain(
             HARD_CODED_ALLOWED_BINARY
@@ -50,10 +62,13 @@ impl ArithmeticSideEffects {
             .collect();
         Self {
             allowed_binary,
+            allowed_operators,
             allowed_unary,
             const_span: None,
             disallowed_int_methods: DISALLOWED_INT_METHODS.iter().copied().collect(),
             expr_span: None,
+            wrapper_types,
+            wrapper_def_ids: FxHashSet::default(),
         }
     }
 
@@ -78,6 +93,40 @@ impl ArithmeticSideEffects {
         }
     }
 
+    /// Checks if the operator is allowed on the type of the operand, or on the lhs and the rhs
+    /// types for binary operations. Returns `None` if no configured operators apply to these
+    /// types, otherwise the other allowlists are overridden.
+    fn allowed_operator(&self, lhs_ty: Ty<'_>, op: ArithmeticOperator, rhs_ty: Option<Ty<'_>>) -> Option<bool> {
+        if self.allowed_operators.is_empty() {
+            return None;
+        }
+        let lhs_ty_string = lhs_ty.to_string();
+        let lhs_ty_string_elem = lhs_ty_string.split('<').next().unwrap_or_default();
+        let rhs_ty_string = rhs_ty.map(|ty| ty.to_string());
+        let rhs_ty_string_elem = rhs_ty_string
+            .as_deref()
+            .map(|ty_string| ty_string.split('<').next().unwrap_or_default());
+        let mut applicable = self
+            .allowed_operators
+            .iter()
+            .filter(|allowed| {
+                allowed.ty == lhs_ty_string_elem
+                    && match (allowed.rhs.as_deref(), rhs_ty_string_elem) {
+                        (None | Some("*"), _) | (_, None) => true,
+                        (Some(rhs), Some(rhs_ty_string_elem)) => rhs == rhs_ty_string_elem,
+                    }
+            })
+            .peekable();
+        applicable.peek()?;
+        Some(applicable.any(|allowed| allowed.operators.contains(&op)))
+    }
+
+    /// Checks if the type is one of the configured wrapper types, whose operations can't have side
+    /// effects.
+    fn is_wrapper_type(&self, ty: Ty<'_>) -> bool {
+        matches!(ty.kind(), ty::Adt(adt, _) if self.wrapper_def_ids.contains(&adt.did()))
+    }
+
     /// Checks if the type of an unary operation like "negation" is present in the inner set of
     /// allowed types.
     fn has_allowed_unary(&self, ty: Ty<'_>) -> bool {
@@ -218,8 +267,17 @@ impl ArithmeticSideEffects {
         actual_rhs = expr_or_init(cx, actual_rhs);
         let lhs_ty = cx.typeck_results().expr_ty(actual_lhs).peel_refs();
         let rhs_ty = cx.typeck_results().expr_ty(actual_rhs).peel_refs();
-        if self.has_allowed_binary(lhs_ty, rhs_ty) {
-            return;
+        match arithmetic_operator(op).and_then(|operator| self.allowed_operator(lhs_ty, operator, Some(rhs_ty))) {
+            Some(true) => return,
+            Some(false) => {},
+            None => {
+                if self.has_allowed_binary(lhs_ty, rhs_ty)
+                    || self.is_wrapper_type(lhs_ty)
+                    || self.is_wrapper_type(rhs_ty)
+                {
+                    return;
+                }
+            },
         }
         if Self::has_specific_allowed_type_and_operation(cx, lhs_ty, op, rhs_ty) {
             return;
@@ -309,7 +367,10 @@ impl ArithmeticSideEffects {
             return;
         }
         let ty = cx.typeck_results().expr_ty(expr).peel_refs();
-        if self.has_allowed_unary(ty) {
+        let allowed = self
+            .allowed_operator(ty, ArithmeticOperator::Neg, None)
+            .unwrap_or_else(|| self.has_allowed_unary(ty) || self.is_wrapper_type(ty));
+        if allowed {
             return;
         }
         let actual_un_expr = peel_hir_expr_refs(un_expr).0;
@@ -326,7 +387,27 @@ impl ArithmeticSideEffects {
     }
 }
 
+fn arithmetic_operator(op: hir::BinOpKind) -> Option<ArithmeticOperator> {
+    match op {
+        hir::BinOpKind::Add => Some(ArithmeticOperator::Add),
+        hir::BinOpKind::Sub => Some(ArithmeticOperator::Sub),
+        hir::BinOpKind::Mul => Some(ArithmeticOperator::Mul),
+        hir::BinOpKind::Div => Some(ArithmeticOperator::Div),
+        hir::BinOpKind::Rem => Some(ArithmeticOperator::Rem),
+        hir::BinOpKind::Shl => Some(ArithmeticOperator::Shl),
+        hir::BinOpKind::Shr => Some(ArithmeticOperator::Shr),
+        _ => None,
+    }
+}
+
 impl<'tcx> LateLintPass<'tcx> for ArithmeticSideEffects {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for path in &self.wrapper_types {
+            let segs: Vec<_> = path.split("::").collect();
+            self.wrapper_def_ids.extend(def_path_def_ids(cx, &segs));
+        }
+    }
+
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'tcx>) {
         if self.should_skip_expr(cx, expr) {
             return;
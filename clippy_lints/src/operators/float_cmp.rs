@@ -1,13 +1,14 @@
 use clippy_utils::consts::{constant_with_source, Constant};
 use clippy_utils::diagnostics::span_lint_and_then;
-use clippy_utils::get_item_name;
+use clippy_utils::macros::{find_assert_eq_args, first_node_macro_backtrace, PanicExpn};
 use clippy_utils::sugg::Sugg;
+use clippy_utils::{get_item_name, is_in_test_context, is_lint_allowed};
 use rustc_errors::Applicability;
-use rustc_hir::{BinOpKind, Expr, ExprKind, UnOp};
+use rustc_hir::{BinOpKind, Expr, ExprKind, Node, UnOp};
 use rustc_lint::LateContext;
 use rustc_middle::ty;
 
-use super::{FLOAT_CMP, FLOAT_CMP_CONST};
+use super::{FLOAT_CMP, FLOAT_CMP_CONST, FLOAT_CMP_IN_GUARD_OR_ASSERT};
 
 pub(crate) fn check<'tcx>(
     cx: &LateContext<'tcx>,
@@ -40,7 +41,15 @@ pub(crate) fn check<'tcx>(
             }
         }
         let is_comparing_arrays = is_array(cx, left) || is_array(cx, right);
-        let (lint, msg) = get_lint_and_message(left_is_local && right_is_local, is_comparing_arrays);
+        let in_guard = is_in_guard(cx, expr) && !is_lint_allowed(cx, FLOAT_CMP_IN_GUARD_OR_ASSERT, expr.hir_id);
+        let (lint, msg) = if in_guard {
+            (
+                FLOAT_CMP_IN_GUARD_OR_ASSERT,
+                "strict comparison of `f32` or `f64` in a `match` guard",
+            )
+        } else {
+            get_lint_and_message(left_is_local && right_is_local, is_comparing_arrays)
+        };
         span_lint_and_then(cx, lint, expr.span, msg, |diag| {
             let lhs = Sugg::hir(cx, left, "..");
             let rhs = Sugg::hir(cx, right, "..");
@@ -58,10 +67,83 @@ pub(crate) fn check<'tcx>(
                 );
             }
             diag.note("`f32::EPSILON` and `f64::EPSILON` are available for the `error_margin`");
+            if in_guard && !is_comparing_arrays {
+                diag.help("if the exact value is intended, compare with `total_cmp` to make it explicit");
+            }
         });
     }
 }
 
+/// Checks for `assert_eq!`-like macro calls on floats outside of test code.
+pub(crate) fn check_assert<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) {
+    if let Some((macro_call, macro_name)) = first_node_macro_backtrace(cx, e).find_map(|macro_call| {
+        let name = cx.tcx.item_name(macro_call.def_id);
+        matches!(
+            name.as_str(),
+            "assert_eq" | "assert_ne" | "debug_assert_eq" | "debug_assert_ne"
+        )
+        .then_some((macro_call, name))
+    }) && let Some((lhs, rhs, panic_expn)) = find_assert_eq_args(cx, e, macro_call.expn)
+        && !macro_call.span.from_expansion()
+        && is_float(cx, lhs)
+        && !is_constant_allowed(cx, lhs)
+        && !is_constant_allowed(cx, rhs)
+        && !(is_signum(cx, lhs) && is_signum(cx, rhs))
+        && !is_in_test_context(cx, e.hir_id)
+    {
+        let is_comparing_arrays = is_array(cx, lhs) || is_array(cx, rhs);
+        span_lint_and_then(
+            cx,
+            FLOAT_CMP_IN_GUARD_OR_ASSERT,
+            macro_call.span,
+            format!("strict comparison of `f32` or `f64` in `{macro_name}!`"),
+            |diag| {
+                if !is_comparing_arrays && matches!(panic_expn, PanicExpn::Empty) {
+                    let mut app = Applicability::HasPlaceholders;
+                    let lhs = Sugg::hir_with_context(cx, lhs, macro_call.span.ctxt(), "..", &mut app);
+                    let rhs = Sugg::hir_with_context(cx, rhs, macro_call.span.ctxt(), "..", &mut app);
+                    let (assert, cmp) = match macro_name.as_str() {
+                        "assert_eq" => ("assert", '<'),
+                        "assert_ne" => ("assert", '>'),
+                        "debug_assert_eq" => ("debug_assert", '<'),
+                        _ => ("debug_assert", '>'),
+                    };
+                    diag.span_suggestion(
+                        macro_call.span,
+                        "consider comparing them within some margin of error",
+                        format!("{assert}!(({}).abs() {cmp} error_margin)", lhs - rhs),
+                        app,
+                    );
+                }
+                diag.note("`f32::EPSILON` and `f64::EPSILON` are available for the `error_margin`");
+            },
+        );
+    }
+}
+
+/// Checks if `expr` is the guard of a `match` arm, or one of the operands of `&&`, `||` or `!`
+/// making up the guard.
+fn is_in_guard(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    let mut child_id = expr.hir_id;
+    for (parent_id, node) in cx.tcx.hir().parent_iter(expr.hir_id) {
+        match node {
+            Node::Expr(parent) => match parent.kind {
+                ExprKind::Binary(op, ..) if matches!(op.node, BinOpKind::And | BinOpKind::Or) => {},
+                ExprKind::Unary(UnOp::Not, _) | ExprKind::DropTemps(_) => {},
+                _ => return false,
+            },
+            Node::Arm(arm) => return arm.guard.is_some_and(|guard| guard.hir_id == child_id),
+            _ => return false,
+        }
+        child_id = parent_id;
+    }
+    false
+}
+
+fn is_constant_allowed(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    constant_with_source(cx, cx.typeck_results(), expr).is_some_and(|(c, _)| is_allowed(&c))
+}
+
 fn get_lint_and_message(is_local: bool, is_comparing_arrays: bool) -> (&'static rustc_lint::Lint, &'static str) {
     if is_local {
         (
@@ -19,6 +19,7 @@ mod numeric_arithmetic;
 mod op_ref;
 mod ptr_eq;
 mod self_assignment;
+mod shift_amount_overflow;
 mod verbose_bit_mask;
 
 pub(crate) mod arithmetic_side_effects;
@@ -625,6 +626,49 @@ declare_clippy_lint! {
     "using `==` or `!=` on float constants instead of comparing difference with an epsilon"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for (in-)equality comparisons of floating-point values in `match` guards, and for
+    /// `assert_eq!`, `assert_ne!`, `debug_assert_eq!` and `debug_assert_ne!` on floating-point
+    /// operands outside of test code.
+    ///
+    /// Comparisons with zero or an infinity are allowed, as for [`float_cmp`].
+    ///
+    /// ### Why is this bad?
+    /// A guard like `x if x == 0.1` reads as a literal pattern, but only matches the exact value,
+    /// which a computed float rarely is. The same goes for an assertion of the exact value of a
+    /// computed float, which panics in production on a rounding difference.
+    ///
+    /// [`float_cmp`]: https://rust-lang.github.io/rust-clippy/master/index.html#float_cmp
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let ratio = 0.1_f64;
+    /// # let computed = 0.3_f64;
+    /// match ratio {
+    ///     x if x == 0.1 => {},
+    ///     _ => {},
+    /// }
+    /// assert_eq!(computed, 0.3);
+    /// ```
+    ///
+    /// Use instead:
+    /// ```no_run
+    /// # let ratio = 0.1_f64;
+    /// # let computed = 0.3_f64;
+    /// let error_margin = f64::EPSILON;
+    /// match ratio {
+    ///     x if (x - 0.1).abs() < error_margin => {},
+    ///     _ => {},
+    /// }
+    /// assert!((computed - 0.3).abs() < error_margin);
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub FLOAT_CMP_IN_GUARD_OR_ASSERT,
+    pedantic,
+    "using `==` or `!=` on floats in a `match` guard, or `assert_eq!` on floats outside of tests"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for getting the remainder of a division by one or minus
@@ -768,6 +812,38 @@ declare_clippy_lint! {
     "explicit self-assignment"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for shifts of integers by an amount which isn't constant, but is known to range up
+    /// to the bit width of the shifted type or more, e.g. because it's computed from a number of
+    /// bytes times 8.
+    ///
+    /// Amounts whose range is only bounded by their type aren't linted.
+    ///
+    /// ### Why is this bad?
+    /// Shifting by the bit width or more panics in debug builds, and masks the amount in release
+    /// builds, so `x << 64` is `x` for a `u64`.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn low_bytes(x: u64, bytes: u8) -> u64 {
+    ///     let bits = u32::from(bytes) * 8;
+    ///     x & ((1 << bits) - 1)
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// fn low_bytes(x: u64, bytes: u8) -> u64 {
+    ///     let bits = u32::from(bytes) * 8;
+    ///     x & 1_u64.checked_shl(bits).map_or(u64::MAX, |bit| bit - 1)
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub SHIFT_AMOUNT_OVERFLOW,
+    suspicious,
+    "shifting by an amount which can reach the bit width of the shifted type"
+}
+
 pub struct Operators {
     arithmetic_context: numeric_arithmetic::Context,
     verbose_bit_mask_threshold: u64,
@@ -795,11 +871,13 @@ impl_lint_pass!(Operators => [
     CMP_OWNED,
     FLOAT_CMP,
     FLOAT_CMP_CONST,
+    FLOAT_CMP_IN_GUARD_OR_ASSERT,
     MODULO_ONE,
     MODULO_ARITHMETIC,
     NEEDLESS_BITWISE_BOOL,
     PTR_EQ,
     SELF_ASSIGNMENT,
+    SHIFT_AMOUNT_OVERFLOW,
 ]);
 impl Operators {
     pub fn new(verbose_bit_mask_threshold: u64, modulo_arithmetic_allow_comparison_to_zero: bool) -> Self {
@@ -813,6 +891,7 @@ impl Operators {
 impl<'tcx> LateLintPass<'tcx> for Operators {
     fn check_expr(&mut self, cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) {
         eq_op::check_assert(cx, e);
+        float_cmp::check_assert(cx, e);
         match e.kind {
             ExprKind::Binary(op, lhs, rhs) => {
                 if !e.span.from_expansion() {
@@ -825,6 +904,7 @@ impl<'tcx> LateLintPass<'tcx> for Operators {
                     identity_op::check(cx, e, op.node, lhs, rhs);
                     needless_bitwise_bool::check(cx, e, op.node, lhs, rhs);
                     ptr_eq::check(cx, e, op.node, lhs, rhs);
+                    shift_amount_overflow::check(cx, e, op.node, lhs, rhs);
                 }
                 self.arithmetic_context.check_binary(cx, e, op.node, lhs, rhs);
                 bit_mask::check(cx, e, op.node, lhs, rhs);
@@ -849,6 +929,9 @@ impl<'tcx> LateLintPass<'tcx> for Operators {
             ExprKind::AssignOp(op, lhs, rhs) => {
                 self.arithmetic_context.check_binary(cx, e, op.node, lhs, rhs);
                 misrefactored_assign_op::check(cx, e, op.node, lhs, rhs);
+                if !e.span.from_expansion() {
+                    shift_amount_overflow::check(cx, e, op.node, lhs, rhs);
+                }
                 modulo_arithmetic::check(cx, e, op.node, lhs, rhs, false);
             },
             ExprKind::Assign(lhs, rhs, _) => {
@@ -0,0 +1,63 @@
+use clippy_utils::consts::constant;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::intervals::{expr_interval, Interval};
+use rustc_hir::{BinOpKind, Expr};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+
+use super::SHIFT_AMOUNT_OVERFLOW;
+
+pub(crate) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    left: &'tcx Expr<'_>,
+    right: &'tcx Expr<'tcx>,
+) {
+    let method = match op {
+        BinOpKind::Shl => "checked_shl",
+        BinOpKind::Shr => "checked_shr",
+        _ => return,
+    };
+    let typeck = cx.typeck_results();
+    let left_ty = typeck.expr_ty(left);
+    let bits = match *left_ty.kind() {
+        ty::Int(ity) => ity.bit_width().unwrap_or(cx.tcx.data_layout.pointer_size.bits()),
+        ty::Uint(uty) => uty.bit_width().unwrap_or(cx.tcx.data_layout.pointer_size.bits()),
+        _ => return,
+    };
+    // Constant amounts are checked by rustc's `arithmetic_overflow` lint.
+    if constant(cx, typeck, right).is_some() {
+        return;
+    }
+    let Some(amount) = expr_interval(cx, right) else {
+        return;
+    };
+    if amount.hi < i128::from(bits) || !is_bounded(cx, right, amount) {
+        return;
+    }
+
+    span_lint_and_then(
+        cx,
+        SHIFT_AMOUNT_OVERFLOW,
+        expr.span,
+        format!("this shift amount can reach the bit width of `{left_ty}`"),
+        |diag| {
+            diag.note(format!("the amount can be up to {}", amount.hi));
+            diag.note(format!(
+                "shifting a `{left_ty}` by {bits} or more panics in debug builds"
+            ));
+            diag.help(format!(
+                "use `{method}` and handle the overflow, or mask the amount with `& {}`",
+                bits - 1
+            ));
+        },
+    );
+}
+
+/// Checks if the interval of the shift amount is bounded by the code rather than by its type.
+/// Bounds close to the maximum of the type come from another value of the type, e.g. the end of a
+/// `0..n` range, which isn't any more precise.
+fn is_bounded(cx: &LateContext<'_>, amount: &Expr<'_>, interval: Interval) -> bool {
+    Interval::of_ty(cx.tcx, cx.typeck_results().expr_ty(amount)).is_some_and(|ty| interval.hi <= ty.hi / 2)
+}
@@ -0,0 +1,108 @@
+use clippy_utils::cargo::target_kind;
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::is_in_test_context;
+use clippy_utils::panics::{panic_sites, PanicConfig, PanicKind};
+use rustc_hir::{Body, BodyOwnerKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::impl_lint_pass;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// When `forbid-panics-in-production` is enabled, checks for the code of libraries, binaries
+    /// and procedural macros which can panic: calls to `unwrap` and `expect` on an `Option` or a
+    /// `Result`, `panic!`, `todo!`, `unimplemented!`, and indexing or slicing, except of an array
+    /// by a constant in its bounds.
+    ///
+    /// Crates built with the test harness, benchmarks, examples and build scripts aren't linted,
+    /// nor are doctests and the code marked with one of the `test-attributes`.
+    ///
+    /// ### Why is this bad?
+    /// Some services can't afford to crash on an unexpected input. This lint enforces that policy
+    /// with a single switch, instead of having to enable `unwrap_used`, `expect_used`, `panic`,
+    /// `todo`, `unimplemented` and `indexing_slicing`, and to configure each of them for tests.
+    ///
+    /// ### Example
+    /// ```toml
+    /// forbid-panics-in-production = true
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// fn first_port(ports: &[u16]) -> u16 {
+    ///     ports[0]
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// fn first_port(ports: &[u16]) -> Option<u16> {
+    ///     ports.first().copied()
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub PANIC_IN_PRODUCTION,
+    style,
+    "code which can panic when `forbid-panics-in-production` is enabled"
+}
+
+impl_lint_pass!(PanicInProduction => [PANIC_IN_PRODUCTION]);
+
+/// `unreachable!` isn't linted.
+const PANICS: PanicConfig = PanicConfig::new(
+    &[
+        PanicKind::Explicit,
+        PanicKind::Todo,
+        PanicKind::Unimplemented,
+        PanicKind::Unwrap,
+        PanicKind::Indexing,
+    ],
+    0,
+);
+
+pub struct PanicInProduction {
+    enabled: bool,
+}
+
+impl PanicInProduction {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for PanicInProduction {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        self.enabled &= target_kind(cx).is_production();
+    }
+
+    fn check_body(&mut self, cx: &LateContext<'tcx>, body: &'tcx Body<'tcx>) {
+        let owner = cx.tcx.hir().body_owner_def_id(body.id());
+        // The bodies of closures are visited with the function they are in, and panics in
+        // constants fail the compilation.
+        if !self.enabled
+            || !matches!(cx.tcx.hir().body_owner_kind(owner), BodyOwnerKind::Fn)
+            || is_in_test_context(cx, cx.tcx.local_def_id_to_hir_id(owner))
+        {
+            return;
+        }
+        let typeck = cx.tcx.typeck_body(body.id());
+        for site in panic_sites(cx, typeck, body.value, PANICS) {
+            if in_external_macro(cx.sess(), site.span) {
+                continue;
+            }
+            let (msg, help) = match site.kind {
+                PanicKind::Unwrap => (
+                    "unwrapping in production code",
+                    "handle the `None` or `Err` case instead, e.g. by returning it with `?`",
+                ),
+                PanicKind::Indexing => (
+                    "indexing or slicing in production code",
+                    "use `get` and handle the `None` case instead",
+                ),
+                PanicKind::Todo => ("`todo!` in production code", "return an error instead"),
+                PanicKind::Unimplemented => ("`unimplemented!` in production code", "return an error instead"),
+                _ => ("`panic!` in production code", "return an error instead"),
+            };
+            span_lint_and_help(cx, PANIC_IN_PRODUCTION, site.span, msg, None, help);
+        }
+    }
+}
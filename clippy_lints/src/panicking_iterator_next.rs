@@ -0,0 +1,131 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::is_res_lang_ctor;
+use clippy_utils::panics::{find_panic, PanicConfig, PanicKind};
+use clippy_utils::visitors::for_each_expr;
+use core::ops::ControlFlow;
+use rustc_hir::LangItem::OptionSome;
+use rustc_hir::{BinOpKind, Expr, ExprKind, ImplItem, ImplItemKind, Node};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::TypeckResults;
+use rustc_session::declare_lint_pass;
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for implementations of `Iterator::next` which never return `None`, and which can
+    /// panic, e.g. by indexing past the end of a slice or unwrapping an empty `Option`.
+    ///
+    /// ### Why is this bad?
+    /// An iterator signals that it's exhausted by returning `None`. `for` loops, `collect` and
+    /// the other adapters call `next` until then, so an iterator panicking instead of returning
+    /// `None` panics in all of them.
+    ///
+    /// ### Known problems
+    /// Infinite iterators never return `None` either. The ones cycling over a buffer, i.e.
+    /// wrapping their position around with `%` or `rem_euclid`, are not linted, but the others
+    /// are if they can panic for another reason.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// struct Items<'a> {
+    ///     items: &'a [u32],
+    ///     pos: usize,
+    /// }
+    ///
+    /// impl Iterator for Items<'_> {
+    ///     type Item = u32;
+    ///
+    ///     fn next(&mut self) -> Option<u32> {
+    ///         let item = self.items[self.pos];
+    ///         self.pos += 1;
+    ///         Some(item)
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # struct Items<'a> {
+    /// #     items: &'a [u32],
+    /// #     pos: usize,
+    /// # }
+    /// impl Iterator for Items<'_> {
+    ///     type Item = u32;
+    ///
+    ///     fn next(&mut self) -> Option<u32> {
+    ///         let item = *self.items.get(self.pos)?;
+    ///         self.pos += 1;
+    ///         Some(item)
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub PANICKING_ITERATOR_NEXT,
+    suspicious,
+    "`Iterator::next` implementation panicking instead of returning `None`"
+}
+
+declare_lint_pass!(PanickingIteratorNext => [PANICKING_ITERATOR_NEXT]);
+
+/// `todo!` and `unimplemented!` are left out, they are placeholders for the whole method.
+const PANICS: PanicConfig = PanicConfig::new(&[PanicKind::Explicit, PanicKind::Unwrap, PanicKind::Indexing], 1);
+
+impl<'tcx> LateLintPass<'tcx> for PanickingIteratorNext {
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx ImplItem<'tcx>) {
+        if let ImplItemKind::Fn(sig, body_id) = item.kind
+            && item.ident.name == sym::next
+            && !item.span.from_expansion()
+            && let Node::Item(parent) = cx.tcx.parent_hir_node(item.hir_id())
+            && let Some(trait_id) = cx.tcx.trait_id_of_impl(parent.owner_id.to_def_id())
+            && cx.tcx.is_diagnostic_item(sym::Iterator, trait_id)
+            && let typeck = cx.tcx.typeck(item.owner_id.def_id)
+            && let body = cx.tcx.hir().body(body_id)
+            && always_some(cx, typeck, body.value)
+            && for_each_expr(body.value, |e| match e.kind {
+                ExprKind::Ret(Some(value)) if !always_some(cx, typeck, value) => ControlFlow::Break(()),
+                _ => ControlFlow::Continue(()),
+            })
+            .is_none()
+            && !wraps_around(body.value)
+            && let Some(panic) = find_panic(cx, typeck, body.value, PANICS)
+        {
+            span_lint_and_then(
+                cx,
+                PANICKING_ITERATOR_NEXT,
+                sig.span,
+                "this `next` implementation never returns `None`, but can panic",
+                |diag| {
+                    diag.span_note(panic.first_span(), "the panic is here");
+                    diag.help("return `None` once the iterator is exhausted");
+                },
+            );
+        }
+    }
+}
+
+/// Checks if `e` computes a remainder, as cycling iterators do to wrap their position around the
+/// buffer they repeat.
+fn wraps_around(e: &Expr<'_>) -> bool {
+    for_each_expr(e, |e| match e.kind {
+        ExprKind::Binary(op, ..) | ExprKind::AssignOp(op, ..) if op.node == BinOpKind::Rem => ControlFlow::Break(()),
+        ExprKind::MethodCall(method, ..) if method.ident.name.as_str() == "rem_euclid" => ControlFlow::Break(()),
+        _ => ControlFlow::Continue(()),
+    })
+    .is_some()
+}
+
+/// Checks if the value of `e` is always `Some(..)`, when it doesn't diverge.
+fn always_some<'tcx>(cx: &LateContext<'tcx>, typeck: &TypeckResults<'tcx>, e: &'tcx Expr<'tcx>) -> bool {
+    if typeck.expr_ty(e).is_never() {
+        return true;
+    }
+    match e.kind {
+        ExprKind::Block(block, None) => block.expr.map_or(true, |e| always_some(cx, typeck, e)),
+        ExprKind::DropTemps(e) => always_some(cx, typeck, e),
+        ExprKind::If(_, then, Some(els)) => always_some(cx, typeck, then) && always_some(cx, typeck, els),
+        ExprKind::Match(_, arms, _) => arms.iter().all(|arm| always_some(cx, typeck, arm.body)),
+        ExprKind::Call(ctor, [_]) => {
+            matches!(ctor.kind, ExprKind::Path(ref qpath) if is_res_lang_ctor(cx, typeck.qpath_res(qpath, ctor.hir_id), OptionSome))
+        },
+        _ => false,
+    }
+}
@@ -1,24 +1,29 @@
-//! Checks for usage of  `&Vec[_]` and `&String`.
+//! Checks for usage of  `&Vec[_]` and `&String`, and of `Vec<_>` and `String` only used by
+//! reference.
 
+use clippy_utils::call_graph::call_graph;
 use clippy_utils::diagnostics::{span_lint, span_lint_and_sugg, span_lint_and_then, span_lint_hir_and_then};
-use clippy_utils::source::snippet_opt;
+use clippy_utils::macros::{is_format_macro, root_macro_call};
+use clippy_utils::source::{snippet_opt, walk_span_to_context};
+use clippy_utils::sugg::Sugg;
 use clippy_utils::ty::expr_sig;
 use clippy_utils::visitors::contains_unsafe_block;
 use clippy_utils::{get_expr_use_or_unification_node, is_lint_allowed, path_def_id, path_to_local};
 use hir::LifetimeName;
 use rustc_errors::{Applicability, MultiSpan};
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::hir_id::{HirId, HirIdMap};
 use rustc_hir::intravisit::{walk_expr, Visitor};
 use rustc_hir::{
-    self as hir, AnonConst, BinOpKind, BindingMode, Body, Expr, ExprKind, FnRetTy, FnSig, GenericArg, ImplItemKind,
-    ItemKind, Lifetime, Mutability, Node, Param, PatKind, QPath, TraitFn, TraitItem, TraitItemKind, TyKind, Unsafety,
+    self as hir, AnonConst, BinOpKind, BindingMode, Body, BorrowKind, CaptureBy, Closure, Expr, ExprKind, FnDecl,
+    FnRetTy, FnSig, GenericArg, ImplItemKind, ItemKind, Lifetime, Mutability, Node, Param, PatKind, PathSegment, QPath,
+    TraitFn, TraitItem, TraitItemKind, TyKind, Unsafety,
 };
 use rustc_infer::infer::TyCtxtInferExt;
 use rustc_infer::traits::{Obligation, ObligationCause};
-use rustc_lint::{LateContext, LateLintPass};
+use rustc_lint::{LateContext, LateLintPass, Lint, LintContext};
 use rustc_middle::hir::nested_filter;
-use rustc_middle::ty::{self, Binder, ClauseKind, ExistentialPredicate, List, PredicateKind, Ty};
+use rustc_middle::ty::{self, Binder, ClauseKind, ExistentialPredicate, GenericArgsRef, List, PredicateKind, Ty};
 use rustc_session::declare_lint_pass;
 use rustc_span::symbol::Symbol;
 use rustc_span::{sym, Span};
@@ -28,6 +33,7 @@ use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt as _
 use std::{fmt, iter};
 
 use crate::vec::is_allowed_vec_method;
+use rustc_middle::lint::in_external_macro;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -151,7 +157,46 @@ declare_clippy_lint! {
     "invalid usage of a null pointer, suggesting `NonNull::dangling()` instead"
 }
 
-declare_lint_pass!(Ptr => [PTR_ARG, CMP_NULL, MUT_FROM_REF, INVALID_NULL_PTR_USAGE]);
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for function arguments of type `String`, `Vec` or `PathBuf` which are passed by
+    /// value, but only ever used through a reference to `str`, `[T]` or `Path` in the function
+    /// body. The suggestion also changes the calls to the function made in the crate.
+    ///
+    /// ### Why is this bad?
+    /// Taking ownership forces callers to give up their value, or to clone it, when a borrowed
+    /// slice would do. `&str`, `&[T]` and `&Path` can be obtained from many other types, too.
+    ///
+    /// ### Known problems
+    /// The calls made from closures, constants and other crates aren't changed, nor the uses of
+    /// the function as a value, e.g. `iter.map(foo)`.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn greet(name: String) {
+    ///     println!("hello {}", name.trim());
+    /// }
+    ///
+    /// let name = String::from("world");
+    /// greet(name.clone());
+    /// ```
+    ///
+    /// Use instead:
+    /// ```no_run
+    /// fn greet(name: &str) {
+    ///     println!("hello {}", name.trim());
+    /// }
+    ///
+    /// let name = String::from("world");
+    /// greet(&name);
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub OWNED_PARAM_ONLY_BORROWED,
+    pedantic,
+    "fn arguments of the type `String`, `Vec<...>` or `PathBuf` only used by reference"
+}
+
+declare_lint_pass!(Ptr => [PTR_ARG, CMP_NULL, MUT_FROM_REF, INVALID_NULL_PTR_USAGE, OWNED_PARAM_ONLY_BORROWED]);
 
 impl<'tcx> LateLintPass<'tcx> for Ptr {
     fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx TraitItem<'_>) {
@@ -180,7 +225,7 @@ impl<'tcx> LateLintPass<'tcx> for Ptr {
                     diag.span_suggestion(
                         arg.span,
                         "change this to",
-                        format!("{}{}", arg.ref_prefix, arg.deref_ty.display(cx)),
+                        arg.replacement_ty(cx),
                         Applicability::Unspecified,
                     );
                 });
@@ -228,24 +273,35 @@ impl<'tcx> LateLintPass<'tcx> for Ptr {
             return;
         }
 
+        let header = sig.header;
         let decl = sig.decl;
         let sig = cx.tcx.fn_sig(item_id).instantiate_identity().skip_binder();
-        let lint_args: Vec<_> = check_fn_args(cx, sig, decl.inputs, body.params)
+        let mut lint_args: Vec<_> = check_fn_args(cx, sig, decl.inputs, body.params)
             .filter(|arg| !is_trait_item || arg.mutability() == Mutability::Not)
             .collect();
+        // Changing the signature of a trait method would break its implementations.
+        if !is_trait_item && !header.asyncness.is_async() {
+            lint_args.extend(check_owned_fn_args(cx, sig, decl.inputs, body.params));
+        }
         let results = check_ptr_arg_usage(cx, body, &lint_args);
 
         for (result, args) in results.iter().zip(lint_args.iter()).filter(|(r, _)| !r.skip) {
-            span_lint_hir_and_then(cx, PTR_ARG, args.emission_id, args.span, args.build_msg(), |diag| {
+            span_lint_hir_and_then(cx, args.lint(), args.emission_id, args.span, args.build_msg(), |diag| {
+                let call_sites = if args.ref_prefix.is_none() {
+                    owned_arg_call_sites(cx, item_id.def_id, args)
+                } else {
+                    Vec::new()
+                };
                 diag.multipart_suggestion(
                     "change this to",
-                    iter::once((args.span, format!("{}{}", args.ref_prefix, args.deref_ty.display(cx))))
+                    iter::once((args.span, args.replacement_ty(cx)))
                         .chain(result.replacements.iter().map(|r| {
                             (
                                 r.expr_span,
                                 format!("{}{}", snippet_opt(cx, r.self_span).unwrap(), r.replacement),
                             )
                         }))
+                        .chain(call_sites)
                         .collect(),
                     Applicability::Unspecified,
                 );
@@ -254,6 +310,10 @@ impl<'tcx> LateLintPass<'tcx> for Ptr {
     }
 
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+
         if let ExprKind::Binary(ref op, l, r) = expr.kind {
             if (op.node == BinOpKind::Eq || op.node == BinOpKind::Ne) && (is_null_path(cx, l) || is_null_path(cx, r)) {
                 span_lint(
@@ -328,22 +388,47 @@ struct PtrArg<'tcx> {
     ty_did: DefId,
     ty_name: Symbol,
     method_renames: &'static [(&'static str, &'static str)],
-    ref_prefix: RefPrefix,
+    /// The reference of the argument, or `None` if it's passed by value
+    ref_prefix: Option<RefPrefix>,
     deref_ty: DerefTy<'tcx>,
 }
-impl PtrArg<'_> {
+impl<'tcx> PtrArg<'tcx> {
     fn build_msg(&self) -> String {
-        format!(
-            "writing `&{}{}` instead of `&{}{}` involves a new object where a slice will do",
-            self.ref_prefix.mutability.prefix_str(),
-            self.ty_name,
-            self.ref_prefix.mutability.prefix_str(),
-            self.deref_ty.argless_str(),
-        )
+        match &self.ref_prefix {
+            Some(ref_prefix) => format!(
+                "writing `&{}{}` instead of `&{}{}` involves a new object where a slice will do",
+                ref_prefix.mutability.prefix_str(),
+                self.ty_name,
+                ref_prefix.mutability.prefix_str(),
+                self.deref_ty.argless_str(),
+            ),
+            None => format!(
+                "this `{}` is passed by value, but only used as a `&{}`",
+                self.ty_name,
+                self.deref_ty.argless_str(),
+            ),
+        }
+    }
+
+    fn lint(&self) -> &'static Lint {
+        if self.ref_prefix.is_some() {
+            PTR_ARG
+        } else {
+            OWNED_PARAM_ONLY_BORROWED
+        }
     }
 
     fn mutability(&self) -> Mutability {
-        self.ref_prefix.mutability
+        self.ref_prefix
+            .as_ref()
+            .map_or(Mutability::Not, |ref_prefix| ref_prefix.mutability)
+    }
+
+    fn replacement_ty(&self, cx: &LateContext<'tcx>) -> String {
+        match &self.ref_prefix {
+            Some(ref_prefix) => format!("{ref_prefix}{}", self.deref_ty.display(cx)),
+            None => format!("&{}", self.deref_ty.display(cx)),
+        }
     }
 }
 
@@ -436,23 +521,6 @@ fn check_fn_args<'cx, 'tcx: 'cx>(
             {
                 let emission_id = params.get(i).map_or(hir_ty.hir_id, |param| param.hir_id);
                 let (method_renames, deref_ty) = match cx.tcx.get_diagnostic_name(adt.did()) {
-                    Some(sym::Vec) => (
-                        [("clone", ".to_owned()")].as_slice(),
-                        DerefTy::Slice(
-                            name.args.and_then(|args| args.args.first()).and_then(|arg| {
-                                if let GenericArg::Type(ty) = arg {
-                                    Some(ty.span)
-                                } else {
-                                    None
-                                }
-                            }),
-                            args.type_at(0),
-                        ),
-                    ),
-                    _ if Some(adt.did()) == cx.tcx.lang_items().string() => {
-                        ([("clone", ".to_owned()"), ("as_str", "")].as_slice(), DerefTy::Str)
-                    },
-                    Some(sym::PathBuf) => ([("clone", ".to_path_buf()"), ("as_path", "")].as_slice(), DerefTy::Path),
                     Some(sym::Cow) if mutability == Mutability::Not => {
                         if let Some((lifetime, ty)) = name.args.and_then(|args| {
                             if let [GenericArg::Lifetime(lifetime), ty] = args.args {
@@ -509,7 +577,7 @@ fn check_fn_args<'cx, 'tcx: 'cx>(
                         }
                         return None;
                     },
-                    _ => return None,
+                    _ => deref_ty_of(cx, adt.did(), args, name)?,
                 };
                 return Some(PtrArg {
                     idx: i,
@@ -518,7 +586,7 @@ fn check_fn_args<'cx, 'tcx: 'cx>(
                     ty_did: adt.did(),
                     ty_name: name.ident.name,
                     method_renames,
-                    ref_prefix: RefPrefix { lt: *lt, mutability },
+                    ref_prefix: Some(RefPrefix { lt: *lt, mutability }),
                     deref_ty,
                 });
             }
@@ -526,6 +594,162 @@ fn check_fn_args<'cx, 'tcx: 'cx>(
         })
 }
 
+/// The methods to rename and the type to take a reference to instead of `String`, `Vec<_>` or
+/// `PathBuf`
+fn deref_ty_of<'tcx>(
+    cx: &LateContext<'tcx>,
+    did: DefId,
+    args: GenericArgsRef<'tcx>,
+    name: &PathSegment<'_>,
+) -> Option<(&'static [(&'static str, &'static str)], DerefTy<'tcx>)> {
+    match cx.tcx.get_diagnostic_name(did) {
+        Some(sym::Vec) => Some((
+            [("clone", ".to_owned()")].as_slice(),
+            DerefTy::Slice(
+                name.args.and_then(|args| args.args.first()).and_then(|arg| {
+                    if let GenericArg::Type(ty) = arg {
+                        Some(ty.span)
+                    } else {
+                        None
+                    }
+                }),
+                args.type_at(0),
+            ),
+        )),
+        _ if Some(did) == cx.tcx.lang_items().string() => {
+            Some(([("clone", ".to_owned()"), ("as_str", "")].as_slice(), DerefTy::Str))
+        },
+        Some(sym::PathBuf) => Some(([("clone", ".to_path_buf()"), ("as_path", "")].as_slice(), DerefTy::Path)),
+        _ => None,
+    }
+}
+
+/// Finds the arguments of type `String`, `Vec<_>` or `PathBuf` passed by value. Functions whose
+/// return type has a lifetime are skipped, as a new reference argument can change its elision.
+fn check_owned_fn_args<'cx, 'tcx: 'cx>(
+    cx: &'cx LateContext<'tcx>,
+    fn_sig: ty::FnSig<'tcx>,
+    hir_tys: &'tcx [hir::Ty<'tcx>],
+    params: &'tcx [Param<'tcx>],
+) -> impl Iterator<Item = PtrArg<'tcx>> + 'cx {
+    let returns_lifetime = fn_sig.output().walk().any(|arg| arg.as_region().is_some());
+    fn_sig
+        .inputs()
+        .iter()
+        .zip(hir_tys.iter())
+        .enumerate()
+        .filter(move |_| !returns_lifetime)
+        .filter_map(move |(i, (ty, hir_ty))| {
+            if let ty::Adt(adt, args) = *ty.kind()
+                && let TyKind::Path(QPath::Resolved(None, path)) = hir_ty.kind
+                && let [.., name] = path.segments
+                && cx.tcx.item_name(adt.did()) == name.ident.name
+                && let Some(param) = params.get(i)
+                && let Some((method_renames, deref_ty)) = deref_ty_of(cx, adt.did(), args, name)
+            {
+                Some(PtrArg {
+                    idx: i,
+                    emission_id: param.hir_id,
+                    span: hir_ty.span,
+                    ty_did: adt.did(),
+                    ty_name: name.ident.name,
+                    method_renames,
+                    ref_prefix: None,
+                    deref_ty,
+                })
+            } else {
+                None
+            }
+        })
+}
+
+/// Checks if `owned_param_only_borrowed` lints the argument `idx` of a function which isn't a
+/// trait method.
+pub(crate) fn is_owned_param_only_borrowed<'tcx>(
+    cx: &LateContext<'tcx>,
+    decl: &'tcx FnDecl<'tcx>,
+    fn_sig: ty::FnSig<'tcx>,
+    body: &'tcx Body<'tcx>,
+    idx: usize,
+) -> bool {
+    let args: Vec<_> = check_owned_fn_args(cx, fn_sig, decl.inputs, body.params)
+        .filter(|arg| arg.idx == idx)
+        .collect();
+    !args.is_empty() && !check_ptr_arg_usage(cx, body, &args)[0].skip
+}
+
+/// The changes to make to the calls to `fn_id` made in the crate when the argument passed by
+/// value becomes a reference: `foo(x.clone())` becomes `foo(&x)`, `foo("x".to_string())` becomes
+/// `foo("x")`, and any other argument is borrowed.
+fn owned_arg_call_sites<'tcx>(cx: &LateContext<'tcx>, fn_id: LocalDefId, arg: &PtrArg<'tcx>) -> Vec<(Span, String)> {
+    let deref_ty = arg.deref_ty.ty(cx);
+    call_graph(cx)
+        .callers(fn_id)
+        .iter()
+        .filter(|call| !call.span.from_expansion())
+        .filter_map(|call| {
+            let typeck = cx.tcx.typeck(call.caller);
+            let call_arg = match cx.tcx.hir_node(call.hir_id) {
+                Node::Expr(Expr {
+                    kind: ExprKind::Call(_, args),
+                    ..
+                }) => args.get(arg.idx)?,
+                Node::Expr(Expr {
+                    kind: ExprKind::MethodCall(_, _, args, _),
+                    ..
+                }) => args.get(arg.idx.checked_sub(1)?)?,
+                _ => return None,
+            };
+            let ctxt = call.span.ctxt();
+            let arg_span = walk_span_to_context(call_arg.span, ctxt)?;
+            let borrowed = match call_arg.kind {
+                _ if call_arg.span.from_expansion() => None,
+                ExprKind::MethodCall(name, recv, [], _) if !recv.span.from_expansion() => {
+                    let recv_ty = typeck.expr_ty(recv);
+                    match name.ident.as_str() {
+                        "clone" if recv_ty.is_ref() => Some(recv),
+                        "to_string" | "to_owned" | "to_vec" | "to_path_buf" | "into"
+                            if recv_ty.is_ref() && recv_ty.peel_refs() == deref_ty =>
+                        {
+                            Some(recv)
+                        },
+                        _ => None,
+                    }
+                },
+                ExprKind::Call(func, [inner])
+                    if let ExprKind::Path(ref qpath) = func.kind
+                        && let Some(func_id) = typeck.qpath_res(qpath, func.hir_id).opt_def_id()
+                        && cx.tcx.is_diagnostic_item(sym::from_fn, func_id)
+                        && !inner.span.from_expansion()
+                        && typeck.expr_ty(inner).is_ref()
+                        && typeck.expr_ty(inner).peel_refs() == deref_ty =>
+                {
+                    Some(inner)
+                },
+                _ => None,
+            };
+            let mut app = Applicability::Unspecified;
+            let sugg = match borrowed {
+                Some(borrowed) => snippet_opt(cx, borrowed.span)?,
+                None => match call_arg.kind {
+                    ExprKind::MethodCall(name, recv, [], _)
+                        if name.ident.name == sym::clone && !call_arg.span.from_expansion() =>
+                    {
+                        format!(
+                            "&{}",
+                            Sugg::hir_with_context(cx, recv, ctxt, "..", &mut app).maybe_par()
+                        )
+                    },
+                    _ => Sugg::hir_with_context(cx, call_arg, ctxt, "..", &mut app)
+                        .addr()
+                        .to_string(),
+                },
+            };
+            Some((arg_span, sugg))
+        })
+        .collect()
+}
+
 fn check_mut_from_ref<'tcx>(cx: &LateContext<'tcx>, sig: &FnSig<'_>, body: Option<&'tcx Body<'_>>) {
     if let FnRetTy::Return(ty) = sig.decl.output
         && let Some((out, Mutability::Mut, _)) = get_ref_lm(ty)
@@ -572,6 +796,8 @@ fn check_ptr_arg_usage<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Body<'_>, args:
         results: Vec<PtrArgResult>,
         /// The number of arguments which can't be linted. Used to return early.
         skip_count: usize,
+        /// The number of `move` closures the current expression is in.
+        move_closures: usize,
     }
     impl<'tcx> Visitor<'tcx> for V<'_, 'tcx> {
         type NestedFilter = nested_filter::OnlyBodies;
@@ -586,11 +812,23 @@ fn check_ptr_arg_usage<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Body<'_>, args:
                 return;
             }
 
+            if let ExprKind::Closure(&Closure {
+                capture_clause: CaptureBy::Value { .. },
+                ..
+            }) = e.kind
+            {
+                self.move_closures += 1;
+                walk_expr(self, e);
+                self.move_closures -= 1;
+                return;
+            }
+
             // Check if this is local we care about
             let Some(&args_idx) = path_to_local(e).and_then(|id| self.bindings.get(&id)) else {
                 return walk_expr(self, e);
             };
             let args = &self.args[args_idx];
+            let child_span = e.span;
             let result = &mut self.results[args_idx];
 
             // Helper function to handle early returns.
@@ -601,6 +839,14 @@ fn check_ptr_arg_usage<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Body<'_>, args:
                 result.skip = true;
             };
 
+            // An argument passed by value is moved into a `move` closure, which may outlive a
+            // reference.
+            let by_value = args.ref_prefix.is_none();
+            if by_value && self.move_closures > 0 {
+                set_skip_flag();
+                return;
+            }
+
             match get_expr_use_or_unification_node(self.cx.tcx, e) {
                 Some((Node::Stmt(_), _)) => (),
                 Some((Node::LetStmt(l), _)) => {
@@ -612,6 +858,23 @@ fn check_ptr_arg_usage<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Body<'_>, args:
                     }
                 },
                 Some((Node::Expr(e), child_id)) => match e.kind {
+                    // An argument passed by value is moved into the call.
+                    ExprKind::Call(..) if by_value => set_skip_flag(),
+                    ExprKind::MethodCall(_, self_arg, ..) if by_value && self_arg.hir_id != child_id => {
+                        set_skip_flag();
+                    },
+                    ExprKind::AddrOf(BorrowKind::Ref, Mutability::Not, _) if by_value => {
+                        if self.cx.typeck_results().expr_ty_adjusted(e).peel_refs() == args.deref_ty.ty(self.cx) {
+                            // `&x` is coerced to the dereferenced type, so it can be replaced by `x`.
+                            result.replacements.push(PtrArgReplacement {
+                                expr_span: e.span,
+                                self_span: child_span,
+                                replacement: "",
+                            });
+                        } else if !is_format_arg(self.cx, e) {
+                            set_skip_flag();
+                        }
+                    },
                     ExprKind::Call(f, expr_args) => {
                         let i = expr_args.iter().position(|arg| arg.hir_id == child_id).unwrap_or(0);
                         if expr_sig(self.cx, f).and_then(|sig| sig.input(i)).map_or(true, |ty| {
@@ -686,7 +949,9 @@ fn check_ptr_arg_usage<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Body<'_>, args:
             .filter_map(|(i, arg)| {
                 let param = &body.params[arg.idx];
                 match param.pat.kind {
-                    PatKind::Binding(BindingMode::NONE, id, _, None) if !is_lint_allowed(cx, PTR_ARG, param.hir_id) => {
+                    PatKind::Binding(BindingMode::NONE, id, _, None)
+                        if !is_lint_allowed(cx, arg.lint(), param.hir_id) =>
+                    {
                         Some((id, i))
                     },
                     _ => {
@@ -700,6 +965,7 @@ fn check_ptr_arg_usage<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Body<'_>, args:
         args,
         results,
         skip_count,
+        move_closures: 0,
     };
     v.visit_expr(body.value);
     v.results
@@ -732,6 +998,12 @@ fn matches_preds<'tcx>(
         })
 }
 
+/// Checks if `e` is the reference to an argument of a formatting macro, which formats a reference
+/// to `str`, `[T]` or `Path` the same way.
+fn is_format_arg(cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
+    e.span.from_expansion() && root_macro_call(e.span).is_some_and(|macro_call| is_format_macro(cx, macro_call.def_id))
+}
+
 fn get_ref_lm<'tcx>(ty: &'tcx hir::Ty<'tcx>) -> Option<(&'tcx Lifetime, Mutability, Span)> {
     if let TyKind::Ref(lt, ref m) = ty.kind {
         Some((lt, m.mutbl, ty.span))
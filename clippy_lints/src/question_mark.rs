@@ -14,8 +14,7 @@ use rustc_errors::Applicability;
 use rustc_hir::def::Res;
 use rustc_hir::LangItem::{self, OptionNone, OptionSome, ResultErr, ResultOk};
 use rustc_hir::{
-    BindingMode, Block, Body, ByRef, Expr, ExprKind, LetStmt, Mutability, Node, PatKind, PathSegment, QPath, Stmt,
-    StmtKind,
+    BindingMode, Block, Body, ByRef, Expr, ExprKind, Mutability, Node, PatKind, PathSegment, QPath, Stmt, StmtKind,
 };
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::Ty;
@@ -128,12 +127,13 @@ fn check_let_some_else_return_none(cx: &LateContext<'_>, stmt: &Stmt<'_>) {
             .map_or(false, |did| implements_trait(cx, init_ty, did, &[]))
     }
 
-    if let StmtKind::Let(LetStmt {
-        pat,
-        init: Some(init_expr),
-        els: Some(els),
-        ..
-    }) = stmt.kind
+    if let StmtKind::Let(local) = stmt.kind
+        && let Some(higher::LetElse {
+            pat,
+            init: init_expr,
+            els,
+            ..
+        }) = higher::LetElse::hir(local)
         && init_expr_can_use_question_mark(cx, init_expr)
         && let Some(ret) = find_let_else_ret_expression(els)
         && let Some(inner_pat) = pat_and_expr_can_be_question_mark(cx, pat, ret)
@@ -0,0 +1,130 @@
+use clippy_utils::call_graph::call_graph;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::local_def_path_def_ids;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{Body, FnDecl};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::impl_lint_pass;
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for functions which can call themselves, directly or through other functions of
+    /// the crate, and shows the whole cycle of calls.
+    ///
+    /// The functions listed in `allowed-recursive-functions` aren't linted, nor are the cycles
+    /// going through them.
+    ///
+    /// ### Why is this bad?
+    /// The stack used by a recursive function depends on its input. Embedded and real-time code
+    /// often runs with a small, fixed stack, whose size must be bounded statically, and a stack
+    /// overflow can't be recovered from.
+    ///
+    /// ### Known problems
+    /// Only the calls to the functions of the crate are followed, and not the calls through
+    /// function pointers, `dyn Trait` or closures, nor the calls to trait methods whose
+    /// implementation isn't known at the call site.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn depth(node: &Node) -> usize {
+    ///     let mut max = 0;
+    ///     for child in &node.children {
+    ///         max = max.max(depth(child));
+    ///     }
+    ///     max + 1
+    /// }
+    /// # struct Node { children: Vec<Node> }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```no_run
+    /// fn depth(node: &Node) -> usize {
+    ///     let mut max = 0;
+    ///     let mut stack = vec![(node, 1)];
+    ///     while let Some((node, depth)) = stack.pop() {
+    ///         max = max.max(depth);
+    ///         stack.extend(node.children.iter().map(|child| (child, depth + 1)));
+    ///     }
+    ///     max
+    /// }
+    /// # struct Node { children: Vec<Node> }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub RECURSION,
+    restriction,
+    "functions which can call themselves, directly or not"
+}
+
+impl_lint_pass!(Recursion => [RECURSION]);
+
+pub struct Recursion {
+    allowed_functions: Vec<String>,
+    /// The `DefId`s of `allowed_functions`.
+    allowed_ids: FxHashSet<DefId>,
+}
+
+impl Recursion {
+    pub fn new(allowed_functions: Vec<String>) -> Self {
+        Self {
+            allowed_functions,
+            allowed_ids: FxHashSet::default(),
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for Recursion {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        self.allowed_ids = self
+            .allowed_functions
+            .iter()
+            .flat_map(|path| local_def_path_def_ids(cx, path))
+            .collect();
+    }
+
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'_>,
+        _: &'tcx Body<'_>,
+        span: Span,
+        def_id: LocalDefId,
+    ) {
+        if matches!(kind, FnKind::Closure)
+            || in_external_macro(cx.sess(), span)
+            || self.allowed_ids.contains(&def_id.to_def_id())
+        {
+            return;
+        }
+        let Some(cycle) =
+            call_graph(cx).cycle_through(def_id, |callee| !self.allowed_ids.contains(&callee.to_def_id()))
+        else {
+            return;
+        };
+        span_lint_and_then(
+            cx,
+            RECURSION,
+            cx.tcx.def_span(def_id),
+            format!("`{}` can call itself", cx.tcx.def_path_str(def_id)),
+            |diag| {
+                for call in &cycle {
+                    let note = if call.caller == call.callee {
+                        format!("`{}` calls itself here", cx.tcx.def_path_str(call.caller))
+                    } else {
+                        format!(
+                            "`{}` calls `{}` here",
+                            cx.tcx.def_path_str(call.caller),
+                            cx.tcx.def_path_str(call.callee)
+                        )
+                    };
+                    diag.span_note(call.span, note);
+                }
+                diag.help("use a loop, with an explicit stack of bounded size if needed");
+            },
+        );
+    }
+}
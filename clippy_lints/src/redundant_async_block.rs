@@ -1,14 +1,12 @@
 use std::ops::ControlFlow;
 
 use clippy_utils::diagnostics::span_lint_and_sugg;
-use clippy_utils::peel_blocks;
 use clippy_utils::source::{snippet, walk_span_to_context};
 use clippy_utils::ty::implements_trait;
 use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{higher, peel_blocks};
 use rustc_errors::Applicability;
-use rustc_hir::{
-    Closure, ClosureKind, CoroutineDesugaring, CoroutineKind, CoroutineSource, Expr, ExprKind, MatchSource,
-};
+use rustc_hir::Expr;
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::lint::in_external_macro;
 use rustc_middle::ty::UpvarCapture;
@@ -75,37 +73,27 @@ impl<'tcx> LateLintPass<'tcx> for RedundantAsyncBlock {
 /// If `expr` is a desugared `async` block, return the original expression if it does not capture
 /// any variable by ref.
 fn desugar_async_block<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) -> Option<&'tcx Expr<'tcx>> {
-    if let ExprKind::Closure(Closure { body, def_id, kind, .. }) = expr.kind
-        && let body = cx.tcx.hir().body(*body)
-        && matches!(
-            kind,
-            ClosureKind::Coroutine(CoroutineKind::Desugared(
-                CoroutineDesugaring::Async,
-                CoroutineSource::Block
-            ))
-        )
-    {
-        cx.typeck_results()
-            .closure_min_captures
-            .get(def_id)
-            .map_or(true, |m| {
-                m.values().all(|places| {
-                    places
-                        .iter()
-                        .all(|place| matches!(place.info.capture_kind, UpvarCapture::ByValue))
-                })
+    let async_block = higher::AsyncBlock::hir(cx, expr)?;
+    cx.typeck_results()
+        .closure_min_captures
+        .get(&async_block.def_id)
+        .map_or(true, |m| {
+            m.values().all(|places| {
+                places
+                    .iter()
+                    .all(|place| matches!(place.info.capture_kind, UpvarCapture::ByValue))
             })
-            .then_some(body.value)
-    } else {
-        None
-    }
+        })
+        .then_some(async_block.body.value)
 }
 
 /// If `expr` is a desugared `.await`, return the original expression if it does not come from a
 /// macro expansion.
 fn desugar_await<'tcx>(expr: &'tcx Expr<'_>) -> Option<&'tcx Expr<'tcx>> {
-    if let ExprKind::Match(match_value, _, MatchSource::AwaitDesugar) = expr.kind
-        && let ExprKind::Call(_, [into_future_arg]) = match_value.kind
+    if let Some(higher::Await {
+        future: into_future_arg,
+        ..
+    }) = higher::Await::hir(expr)
         && let ctxt = expr.span.ctxt()
         && for_each_expr(into_future_arg, |e| {
             walk_span_to_context(e.span, ctxt).map_or(ControlFlow::Break(()), |_| ControlFlow::Continue(()))
@@ -261,6 +261,10 @@ impl<'tcx> LateLintPass<'tcx> for RedundantClosureCall {
             closure_usage_count.count
         }
 
+        if in_external_macro(cx.sess(), block.span) {
+            return;
+        }
+
         for w in block.stmts.windows(2) {
             if let hir::StmtKind::Let(local) = w[0].kind
                 && let Some(t) = local.init
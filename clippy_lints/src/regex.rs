@@ -2,8 +2,9 @@ use std::fmt::Display;
 
 use clippy_utils::consts::{constant, Constant};
 use clippy_utils::diagnostics::{span_lint, span_lint_and_help};
+use clippy_utils::ecosystem::{self, ItemPaths};
+use clippy_utils::path_def_id;
 use clippy_utils::source::snippet_opt;
-use clippy_utils::{def_path_def_ids, path_def_id, paths};
 use rustc_ast::ast::{LitKind, StrStyle};
 use rustc_hir::def_id::DefIdMap;
 use rustc_hir::{BorrowKind, Expr, ExprKind};
@@ -74,21 +75,18 @@ impl<'tcx> LateLintPass<'tcx> for Regex {
     fn check_crate(&mut self, cx: &LateContext<'tcx>) {
         // We don't use `match_def_path` here because that relies on matching the exact path, which changed
         // between regex 1.8 and 1.9
-        //
-        // `def_path_def_ids` will resolve through re-exports but is relatively heavy, so we only perform
-        // the operation once and store the results
-        let mut resolve = |path, kind| {
-            for id in def_path_def_ids(cx, path) {
+        let mut resolve = |path: ItemPaths, kind| {
+            for &id in &*ecosystem::def_ids(cx, path) {
                 self.definitions.insert(id, kind);
             }
         };
 
-        resolve(&paths::REGEX_NEW, RegexKind::Unicode);
-        resolve(&paths::REGEX_BUILDER_NEW, RegexKind::Unicode);
-        resolve(&paths::REGEX_SET_NEW, RegexKind::UnicodeSet);
-        resolve(&paths::REGEX_BYTES_NEW, RegexKind::Bytes);
-        resolve(&paths::REGEX_BYTES_BUILDER_NEW, RegexKind::Bytes);
-        resolve(&paths::REGEX_BYTES_SET_NEW, RegexKind::BytesSet);
+        resolve(ecosystem::REGEX_NEW, RegexKind::Unicode);
+        resolve(ecosystem::REGEX_BUILDER_NEW, RegexKind::Unicode);
+        resolve(ecosystem::REGEX_SET_NEW, RegexKind::UnicodeSet);
+        resolve(ecosystem::REGEX_BYTES_NEW, RegexKind::Bytes);
+        resolve(ecosystem::REGEX_BYTES_BUILDER_NEW, RegexKind::Bytes);
+        resolve(ecosystem::REGEX_BYTES_SET_NEW, RegexKind::BytesSet);
     }
 
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
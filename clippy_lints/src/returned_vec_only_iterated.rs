@@ -0,0 +1,394 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet_opt;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{crate_cache, is_def_id_trait_method, is_trait_method, path_to_local};
+use core::ops::ControlFlow;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_errors::Applicability;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::{walk_expr, walk_pat, FnKind, Visitor};
+use rustc_hir::{
+    Body, BorrowKind, CaptureBy, Closure, Expr, ExprKind, FnDecl, FnRetTy, GenericArg, HirId, Node, Pat, PatKind,
+    QPath, TyKind,
+};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::hir::nested_filter;
+use rustc_middle::ty::adjustment::Adjust;
+use rustc_middle::ty::{TyCtxt, TypeckResults};
+use rustc_session::declare_lint_pass;
+use rustc_span::{sym, DesugaringKind, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for functions of the crate which collect an iterator into a `Vec` and return it,
+    /// when every call to them iterates the `Vec` right away.
+    ///
+    /// ### Why is this bad?
+    /// The `Vec` is allocated only to be consumed. Returning `impl Iterator` avoids the
+    /// allocation, and lets the callers stop iterating early without computing every item.
+    ///
+    /// ### Known problems
+    /// Unlike the `Vec`, the iterator is lazy: side effects of the iterator chain happen while the
+    /// caller iterates.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn evens(n: u32) -> Vec<u32> {
+    ///     (0..n).filter(|x| x % 2 == 0).collect()
+    /// }
+    ///
+    /// for x in evens(10) {
+    ///     println!("{x}");
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// fn evens(n: u32) -> impl Iterator<Item = u32> {
+    ///     (0..n).filter(|x| x % 2 == 0)
+    /// }
+    ///
+    /// for x in evens(10) {
+    ///     println!("{x}");
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub RETURNED_VEC_ONLY_ITERATED,
+    pedantic,
+    "functions returning a `Vec` which every caller only iterates"
+}
+
+declare_lint_pass!(ReturnedVecOnlyIterated => [RETURNED_VEC_ONLY_ITERATED]);
+
+impl<'tcx> LateLintPass<'tcx> for ReturnedVecOnlyIterated {
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        decl: &'tcx FnDecl<'_>,
+        body: &'tcx Body<'_>,
+        span: Span,
+        def_id: LocalDefId,
+    ) {
+        if span.from_expansion()
+            || matches!(kind, FnKind::Closure)
+            || kind.asyncness().is_async()
+            || cx.effective_visibilities.is_exported(def_id)
+            || is_def_id_trait_method(cx, def_id)
+            || cx.tcx.trait_of_item(def_id.to_def_id()).is_some()
+        {
+            return;
+        }
+
+        if let FnRetTy::Return(ret_ty) = decl.output
+            && let TyKind::Path(QPath::Resolved(None, path)) = ret_ty.kind
+            && let [.., segment] = path.segments
+            && let Some([GenericArg::Type(item_ty)]) = segment.args.map(|args| args.args)
+            && is_type_diagnostic_item(
+                cx,
+                cx.tcx.fn_sig(def_id).instantiate_identity().skip_binder().output(),
+                sym::Vec,
+            )
+            && let ExprKind::Block(block, None) = body.value.kind
+            && let Some(tail) = block.expr
+            && let ExprKind::MethodCall(method, iter, [], _) = tail.kind
+            && method.ident.as_str() == "collect"
+            && is_trait_method(cx, tail, sym::Iterator)
+            && !tail.span.from_expansion()
+            && !has_return(body)
+            && let Some(lifetime_bound) = lifetime_bound(cx, def_id)
+            && !borrows_locals(cx, body, iter)
+            && let Some(Some(call_sites)) = crate_cache::get_or_compute(cx, || IteratedCalls::collect(cx.tcx))
+                .0
+                .get(&def_id)
+            && !call_sites.is_empty()
+        {
+            span_lint_and_then(
+                cx,
+                RETURNED_VEC_ONLY_ITERATED,
+                ret_ty.span,
+                "this function returns a `Vec` which every caller only iterates",
+                |diag| {
+                    let (Some(item_ty), Some(iter)) = (snippet_opt(cx, item_ty.span), snippet_opt(cx, iter.span))
+                    else {
+                        return;
+                    };
+                    let mut sugg = vec![
+                        (ret_ty.span, format!("impl Iterator<Item = {item_ty}>{lifetime_bound}")),
+                        (tail.span, iter),
+                    ];
+                    // `.into_iter()` on the returned iterator would be useless.
+                    sugg.extend(call_sites.iter().filter_map(|&call| match call {
+                        IteratedCall::IntoIter { into_iter, call } => Some((into_iter, snippet_opt(cx, call)?)),
+                        IteratedCall::ForLoop => None,
+                    }));
+                    diag.multipart_suggestion(
+                        "return the iterator instead of collecting it",
+                        sugg,
+                        Applicability::MaybeIncorrect,
+                    );
+                },
+            );
+        }
+    }
+}
+
+fn has_return(body: &Body<'_>) -> bool {
+    for_each_expr(body.value, |e| {
+        if let ExprKind::Ret(_) = e.kind {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .is_some()
+}
+
+/// The bound to add to the returned `impl Iterator` so it can capture the lifetime of the
+/// arguments, or `None` if there are several lifetimes.
+fn lifetime_bound(cx: &LateContext<'_>, def_id: LocalDefId) -> Option<&'static str> {
+    let fn_sig = cx.tcx.fn_sig(def_id).instantiate_identity().skip_binder();
+    match fn_sig
+        .inputs()
+        .iter()
+        .flat_map(|ty| ty.walk())
+        .filter(|arg| arg.as_region().is_some())
+        .count()
+    {
+        0 => Some(""),
+        1 => Some(" + '_"),
+        _ => None,
+    }
+}
+
+/// Checks if the iterator borrows locals of the function, which the returned iterator can't
+/// outlive: any local besides the arguments, or an argument which isn't a reference and is
+/// borrowed rather than moved.
+fn borrows_locals<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Body<'_>, iter: &'tcx Expr<'_>) -> bool {
+    struct V<'a, 'tcx> {
+        cx: &'a LateContext<'tcx>,
+        params: FxHashSet<HirId>,
+        /// The bindings defined in the iterator expression, e.g. by the arguments of closures
+        inner_bindings: FxHashSet<HirId>,
+        /// The number of closures capturing by reference the current expression is in
+        ref_closures: usize,
+        borrows: bool,
+    }
+    impl<'tcx> Visitor<'tcx> for V<'_, 'tcx> {
+        type NestedFilter = nested_filter::OnlyBodies;
+
+        fn nested_visit_map(&mut self) -> Self::Map {
+            self.cx.tcx.hir()
+        }
+
+        fn visit_pat(&mut self, pat: &'tcx Pat<'tcx>) {
+            if let PatKind::Binding(_, id, ..) = pat.kind {
+                self.inner_bindings.insert(id);
+            }
+            walk_pat(self, pat);
+        }
+
+        fn visit_expr(&mut self, e: &'tcx Expr<'tcx>) {
+            if let ExprKind::Closure(&Closure { capture_clause, .. }) = e.kind
+                && !matches!(capture_clause, CaptureBy::Value { .. })
+            {
+                self.ref_closures += 1;
+                walk_expr(self, e);
+                self.ref_closures -= 1;
+                return;
+            }
+            if let Some(id) = path_to_local(e)
+                && !self.inner_bindings.contains(&id)
+            {
+                let typeck = self.cx.typeck_results();
+                let borrowed = || {
+                    typeck
+                        .expr_adjustments(e)
+                        .iter()
+                        .any(|adjust| matches!(adjust.kind, Adjust::Borrow(_)))
+                        || matches!(
+                            self.cx.tcx.parent_hir_node(e.hir_id),
+                            Node::Expr(Expr {
+                                kind: ExprKind::AddrOf(BorrowKind::Ref, ..),
+                                ..
+                            })
+                        )
+                };
+                if !self.params.contains(&id) || self.ref_closures > 0 || (!typeck.node_type(id).is_ref() && borrowed())
+                {
+                    self.borrows = true;
+                }
+                return;
+            }
+            walk_expr(self, e);
+        }
+    }
+
+    let mut params = FxHashSet::default();
+    for param in body.params {
+        param.pat.each_binding(|_, id, _, _| {
+            params.insert(id);
+        });
+    }
+    let mut v = V {
+        cx,
+        params,
+        inner_bindings: FxHashSet::default(),
+        ref_closures: 0,
+        borrows: false,
+    };
+    v.visit_expr(iter);
+    v.borrows
+}
+
+/// A call whose result is only iterated
+#[derive(Clone, Copy)]
+enum IteratedCall {
+    /// `for x in foo() { .. }`
+    ForLoop,
+    /// `foo().into_iter()` followed by iterator methods
+    IntoIter { into_iter: Span, call: Span },
+}
+
+/// For each function of the crate, its calls if each use of the function is a call whose result
+/// is only iterated, or `None` otherwise.
+struct IteratedCalls(FxHashMap<LocalDefId, Option<Vec<IteratedCall>>>);
+
+impl IteratedCalls {
+    fn collect(tcx: TyCtxt<'_>) -> Self {
+        let mut calls = Self(FxHashMap::default());
+        for owner in tcx.hir().body_owners() {
+            let mut v = UsesVisitor {
+                tcx,
+                typeck: tcx.typeck(owner),
+                calls: &mut calls,
+            };
+            v.visit_expr(tcx.hir().body(tcx.hir().body_owned_by(owner)).value);
+        }
+        calls
+    }
+}
+
+struct UsesVisitor<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    calls: &'a mut IteratedCalls,
+}
+
+impl<'tcx> UsesVisitor<'_, 'tcx> {
+    fn record(&mut self, def_id: LocalDefId, call: Option<&'tcx Expr<'tcx>>) {
+        let iterated = call.and_then(|call| self.iterated_call(call));
+        let calls = self.calls.0.entry(def_id).or_insert_with(|| Some(Vec::new()));
+        match (calls, iterated) {
+            (Some(calls), Some(iterated)) => calls.push(iterated),
+            (calls, None) => *calls = None,
+            (None, Some(_)) => {},
+        }
+    }
+
+    fn is_into_iter(&self, e: &Expr<'_>) -> bool {
+        let def_id = match e.kind {
+            ExprKind::MethodCall(..) => self.typeck.type_dependent_def_id(e.hir_id),
+            ExprKind::Call(func, _) => match func.kind {
+                ExprKind::Path(ref qpath) => self.typeck.qpath_res(qpath, func.hir_id).opt_def_id(),
+                _ => None,
+            },
+            _ => None,
+        };
+        def_id.is_some() && def_id == self.tcx.lang_items().into_iter_fn()
+    }
+
+    /// Checks if the result of the call is only iterated, by a `for` loop or by calling
+    /// `.into_iter()` then iterator methods which don't need a double ended iterator.
+    fn iterated_call(&self, call: &'tcx Expr<'tcx>) -> Option<IteratedCall> {
+        let Node::Expr(parent) = self.tcx.parent_hir_node(call.hir_id) else {
+            return None;
+        };
+        if parent.span.is_desugaring(DesugaringKind::ForLoop) && self.is_into_iter(parent) {
+            return Some(IteratedCall::ForLoop);
+        }
+        if !(matches!(parent.kind, ExprKind::MethodCall(_, recv, [], _) if recv.hir_id == call.hir_id)
+            && self.is_into_iter(parent))
+            || parent.span.from_expansion()
+        {
+            return None;
+        }
+
+        // Follow the chain of iterator methods
+        let mut e = parent;
+        loop {
+            match self.tcx.parent_hir_node(e.hir_id) {
+                Node::Expr(next) if next.span.is_desugaring(DesugaringKind::ForLoop) && self.is_into_iter(next) => {
+                    break;
+                },
+                Node::Expr(
+                    next @ Expr {
+                        kind: ExprKind::MethodCall(method, recv, ..),
+                        ..
+                    },
+                ) if recv.hir_id == e.hir_id => {
+                    let def_id = self.typeck.type_dependent_def_id(next.hir_id)?;
+                    if self.tcx.trait_of_item(def_id) != self.tcx.get_diagnostic_item(sym::Iterator)
+                        || matches!(method.ident.as_str(), "rev" | "rposition")
+                    {
+                        return None;
+                    }
+                    e = next;
+                },
+                // The iterator must not escape, as its type changes.
+                _ => {
+                    let ty = self.typeck.expr_ty(e);
+                    let is_iterator = self.tcx.get_diagnostic_item(sym::Iterator).is_some_and(|iterator| {
+                        clippy_utils::ty::implements_trait_with_env(
+                            self.tcx,
+                            self.tcx.param_env(e.hir_id.owner.def_id),
+                            ty,
+                            iterator,
+                            None,
+                            &[],
+                        )
+                    });
+                    if is_iterator {
+                        return None;
+                    }
+                    break;
+                },
+            }
+        }
+        Some(IteratedCall::IntoIter {
+            into_iter: parent.span,
+            call: call.span,
+        })
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for UsesVisitor<'_, 'tcx> {
+    fn visit_expr(&mut self, e: &'tcx Expr<'tcx>) {
+        match e.kind {
+            ExprKind::Path(ref qpath) => {
+                if let Res::Def(DefKind::Fn | DefKind::AssocFn, def_id) = self.typeck.qpath_res(qpath, e.hir_id)
+                    && let Some(def_id) = def_id.as_local()
+                {
+                    let call = match self.tcx.parent_hir_node(e.hir_id) {
+                        Node::Expr(
+                            call @ Expr {
+                                kind: ExprKind::Call(func, _),
+                                ..
+                            },
+                        ) if func.hir_id == e.hir_id => Some(call),
+                        _ => None,
+                    };
+                    self.record(def_id, call);
+                }
+            },
+            ExprKind::MethodCall(..) => {
+                if let Some(def_id) = self.typeck.type_dependent_def_id(e.hir_id).and_then(DefId::as_local) {
+                    self.record(def_id, Some(e));
+                }
+            },
+            _ => {},
+        }
+        walk_expr(self, e);
+    }
+}
@@ -1,12 +1,13 @@
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::source::{indent_of, snippet};
-use clippy_utils::{expr_or_init, get_attr, path_to_local, peel_hir_expr_unary};
+use clippy_utils::ty::has_significant_drop_attr;
+use clippy_utils::{expr_or_init, path_to_local, peel_hir_expr_unary};
 use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_errors::Applicability;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::intravisit::{walk_expr, Visitor};
 use rustc_hir::{self as hir, HirId};
-use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::{GenericArgKind, Ty};
 use rustc_session::impl_lint_pass;
 use rustc_span::symbol::Ident;
@@ -171,15 +172,10 @@ impl<'cx, 'others, 'tcx> AttrChecker<'cx, 'others, 'tcx> {
     }
 
     fn has_sig_drop_attr_uncached(&mut self, ty: Ty<'tcx>) -> bool {
-        if let Some(adt) = ty.ty_adt_def() {
-            let mut iter = get_attr(
-                self.cx.sess(),
-                self.cx.tcx.get_attrs_unchecked(adt.did()),
-                "has_significant_drop",
-            );
-            if iter.next().is_some() {
-                return true;
-            }
+        if let Some(adt) = ty.ty_adt_def()
+            && has_significant_drop_attr(self.cx, adt.did())
+        {
+            return true;
         }
         match ty.kind() {
             rustc_middle::ty::Adt(a, b) => {
@@ -1,4 +1,5 @@
 use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::sugg::MultipartSugg;
 use clippy_utils::ty::is_normalizable;
 use clippy_utils::{eq_expr_value, path_to_local};
 use rustc_abi::WrappingRange;
@@ -102,12 +103,11 @@ pub(super) fn check<'tcx>(
             expr.span,
             "this transmute is always evaluated eagerly, even if the condition is false",
             |diag| {
-                diag.multipart_suggestion(
+                let mut sugg = MultipartSugg::new(cx, then_some_call.span.ctxt());
+                sugg.replace(path.ident.span, "then").insert_before(arg.span, "|| ");
+                sugg.emit(
+                    diag,
                     "consider using `bool::then` to only transmute if the condition holds",
-                    vec![
-                        (path.ident.span, "then".into()),
-                        (arg.span.shrink_to_lo(), "|| ".into()),
-                    ],
                     Applicability::MaybeIncorrect,
                 );
             },
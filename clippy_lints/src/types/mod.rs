@@ -9,6 +9,8 @@ mod type_complexity;
 mod utils;
 mod vec_box;
 
+use clippy_config::types::ThresholdOverride;
+use clippy_utils::thresholds::{Threshold, ThresholdKind};
 use rustc_hir as hir;
 use rustc_hir::intravisit::FnKind;
 use rustc_hir::{
@@ -305,7 +307,7 @@ declare_clippy_lint! {
 
 pub struct Types {
     vec_box_size_threshold: u64,
-    type_complexity_threshold: u64,
+    type_complexity_threshold: Threshold,
     avoid_breaking_exported_api: bool,
 }
 
@@ -446,10 +448,19 @@ impl<'tcx> LateLintPass<'tcx> for Types {
 }
 
 impl Types {
-    pub fn new(vec_box_size_threshold: u64, type_complexity_threshold: u64, avoid_breaking_exported_api: bool) -> Self {
+    pub fn new(
+        vec_box_size_threshold: u64,
+        type_complexity_threshold: u64,
+        avoid_breaking_exported_api: bool,
+        threshold_overrides: &[ThresholdOverride],
+    ) -> Self {
         Self {
             vec_box_size_threshold,
-            type_complexity_threshold,
+            type_complexity_threshold: Threshold::new(
+                ThresholdKind::TypeComplexity,
+                type_complexity_threshold,
+                threshold_overrides,
+            ),
             avoid_breaking_exported_api,
         }
     }
@@ -486,7 +497,7 @@ impl Types {
             return;
         }
 
-        if !context.is_nested_call && type_complexity::check(cx, hir_ty, self.type_complexity_threshold) {
+        if !context.is_nested_call && type_complexity::check(cx, hir_ty, &self.type_complexity_threshold) {
             return;
         }
 
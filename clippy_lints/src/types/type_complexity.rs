@@ -1,4 +1,5 @@
 use clippy_utils::diagnostics::span_lint;
+use clippy_utils::thresholds::Threshold;
 use rustc_hir as hir;
 use rustc_hir::intravisit::{walk_inf, walk_ty, Visitor};
 use rustc_hir::{GenericParamKind, TyKind};
@@ -7,14 +8,14 @@ use rustc_target::spec::abi::Abi;
 
 use super::TYPE_COMPLEXITY;
 
-pub(super) fn check(cx: &LateContext<'_>, ty: &hir::Ty<'_>, type_complexity_threshold: u64) -> bool {
+pub(super) fn check(cx: &LateContext<'_>, ty: &hir::Ty<'_>, type_complexity_threshold: &Threshold) -> bool {
     let score = {
         let mut visitor = TypeComplexityVisitor { score: 0, nest: 1 };
         visitor.visit_ty(ty);
         visitor.score
     };
 
-    if score > type_complexity_threshold {
+    if score > type_complexity_threshold.resolve(cx, ty.hir_id, ty.span) {
         span_lint(
             cx,
             TYPE_COMPLEXITY,
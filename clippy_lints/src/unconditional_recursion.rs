@@ -1,7 +1,8 @@
+use clippy_utils::call_graph::call_graph;
 use clippy_utils::diagnostics::span_lint_and_then;
-use clippy_utils::{expr_or_init, fn_def_id_with_node_args, path_def_id};
+use clippy_utils::{crate_cache, expr_or_init, fn_def_id_with_node_args, path_def_id};
 use rustc_ast::BinOpKind;
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{DefId, LocalDefId};
@@ -12,7 +13,7 @@ use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::hir::map::Map;
 use rustc_middle::hir::nested_filter;
 use rustc_middle::ty::{self, AssocKind, Ty, TyCtxt};
-use rustc_session::impl_lint_pass;
+use rustc_session::declare_lint_pass;
 use rustc_span::symbol::{kw, Ident};
 use rustc_span::{sym, Span};
 use rustc_trait_selection::traits::error_reporting::suggestions::ReturnsVisitor;
@@ -20,7 +21,8 @@ use rustc_trait_selection::traits::error_reporting::suggestions::ReturnsVisitor;
 declare_clippy_lint! {
     /// ### What it does
     /// Checks that there isn't an infinite recursion in trait
-    /// implementations.
+    /// implementations, including through other functions, as in
+    /// `eq` calling `ne` which calls back `eq`.
     ///
     /// ### Why is this bad?
     /// This is a hard to find infinite recursion that will crash any code
@@ -48,14 +50,49 @@ declare_clippy_lint! {
     "detect unconditional recursion in some traits implementation"
 }
 
-#[derive(Default)]
-pub struct UnconditionalRecursion {
-    /// The key is the `DefId` of the type implementing the `Default` trait and the value is the
-    /// `DefId` of the return call.
-    default_impl_for_type: FxHashMap<DefId, DefId>,
-}
+declare_lint_pass!(UnconditionalRecursion => [UNCONDITIONAL_RECURSION]);
+
+/// The key is the `DefId` of the type implementing the `Default` trait and the value is the
+/// `DefId` of the return call.
+struct DefaultImplForType(FxHashMap<DefId, DefId>);
 
-impl_lint_pass!(UnconditionalRecursion => [UNCONDITIONAL_RECURSION]);
+impl DefaultImplForType {
+    fn collect(cx: &LateContext<'_>) -> Self {
+        let mut default_impl_for_type = FxHashMap::default();
+        if let Some(default_trait_id) = cx.tcx.get_diagnostic_item(sym::Default) {
+            let impls = cx.tcx.trait_impls_of(default_trait_id);
+            for (ty, impl_def_ids) in impls.non_blanket_impls() {
+                let Some(self_def_id) = ty.def() else { continue };
+                for impl_def_id in impl_def_ids {
+                    if !cx.tcx.has_attr(*impl_def_id, sym::automatically_derived) &&
+                        let Some(assoc_item) = cx
+                            .tcx
+                            .associated_items(impl_def_id)
+                            .in_definition_order()
+                            // We're not interested in foreign implementations of the `Default` trait.
+                            .find(|item| {
+                                item.kind == AssocKind::Fn && item.def_id.is_local() && item.name == kw::Default
+                            })
+                        && let Some(body_node) = cx.tcx.hir().get_if_local(assoc_item.def_id)
+                        && let Some(body_id) = body_node.body_id()
+                        && let body = cx.tcx.hir().body(body_id)
+                        // We don't want to keep it if it has conditional return.
+                        && let [return_expr] = get_return_calls_in_body(body).as_slice()
+                        && let ExprKind::Call(call_expr, _) = return_expr.kind
+                        // We need to use typeck here to infer the actual function being called.
+                        && let body_def_id = cx.tcx.hir().enclosing_body_owner(call_expr.hir_id)
+                        && let Some(body_owner) = cx.tcx.hir().maybe_body_owned_by(body_def_id)
+                        && let typeck = cx.tcx.typeck_body(body_owner)
+                        && let Some(call_def_id) = typeck.type_dependent_def_id(call_expr.hir_id)
+                    {
+                        default_impl_for_type.insert(self_def_id, call_def_id);
+                    }
+                }
+            }
+        }
+        Self(default_impl_for_type)
+    }
+}
 
 fn span_error(cx: &LateContext<'_>, method_span: Span, expr: &Expr<'_>) {
     span_lint_and_then(
@@ -309,85 +346,109 @@ where
     }
 }
 
-impl UnconditionalRecursion {
-    fn init_default_impl_for_type_if_needed(&mut self, cx: &LateContext<'_>) {
-        if self.default_impl_for_type.is_empty()
-            && let Some(default_trait_id) = cx.tcx.get_diagnostic_item(sym::Default)
-        {
-            let impls = cx.tcx.trait_impls_of(default_trait_id);
-            for (ty, impl_def_ids) in impls.non_blanket_impls() {
-                let Some(self_def_id) = ty.def() else { continue };
-                for impl_def_id in impl_def_ids {
-                    if !cx.tcx.has_attr(*impl_def_id, sym::automatically_derived) &&
-                        let Some(assoc_item) = cx
-                            .tcx
-                            .associated_items(impl_def_id)
-                            .in_definition_order()
-                            // We're not interested in foreign implementations of the `Default` trait.
-                            .find(|item| {
-                                item.kind == AssocKind::Fn && item.def_id.is_local() && item.name == kw::Default
-                            })
-                        && let Some(body_node) = cx.tcx.hir().get_if_local(assoc_item.def_id)
-                        && let Some(body_id) = body_node.body_id()
-                        && let body = cx.tcx.hir().body(body_id)
-                        // We don't want to keep it if it has conditional return.
-                        && let [return_expr] = get_return_calls_in_body(body).as_slice()
-                        && let ExprKind::Call(call_expr, _) = return_expr.kind
-                        // We need to use typeck here to infer the actual function being called.
-                        && let body_def_id = cx.tcx.hir().enclosing_body_owner(call_expr.hir_id)
-                        && let Some(body_owner) = cx.tcx.hir().maybe_body_owned_by(body_def_id)
-                        && let typeck = cx.tcx.typeck_body(body_owner)
-                        && let Some(call_def_id) = typeck.type_dependent_def_id(call_expr.hir_id)
-                    {
-                        self.default_impl_for_type.insert(self_def_id, call_def_id);
-                    }
-                }
-            }
-        }
+fn check_default_new<'tcx>(
+    cx: &LateContext<'tcx>,
+    decl: &FnDecl<'tcx>,
+    body: &'tcx Body<'tcx>,
+    method_span: Span,
+    method_def_id: LocalDefId,
+) {
+    // We're only interested into static methods.
+    if decl.implicit_self.has_implicit_self() {
+        return;
+    }
+    // We don't check trait implementations.
+    if get_impl_trait_def_id(cx, method_def_id).is_some() {
+        return;
     }
 
-    fn check_default_new<'tcx>(
-        &mut self,
-        cx: &LateContext<'tcx>,
-        decl: &FnDecl<'tcx>,
-        body: &'tcx Body<'tcx>,
-        method_span: Span,
-        method_def_id: LocalDefId,
-    ) {
-        // We're only interested into static methods.
-        if decl.implicit_self.has_implicit_self() {
+    let hir_id = cx.tcx.local_def_id_to_hir_id(method_def_id);
+    if let Some((
+        _,
+        Node::Item(Item {
+            kind: ItemKind::Impl(impl_),
+            ..
+        }),
+    )) = cx.tcx.hir().parent_iter(hir_id).next()
+        && let Some(implemented_ty_id) = get_hir_ty_def_id(cx.tcx, *impl_.self_ty)
+        && let Some(return_def_id) = crate_cache::get_or_compute(cx, || DefaultImplForType::collect(cx))
+            .0
+            .get(&implemented_ty_id)
+        && method_def_id.to_def_id() == *return_def_id
+    {
+        let mut c = CheckCalls {
+            cx,
+            map: cx.tcx.hir(),
+            implemented_ty_id,
+            found_default_call: false,
+            method_span,
+        };
+        walk_body(&mut c, body);
+    }
+}
+
+/// Peels the operations applied to the result of a call, e.g. `!self.ne(other)`.
+fn peel_call_result<'tcx>(mut expr: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    while let ExprKind::Unary(_, inner) | ExprKind::AddrOf(_, _, inner) | ExprKind::DropTemps(inner) =
+        expr.peel_blocks().kind
+    {
+        expr = inner;
+    }
+    expr.peel_blocks()
+}
+
+/// Checks if the method of a trait implementation unconditionally calls a function of the crate
+/// which unconditionally calls back the method, possibly through other functions. The direct
+/// recursion is linted by rustc.
+fn check_mutual_recursion<'tcx>(
+    cx: &LateContext<'tcx>,
+    method_span: Span,
+    method_def_id: LocalDefId,
+    expr: &'tcx Expr<'tcx>,
+) {
+    if get_impl_trait_def_id(cx, method_def_id).is_none() {
+        return;
+    }
+    let call_graph = call_graph(cx);
+    if !call_graph.is_recursive(method_def_id) {
+        return;
+    }
+    let mut call = peel_call_result(expr);
+    let mut calls = Vec::new();
+    let mut seen = FxHashSet::default();
+    loop {
+        let Some(callee) = call_graph.callee(call.hir_id) else {
             return;
+        };
+        calls.push((call.span, callee));
+        if callee == method_def_id {
+            break;
         }
-        // We don't check trait implementations.
-        if get_impl_trait_def_id(cx, method_def_id).is_some() {
+        if !seen.insert(callee) {
             return;
         }
-
-        let hir_id = cx.tcx.local_def_id_to_hir_id(method_def_id);
-        if let Some((
-            _,
-            Node::Item(Item {
-                kind: ItemKind::Impl(impl_),
-                ..
-            }),
-        )) = cx.tcx.hir().parent_iter(hir_id).next()
-            && let Some(implemented_ty_id) = get_hir_ty_def_id(cx.tcx, *impl_.self_ty)
-            && {
-                self.init_default_impl_for_type_if_needed(cx);
-                true
-            }
-            && let Some(return_def_id) = self.default_impl_for_type.get(&implemented_ty_id)
-            && method_def_id.to_def_id() == *return_def_id
-        {
-            let mut c = CheckCalls {
-                cx,
-                map: cx.tcx.hir(),
-                implemented_ty_id,
-                found_default_call: false,
-                method_span,
-            };
-            walk_body(&mut c, body);
+        let Some(body_id) = cx.tcx.hir().maybe_body_owned_by(callee) else {
+            return;
+        };
+        let body = cx.tcx.hir().body(body_id);
+        let expr = body.value.peel_blocks();
+        if has_conditional_return(body, expr) {
+            return;
         }
+        call = peel_call_result(expr);
+    }
+    if calls.len() > 1 {
+        span_lint_and_then(
+            cx,
+            UNCONDITIONAL_RECURSION,
+            method_span,
+            "function cannot return without recursing",
+            |diag| {
+                for (span, callee) in calls {
+                    diag.span_note(span, format!("calls `{}`", cx.tcx.def_path_str(callee)));
+                }
+            },
+        );
     }
 }
 
@@ -441,7 +502,8 @@ impl<'tcx> LateLintPass<'tcx> for UnconditionalRecursion {
                 sym::from => check_from(cx, method_span, method_def_id, expr),
                 _ => {},
             }
-            self.check_default_new(cx, decl, body, method_span, method_def_id);
+            check_mutual_recursion(cx, method_span, method_def_id, expr);
+            check_default_new(cx, decl, body, method_span, method_def_id);
         }
     }
 }
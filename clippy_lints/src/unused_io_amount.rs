@@ -1,6 +1,7 @@
 use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::ecosystem::{self, is_trait_method_in};
 use clippy_utils::macros::{is_panic, root_macro_call_first_node};
-use clippy_utils::{is_res_lang_ctor, is_trait_method, match_trait_method, paths, peel_blocks};
+use clippy_utils::{higher, is_res_lang_ctor, is_trait_method, peel_blocks};
 use hir::{ExprKind, HirId, PatKind};
 use rustc_hir as hir;
 use rustc_lint::{LateContext, LateLintPass};
@@ -160,7 +161,9 @@ fn should_lint<'a>(cx: &LateContext<'a>, mut inner: &'a hir::Expr<'a>) -> Option
     inner = unpack_match(inner);
     inner = unpack_try(inner);
     inner = unpack_call_chain(inner);
-    inner = unpack_await(inner);
+    if let Some(await_) = higher::Await::hir(inner) {
+        inner = await_.future;
+    }
     // we type-check it to get whether it's a read/write or their vectorized forms
     // and keep only the ones that are produce io amount
     check_io_mode(cx, inner)
@@ -233,22 +236,6 @@ fn unpack_match<'a>(mut expr: &'a hir::Expr<'a>) -> &'a hir::Expr<'a> {
     expr
 }
 
-/// If `expr` is an (e).await, return the inner expression "e" that's being
-/// waited on.  Otherwise return None.
-fn unpack_await<'a>(expr: &'a hir::Expr<'a>) -> &hir::Expr<'a> {
-    if let ExprKind::Match(expr, _, hir::MatchSource::AwaitDesugar) = expr.kind {
-        if let ExprKind::Call(func, [ref arg_0, ..]) = expr.kind {
-            if matches!(
-                func.kind,
-                ExprKind::Path(hir::QPath::LangItem(hir::LangItem::IntoFutureIntoFuture, ..))
-            ) {
-                return arg_0;
-            }
-        }
-    }
-    expr
-}
-
 /// Check whether the current expr is a function call for an IO operation
 fn check_io_mode(cx: &LateContext<'_>, call: &hir::Expr<'_>) -> Option<IoOp> {
     let ExprKind::MethodCall(path, ..) = call.kind else {
@@ -266,10 +253,10 @@ fn check_io_mode(cx: &LateContext<'_>, call: &hir::Expr<'_>) -> Option<IoOp> {
     match (
         is_trait_method(cx, call, sym::IoRead),
         is_trait_method(cx, call, sym::IoWrite),
-        match_trait_method(cx, call, &paths::FUTURES_IO_ASYNCREADEXT)
-            || match_trait_method(cx, call, &paths::TOKIO_IO_ASYNCREADEXT),
-        match_trait_method(cx, call, &paths::TOKIO_IO_ASYNCWRITEEXT)
-            || match_trait_method(cx, call, &paths::FUTURES_IO_ASYNCWRITEEXT),
+        is_trait_method_in(cx, call, ecosystem::FUTURES_IO_ASYNC_READ_EXT)
+            || is_trait_method_in(cx, call, ecosystem::TOKIO_IO_ASYNC_READ_EXT),
+        is_trait_method_in(cx, call, ecosystem::TOKIO_IO_ASYNC_WRITE_EXT)
+            || is_trait_method_in(cx, call, ecosystem::FUTURES_IO_ASYNC_WRITE_EXT),
     ) {
         (true, _, _, _) => Some(IoOp::SyncRead(vectorized)),
         (_, true, _, _) => Some(IoOp::SyncWrite(vectorized)),
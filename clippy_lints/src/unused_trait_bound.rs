@@ -0,0 +1,440 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet_opt;
+use rustc_errors::Applicability;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::{walk_expr, walk_pat, Visitor};
+use rustc_hir::{
+    AnonConst, Expr, ExprKind, GenericBound, Generics, Impl, ImplItem, ImplItemKind, Item, ItemKind, Mutability, Node,
+    Pat, PredicateOrigin, TraitBoundModifier, WherePredicate,
+};
+use rustc_infer::infer::TyCtxtInferExt;
+use rustc_infer::traits::{Obligation, ObligationCause};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::hir::nested_filter;
+use rustc_middle::ty::adjustment::{Adjust, PointerCoercion};
+use rustc_middle::ty::{
+    self, Binder, ClauseKind, GenericArgsRef, ParamEnv, PredicateKind, ToPredicate, Ty, TyCtxt, TypeVisitableExt,
+    TypeckResults,
+};
+use rustc_session::declare_lint_pass;
+use rustc_span::Span;
+use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt as _;
+use rustc_trait_selection::traits::{elaborate, Reveal};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for trait bounds on the generic parameters of functions and inherent impls which
+    /// aren't required by the bodies of the functions, nor by their signatures or other bounds.
+    ///
+    /// ### Why is this bad?
+    /// Each bound is a requirement on the callers, who may have to implement the trait or add
+    /// the bound to their own functions for nothing.
+    ///
+    /// ### Known problems
+    /// A bound may be a deliberate part of the API, kept so it can be used later without a
+    /// breaking change. Bounds on `Copy` and `Sized` aren't linted, as they can be required by
+    /// moves of the values, nor bounds on the `Fn` traits, which guide the inference of the
+    /// closures passed to the function.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn largest<T: PartialOrd + Clone>(values: &[T]) -> Option<&T> {
+    ///     values.iter().reduce(|a, b| if b > a { b } else { a })
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// fn largest<T: PartialOrd>(values: &[T]) -> Option<&T> {
+    ///     values.iter().reduce(|a, b| if b > a { b } else { a })
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub UNUSED_TRAIT_BOUND,
+    pedantic,
+    "trait bounds on generic parameters which nothing requires"
+}
+
+declare_lint_pass!(UnusedTraitBound => [UNUSED_TRAIT_BOUND]);
+
+impl<'tcx> LateLintPass<'tcx> for UnusedTraitBound {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if item.span.from_expansion() {
+            return;
+        }
+        match item.kind {
+            ItemKind::Fn(_, generics, _) => check(cx, generics, item.owner_id.def_id, &[item.owner_id.def_id]),
+            ItemKind::Impl(Impl {
+                of_trait: None,
+                generics,
+                items,
+                ..
+            }) if items
+                .iter()
+                .all(|item| matches!(item.kind, rustc_hir::AssocItemKind::Fn { .. })) =>
+            {
+                let owners: Vec<_> = items
+                    .iter()
+                    .map(|item| item.id.owner_id.def_id)
+                    .chain([item.owner_id.def_id])
+                    .collect();
+                check(cx, generics, item.owner_id.def_id, &owners);
+            },
+            _ => {},
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx ImplItem<'tcx>) {
+        if let ImplItemKind::Fn(..) = item.kind
+            && !item.span.from_expansion()
+            && let Node::Item(Item {
+                kind: ItemKind::Impl(Impl { of_trait: None, .. }),
+                ..
+            }) = cx.tcx.parent_hir_node(item.hir_id())
+        {
+            check(cx, item.generics, item.owner_id.def_id, &[item.owner_id.def_id]);
+        }
+    }
+}
+
+/// A trait bound written in the generics of an item
+struct Candidate<'tcx> {
+    predicate_pos: usize,
+    bound_pos: usize,
+    /// The clauses of the item coming from the bound, e.g. `F: Fn<(u8,)>` and the projection of
+    /// its output for `F: Fn(u8) -> u8`
+    clauses: Vec<ty::Clause<'tcx>>,
+}
+
+/// Lints the trait bounds in `generics`, the generics of `def_id`, which aren't required by any
+/// of `owners`: `def_id` itself, and the methods of an impl.
+fn check<'tcx>(cx: &LateContext<'tcx>, generics: &'tcx Generics<'tcx>, def_id: LocalDefId, owners: &[LocalDefId]) {
+    let tcx = cx.tcx;
+    let candidates = candidates(tcx, generics, def_id);
+    if candidates.is_empty() {
+        return;
+    }
+    let Some(obligations) = owners
+        .iter()
+        .map(|&owner| Some((owner, obligations(tcx, owner)?)))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return;
+    };
+
+    // Remove the bounds one by one, so that two bounds implying one another aren't both removed.
+    let mut removed: Vec<ty::Clause<'tcx>> = Vec::new();
+    let mut unused = Vec::new();
+    for candidate in &candidates {
+        let without: Vec<_> = removed.iter().chain(&candidate.clauses).copied().collect();
+        let is_required = obligations.iter().any(|(owner, obligations)| {
+            let full_env = param_env(tcx, *owner, &[]);
+            let env = param_env(tcx, *owner, &without);
+            let infcx = tcx.infer_ctxt().build();
+            let holds = |env, predicate| {
+                infcx.predicate_must_hold_modulo_regions(&Obligation::new(
+                    tcx,
+                    ObligationCause::dummy(),
+                    env,
+                    predicate,
+                ))
+            };
+            obligations
+                .iter()
+                .filter(|(source, _)| !source.is_some_and(|source| without.contains(&source)))
+                .any(|&(_, predicate)| holds(full_env, predicate) && !holds(env, predicate))
+        });
+        if !is_required {
+            removed.extend(&candidate.clauses);
+            unused.push(candidate);
+        }
+    }
+
+    for (predicate_pos, predicate) in generics.predicates.iter().enumerate() {
+        let unused_bounds: Vec<_> = unused
+            .iter()
+            .filter(|candidate| candidate.predicate_pos == predicate_pos)
+            .map(|candidate| candidate.bound_pos)
+            .collect();
+        if let WherePredicate::BoundPredicate(bound_predicate) = predicate
+            && !unused_bounds.is_empty()
+        {
+            emit(cx, generics, predicate_pos, bound_predicate, &unused_bounds);
+        }
+    }
+}
+
+fn emit(
+    cx: &LateContext<'_>,
+    generics: &Generics<'_>,
+    predicate_pos: usize,
+    predicate: &rustc_hir::WhereBoundPredicate<'_>,
+    unused_bounds: &[usize],
+) {
+    let bounds = predicate.bounds;
+    let spans: Vec<Span> = unused_bounds.iter().map(|&pos| bounds[pos].span()).collect();
+    let (msg, help) = if let [_] = unused_bounds {
+        ("this trait bound is never required", "remove the bound")
+    } else {
+        ("these trait bounds are never required", "remove the bounds")
+    };
+    span_lint_and_then(cx, UNUSED_TRAIT_BOUND, spans, msg, |diag| {
+        let (Some(first), Some(last)) = (bounds.first(), bounds.last()) else {
+            return;
+        };
+        let remaining: Option<Vec<_>> = bounds
+            .iter()
+            .enumerate()
+            .filter(|(pos, _)| !unused_bounds.contains(pos))
+            .map(|(_, bound)| snippet_opt(cx, bound.span()))
+            .collect();
+        let Some(remaining) = remaining else {
+            return;
+        };
+        let (span, sugg) = if !remaining.is_empty() {
+            (first.span().to(last.span()), remaining.join(" + "))
+        } else if predicate.origin == PredicateOrigin::WhereClause {
+            if let [_] = bounds {
+                (generics.span_for_bound_removal(predicate_pos, 0), String::new())
+            } else {
+                (where_predicate_removal_span(generics, predicate_pos), String::new())
+            }
+        } else {
+            // `T: A + B`
+            //  ^^^^^^^^
+            (predicate.bounded_ty.span.shrink_to_hi().to(last.span()), String::new())
+        };
+        diag.span_suggestion(span, help, sugg, Applicability::MaybeIncorrect);
+    });
+}
+
+/// The span to remove the predicate `pos` of a `where` clause with its comma, or the whole
+/// clause if it's the only predicate.
+fn where_predicate_removal_span(generics: &Generics<'_>, pos: usize) -> Span {
+    let span = generics.predicates[pos].span();
+    if let Some(next) = generics.predicates.get(pos + 1)
+        && next.in_where_clause()
+    {
+        span.until(next.span())
+    } else if let Some(prev) = pos.checked_sub(1).map(|pos| &generics.predicates[pos])
+        && prev.in_where_clause()
+    {
+        prev.span().shrink_to_hi().to(span)
+    } else {
+        generics.where_clause_span
+    }
+}
+
+/// The trait bounds which can be linted. Bounds on `Copy` and `Sized` can be required by moves,
+/// which aren't obligations, and bounds on the `Fn` traits are needed to infer the signature of
+/// the closures passed by the callers.
+fn candidates<'tcx>(tcx: TyCtxt<'tcx>, generics: &Generics<'_>, def_id: LocalDefId) -> Vec<Candidate<'tcx>> {
+    let predicates = tcx.predicates_of(def_id).predicates;
+    let lang_items = tcx.lang_items();
+    let mut candidates = Vec::new();
+    for (predicate_pos, predicate) in generics.predicates.iter().enumerate() {
+        let WherePredicate::BoundPredicate(predicate) = predicate else {
+            continue;
+        };
+        if predicate.origin == PredicateOrigin::ImplTrait || predicate.span.from_expansion() {
+            continue;
+        }
+        for (bound_pos, bound) in predicate.bounds.iter().enumerate() {
+            let GenericBound::Trait(poly_trait_ref, TraitBoundModifier::None) = bound else {
+                continue;
+            };
+            let Some(trait_id) = poly_trait_ref.trait_ref.trait_def_id() else {
+                continue;
+            };
+            let clauses: Vec<_> = predicates
+                .iter()
+                .filter(|(_, span)| bound.span().contains(*span))
+                .map(|&(clause, _)| clause)
+                .collect();
+            let implies_copy = elaborate(tcx, clauses.iter().copied())
+                .filter_map(ty::Clause::as_trait_clause)
+                .any(|trait_clause| Some(trait_clause.def_id()) == lang_items.copy_trait());
+            if !clauses.is_empty()
+                && !implies_copy
+                && !tcx.is_fn_trait(trait_id)
+                && ![lang_items.sized_trait(), lang_items.drop_trait()].contains(&Some(trait_id))
+            {
+                candidates.push(Candidate {
+                    predicate_pos,
+                    bound_pos,
+                    clauses,
+                });
+            }
+        }
+    }
+    candidates
+}
+
+/// The param env of `owner` without the `removed` clauses
+fn param_env<'tcx>(tcx: TyCtxt<'tcx>, owner: LocalDefId, removed: &[ty::Clause<'tcx>]) -> ParamEnv<'tcx> {
+    let clauses = tcx
+        .predicates_of(owner)
+        .instantiate_identity(tcx)
+        .predicates
+        .into_iter()
+        .filter(|clause| !removed.contains(clause));
+    ParamEnv::new(tcx.mk_clauses_from_iter(elaborate(tcx, clauses)), Reveal::UserFacing)
+}
+
+/// The obligations of the body and signature of a function, or of the self type of an impl,
+/// each with the clause of the item it comes from, if any. Returns `None` if they can't be known.
+fn obligations(tcx: TyCtxt<'_>, owner: LocalDefId) -> Option<Vec<(Option<ty::Clause<'_>>, ty::Predicate<'_>)>> {
+    let mut collector = ObligationCollector {
+        tcx,
+        typeck: None,
+        obligations: Vec::new(),
+    };
+    match tcx.def_kind(owner) {
+        DefKind::Fn | DefKind::AssocFn => {
+            if tcx.asyncness(owner).is_async() {
+                return None;
+            }
+            let sig = tcx.liberate_late_bound_regions(owner.to_def_id(), tcx.fn_sig(owner).instantiate_identity());
+            // The hidden type of an opaque return type may need the bounds.
+            if sig.output().has_opaque_types() {
+                return None;
+            }
+            for ty in sig.inputs_and_output {
+                collector.well_formed(ty);
+            }
+            if tcx.hir().maybe_body_owned_by(owner).is_some() {
+                let typeck = tcx.typeck(owner);
+                if typeck.tainted_by_errors.is_some() {
+                    return None;
+                }
+                collector.typeck = Some(typeck);
+                collector.visit_expr(tcx.hir().body(tcx.hir().body_owned_by(owner)).value);
+            }
+        },
+        DefKind::Impl { .. } => collector.well_formed(tcx.type_of(owner).instantiate_identity()),
+        _ => return None,
+    }
+
+    let mut obligations = collector.obligations;
+    // The other bounds can require the bound through the where clauses of their trait.
+    for &(clause, _) in tcx.predicates_of(owner).predicates {
+        if let Some(trait_clause) = clause.as_trait_clause()
+            && let Some(trait_clause) = trait_clause.no_bound_vars()
+        {
+            obligations.extend(
+                tcx.predicates_of(trait_clause.def_id())
+                    .instantiate(tcx, trait_clause.trait_ref.args)
+                    .predicates
+                    .into_iter()
+                    .filter(|&predicate| predicate != clause)
+                    .map(|predicate| (Some(clause), predicate.as_predicate())),
+            );
+        }
+    }
+    Some(obligations)
+}
+
+struct ObligationCollector<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    typeck: Option<&'tcx TypeckResults<'tcx>>,
+    obligations: Vec<(Option<ty::Clause<'tcx>>, ty::Predicate<'tcx>)>,
+}
+
+impl<'tcx> ObligationCollector<'tcx> {
+    fn push(&mut self, predicate: ty::Predicate<'tcx>) {
+        if !predicate.references_error() {
+            self.obligations.push((None, self.tcx.erase_regions(predicate)));
+        }
+    }
+
+    fn well_formed(&mut self, ty: Ty<'tcx>) {
+        // The type of a closure has the generics of its parent, bounds included. Its body is
+        // visited instead.
+        let has_closure = ty.walk().any(|arg| {
+            arg.as_type().is_some_and(|ty| {
+                matches!(
+                    ty.kind(),
+                    ty::Closure(..) | ty::Coroutine(..) | ty::CoroutineClosure(..)
+                )
+            })
+        });
+        if ty.has_param() && !has_closure {
+            self.push(
+                self.tcx
+                    .mk_predicate(Binder::dummy(PredicateKind::Clause(ClauseKind::WellFormed(ty.into())))),
+            );
+        }
+    }
+
+    fn item_predicates(&mut self, def_id: DefId, args: GenericArgsRef<'tcx>) {
+        for clause in self.tcx.predicates_of(def_id).instantiate(self.tcx, args).predicates {
+            self.push(clause.as_predicate());
+        }
+    }
+
+    fn implements(&mut self, ty: Ty<'tcx>, trait_id: Option<DefId>, args: &[ty::GenericArg<'tcx>]) {
+        if let Some(trait_id) = trait_id {
+            let trait_ref = ty::TraitRef::new(self.tcx, trait_id, [ty.into()].into_iter().chain(args.iter().copied()));
+            self.push(trait_ref.to_predicate(self.tcx));
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for ObligationCollector<'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_expr(&mut self, e: &'tcx Expr<'tcx>) {
+        let typeck = self.typeck.unwrap();
+        // Method calls, and overloaded operators and calls
+        if let Some(def_id) = typeck.type_dependent_def_id(e.hir_id) {
+            self.item_predicates(def_id, typeck.node_args(e.hir_id));
+        }
+        if let ExprKind::Path(ref qpath) = e.kind
+            && let Res::Def(
+                DefKind::Fn | DefKind::AssocFn | DefKind::Const | DefKind::AssocConst | DefKind::Static { .. },
+                def_id,
+            ) = typeck.qpath_res(qpath, e.hir_id)
+            && let Some(args) = typeck.node_args_opt(e.hir_id)
+        {
+            self.item_predicates(def_id, args);
+        }
+
+        let mut ty = typeck.expr_ty(e);
+        self.well_formed(ty);
+        for adjustment in typeck.expr_adjustments(e) {
+            match adjustment.kind {
+                Adjust::Deref(Some(overloaded)) => {
+                    let trait_id = match overloaded.mutbl {
+                        Mutability::Not => self.tcx.lang_items().deref_trait(),
+                        Mutability::Mut => self.tcx.lang_items().deref_mut_trait(),
+                    };
+                    self.implements(ty, trait_id, &[]);
+                },
+                Adjust::Pointer(PointerCoercion::Unsize) => {
+                    self.implements(
+                        ty,
+                        self.tcx.lang_items().coerce_unsized_trait(),
+                        &[adjustment.target.into()],
+                    );
+                },
+                _ => {},
+            }
+            ty = adjustment.target;
+        }
+        walk_expr(self, e);
+    }
+
+    // Array lengths are typeck'd on their own, and can't use the generic parameters.
+    fn visit_anon_const(&mut self, _: &'tcx AnonConst) {}
+
+    fn visit_pat(&mut self, pat: &'tcx Pat<'tcx>) {
+        if let Some(ty) = self.typeck.unwrap().node_type_opt(pat.hir_id) {
+            self.well_formed(ty);
+        }
+        walk_pat(self, pat);
+    }
+}
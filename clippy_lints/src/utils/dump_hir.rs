@@ -1,3 +1,5 @@
+#![allow(clippy::print_in_library)]
+
 use clippy_utils::get_attr;
 use hir::TraitItem;
 use rustc_hir as hir;
@@ -1,6 +1,8 @@
 pub mod almost_standard_lint_formulation;
 pub mod collapsible_calls;
 pub mod compiler_lint_functions;
+pub mod configuration_lints;
+pub mod crate_walk_in_node_check;
 pub mod interning_defined_symbol;
 pub mod invalid_paths;
 pub mod lint_without_lint_pass;
@@ -8,5 +10,6 @@ pub mod metadata_collector;
 pub mod msrv_attr_impl;
 pub mod outer_expn_data_pass;
 pub mod produce_ice;
+pub mod unguarded_span_lint;
 pub mod unnecessary_def_path;
 pub mod unsorted_clippy_utils_paths;
@@ -0,0 +1,313 @@
+use crate::utils::internal_lints::lint_without_lint_pass::{extract_clippy_version_value, is_lint_ref_type};
+use clippy_config::{get_configuration_metadata, ClippyConfiguration};
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::visitors::{for_each_expr, for_each_expr_with_closures, Descend};
+use clippy_utils::{match_def_path, path_to_local, paths, peel_blocks};
+use rustc_ast as ast;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind, HirId, Item, ItemKind, LetStmt, Mutability, PatKind, QPath, StmtKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty;
+use rustc_session::impl_lint_pass;
+use rustc_span::{Span, Symbol};
+use std::ops::ControlFlow;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks that the fields of `Conf` read by each lint pass in `register_lints` match the
+    /// lints listed in the `Lint:` line of their documentation: a pass which reads a field must
+    /// have one of the listed lints, and each listed lint must belong to a pass which reads the
+    /// field. Fields which are read outside of the registration of a pass apply to all lints, and
+    /// aren't checked.
+    ///
+    /// Also checks that the documentation of new lints mentions the configuration keys listing
+    /// them, e.g. `` `too-many-lines-threshold` `` or `too-many-lines-threshold = 100` in a TOML
+    /// example.
+    ///
+    /// ### Why is this bad?
+    /// The `Lint:` lines generate the configuration sections of the documentation of the lints
+    /// and the list of affected lints of the book, which are wrong when they are out of date.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// define_Conf! {
+    ///     /// Lint: FOO.
+    ///     (threshold: u64 = 10),
+    /// }
+    /// store.register_late_pass(move |_| Box::new(bar::Bar::new(threshold)));
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// define_Conf! {
+    ///     /// Lint: BAR.
+    ///     (threshold: u64 = 10),
+    /// }
+    /// store.register_late_pass(move |_| Box::new(bar::Bar::new(threshold)));
+    /// ```
+    pub INCONSISTENT_CONFIGURATION_LINTS,
+    internal,
+    "the lints affected by a configuration key don't match the lint passes reading it"
+}
+
+pub struct ConfigurationLints {
+    /// The configuration keys, by the name of their field in `Conf`.
+    metadata: FxHashMap<String, ClippyConfiguration>,
+}
+
+impl ConfigurationLints {
+    pub fn new() -> Self {
+        Self {
+            metadata: get_configuration_metadata()
+                .into_iter()
+                .map(|conf| (conf.name.replace('-', "_"), conf))
+                .collect(),
+        }
+    }
+}
+
+impl_lint_pass!(ConfigurationLints => [INCONSISTENT_CONFIGURATION_LINTS]);
+
+impl<'tcx> LateLintPass<'tcx> for ConfigurationLints {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if let ItemKind::Static(ty, Mutability::Not, _) = item.kind
+            && is_lint_ref_type(cx, ty)
+            && extract_clippy_version_value(cx, item).is_some_and(|version| is_new_version(version.as_str()))
+        {
+            let lint = item.ident.as_str().to_lowercase();
+            let docs: String = cx
+                .tcx
+                .hir()
+                .attrs(item.hir_id())
+                .iter()
+                .filter_map(|attr| ast::Attribute::doc_str(attr).map(|sym| sym.as_str().to_owned() + "\n"))
+                .collect();
+            for conf in self.metadata.values() {
+                if conf.deprecation_reason.is_none() && conf.lints.contains(&lint) && !mentions(&docs, &conf.name) {
+                    span_lint_and_help(
+                        cx,
+                        INCONSISTENT_CONFIGURATION_LINTS,
+                        item.ident.span,
+                        format!(
+                            "the configuration `{}` lists `{lint}`, but its documentation doesn't mention it",
+                            conf.name
+                        ),
+                        None,
+                        format!("mention `{}` in the documentation of the lint", conf.name),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_local(&mut self, cx: &LateContext<'tcx>, local: &'tcx LetStmt<'_>) {
+        if let PatKind::Struct(_, field_pats, _) = local.pat.kind
+            && let ty::Adt(adt, _) = cx.typeck_results().pat_ty(local.pat).kind()
+            && match_def_path(cx, adt.did(), &paths::CONF)
+            && let Some(body_id) = cx.enclosing_body
+        {
+            let mut fields = FxHashMap::default();
+            for field_pat in field_pats {
+                if let PatKind::Binding(_, id, ..) = field_pat.pat.kind {
+                    fields.insert(id, (field_pat.ident.name, field_pat.pat.span));
+                }
+            }
+            check_registrations(cx, &self.metadata, cx.tcx.hir().body(body_id).value, &fields);
+        }
+    }
+}
+
+/// Returns whether a `clippy::version` is at least the version of Clippy, i.e. whether the lint is
+/// added in this release. Older lints aren't required to mention their configuration keys.
+fn is_new_version(version: &str) -> bool {
+    let parse = |version: &str| -> Option<(u32, u32)> {
+        let mut parts = version.split('.').map(str::parse);
+        Some((parts.next()?.ok()?, parts.next()?.ok()?))
+    };
+    // The crate version `0.1.80` corresponds to the Rust version `1.80.0`.
+    let current = env!("CARGO_PKG_VERSION")
+        .split('.')
+        .nth(2)
+        .and_then(|minor| minor.parse().ok());
+    matches!((parse(version), current), (Some((1, minor)), Some(current)) if minor >= current)
+}
+
+/// Returns whether `docs` contains the configuration key `key`, e.g. in backticks or in a TOML
+/// example.
+fn mentions(docs: &str, key: &str) -> bool {
+    let is_key_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_');
+    docs.match_indices(key)
+        .any(|(start, _)| !docs[..start].ends_with(is_key_char) && !docs[start + key.len()..].starts_with(is_key_char))
+}
+
+/// How the fields of `Conf` are read by the function registering the lint passes.
+#[derive(Default)]
+struct Reads {
+    /// The fields read by each pass, with the span of its registration.
+    passes: Vec<(DefId, Span, FxHashSet<Symbol>)>,
+    /// The fields read outside of the registration of a pass.
+    global: FxHashSet<Symbol>,
+}
+
+fn check_registrations<'tcx>(
+    cx: &LateContext<'tcx>,
+    metadata: &FxHashMap<String, ClippyConfiguration>,
+    body: &'tcx Expr<'tcx>,
+    fields: &FxHashMap<HirId, (Symbol, Span)>,
+) {
+    // The locals initialized from fields, like `let msrv = || msrv.clone();`.
+    let mut aliases: FxHashMap<HirId, FxHashSet<Symbol>> = fields
+        .iter()
+        .map(|(&id, &(name, _))| (id, FxHashSet::from_iter([name])))
+        .collect();
+    // The initializers of the aliases, whose reads are attributed to the uses of the aliases.
+    let mut alias_inits = FxHashSet::default();
+    let mut reads = Reads::default();
+    for_each_expr_with_closures(cx, body, |e| {
+        if alias_inits.contains(&e.hir_id) {
+            return ControlFlow::Continue(Descend::No);
+        }
+        if let ExprKind::Block(block, _) = e.kind {
+            for stmt in block.stmts {
+                if let StmtKind::Let(local) = stmt.kind
+                    && let PatKind::Binding(_, id, ..) = local.pat.kind
+                    && let Some(init) = local.init
+                {
+                    let read = fields_read(init, &aliases);
+                    if !read.is_empty() {
+                        aliases.insert(id, read);
+                        alias_inits.insert(init.hir_id);
+                    }
+                }
+            }
+        }
+        if let ExprKind::MethodCall(segment, _, [arg], _) = e.kind
+            && matches!(segment.ident.as_str(), "register_late_pass" | "register_early_pass")
+            && let ExprKind::Closure(closure) = arg.kind
+        {
+            let closure_body = cx.tcx.hir().body(closure.body).value;
+            if let Some(pass) = constructed_pass(cx, closure_body) {
+                reads.passes.push((pass, e.span, fields_read(closure_body, &aliases)));
+            }
+            return ControlFlow::<!, _>::Continue(Descend::No);
+        }
+        if let Some(id) = path_to_local(e)
+            && let Some(read) = aliases.get(&id)
+        {
+            reads.global.extend(read.iter().copied());
+        }
+        ControlFlow::Continue(Descend::Yes)
+    });
+    report(cx, metadata, fields, &reads);
+}
+
+/// Returns the fields read by `expr`, directly or through the locals initialized from them.
+fn fields_read(expr: &Expr<'_>, aliases: &FxHashMap<HirId, FxHashSet<Symbol>>) -> FxHashSet<Symbol> {
+    let mut read = FxHashSet::default();
+    for_each_expr(expr, |e| {
+        if let Some(id) = path_to_local(e)
+            && let Some(fields) = aliases.get(&id)
+        {
+            read.extend(fields.iter().copied());
+        }
+        ControlFlow::<!>::Continue(())
+    });
+    read
+}
+
+/// Returns the type of the lint pass boxed by the body of a registration closure.
+fn constructed_pass<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Expr<'tcx>) -> Option<DefId> {
+    match cx.typeck_results().expr_ty(peel_blocks(body)).kind() {
+        ty::Adt(boxed, args) if boxed.is_box() => args.type_at(0).ty_adt_def().map(ty::AdtDef::did),
+        _ => None,
+    }
+}
+
+/// Returns the names of the lints of a lint pass, from the `get_lints` function generated by
+/// `declare_lint_pass!` and `impl_lint_pass!`.
+fn pass_lints(cx: &LateContext<'_>, pass: DefId) -> FxHashSet<String> {
+    let mut lints = FxHashSet::default();
+    for &impl_id in cx.tcx.inherent_impls(pass).into_iter().flatten() {
+        for item in cx.tcx.associated_items(impl_id).in_definition_order() {
+            if item.name.as_str() == "get_lints"
+                && let Some(local_id) = item.def_id.as_local()
+                && let Some(body_id) = cx.tcx.hir().maybe_body_owned_by(local_id)
+            {
+                let typeck = cx.tcx.typeck(local_id);
+                for_each_expr(cx.tcx.hir().body(body_id).value, |e| {
+                    if let ExprKind::Path(ref qpath @ (QPath::Resolved(..) | QPath::TypeRelative(..))) = e.kind
+                        && let Res::Def(DefKind::Static { .. }, lint) = typeck.qpath_res(qpath, e.hir_id)
+                    {
+                        lints.insert(cx.tcx.item_name(lint).as_str().to_lowercase());
+                    }
+                    ControlFlow::<!>::Continue(())
+                });
+            }
+        }
+    }
+    lints
+}
+
+fn report(
+    cx: &LateContext<'_>,
+    metadata: &FxHashMap<String, ClippyConfiguration>,
+    fields: &FxHashMap<HirId, (Symbol, Span)>,
+    reads: &Reads,
+) {
+    let pass_lints: Vec<_> = reads
+        .passes
+        .iter()
+        .map(|(pass, span, read)| (*pass, *span, read, pass_lints(cx, *pass)))
+        .collect();
+
+    for (pass, span, read, lints) in &pass_lints {
+        for field in *read {
+            if let Some(conf) = metadata.get(field.as_str())
+                && !lints.is_empty()
+                && !lints.iter().any(|lint| conf.lints.contains(lint))
+            {
+                span_lint_and_help(
+                    cx,
+                    INCONSISTENT_CONFIGURATION_LINTS,
+                    *span,
+                    format!(
+                        "`{}` reads the configuration `{}`, which doesn't list any of its lints",
+                        cx.tcx.item_name(*pass),
+                        conf.name
+                    ),
+                    None,
+                    "add the affected lints to the `Lint:` line of the configuration",
+                );
+            }
+        }
+    }
+
+    for &(name, span) in fields.values() {
+        let Some(conf) = metadata.get(name.as_str()) else {
+            continue;
+        };
+        if reads.global.contains(&name) || conf.deprecation_reason.is_some() {
+            continue;
+        }
+        for lint in &conf.lints {
+            if !pass_lints
+                .iter()
+                .any(|(_, _, read, lints)| read.contains(&name) && lints.contains(lint))
+            {
+                span_lint_and_help(
+                    cx,
+                    INCONSISTENT_CONFIGURATION_LINTS,
+                    span,
+                    format!(
+                        "the configuration `{}` lists `{lint}`, but its lint pass doesn't read it",
+                        conf.name
+                    ),
+                    None,
+                    "remove the lint from the `Lint:` line of the configuration, or pass the value to the lint pass",
+                );
+            }
+        }
+    }
+}
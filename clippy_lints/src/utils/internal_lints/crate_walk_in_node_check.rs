@@ -0,0 +1,115 @@
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{match_def_path, paths};
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind, ImplItem, ImplItemKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::TypeckResults;
+use rustc_session::declare_lint_pass;
+use std::ops::ControlFlow;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for walks of the whole crate, e.g. `tcx.hir().items()` or
+    /// `tcx.hir().visit_all_item_likes_in_crate(..)`, in the `check_*` methods of late lint passes
+    /// which are called for each node of the crate, like `check_expr` or `check_fn`.
+    ///
+    /// Only the calls written in the `check_*` methods are checked, not the ones of the functions
+    /// they call.
+    ///
+    /// ### Why is this bad?
+    /// Walking the crate for each of its nodes makes clippy quadratic in the size of the crate.
+    /// The walk should be done once, in `check_crate` or with `clippy_utils::crate_cache`, which
+    /// shares its result between the passes.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+    ///     let impls = cx.tcx.hir().items().filter(|id| is_impl(cx, *id)).count();
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// struct ImplCount(usize);
+    ///
+    /// fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+    ///     let impls = crate_cache::get_or_compute(cx, || {
+    ///         ImplCount(cx.tcx.hir().items().filter(|id| is_impl(cx, *id)).count())
+    ///     });
+    /// }
+    /// ```
+    pub CRATE_WALK_IN_NODE_CHECK,
+    internal,
+    "walking the whole crate in a lint pass method which is called for each node"
+}
+
+declare_lint_pass!(CrateWalkInNodeCheck => [CRATE_WALK_IN_NODE_CHECK]);
+
+/// The methods of late lint passes which are called once for the crate.
+const CRATE_METHODS: &[&str] = &["check_crate", "check_crate_post"];
+
+/// The methods of `TyCtxt` and of the HIR map which walk the whole crate.
+const CRATE_WALKS: &[&str] = &[
+    "all_local_trait_impls",
+    "body_owners",
+    "for_each_module",
+    "hir_crate_items",
+    "items",
+    "par_body_owners",
+    "par_for_each_module",
+    "visit_all_item_likes_in_crate",
+    "walk_attributes",
+    "walk_toplevel_module",
+];
+
+impl<'tcx> LateLintPass<'tcx> for CrateWalkInNodeCheck {
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx ImplItem<'_>) {
+        if item.ident.as_str().starts_with("check_")
+            && !CRATE_METHODS.contains(&item.ident.as_str())
+            && let ImplItemKind::Fn(_, body_id) = item.kind
+            && let Some(trait_id) = cx
+                .tcx
+                .trait_id_of_impl(cx.tcx.local_parent(item.owner_id.def_id).to_def_id())
+            && match_def_path(cx, trait_id, &paths::LATE_LINT_PASS)
+            && let Some(call) = first_crate_walk(cx, cx.tcx.typeck_body(body_id), cx.tcx.hir().body(body_id).value)
+        {
+            span_lint_hir_and_then(
+                cx,
+                CRATE_WALK_IN_NODE_CHECK,
+                call.hir_id,
+                call.span,
+                format!("the whole crate is walked each time `{}` is called", item.ident),
+                |diag| {
+                    diag.help("walk it once, in `check_crate` or with `clippy_utils::crate_cache::get_or_compute`");
+                },
+            );
+        }
+    }
+}
+
+/// Returns the first call to a method of `rustc_middle` walking the whole crate.
+fn first_crate_walk<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    body: &'tcx Expr<'tcx>,
+) -> Option<&'tcx Expr<'tcx>> {
+    for_each_expr(body, |e| {
+        let def_id: Option<DefId> = match e.kind {
+            ExprKind::Call(callee, _) => match callee.kind {
+                ExprKind::Path(ref qpath) => typeck.qpath_res(qpath, callee.hir_id).opt_def_id(),
+                _ => None,
+            },
+            ExprKind::MethodCall(..) => typeck.type_dependent_def_id(e.hir_id),
+            _ => None,
+        };
+        if let Some(def_id) = def_id
+            && CRATE_WALKS.contains(&cx.tcx.item_name(def_id).as_str())
+            && cx.tcx.crate_name(def_id.krate).as_str() == "rustc_middle"
+        {
+            ControlFlow::Break(e)
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+}
@@ -184,7 +184,6 @@ This lint has the following configuration variables:
         self.config
             .iter()
             .filter(|config| config.deprecation_reason.is_none())
-            .filter(|config| !config.lints.is_empty())
             .map(map_fn)
             .join("\n")
     }
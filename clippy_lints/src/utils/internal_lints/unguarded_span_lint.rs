@@ -0,0 +1,263 @@
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::visitors::{for_each_expr, Descend};
+use clippy_utils::{match_def_path, paths};
+use rustc_hir::{BinOpKind, Expr, ExprKind, ImplItem, ImplItemKind, Node, QPath, StmtKind, UnOp};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::TypeckResults;
+use rustc_session::declare_lint_pass;
+use rustc_span::Symbol;
+use std::ops::ControlFlow;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls to the `span_lint*` functions in the `check_*` methods of lint passes
+    /// which look at expressions, statements, patterns and types, when the call isn't dominated by a
+    /// check of the macro expansions, e.g. `in_external_macro`, `Span::from_expansion` or
+    /// `clippy_utils::macros::lintability`: the check must be in a condition leading to the call, or
+    /// in a statement coming before it, such as an early `return`.
+    ///
+    /// Only the calls written in the `check_*` methods are checked, not the ones of the functions
+    /// they call.
+    ///
+    /// ### Why is this bad?
+    /// The code produced by macros often can't be changed by the user, so linting it is one of the
+    /// most common sources of false positives.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+    ///     if let ExprKind::Lit(_) = expr.kind {
+    ///         span_lint(cx, LINT, expr.span, "message");
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+    ///     if let ExprKind::Lit(_) = expr.kind
+    ///         && !in_external_macro(cx.sess(), expr.span)
+    ///     {
+    ///         span_lint(cx, LINT, expr.span, "message");
+    ///     }
+    /// }
+    /// ```
+    pub UNGUARDED_SPAN_LINT,
+    internal,
+    "calling `span_lint*` without checking whether the code comes from a macro expansion"
+}
+
+declare_lint_pass!(UnguardedSpanLint => [UNGUARDED_SPAN_LINT]);
+
+/// The methods of lint passes which are called for code which macros commonly produce.
+const CHECKED_METHODS: &[&str] = &[
+    "check_arm",
+    "check_block",
+    "check_expr",
+    "check_expr_post",
+    "check_local",
+    "check_pat",
+    "check_stmt",
+    "check_ty",
+];
+
+/// The names of the functions and methods which look at the expansions of spans.
+const GUARDS: &[&str] = &[
+    "eq_ctxt",
+    "expn_is_local",
+    "first_node_in_macro",
+    "from_expansion",
+    "in_external_macro",
+    "is_expn_of",
+    "is_from_proc_macro",
+    "is_root",
+    "lintability",
+    "macro_backtrace",
+    "matching_root_macro_call",
+    "outer_expn_data",
+    "root_macro_call",
+    "root_macro_call_first_node",
+    "span_is_local",
+    "walk_span_to_context",
+];
+
+impl<'tcx> LateLintPass<'tcx> for UnguardedSpanLint {
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx ImplItem<'_>) {
+        if CHECKED_METHODS.contains(&item.ident.as_str())
+            && let ImplItemKind::Fn(_, body_id) = item.kind
+            && let Some(trait_id) = cx
+                .tcx
+                .trait_id_of_impl(cx.tcx.local_parent(item.owner_id.def_id).to_def_id())
+            && (match_def_path(cx, trait_id, &paths::LATE_LINT_PASS)
+                || match_def_path(cx, trait_id, &paths::EARLY_LINT_PASS))
+            && let Some(call) = first_unguarded_call(cx, cx.tcx.typeck_body(body_id), cx.tcx.hir().body(body_id).value)
+        {
+            span_lint_hir_and_then(
+                cx,
+                UNGUARDED_SPAN_LINT,
+                call.hir_id,
+                call.span,
+                "`span_lint*` is called without checking whether the code comes from a macro expansion",
+                |diag| {
+                    diag.help("check the span with e.g. `in_external_macro` or `clippy_utils::macros::lintability`");
+                },
+            );
+        }
+    }
+}
+
+/// Returns the first call to a `span_lint*` function of `clippy_utils` which isn't dominated by a
+/// guard.
+fn first_unguarded_call<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    body: &'tcx Expr<'tcx>,
+) -> Option<&'tcx Expr<'tcx>> {
+    for_each_expr(body, |e| {
+        if let ExprKind::Call(callee, _) = e.kind
+            && let ExprKind::Path(ref qpath) = callee.kind
+            && called_name(e).is_some_and(|name| name.as_str().starts_with("span_lint"))
+            && let Some(def_id) = typeck.qpath_res(qpath, callee.hir_id).opt_def_id()
+            && cx.tcx.crate_name(def_id.krate).as_str() == "clippy_utils"
+            && !is_guarded(cx, typeck, e)
+        {
+            ControlFlow::Break(e)
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+}
+
+fn called_name(e: &Expr<'_>) -> Option<Symbol> {
+    match e.kind {
+        ExprKind::Call(callee, _) => match callee.kind {
+            ExprKind::Path(QPath::Resolved(_, path)) => path.segments.last().map(|s| s.ident.name),
+            ExprKind::Path(QPath::TypeRelative(_, segment)) => Some(segment.ident.name),
+            _ => None,
+        },
+        ExprKind::MethodCall(segment, ..) => Some(segment.ident.name),
+        _ => None,
+    }
+}
+
+/// Checks if a guard is evaluated on every path leading to the call, i.e. in a condition of an
+/// enclosing `if` or `match`, in the initializer of an enclosing `let-else`, or in a statement
+/// coming before it in an enclosing block, such as the condition of an early `return`.
+fn is_guarded(cx: &LateContext<'_>, typeck: &TypeckResults<'_>, call: &Expr<'_>) -> bool {
+    let mut child = call.hir_id;
+    for (parent_id, parent) in cx.tcx.hir().parent_iter(call.hir_id) {
+        let guarded =
+            match parent {
+                Node::Expr(parent) => match parent.kind {
+                    ExprKind::If(cond, then, _) if then.hir_id == child => evaluates_guard(cond, Branch::Then),
+                    ExprKind::If(cond, _, Some(els)) if els.hir_id == child => evaluates_guard(cond, Branch::Else),
+                    ExprKind::Match(scrutinee, ..) => evaluates_guard(scrutinee, Branch::Any),
+                    _ => false,
+                },
+                Node::Arm(arm) => arm.guard.is_some_and(|guard| evaluates_guard(guard, Branch::Then)),
+                Node::LetStmt(local) => {
+                    local.els.is_some_and(|els| els.hir_id == child)
+                        && local.init.is_some_and(|init| evaluates_guard(init, Branch::Any))
+                },
+                Node::Block(block) => block
+                    .stmts
+                    .iter()
+                    .take_while(|stmt| stmt.hir_id != child)
+                    .any(|stmt| match stmt.kind {
+                        StmtKind::Let(local) => local.init.is_some_and(|init| evaluates_guard_before(typeck, init)),
+                        StmtKind::Expr(e) | StmtKind::Semi(e) => evaluates_guard_before(typeck, e),
+                        StmtKind::Item(_) => false,
+                    }),
+                Node::ImplItem(_) => return false,
+                _ => false,
+            };
+        if guarded {
+            return true;
+        }
+        child = parent_id;
+    }
+    false
+}
+
+/// Checks if a guard is called whenever the code following the expression is reached. When a
+/// branch of an `if` diverges, e.g. with an early `return`, the code following it is only reached
+/// through the other branch.
+fn evaluates_guard_before(typeck: &TypeckResults<'_>, e: &Expr<'_>) -> bool {
+    if let ExprKind::If(cond, then, els) = e.kind {
+        let through_then = typeck.expr_ty(then).is_never()
+            || evaluates_guard(cond, Branch::Then)
+            || evaluates_guard(then, Branch::Any);
+        let through_else = match els {
+            Some(els) => {
+                typeck.expr_ty(els).is_never()
+                    || evaluates_guard(cond, Branch::Else)
+                    || evaluates_guard_before(typeck, els)
+            },
+            None => evaluates_guard(cond, Branch::Else),
+        };
+        through_then && through_else
+    } else {
+        evaluates_guard(e, Branch::Any)
+    }
+}
+
+/// The outcome of a condition which leads to the checked code.
+#[derive(Clone, Copy, PartialEq)]
+enum Branch {
+    /// The condition is true, so all the operands of `&&` are evaluated.
+    Then,
+    /// The condition is false, so all the operands of `||` are evaluated.
+    Else,
+    /// Any outcome, so only the first operand of `&&` and `||` is evaluated.
+    Any,
+}
+
+impl Branch {
+    fn negated(self) -> Self {
+        match self {
+            Self::Then => Self::Else,
+            Self::Else => Self::Then,
+            Self::Any => Self::Any,
+        }
+    }
+}
+
+/// Checks if a guard is called whenever the expression is evaluated to the given outcome.
+fn evaluates_guard(e: &Expr<'_>, branch: Branch) -> bool {
+    for_each_expr(e, |e| match e.kind {
+        ExprKind::If(cond, ..) | ExprKind::Match(cond, ..) if evaluates_guard(cond, Branch::Any) => {
+            ControlFlow::Break(())
+        },
+        ExprKind::If(..) | ExprKind::Match(..) | ExprKind::Loop(..) => ControlFlow::Continue(Descend::No),
+        ExprKind::Unary(UnOp::Not, inner) => {
+            if evaluates_guard(inner, branch.negated()) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(Descend::No)
+            }
+        },
+        ExprKind::Binary(op, lhs, rhs) if matches!(op.node, BinOpKind::And | BinOpKind::Or) => {
+            let rhs_evaluated = match op.node {
+                BinOpKind::And => branch == Branch::Then,
+                _ => branch == Branch::Else,
+            };
+            if evaluates_guard(lhs, branch) || (rhs_evaluated && evaluates_guard(rhs, branch)) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(Descend::No)
+            }
+        },
+        // Comparing the contexts of two spans, as `Span::eq_ctxt` does
+        ExprKind::Binary(op, lhs, rhs)
+            if matches!(op.node, BinOpKind::Eq | BinOpKind::Ne)
+                && [lhs, rhs]
+                    .iter()
+                    .any(|e| called_name(e).is_some_and(|name| name.as_str() == "ctxt")) =>
+        {
+            ControlFlow::Break(())
+        },
+        _ if called_name(e).is_some_and(|name| GUARDS.contains(&name.as_str())) => ControlFlow::Break(()),
+        _ => ControlFlow::Continue(Descend::Yes),
+    })
+    .is_some()
+}
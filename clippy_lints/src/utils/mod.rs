@@ -3,3 +3,4 @@ pub mod dump_hir;
 pub mod format_args_collector;
 #[cfg(feature = "internal")]
 pub mod internal_lints;
+pub mod threshold_attrs;
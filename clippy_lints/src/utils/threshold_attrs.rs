@@ -0,0 +1,15 @@
+use clippy_utils::thresholds::check_attr;
+use rustc_ast::Attribute;
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_session::declare_lint_pass;
+
+declare_lint_pass!(
+    /// Reports the malformed threshold attributes, e.g. `#[clippy::limit(..)]`, once each
+    ThresholdAttrs => []
+);
+
+impl LateLintPass<'_> for ThresholdAttrs {
+    fn check_attribute(&mut self, cx: &LateContext<'_>, attr: &Attribute) {
+        check_attr(cx.sess(), attr);
+    }
+}
@@ -1,12 +1,11 @@
+use clippy_utils::cargo::{target_kind, TargetKind};
 use clippy_utils::diagnostics::{span_lint, span_lint_and_then};
-use clippy_utils::macros::{format_arg_removal_span, root_macro_call_first_node, FormatArgsStorage, MacroCall};
+use clippy_utils::format_string::{FormatString, Piece, Placeholder};
+use clippy_utils::is_in_test_context;
+use clippy_utils::macros::{root_macro_call_first_node, FormatArgsStorage, MacroCall};
 use clippy_utils::source::{expand_past_previous_comma, snippet_opt};
-use clippy_utils::{is_in_cfg_test, is_in_test_function};
 use rustc_ast::token::LitKind;
-use rustc_ast::{
-    FormatArgPosition, FormatArgPositionKind, FormatArgs, FormatArgsPiece, FormatOptions, FormatPlaceholder,
-    FormatTrait,
-};
+use rustc_ast::FormatTrait;
 use rustc_errors::Applicability;
 use rustc_hir::{Expr, Impl, Item, ItemKind};
 use rustc_lint::{LateContext, LateLintPass, LintContext};
@@ -105,6 +104,41 @@ declare_clippy_lint! {
     "printing on stderr"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for printing on *stdout* or *stderr* in library crates, outside of tests, and
+    /// suggests to log the message with the facade configured in `logging-facade` instead.
+    ///
+    /// ### Why is this bad?
+    /// A library doesn't know where the output of the program using it should go. Printing
+    /// interleaves its messages with the output of the program, which can't silence or redirect
+    /// them, while a logging facade lets the program choose.
+    ///
+    /// ### Known problems
+    /// Libraries whose purpose is to print, e.g. to implement a command line interface, should
+    /// allow the lint.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// pub fn load(path: &Path) -> Config {
+    ///     println!("loading {}", path.display());
+    ///     // ..
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// pub fn load(path: &Path) -> Config {
+    ///     log::info!("loading {}", path.display());
+    ///     // ..
+    /// }
+    /// ```
+    #[clippy::version = "1.80.0"]
+    pub PRINT_IN_LIBRARY,
+    style,
+    "printing on stdout or stderr in a library"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for usage of `Debug` formatting. The purpose of this
@@ -239,13 +273,15 @@ pub struct Write {
     format_args: FormatArgsStorage,
     in_debug_impl: bool,
     allow_print_in_tests: bool,
+    logging_facade: String,
 }
 
 impl Write {
-    pub fn new(format_args: FormatArgsStorage, allow_print_in_tests: bool) -> Self {
+    pub fn new(format_args: FormatArgsStorage, allow_print_in_tests: bool, logging_facade: String) -> Self {
         Self {
             format_args,
             allow_print_in_tests,
+            logging_facade,
             ..Default::default()
         }
     }
@@ -256,6 +292,7 @@ impl_lint_pass!(Write => [
     PRINTLN_EMPTY_STRING,
     PRINT_STDOUT,
     PRINT_STDERR,
+    PRINT_IN_LIBRARY,
     USE_DEBUG,
     PRINT_LITERAL,
     WRITE_WITH_NEWLINE,
@@ -287,15 +324,19 @@ impl<'tcx> LateLintPass<'tcx> for Write {
             return;
         };
 
-        let is_build_script = cx
-            .sess()
-            .opts
-            .crate_name
-            .as_ref()
-            .map_or(false, |crate_name| crate_name == "build_script_build");
+        let target_kind = target_kind(cx);
+        let is_build_script = target_kind == TargetKind::BuildScript;
 
-        let allowed_in_tests = self.allow_print_in_tests
-            && (is_in_test_function(cx.tcx, expr.hir_id) || is_in_cfg_test(cx.tcx, expr.hir_id));
+        if matches!(
+            diag_name,
+            sym::print_macro | sym::println_macro | sym::eprint_macro | sym::eprintln_macro
+        ) && target_kind == TargetKind::Lib
+            && !is_in_test_context(cx, expr.hir_id)
+        {
+            check_print_in_library(cx, &macro_call, name, &self.logging_facade);
+        }
+
+        let allowed_in_tests = self.allow_print_in_tests && is_in_test_context(cx, expr.hir_id);
         match diag_name {
             sym::print_macro | sym::println_macro if !allowed_in_tests => {
                 if !is_build_script {
@@ -310,30 +351,28 @@ impl<'tcx> LateLintPass<'tcx> for Write {
         }
 
         if let Some(format_args) = self.format_args.get(cx, expr, macro_call.expn) {
+            let format_string = FormatString::new(format_args);
             // ignore `writeln!(w)` and `write!(v, some_macro!())`
-            if format_args.span.from_expansion() {
+            if format_string.span().from_expansion() {
                 return;
             }
 
             match diag_name {
                 sym::print_macro | sym::eprint_macro | sym::write_macro => {
-                    check_newline(cx, format_args, &macro_call, name);
+                    check_newline(cx, format_string, &macro_call, name);
                 },
                 sym::println_macro | sym::eprintln_macro | sym::writeln_macro => {
-                    check_empty_string(cx, format_args, &macro_call, name);
+                    check_empty_string(cx, format_string, &macro_call, name);
                 },
                 _ => {},
             }
 
-            check_literal(cx, format_args, name);
+            check_literal(cx, format_string, name);
 
             if !self.in_debug_impl {
-                for piece in &format_args.template {
-                    if let &FormatArgsPiece::Placeholder(FormatPlaceholder {
-                        span: Some(span),
-                        format_trait: FormatTrait::Debug,
-                        ..
-                    }) = piece
+                for placeholder in format_string.placeholders() {
+                    if let Some(span) = placeholder.span()
+                        && placeholder.format_trait() == FormatTrait::Debug
                     {
                         span_lint(cx, USE_DEBUG, span, "use of `Debug`-based formatting");
                     }
@@ -343,6 +382,30 @@ impl<'tcx> LateLintPass<'tcx> for Write {
     }
 }
 
+fn check_print_in_library(cx: &LateContext<'_>, macro_call: &MacroCall, name: &str, facade: &str) {
+    let level = if name.starts_with('e') { "warn" } else { "info" };
+    span_lint_and_then(
+        cx,
+        PRINT_IN_LIBRARY,
+        macro_call.span,
+        format!("use of `{name}!` in a library"),
+        |diag| {
+            // The logging macros need a message
+            if snippet_opt(cx, macro_call.span).is_some_and(|snippet| !snippet.ends_with("!()")) {
+                let name_span = cx.sess().source_map().span_until_char(macro_call.span, '!');
+                diag.span_suggestion(
+                    name_span,
+                    format!("log the message with `{facade}` instead"),
+                    format!("{facade}::{level}"),
+                    Applicability::MaybeIncorrect,
+                );
+            } else {
+                diag.help(format!("log a message with `{facade}` instead"));
+            }
+        },
+    );
+}
+
 fn is_debug_impl(cx: &LateContext<'_>, item: &Item<'_>) -> bool {
     if let ItemKind::Impl(Impl {
         of_trait: Some(trait_ref),
@@ -356,29 +419,24 @@ fn is_debug_impl(cx: &LateContext<'_>, item: &Item<'_>) -> bool {
     }
 }
 
-fn check_newline(cx: &LateContext<'_>, format_args: &FormatArgs, macro_call: &MacroCall, name: &str) {
-    let Some(FormatArgsPiece::Literal(last)) = format_args.template.last() else {
+fn check_newline(cx: &LateContext<'_>, format_string: FormatString<'_>, macro_call: &MacroCall, name: &str) {
+    let Some(Piece::Literal(last)) = format_string.pieces().last() else {
         return;
     };
 
     let count_vertical_whitespace = || {
-        format_args
-            .template
-            .iter()
-            .filter_map(|piece| match piece {
-                FormatArgsPiece::Literal(literal) => Some(literal),
-                FormatArgsPiece::Placeholder(_) => None,
-            })
-            .flat_map(|literal| literal.as_str().chars())
+        format_string
+            .literals()
+            .flat_map(str::chars)
             .filter(|ch| matches!(ch, '\r' | '\n'))
             .count()
     };
 
-    if last.as_str().ends_with('\n')
+    if last.ends_with('\n')
         // ignore format strings with other internal vertical whitespace
         && count_vertical_whitespace() == 1
     {
-        let mut format_string_span = format_args.span;
+        let mut format_string_span = format_string.span();
 
         let lint = if name == "write" {
             format_string_span = expand_past_previous_comma(cx, format_string_span);
@@ -399,7 +457,7 @@ fn check_newline(cx: &LateContext<'_>, format_args: &FormatArgs, macro_call: &Ma
                     return;
                 };
 
-                if format_args.template.len() == 1 && last.as_str() == "\n" {
+                if format_string.as_literal().as_deref() == Some("\n") {
                     // print!("\n"), write!(f, "\n")
 
                     diag.multipart_suggestion(
@@ -424,11 +482,9 @@ fn check_newline(cx: &LateContext<'_>, format_args: &FormatArgs, macro_call: &Ma
     }
 }
 
-fn check_empty_string(cx: &LateContext<'_>, format_args: &FormatArgs, macro_call: &MacroCall, name: &str) {
-    if let [FormatArgsPiece::Literal(literal)] = &format_args.template[..]
-        && literal.as_str() == "\n"
-    {
-        let mut span = format_args.span;
+fn check_empty_string(cx: &LateContext<'_>, format_string: FormatString<'_>, macro_call: &MacroCall, name: &str) {
+    if format_string.as_literal().as_deref() == Some("\n") {
+        let mut span = format_string.span();
 
         let lint = if name == "writeln" {
             span = expand_past_previous_comma(cx, span);
@@ -455,42 +511,28 @@ fn check_empty_string(cx: &LateContext<'_>, format_args: &FormatArgs, macro_call
     }
 }
 
-fn check_literal(cx: &LateContext<'_>, format_args: &FormatArgs, name: &str) {
-    let arg_index = |argument: &FormatArgPosition| argument.index.unwrap_or_else(|pos| pos);
-
+fn check_literal(cx: &LateContext<'_>, format_string: FormatString<'_>, name: &str) {
     let lint_name = if name.starts_with("write") {
         WRITE_LITERAL
     } else {
         PRINT_LITERAL
     };
 
-    let mut counts = vec![0u32; format_args.arguments.all_args().len()];
-    for piece in &format_args.template {
-        if let FormatArgsPiece::Placeholder(placeholder) = piece {
-            counts[arg_index(&placeholder.argument)] += 1;
-        }
-    }
-
     let mut suggestion: Vec<(Span, String)> = vec![];
     // holds index of replaced positional arguments; used to decrement the index of the remaining
     // positional arguments.
     let mut replaced_position: Vec<usize> = vec![];
     let mut sug_span: Option<Span> = None;
 
-    for piece in &format_args.template {
-        if let FormatArgsPiece::Placeholder(FormatPlaceholder {
-            argument,
-            span: Some(placeholder_span),
-            format_trait: FormatTrait::Display,
-            format_options,
-        }) = piece
-            && *format_options == FormatOptions::default()
-            && let index = arg_index(argument)
-            && counts[index] == 1
-            && let Some(arg) = format_args.arguments.by_index(index)
-            && let rustc_ast::ExprKind::Lit(lit) = &arg.expr.kind
-            && !arg.expr.span.from_expansion()
-            && let Some(value_string) = snippet_opt(cx, arg.expr.span)
+    for placeholder in format_string.placeholders() {
+        if let Some(placeholder_span) = placeholder.span()
+            && placeholder.is_plain_display()
+            && let Some(arg) = placeholder.arg
+            && format_string.use_count(arg.index) == 1
+            && let arg_expr = arg.ast_expr()
+            && let rustc_ast::ExprKind::Lit(lit) = &arg_expr.kind
+            && !arg_expr.span.from_expansion()
+            && let Some(value_string) = snippet_opt(cx, arg_expr.span)
         {
             let (replacement, replace_raw) = match lit.kind {
                 LitKind::Str | LitKind::StrRaw(_) => match extract_str_literal(&value_string) {
@@ -513,7 +555,7 @@ fn check_literal(cx: &LateContext<'_>, format_args: &FormatArgs, name: &str) {
                 _ => continue,
             };
 
-            let Some(format_string_snippet) = snippet_opt(cx, format_args.span) else {
+            let Some(format_string_snippet) = snippet_opt(cx, format_string.span()) else {
                 continue;
             };
             let format_string_is_raw = format_string_snippet.starts_with('r');
@@ -535,19 +577,19 @@ fn check_literal(cx: &LateContext<'_>, format_args: &FormatArgs, name: &str) {
                 },
             };
 
-            sug_span = Some(sug_span.unwrap_or(arg.expr.span).to(arg.expr.span));
+            sug_span = Some(sug_span.unwrap_or(arg_expr.span).to(arg_expr.span));
 
-            if let Some((_, index)) = positional_arg_piece_span(piece) {
+            if let Some((_, index)) = positional_arg_piece_span(placeholder) {
                 replaced_position.push(index);
             }
 
             if let Some(replacement) = replacement
                 // `format!("{}", "a")`, `format!("{named}", named = "b")
                 //              ~~~~~                      ~~~~~~~~~~~~~
-                && let Some(removal_span) = format_arg_removal_span(format_args, index)
+                && let Some(removal_span) = format_string.arg_removal_span(arg.index)
             {
                 let replacement = escape_braces(&replacement, !format_string_is_raw && !replace_raw);
-                suggestion.push((*placeholder_span, replacement));
+                suggestion.push((placeholder_span, replacement));
                 suggestion.push((removal_span, String::new()));
             }
         }
@@ -555,8 +597,8 @@ fn check_literal(cx: &LateContext<'_>, format_args: &FormatArgs, name: &str) {
 
     // Decrement the index of the remaining by the number of replaced positional arguments
     if !suggestion.is_empty() {
-        for piece in &format_args.template {
-            if let Some((span, index)) = positional_arg_piece_span(piece)
+        for placeholder in format_string.placeholders() {
+            if let Some((span, index)) = positional_arg_piece_span(placeholder)
                 && suggestion.iter().all(|(s, _)| *s != span)
             {
                 let decrement = replaced_position.iter().filter(|i| **i < index).count();
@@ -574,20 +616,13 @@ fn check_literal(cx: &LateContext<'_>, format_args: &FormatArgs, name: &str) {
     }
 }
 
-/// Extract Span and its index from the given `piece`, iff it's positional argument.
-fn positional_arg_piece_span(piece: &FormatArgsPiece) -> Option<(Span, usize)> {
-    match piece {
-        FormatArgsPiece::Placeholder(FormatPlaceholder {
-            argument:
-                FormatArgPosition {
-                    index: Ok(index),
-                    kind: FormatArgPositionKind::Number,
-                    ..
-                },
-            span: Some(span),
-            ..
-        }) => Some((*span, *index)),
-        _ => None,
+/// Extract Span and its index from the given `placeholder`, iff it's positional argument.
+fn positional_arg_piece_span(placeholder: Placeholder<'_>) -> Option<(Span, usize)> {
+    let param = placeholder.param();
+    if param.is_numbered() {
+        Some((placeholder.span()?, param.index()?))
+    } else {
+        None
     }
 }
 
@@ -0,0 +1,127 @@
+//! Allowing the current warnings of some lints, for `cargo clippy --annotate-allows`.
+//!
+//! A pass collects the spans of the items of the crate before the lints run. The functions of
+//! [`crate::diagnostics`] mark the warnings of the annotated lints, and a hook on the emission of
+//! the diagnostics replaces their suggestions by the insertion of an `#[allow]` attribute on the
+//! innermost item containing them, which `cargo fix` applies. The suggestions of the other
+//! diagnostics are dropped, so that nothing else is changed.
+
+use crate::source::snippet_indent;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::{
+    Applicability, CodeSuggestion, Diag, DiagArgValue, DiagInner, ErrorGuaranteed, Substitution, SubstitutionPart,
+    SuggestionStyle, TRACK_DIAGNOSTIC,
+};
+use rustc_lint::{LateContext, LateLintPass, Lint, LintStore};
+use rustc_session::impl_lint_pass;
+use rustc_span::Span;
+use std::sync::{Mutex, OnceLock};
+
+/// The argument of the diagnostics to annotate, with the name of their lint as value.
+const MARKER: &str = "clippy_annotated_lint";
+
+/// The reason of the inserted attributes.
+const REASON: &str = "TODO: legacy";
+
+type TrackDiagnostic = fn(DiagInner, &mut dyn FnMut(DiagInner) -> Option<ErrorGuaranteed>) -> Option<ErrorGuaranteed>;
+
+static HOOK: TrackDiagnostic = track_diagnostic;
+
+/// The hook which was installed before [`track_diagnostic`].
+static PREVIOUS: OnceLock<&'static TrackDiagnostic> = OnceLock::new();
+
+/// The annotated lints, like `clippy::needless_return`.
+static LINTS: OnceLock<Vec<String>> = OnceLock::new();
+
+struct Items {
+    /// The span of each item which isn't from a macro expansion, and the indentation of its line.
+    spans: Vec<(Span, String)>,
+    /// Whether the attributes can have a reason, which needs the `lint_reasons` feature.
+    reason: bool,
+    /// The items which already got an attribute for each lint.
+    annotated: FxHashSet<(Span, String)>,
+}
+
+static ITEMS: Mutex<Option<Items>> = Mutex::new(None);
+
+/// Enables the annotation of the warnings of `lints`, given without the `clippy::` prefix.
+pub fn enable(store: &mut LintStore, lints: &[String]) {
+    let _ = LINTS.set(lints.iter().map(|lint| format!("clippy::{lint}")).collect());
+    PREVIOUS.get_or_init(|| TRACK_DIAGNOSTIC.swap(&HOOK));
+    store.register_late_pass(|_| Box::new(CollectItems));
+}
+
+/// Marks a diagnostic emitted by one of the functions of [`crate::diagnostics`] to be annotated,
+/// if its lint is one of the annotated lints.
+pub(crate) fn mark(diag: &mut Diag<'_, ()>, lint: &'static Lint) {
+    let name = lint.name_lower();
+    if LINTS.get().is_some_and(|lints| lints.contains(&name)) {
+        diag.arg(MARKER, name);
+    }
+}
+
+fn track_diagnostic(
+    mut diag: DiagInner,
+    emit: &mut dyn FnMut(DiagInner) -> Option<ErrorGuaranteed>,
+) -> Option<ErrorGuaranteed> {
+    let insertion = match (diag.args.swap_remove(MARKER), diag.span.primary_span()) {
+        (Some(DiagArgValue::Str(lint)), Some(span)) => allow_attribute(&lint, span.source_callsite()),
+        _ => None,
+    };
+    diag.suggestions = Ok(insertion.into_iter().collect());
+    (PREVIOUS.get().unwrap())(diag, emit)
+}
+
+/// Returns the insertion of the attribute allowing `lint` on the innermost item containing `span`,
+/// unless the item already got it.
+fn allow_attribute(lint: &str, span: Span) -> Option<CodeSuggestion> {
+    let mut items = ITEMS.lock().unwrap();
+    let items = items.as_mut()?;
+    let (item, indent) = items
+        .spans
+        .iter()
+        .filter(|(item, _)| item.contains(span))
+        .min_by_key(|(item, _)| item.hi() - item.lo())?;
+    if !items.annotated.insert((*item, lint.to_string())) {
+        return None;
+    }
+    let reason = if items.reason {
+        format!(", reason = \"{REASON}\"")
+    } else {
+        String::new()
+    };
+    Some(CodeSuggestion {
+        substitutions: vec![Substitution {
+            parts: vec![SubstitutionPart {
+                span: item.shrink_to_lo(),
+                snippet: format!("#[allow({lint}{reason})]\n{indent}"),
+            }],
+        }],
+        msg: format!("allow `{lint}` on the item").into(),
+        style: SuggestionStyle::ShowCode,
+        applicability: Applicability::MachineApplicable,
+    })
+}
+
+struct CollectItems;
+
+impl_lint_pass!(CollectItems => []);
+
+impl LateLintPass<'_> for CollectItems {
+    fn check_crate(&mut self, cx: &LateContext<'_>) {
+        let hir = cx.tcx.hir();
+        let crate_items = cx.tcx.hir_crate_items(());
+        let spans = (crate_items.free_items().map(|id| hir.item(id).span))
+            .chain(crate_items.impl_items().map(|id| hir.impl_item(id).span))
+            .chain(crate_items.trait_items().map(|id| hir.trait_item(id).span))
+            .chain(crate_items.foreign_items().map(|id| hir.foreign_item(id).span))
+            .filter(|span| !span.from_expansion())
+            .map(|span| (span, snippet_indent(cx, span).unwrap_or_default()))
+            .collect();
+        *ITEMS.lock().unwrap() = Some(Items {
+            spans,
+            reason: cx.tcx.features().lint_reasons,
+            annotated: FxHashSet::default(),
+        });
+    }
+}
@@ -1,14 +1,12 @@
 use rustc_ast::{ast, attr};
 use rustc_errors::Applicability;
-use rustc_lexer::TokenKind;
 use rustc_lint::LateContext;
 use rustc_middle::ty::{AdtDef, TyCtxt};
 use rustc_session::Session;
 use rustc_span::{sym, Span};
 use std::str::FromStr;
 
-use crate::source::snippet_opt;
-use crate::tokenize_with_text;
+use crate::source::{snippet_opt, text_contains_cfg};
 
 /// Deprecation status of attributes known by Clippy.
 pub enum DeprecationStatus {
@@ -21,13 +19,21 @@ pub enum DeprecationStatus {
 
 #[rustfmt::skip]
 pub const BUILTIN_ATTRIBUTES: &[(&str, DeprecationStatus)] = &[
-    ("author",                DeprecationStatus::None),
-    ("version",               DeprecationStatus::None),
-    ("cognitive_complexity",  DeprecationStatus::None),
-    ("cyclomatic_complexity", DeprecationStatus::Replaced("cognitive_complexity")),
-    ("dump",                  DeprecationStatus::None),
-    ("msrv",                  DeprecationStatus::None),
-    ("has_significant_drop",  DeprecationStatus::None),
+    ("author",                  DeprecationStatus::None),
+    ("version",                 DeprecationStatus::None),
+    ("cognitive_complexity",    DeprecationStatus::None),
+    ("cyclomatic_complexity",   DeprecationStatus::Replaced("cognitive_complexity")),
+    ("dump",                    DeprecationStatus::None),
+    ("msrv",                    DeprecationStatus::None),
+    ("has_significant_drop",    DeprecationStatus::None),
+    ("limit",                   DeprecationStatus::None),
+    ("max_args",                DeprecationStatus::None),
+    ("max_lines",               DeprecationStatus::None),
+    ("max_cognitive_complexity",DeprecationStatus::None),
+    ("max_type_complexity",     DeprecationStatus::None),
+    ("max_struct_bools",        DeprecationStatus::None),
+    ("max_fn_params_bools",     DeprecationStatus::None),
+    ("no_alloc",                DeprecationStatus::None),
 ];
 
 pub struct LimitStack {
@@ -118,7 +124,7 @@ pub fn get_attr<'a>(
     })
 }
 
-fn parse_attrs<F: FnMut(u64)>(sess: &Session, attrs: &[ast::Attribute], name: &'static str, mut f: F) {
+pub(crate) fn parse_attrs<F: FnMut(u64)>(sess: &Session, attrs: &[ast::Attribute], name: &'static str, mut f: F) {
     for attr in get_attr(sess, attrs, name) {
         if let Some(ref value) = attr.value_str() {
             if let Ok(value) = FromStr::from_str(value.as_str()) {
@@ -183,21 +189,5 @@ pub fn span_contains_cfg(cx: &LateContext<'_>, s: Span) -> bool {
         // Assume true. This would require either an invalid span, or one which crosses file boundaries.
         return true;
     };
-    let mut iter = tokenize_with_text(&snip);
-
-    // Search for the token sequence [`#`, `[`, `cfg`]
-    while iter.any(|(t, _)| matches!(t, TokenKind::Pound)) {
-        let mut iter = iter.by_ref().skip_while(|(t, _)| {
-            matches!(
-                t,
-                TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. }
-            )
-        });
-        if matches!(iter.next(), Some((TokenKind::OpenBracket, _)))
-            && matches!(iter.next(), Some((TokenKind::Ident, "cfg")))
-        {
-            return true;
-        }
-    }
-    false
+    text_contains_cfg(&snip)
 }
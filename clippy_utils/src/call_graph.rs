@@ -0,0 +1,218 @@
+//! The call graph of the functions of the local crate.
+//!
+//! The graph is built from the HIR bodies of the functions and methods of the crate the first time
+//! it's needed, and is then shared by all the lints. An edge is added for each call whose callee
+//! is a function of the local crate: a path to a function, or a method call. Calls to trait
+//! methods are resolved to the implementation of the local crate when the types are known at the
+//! call site. Calls through function pointers, `dyn Trait` and closures aren't followed, and the
+//! bodies of closures aren't part of the body of the function defining them.
+
+use crate::crate_cache;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::{walk_expr, Visitor};
+use rustc_hir::{Expr, ExprKind, HirId};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, GenericArgsRef, Instance, TyCtxt, TypeckResults};
+use rustc_span::Span;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// A call from a function of the local crate to another one
+#[derive(Clone, Copy, Debug)]
+pub struct Call {
+    pub caller: LocalDefId,
+    pub callee: LocalDefId,
+    /// The call or method call expression
+    pub hir_id: HirId,
+    pub span: Span,
+}
+
+#[derive(Default)]
+pub struct CallGraph {
+    calls: FxHashMap<LocalDefId, Vec<Call>>,
+    callers: FxHashMap<LocalDefId, Vec<Call>>,
+    callees: FxHashMap<HirId, LocalDefId>,
+}
+
+impl CallGraph {
+    fn build(tcx: TyCtxt<'_>) -> Self {
+        let mut graph = Self::default();
+        for def_id in tcx.hir().body_owners() {
+            if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn) {
+                continue;
+            }
+            let typeck_results = tcx.typeck(def_id);
+            if typeck_results.tainted_by_errors.is_some() {
+                continue;
+            }
+            let mut visitor = CallVisitor {
+                tcx,
+                typeck_results,
+                param_env: tcx.param_env(def_id),
+                caller: def_id,
+                calls: Vec::new(),
+            };
+            visitor.visit_expr(tcx.hir().body(tcx.hir().body_owned_by(def_id)).value);
+            graph
+                .callees
+                .extend(visitor.calls.iter().map(|call| (call.hir_id, call.callee)));
+            for &call in &visitor.calls {
+                graph.callers.entry(call.callee).or_default().push(call);
+            }
+            graph.calls.insert(def_id, visitor.calls);
+        }
+        graph
+    }
+
+    /// The calls made by the body of `def_id`, in order
+    pub fn calls(&self, def_id: LocalDefId) -> &[Call] {
+        self.calls.get(&def_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// The calls made to `def_id` by the functions of the local crate
+    pub fn callers(&self, def_id: LocalDefId) -> &[Call] {
+        self.callers.get(&def_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// The function called by the call or method call expression `hir_id`, if it's one of the
+    /// local crate
+    pub fn callee(&self, hir_id: HirId) -> Option<LocalDefId> {
+        self.callees.get(&hir_id).copied()
+    }
+
+    /// Returns the shortest chain of calls from `from` to a function for which `pred` returns
+    /// `true`. The chain has at least one call, so `from` itself is only found through a cycle.
+    pub fn find_path(&self, from: LocalDefId, pred: impl FnMut(LocalDefId) -> bool) -> Option<Vec<Call>> {
+        self.find_path_through(from, pred, |_| true)
+    }
+
+    /// Like [`CallGraph::find_path`], but only goes through the functions for which `through`
+    /// returns `true`.
+    pub fn find_path_through(
+        &self,
+        from: LocalDefId,
+        mut pred: impl FnMut(LocalDefId) -> bool,
+        mut through: impl FnMut(LocalDefId) -> bool,
+    ) -> Option<Vec<Call>> {
+        // The call through which each function was first reached
+        let mut reached_by: FxHashMap<LocalDefId, Call> = FxHashMap::default();
+        let mut queue = VecDeque::from([from]);
+        while let Some(caller) = queue.pop_front() {
+            for &call in self.calls(caller) {
+                if pred(call.callee) {
+                    let mut path = vec![call];
+                    let mut current = caller;
+                    while current != from {
+                        let call = reached_by[&current];
+                        path.push(call);
+                        current = call.caller;
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                if call.callee != from && !reached_by.contains_key(&call.callee) && through(call.callee) {
+                    reached_by.insert(call.callee, call);
+                    queue.push_back(call.callee);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the shortest chain of calls from `from` to `to`
+    pub fn path(&self, from: LocalDefId, to: LocalDefId) -> Option<Vec<Call>> {
+        self.find_path(from, |def_id| def_id == to)
+    }
+
+    /// Checks if `to` can be called, directly or not, by `from`
+    pub fn is_reachable(&self, from: LocalDefId, to: LocalDefId) -> bool {
+        self.path(from, to).is_some()
+    }
+
+    /// Returns the shortest chain of calls from `def_id` back to itself
+    pub fn cycle(&self, def_id: LocalDefId) -> Option<Vec<Call>> {
+        self.path(def_id, def_id)
+    }
+
+    /// Returns the shortest chain of calls from `def_id` back to itself, going only through the
+    /// functions for which `through` returns `true`
+    pub fn cycle_through(&self, def_id: LocalDefId, through: impl FnMut(LocalDefId) -> bool) -> Option<Vec<Call>> {
+        self.find_path_through(def_id, |callee| callee == def_id, through)
+    }
+
+    /// Checks if `def_id` can call itself, directly or not
+    pub fn is_recursive(&self, def_id: LocalDefId) -> bool {
+        self.cycle(def_id).is_some()
+    }
+
+    /// The functions which can be called, directly or not, by `from`
+    pub fn reachable_from(&self, from: LocalDefId) -> FxHashSet<LocalDefId> {
+        let mut reached = FxHashSet::default();
+        let mut stack = vec![from];
+        while let Some(caller) = stack.pop() {
+            for call in self.calls(caller) {
+                if reached.insert(call.callee) {
+                    stack.push(call.callee);
+                }
+            }
+        }
+        reached
+    }
+}
+
+/// Returns the call graph of the local crate, building it on the first call
+pub fn call_graph(cx: &LateContext<'_>) -> Rc<CallGraph> {
+    crate_cache::get_or_compute(cx, || CallGraph::build(cx.tcx))
+}
+
+struct CallVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    caller: LocalDefId,
+    calls: Vec<Call>,
+}
+
+impl<'tcx> CallVisitor<'tcx> {
+    fn callee(&self, expr: &Expr<'_>) -> Option<LocalDefId> {
+        let (def_id, args): (DefId, GenericArgsRef<'tcx>) = match expr.kind {
+            ExprKind::Call(callee, _) => match callee.kind {
+                ExprKind::Path(ref qpath) => match self.typeck_results.qpath_res(qpath, callee.hir_id) {
+                    Res::Def(DefKind::Fn | DefKind::AssocFn, def_id) => {
+                        (def_id, self.typeck_results.node_args(callee.hir_id))
+                    },
+                    _ => return None,
+                },
+                _ => return None,
+            },
+            ExprKind::MethodCall(..) => (
+                self.typeck_results.type_dependent_def_id(expr.hir_id)?,
+                self.typeck_results.node_args(expr.hir_id),
+            ),
+            _ => return None,
+        };
+        if self.tcx.trait_of_item(def_id).is_none() {
+            return def_id.as_local();
+        }
+        match Instance::resolve(self.tcx, self.param_env, def_id, args) {
+            Ok(Some(instance)) if matches!(instance.def, ty::InstanceDef::Item(_)) => instance.def_id().as_local(),
+            _ => None,
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for CallVisitor<'tcx> {
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        if let Some(callee) = self.callee(expr) {
+            self.calls.push(Call {
+                caller: self.caller,
+                callee,
+                hir_id: expr.hir_id,
+                span: expr.span,
+            });
+        }
+        walk_expr(self, expr);
+    }
+}
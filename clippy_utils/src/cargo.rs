@@ -0,0 +1,452 @@
+//! Cached access to the Cargo package of the crate being linted: its `Cargo.toml` and the output
+//! of `cargo metadata`.
+//!
+//! Both are read at most once per crate, so lint passes can query them freely.
+
+use cargo_metadata::{Metadata, MetadataCommand, Package};
+use rustc_data_structures::sync::Lrc;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_lint::{LateContext, LintContext};
+use rustc_session::config::CrateType;
+use rustc_span::def_id::StableCrateId;
+use rustc_span::{sym, BytePos, Pos, SourceFile, Span, Symbol, SyntaxContext};
+use serde::{Deserialize, Serialize};
+use std::cell::{OnceCell, RefCell};
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+use toml::Spanned;
+
+/// The kind of Cargo target the crate being linted is built for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TargetKind {
+    Lib,
+    Bin,
+    ProcMacro,
+    /// The `build.rs` build script
+    BuildScript,
+    /// A crate built with the test harness, e.g. by `cargo test`
+    Test,
+    /// An example of the `examples` directory
+    Example,
+    /// A benchmark of the `benches` directory without the test harness
+    Bench,
+}
+
+impl TargetKind {
+    pub fn is_executable(self) -> bool {
+        matches!(
+            self,
+            Self::Bin | Self::BuildScript | Self::Test | Self::Example | Self::Bench
+        )
+    }
+
+    /// Checks if the target is part of what the package ships: its library, binaries and
+    /// procedural macros, as opposed to its tests, examples, benchmarks and build script.
+    pub fn is_production(self) -> bool {
+        matches!(self, Self::Lib | Self::Bin | Self::ProcMacro)
+    }
+}
+
+/// Returns the kind of target the crate being linted is built for.
+pub fn target_kind(cx: &LateContext<'_>) -> TargetKind {
+    let crate_types = cx.tcx.crate_types();
+    if cx.sess().opts.test {
+        TargetKind::Test
+    } else if cx.tcx.crate_name(LOCAL_CRATE).as_str() == "build_script_build" {
+        TargetKind::BuildScript
+    } else if let Some(kind) = crate_root_dir(cx).and_then(|dir| target_kind_of_dir(&dir)) {
+        kind
+    } else if crate_types.contains(&CrateType::ProcMacro) {
+        TargetKind::ProcMacro
+    } else if crate_types.contains(&CrateType::Executable) || has_custom_entry_point(cx) {
+        TargetKind::Bin
+    } else {
+        TargetKind::Lib
+    }
+}
+
+/// Checks if the crate replaces the `main` function, with `#![no_main]` or a `#[rustc_main]`
+/// function, which only binaries do. Such crates may still be built with `--crate-type=lib`, e.g.
+/// by test runners which do so for the files without a `main` function.
+fn has_custom_entry_point(cx: &LateContext<'_>) -> bool {
+    let hir = cx.tcx.hir();
+    hir.krate_attrs().iter().any(|attr| attr.has_name(sym::no_main))
+        || hir
+            .root_module()
+            .item_ids
+            .iter()
+            .any(|&id| hir.attrs(id.hir_id()).iter().any(|attr| attr.has_name(sym::rustc_main)))
+}
+
+/// Returns the directory of the package the root of the crate being linted is in, e.g. `src` or
+/// `examples`.
+fn crate_root_dir(cx: &LateContext<'_>) -> Option<String> {
+    let root = cx.sess().local_crate_source_file()?;
+    let root = root.local_path()?;
+    let root = if root.is_relative() {
+        cx.sess().opts.working_dir.local_path()?.join(root)
+    } else {
+        root.to_path_buf()
+    };
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")?;
+    match root.strip_prefix(manifest_dir).ok()?.components().next()? {
+        Component::Normal(dir) => dir.to_str().map(String::from),
+        _ => None,
+    }
+}
+
+/// Returns the kind of the targets which Cargo discovers in the directory `dir` of a package,
+/// for the ones which aren't built differently from the other binaries.
+fn target_kind_of_dir(dir: &str) -> Option<TargetKind> {
+    match dir {
+        "examples" => Some(TargetKind::Example),
+        "benches" => Some(TargetKind::Bench),
+        _ => None,
+    }
+}
+
+/// Checks if the Cargo feature is enabled for the crate being linted.
+pub fn is_feature_enabled(cx: &LateContext<'_>, feature: &str) -> bool {
+    cx.sess()
+        .psess
+        .config
+        .contains(&(sym::feature, Some(Symbol::intern(feature))))
+}
+
+/// Checks if the package of the crate being linted declares a dependency on `package`, in any of
+/// the dependency tables of its `Cargo.toml`.
+pub fn has_dependency(cx: &LateContext<'_>, package: &str) -> bool {
+    manifest(cx).is_some_and(|manifest| {
+        manifest
+            .toml
+            .dependencies
+            .iter()
+            .any(|dependency| dependency.package == package)
+    })
+}
+
+/// Checks if the package of the crate being linted is a member of the workspace.
+pub fn is_workspace_member(cx: &LateContext<'_>) -> bool {
+    metadata(cx, false).is_ok_and(|metadata| {
+        current_package(&metadata).is_some_and(|package| metadata.workspace_members.contains(&package.id))
+    })
+}
+
+/// Returns the package of the crate being linted among the packages of the metadata.
+pub fn current_package(metadata: &Metadata) -> Option<&Package> {
+    let dir = std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())?;
+    metadata
+        .packages
+        .iter()
+        .find(|package| package.manifest_path.parent().is_some_and(|parent| parent == dir))
+}
+
+/// Returns the output of `cargo metadata`, with the whole dependency graph if `with_deps` is
+/// set, or only the workspace members otherwise. The error is the reason `cargo metadata` failed.
+pub fn metadata(cx: &LateContext<'_>, with_deps: bool) -> Result<Rc<Metadata>, String> {
+    with_cache(cx, |cache| {
+        cache.metadata[usize::from(with_deps)]
+            .get_or_init(|| {
+                let mut command = MetadataCommand::new();
+                if !with_deps {
+                    command.no_deps();
+                }
+                command.exec().map(Rc::new).map_err(|e| e.to_string())
+            })
+            .clone()
+    })
+}
+
+/// Returns the `Cargo.toml` of the package of the crate being linted, or `None` if it can't be
+/// read.
+pub fn manifest(cx: &LateContext<'_>) -> Option<Rc<Manifest>> {
+    with_cache(cx, |cache| {
+        cache
+            .manifest
+            .get_or_init(|| {
+                let file = cx.sess().source_map().load_file(Path::new("Cargo.toml")).ok()?;
+                let toml = ManifestToml::parse(file.src.as_deref()?)?;
+                Some(Rc::new(Manifest { file, toml }))
+            })
+            .clone()
+    })
+}
+
+#[derive(Default)]
+struct Cache {
+    krate: Option<StableCrateId>,
+    manifest: OnceCell<Option<Rc<Manifest>>>,
+    /// The metadata without and with the dependencies
+    metadata: [OnceCell<Result<Rc<Metadata>, String>>; 2],
+}
+
+thread_local! {
+    static CACHE: RefCell<Cache> = RefCell::default();
+}
+
+fn with_cache<T>(cx: &LateContext<'_>, f: impl FnOnce(&Cache) -> T) -> T {
+    let krate = cx.tcx.stable_crate_id(LOCAL_CRATE);
+    CACHE.with_borrow_mut(|cache| {
+        if cache.krate != Some(krate) {
+            *cache = Cache {
+                krate: Some(krate),
+                ..Cache::default()
+            };
+        }
+        f(cache)
+    })
+}
+
+/// The `Cargo.toml` of a package, loaded in the source map so lints can point into it
+pub struct Manifest {
+    pub file: Lrc<SourceFile>,
+    pub toml: ManifestToml,
+}
+
+impl Manifest {
+    /// Returns the span of the byte range of the file, as given by [`Spanned::span`].
+    pub fn span(&self, range: Range<usize>) -> Span {
+        Span::new(
+            self.file.start_pos + BytePos::from_usize(range.start),
+            self.file.start_pos + BytePos::from_usize(range.end),
+            SyntaxContext::root(),
+            None,
+        )
+    }
+}
+
+/// The parts of a `Cargo.toml` used by lints, with the byte ranges of their keys
+#[derive(Default)]
+pub struct ManifestToml {
+    /// The dependencies of all the dependency tables, in the order they are declared in
+    pub dependencies: Vec<Dependency>,
+    /// The names of the features, in the order they are declared in
+    pub features: Vec<Spanned<String>>,
+    pub lints: Lints,
+}
+
+impl ManifestToml {
+    /// Parses the manifest, returning `None` if it isn't valid TOML. Each part is parsed on its
+    /// own, so an unexpected value in one of them doesn't hide the others.
+    pub fn parse(src: &str) -> Option<Self> {
+        #[derive(Deserialize)]
+        struct Features {
+            #[serde(default)]
+            features: BTreeMap<Spanned<String>, toml::Value>,
+        }
+
+        #[derive(Deserialize)]
+        struct LintsTable {
+            #[serde(default)]
+            lints: Lints,
+        }
+
+        let _: toml::Table = toml::from_str(src).ok()?;
+        let mut features: Vec<_> = toml::from_str::<Features>(src)
+            .map(|table| table.features.into_keys().collect())
+            .unwrap_or_default();
+        features.sort_by_key(|feature| feature.span().start);
+        Some(Self {
+            dependencies: toml::from_str::<DependencyTables>(src)
+                .map(DependencyTables::into_dependencies)
+                .unwrap_or_default(),
+            features,
+            lints: toml::from_str::<LintsTable>(src)
+                .map(|table| table.lints)
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// The table a dependency is declared in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DependencyKind {
+    Normal,
+    Dev,
+    Build,
+}
+
+#[derive(Debug)]
+pub struct Dependency {
+    /// The byte range of the key of the dependency in its table
+    pub key: Range<usize>,
+    /// The name of the package, which differs from the key if the dependency is renamed
+    pub package: String,
+    /// The `git` URL of the dependency
+    pub git: Option<String>,
+    pub kind: DependencyKind,
+}
+
+type DependencyTable = BTreeMap<Spanned<String>, toml::Value>;
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+struct DependencyTables {
+    #[serde(default)]
+    dependencies: DependencyTable,
+    #[serde(default)]
+    dev_dependencies: DependencyTable,
+    #[serde(default)]
+    build_dependencies: DependencyTable,
+    #[serde(default)]
+    target: BTreeMap<String, DependencyTables>,
+}
+
+impl DependencyTables {
+    fn into_dependencies(self) -> Vec<Dependency> {
+        let mut dependencies = Vec::new();
+        self.collect(&mut dependencies);
+        dependencies.sort_by_key(|dependency| dependency.key.start);
+        dependencies
+    }
+
+    fn collect(self, dependencies: &mut Vec<Dependency>) {
+        for (table, kind) in [
+            (self.dependencies, DependencyKind::Normal),
+            (self.dev_dependencies, DependencyKind::Dev),
+            (self.build_dependencies, DependencyKind::Build),
+        ] {
+            dependencies.extend(table.into_iter().map(|(key, config)| {
+                let field = |name| config.get(name).and_then(toml::Value::as_str).map(String::from);
+                Dependency {
+                    package: field("package").unwrap_or_else(|| key.get_ref().clone()),
+                    git: field("git"),
+                    key: key.span(),
+                    kind,
+                }
+            }));
+        }
+        for tables in self.target.into_values() {
+            tables.collect(dependencies);
+        }
+    }
+}
+
+/// The `[lints]` table
+#[derive(Deserialize, Default, Debug)]
+pub struct Lints {
+    #[serde(default)]
+    pub rust: LintTable,
+    #[serde(default)]
+    pub clippy: LintTable,
+}
+
+pub type LintTable = BTreeMap<Spanned<String>, Spanned<LintConfig>>;
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LintConfigTable {
+    pub level: String,
+    pub priority: Option<i64>,
+}
+
+/// The configuration of a lint or lint group, either `lint = "level"` or
+/// `lint = { level = "level", priority = n }`
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum LintConfig {
+    Level(String),
+    Table(LintConfigTable),
+}
+
+impl LintConfig {
+    pub fn level(&self) -> &str {
+        match self {
+            LintConfig::Level(level) => level,
+            LintConfig::Table(table) => &table.level,
+        }
+    }
+
+    pub fn priority(&self) -> i64 {
+        match self {
+            LintConfig::Level(_) => 0,
+            LintConfig::Table(table) => table.priority.unwrap_or(0),
+        }
+    }
+
+    /// Checks if the priority is left to its default
+    pub fn is_implicit(&self) -> bool {
+        if let LintConfig::Table(table) = self {
+            table.priority.is_none()
+        } else {
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{target_kind_of_dir, DependencyKind, ManifestToml, TargetKind};
+
+    #[test]
+    fn parse_manifest() {
+        let src = r#"
+            [package]
+            name = "krate"
+
+            [features]
+            std = []
+            alloc = []
+
+            [dependencies]
+            serde = "1"
+            renamed = { package = "regex", git = "https://example.com/regex" }
+
+            [target.'cfg(unix)'.build-dependencies]
+            cc = "1"
+
+            [lints.clippy]
+            all = { level = "warn", priority = -1 }
+            unwrap_used = "deny"
+        "#;
+        let toml = ManifestToml::parse(src).unwrap();
+
+        let features: Vec<_> = toml.features.iter().map(|feature| feature.get_ref().as_str()).collect();
+        assert_eq!(features, ["std", "alloc"]);
+
+        let dependencies: Vec<_> = toml
+            .dependencies
+            .iter()
+            .map(|dependency| (dependency.package.as_str(), dependency.git.as_deref(), dependency.kind))
+            .collect();
+        assert_eq!(
+            dependencies,
+            [
+                ("serde", None, DependencyKind::Normal),
+                ("regex", Some("https://example.com/regex"), DependencyKind::Normal),
+                ("cc", None, DependencyKind::Build),
+            ]
+        );
+        assert_eq!(&src[toml.dependencies[1].key.clone()], "renamed");
+
+        let lints: Vec<_> = toml
+            .lints
+            .clippy
+            .iter()
+            .map(|(name, config)| (name.get_ref().as_str(), config.get_ref().priority()))
+            .collect();
+        assert_eq!(lints, [("all", -1), ("unwrap_used", 0)]);
+        assert!(toml.lints.rust.is_empty());
+    }
+
+    #[test]
+    fn parse_malformed_parts() {
+        // A lint table that doesn't match the expected shape doesn't hide the dependencies.
+        let toml = ManifestToml::parse("[lints]\nclippy = 1\n[dependencies]\nserde = \"1\"\n").unwrap();
+        assert_eq!(toml.dependencies.len(), 1);
+        assert!(toml.lints.clippy.is_empty());
+
+        assert!(ManifestToml::parse("[dependencies").is_none());
+    }
+
+    #[test]
+    fn target_kinds_of_dirs() {
+        assert_eq!(target_kind_of_dir("examples"), Some(TargetKind::Example));
+        assert_eq!(target_kind_of_dir("benches"), Some(TargetKind::Bench));
+        assert_eq!(target_kind_of_dir("src"), None);
+        assert_eq!(target_kind_of_dir("tests"), None);
+    }
+}
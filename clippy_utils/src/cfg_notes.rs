@@ -0,0 +1,58 @@
+//! Noting the cfgs of the crate on its diagnostics, for `cargo clippy --aggregate`.
+//!
+//! The same file is often compiled several times in a workspace, e.g. with and without `test`, or
+//! with different features. A hook on the emission of the diagnostics adds a note starting with
+//! [`NOTE_PREFIX`] to the warnings and errors, which `cargo clippy` uses to merge the identical
+//! diagnostics of the compilations and to list the cfgs each of them was emitted with.
+
+use rustc_errors::{DiagArgValue, DiagInner, ErrorGuaranteed, Level, MultiSpan, Style, Subdiag, TRACK_DIAGNOSTIC};
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+/// The start of the note, followed by the cfgs of the crate separated by `, `.
+pub const NOTE_PREFIX: &str = "clippy cfgs:";
+
+/// The argument marking the diagnostics which were noted, as the diagnostics held back by
+/// `--collapse-macro-diagnostics` go through the hook twice.
+const MARKER: &str = "clippy_cfg_noted";
+
+type TrackDiagnostic = fn(DiagInner, &mut dyn FnMut(DiagInner) -> Option<ErrorGuaranteed>) -> Option<ErrorGuaranteed>;
+
+static HOOK: TrackDiagnostic = track_diagnostic;
+
+/// The hook which was installed before [`track_diagnostic`].
+static PREVIOUS: OnceLock<&'static TrackDiagnostic> = OnceLock::new();
+
+/// The note added to the diagnostics.
+static NOTE: OnceLock<String> = OnceLock::new();
+
+/// Enables the notes, with the cfgs passed to the compiler, like `test` or `feature="std"`.
+pub fn enable(cfgs: &[String]) {
+    let mut note = NOTE_PREFIX.to_string();
+    if !cfgs.is_empty() {
+        note.push(' ');
+        note.push_str(&cfgs.join(", "));
+    }
+    let _ = NOTE.set(note);
+    PREVIOUS.get_or_init(|| TRACK_DIAGNOSTIC.swap(&HOOK));
+}
+
+fn track_diagnostic(
+    mut diag: DiagInner,
+    emit: &mut dyn FnMut(DiagInner) -> Option<ErrorGuaranteed>,
+) -> Option<ErrorGuaranteed> {
+    // Summaries like "aborting due to 2 previous errors" don't have a span
+    if matches!(diag.level(), Level::Warning | Level::ForceWarning(_) | Level::Error)
+        && diag.span.primary_span().is_some()
+        && !diag.args.contains_key(MARKER)
+        && let Some(note) = NOTE.get()
+    {
+        diag.args.insert(MARKER.into(), DiagArgValue::Str(Cow::Borrowed("")));
+        diag.children.push(Subdiag {
+            level: Level::Note,
+            messages: vec![(note.clone().into(), Style::NoStyle)],
+            span: MultiSpan::new(),
+        });
+    }
+    (PREVIOUS.get().unwrap())(diag, emit)
+}
@@ -5,18 +5,21 @@ use crate::{clip, is_direct_expn_of, sext, unsext};
 
 use rustc_ast::ast::{self, LitFloatType, LitKind};
 use rustc_data_structures::sync::Lrc;
-use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def::{CtorKind, CtorOf, DefKind, Res};
+use rustc_hir::def_id::{DefIdMap, LOCAL_CRATE};
 use rustc_hir::{BinOp, BinOpKind, Block, ConstBlock, Expr, ExprKind, HirId, Item, ItemKind, Node, QPath, UnOp};
 use rustc_lexer::tokenize;
 use rustc_lint::LateContext;
-use rustc_middle::mir::interpret::{alloc_range, Scalar};
+use rustc_middle::mir::interpret::{alloc_range, AllocId, Scalar};
 use rustc_middle::mir::ConstValue;
+use rustc_middle::ty::util::{Discr, IntTypeExt};
 use rustc_middle::ty::{self, EarlyBinder, FloatTy, GenericArgsRef, IntTy, List, ScalarInt, Ty, TyCtxt, UintTy};
 use rustc_middle::{bug, mir, span_bug};
-use rustc_span::def_id::DefId;
-use rustc_span::symbol::{Ident, Symbol};
+use rustc_span::def_id::{DefId, StableCrateId};
+use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::SyntaxContext;
 use rustc_target::abi::Size;
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
 use std::iter;
@@ -315,6 +318,11 @@ pub fn constant_is_empty(lcx: &LateContext<'_>, e: &Expr<'_>) -> Option<bool> {
     ConstEvalLateContext::new(lcx, lcx.typeck_results()).expr_is_empty(e)
 }
 
+/// Attempts to evaluate the length of an array, slice or `str`, through references and constants.
+pub fn constant_len(lcx: &LateContext<'_>, e: &Expr<'_>) -> Option<u64> {
+    ConstEvalLateContext::new(lcx, lcx.typeck_results()).expr_len(e)
+}
+
 /// Attempts to evaluate the expression as a constant.
 pub fn constant<'tcx>(
     lcx: &LateContext<'tcx>,
@@ -468,24 +476,101 @@ impl<'a, 'tcx> ConstEvalLateContext<'a, 'tcx> {
                     None
                 }
             },
+            ExprKind::MethodCall(method, recv, [], _)
+                if method.ident.name == sym::len
+                    && matches!(
+                        self.typeck_results.expr_ty_adjusted(recv).peel_refs().kind(),
+                        ty::Array(..) | ty::Slice(_) | ty::Str
+                    ) =>
+            {
+                let len = self.expr_len(recv)?;
+                // The call isn't a constant expression, so `constant_simple` rejects it.
+                self.source = ConstantSource::Constant;
+                Some(Constant::Int(len.into()))
+            },
+            ExprKind::Cast(operand, _) => self.cast_discriminant(operand, self.typeck_results.expr_ty(e)),
             ExprKind::Index(arr, index, _) => self.index(arr, index),
             ExprKind::AddrOf(_, _, inner) => self.expr(inner).map(|r| Constant::Ref(Box::new(r))),
-            ExprKind::Field(local_expr, ref field) => {
-                let result = self.expr(local_expr);
-                if let Some(Constant::Adt(constant)) = &self.expr(local_expr)
-                    && let ty::Adt(adt_def, _) = constant.ty().kind()
-                    && adt_def.is_struct()
-                    && let Some(desired_field) = field_of_struct(*adt_def, self.lcx, *constant, field)
+            ExprKind::Field(local_expr, ref field) => match self.expr(local_expr) {
+                Some(Constant::Adt(constant))
+                    if let ty::Adt(adt_def, _) = constant.ty().kind()
+                        && adt_def.is_struct() =>
                 {
+                    let desired_field = field_of_struct(*adt_def, self.lcx, constant, field)?;
                     mir_to_const(self.lcx, desired_field)
+                },
+                Some(Constant::Tuple(mut fields)) => {
+                    let index = field.name.as_str().parse::<usize>().ok()?;
+                    (index < fields.len()).then(|| fields.swap_remove(index))
+                },
+                result => result,
+            },
+            _ => None,
+        }
+    }
+
+    /// Simple constant folding to determine the length of an array, slice or `str`. The length of
+    /// an array comes from its type, the one of a slice or `str` from the literal or the constant
+    /// it comes from.
+    pub fn expr_len(&mut self, e: &Expr<'_>) -> Option<u64> {
+        if let ty::Array(_, n) = self.typeck_results.expr_ty(e).peel_refs().kind() {
+            self.source = ConstantSource::Constant;
+            return n.try_eval_target_usize(self.lcx.tcx, self.lcx.param_env);
+        }
+        match e.kind {
+            ExprKind::ConstBlock(ConstBlock { body, .. }) => self.expr_len(self.lcx.tcx.hir().body(body).value),
+            ExprKind::DropTemps(e) | ExprKind::AddrOf(_, _, e) => self.expr_len(e),
+            ExprKind::Path(ref qpath) => {
+                let res = self.typeck_results.qpath_res(qpath, e.hir_id);
+                let compute = |this: &mut Self| {
+                    this.fetch_path_and_apply(qpath, e.hir_id, this.typeck_results.expr_ty(e), |this, result| {
+                        mir_len(this.lcx, result)
+                    })
+                };
+                let len = if let Res::Def(DefKind::Const, def_id) = res
+                    && self.typeck_results.node_args(e.hir_id).is_empty()
+                {
+                    cached(self.lcx.tcx, def_id, || compute(self).map(u128::from)).and_then(|len| len.try_into().ok())
                 } else {
-                    result
-                }
+                    compute(self)
+                };
+                self.source = ConstantSource::Constant;
+                len
+            },
+            ExprKind::Lit(lit) if is_direct_expn_of(e.span, "cfg").is_none() => match &lit.node {
+                LitKind::Str(s, _) => Some(s.as_str().len() as u64),
+                LitKind::ByteStr(s, _) | LitKind::CStr(s, _) => Some(s.len() as u64),
+                _ => None,
             },
             _ => None,
         }
     }
 
+    /// Evaluates the cast of a unit variant of an enum to an integer, e.g. `E::A as u8`.
+    fn cast_discriminant(&mut self, operand: &Expr<'_>, cast_ty: Ty<'_>) -> Option<Constant<'tcx>> {
+        let ExprKind::Path(ref qpath) = operand.kind else {
+            return None;
+        };
+        let Res::Def(DefKind::Ctor(CtorOf::Variant, CtorKind::Const), ctor_id) =
+            self.typeck_results.qpath_res(qpath, operand.hir_id)
+        else {
+            return None;
+        };
+        let tcx = self.lcx.tcx;
+        let discr = variant_discriminant(tcx, tcx.parent(ctor_id))?;
+        let value = match *discr.ty.kind() {
+            ty::Int(ity) => sext(tcx, discr.val, ity),
+            #[expect(clippy::cast_possible_wrap)]
+            _ => discr.val as i128,
+        };
+        match *cast_ty.kind() {
+            ty::Int(ity) => Some(Constant::Int(unsext(tcx, value, ity))),
+            #[expect(clippy::cast_sign_loss)]
+            ty::Uint(ity) => Some(Constant::Int(clip(tcx, value as u128, ity))),
+            _ => None,
+        }
+    }
+
     /// Simple constant folding to determine if an expression is an empty slice, str, array, …
     /// `None` will be returned if the constness cannot be determined, or if the resolution
     /// leaves the local crate.
@@ -827,6 +912,15 @@ pub fn mir_to_const<'tcx>(lcx: &LateContext<'tcx>, result: mir::Const<'tcx>) ->
             String::from_utf8(data.to_owned()).ok().map(Constant::Str)
         },
         (_, ty::Adt(adt_def, _)) if adt_def.is_struct() => Some(Constant::Adt(result)),
+        (_, ty::Tuple(tys)) if !tys.is_empty() => {
+            let fields = lcx.tcx.try_destructure_mir_constant_for_user_output(val, result.ty())?;
+            fields
+                .fields
+                .iter()
+                .map(|&(val, ty)| mir_to_const(lcx, mir::Const::Val(val, ty)))
+                .collect::<Option<_>>()
+                .map(Constant::Tuple)
+        },
         (ConstValue::Indirect { alloc_id, offset }, ty::Array(sub_type, len)) => {
             let alloc = lcx.tcx.global_alloc(alloc_id).unwrap_memory().inner();
             let len = len.try_to_target_usize(lcx.tcx)?;
@@ -860,20 +954,7 @@ fn mir_is_empty<'tcx>(lcx: &LateContext<'tcx>, result: mir::Const<'tcx>) -> Opti
         (_, ty::Ref(_, inner_ty, _)) => match inner_ty.kind() {
             ty::Str | ty::Slice(_) => {
                 if let ConstValue::Indirect { alloc_id, offset } = val {
-                    // Get the length from the slice, using the same formula as
-                    // [`ConstValue::try_get_slice_bytes_for_diagnostics`].
-                    let a = lcx.tcx.global_alloc(alloc_id).unwrap_memory().inner();
-                    let ptr_size = lcx.tcx.data_layout.pointer_size;
-                    if a.size() < offset + 2 * ptr_size {
-                        // (partially) dangling reference
-                        return None;
-                    }
-                    let len = a
-                        .read_scalar(&lcx.tcx, alloc_range(offset + ptr_size, ptr_size), false)
-                        .ok()?
-                        .to_target_usize(&lcx.tcx)
-                        .ok()?;
-                    Some(len == 0)
+                    Some(slice_len(lcx, alloc_id, offset)? == 0)
                 } else {
                     None
                 }
@@ -887,6 +968,85 @@ fn mir_is_empty<'tcx>(lcx: &LateContext<'tcx>, result: mir::Const<'tcx>) -> Opti
     }
 }
 
+fn mir_len<'tcx>(lcx: &LateContext<'tcx>, result: mir::Const<'tcx>) -> Option<u64> {
+    let mir::Const::Val(val, _) = result else {
+        // We only work on evaluated consts.
+        return None;
+    };
+    match (val, result.ty().kind()) {
+        (ConstValue::Indirect { alloc_id, offset }, ty::Ref(_, inner_ty, _))
+            if matches!(inner_ty.kind(), ty::Str | ty::Slice(_)) =>
+        {
+            slice_len(lcx, alloc_id, offset)
+        },
+        (ConstValue::Slice { meta, .. }, ty::Ref(..)) => Some(meta),
+        _ => None,
+    }
+}
+
+/// Reads the length of the wide reference to a slice or `str` at the given offset of an allocation.
+fn slice_len(lcx: &LateContext<'_>, alloc_id: AllocId, offset: Size) -> Option<u64> {
+    // Get the length from the slice, using the same formula as
+    // [`ConstValue::try_get_slice_bytes_for_diagnostics`].
+    let a = lcx.tcx.global_alloc(alloc_id).unwrap_memory().inner();
+    let ptr_size = lcx.tcx.data_layout.pointer_size;
+    if a.size() < offset + 2 * ptr_size {
+        // (partially) dangling reference
+        return None;
+    }
+    a.read_scalar(&lcx.tcx, alloc_range(offset + ptr_size, ptr_size), false)
+        .ok()?
+        .to_target_usize(&lcx.tcx)
+        .ok()
+}
+
+thread_local! {
+    /// The values which only depend on an item, by its `DefId`: the lengths of the non-generic
+    /// constants and the discriminants of the enum variants. Cleared when the crate being compiled
+    /// changes.
+    static CACHE: RefCell<(Option<StableCrateId>, DefIdMap<Option<u128>>)> =
+        RefCell::new((None, DefIdMap::default()));
+}
+
+fn cached(tcx: TyCtxt<'_>, def_id: DefId, f: impl FnOnce() -> Option<u128>) -> Option<u128> {
+    let krate = tcx.stable_crate_id(LOCAL_CRATE);
+    let hit = CACHE.with_borrow_mut(|(cached_krate, values)| {
+        if *cached_krate != Some(krate) {
+            *cached_krate = Some(krate);
+            *values = DefIdMap::default();
+        }
+        values.get(&def_id).copied()
+    });
+    hit.unwrap_or_else(|| {
+        // `f` can evaluate other constants, so the cache isn't borrowed while it runs.
+        let value = f();
+        CACHE.with_borrow_mut(|(_, values)| values.insert(def_id, value));
+        value
+    })
+}
+
+/// Returns the discriminant of an enum variant, as a value of the representation type of the
+/// enum. Returns `None` if it can't be evaluated.
+pub fn variant_discriminant(tcx: TyCtxt<'_>, variant_id: DefId) -> Option<Discr<'_>> {
+    let adt = tcx.adt_def(tcx.parent(variant_id));
+    if !adt.is_enum() {
+        return None;
+    }
+    let val = cached(tcx, variant_id, || {
+        let (explicit, offset) = adt.discriminant_def_for_variant(adt.variant_index_with_id(variant_id));
+        let base = match explicit {
+            Some(expr_did) => adt.eval_explicit_discr(tcx, expr_did).ok()?,
+            None => adt.repr().discr_type().initial_discriminant(tcx),
+        };
+        let (discr, overflowed) = base.checked_add(tcx, u128::from(offset));
+        (!overflowed).then_some(discr.val)
+    })?;
+    Some(Discr {
+        val,
+        ty: adt.repr().discr_type().to_ty(tcx),
+    })
+}
+
 fn field_of_struct<'tcx>(
     adt_def: ty::AdtDef<'tcx>,
     lcx: &LateContext<'tcx>,
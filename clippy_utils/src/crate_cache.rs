@@ -0,0 +1,52 @@
+//! A cache of the values computed from the whole local crate, shared by all the lint passes.
+//!
+//! Walking the whole crate from a callback which is called for each expression, item or function
+//! makes linting quadratic in the size of the crate. Such a walk should be done once: each kind of
+//! precomputation is identified by the type of its result, and [`get_or_compute`] only computes it
+//! the first time it's asked for in a crate.
+//!
+//! ```rust,ignore
+//! struct DefaultImpls(FxHashMap<DefId, DefId>);
+//!
+//! let default_impls = crate_cache::get_or_compute(cx, || DefaultImpls(collect_default_impls(cx)));
+//! ```
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_lint::LateContext;
+use rustc_span::def_id::StableCrateId;
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Default)]
+struct Cache {
+    krate: Option<StableCrateId>,
+    values: FxHashMap<TypeId, Rc<dyn Any>>,
+}
+
+thread_local! {
+    static CACHE: RefCell<Cache> = RefCell::default();
+}
+
+/// Returns the value of type `T` computed for the local crate, calling `compute` if it wasn't
+/// computed yet. `compute` can itself use the cache for values of other types.
+pub fn get_or_compute<T: 'static>(cx: &LateContext<'_>, compute: impl FnOnce() -> T) -> Rc<T> {
+    let krate = cx.tcx.stable_crate_id(LOCAL_CRATE);
+    let cached = CACHE.with_borrow_mut(|cache| {
+        if cache.krate != Some(krate) {
+            *cache = Cache {
+                krate: Some(krate),
+                values: FxHashMap::default(),
+            };
+        }
+        cache.values.get(&TypeId::of::<T>()).cloned()
+    });
+    if let Some(value) = cached {
+        return value.downcast().unwrap();
+    }
+    // The cache isn't borrowed while the value is computed, since `compute` can use it.
+    let value = Rc::new(compute());
+    CACHE.with_borrow_mut(|cache| cache.values.insert(TypeId::of::<T>(), value.clone()));
+    value
+}
@@ -8,11 +8,23 @@
 //! Thank you!
 //! ~The `INTERNAL_METADATA_COLLECTOR` lint
 
+use crate::{annotate_allows, lint_cache, macro_diagnostics, summary, warn_budget};
 use rustc_errors::{Applicability, Diag, DiagMessage, MultiSpan, SubdiagMessage};
 use rustc_hir::HirId;
 use rustc_lint::{LateContext, Lint, LintContext};
 use rustc_span::Span;
 use std::env;
+use std::sync::OnceLock;
+
+/// The lints whose `MaybeIncorrect` suggestions are applied, set by
+/// `cargo clippy --fix --fix-maybe-incorrect`.
+static FIX_MAYBE_INCORRECT: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Makes `cargo fix` apply the `MaybeIncorrect` suggestions of `lints`, given by their names
+/// without the `clippy::` prefix, by emitting them as `MachineApplicable`.
+pub fn set_fix_maybe_incorrect(lints: Vec<String>) {
+    let _ = FIX_MAYBE_INCORRECT.set(lints);
+}
 
 fn docs_link(diag: &mut Diag<'_, ()>, lint: &'static Lint) {
     if env::var("CLIPPY_DISABLE_DOCS_LINKS").is_err() {
@@ -28,6 +40,22 @@ fn docs_link(diag: &mut Diag<'_, ()>, lint: &'static Lint) {
     }
 }
 
+fn fix_maybe_incorrect(diag: &mut Diag<'_, ()>, lint: &'static Lint) {
+    let name = lint.name_lower();
+    if let Some(lints) = FIX_MAYBE_INCORRECT.get()
+        && lints
+            .iter()
+            .any(|fixed| Some(fixed.as_str()) == name.strip_prefix("clippy::"))
+        && let Ok(suggestions) = &mut diag.suggestions
+    {
+        for suggestion in suggestions {
+            if suggestion.applicability == Applicability::MaybeIncorrect {
+                suggestion.applicability = Applicability::MachineApplicable;
+            }
+        }
+    }
+}
+
 /// Emit a basic lint message with a `msg` and a `span`.
 ///
 /// This is the most primitive of our lint emission methods and can
@@ -60,9 +88,16 @@ fn docs_link(diag: &mut Diag<'_, ()>, lint: &'static Lint) {
 ///    |     ^^^^^^^^^^^^^^^^^^^^^^^
 /// ```
 pub fn span_lint<T: LintContext>(cx: &T, lint: &'static Lint, sp: impl Into<MultiSpan>, msg: impl Into<DiagMessage>) {
+    summary::count_allowed(lint, || cx.get_lint_level(lint));
     #[expect(clippy::disallowed_methods)]
     cx.span_lint(lint, sp, msg.into(), |diag| {
         docs_link(diag, lint);
+        fix_maybe_incorrect(diag, lint);
+        lint_cache::record(diag, lint, None);
+        warn_budget::count(diag, lint);
+        summary::count(diag, lint);
+        macro_diagnostics::mark(diag, lint);
+        annotate_allows::mark(diag, lint);
     });
 }
 
@@ -108,6 +143,7 @@ pub fn span_lint_and_help<T: LintContext>(
     help_span: Option<Span>,
     help: impl Into<SubdiagMessage>,
 ) {
+    summary::count_allowed(lint, || cx.get_lint_level(lint));
     #[expect(clippy::disallowed_methods)]
     cx.span_lint(lint, span, msg.into(), |diag| {
         if let Some(help_span) = help_span {
@@ -116,6 +152,12 @@ pub fn span_lint_and_help<T: LintContext>(
             diag.help(help.into());
         }
         docs_link(diag, lint);
+        fix_maybe_incorrect(diag, lint);
+        lint_cache::record(diag, lint, None);
+        warn_budget::count(diag, lint);
+        summary::count(diag, lint);
+        macro_diagnostics::mark(diag, lint);
+        annotate_allows::mark(diag, lint);
     });
 }
 
@@ -164,6 +206,7 @@ pub fn span_lint_and_note<T: LintContext>(
     note_span: Option<Span>,
     note: impl Into<SubdiagMessage>,
 ) {
+    summary::count_allowed(lint, || cx.get_lint_level(lint));
     #[expect(clippy::disallowed_methods)]
     cx.span_lint(lint, span, msg.into(), |diag| {
         if let Some(note_span) = note_span {
@@ -172,6 +215,12 @@ pub fn span_lint_and_note<T: LintContext>(
             diag.note(note.into());
         }
         docs_link(diag, lint);
+        fix_maybe_incorrect(diag, lint);
+        lint_cache::record(diag, lint, None);
+        warn_budget::count(diag, lint);
+        summary::count(diag, lint);
+        macro_diagnostics::mark(diag, lint);
+        annotate_allows::mark(diag, lint);
     });
 }
 
@@ -200,10 +249,17 @@ where
     M: Into<DiagMessage>,
     F: FnOnce(&mut Diag<'_, ()>),
 {
+    summary::count_allowed(lint, || cx.get_lint_level(lint));
     #[expect(clippy::disallowed_methods)]
     cx.span_lint(lint, sp, msg, |diag| {
         f(diag);
         docs_link(diag, lint);
+        fix_maybe_incorrect(diag, lint);
+        lint_cache::record(diag, lint, None);
+        warn_budget::count(diag, lint);
+        summary::count(diag, lint);
+        macro_diagnostics::mark(diag, lint);
+        annotate_allows::mark(diag, lint);
     });
 }
 
@@ -232,9 +288,16 @@ where
 /// the compiler check lint level attributes at the place of the expression and
 /// the `#[allow]` will work.
 pub fn span_lint_hir(cx: &LateContext<'_>, lint: &'static Lint, hir_id: HirId, sp: Span, msg: impl Into<DiagMessage>) {
+    summary::count_allowed(lint, || cx.tcx.lint_level_at_node(lint, hir_id).0);
     #[expect(clippy::disallowed_methods)]
     cx.tcx.node_span_lint(lint, hir_id, sp, msg.into(), |diag| {
         docs_link(diag, lint);
+        fix_maybe_incorrect(diag, lint);
+        lint_cache::record(diag, lint, Some(hir_id));
+        warn_budget::count(diag, lint);
+        summary::count(diag, lint);
+        macro_diagnostics::mark(diag, lint);
+        annotate_allows::mark(diag, lint);
     });
 }
 
@@ -270,10 +333,17 @@ pub fn span_lint_hir_and_then(
     msg: impl Into<DiagMessage>,
     f: impl FnOnce(&mut Diag<'_, ()>),
 ) {
+    summary::count_allowed(lint, || cx.tcx.lint_level_at_node(lint, hir_id).0);
     #[expect(clippy::disallowed_methods)]
     cx.tcx.node_span_lint(lint, hir_id, sp, msg.into(), |diag| {
         f(diag);
         docs_link(diag, lint);
+        fix_maybe_incorrect(diag, lint);
+        lint_cache::record(diag, lint, Some(hir_id));
+        warn_budget::count(diag, lint);
+        summary::count(diag, lint);
+        macro_diagnostics::mark(diag, lint);
+        annotate_allows::mark(diag, lint);
     });
 }
 
@@ -10,10 +10,12 @@
 //!  - option-if-let-else
 
 use crate::consts::{constant, FullInt};
+use crate::eval::effects_of;
+use crate::local_def_path_def_ids;
 use crate::ty::{all_predicates_of, is_copy};
 use crate::visitors::is_const_evaluatable;
 use rustc_hir::def::{DefKind, Res};
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, DefIdSet};
 use rustc_hir::intravisit::{walk_expr, Visitor};
 use rustc_hir::{BinOpKind, Block, Expr, ExprKind, QPath, UnOp};
 use rustc_lint::LateContext;
@@ -98,6 +100,24 @@ fn fn_eagerness(cx: &LateContext<'_>, fn_id: DefId, name: Symbol, have_one_arg:
     }
 }
 
+/// Determine the eagerness of the call `call` to a function which can't be evaluated at compile
+/// time.
+fn call_eagerness<'tcx>(
+    cx: &LateContext<'tcx>,
+    call: &'tcx Expr<'_>,
+    fn_id: DefId,
+    name: Symbol,
+    have_one_arg: bool,
+) -> EagernessSuggestion {
+    match fn_eagerness(cx, fn_id, name, have_one_arg) {
+        // A `const fn` can't allocate or call non-`const` functions, so it's likely to be cheap.
+        EagernessSuggestion::Lazy if cx.tcx.is_const_fn(fn_id) => EagernessSuggestion::NoChange,
+        // Neither can a call which doesn't allocate, panic or have side effects.
+        EagernessSuggestion::Lazy if effects_of(cx, call).is_pure() => EagernessSuggestion::NoChange,
+        eagerness => eagerness,
+    }
+}
+
 fn res_has_significant_drop(res: Res, cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
     if let Res::Def(DefKind::Ctor(..) | DefKind::Variant | DefKind::Enum | DefKind::Struct, _)
     | Res::SelfCtor(_)
@@ -112,9 +132,10 @@ fn res_has_significant_drop(res: Res, cx: &LateContext<'_>, e: &Expr<'_>) -> boo
 }
 
 #[expect(clippy::too_many_lines)]
-fn expr_eagerness<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) -> EagernessSuggestion {
+fn expr_eagerness<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'_>, expensive_calls: &DefIdSet) -> EagernessSuggestion {
     struct V<'cx, 'tcx> {
         cx: &'cx LateContext<'tcx>,
+        expensive_calls: &'cx DefIdSet,
         eagerness: EagernessSuggestion,
     }
 
@@ -153,6 +174,7 @@ fn expr_eagerness<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) -> EagernessS
                             return;
                         }
                     },
+                    Res::Def(_, id) if self.expensive_calls.contains(&id) => self.eagerness |= Lazy,
                     Res::Def(_, id) if self.cx.tcx.is_promotable_const_fn(id) => (),
                     // No need to walk the arguments here, `is_const_evaluatable` already did
                     Res::Def(..) if is_const_evaluatable(self.cx, e) => {
@@ -162,15 +184,24 @@ fn expr_eagerness<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) -> EagernessS
                     Res::Def(_, id) => match path {
                         QPath::Resolved(_, p) => {
                             self.eagerness |=
-                                fn_eagerness(self.cx, id, p.segments.last().unwrap().ident.name, !args.is_empty());
+                                call_eagerness(self.cx, e, id, p.segments.last().unwrap().ident.name, !args.is_empty());
                         },
                         QPath::TypeRelative(_, name) => {
-                            self.eagerness |= fn_eagerness(self.cx, id, name.ident.name, !args.is_empty());
+                            self.eagerness |= call_eagerness(self.cx, e, id, name.ident.name, !args.is_empty());
                         },
                         QPath::LangItem(..) => self.eagerness = Lazy,
                     },
                     _ => self.eagerness = Lazy,
                 },
+                ExprKind::MethodCall(..)
+                    if self
+                        .cx
+                        .typeck_results()
+                        .type_dependent_def_id(e.hir_id)
+                        .is_some_and(|id| self.expensive_calls.contains(&id)) =>
+                {
+                    self.eagerness |= Lazy;
+                },
                 // No need to walk the arguments here, `is_const_evaluatable` already did
                 ExprKind::MethodCall(..) if is_const_evaluatable(self.cx, e) => {
                     self.eagerness |= NoChange;
@@ -194,7 +225,7 @@ fn expr_eagerness<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) -> EagernessS
                         .cx
                         .typeck_results()
                         .type_dependent_def_id(e.hir_id)
-                        .map_or(Lazy, |id| fn_eagerness(self.cx, id, name.ident.name, true));
+                        .map_or(Lazy, |id| call_eagerness(self.cx, e, id, name.ident.name, true));
                 },
                 ExprKind::Index(_, e, _) => {
                     let ty = self.cx.typeck_results().expr_ty_adjusted(e);
@@ -316,6 +347,7 @@ fn expr_eagerness<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) -> EagernessS
 
     let mut v = V {
         cx,
+        expensive_calls,
         eagerness: EagernessSuggestion::Eager,
     };
     v.visit_expr(e);
@@ -324,10 +356,35 @@ fn expr_eagerness<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) -> EagernessS
 
 /// Whether the given expression should be changed to evaluate eagerly
 pub fn switch_to_eager_eval<'tcx>(cx: &'_ LateContext<'tcx>, expr: &'tcx Expr<'_>) -> bool {
-    expr_eagerness(cx, expr) == EagernessSuggestion::Eager
+    switch_to_eager_eval_with_costs(cx, expr, &DefIdSet::default())
 }
 
 /// Whether the given expression should be changed to evaluate lazily
 pub fn switch_to_lazy_eval<'tcx>(cx: &'_ LateContext<'tcx>, expr: &'tcx Expr<'_>) -> bool {
-    expr_eagerness(cx, expr) == EagernessSuggestion::Lazy
+    switch_to_lazy_eval_with_costs(cx, expr, &DefIdSet::default())
+}
+
+/// Like [`switch_to_eager_eval`], but the calls to the functions of `expensive_calls` are always
+/// considered expensive, see [`expensive_call_ids`].
+pub fn switch_to_eager_eval_with_costs<'tcx>(
+    cx: &'_ LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    expensive_calls: &DefIdSet,
+) -> bool {
+    expr_eagerness(cx, expr, expensive_calls) == EagernessSuggestion::Eager
+}
+
+/// Like [`switch_to_lazy_eval`], but the calls to the functions of `expensive_calls` are always
+/// considered expensive, see [`expensive_call_ids`].
+pub fn switch_to_lazy_eval_with_costs<'tcx>(
+    cx: &'_ LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    expensive_calls: &DefIdSet,
+) -> bool {
+    expr_eagerness(cx, expr, expensive_calls) == EagernessSuggestion::Lazy
+}
+
+/// Resolves the paths of the `expensive-calls` configuration
+pub fn expensive_call_ids(cx: &LateContext<'_>, paths: &[String]) -> DefIdSet {
+    paths.iter().flat_map(|path| local_def_path_def_ids(cx, path)).collect()
 }
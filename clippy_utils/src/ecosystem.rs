@@ -0,0 +1,113 @@
+//! Paths to the items of commonly used third-party crates.
+//!
+//! Unlike the definition paths of [`crate::paths`], the paths here are the public paths users
+//! write, which are resolved through re-exports. Crates often move items between private modules
+//! across releases and only keep their public paths stable, so matching the definition path breaks
+//! as soon as the crate is updated.
+//!
+//! An item can list several paths, e.g. when it's reachable both from the root of the crate and
+//! from a module, or when it's exported by a facade crate like `futures`. Resolving the paths is
+//! expensive, so the results are cached for the crate being compiled.
+
+use crate::{def_path_def_ids, path_def_id};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_hir::Expr;
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, Ty};
+use rustc_span::def_id::StableCrateId;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The public paths of an item of a third-party crate.
+pub type ItemPaths = &'static [&'static [&'static str]];
+
+pub const FUTURES_BLOCK_ON: ItemPaths = &[&["futures", "executor", "block_on"], &["futures_executor", "block_on"]];
+pub const FUTURES_IO_ASYNC_READ_EXT: ItemPaths = &[
+    &["futures", "io", "AsyncReadExt"],
+    &["futures_util", "io", "AsyncReadExt"],
+];
+pub const FUTURES_IO_ASYNC_WRITE_EXT: ItemPaths = &[
+    &["futures", "io", "AsyncWriteExt"],
+    &["futures_util", "io", "AsyncWriteExt"],
+];
+pub const FUTURES_JOIN_ALL: ItemPaths = &[
+    &["futures", "future", "join_all"],
+    &["futures_util", "future", "join_all"],
+];
+pub const RAND_RANDOM: ItemPaths = &[&["rand", "random"]];
+pub const RAND_RNG: ItemPaths = &[&["rand", "Rng"]];
+pub const RAND_THREAD_RNG: ItemPaths = &[&["rand", "thread_rng"]];
+pub const REGEX_BUILDER_NEW: ItemPaths = &[&["regex", "RegexBuilder", "new"]];
+pub const REGEX_BYTES_BUILDER_NEW: ItemPaths = &[&["regex", "bytes", "RegexBuilder", "new"]];
+pub const REGEX_BYTES_NEW: ItemPaths = &[&["regex", "bytes", "Regex", "new"]];
+pub const REGEX_BYTES_SET_NEW: ItemPaths = &[&["regex", "bytes", "RegexSet", "new"]];
+pub const REGEX_NEW: ItemPaths = &[&["regex", "Regex", "new"]];
+pub const REGEX_SET_NEW: ItemPaths = &[&["regex", "RegexSet", "new"]];
+pub const SERDE_DESERIALIZE: ItemPaths = &[&["serde", "de", "Deserialize"]];
+pub const SERDE_DE_VISITOR: ItemPaths = &[&["serde", "de", "Visitor"]];
+pub const SERDE_SERIALIZE: ItemPaths = &[&["serde", "ser", "Serialize"]];
+pub const TOKIO_FILE_OPTIONS: ItemPaths = &[&["tokio", "fs", "File", "options"]];
+pub const TOKIO_IO_ASYNC_READ_EXT: ItemPaths = &[&["tokio", "io", "AsyncReadExt"]];
+pub const TOKIO_IO_ASYNC_WRITE_EXT: ItemPaths = &[&["tokio", "io", "AsyncWriteExt"]];
+pub const TOKIO_MUTEX: ItemPaths = &[&["tokio", "sync", "Mutex"]];
+pub const TOKIO_OPEN_OPTIONS: ItemPaths = &[&["tokio", "fs", "OpenOptions"]];
+pub const TOKIO_OPEN_OPTIONS_NEW: ItemPaths = &[&["tokio", "fs", "OpenOptions", "new"]];
+pub const TOKIO_RUNTIME_BLOCK_ON: ItemPaths = &[&["tokio", "runtime", "Runtime", "block_on"]];
+pub const TOKIO_SLEEP: ItemPaths = &[&["tokio", "time", "sleep"]];
+pub const TOKIO_SPAWN: ItemPaths = &[&["tokio", "spawn"], &["tokio", "task", "spawn"]];
+pub const TOKIO_SPAWN_BLOCKING: ItemPaths = &[&["tokio", "task", "spawn_blocking"]];
+
+/// The resolved items, for the crate being compiled.
+#[derive(Default)]
+struct Resolved {
+    krate: Option<StableCrateId>,
+    def_ids: FxHashMap<ItemPaths, Rc<[DefId]>>,
+}
+
+thread_local! {
+    static RESOLVED: RefCell<Resolved> = RefCell::default();
+}
+
+/// Resolves the paths of an item to its `DefId`s. Returns an empty slice if the crate isn't a
+/// dependency.
+pub fn def_ids(cx: &LateContext<'_>, path: ItemPaths) -> Rc<[DefId]> {
+    let krate = cx.tcx.stable_crate_id(LOCAL_CRATE);
+    RESOLVED.with_borrow_mut(|resolved| {
+        if resolved.krate != Some(krate) {
+            resolved.krate = Some(krate);
+            resolved.def_ids.clear();
+        }
+        resolved
+            .def_ids
+            .entry(path)
+            .or_insert_with(|| path.iter().flat_map(|path| def_path_def_ids(cx, path)).collect())
+            .clone()
+    })
+}
+
+/// Checks if the given `DefId` is the item.
+pub fn is_def_in(cx: &LateContext<'_>, did: DefId, path: ItemPaths) -> bool {
+    def_ids(cx, path).contains(&did)
+}
+
+/// If `expr` is a path which resolves to an item, checks if it's the given item.
+pub fn is_expr_path_in(cx: &LateContext<'_>, expr: &Expr<'_>, path: ItemPaths) -> bool {
+    path_def_id(cx, expr).map_or(false, |did| is_def_in(cx, did, path))
+}
+
+/// Checks if the method call given in `expr` belongs to the given trait.
+pub fn is_trait_method_in(cx: &LateContext<'_>, expr: &Expr<'_>, path: ItemPaths) -> bool {
+    cx.typeck_results()
+        .type_dependent_def_id(expr.hir_id)
+        .and_then(|did| cx.tcx.trait_of_item(did))
+        .map_or(false, |trait_did| is_def_in(cx, trait_did, path))
+}
+
+/// Checks if the type is the given ADT, ignoring its generic arguments.
+pub fn is_type_in(cx: &LateContext<'_>, ty: Ty<'_>, path: ItemPaths) -> bool {
+    match ty.kind() {
+        ty::Adt(adt, _) => is_def_in(cx, adt.did(), path),
+        _ => false,
+    }
+}
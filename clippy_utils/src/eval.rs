@@ -0,0 +1,454 @@
+//! A conservative classification of what the evaluation of an expression can do besides computing
+//! its value, for the lints which need to know whether an expression can be moved, evaluated lazily
+//! or removed.
+//!
+//! Constants are pure. Otherwise the effects of the calls to the standard library are taken from a
+//! table of known functions, and the calls to the other functions are assumed to have every effect.
+//! The bodies of the closures count only when the closures are passed to a call. Arithmetic
+//! overflow isn't considered to panic, since it only does with overflow checks.
+
+use crate::consts::constant;
+use crate::panics::{division_may_panic, index_may_panic};
+use crate::path_to_local;
+use crate::ty::all_predicates_of;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{walk_expr, walk_pat, Visitor};
+use rustc_hir::{BinOpKind, Expr, ExprKind, HirIdSet, Pat, PatKind, UnOp};
+use rustc_lint::LateContext;
+use rustc_middle::ty::adjustment::Adjust;
+use rustc_middle::ty::{self, ClauseKind, GenericArg, GenericArgsRef, Instance, ParamEnv, Ty, TypeckResults};
+use rustc_span::sym;
+use std::ops::{BitOr, BitOrAssign};
+
+/// A set of effects. The empty set is [`Effects::PURE`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Effects(u8);
+
+impl Effects {
+    /// Evaluating the expression again, or not at all, makes no difference besides its value.
+    pub const PURE: Self = Self(0);
+    /// Allocates on the heap.
+    pub const ALLOCATES: Self = Self(1);
+    pub const MAY_PANIC: Self = Self(1 << 1);
+    /// Reads or writes files, streams, sockets, the environment or the clock, or starts threads or
+    /// processes.
+    pub const DOES_IO: Self = Self(1 << 2);
+    /// Mutates a place which isn't declared in the expression, or the state of the process.
+    pub const MUTATES_ENV: Self = Self(1 << 3);
+    /// Every effect, the ones of an unknown function.
+    pub const ALL: Self = Self(0b1111);
+
+    const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn is_pure(self) -> bool {
+        self == Self::PURE
+    }
+
+    /// Whether all of `effects` are in `self`.
+    pub fn contains(self, effects: Self) -> bool {
+        self.0 & effects.0 == effects.0
+    }
+}
+
+impl BitOr for Effects {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for Effects {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs);
+    }
+}
+
+/// The effects of the functions of the standard library whose path starts with one of these. The
+/// first match applies.
+const KNOWN_PATHS: &[(&str, Effects)] = &[
+    ("core::panicking", Effects::MAY_PANIC),
+    ("std::panicking", Effects::MAY_PANIC),
+    ("std::env::set_var", Effects::MUTATES_ENV.union(Effects::DOES_IO)),
+    ("std::env::remove_var", Effects::MUTATES_ENV.union(Effects::DOES_IO)),
+    (
+        "std::env::set_current_dir",
+        Effects::MUTATES_ENV.union(Effects::DOES_IO),
+    ),
+    ("std::env", Effects::DOES_IO.union(Effects::ALLOCATES)),
+    ("std::io", Effects::DOES_IO.union(Effects::MAY_PANIC)),
+    ("std::fs", Effects::DOES_IO.union(Effects::ALLOCATES)),
+    ("std::net", Effects::DOES_IO),
+    ("std::time", Effects::DOES_IO),
+    ("std::process", Effects::DOES_IO.union(Effects::MUTATES_ENV)),
+    ("std::thread", Effects::ALL),
+    ("std::sync", Effects::ALL),
+    ("alloc::fmt::format", Effects::ALLOCATES),
+];
+
+/// The functions of the standard library with these names don't allocate, even if they are
+/// defined in `alloc` or are generic over a type of `alloc`.
+const NON_ALLOCATING: &[&str] = &[
+    "as_bytes",
+    "as_mut",
+    "as_mut_ptr",
+    "as_mut_slice",
+    "as_ptr",
+    "as_ref",
+    "as_slice",
+    "as_str",
+    "borrow",
+    "borrow_mut",
+    "bytes",
+    "capacity",
+    "chars",
+    "cmp",
+    "contains",
+    "contains_key",
+    "deref",
+    "deref_mut",
+    "ends_with",
+    "eq",
+    "first",
+    "ge",
+    "get",
+    "get_mut",
+    "gt",
+    "hash",
+    "index",
+    "index_mut",
+    "is_empty",
+    "is_err",
+    "is_none",
+    "is_ok",
+    "is_some",
+    "iter",
+    "iter_mut",
+    "keys",
+    "last",
+    "le",
+    "len",
+    "lt",
+    "ne",
+    "partial_cmp",
+    "pop",
+    "starts_with",
+    "take",
+    "trim",
+    "values",
+];
+
+/// The functions of the standard library with these names, or with a name of [`NON_ALLOCATING`],
+/// don't panic, except through the closures they call.
+const NON_PANICKING: &[&str] = &[
+    "all",
+    "and_then",
+    "any",
+    "clone",
+    "cloned",
+    "collect",
+    "copied",
+    "count",
+    "enumerate",
+    "err",
+    "filter",
+    "filter_map",
+    "find",
+    "from",
+    "into",
+    "into_iter",
+    "map",
+    "map_or",
+    "map_or_else",
+    "max",
+    "min",
+    "next",
+    "ok",
+    "ok_or",
+    "ok_or_else",
+    "or_else",
+    "position",
+    "push_str",
+    "rev",
+    "to_owned",
+    "to_string",
+    "to_vec",
+    "unwrap_or",
+    "unwrap_or_default",
+    "unwrap_or_else",
+    "zip",
+];
+
+/// Returns the effects which evaluating `expr` may have. The classification is conservative: an
+/// effect which isn't returned can't happen, but the returned ones may not.
+pub fn effects_of<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) -> Effects {
+    let owner = cx.tcx.hir().enclosing_body_owner(expr.hir_id);
+    let typeck_results = cx.tcx.typeck(owner);
+    if constant(cx, typeck_results, expr).is_some() {
+        return Effects::PURE;
+    }
+    let mut visitor = EffectsVisitor {
+        cx,
+        typeck_results,
+        param_env: cx.tcx.param_env(owner),
+        locals: HirIdSet::default(),
+        effects: Effects::PURE,
+    };
+    visitor.visit_expr(expr);
+    visitor.effects
+}
+
+struct EffectsVisitor<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    /// The locals declared in the expression, which it can mutate without effects.
+    locals: HirIdSet,
+    effects: Effects,
+}
+
+impl<'a, 'tcx> EffectsVisitor<'a, 'tcx> {
+    /// Whether `place` is a local declared in the expression, or a part of one.
+    fn is_inner_place(&self, mut place: &Expr<'_>) -> bool {
+        loop {
+            match place.kind {
+                ExprKind::Field(base, _) | ExprKind::Index(base, ..) => place = base,
+                // Through a reference, the place can be anywhere.
+                ExprKind::Unary(UnOp::Deref, base) if self.typeck_results.expr_ty(base).is_box() => place = base,
+                _ => return path_to_local(place).map_or(false, |id| self.locals.contains(&id)),
+            }
+        }
+    }
+
+    /// Adds the effects of a call to `def_id` with the given generic arguments and arguments.
+    fn call(&mut self, def_id: DefId, generic_args: GenericArgsRef<'tcx>, args: &[&'tcx Expr<'tcx>]) {
+        self.effects |= self.fn_effects(def_id, generic_args);
+        for arg in args {
+            let ty = self.typeck_results.expr_ty_adjusted(arg);
+            if self.may_mutate_through(ty) && !self.is_inner_place(arg.peel_borrows()) {
+                self.effects |= Effects::MUTATES_ENV;
+            }
+            match arg.kind {
+                ExprKind::Closure(closure) => {
+                    let body = self.cx.tcx.hir().body(closure.body);
+                    for param in body.params {
+                        self.visit_pat(param.pat);
+                    }
+                    self.visit_expr(body.value);
+                },
+                // The closures and functions which aren't visible here are unknown.
+                _ if !is_known_callable(self.cx, ty.peel_refs()) => self.effects = Effects::ALL,
+                _ => {},
+            }
+        }
+    }
+
+    /// Whether a value of type `ty` gives mutable access to something.
+    fn may_mutate_through(&self, ty: Ty<'tcx>) -> bool {
+        match ty.kind() {
+            ty::Ref(_, _, ty::Mutability::Mut) | ty::RawPtr(_, ty::Mutability::Mut) => true,
+            ty::Ref(_, inner, _) => !inner.is_freeze(self.cx.tcx, self.param_env),
+            _ => false,
+        }
+    }
+
+    /// Returns the effects of the function `def_id`, or of the implementation of the trait method
+    /// `def_id` which is called.
+    fn fn_effects(&self, def_id: DefId, generic_args: GenericArgsRef<'tcx>) -> Effects {
+        let tcx = self.cx.tcx;
+        let def_id = if tcx.trait_of_item(def_id).is_some() {
+            match Instance::resolve(tcx, self.param_env, def_id, generic_args) {
+                Ok(Some(instance)) => instance.def_id(),
+                _ => return Effects::ALL,
+            }
+        } else {
+            def_id
+        };
+        let krate = tcx.crate_name(def_id.krate);
+        if ![sym::std, sym::core, sym::alloc].contains(&krate) {
+            return Effects::ALL;
+        }
+        let mut path = krate.to_string();
+        for name in tcx
+            .def_path(def_id)
+            .data
+            .iter()
+            .filter_map(|data| data.data.get_opt_name())
+        {
+            path.push_str("::");
+            path.push_str(name.as_str());
+        }
+        if let Some(&(_, effects)) = KNOWN_PATHS.iter().find(|(prefix, _)| {
+            path.strip_prefix(prefix)
+                .map_or(false, |rest| rest.is_empty() || rest.starts_with("::"))
+        }) {
+            return effects;
+        }
+        // The generic functions of the standard library call the implementations of their bounds,
+        // which are unknown for the types of the other crates.
+        if generic_args.types().any(|ty| self.is_foreign_type(ty))
+            && all_predicates_of(tcx, def_id).any(|(clause, _)| match clause.kind().skip_binder() {
+                ClauseKind::Trait(pred) => {
+                    !tcx.trait_is_auto(pred.def_id()) && !is_marker_trait(self.cx, pred.def_id())
+                },
+                _ => false,
+            })
+        {
+            return Effects::ALL;
+        }
+        let name = tcx.item_name(def_id);
+        let name = name.as_str();
+        let is_const = tcx.is_const_fn_raw(def_id);
+        let mut effects = Effects::PURE;
+        if !is_const
+            && !NON_ALLOCATING.contains(&name)
+            && (krate == sym::alloc || generic_args.types().any(|ty| contains_alloc_type(self.cx, ty)))
+        {
+            effects |= Effects::ALLOCATES;
+        }
+        if matches!(name, "unwrap" | "expect" | "unwrap_err" | "expect_err")
+            || !is_const && !NON_ALLOCATING.contains(&name) && !NON_PANICKING.contains(&name)
+        {
+            effects |= Effects::MAY_PANIC;
+        }
+        effects
+    }
+
+    /// Whether `ty` contains a type whose trait implementations may be outside of the standard
+    /// library.
+    fn is_foreign_type(&self, ty: Ty<'tcx>) -> bool {
+        ty.walk().filter_map(GenericArg::as_type).any(|ty| match ty.kind() {
+            ty::Adt(adt, _) => ![sym::std, sym::core, sym::alloc].contains(&self.cx.tcx.crate_name(adt.did().krate)),
+            ty::Param(_) | ty::Alias(..) | ty::Dynamic(..) | ty::Foreign(_) => true,
+            _ => false,
+        })
+    }
+
+    /// Adds the effects of the overloaded operator of `expr`, if it is one.
+    fn overloaded_op(&mut self, expr: &'tcx Expr<'tcx>, args: &[&'tcx Expr<'tcx>]) -> bool {
+        if let Some(def_id) = self.typeck_results.type_dependent_def_id(expr.hir_id) {
+            self.call(def_id, self.typeck_results.node_args(expr.hir_id), args);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Whether `ty` isn't a closure, a function pointer or a trait object, or is a function of the
+/// standard library.
+fn is_known_callable(cx: &LateContext<'_>, ty: Ty<'_>) -> bool {
+    match ty.kind() {
+        ty::Closure(..) | ty::FnPtr(_) | ty::Dynamic(..) | ty::Coroutine(..) => false,
+        ty::FnDef(def_id, _) => [sym::std, sym::core, sym::alloc].contains(&cx.tcx.crate_name(def_id.krate)),
+        _ => true,
+    }
+}
+
+fn is_marker_trait(cx: &LateContext<'_>, def_id: DefId) -> bool {
+    cx.tcx.lang_items().sized_trait() == Some(def_id) || cx.tcx.is_diagnostic_item(sym::Copy, def_id)
+}
+
+/// Whether `ty` contains a type of `alloc`, which the functions generic over it may allocate.
+fn contains_alloc_type(cx: &LateContext<'_>, ty: Ty<'_>) -> bool {
+    ty.walk().filter_map(GenericArg::as_type).any(|ty| match ty.kind() {
+        ty::Adt(adt, _) => cx.tcx.crate_name(adt.did().krate) == sym::alloc,
+        _ => false,
+    })
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for EffectsVisitor<'a, 'tcx> {
+    fn visit_pat(&mut self, pat: &'tcx Pat<'tcx>) {
+        if let PatKind::Binding(_, id, ..) = pat.kind {
+            self.locals.insert(id);
+        }
+        walk_pat(self, pat);
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        if self.effects == Effects::ALL {
+            return;
+        }
+        for adjustment in self.typeck_results.expr_adjustments(expr) {
+            // The overloaded dereferences of the standard library are pure, the other ones unknown.
+            if let Adjust::Deref(Some(_)) = adjustment.kind
+                && self.is_foreign_type(self.typeck_results.expr_ty(expr).peel_refs())
+            {
+                self.effects = Effects::ALL;
+            }
+        }
+        match expr.kind {
+            ExprKind::Call(callee, args) => {
+                let args: Vec<_> = args.iter().collect();
+                match callee.kind {
+                    ExprKind::Path(ref qpath) => match self.typeck_results.qpath_res(qpath, callee.hir_id) {
+                        Res::Def(DefKind::Ctor(..) | DefKind::Variant, _) | Res::SelfCtor(_) => {
+                            for arg in args {
+                                self.visit_expr(arg);
+                            }
+                        },
+                        Res::Def(DefKind::Fn | DefKind::AssocFn, def_id) => {
+                            self.call(def_id, self.typeck_results.node_args(callee.hir_id), &args);
+                            for arg in args.iter().filter(|arg| !matches!(arg.kind, ExprKind::Closure(_))) {
+                                self.visit_expr(arg);
+                            }
+                        },
+                        _ => self.effects = Effects::ALL,
+                    },
+                    _ => self.effects = Effects::ALL,
+                }
+                return;
+            },
+            ExprKind::MethodCall(_, receiver, args, _) => {
+                let args: Vec<_> = [receiver].into_iter().chain(args).collect();
+                if !self.overloaded_op(expr, &args) {
+                    self.effects = Effects::ALL;
+                }
+                for arg in args.iter().filter(|arg| !matches!(arg.kind, ExprKind::Closure(_))) {
+                    self.visit_expr(arg);
+                }
+                return;
+            },
+            ExprKind::Assign(place, ..) => {
+                if !self.is_inner_place(place) {
+                    self.effects |= Effects::MUTATES_ENV;
+                }
+            },
+            ExprKind::AssignOp(op, place, value) => {
+                if !self.is_inner_place(place) {
+                    self.effects |= Effects::MUTATES_ENV;
+                }
+                if !self.overloaded_op(expr, &[place, value])
+                    && matches!(op.node, BinOpKind::Div | BinOpKind::Rem)
+                    && division_may_panic(self.cx, self.typeck_results, value)
+                {
+                    self.effects |= Effects::MAY_PANIC;
+                }
+            },
+            ExprKind::Binary(op, lhs, rhs) => {
+                if !self.overloaded_op(expr, &[lhs, rhs])
+                    && matches!(op.node, BinOpKind::Div | BinOpKind::Rem)
+                    && division_may_panic(self.cx, self.typeck_results, rhs)
+                {
+                    self.effects |= Effects::MAY_PANIC;
+                }
+            },
+            ExprKind::Unary(_, operand) => {
+                self.overloaded_op(expr, &[operand]);
+            },
+            ExprKind::Index(base, index, _) => {
+                if self.overloaded_op(expr, &[base, index])
+                    || index_may_panic(self.cx, self.typeck_results, base, index)
+                {
+                    self.effects |= Effects::MAY_PANIC;
+                }
+            },
+            ExprKind::InlineAsm(_) | ExprKind::Yield(..) => self.effects = Effects::ALL,
+            _ => {},
+        }
+        walk_expr(self, expr);
+    }
+}
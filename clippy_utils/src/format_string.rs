@@ -0,0 +1,263 @@
+//! A typed view of the format string of a `format_args!` invocation, and so of the `format!`,
+//! `write!`, `println!`, `panic!`... invocations expanding to it.
+//!
+//! The format string is parsed by rustc into a [`FormatArgs`] node, which clippy keeps in the
+//! [`FormatArgsStorage`](crate::macros::FormatArgsStorage). The types here map each placeholder and
+//! `width$`/`precision$` parameter to the argument it refers to, whether that argument is passed
+//! positionally, by name, or captured inline as in `format!("{x}")`.
+
+use crate::macros::{
+    find_format_arg_expr, format_arg_removal_span, format_args_inputs_span, format_placeholder_format_span,
+    FormatParamUsage,
+};
+use arrayvec::ArrayVec;
+use rustc_ast::{
+    FormatArgPosition, FormatArgPositionKind, FormatArgs, FormatArgsPiece, FormatArgument, FormatArgumentKind,
+    FormatCount, FormatOptions, FormatPlaceholder, FormatTrait,
+};
+use rustc_hir::Expr;
+use rustc_span::{sym, Span, Symbol};
+
+/// The format string of a `format_args!` invocation
+#[derive(Clone, Copy)]
+pub struct FormatString<'a> {
+    pub format_args: &'a FormatArgs,
+}
+
+impl<'a> FormatString<'a> {
+    pub fn new(format_args: &'a FormatArgs) -> Self {
+        Self { format_args }
+    }
+
+    /// The span of the format string literal
+    pub fn span(self) -> Span {
+        self.format_args.span
+    }
+
+    /// The pieces of the format string, in order
+    pub fn pieces(self) -> impl Iterator<Item = Piece<'a>> {
+        self.format_args.template.iter().map(move |piece| match piece {
+            FormatArgsPiece::Literal(literal) => Piece::Literal(literal.as_str()),
+            FormatArgsPiece::Placeholder(placeholder) => Piece::Placeholder(Placeholder {
+                placeholder,
+                arg: self.arg_at(&placeholder.argument),
+            }),
+        })
+    }
+
+    /// The placeholders of the format string, in order
+    pub fn placeholders(self) -> impl Iterator<Item = Placeholder<'a>> {
+        self.pieces().filter_map(|piece| match piece {
+            Piece::Placeholder(placeholder) => Some(placeholder),
+            Piece::Literal(_) => None,
+        })
+    }
+
+    /// The literal pieces of the format string, with `{{` and `}}` unescaped
+    pub fn literals(self) -> impl Iterator<Item = &'a str> {
+        self.pieces().filter_map(|piece| match piece {
+            Piece::Literal(literal) => Some(literal),
+            Piece::Placeholder(_) => None,
+        })
+    }
+
+    /// Returns the formatted string if the format string has no placeholders, e.g. `a{b` for
+    /// `format!("a{{b")`
+    pub fn as_literal(self) -> Option<String> {
+        self.pieces()
+            .map(|piece| match piece {
+                Piece::Literal(literal) => Some(literal),
+                Piece::Placeholder(_) => None,
+            })
+            .collect()
+    }
+
+    /// Every reference to an argument: the value of each placeholder and its `width$` and
+    /// `precision$` parameters
+    pub fn params(self) -> impl Iterator<Item = Param<'a>> {
+        self.placeholders().flat_map(Placeholder::params)
+    }
+
+    /// The arguments, explicit ones first, followed by the ones captured inline
+    pub fn args(self) -> impl Iterator<Item = Arg<'a>> {
+        self.format_args
+            .arguments
+            .all_args()
+            .iter()
+            .enumerate()
+            .map(|(index, argument)| Arg { index, argument })
+    }
+
+    pub fn arg(self, index: usize) -> Option<Arg<'a>> {
+        let argument = self.format_args.arguments.all_args().get(index)?;
+        Some(Arg { index, argument })
+    }
+
+    fn arg_at(self, position: &FormatArgPosition) -> Option<Arg<'a>> {
+        self.arg(position.index.ok()?)
+    }
+
+    /// The number of references to the argument at `index`
+    pub fn use_count(self, index: usize) -> usize {
+        self.params().filter(|param| param.index() == Some(index)).count()
+    }
+
+    /// Checks if the argument at `index` is referred to by multiple params
+    pub fn is_aliased(self, index: usize) -> bool {
+        self.params()
+            .filter(|param| param.index() == Some(index))
+            .nth(1)
+            .is_some()
+    }
+
+    /// The span covering the argument at `index` and the comma before it, to remove it
+    pub fn arg_removal_span(self, index: usize) -> Option<Span> {
+        format_arg_removal_span(self.format_args, index)
+    }
+
+    /// The span covering the format string and the explicit arguments, see
+    /// [`format_args_inputs_span`]
+    pub fn inputs_span(self) -> Span {
+        format_args_inputs_span(self.format_args)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Piece<'a> {
+    Literal(&'a str),
+    Placeholder(Placeholder<'a>),
+}
+
+/// A `{..}` in the format string
+#[derive(Clone, Copy)]
+pub struct Placeholder<'a> {
+    pub placeholder: &'a FormatPlaceholder,
+    /// The argument formatted by the placeholder, if it exists
+    pub arg: Option<Arg<'a>>,
+}
+
+impl<'a> Placeholder<'a> {
+    /// The span of the `{..}`, if it's written in the format string
+    pub fn span(self) -> Option<Span> {
+        self.placeholder.span
+    }
+
+    pub fn format_trait(self) -> FormatTrait {
+        self.placeholder.format_trait
+    }
+
+    /// The name of the formatting trait, e.g. `Debug` for `{:?}`
+    pub fn format_trait_name(self) -> Symbol {
+        match self.placeholder.format_trait {
+            FormatTrait::Display => sym::Display,
+            FormatTrait::Debug => sym::Debug,
+            FormatTrait::LowerExp => sym!(LowerExp),
+            FormatTrait::UpperExp => sym!(UpperExp),
+            FormatTrait::Octal => sym!(Octal),
+            FormatTrait::Pointer => sym::Pointer,
+            FormatTrait::Binary => sym!(Binary),
+            FormatTrait::LowerHex => sym!(LowerHex),
+            FormatTrait::UpperHex => sym!(UpperHex),
+        }
+    }
+
+    pub fn format_options(self) -> &'a FormatOptions {
+        &self.placeholder.format_options
+    }
+
+    /// Checks if the placeholder is a `{}` without format specifiers, e.g. `{}`, `{0}` or `{x}`
+    pub fn is_plain_display(self) -> bool {
+        self.placeholder.format_trait == FormatTrait::Display
+            && self.placeholder.format_options == FormatOptions::default()
+    }
+
+    /// The span of the `:` and the format specifiers, see [`format_placeholder_format_span`]
+    pub fn format_span(self) -> Option<Span> {
+        format_placeholder_format_span(self.placeholder)
+    }
+
+    /// The param referring to the formatted value, e.g. `x` in `{x:w$}`
+    pub fn param(self) -> Param<'a> {
+        Param {
+            position: &self.placeholder.argument,
+            usage: FormatParamUsage::Argument,
+        }
+    }
+
+    /// The param of the formatted value and the ones of its `width$` and `precision$`
+    pub fn params(self) -> ArrayVec<Param<'a>, 3> {
+        let mut params = ArrayVec::new();
+        params.push(self.param());
+        if let Some(FormatCount::Argument(position)) = &self.placeholder.format_options.width {
+            params.push(Param {
+                position,
+                usage: FormatParamUsage::Width,
+            });
+        }
+        if let Some(FormatCount::Argument(position)) = &self.placeholder.format_options.precision {
+            params.push(Param {
+                position,
+                usage: FormatParamUsage::Precision,
+            });
+        }
+        params
+    }
+}
+
+/// A reference to an argument from the format string, e.g. `x`, `0` and `w` in `{x:0$.w$}`
+#[derive(Clone, Copy)]
+pub struct Param<'a> {
+    pub position: &'a FormatArgPosition,
+    pub usage: FormatParamUsage,
+}
+
+impl Param<'_> {
+    /// The index of the argument the param refers to, `None` if the argument doesn't exist
+    pub fn index(self) -> Option<usize> {
+        self.position.index.ok()
+    }
+
+    /// The span of the param in the format string, if it's written, e.g. not for the implicit
+    /// position of `{}`
+    pub fn span(self) -> Option<Span> {
+        self.position.span
+    }
+
+    /// Checks if the param refers to its argument by number, e.g. `{0}`
+    pub fn is_numbered(self) -> bool {
+        self.position.kind == FormatArgPositionKind::Number
+    }
+}
+
+/// An argument of the `format_args!` invocation
+#[derive(Clone, Copy)]
+pub struct Arg<'a> {
+    pub index: usize,
+    pub argument: &'a FormatArgument,
+}
+
+impl<'a> Arg<'a> {
+    /// The AST expression of the argument's value
+    pub fn ast_expr(self) -> &'a rustc_ast::Expr {
+        &self.argument.expr
+    }
+
+    /// The name of the argument, for `x` in `format!("{x}", x = 1)` or `format!("{x}")`
+    pub fn name(self) -> Option<Symbol> {
+        match self.argument.kind {
+            FormatArgumentKind::Named(ident) | FormatArgumentKind::Captured(ident) => Some(ident.name),
+            FormatArgumentKind::Normal => None,
+        }
+    }
+
+    /// Checks if the argument is captured inline in the format string, e.g. `x` in
+    /// `format!("{x}")`
+    pub fn is_inline(self) -> bool {
+        matches!(self.argument.kind, FormatArgumentKind::Captured(_))
+    }
+
+    /// Finds the HIR expression of the argument's value in `start`, see [`find_format_arg_expr`]
+    pub fn hir_expr<'hir>(self, start: &'hir Expr<'hir>) -> Result<&'hir Expr<'hir>, &'a rustc_ast::Expr> {
+        find_format_arg_expr(start, self.argument)
+    }
+}
@@ -3,14 +3,22 @@
 #![deny(clippy::missing_docs_in_private_items)]
 
 use crate::consts::{constant_simple, Constant};
+use crate::macros::find_format_arg_expr;
+use crate::source::snippet_with_context;
+use crate::sugg::Sugg;
 use crate::ty::is_type_diagnostic_item;
 use crate::{is_expn_of, match_def_path, paths};
 
-use rustc_ast::ast;
+use rustc_ast::{ast, FormatArgs, FormatArgument, FormatArgumentKind};
+use rustc_errors::Applicability;
 use rustc_hir as hir;
-use rustc_hir::{Arm, Block, Expr, ExprKind, HirId, LoopSource, MatchSource, Node, Pat, QPath};
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::{
+    Arm, Block, Body, CaptureBy, Closure, ClosureKind, CoroutineDesugaring, CoroutineKind, CoroutineSource, Expr,
+    ExprKind, HirId, LetStmt, LoopSource, MatchSource, Node, Pat, QPath,
+};
 use rustc_lint::LateContext;
-use rustc_span::{sym, symbol, Span};
+use rustc_span::{sym, symbol, DesugaringKind, Span, SyntaxContext};
 
 /// The essential nodes of a desugared for loop as well as the entire span:
 /// `for pat in arg { body }` becomes `(pat, arg, body)`. Returns `(pat, arg, body, span)`.
@@ -114,19 +122,8 @@ impl<'hir> IfLet<'hir> {
             if_else,
         ) = expr.kind
         {
-            let mut iter = cx.tcx.hir().parent_iter(expr.hir_id);
-            if let Some((_, Node::Block(Block { stmts: [], .. }))) = iter.next() {
-                if let Some((
-                    _,
-                    Node::Expr(Expr {
-                        kind: ExprKind::Loop(_, _, LoopSource::While, _),
-                        ..
-                    }),
-                )) = iter.next()
-                {
-                    // while loop desugar
-                    return None;
-                }
+            if is_while_desugar(cx, expr) {
+                return None;
             }
             return Some(Self {
                 let_pat,
@@ -410,6 +407,366 @@ impl<'hir> WhileLet<'hir> {
     }
 }
 
+/// A `let` statement with an `else` block
+pub struct LetElse<'hir> {
+    /// `let` pattern
+    pub pat: &'hir Pat<'hir>,
+    /// `let` type annotation
+    pub ty: Option<&'hir hir::Ty<'hir>>,
+    /// `let` initializer
+    pub init: &'hir Expr<'hir>,
+    /// diverging `else` block
+    pub els: &'hir Block<'hir>,
+    /// entire statement span
+    pub span: Span,
+}
+
+impl<'hir> LetElse<'hir> {
+    /// Parses a `let ... else` statement
+    pub fn hir(local: &LetStmt<'hir>) -> Option<Self> {
+        if let LetStmt {
+            pat,
+            ty,
+            init: Some(init),
+            els: Some(els),
+            span,
+            ..
+        } = *local
+        {
+            Some(Self {
+                pat,
+                ty,
+                init,
+                els,
+                span,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the source of the statement with the given initializer, e.g. to replace the
+    /// initializer while keeping the rest of the statement.
+    pub fn sugg_with_init(&self, cx: &LateContext<'_>, init: &str, applicability: &mut Applicability) -> String {
+        let ctxt = self.span.ctxt();
+        let (pat, _) = snippet_with_context(cx, self.pat.span, ctxt, "..", applicability);
+        let (els, _) = snippet_with_context(cx, self.els.span, ctxt, "{ .. }", applicability);
+        if let Some(ty) = self.ty {
+            let (ty, _) = snippet_with_context(cx, ty.span, ctxt, "_", applicability);
+            format!("let {pat}: {ty} = {init} else {els};")
+        } else {
+            format!("let {pat} = {init} else {els};")
+        }
+    }
+}
+
+/// One of the conditions of a `let` chain
+#[derive(Clone, Copy)]
+pub enum LetChainCond<'hir> {
+    /// `let PAT = EXPR`
+    Let(&'hir hir::LetExpr<'hir>),
+    /// a boolean condition
+    Expr(&'hir Expr<'hir>),
+}
+
+impl<'hir> LetChainCond<'hir> {
+    /// The span of the condition as written
+    pub fn span(self) -> Span {
+        match self {
+            Self::Let(let_expr) => let_expr.span,
+            Self::Expr(e) => e.span,
+        }
+    }
+
+    /// Returns the source of the condition, e.g. `let Some(x) = opt` or `x > 1`
+    pub fn sugg(self, cx: &LateContext<'_>, ctxt: SyntaxContext, applicability: &mut Applicability) -> String {
+        match self {
+            Self::Let(let_expr) => {
+                let (pat, _) = snippet_with_context(cx, let_expr.pat.span, ctxt, "..", applicability);
+                let init = Sugg::hir_with_context(cx, let_expr.init, ctxt, "..", applicability);
+                format!("let {pat} = {init}")
+            },
+            Self::Expr(e) => Sugg::hir_with_context(cx, e, ctxt, "..", applicability)
+                .maybe_par()
+                .to_string(),
+        }
+    }
+}
+
+/// Splits the condition of an `if` or `while` into the conditions of a `let` chain. Returns
+/// `None` if there are no `let` expressions in the condition.
+fn let_chain<'hir>(cond: &'hir Expr<'hir>) -> Option<Vec<LetChainCond<'hir>>> {
+    /// Adds the conditions of the `&&` tree in order
+    fn push<'hir>(e: &'hir Expr<'hir>, conds: &mut Vec<LetChainCond<'hir>>) {
+        match e.kind {
+            ExprKind::Binary(op, lhs, rhs) if op.node == hir::BinOpKind::And => {
+                push(lhs, conds);
+                push(rhs, conds);
+            },
+            ExprKind::Let(let_expr) => conds.push(LetChainCond::Let(let_expr)),
+            ExprKind::DropTemps(e) => conds.push(LetChainCond::Expr(e)),
+            _ => conds.push(LetChainCond::Expr(e)),
+        }
+    }
+
+    let mut conds = Vec::new();
+    push(cond, &mut conds);
+    conds
+        .iter()
+        .any(|cond| matches!(cond, LetChainCond::Let(_)))
+        .then_some(conds)
+}
+
+/// Checks if the `if` expression is the one of a desugared `while` loop
+fn is_while_desugar(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    let mut iter = cx.tcx.hir().parent_iter(expr.hir_id);
+    matches!(iter.next(), Some((_, Node::Block(Block { stmts: [], .. }))))
+        && matches!(
+            iter.next(),
+            Some((
+                _,
+                Node::Expr(Expr {
+                    kind: ExprKind::Loop(_, _, LoopSource::While, _),
+                    ..
+                }),
+            ))
+        )
+}
+
+/// An `if` expression whose condition is a `let` chain, e.g. `if let Some(x) = a && x > 1`. A
+/// single `if let` is a chain of one condition.
+pub struct IfLetChain<'hir> {
+    /// the conditions of the chain, in order
+    pub conds: Vec<LetChainCond<'hir>>,
+    /// `if` then expression
+    pub then: &'hir Expr<'hir>,
+    /// `else` expression
+    pub r#else: Option<&'hir Expr<'hir>>,
+}
+
+impl<'hir> IfLetChain<'hir> {
+    /// Parses an `if` expression with a `let` chain
+    pub fn hir(cx: &LateContext<'_>, expr: &Expr<'hir>) -> Option<Self> {
+        if let ExprKind::If(cond, then, r#else) = expr.kind
+            && !is_while_desugar(cx, expr)
+        {
+            Some(Self {
+                conds: let_chain(cond)?,
+                then,
+                r#else,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// A desugared `while` loop whose condition is a `let` chain, e.g.
+/// `while let Some(x) = it.next() && x > 1`. A single `while let` is a chain of one condition.
+pub struct WhileLetChain<'hir> {
+    /// the conditions of the chain, in order
+    pub conds: Vec<LetChainCond<'hir>>,
+    /// `while` loop body
+    pub body: &'hir Expr<'hir>,
+    /// Span of the loop header
+    pub span: Span,
+}
+
+impl<'hir> WhileLetChain<'hir> {
+    /// Parses a desugared `while` loop with a `let` chain
+    pub fn hir(expr: &Expr<'hir>) -> Option<Self> {
+        if let ExprKind::Loop(
+            Block {
+                expr:
+                    Some(Expr {
+                        kind: ExprKind::If(cond, body, _),
+                        ..
+                    }),
+                ..
+            },
+            _,
+            LoopSource::While,
+            span,
+        ) = expr.kind
+        {
+            Some(Self {
+                conds: let_chain(cond)?,
+                body,
+                span,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// An `async` block
+pub struct AsyncBlock<'hir> {
+    /// the body of the block
+    pub body: &'hir Body<'hir>,
+    /// whether the block is `async move`
+    pub capture_clause: CaptureBy,
+    /// the `DefId` of the coroutine, e.g. to look up its captures
+    pub def_id: LocalDefId,
+    /// entire block span
+    pub span: Span,
+}
+
+impl<'hir> AsyncBlock<'hir> {
+    /// Parses an `async` block
+    pub fn hir(cx: &LateContext<'hir>, expr: &Expr<'_>) -> Option<Self> {
+        if let ExprKind::Closure(&Closure {
+            kind: ClosureKind::Coroutine(CoroutineKind::Desugared(CoroutineDesugaring::Async, CoroutineSource::Block)),
+            body,
+            capture_clause,
+            def_id,
+            ..
+        }) = expr.kind
+        {
+            Some(Self {
+                body: cx.tcx.hir().body(body),
+                capture_clause,
+                def_id,
+                span: expr.span,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// A desugared `.await`
+pub struct Await<'hir> {
+    /// the awaited expression
+    pub future: &'hir Expr<'hir>,
+    /// `future.await`
+    pub span: Span,
+}
+
+impl<'hir> Await<'hir> {
+    /// Parses a desugared `.await`
+    pub fn hir(expr: &Expr<'hir>) -> Option<Self> {
+        if let ExprKind::Match(scrutinee, _, MatchSource::AwaitDesugar) = expr.kind
+            && let ExprKind::Call(func, [future]) = scrutinee.kind
+            && let ExprKind::Path(QPath::LangItem(hir::LangItem::IntoFutureIntoFuture, ..)) = func.kind
+        {
+            Some(Self {
+                future,
+                span: expr.span,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the source of the `.await` expression, e.g. `fut.await` or `(&mut fut).await`
+    pub fn sugg(&self, cx: &LateContext<'_>, ctxt: SyntaxContext, applicability: &mut Applicability) -> String {
+        let future = Sugg::hir_with_context(cx, self.future, ctxt, "..", applicability).maybe_par();
+        format!("{future}.await")
+    }
+}
+
+/// A desugared `?` operator
+pub struct QuestionMark<'hir> {
+    /// the expression the operator is applied to
+    pub expr: &'hir Expr<'hir>,
+    /// `expr?`
+    pub span: Span,
+}
+
+impl<'hir> QuestionMark<'hir> {
+    /// Parses a desugared `?` operator
+    pub fn hir(expr: &Expr<'hir>) -> Option<Self> {
+        if let ExprKind::Match(scrutinee, _, MatchSource::TryDesugar(_)) = expr.kind
+            && let ExprKind::Call(func, [inner]) = scrutinee.kind
+            && let ExprKind::Path(QPath::LangItem(hir::LangItem::TryTraitBranch, ..)) = func.kind
+        {
+            Some(Self {
+                expr: inner,
+                span: expr.span,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the source of the `?` expression, e.g. `x?` or `(*x)?`
+    pub fn sugg(&self, cx: &LateContext<'_>, ctxt: SyntaxContext, applicability: &mut Applicability) -> String {
+        let inner = Sugg::hir_with_context(cx, self.expr, ctxt, "..", applicability).maybe_par();
+        format!("{inner}?")
+    }
+}
+
+/// A desugared `try` block
+pub struct TryBlock<'hir> {
+    /// the block, whose trailing expression is wrapped in `Try::from_output`
+    pub block: &'hir Block<'hir>,
+    /// the trailing expression as written, `None` if the block doesn't have one
+    pub tail: Option<&'hir Expr<'hir>>,
+}
+
+impl<'hir> TryBlock<'hir> {
+    /// Parses a desugared `try` block
+    pub fn hir(expr: &Expr<'hir>) -> Option<Self> {
+        if let ExprKind::Block(block, None) = expr.kind
+            && let Some(wrapped) = block.expr
+            && let ExprKind::Call(func, [tail]) = wrapped.kind
+            && let ExprKind::Path(QPath::LangItem(hir::LangItem::TryTraitFromOutput, ..)) = func.kind
+            && wrapped.span.is_desugaring(DesugaringKind::TryBlock)
+        {
+            let implicit_unit =
+                matches!(tail.kind, ExprKind::Tup([])) && tail.span.is_desugaring(DesugaringKind::TryBlock);
+            Some(Self {
+                block,
+                tail: (!implicit_unit).then_some(tail),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// An argument of a `format_args!` invocation, either written inline in the format string, as
+/// `x` in `format!("{x}")`, or passed explicitly
+pub struct FormatArg<'hir, 'ast> {
+    /// the argument in the AST
+    pub arg: &'ast FormatArgument,
+    /// the value of the argument in the HIR
+    pub expr: &'hir Expr<'hir>,
+}
+
+impl<'hir, 'ast> FormatArg<'hir, 'ast> {
+    /// Returns the arguments of the `format_args!` invocation whose expansion is found in `start`,
+    /// skipping the ones whose value can't be found
+    pub fn all(start: &'hir Expr<'hir>, format_args: &'ast FormatArgs) -> impl Iterator<Item = Self> + 'ast
+    where
+        'hir: 'ast,
+    {
+        format_args.arguments.all_args().iter().filter_map(move |arg| {
+            Some(Self {
+                arg,
+                expr: find_format_arg_expr(start, arg).ok()?,
+            })
+        })
+    }
+
+    /// Checks if the argument is written inline in the format string, e.g. `x` in `format!("{x}")`
+    pub fn is_inline(&self) -> bool {
+        matches!(self.arg.kind, FormatArgumentKind::Captured(_))
+    }
+
+    /// Returns the source of the argument's value. For an inline argument this is the name of the
+    /// captured variable.
+    pub fn sugg(&self, cx: &LateContext<'_>, ctxt: SyntaxContext, applicability: &mut Applicability) -> String {
+        match self.arg.kind {
+            FormatArgumentKind::Captured(ident) => ident.to_string(),
+            FormatArgumentKind::Normal | FormatArgumentKind::Named(_) => {
+                Sugg::hir_with_context(cx, self.expr, ctxt, "..", applicability).to_string()
+            },
+        }
+    }
+}
+
 /// Converts a `hir` binary operator to the corresponding `ast` type.
 #[must_use]
 pub fn binop(op: hir::BinOpKind) -> ast::BinOpKind {
@@ -27,11 +27,15 @@ type SpanlessEqCallback<'a> = dyn FnMut(&Expr<'_>, &Expr<'_>) -> bool + 'a;
 /// ID and span.
 ///
 /// Note that some expressions kinds are not considered but could be added.
+#[expect(clippy::struct_excessive_bools)]
 pub struct SpanlessEq<'a, 'tcx> {
     /// Context used to evaluate constant expressions.
     cx: &'a LateContext<'tcx>,
     maybe_typeck_results: Option<(&'tcx TypeckResults<'tcx>, &'tcx TypeckResults<'tcx>)>,
     allow_side_effects: bool,
+    ignore_literals: bool,
+    unify_locals: bool,
+    ignore_path_prefixes: bool,
     expr_fallback: Option<Box<SpanlessEqCallback<'a>>>,
 }
 
@@ -41,6 +45,9 @@ impl<'a, 'tcx> SpanlessEq<'a, 'tcx> {
             cx,
             maybe_typeck_results: cx.maybe_typeck_results().map(|x| (x, x)),
             allow_side_effects: true,
+            ignore_literals: false,
+            unify_locals: false,
+            ignore_path_prefixes: false,
             expr_fallback: None,
         }
     }
@@ -54,6 +61,38 @@ impl<'a, 'tcx> SpanlessEq<'a, 'tcx> {
         }
     }
 
+    /// Consider the literals of the same kind as equal whatever their values, and so the
+    /// expressions evaluated to constants of the same kind. E.g. `x + 1` and `x + 2` are equal,
+    /// but not `x + 1` and `x + 1.5`.
+    #[must_use]
+    pub fn ignore_literals(self) -> Self {
+        Self {
+            ignore_literals: true,
+            ..self
+        }
+    }
+
+    /// Consider the locals declared outside of the compared expressions as equal if they are
+    /// renamed consistently. E.g. `a + b * a` and `x + y * x` are equal, but not `a + b * a` and
+    /// `x + x * y`. The locals declared inside of the expressions are always unified.
+    #[must_use]
+    pub fn unify_locals(self) -> Self {
+        Self {
+            unify_locals: true,
+            ..self
+        }
+    }
+
+    /// Compare only the last segments of the paths resolving to the same item, e.g.
+    /// `std::mem::take` and `take` are equal, but not `a::Error` and `b::Error`.
+    #[must_use]
+    pub fn ignore_path_prefixes(self) -> Self {
+        Self {
+            ignore_path_prefixes: true,
+            ..self
+        }
+    }
+
     #[must_use]
     pub fn expr_fallback(self, expr_fallback: impl FnMut(&Expr<'_>, &Expr<'_>) -> bool + 'a) -> Self {
         Self {
@@ -258,7 +297,7 @@ impl HirEqInterExpr<'_, '_, '_> {
                 constant_simple(self.inner.cx, typeck_lhs, left),
                 constant_simple(self.inner.cx, typeck_rhs, right),
             )
-            && l == r
+            && (l == r || self.inner.ignore_literals && std::mem::discriminant(&l) == std::mem::discriminant(&r))
         {
             return true;
         }
@@ -310,7 +349,11 @@ impl HirEqInterExpr<'_, '_, '_> {
             (&ExprKind::Let(l), &ExprKind::Let(r)) => {
                 self.eq_pat(l.pat, r.pat) && both(&l.ty, &r.ty, |l, r| self.eq_ty(l, r)) && self.eq_expr(l.init, r.init)
             },
-            (ExprKind::Lit(l), ExprKind::Lit(r)) => l.node == r.node,
+            (ExprKind::Lit(l), ExprKind::Lit(r)) => {
+                l.node == r.node
+                    || self.inner.ignore_literals
+                        && std::mem::discriminant(&l.node) == std::mem::discriminant(&r.node)
+            },
             (&ExprKind::Loop(lb, ref ll, ref lls, _), &ExprKind::Loop(rb, ref rl, ref rls, _)) => {
                 lls == rls && self.eq_block(lb, rb) && both(ll, rl, |l, r| l.ident.name == r.ident.name)
             },
@@ -477,8 +520,26 @@ impl HirEqInterExpr<'_, '_, '_> {
 
     pub fn eq_path(&mut self, left: &Path<'_>, right: &Path<'_>) -> bool {
         match (left.res, right.res) {
-            (Res::Local(l), Res::Local(r)) => l == r || self.locals.get(&l) == Some(&r),
+            (Res::Local(l), Res::Local(r)) => match self.locals.get(&l) {
+                Some(&mapped) => l == r || mapped == r,
+                // The mapping has to stay one-to-one, e.g. `x` can't be unified with `y` in `(x, y)`
+                // and `(y, y)`.
+                None if self.inner.unify_locals => {
+                    let unified = !self.locals.values().any(|&mapped| mapped == r);
+                    if unified {
+                        self.locals.insert(l, r);
+                    }
+                    unified
+                },
+                None => l == r,
+            },
             (Res::Local(_), _) | (_, Res::Local(_)) => false,
+            (l, r) if self.inner.ignore_path_prefixes && l == r => {
+                match (left.segments.last(), right.segments.last()) {
+                    (Some(l), Some(r)) => self.eq_path_segment(l, r),
+                    (l, r) => l.is_none() && r.is_none(),
+                }
+            },
             _ => over(left.segments, right.segments, |l, r| self.eq_path_segment(l, r)),
         }
     }
@@ -669,6 +730,8 @@ pub struct SpanlessHash<'a, 'tcx> {
     /// Context used to evaluate constant expressions.
     cx: &'a LateContext<'tcx>,
     maybe_typeck_results: Option<&'tcx TypeckResults<'tcx>>,
+    ignore_literals: bool,
+    ignore_path_prefixes: bool,
     s: FxHasher,
 }
 
@@ -677,10 +740,33 @@ impl<'a, 'tcx> SpanlessHash<'a, 'tcx> {
         Self {
             cx,
             maybe_typeck_results: cx.maybe_typeck_results(),
+            ignore_literals: false,
+            ignore_path_prefixes: false,
             s: FxHasher::default(),
         }
     }
 
+    /// Hashes the literals and the constants by their kind only, to match
+    /// [`SpanlessEq::ignore_literals`].
+    #[must_use]
+    pub fn ignore_literals(self) -> Self {
+        Self {
+            ignore_literals: true,
+            ..self
+        }
+    }
+
+    /// Hashes the paths by what they resolve to and their last segments, to match
+    /// [`SpanlessEq::ignore_path_prefixes`]. The locals are always hashed the same, which also
+    /// matches [`SpanlessEq::unify_locals`].
+    #[must_use]
+    pub fn ignore_path_prefixes(self) -> Self {
+        Self {
+            ignore_path_prefixes: true,
+            ..self
+        }
+    }
+
     pub fn finish(self) -> u64 {
         self.s.finish()
     }
@@ -705,7 +791,11 @@ impl<'a, 'tcx> SpanlessHash<'a, 'tcx> {
 
         // const hashing may result in the same hash as some unrelated node, so add a sort of
         // discriminant depending on which path we're choosing next
-        simple_const.hash(&mut self.s);
+        if self.ignore_literals {
+            simple_const.as_ref().map(std::mem::discriminant).hash(&mut self.s);
+        } else {
+            simple_const.hash(&mut self.s);
+        }
         if simple_const.is_some() {
             return;
         }
@@ -845,7 +935,11 @@ impl<'a, 'tcx> SpanlessHash<'a, 'tcx> {
                 self.hash_pat(pat);
             },
             ExprKind::Lit(l) => {
-                l.node.hash(&mut self.s);
+                if self.ignore_literals {
+                    std::mem::discriminant(&l.node).hash(&mut self.s);
+                } else {
+                    l.node.hash(&mut self.s);
+                }
             },
             ExprKind::Loop(b, ref i, ..) => {
                 self.hash_block(b);
@@ -1017,6 +1111,13 @@ impl<'a, 'tcx> SpanlessHash<'a, 'tcx> {
             // e.g. The expressions `if let Some(x) = foo() {}` and `if let Some(y) = foo() {}` are considered equal
             // even though the binding names are different and they have different `HirId`s.
             Res::Local(_) => 1_usize.hash(&mut self.s),
+            res if self.ignore_path_prefixes => {
+                res.hash(&mut self.s);
+                if let Some(seg) = path.segments.last() {
+                    self.hash_name(seg.ident.name);
+                    self.hash_generic_args(seg.args().args);
+                }
+            },
             _ => {
                 for seg in path.segments {
                     self.hash_name(seg.ident.name);
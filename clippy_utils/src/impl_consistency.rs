@@ -0,0 +1,303 @@
+//! Utilities to compare related trait impls on the same type.
+//!
+//! Some traits have to agree with each other, e.g. two values which are equal according to
+//! `PartialEq` must have the same hash, and `Ord` must order values the same way `PartialOrd`
+//! does. A common way to break this is for the impls to look at different fields of the type.
+//! [`paired_impls`] finds the impls of the related trait, and [`fields_used`] computes which
+//! fields the method of an impl reads, so that lints can compare them.
+//!
+//! Only structs are handled. Computing the fields used requires walking the body of the method,
+//! so the results are cached for the crate being compiled.
+
+use crate::ecosystem::{self, SERDE_DESERIALIZE, SERDE_SERIALIZE};
+use crate::get_parent_expr_for_hir;
+use rustc_hir::def::Res;
+use rustc_hir::def_id::{DefIdMap, LOCAL_CRATE};
+use rustc_hir::intravisit::{walk_expr, walk_pat, Visitor};
+use rustc_hir::{Expr, ExprKind, LetExpr, Node, Pat, PatKind, QPath, UnOp};
+use rustc_index::bit_set::BitSet;
+use rustc_lint::LateContext;
+use rustc_middle::hir::nested_filter;
+use rustc_middle::ty::{self, AdtDef, Ty, TypeckResults};
+use rustc_span::def_id::{DefId, StableCrateId};
+use rustc_span::symbol::{sym, Symbol};
+use rustc_target::abi::FieldIdx;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Two traits whose impls on the same type have to be consistent with each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraitPair {
+    /// Equal values must have the same hash.
+    PartialEqHash,
+    /// `Ord` must agree with `PartialOrd`.
+    PartialOrdOrd,
+    /// The hash of a value must be the same as the hash of what it borrows as.
+    BorrowHash,
+    /// Deserializing must read back the fields which were serialized.
+    SerializeDeserialize,
+}
+
+impl TraitPair {
+    /// Gets the two traits, each with the method whose body is compared.
+    pub fn traits(self, cx: &LateContext<'_>) -> Option<[(DefId, Symbol); 2]> {
+        let tcx = cx.tcx;
+        Some(match self {
+            Self::PartialEqHash => [
+                (tcx.lang_items().eq_trait()?, sym::eq),
+                (tcx.get_diagnostic_item(sym::Hash)?, sym::hash),
+            ],
+            Self::PartialOrdOrd => [
+                (tcx.lang_items().partial_ord_trait()?, sym::partial_cmp),
+                (tcx.get_diagnostic_item(sym::Ord)?, sym::cmp),
+            ],
+            Self::BorrowHash => [
+                (tcx.get_diagnostic_item(sym::Borrow)?, Symbol::intern("borrow")),
+                (tcx.get_diagnostic_item(sym::Hash)?, sym::hash),
+            ],
+            Self::SerializeDeserialize => [
+                (
+                    *ecosystem::def_ids(cx, SERDE_SERIALIZE).first()?,
+                    Symbol::intern("serialize"),
+                ),
+                (
+                    *ecosystem::def_ids(cx, SERDE_DESERIALIZE).first()?,
+                    Symbol::intern("deserialize"),
+                ),
+            ],
+        })
+    }
+
+    /// Checks if the trait's generic argument has to be the implementing type for the impl to be
+    /// paired, i.e. only `impl PartialEq<Foo> for Foo` is related to `impl Hash for Foo`.
+    fn requires_self_rhs(self) -> bool {
+        matches!(self, Self::PartialEqHash | Self::PartialOrdOrd)
+    }
+}
+
+/// The fields of a struct read by the method of an impl.
+#[derive(Clone, Debug)]
+pub struct FieldUsage {
+    /// The fields which are read.
+    pub fields: BitSet<FieldIdx>,
+    /// Whether `fields` is all the method uses. This is `false` when a value of the type is used
+    /// as a whole, e.g. passed to a function, as any of its fields may be read there.
+    pub complete: bool,
+}
+
+impl FieldUsage {
+    fn all(adt: AdtDef<'_>) -> Self {
+        let mut fields = BitSet::new_empty(adt.non_enum_variant().fields.len());
+        fields.insert_all();
+        Self { fields, complete: true }
+    }
+
+    /// Checks if the given field is read.
+    pub fn uses(&self, field: FieldIdx) -> bool {
+        self.fields.contains(field)
+    }
+}
+
+/// Gets the struct implementing the trait of an impl.
+fn impl_struct<'tcx>(cx: &LateContext<'tcx>, impl_id: DefId) -> Option<AdtDef<'tcx>> {
+    match cx.tcx.type_of(impl_id).instantiate_identity().kind() {
+        ty::Adt(adt, _) if adt.is_struct() => Some(*adt),
+        _ => None,
+    }
+}
+
+/// Finds the impls of the other trait of the pair for the type implementing the trait of
+/// `impl_id`, which can be either trait of the pair.
+pub fn paired_impls(cx: &LateContext<'_>, impl_id: DefId, pair: TraitPair) -> Vec<DefId> {
+    let Some(adt) = impl_struct(cx, impl_id) else {
+        return Vec::new();
+    };
+    let Some([(first, _), (second, _)]) = pair.traits(cx) else {
+        return Vec::new();
+    };
+    let Some(trait_ref) = cx.tcx.impl_trait_ref(impl_id) else {
+        return Vec::new();
+    };
+    let other = if trait_ref.skip_binder().def_id == first {
+        second
+    } else {
+        first
+    };
+    let is_self = |ty: Ty<'_>| matches!(ty.kind(), ty::Adt(other_adt, _) if *other_adt == adt);
+    let rhs_is_self = |other_id| {
+        !pair.requires_self_rhs()
+            || cx.tcx.impl_trait_ref(other_id).map_or(false, |trait_ref| {
+                trait_ref
+                    .instantiate_identity()
+                    .args
+                    .types()
+                    .nth(1)
+                    .map_or(true, is_self)
+            })
+    };
+
+    let mut impls = Vec::new();
+    cx.tcx
+        .for_each_relevant_impl(other, cx.tcx.type_of(impl_id).instantiate_identity(), |other_id| {
+            if is_self(cx.tcx.type_of(other_id).instantiate_identity()) && rhs_is_self(other_id) {
+                impls.push(other_id);
+            }
+        });
+    impls
+}
+
+#[derive(Default)]
+struct Cache {
+    krate: Option<StableCrateId>,
+    usages: DefIdMap<Option<Rc<FieldUsage>>>,
+}
+
+thread_local! {
+    static CACHE: RefCell<Cache> = RefCell::default();
+}
+
+/// Computes the fields of the implementing struct which the given method of an impl reads.
+/// Derived impls are assumed to read every field.
+///
+/// Returns `None` if the type isn't a struct, or if the method isn't defined in the current crate.
+/// A method which isn't overridden by the impl isn't looked at either.
+pub fn fields_used(cx: &LateContext<'_>, impl_id: DefId, method: Symbol) -> Option<Rc<FieldUsage>> {
+    let adt = impl_struct(cx, impl_id)?;
+    if cx.tcx.is_automatically_derived(impl_id) {
+        return Some(Rc::new(FieldUsage::all(adt)));
+    }
+    let fn_id = cx
+        .tcx
+        .associated_items(impl_id)
+        .filter_by_name_unhygienic(method)
+        .next()?
+        .def_id;
+
+    let krate = cx.tcx.stable_crate_id(LOCAL_CRATE);
+    let hit = CACHE.with_borrow_mut(|cache| {
+        if cache.krate != Some(krate) {
+            cache.krate = Some(krate);
+            cache.usages = DefIdMap::default();
+        }
+        cache.usages.get(&fn_id).cloned()
+    });
+    hit.unwrap_or_else(|| {
+        let usage = compute_fields_used(cx, adt, fn_id).map(Rc::new);
+        CACHE.with_borrow_mut(|cache| cache.usages.insert(fn_id, usage.clone()));
+        usage
+    })
+}
+
+fn compute_fields_used<'tcx>(cx: &LateContext<'tcx>, adt: AdtDef<'tcx>, fn_id: DefId) -> Option<FieldUsage> {
+    let local_id = fn_id.as_local()?;
+    let body = cx.tcx.hir().maybe_body_owned_by(local_id)?;
+    let mut v = FieldVisitor {
+        cx,
+        typeck: cx.tcx.typeck(local_id),
+        adt,
+        usage: FieldUsage {
+            fields: BitSet::new_empty(adt.non_enum_variant().fields.len()),
+            complete: true,
+        },
+    };
+    v.visit_body(cx.tcx.hir().body(body));
+    Some(v.usage)
+}
+
+struct FieldVisitor<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    adt: AdtDef<'tcx>,
+    usage: FieldUsage,
+}
+
+impl<'tcx> FieldVisitor<'_, 'tcx> {
+    fn is_self(&self, ty: Ty<'tcx>) -> bool {
+        matches!(ty.peel_refs().kind(), ty::Adt(adt, _) if *adt == self.adt)
+    }
+
+    /// Checks if the value of a local of the struct's type is only used to read its fields, either
+    /// by projecting them, or by matching against a pattern.
+    fn is_projected(&self, e: &Expr<'_>) -> bool {
+        let mut child = e.hir_id;
+        while let Some(parent) = get_parent_expr_for_hir(self.cx, child) {
+            match parent.kind {
+                ExprKind::Unary(UnOp::Deref, _) | ExprKind::AddrOf(..) | ExprKind::Tup(_) => child = parent.hir_id,
+                ExprKind::Field(base, _) => return base.hir_id == child,
+                ExprKind::Match(scrutinee, ..) | ExprKind::Let(&LetExpr { init: scrutinee, .. }) => {
+                    return scrutinee.hir_id == child;
+                },
+                _ => return false,
+            }
+        }
+        matches!(self.cx.tcx.parent_hir_node(child), Node::LetStmt(_))
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for FieldVisitor<'_, 'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.cx.tcx.hir()
+    }
+
+    fn visit_expr(&mut self, e: &'tcx Expr<'tcx>) {
+        match e.kind {
+            ExprKind::Field(base, _) if self.is_self(self.typeck.expr_ty_adjusted(base)) => {
+                if let Some(idx) = self.typeck.opt_field_index(e.hir_id) {
+                    self.usage.fields.insert(idx);
+                }
+            },
+            ExprKind::Struct(_, fields, base) if self.is_self(self.typeck.expr_ty(e)) => {
+                for field in fields {
+                    if let Some(idx) = self.typeck.opt_field_index(field.hir_id) {
+                        self.usage.fields.insert(idx);
+                    }
+                }
+                if base.is_some() {
+                    self.usage.fields.insert_all();
+                }
+            },
+            ExprKind::Path(QPath::Resolved(None, path))
+                if matches!(path.res, Res::Local(_))
+                    && self.is_self(self.typeck.expr_ty(e))
+                    && !self.is_projected(e) =>
+            {
+                self.usage.complete = false;
+            },
+            _ => {},
+        }
+        walk_expr(self, e);
+    }
+
+    fn visit_pat(&mut self, p: &'tcx Pat<'tcx>) {
+        if self.is_self(self.typeck.pat_ty(p)) {
+            match p.kind {
+                PatKind::Struct(_, fields, _) => {
+                    for field in fields {
+                        if let Some(idx) = self.typeck.opt_field_index(field.hir_id)
+                            && !matches!(field.pat.kind, PatKind::Wild)
+                        {
+                            self.usage.fields.insert(idx);
+                        }
+                    }
+                },
+                PatKind::TupleStruct(_, pats, dotdot) => {
+                    let total = self.adt.non_enum_variant().fields.len();
+                    let skipped = total.saturating_sub(pats.len());
+                    for (i, pat) in pats.iter().enumerate() {
+                        let idx = match dotdot.as_opt_usize() {
+                            Some(pos) if i >= pos => i + skipped,
+                            _ => i,
+                        };
+                        if !matches!(pat.kind, PatKind::Wild) {
+                            self.usage.fields.insert(FieldIdx::from_usize(idx));
+                        }
+                    }
+                },
+                _ => {},
+            }
+        }
+        walk_pat(self, p);
+    }
+}
@@ -0,0 +1,480 @@
+//! A conservative interval analysis of the integer values of a function.
+//!
+//! The interval of an expression is computed from its type, from constants, from the operators
+//! which bound their result, such as `%`, `&` and `min`, and, for the locals which can't be
+//! mutated, from their initializer and from the conditions the use is guarded by: comparisons in
+//! `if` and `while` conditions, early returns, `assert!`s, `match` arm patterns and the range of a
+//! `for` loop.
+//!
+//! The intervals of the locals are cached per body.
+
+use crate::consts::{constant, Constant};
+use crate::{higher, is_trait_method, path_to_local, path_to_local_id, sext};
+use rustc_ast::ast::RangeLimits;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_hir::{BinOpKind, BindingMode, Block, Expr, ExprKind, HirId, Node, Pat, PatKind, RangeEnd, StmtKind, UnOp};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::def_id::StableCrateId;
+use rustc_span::sym;
+use std::cell::RefCell;
+
+/// How deep the operands, initializers and conditions are followed.
+const MAX_DEPTH: u32 = 8;
+
+/// An inclusive interval of integers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub lo: i128,
+    pub hi: i128,
+}
+
+impl Interval {
+    pub fn new(lo: i128, hi: i128) -> Self {
+        debug_assert!(lo <= hi);
+        Self { lo, hi }
+    }
+
+    /// The interval of the values of an integer type. Returns `None` for other types, and for
+    /// `u128`, whose values don't all fit in an `i128`.
+    pub fn of_ty(tcx: TyCtxt<'_>, ty: Ty<'_>) -> Option<Self> {
+        let pointer_bits = tcx.data_layout.pointer_size.bits();
+        match *ty.kind() {
+            ty::Int(ity) => {
+                let shift = 128 - ity.bit_width().unwrap_or(pointer_bits);
+                Some(Self::new(i128::MIN >> shift, i128::MAX >> shift))
+            },
+            ty::Uint(uty) => match uty.bit_width().unwrap_or(pointer_bits) {
+                128 => None,
+                bits => Some(Self::new(0, (1 << bits) - 1)),
+            },
+            _ => None,
+        }
+    }
+
+    pub fn contains(self, value: i128) -> bool {
+        self.lo <= value && value <= self.hi
+    }
+
+    /// Checks if every value of the interval is in `other`.
+    pub fn is_subset_of(self, other: Self) -> bool {
+        other.lo <= self.lo && self.hi <= other.hi
+    }
+
+    /// Checks if every value of the interval is non-negative and less than `len`, i.e. whether
+    /// the values are valid indices of a slice of length `len`.
+    pub fn is_index_of(self, len: u128) -> bool {
+        self.lo >= 0 && i128::try_from(len).map_or(true, |len| self.hi < len)
+    }
+
+    /// Returns the values in both intervals. An empty intersection, which means the code can't be
+    /// reached, is approximated by `self`.
+    #[must_use]
+    pub fn intersect(self, other: Self) -> Self {
+        let (lo, hi) = (self.lo.max(other.lo), self.hi.min(other.hi));
+        if lo <= hi { Self::new(lo, hi) } else { self }
+    }
+
+    /// Returns the smallest interval containing both intervals.
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        Self::new(self.lo.min(other.lo), self.hi.max(other.hi))
+    }
+
+    /// Returns the interval of the results of `op` applied to the bounds of both intervals, which
+    /// is the interval of the results for any monotonic operation. Returns `None` on overflow.
+    fn corners(self, other: Self, op: impl Fn(i128, i128) -> Option<i128>) -> Option<Self> {
+        let values = [
+            op(self.lo, other.lo)?,
+            op(self.lo, other.hi)?,
+            op(self.hi, other.lo)?,
+            op(self.hi, other.hi)?,
+        ];
+        Some(Self::new(*values.iter().min()?, *values.iter().max()?))
+    }
+}
+
+/// Returns the interval of the values the integer expression can evaluate to. Returns `None` if
+/// the expression isn't an integer, or is a `u128`.
+pub fn expr_interval<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> Option<Interval> {
+    Intervals { cx }.expr(e, 0)
+}
+
+#[derive(Default)]
+struct Cache {
+    krate: Option<StableCrateId>,
+    /// The intervals of the locals before the conditions of their uses are taken into account.
+    locals: FxHashMap<HirId, Option<Interval>>,
+}
+
+thread_local! {
+    static CACHE: RefCell<Cache> = RefCell::default();
+}
+
+struct Intervals<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+}
+
+impl<'tcx> Intervals<'_, 'tcx> {
+    fn expr(&self, e: &'tcx Expr<'tcx>, depth: u32) -> Option<Interval> {
+        let ty_interval = Interval::of_ty(self.cx.tcx, self.cx.typeck_results().expr_ty(e))?;
+        let interval = if depth >= MAX_DEPTH {
+            None
+        } else {
+            self.expr_uncapped(e, depth + 1)
+        };
+        Some(interval.map_or(ty_interval, |interval| {
+            // A result which doesn't fit in the type overflowed.
+            if interval.is_subset_of(ty_interval) {
+                interval
+            } else {
+                ty_interval
+            }
+        }))
+    }
+
+    fn expr_uncapped(&self, e: &'tcx Expr<'tcx>, depth: u32) -> Option<Interval> {
+        if let Some(Constant::Int(value)) = constant(self.cx, self.cx.typeck_results(), e) {
+            let value = match *self.cx.typeck_results().expr_ty(e).kind() {
+                ty::Int(ity) => sext(self.cx.tcx, value, ity),
+                _ => i128::try_from(value).ok()?,
+            };
+            return Some(Interval::new(value, value));
+        }
+
+        match e.kind {
+            ExprKind::Path(_) => {
+                let id = path_to_local(e)?;
+                let base = self.local(id, depth)?;
+                Some(self.refine(id, e, base, depth))
+            },
+            ExprKind::DropTemps(inner) => self.expr(inner, depth),
+            ExprKind::Block(
+                Block {
+                    stmts: [],
+                    expr: Some(tail),
+                    ..
+                },
+                _,
+            ) => self.expr(tail, depth),
+            ExprKind::If(_, then, Some(els)) => Some(self.expr(then, depth)?.union(self.expr(els, depth)?)),
+            ExprKind::Match(_, arms, _) => arms
+                .iter()
+                .map(|arm| self.expr(arm.body, depth))
+                .reduce(|a, b| Some(a?.union(b?)))?,
+            ExprKind::Unary(UnOp::Neg, inner) => {
+                let inner = self.expr(inner, depth)?;
+                Some(Interval::new(inner.hi.checked_neg()?, inner.lo.checked_neg()?))
+            },
+            ExprKind::Cast(inner, _) => match *self.cx.typeck_results().expr_ty(inner).kind() {
+                ty::Bool => Some(Interval::new(0, 1)),
+                ty::Char => Some(Interval::new(0, char::MAX as i128)),
+                // A value which doesn't fit in the target type is wrapped, which `expr` handles.
+                _ => self.expr(inner, depth),
+            },
+            ExprKind::Binary(op, lhs, rhs) => self.binary(op.node, lhs, rhs, depth),
+            // The conversions between integer types are lossless.
+            ExprKind::Call(func, [arg])
+                if let ExprKind::Path(ref qpath) = func.kind
+                    && let Some(id) = self.cx.qpath_res(qpath, func.hir_id).opt_def_id()
+                    && self.cx.tcx.is_diagnostic_item(sym::from_fn, id) =>
+            {
+                self.expr(arg, depth)
+            },
+            ExprKind::MethodCall(path, recv, args, _) => {
+                let recv = self.expr(recv, depth)?;
+                match (path.ident.as_str(), args) {
+                    ("into", []) if is_trait_method(self.cx, e, sym::Into) => Some(recv),
+                    ("min", [arg]) => {
+                        let arg = self.expr(arg, depth)?;
+                        Some(Interval::new(recv.lo.min(arg.lo), recv.hi.min(arg.hi)))
+                    },
+                    ("max", [arg]) => {
+                        let arg = self.expr(arg, depth)?;
+                        Some(Interval::new(recv.lo.max(arg.lo), recv.hi.max(arg.hi)))
+                    },
+                    ("clamp", [lo, hi]) => {
+                        let (lo, hi) = (self.expr(lo, depth)?, self.expr(hi, depth)?);
+                        (lo.lo <= hi.hi).then(|| Interval::new(lo.lo, hi.hi))
+                    },
+                    ("rem_euclid", [arg]) => {
+                        let arg = self.expr(arg, depth)?;
+                        (arg.lo > 0).then(|| Interval::new(0, arg.hi - 1))
+                    },
+                    // At most the width of the widest integer type.
+                    ("count_ones" | "count_zeros" | "leading_zeros" | "trailing_zeros", []) => {
+                        Some(Interval::new(0, 128))
+                    },
+                    _ => None,
+                }
+            },
+            _ => None,
+        }
+    }
+
+    fn binary(&self, op: BinOpKind, lhs: &'tcx Expr<'tcx>, rhs: &'tcx Expr<'tcx>, depth: u32) -> Option<Interval> {
+        let (l, r) = (self.expr(lhs, depth)?, self.expr(rhs, depth)?);
+        match op {
+            BinOpKind::Add => l.corners(r, i128::checked_add),
+            BinOpKind::Sub => l.corners(r, i128::checked_sub),
+            BinOpKind::Mul => l.corners(r, i128::checked_mul),
+            BinOpKind::Div if r.lo > 0 || r.hi < 0 => l.corners(r, i128::checked_div),
+            BinOpKind::Rem if r.lo > 0 || r.hi < 0 => {
+                let max = r.lo.checked_abs()?.max(r.hi.checked_abs()?) - 1;
+                let rem = if l.lo >= 0 {
+                    Interval::new(0, max)
+                } else if l.hi <= 0 {
+                    Interval::new(-max, 0)
+                } else {
+                    Interval::new(-max, max)
+                };
+                Some(rem.intersect(Interval::new(l.lo.min(0), l.hi.max(0))))
+            },
+            BinOpKind::BitAnd => match (l.lo >= 0, r.lo >= 0) {
+                (true, true) => Some(Interval::new(0, l.hi.min(r.hi))),
+                (true, false) => Some(Interval::new(0, l.hi)),
+                (false, true) => Some(Interval::new(0, r.hi)),
+                (false, false) => None,
+            },
+            BinOpKind::BitOr | BinOpKind::BitXor if l.lo >= 0 && r.lo >= 0 => {
+                // Both values fit in the bits of the largest one.
+                let max = l.hi.max(r.hi);
+                let all_ones = i128::MAX.checked_shr(max.leading_zeros() - 1)?;
+                let lo = if op == BinOpKind::BitOr { l.lo.max(r.lo) } else { 0 };
+                Some(Interval::new(lo, all_ones))
+            },
+            BinOpKind::Shr if l.lo >= 0 && r.lo >= 0 && r.hi < 128 => Some(Interval::new(l.lo >> r.hi, l.hi >> r.lo)),
+            _ => None,
+        }
+    }
+
+    /// The interval of a local, before the conditions of its use are taken into account.
+    fn local(&self, id: HirId, depth: u32) -> Option<Interval> {
+        let krate = self.cx.tcx.stable_crate_id(LOCAL_CRATE);
+        let hit = CACHE.with_borrow_mut(|cache| {
+            if cache.krate != Some(krate) {
+                cache.krate = Some(krate);
+                cache.locals = FxHashMap::default();
+            }
+            cache.locals.get(&id).copied()
+        });
+        hit.unwrap_or_else(|| {
+            // The initializer can use other locals, so the cache isn't borrowed while it's computed.
+            let interval = self.local_uncached(id, depth);
+            // Intervals cut short by the depth limit aren't cached.
+            if depth == 1 {
+                CACHE.with_borrow_mut(|cache| cache.locals.insert(id, interval));
+            }
+            interval
+        })
+    }
+
+    fn local_uncached(&self, id: HirId, depth: u32) -> Option<Interval> {
+        let ty_interval = Interval::of_ty(self.cx.tcx, self.cx.typeck_results().node_type(id))?;
+        let Node::Pat(pat) = self.cx.tcx.hir_node(id) else {
+            return Some(ty_interval);
+        };
+        let PatKind::Binding(BindingMode::NONE, _, _, sub) = pat.kind else {
+            // A mutable local can change after its initialization, or after a condition.
+            return None;
+        };
+        if let Some(sub) = sub {
+            return Some(self.pat(sub).map_or(ty_interval, |i| i.intersect(ty_interval)));
+        }
+        let interval = match self.cx.tcx.parent_hir_node(id) {
+            Node::LetStmt(local) => local.init.and_then(|init| self.expr(init, depth)),
+            // The item of a `for` loop over a range, whose pattern is 10 nodes below the desugared
+            // loop.
+            _ => self
+                .cx
+                .tcx
+                .hir()
+                .parent_iter(id)
+                .take(10)
+                .find_map(|(_, node)| match node {
+                    Node::Expr(e) => higher::ForLoop::hir(e),
+                    _ => None,
+                })
+                .filter(|for_loop| for_loop.pat.hir_id == id)
+                .and_then(|for_loop| {
+                    let range = higher::Range::hir(for_loop.arg)?;
+                    let start = self.expr(range.start?, depth)?;
+                    let end = self.expr(range.end?, depth)?;
+                    let hi = match range.limits {
+                        RangeLimits::HalfOpen => end.hi - 1,
+                        RangeLimits::Closed => end.hi,
+                    };
+                    (start.lo <= hi).then(|| Interval::new(start.lo, hi))
+                }),
+        };
+        Some(interval.unwrap_or(ty_interval))
+    }
+
+    /// The interval of the values matched by a pattern, if it restricts them.
+    fn pat(&self, pat: &'tcx Pat<'tcx>) -> Option<Interval> {
+        let typeck = self.cx.typeck_results();
+        let value = |e: &'tcx Expr<'tcx>| match constant(self.cx, typeck, e)? {
+            Constant::Int(value) => match *typeck.expr_ty(e).kind() {
+                ty::Int(ity) => Some(sext(self.cx.tcx, value, ity)),
+                _ => i128::try_from(value).ok(),
+            },
+            _ => None,
+        };
+        match pat.kind {
+            PatKind::Lit(e) => value(e).map(|v| Interval::new(v, v)),
+            PatKind::Range(lo, hi, end) => {
+                let ty_interval = Interval::of_ty(self.cx.tcx, typeck.pat_ty(pat))?;
+                let lo = lo.map_or(Some(ty_interval.lo), value)?;
+                let hi = match (hi, end) {
+                    (Some(hi), RangeEnd::Included) => value(hi)?,
+                    (Some(hi), RangeEnd::Excluded) => value(hi)? - 1,
+                    (None, _) => ty_interval.hi,
+                };
+                (lo <= hi).then(|| Interval::new(lo, hi))
+            },
+            PatKind::Or(pats) => pats.iter().map(|pat| self.pat(pat)).reduce(|a, b| Some(a?.union(b?)))?,
+            PatKind::Binding(.., Some(sub)) => self.pat(sub),
+            _ => None,
+        }
+    }
+
+    /// Narrows the interval of the local used by `use_expr` with the conditions guarding the use.
+    fn refine(&self, id: HirId, use_expr: &'tcx Expr<'tcx>, mut interval: Interval, depth: u32) -> Interval {
+        let mut child_id = use_expr.hir_id;
+        for (parent_id, parent) in self.cx.tcx.hir().parent_iter(use_expr.hir_id) {
+            match parent {
+                Node::Expr(e) => match e.kind {
+                    ExprKind::If(cond, then, els) => {
+                        if then.hir_id == child_id {
+                            interval = self.assume(id, cond, true, interval, depth);
+                        } else if els.is_some_and(|els| els.hir_id == child_id) {
+                            interval = self.assume(id, cond, false, interval, depth);
+                        }
+                    },
+                    ExprKind::Binary(op, lhs, rhs) if rhs.hir_id == child_id => match op.node {
+                        BinOpKind::And => interval = self.assume(id, lhs, true, interval, depth),
+                        BinOpKind::Or => interval = self.assume(id, lhs, false, interval, depth),
+                        _ => {},
+                    },
+                    _ => {},
+                },
+                Node::Arm(arm) if arm.body.hir_id == child_id => {
+                    if let Some(guard) = arm.guard {
+                        interval = self.assume(id, guard, true, interval, depth);
+                    }
+                    if let Node::Expr(Expr {
+                        kind: ExprKind::Match(scrutinee, ..),
+                        ..
+                    }) = self.cx.tcx.parent_hir_node(parent_id)
+                        && path_to_local_id(scrutinee, id)
+                        && let Some(pat) = self.pat(arm.pat)
+                    {
+                        interval = interval.intersect(pat);
+                    }
+                },
+                Node::Block(block) => {
+                    // The conditions of the `if`s before the use which diverge when they hold,
+                    // as in `if i >= len { return; }` or `assert!(i < len)`.
+                    for stmt in block.stmts.iter().take_while(|stmt| stmt.hir_id != child_id) {
+                        if let StmtKind::Expr(e) | StmtKind::Semi(e) = stmt.kind
+                            && let ExprKind::If(cond, then, None) = e.kind
+                            && self.cx.typeck_results().expr_ty(then).is_never()
+                        {
+                            interval = self.assume(id, cond, false, interval, depth);
+                        }
+                    }
+                },
+                Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_) => break,
+                _ => {},
+            }
+            child_id = parent_id;
+        }
+        interval
+    }
+
+    /// Narrows the interval of the local assuming the condition evaluates to `holds`.
+    fn assume(&self, id: HirId, cond: &'tcx Expr<'tcx>, holds: bool, interval: Interval, depth: u32) -> Interval {
+        match cond.kind {
+            ExprKind::DropTemps(cond) => self.assume(id, cond, holds, interval, depth),
+            ExprKind::Unary(UnOp::Not, cond) => self.assume(id, cond, !holds, interval, depth),
+            ExprKind::Binary(op, lhs, rhs) => match (op.node, holds) {
+                (BinOpKind::And, true) | (BinOpKind::Or, false) => {
+                    let interval = self.assume(id, lhs, holds, interval, depth);
+                    self.assume(id, rhs, holds, interval, depth)
+                },
+                (BinOpKind::Lt | BinOpKind::Le | BinOpKind::Gt | BinOpKind::Ge | BinOpKind::Eq | BinOpKind::Ne, _) => {
+                    let (op, other) = if path_to_local_id(lhs, id) {
+                        (op.node, rhs)
+                    } else if path_to_local_id(rhs, id) {
+                        (flip(op.node), lhs)
+                    } else {
+                        return interval;
+                    };
+                    let op = if holds { op } else { negate(op) };
+                    let Some(other) = self.expr(other, depth) else {
+                        return interval;
+                    };
+                    let bound = match op {
+                        BinOpKind::Lt => other.hi.checked_sub(1).map(|hi| Interval::new(i128::MIN, hi)),
+                        BinOpKind::Le => Some(Interval::new(i128::MIN, other.hi)),
+                        BinOpKind::Gt => other.lo.checked_add(1).map(|lo| Interval::new(lo, i128::MAX)),
+                        BinOpKind::Ge => Some(Interval::new(other.lo, i128::MAX)),
+                        BinOpKind::Eq => Some(other),
+                        _ => None,
+                    };
+                    bound.map_or(interval, |bound| interval.intersect(bound))
+                },
+                _ => interval,
+            },
+            // `(lo..hi).contains(&x)`
+            ExprKind::MethodCall(path, recv, [arg], _)
+                if holds
+                    && path.ident.as_str() == "contains"
+                    && let ExprKind::AddrOf(_, _, arg) = arg.kind
+                    && path_to_local_id(arg, id)
+                    && let Some(range) = higher::Range::hir(recv.peel_drop_temps()) =>
+            {
+                let lo = range
+                    .start
+                    .and_then(|start| self.expr(start, depth))
+                    .map_or(i128::MIN, |i| i.lo);
+                let hi = match (range.end.and_then(|end| self.expr(end, depth)), range.limits) {
+                    (Some(end), RangeLimits::HalfOpen) => end.hi - 1,
+                    (Some(end), RangeLimits::Closed) => end.hi,
+                    (None, _) => i128::MAX,
+                };
+                if lo <= hi {
+                    interval.intersect(Interval::new(lo, hi))
+                } else {
+                    interval
+                }
+            },
+            _ => interval,
+        }
+    }
+}
+
+/// Returns the operator with its operands swapped, e.g. `<` for `>`.
+fn flip(op: BinOpKind) -> BinOpKind {
+    match op {
+        BinOpKind::Lt => BinOpKind::Gt,
+        BinOpKind::Le => BinOpKind::Ge,
+        BinOpKind::Gt => BinOpKind::Lt,
+        BinOpKind::Ge => BinOpKind::Le,
+        op => op,
+    }
+}
+
+/// Returns the comparison which holds when the given one doesn't, e.g. `>=` for `<`.
+fn negate(op: BinOpKind) -> BinOpKind {
+    match op {
+        BinOpKind::Lt => BinOpKind::Ge,
+        BinOpKind::Le => BinOpKind::Gt,
+        BinOpKind::Gt => BinOpKind::Le,
+        BinOpKind::Ge => BinOpKind::Lt,
+        BinOpKind::Eq => BinOpKind::Ne,
+        BinOpKind::Ne => BinOpKind::Eq,
+        op => op,
+    }
+}
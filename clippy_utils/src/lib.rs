@@ -52,27 +52,45 @@ extern crate rustc_trait_selection;
 #[macro_use]
 pub mod sym_helper;
 
+pub mod annotate_allows;
 pub mod ast_utils;
 pub mod attrs;
+pub mod call_graph;
+pub mod cargo;
+pub mod cfg_notes;
 mod check_proc_macro;
 pub mod comparisons;
 pub mod consts;
+pub mod crate_cache;
 pub mod diagnostics;
 pub mod eager_or_lazy;
+pub mod ecosystem;
+pub mod eval;
+pub mod format_string;
 pub mod higher;
 mod hir_utils;
+pub mod impl_consistency;
+pub mod intervals;
+pub mod lint_cache;
+pub mod lint_profile;
+pub mod macro_diagnostics;
 pub mod macros;
 pub mod mir;
 pub mod numeric_literal;
+pub mod panics;
 pub mod paths;
+pub mod plugins;
 pub mod ptr;
 pub mod qualify_min_const_fn;
 pub mod source;
 pub mod str_utils;
 pub mod sugg;
+pub mod summary;
+pub mod thresholds;
 pub mod ty;
 pub mod usage;
 pub mod visitors;
+pub mod warn_budget;
 
 pub use self::attrs::*;
 pub use self::check_proc_macro::{is_from_proc_macro, is_span_if, is_span_match};
@@ -118,7 +136,7 @@ use rustc_middle::ty::{
 use rustc_span::hygiene::{ExpnKind, MacroKind};
 use rustc_span::source_map::SourceMap;
 use rustc_span::symbol::{kw, Ident, Symbol};
-use rustc_span::{sym, Span};
+use rustc_span::{sym, FileName, Span};
 use rustc_target::abi::Integer;
 use visitors::Visitable;
 
@@ -709,6 +727,23 @@ pub fn def_path_def_ids(cx: &LateContext<'_>, path: &[&str]) -> impl Iterator<It
     def_path_res(cx, path).into_iter().filter_map(|res| res.opt_def_id())
 }
 
+/// Resolves a def path like `std::vec::Vec` to its [`DefId`]s, see [`def_path_res`]. Unlike
+/// [`def_path_def_ids`], the path is written as a string and can start with `crate` to refer to
+/// the local crate, as is done in `clippy.toml`.
+pub fn local_def_path_def_ids(cx: &LateContext<'_>, path: &str) -> Vec<DefId> {
+    let mut segs: Vec<_> = path.split("::").collect();
+    if segs[0] == "crate" {
+        if segs.len() == 1 {
+            return vec![LOCAL_CRATE.as_def_id()];
+        }
+        let local_crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+        segs[0] = local_crate_name.as_str();
+        def_path_def_ids(cx, &segs).collect()
+    } else {
+        def_path_def_ids(cx, &segs).collect()
+    }
+}
+
 /// Convenience function to get the `DefId` of a trait by path.
 /// It could be a trait or trait alias.
 ///
@@ -2571,6 +2606,63 @@ pub fn is_in_test(tcx: TyCtxt<'_>, hir_id: HirId) -> bool {
     is_in_test_function(tcx, hir_id) || is_in_cfg_test(tcx, hir_id)
 }
 
+static TEST_ATTRIBUTES: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Sets the paths of the attributes and macros of the test frameworks, from the `test-attributes`
+/// configuration, for [`is_in_test_context`].
+pub fn set_test_attributes(paths: Vec<String>) {
+    let _ = TEST_ATTRIBUTES.set(paths);
+}
+
+/// Checks if the node is test code: in a `#[test]` or `#[bench]` function, in an item marked
+/// `#[cfg(test)]`, in a doctest, or in code marked or expanded by one of the attributes and macros
+/// of the `test-attributes` configuration.
+///
+/// This is the check of the lints which are relaxed in tests.
+///
+/// Note: Add `//@compile-flags: --test` to UI tests with a `#[test]` function
+pub fn is_in_test_context(cx: &LateContext<'_>, hir_id: HirId) -> bool {
+    let tcx = cx.tcx;
+    is_in_test(tcx, hir_id)
+        || matches!(
+            tcx.sess.source_map().span_to_filename(tcx.hir().span(hir_id)),
+            FileName::DocTest(..)
+        )
+        || TEST_ATTRIBUTES.get().map_or(false, |paths| {
+            !paths.is_empty() && is_in_custom_test(tcx, hir_id, paths)
+        })
+}
+
+/// Checks if the node or one of its parents has one of the attributes `paths`, or comes from the
+/// expansion of one of the macros `paths`.
+fn is_in_custom_test(tcx: TyCtxt<'_>, hir_id: HirId, paths: &[String]) -> bool {
+    let is_test_path = |path: &str| {
+        paths.iter().any(|test_path| {
+            test_path == path || !test_path.contains("::") && path.rsplit("::").next() == Some(test_path)
+        })
+    };
+    once(hir_id).chain(tcx.hir().parent_id_iter(hir_id)).any(|id| {
+        tcx.hir().attrs(id).iter().any(|attr| {
+            if let ast::AttrKind::Normal(normal) = &attr.kind {
+                let path = normal
+                    .item
+                    .path
+                    .segments
+                    .iter()
+                    .map(|seg| seg.ident.as_str())
+                    .join("::");
+                is_test_path(&path)
+            } else {
+                false
+            }
+        }) || tcx
+            .hir()
+            .span(id)
+            .macro_backtrace()
+            .any(|expn| matches!(expn.kind, ExpnKind::Macro(_, name) if is_test_path(name.as_str())))
+    })
+}
+
 /// Checks if the item of any of its parents has `#[cfg(...)]` attribute applied.
 pub fn inherits_cfg(tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
     let hir = tcx.hir();
@@ -2860,6 +2952,181 @@ pub fn expr_use_ctxt<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> Optio
     })
 }
 
+/// One step of the way an expression's value is used, see [`expr_use_chain`].
+#[derive(Clone, Copy, Debug)]
+pub enum ExprUse<'tcx> {
+    /// The value is borrowed, either explicitly or by an adjustment. The chain continues with the
+    /// reference.
+    Borrowed(Mutability),
+    /// The value is dereferenced, either explicitly or by an adjustment. The chain continues with
+    /// the place it points to.
+    Deref,
+    /// A field of the value is accessed. The chain continues with the field.
+    Field(Ident),
+    /// The value is indexed. The chain continues with the element.
+    Index,
+    /// The value is passed to a function or method. The receiver of a method call is the argument
+    /// `0`, and its other arguments start at `1`. `callee` is `None` for calls to closures and
+    /// function pointers.
+    Arg {
+        call: &'tcx Expr<'tcx>,
+        callee: Option<DefId>,
+        index: usize,
+    },
+    /// The value is called.
+    Callee(&'tcx Expr<'tcx>),
+    /// The value initializes the pattern of a `let` statement.
+    Bound(&'tcx LetStmt<'tcx>),
+    /// The value is the scrutinee of a `match`, or of an `if let` or `while let`.
+    Matched(&'tcx Expr<'tcx>),
+    /// The value is returned from the function or closure, or initializes a constant or static.
+    Returned(OwnerId),
+    /// The value is assigned to a place.
+    Assigned(&'tcx Expr<'tcx>),
+    /// The value is the place which is assigned to, possibly by a compound assignment.
+    AssignedTo(&'tcx Expr<'tcx>),
+    /// The value initializes a field of a struct.
+    FieldInit(&'tcx ExprField<'tcx>),
+    /// The value is discarded by an expression statement.
+    Discarded,
+    /// The value is used by another expression, e.g. an operator or a cast.
+    Expr(&'tcx Expr<'tcx>),
+    /// The value is used by another node.
+    Other(Node<'tcx>),
+}
+
+impl<'tcx> ExprUse<'tcx> {
+    /// Checks if the use refers to a part of the value, or to the place it points to, i.e. if the
+    /// chain continues with the same place.
+    pub fn is_projection(&self) -> bool {
+        matches!(self, Self::Deref | Self::Field(_) | Self::Index)
+    }
+}
+
+/// An iterator over the uses of an expression's value, from the expression up to the node which
+/// consumes it, see [`expr_use_chain`].
+pub struct ExprUseChain<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    /// The adjustments of the current expression which weren't yielded yet.
+    adjustments: &'tcx [Adjustment<'tcx>],
+    /// The node using the current expression, and the child of that node it's used through.
+    parent: Option<(Node<'tcx>, HirId)>,
+}
+
+impl<'a, 'tcx> ExprUseChain<'a, 'tcx> {
+    fn continue_with(&mut self, e: &'tcx Expr<'tcx>) {
+        if let Some(ControlFlow::Continue((parent, child_id))) =
+            walk_to_expr_usage(self.cx, e, |_, _, _| ControlFlow::<!>::Continue(()))
+        {
+            self.adjustments = match self.cx.tcx.hir_node(child_id) {
+                Node::Expr(child) => self.cx.typeck_results().expr_adjustments(child),
+                _ => &[],
+            };
+            self.parent = Some((parent, child_id));
+        }
+    }
+
+    /// Checks if the place the expression refers to is mutably borrowed or assigned to, possibly
+    /// through dereferences, field accesses and indexing.
+    pub fn mutates_place(self) -> bool {
+        for use_ in self {
+            match use_ {
+                ExprUse::Borrowed(Mutability::Mut) | ExprUse::AssignedTo(_) => return true,
+                use_ if use_.is_projection() => {},
+                _ => return false,
+            }
+        }
+        false
+    }
+}
+
+impl<'tcx> Iterator for ExprUseChain<'_, 'tcx> {
+    type Item = ExprUse<'tcx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let [adjustment, rest @ ..] = self.adjustments {
+            self.adjustments = rest;
+            match adjustment.kind {
+                Adjust::Deref(_) => return Some(ExprUse::Deref),
+                Adjust::Borrow(AutoBorrow::Ref(_, mutbl)) => return Some(ExprUse::Borrowed(mutbl.into())),
+                Adjust::Borrow(AutoBorrow::RawPtr(mutbl)) => return Some(ExprUse::Borrowed(mutbl)),
+                _ => {},
+            }
+        }
+        let (parent, child_id) = self.parent.take()?;
+        let use_ = match parent {
+            Node::Expr(parent) => match parent.kind {
+                ExprKind::AddrOf(_, mutbl, _) => {
+                    self.continue_with(parent);
+                    ExprUse::Borrowed(mutbl)
+                },
+                ExprKind::Unary(UnOp::Deref, _) => {
+                    self.continue_with(parent);
+                    ExprUse::Deref
+                },
+                ExprKind::Field(_, name) => {
+                    self.continue_with(parent);
+                    ExprUse::Field(name)
+                },
+                ExprKind::Index(base, ..) if base.hir_id == child_id => {
+                    self.continue_with(parent);
+                    ExprUse::Index
+                },
+                ExprKind::Call(func, args) => match args.iter().position(|arg| arg.hir_id == child_id) {
+                    Some(index) => ExprUse::Arg {
+                        call: parent,
+                        callee: fn_def_id(self.cx, parent),
+                        index,
+                    },
+                    None => ExprUse::Callee(func),
+                },
+                ExprKind::MethodCall(_, _, args, _) => ExprUse::Arg {
+                    call: parent,
+                    callee: fn_def_id(self.cx, parent),
+                    index: args.iter().position(|arg| arg.hir_id == child_id).map_or(0, |i| i + 1),
+                },
+                ExprKind::Match(scrutinee, ..) if scrutinee.hir_id == child_id => ExprUse::Matched(parent),
+                ExprKind::Let(..) => ExprUse::Matched(parent),
+                ExprKind::Assign(lhs, ..) | ExprKind::AssignOp(_, lhs, _) if lhs.hir_id == child_id => {
+                    ExprUse::AssignedTo(parent)
+                },
+                ExprKind::Assign(..) | ExprKind::AssignOp(..) => ExprUse::Assigned(parent),
+                ExprKind::Ret(_) => ExprUse::Returned(OwnerId {
+                    def_id: self.cx.tcx.hir().enclosing_body_owner(parent.hir_id),
+                }),
+                ExprKind::Closure(closure) => ExprUse::Returned(OwnerId { def_id: closure.def_id }),
+                _ => ExprUse::Expr(parent),
+            },
+            Node::LetStmt(local) => ExprUse::Bound(local),
+            Node::Stmt(_) => ExprUse::Discarded,
+            Node::ExprField(field) => ExprUse::FieldInit(field),
+            Node::Item(&Item { owner_id, .. })
+            | Node::TraitItem(&TraitItem { owner_id, .. })
+            | Node::ImplItem(&ImplItem { owner_id, .. }) => ExprUse::Returned(owner_id),
+            _ => ExprUse::Other(parent),
+        };
+        Some(use_)
+    }
+}
+
+/// Returns an iterator over the uses of the value of an expression, up to the node which consumes
+/// it. Each borrow, dereference, field access and indexing, explicit or not, is one step of the
+/// chain, and the last step is the use which consumes the resulting value. `if`, `match`,
+/// blocks and `break` are walked through as in [`walk_to_expr_usage`].
+///
+/// e.g. the uses of `x` in `f(&x.field)` are `Field(field)`, `Borrowed(Not)` and
+/// `Arg { index: 0, .. }`, and the ones of `v` in `v.push(1)` where `v` is a `Vec` are
+/// `Borrowed(Mut)` and `Arg { index: 0, .. }`.
+pub fn expr_use_chain<'a, 'tcx>(cx: &'a LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> ExprUseChain<'a, 'tcx> {
+    let mut chain = ExprUseChain {
+        cx,
+        adjustments: &[],
+        parent: None,
+    };
+    chain.continue_with(e);
+    chain
+}
+
 /// Tokenizes the input while keeping the text associated with each token.
 pub fn tokenize_with_text(s: &str) -> impl Iterator<Item = (TokenKind, &str)> {
     let mut pos = 0;
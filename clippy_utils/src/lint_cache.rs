@@ -0,0 +1,621 @@
+//! An opt-in cache of the diagnostics of the late lint passes, enabled by
+//! `cargo clippy --lint-cache`.
+//!
+//! The diagnostics emitted while linting a function are stored in `target/` along with a
+//! fingerprint of its source. On the next run, if nothing but the bodies of the functions of the
+//! crate changed, the functions whose fingerprint didn't change aren't linted again: their stored
+//! diagnostics are emitted instead.
+//!
+//! Only the diagnostics emitted through the functions of [`crate::diagnostics`] can be stored. A
+//! function for which a diagnostic can't be stored, e.g. because it points into another function,
+//! is linted on every run.
+
+use crate::{summary, warn_budget};
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_errors::{
+    Applicability, CodeSuggestion, DiagInner, DiagMessage, Level, MultiSpan, Style, Subdiag, Substitution,
+    SubstitutionPart, SuggestionStyle,
+};
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{LocalDefId, LOCAL_CRATE};
+use rustc_hir::{Body, HirId, ItemLocalId, OwnerId};
+use rustc_lint::{LateContext, LateLintPass, Lint, LintStore};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::impl_lint_pass;
+use rustc_span::{BytePos, Span};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::hash::Hash;
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+/// The passes which run on the functions whose diagnostics are replayed too, as they collect
+/// information about the whole crate which they only lint in `check_crate_post`.
+const ALWAYS_RUN: &[&str] = &[
+    "DbgMacro",
+    "LintWithoutLintPass",
+    "MacroUseImports",
+    "ManualNonExhaustiveEnum",
+    "MissingDoc",
+    "MultipleInherentImpl",
+    "NeedlessPassByRefMut",
+    "SameNameMethod",
+    "SingleCallFn",
+    "UnnecessaryDefPath",
+    "UnusedAsync",
+    "UselessVec",
+];
+
+/// The levels of the children of a diagnostic which can be stored, by their index.
+const CHILD_LEVELS: [Level; 5] = [
+    Level::Note,
+    Level::OnceNote,
+    Level::Help,
+    Level::OnceHelp,
+    Level::Warning,
+];
+
+const SUGGESTION_STYLES: [SuggestionStyle; 5] = [
+    SuggestionStyle::HideCodeInline,
+    SuggestionStyle::HideCodeAlways,
+    SuggestionStyle::CompletelyHidden,
+    SuggestionStyle::ShowCode,
+    SuggestionStyle::ShowAlways,
+];
+
+const APPLICABILITIES: [Applicability; 4] = [
+    Applicability::MachineApplicable,
+    Applicability::MaybeIncorrect,
+    Applicability::HasPlaceholders,
+    Applicability::Unspecified,
+];
+
+/// Replaces the late passes registered from the index `first_pass` on by a single pass, which
+/// runs them on the functions of the crate unless their diagnostics are cached.
+pub fn enable(store: &mut LintStore, first_pass: usize) {
+    let passes: Vec<_> = store.late_passes.drain(first_pass..).collect();
+    let lints = store.get_lints().to_vec();
+    store
+        .register_late_pass(move |tcx| Box::new(LintCache::new(passes.iter().map(|pass| pass(tcx)).collect(), &lints)));
+}
+
+type LateLintPassObject<'tcx> = Box<dyn LateLintPass<'tcx> + 'tcx>;
+
+/// A span relative to the start of the function it is in.
+type RelativeSpan = (u32, u32);
+
+#[derive(Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// The version of Clippy which wrote the cache, see [`clippy_version`].
+    version: String,
+    /// The hash of everything but the bodies of the functions, which the cached diagnostics are
+    /// only valid for.
+    global: String,
+    /// The cached functions, by the hash of their path.
+    functions: BTreeMap<String, Entry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    fingerprint: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Diagnostic {
+    lint: String,
+    /// The `ItemLocalId` in the function of the node whose lint level applies.
+    node: u32,
+    message: String,
+    span: Spans,
+    children: Vec<Child>,
+    suggestions: Vec<Suggestion>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Spans {
+    primary: Vec<RelativeSpan>,
+    labels: Vec<(RelativeSpan, String)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Child {
+    /// The index in `CHILD_LEVELS`.
+    level: usize,
+    message: String,
+    span: Spans,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Suggestion {
+    substitutions: Vec<Vec<(RelativeSpan, String)>>,
+    message: String,
+    /// The index in `SUGGESTION_STYLES`.
+    style: usize,
+    /// The index in `APPLICABILITIES`.
+    applicability: usize,
+}
+
+/// A function whose diagnostics can be cached.
+struct Function {
+    /// The hash of the path of the function, which identifies it across compilations.
+    key: String,
+    /// The hash of the source of the function.
+    fingerprint: String,
+    /// The span of the function and its body, which the cached spans are relative to.
+    span: Span,
+    /// The span of the body, which isn't part of the global hash.
+    body: Span,
+}
+
+/// The function which the lint passes are currently run on.
+enum Window {
+    /// Its diagnostics were replayed, so only the `ALWAYS_RUN` passes are run.
+    Replayed(LocalDefId),
+    /// Its diagnostics are recorded.
+    Recorded(LocalDefId),
+}
+
+/// Records the diagnostics emitted while linting a function.
+struct Recorder {
+    function: LocalDefId,
+    span: Span,
+    /// The node passed to the current `check_*` call, whose lint level `span_lint` uses.
+    node: HirId,
+    /// Whether the diagnostics are recorded, i.e. the current pass isn't one of `ALWAYS_RUN`.
+    enabled: bool,
+    diagnostics: Vec<Diagnostic>,
+    /// Whether all the diagnostics could be recorded.
+    cacheable: bool,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Records a diagnostic emitted by one of the functions of [`crate::diagnostics`]. `hir_id` is
+/// the node the lint is emitted at, if it isn't the one being checked.
+pub(crate) fn record(diag: &DiagInner, lint: &'static Lint, hir_id: Option<HirId>) {
+    RECORDER.with_borrow_mut(|recorder| {
+        if let Some(recorder) = recorder
+            && recorder.enabled
+        {
+            match recorder.diagnostic(diag, lint, hir_id.unwrap_or(recorder.node)) {
+                Some(diagnostic) => recorder.diagnostics.push(diagnostic),
+                None => recorder.cacheable = false,
+            }
+        }
+    });
+}
+
+fn set_recorder_state(node: HirId, enabled: bool) {
+    RECORDER.with_borrow_mut(|recorder| {
+        if let Some(recorder) = recorder {
+            recorder.node = node;
+            recorder.enabled = enabled;
+        }
+    });
+}
+
+impl Recorder {
+    fn diagnostic(&self, diag: &DiagInner, lint: &Lint, hir_id: HirId) -> Option<Diagnostic> {
+        if hir_id.owner.def_id != self.function || !diag.args.is_empty() {
+            return None;
+        }
+        let children = diag
+            .children
+            .iter()
+            .map(|child| {
+                Some(Child {
+                    level: CHILD_LEVELS.iter().position(|&level| level == child.level)?,
+                    message: single_message(&child.messages)?,
+                    span: self.spans(&child.span)?,
+                })
+            })
+            .collect::<Option<_>>()?;
+        let suggestions = diag
+            .suggestions
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .map(|suggestion| self.suggestion(suggestion))
+            .collect::<Option<_>>()?;
+        Some(Diagnostic {
+            lint: lint.name_lower(),
+            node: hir_id.local_id.as_u32(),
+            message: single_message(&diag.messages)?,
+            span: self.spans(&diag.span)?,
+            children,
+            suggestions,
+        })
+    }
+
+    fn suggestion(&self, suggestion: &CodeSuggestion) -> Option<Suggestion> {
+        let substitutions = suggestion
+            .substitutions
+            .iter()
+            .map(|substitution| {
+                substitution
+                    .parts
+                    .iter()
+                    .map(|part| Some((self.span(part.span)?, part.snippet.clone())))
+                    .collect()
+            })
+            .collect::<Option<_>>()?;
+        Some(Suggestion {
+            substitutions,
+            message: message(&suggestion.msg)?,
+            style: SUGGESTION_STYLES.iter().position(|&style| style == suggestion.style)?,
+            applicability: APPLICABILITIES
+                .iter()
+                .position(|&applicability| applicability == suggestion.applicability)?,
+        })
+    }
+
+    fn spans(&self, span: &MultiSpan) -> Option<Spans> {
+        let mut span = span.clone();
+        let mut labels = Vec::new();
+        while let Some((label_span, label)) = span.pop_span_label() {
+            labels.push((self.span(label_span)?, message(&label)?));
+        }
+        labels.reverse();
+        let primary: Vec<_> = span
+            .primary_spans()
+            .iter()
+            .map(|&span| self.span(span))
+            .collect::<Option<_>>()?;
+        // The primary spans are sorted when replayed.
+        primary
+            .windows(2)
+            .all(|spans| spans[0] <= spans[1])
+            .then_some(Spans { primary, labels })
+    }
+
+    fn span(&self, span: Span) -> Option<RelativeSpan> {
+        (span.ctxt().is_root() && self.span.contains(span))
+            .then(|| ((span.lo() - self.span.lo()).0, (span.hi() - self.span.lo()).0))
+    }
+}
+
+fn message(message: &DiagMessage) -> Option<String> {
+    match message {
+        DiagMessage::Str(message) | DiagMessage::Translated(message) => Some(message.to_string()),
+        DiagMessage::FluentIdentifier(..) => None,
+    }
+}
+
+fn single_message(messages: &[(DiagMessage, Style)]) -> Option<String> {
+    match messages {
+        [(msg, Style::NoStyle)] => message(msg),
+        _ => None,
+    }
+}
+
+fn absolute(span: RelativeSpan, start: BytePos) -> Span {
+    Span::with_root_ctxt(start + BytePos(span.0), start + BytePos(span.1))
+}
+
+impl Spans {
+    fn to_multi_span(&self, start: BytePos) -> MultiSpan {
+        let mut span = MultiSpan::from_spans(self.primary.iter().map(|&span| absolute(span, start)).collect());
+        for &(label_span, ref label) in &self.labels {
+            span.push_span_label(absolute(label_span, start), label.clone());
+        }
+        span
+    }
+}
+
+struct LintCache<'tcx> {
+    passes: Vec<LateLintPassObject<'tcx>>,
+    /// Whether each of `passes` is one of `ALWAYS_RUN`.
+    always_run: Vec<bool>,
+    lints: FxHashMap<String, &'static Lint>,
+    functions: FxHashMap<LocalDefId, Function>,
+    path: Option<PathBuf>,
+    previous: CacheFile,
+    next: CacheFile,
+    window: Option<Window>,
+}
+
+impl_lint_pass!(LintCache<'_> => []);
+
+impl<'tcx> LintCache<'tcx> {
+    fn new(passes: Vec<LateLintPassObject<'tcx>>, lints: &[&'static Lint]) -> Self {
+        Self {
+            always_run: passes.iter().map(|pass| ALWAYS_RUN.contains(&pass.name())).collect(),
+            passes,
+            lints: lints.iter().map(|&lint| (lint.name_lower(), lint)).collect(),
+            functions: FxHashMap::default(),
+            path: None,
+            previous: CacheFile::default(),
+            next: CacheFile::default(),
+            window: None,
+        }
+    }
+
+    fn forward(&mut self, cx: &LateContext<'tcx>, mut check: impl FnMut(&mut LateLintPassObject<'tcx>)) {
+        let passes = self.passes.iter_mut().zip(&self.always_run);
+        match self.window {
+            None => passes.for_each(|(pass, _)| check(pass)),
+            Some(Window::Replayed(_)) => passes
+                .filter(|(_, &always_run)| always_run)
+                .for_each(|(pass, _)| check(pass)),
+            Some(Window::Recorded(_)) => {
+                let node = cx.last_node_with_lint_attrs;
+                set_recorder_state(node, true);
+                for (pass, &always_run) in passes {
+                    if always_run {
+                        set_recorder_state(node, false);
+                        check(pass);
+                        set_recorder_state(node, true);
+                    } else {
+                        check(pass);
+                    }
+                }
+            },
+        }
+    }
+
+    fn load(&mut self, tcx: TyCtxt<'tcx>) {
+        let Some(path) = cache_path(tcx) else {
+            return;
+        };
+        self.functions = functions(tcx);
+        let version = clippy_version();
+        let global = global_hash(tcx, &self.functions);
+        // A cache which can't be read is replaced.
+        self.previous = fs::read(&path)
+            .ok()
+            .and_then(|cache| serde_json::from_slice::<CacheFile>(&cache).ok())
+            .filter(|cache| cache.version == version && cache.global == global)
+            .unwrap_or_default();
+        self.next.version = version;
+        self.next.global = global;
+        self.path = Some(path);
+    }
+
+    fn save(&mut self, tcx: TyCtxt<'tcx>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| serde_json::to_vec(&self.next).map_err(io::Error::from))
+            .and_then(|cache| fs::write(path, cache));
+        if let Err(err) = result {
+            tcx.dcx()
+                .warn(format!("could not write the lint cache to `{}`: {err}", path.display()));
+        }
+    }
+
+    fn enter_fn(&mut self, cx: &LateContext<'tcx>, def_id: LocalDefId) {
+        if self.window.is_some() {
+            return;
+        }
+        let Some(function) = self.functions.get(&def_id) else {
+            return;
+        };
+        if let Some(entry) = self.previous.functions.remove(&function.key)
+            && entry.fingerprint == function.fingerprint
+        {
+            for diagnostic in &entry.diagnostics {
+                self.replay(cx, def_id, function.span.lo(), diagnostic);
+            }
+            self.next.functions.insert(function.key.clone(), entry);
+            self.window = Some(Window::Replayed(def_id));
+        } else {
+            RECORDER.set(Some(Recorder {
+                function: def_id,
+                span: function.span,
+                node: cx.last_node_with_lint_attrs,
+                enabled: true,
+                diagnostics: Vec::new(),
+                cacheable: true,
+            }));
+            self.window = Some(Window::Recorded(def_id));
+        }
+    }
+
+    fn exit_body(&mut self, cx: &LateContext<'tcx>, body: &Body<'_>) {
+        let owner = cx.tcx.hir().body_owner_def_id(body.id());
+        match self.window {
+            Some(Window::Replayed(def_id)) if def_id == owner => self.window = None,
+            Some(Window::Recorded(def_id)) if def_id == owner => {
+                self.window = None;
+                if let Some(recorder) = RECORDER.take()
+                    && recorder.cacheable
+                {
+                    let function = &self.functions[&def_id];
+                    self.next.functions.insert(
+                        function.key.clone(),
+                        Entry {
+                            fingerprint: function.fingerprint.clone(),
+                            diagnostics: recorder.diagnostics,
+                        },
+                    );
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn replay(&self, cx: &LateContext<'tcx>, def_id: LocalDefId, start: BytePos, diagnostic: &Diagnostic) {
+        let Some(&lint) = self.lints.get(&diagnostic.lint) else {
+            return;
+        };
+        let hir_id = HirId {
+            owner: OwnerId { def_id },
+            local_id: ItemLocalId::from_u32(diagnostic.node),
+        };
+        #[expect(clippy::disallowed_methods)]
+        cx.tcx.node_span_lint(
+            lint,
+            hir_id,
+            diagnostic.span.to_multi_span(start),
+            diagnostic.message.clone(),
+            |diag| {
+                diag.children.extend(diagnostic.children.iter().map(|child| Subdiag {
+                    level: CHILD_LEVELS[child.level],
+                    messages: vec![(child.message.clone().into(), Style::NoStyle)],
+                    span: child.span.to_multi_span(start),
+                }));
+                if let Ok(suggestions) = &mut diag.suggestions {
+                    suggestions.extend(diagnostic.suggestions.iter().map(|suggestion| {
+                        CodeSuggestion {
+                            substitutions: suggestion
+                                .substitutions
+                                .iter()
+                                .map(|parts| Substitution {
+                                    parts: parts
+                                        .iter()
+                                        .map(|&(span, ref snippet)| SubstitutionPart {
+                                            span: absolute(span, start),
+                                            snippet: snippet.clone(),
+                                        })
+                                        .collect(),
+                                })
+                                .collect(),
+                            msg: suggestion.message.clone().into(),
+                            style: SUGGESTION_STYLES[suggestion.style],
+                            applicability: APPLICABILITIES[suggestion.applicability],
+                        }
+                    }));
+                }
+                warn_budget::count(diag, lint);
+                summary::count(diag, lint);
+            },
+        );
+    }
+}
+
+/// Returns the path of the cache of the crate, in the parent of its output directory, e.g.
+/// `target/debug/clippy-cache`.
+fn cache_path(tcx: TyCtxt<'_>) -> Option<PathBuf> {
+    let output_dir = tcx.sess.io.output_dir.as_ref()?;
+    Some(output_dir.parent()?.join("clippy-cache").join(format!(
+        "{}{}.json",
+        tcx.crate_name(LOCAL_CRATE),
+        tcx.sess.opts.cg.extra_filename
+    )))
+}
+
+/// Returns the functions of the crate which aren't generated by a macro.
+fn functions(tcx: TyCtxt<'_>) -> FxHashMap<LocalDefId, Function> {
+    let source_map = tcx.sess.source_map();
+    tcx.hir()
+        .body_owners()
+        .filter(|&def_id| matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn))
+        .filter_map(|def_id| {
+            let span = tcx.hir().span_with_body(tcx.local_def_id_to_hir_id(def_id));
+            let body = tcx.hir().body(tcx.hir().body_owned_by(def_id)).value.span;
+            if span.from_expansion() || body.from_expansion() || !span.contains(body) {
+                return None;
+            }
+            let mut hasher = StableHasher::new();
+            source_map.span_to_snippet(span).ok()?.hash(&mut hasher);
+            let function = Function {
+                key: tcx.def_path_hash(def_id.to_def_id()).0.to_hex(),
+                fingerprint: hasher.finish::<Fingerprint>().to_hex(),
+                span,
+                body,
+            };
+            Some((def_id, function))
+        })
+        .collect()
+}
+
+/// Hashes the source of the crate without the bodies of `functions`, the dependencies, and the
+/// arguments and configuration of Clippy.
+fn global_hash(tcx: TyCtxt<'_>, functions: &FxHashMap<LocalDefId, Function>) -> String {
+    let mut hasher = StableHasher::new();
+    #[expect(rustc::potential_query_instability, reason = "the bodies are sorted")]
+    let mut bodies: Vec<Span> = functions.values().map(|function| function.body).collect();
+    bodies.sort_by_key(|body| body.lo());
+    for file in tcx.sess.source_map().files().iter() {
+        if file.cnum != LOCAL_CRATE {
+            continue;
+        }
+        file.name.prefer_local().to_string().hash(&mut hasher);
+        let Some(src) = &file.src else {
+            continue;
+        };
+        let mut pos = file.start_pos;
+        for body in &bodies {
+            // The bodies of nested functions were already skipped.
+            if body.lo() >= pos && body.hi() <= file.end_position() {
+                src[(pos - file.start_pos).0 as usize..(body.lo() - file.start_pos).0 as usize].hash(&mut hasher);
+                pos = body.hi();
+            }
+        }
+        src[(pos - file.start_pos).0 as usize..].hash(&mut hasher);
+    }
+    for &cnum in tcx.crates(()) {
+        tcx.crate_hash(cnum).as_u128().hash(&mut hasher);
+    }
+    env::args_os().collect::<Vec<_>>().hash(&mut hasher);
+    for var in [
+        "CLIPPY_ARGS",
+        "CLIPPY_CONF_DIR",
+        "CLIPPY_DISABLE_DOCS_LINKS",
+        clippy_config::PROFILE_ENV_VAR,
+    ] {
+        env::var_os(var).hash(&mut hasher);
+    }
+    if let Ok((Some(conf), _)) = clippy_config::lookup_conf_file() {
+        fs::read(conf).ok().hash(&mut hasher);
+    }
+    hasher.finish::<Fingerprint>().to_hex()
+}
+
+/// Returns the version of Clippy, including a hash of its executable since a rebuilt Clippy may
+/// lint differently.
+fn clippy_version() -> String {
+    let mut hasher = StableHasher::new();
+    if let Ok(metadata) = env::current_exe().and_then(fs::metadata) {
+        metadata.len().hash(&mut hasher);
+        metadata.modified().ok().hash(&mut hasher);
+    }
+    format!(
+        "{}-{}",
+        env!("CARGO_PKG_VERSION"),
+        hasher.finish::<Fingerprint>().to_hex()
+    )
+}
+
+macro_rules! before {
+    (check_crate, $self:ident, $cx:ident) => {
+        $self.load($cx.tcx)
+    };
+    (check_fn, $self:ident, $cx:ident, $kind:ident, $decl:ident, $body:ident, $span:ident, $def_id:ident) => {
+        $self.enter_fn($cx, $def_id)
+    };
+    ($($any:tt)*) => {};
+}
+
+macro_rules! after {
+    (check_body_post, $self:ident, $cx:ident, $body:ident) => {
+        $self.exit_body($cx, $body)
+    };
+    (check_crate_post, $self:ident, $cx:ident) => {
+        $self.save($cx.tcx)
+    };
+    ($($any:tt)*) => {};
+}
+
+macro_rules! impl_forwarding_pass {
+    ([], [$($(#[$attr:meta])* fn $f:ident($($param:ident: $arg:ty),*);)*]) => {
+        impl<'tcx> LateLintPass<'tcx> for LintCache<'tcx> {
+            $(fn $f(&mut self, cx: &LateContext<'tcx>, $($param: $arg),*) {
+                before!($f, self, cx $(, $param)*);
+                self.forward(cx, |pass| pass.$f(cx, $($param),*));
+                after!($f, self, cx $(, $param)*);
+            })*
+        }
+    };
+}
+
+rustc_lint::late_lint_methods!(impl_forwarding_pass, []);
@@ -0,0 +1,116 @@
+//! Measuring of the time spent in each late lint pass, enabled by `cargo clippy --lint-profile`.
+//!
+//! The passes are wrapped in a pass timing all of their calls, which keeps their name. A report
+//! of the slowest passes is emitted as a note at the end of the crate.
+
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_lint::{LateContext, LateLintPass, LintPass, LintStore};
+use rustc_session::impl_lint_pass;
+use std::fmt::Write;
+use std::mem;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The number of passes listed in the report.
+const REPORTED_PASSES: usize = 20;
+
+type Times = Arc<Mutex<Vec<(&'static str, Duration)>>>;
+
+/// Times the late passes registered from the index `first_pass` on, and adds a pass reporting
+/// the times at the end of the crate.
+pub fn enable(store: &mut LintStore, first_pass: usize) {
+    let times = Times::default();
+    let passes: Vec<_> = store.late_passes.drain(first_pass..).collect();
+    for pass in passes {
+        let times = Arc::clone(&times);
+        store.register_late_pass(move |tcx| {
+            Box::new(Timed {
+                pass: pass(tcx),
+                time: Duration::ZERO,
+                times: Arc::clone(&times),
+            })
+        });
+    }
+    store.register_late_pass(move |_| {
+        Box::new(Report {
+            times: Arc::clone(&times),
+        })
+    });
+}
+
+struct Timed<'tcx> {
+    pass: Box<dyn LateLintPass<'tcx> + 'tcx>,
+    time: Duration,
+    times: Times,
+}
+
+#[expect(
+    rustc::lint_pass_impl_without_macro,
+    reason = "the name is the one of the timed pass"
+)]
+impl LintPass for Timed<'_> {
+    fn name(&self) -> &'static str {
+        self.pass.name()
+    }
+}
+
+macro_rules! after {
+    (check_crate_post, $self:ident) => {
+        $self.times.lock().unwrap().push(($self.pass.name(), $self.time))
+    };
+    ($($any:tt)*) => {};
+}
+
+macro_rules! impl_timed_pass {
+    ([], [$($(#[$attr:meta])* fn $f:ident($($param:ident: $arg:ty),*);)*]) => {
+        impl<'tcx> LateLintPass<'tcx> for Timed<'tcx> {
+            $(fn $f(&mut self, cx: &LateContext<'tcx>, $($param: $arg),*) {
+                let start = Instant::now();
+                self.pass.$f(cx, $($param),*);
+                self.time += start.elapsed();
+                after!($f, self);
+            })*
+        }
+    };
+}
+
+rustc_lint::late_lint_methods!(impl_timed_pass, []);
+
+struct Report {
+    times: Times,
+}
+
+impl_lint_pass!(Report => []);
+
+impl LateLintPass<'_> for Report {
+    fn check_crate_post(&mut self, cx: &LateContext<'_>) {
+        let mut times = mem::take(&mut *self.times.lock().unwrap());
+        times.sort_by(|(_, a), (_, b)| b.cmp(a));
+        let total: Duration = times.iter().map(|&(_, time)| time).sum();
+        let share = |time: Duration| 100. * time.as_secs_f64() / total.as_secs_f64().max(f64::EPSILON);
+
+        let mut report = format!(
+            "the lint passes of `{}` took {}",
+            cx.tcx.crate_name(LOCAL_CRATE),
+            millis(total)
+        );
+        for &(name, time) in times.iter().take(REPORTED_PASSES) {
+            let _ = write!(report, "\n{:>12} {:>5.1}%  {name}", millis(time), share(time));
+        }
+        if let Some(rest) = times.get(REPORTED_PASSES..).filter(|rest| !rest.is_empty()) {
+            let time = rest.iter().map(|&(_, time)| time).sum();
+            let _ = write!(
+                report,
+                "\n{:>12} {:>5.1}%  {} other passes",
+                millis(time),
+                share(time),
+                rest.len()
+            );
+        }
+        cx.tcx.dcx().note(report);
+    }
+}
+
+fn millis(time: Duration) -> String {
+    format!("{:.2}ms", time.as_secs_f64() * 1000.)
+}
@@ -0,0 +1,118 @@
+//! Collapsing of the warnings of a lint in many expansions of the same macro, for
+//! `cargo clippy --collapse-macro-diagnostics`.
+//!
+//! The functions of [`crate::diagnostics`] mark the warnings whose primary span comes from a macro
+//! expansion. A hook on the emission of the diagnostics holds these back, grouped by lint and
+//! definition site of the macro, and a pass registered after all the others emits the first
+//! warning of each group with a note listing the call sites of the macro.
+
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_errors::{
+    Diag, DiagArgValue, DiagInner, ErrorGuaranteed, Level, MultiSpan, Style, Subdiag, TRACK_DIAGNOSTIC,
+};
+use rustc_lint::{LateContext, LateLintPass, Lint, LintStore};
+use rustc_session::impl_lint_pass;
+use rustc_span::{ExpnKind, Span, Symbol};
+use std::sync::{Mutex, OnceLock};
+
+/// The argument of the diagnostics to hold back, with the name of their lint as value.
+const MARKER: &str = "clippy_macro_diagnostic_lint";
+
+/// The number of call sites listed in the note of a collapsed warning.
+const LISTED_SITES: usize = 5;
+
+type TrackDiagnostic = fn(DiagInner, &mut dyn FnMut(DiagInner) -> Option<ErrorGuaranteed>) -> Option<ErrorGuaranteed>;
+
+static HOOK: TrackDiagnostic = track_diagnostic;
+
+/// The hook which was installed before [`track_diagnostic`], usually the one of incremental
+/// compilation.
+static PREVIOUS: OnceLock<&'static TrackDiagnostic> = OnceLock::new();
+
+/// The warnings held back by lint name, name and definition site of the macro, if enabled.
+#[expect(clippy::type_complexity)]
+static HELD: Mutex<Option<FxIndexMap<(String, Symbol, Span), Vec<DiagInner>>>> = Mutex::new(None);
+
+/// Enables the collapsing of the warnings. This has to be called after all the passes are
+/// registered.
+pub fn enable(store: &mut LintStore) {
+    *HELD.lock().unwrap() = Some(FxIndexMap::default());
+    PREVIOUS.get_or_init(|| TRACK_DIAGNOSTIC.swap(&HOOK));
+    store.register_late_pass(|_| Box::new(CollapseMacroDiagnostics));
+}
+
+/// Marks a diagnostic emitted by one of the functions of [`crate::diagnostics`] to be held back,
+/// if its primary span comes from a macro expansion.
+pub(crate) fn mark(diag: &mut Diag<'_, ()>, lint: &'static Lint) {
+    if HELD.lock().unwrap().is_some()
+        && let Some(span) = diag.span.primary_span()
+        && matches!(span.ctxt().outer_expn_data().kind, ExpnKind::Macro(..))
+    {
+        diag.arg(MARKER, lint.name_lower());
+    }
+}
+
+fn track_diagnostic(
+    mut diag: DiagInner,
+    emit: &mut dyn FnMut(DiagInner) -> Option<ErrorGuaranteed>,
+) -> Option<ErrorGuaranteed> {
+    // Only warnings are held back, errors have to be emitted even if the crate isn't linted to the end
+    if let Some(DiagArgValue::Str(lint)) = diag.args.swap_remove(MARKER)
+        && diag.level() == Level::Warning
+        && let Some(span) = diag.span.primary_span()
+    {
+        let data = span.ctxt().outer_expn_data();
+        if let ExpnKind::Macro(_, name) = data.kind {
+            let mut held = HELD.lock().unwrap();
+            if let Some(held) = &mut *held {
+                held.entry((lint.into_owned(), name, data.def_site))
+                    .or_default()
+                    .push(diag);
+                return None;
+            }
+        }
+    }
+    (PREVIOUS.get().unwrap())(diag, emit)
+}
+
+struct CollapseMacroDiagnostics;
+
+impl_lint_pass!(CollapseMacroDiagnostics => []);
+
+impl LateLintPass<'_> for CollapseMacroDiagnostics {
+    fn check_crate_post(&mut self, cx: &LateContext<'_>) {
+        let held = HELD.lock().unwrap().replace(FxIndexMap::default()).unwrap_or_default();
+        let dcx = cx.tcx.dcx();
+        for ((_, name, _), diags) in held {
+            let mut sites = Vec::new();
+            for diag in &diags {
+                let site = diag.span.primary_span().map_or(diag.sort_span, Span::source_callsite);
+                if !sites.contains(&site) {
+                    sites.push(site);
+                }
+            }
+            // A single expansion can't be collapsed, e.g. when its warnings come from different lines
+            if sites.len() < 2 {
+                for diag in diags {
+                    dcx.emit_diagnostic(diag);
+                }
+                continue;
+            }
+            let mut diag = diags.into_iter().next().unwrap();
+            let mut message = format!(
+                "this warning is emitted in the expansions of `{name}` at {} places",
+                sites.len()
+            );
+            if sites.len() > LISTED_SITES {
+                message.push_str(&format!(", the first {LISTED_SITES} of which are shown"));
+            }
+            sites.truncate(LISTED_SITES);
+            diag.children.push(Subdiag {
+                level: Level::Note,
+                messages: vec![(message.into(), Style::NoStyle)],
+                span: MultiSpan::from_spans(sites),
+            });
+            dcx.emit_diagnostic(diag);
+        }
+    }
+}
@@ -62,6 +62,94 @@ impl MacroCall {
     pub fn is_local(&self) -> bool {
         span_is_local(self.span)
     }
+
+    /// Returns the crate which defines the called macro.
+    pub fn origin(&self, cx: &LateContext<'_>) -> MacroOrigin {
+        if self.def_id.is_local() {
+            MacroOrigin::Local
+        } else if matches!(cx.tcx.crate_name(self.def_id.krate), sym::core | sym::alloc | sym::std) {
+            MacroOrigin::Std
+        } else {
+            MacroOrigin::External
+        }
+    }
+}
+
+/// The crate which defines a macro.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MacroOrigin {
+    /// The local crate.
+    Local,
+    /// `core`, `alloc` or `std`.
+    Std,
+    /// Any other crate.
+    External,
+}
+
+/// What a lint may do with some code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Lintability {
+    Skip,
+    /// Lint, without suggesting edits.
+    Lint,
+    LintAndSuggest,
+}
+
+impl Lintability {
+    pub fn should_lint(self) -> bool {
+        self >= Self::Lint
+    }
+
+    pub fn should_suggest(self) -> bool {
+        self == Self::LintAndSuggest
+    }
+}
+
+/// What a lint may do with the code produced by the macros of each origin, see [`lintability`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MacroPolicy {
+    pub local: Lintability,
+    pub std: Lintability,
+    pub external: Lintability,
+}
+
+impl MacroPolicy {
+    /// Only lints the code written outside of macros.
+    pub const OUTSIDE_MACROS: Self = Self {
+        local: Lintability::Skip,
+        std: Lintability::Skip,
+        external: Lintability::Skip,
+    };
+
+    /// Also lints the code produced by the macros of the local crate, without suggestions, since
+    /// editing the macro would change all of its calls.
+    pub const LOCAL_MACROS: Self = Self {
+        local: Lintability::Lint,
+        std: Lintability::Skip,
+        external: Lintability::Skip,
+    };
+
+    fn get(self, origin: MacroOrigin) -> Lintability {
+        match origin {
+            MacroOrigin::Local => self.local,
+            MacroOrigin::Std => self.std,
+            MacroOrigin::External => self.external,
+        }
+    }
+}
+
+/// Returns what a lint following `policy` may do with the code at `span`, given all of the macro
+/// calls which produced it, e.g. the code of a local macro called by an external macro is
+/// treated as external code.
+///
+/// The code passed to a macro as an argument has the context of the call site, so it's treated
+/// like the code around the call. Desugarings aren't macro calls, and the code of procedural
+/// macros which reuse the spans of their input can only be recognized by
+/// [`is_from_proc_macro`](crate::is_from_proc_macro).
+pub fn lintability(cx: &LateContext<'_>, span: Span, policy: MacroPolicy) -> Lintability {
+    macro_backtrace(span)
+        .map(|call| policy.get(call.origin(cx)))
+        .fold(Lintability::LintAndSuggest, Ord::min)
 }
 
 /// Returns an iterator of expansions that created the given span
@@ -0,0 +1,523 @@
+//! Dataflow analyses of the locals of a MIR body, for lints which need to know what may have
+//! happened to a value before some point, e.g. whether it may have been moved or freed.
+//!
+//! The analyses only track whole locals: assigning or moving a field of a local doesn't change
+//! its state. They are run to a fixpoint with [`run`], which returns a cursor giving their state
+//! before or after any location:
+//!
+//! ```rust,ignore
+//! let mir = cx.tcx.optimized_mir(def_id);
+//! let mut reclaimed = run(cx.tcx, mir, MaybeReclaimedPointers::new(mir, |callee| frees.contains(&callee)));
+//! for (block, data) in mir.basic_blocks.iter_enumerated() {
+//!     if let TerminatorKind::Call { func, args, fn_span, .. } = &data.terminator().kind
+//!         && let Some((callee, _)) = func.const_fn_def()
+//!         && frees.contains(&callee)
+//!         && let Some(pointer) = args.first().and_then(|arg| arg.node.place()?.as_local())
+//!     {
+//!         reclaimed.seek_before_primary_effect(mir.terminator_loc(block));
+//!         if reclaimed.analysis().is_reclaimed(reclaimed.get(), pointer) {
+//!             span_lint(cx, DOUBLE_FREE, *fn_span, "this pointer may have already been freed");
+//!         }
+//!     }
+//! }
+//! ```
+
+use super::enclosing_mir;
+use rustc_hir::def_id::DefId;
+use rustc_hir::Expr;
+use rustc_index::bit_set::BitSet;
+use rustc_index::IndexVec;
+use rustc_lint::LateContext;
+use rustc_middle::mir::visit::Visitor;
+use rustc_middle::mir::{
+    BasicBlock, Body, CallReturnPlaces, Local, Location, Operand, Rvalue, Statement, StatementKind, Terminator,
+    TerminatorEdges, TerminatorKind,
+};
+use rustc_middle::ty::TyCtxt;
+use rustc_mir_dataflow::fmt::DebugWithContext;
+use rustc_mir_dataflow::{Analysis, AnalysisDomain, GenKill, GenKillAnalysis, ResultsCursor};
+use rustc_span::Span;
+
+/// Runs `analysis` on `mir` to its fixpoint, and returns a cursor to inspect its results.
+pub fn run<'mir, 'tcx, A: Analysis<'tcx>>(
+    tcx: TyCtxt<'tcx>,
+    mir: &'mir Body<'tcx>,
+    analysis: A,
+) -> ResultsCursor<'mir, 'tcx, A>
+where
+    A::Domain: DebugWithContext<A>,
+{
+    analysis
+        .into_engine(tcx, mir)
+        .iterate_to_fixpoint()
+        .into_results_cursor(mir)
+}
+
+fn tracked_locals(mir: &Body<'_>, locals: &[Local]) -> BitSet<Local> {
+    let mut tracked = BitSet::new_empty(mir.local_decls.len());
+    for &local in locals {
+        tracked.insert(local);
+    }
+    tracked
+}
+
+/// The locals among the tracked ones which may hold a value: they were assigned, or are
+/// arguments, and may not have been moved out, dropped or marked dead since.
+pub struct MaybeInitializedLocals {
+    tracked: BitSet<Local>,
+}
+
+impl MaybeInitializedLocals {
+    pub fn new(mir: &Body<'_>, locals: &[Local]) -> Self {
+        Self {
+            tracked: tracked_locals(mir, locals),
+        }
+    }
+}
+
+/// The locals among the tracked ones which may have been moved out, dropped or marked dead, and
+/// not assigned again since.
+pub struct MaybeConsumedLocals {
+    tracked: BitSet<Local>,
+}
+
+impl MaybeConsumedLocals {
+    pub fn new(mir: &Body<'_>, locals: &[Local]) -> Self {
+        Self {
+            tracked: tracked_locals(mir, locals),
+        }
+    }
+}
+
+/// Applies the effects of statements and terminators on the initialization of the tracked locals.
+/// The state is the set of the initialized locals, or of the consumed ones if `consumed` is set.
+struct InitTransfer<'a, T> {
+    tracked: &'a BitSet<Local>,
+    trans: &'a mut T,
+    consumed: bool,
+}
+
+impl<T: GenKill<Local>> InitTransfer<'_, T> {
+    fn set(&mut self, local: Local, initialized: bool) {
+        if self.tracked.contains(local) {
+            if initialized == self.consumed {
+                self.trans.kill(local);
+            } else {
+                self.trans.gen(local);
+            }
+        }
+    }
+}
+
+impl<'tcx, T: GenKill<Local>> Visitor<'tcx> for InitTransfer<'_, T> {
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        match &statement.kind {
+            StatementKind::Assign(box (place, rvalue)) => {
+                // The operands are read before the place is assigned.
+                self.visit_rvalue(rvalue, location);
+                if let Some(local) = place.as_local() {
+                    self.set(local, true);
+                }
+            },
+            StatementKind::StorageDead(local) => self.set(*local, false),
+            StatementKind::Deinit(box place) => {
+                if let Some(local) = place.as_local() {
+                    self.set(local, false);
+                }
+            },
+            _ => self.super_statement(statement, location),
+        }
+    }
+
+    fn visit_operand(&mut self, operand: &Operand<'tcx>, _: Location) {
+        if let Operand::Move(place) = operand
+            && let Some(local) = place.as_local()
+        {
+            self.set(local, false);
+        }
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        if let TerminatorKind::Drop { place, .. } = &terminator.kind {
+            if let Some(local) = place.as_local() {
+                self.set(local, false);
+            }
+        } else {
+            self.super_terminator(terminator, location);
+        }
+    }
+}
+
+macro_rules! impl_init_analysis {
+    ($analysis:ident, $name:literal, $consumed:literal) => {
+        impl<'tcx> AnalysisDomain<'tcx> for $analysis {
+            type Domain = BitSet<Local>;
+            const NAME: &'static str = $name;
+
+            fn bottom_value(&self, mir: &Body<'tcx>) -> Self::Domain {
+                BitSet::new_empty(mir.local_decls.len())
+            }
+
+            fn initialize_start_block(&self, mir: &Body<'tcx>, state: &mut Self::Domain) {
+                if !$consumed {
+                    for arg in mir.args_iter() {
+                        if self.tracked.contains(arg) {
+                            state.insert(arg);
+                        }
+                    }
+                }
+            }
+        }
+
+        impl<'tcx> GenKillAnalysis<'tcx> for $analysis {
+            type Idx = Local;
+
+            fn domain_size(&self, mir: &Body<'tcx>) -> usize {
+                mir.local_decls.len()
+            }
+
+            fn statement_effect(
+                &mut self,
+                trans: &mut impl GenKill<Local>,
+                statement: &Statement<'tcx>,
+                location: Location,
+            ) {
+                InitTransfer {
+                    tracked: &self.tracked,
+                    trans,
+                    consumed: $consumed,
+                }
+                .visit_statement(statement, location);
+            }
+
+            fn terminator_effect<'mir>(
+                &mut self,
+                trans: &mut Self::Domain,
+                terminator: &'mir Terminator<'tcx>,
+                location: Location,
+            ) -> TerminatorEdges<'mir, 'tcx> {
+                InitTransfer {
+                    tracked: &self.tracked,
+                    trans,
+                    consumed: $consumed,
+                }
+                .visit_terminator(terminator, location);
+                terminator.edges()
+            }
+
+            fn call_return_effect(
+                &mut self,
+                trans: &mut Self::Domain,
+                _: BasicBlock,
+                return_places: CallReturnPlaces<'_, 'tcx>,
+            ) {
+                let mut transfer = InitTransfer {
+                    tracked: &self.tracked,
+                    trans,
+                    consumed: $consumed,
+                };
+                return_places.for_each(|place| {
+                    if let Some(local) = place.as_local() {
+                        transfer.set(local, true);
+                    }
+                });
+            }
+        }
+    };
+}
+
+impl_init_analysis!(MaybeInitializedLocals, "maybe_initialized_locals", false);
+impl_init_analysis!(MaybeConsumedLocals, "maybe_consumed_locals", true);
+
+/// The pointers which may have been passed as the first argument of a function reclaiming their
+/// pointee, like `Box::from_raw` or `std::alloc::dealloc`, and not reassigned since.
+///
+/// The locals which are only assigned once, with a copy or a cast of another local, are
+/// considered to hold the same pointer as that local.
+pub struct MaybeReclaimedPointers<F> {
+    reclaims: F,
+    /// The local each local is a copy of, or itself.
+    roots: IndexVec<Local, Local>,
+}
+
+impl<F: Fn(DefId) -> bool> MaybeReclaimedPointers<F> {
+    /// `reclaims` tells whether a function reclaims the pointee of its first argument.
+    pub fn new(mir: &Body<'_>, reclaims: F) -> Self {
+        let mut assignments = IndexVec::from_elem(0_u32, &mir.local_decls);
+        // The arguments are assigned on entry.
+        for arg in mir.args_iter() {
+            assignments[arg] = 1;
+        }
+        let mut copied: IndexVec<Local, Option<Local>> = IndexVec::from_elem(None, &mir.local_decls);
+        for data in mir.basic_blocks.iter() {
+            for statement in &data.statements {
+                if let StatementKind::Assign(box (place, rvalue)) = &statement.kind
+                    && let Some(local) = place.as_local()
+                {
+                    assignments[local] += 1;
+                    if let Rvalue::Use(operand) | Rvalue::Cast(_, operand, _) = rvalue {
+                        copied[local] = operand.place().and_then(|place| place.as_local());
+                    }
+                }
+            }
+            if let TerminatorKind::Call { destination, .. } = &data.terminator().kind
+                && let Some(local) = destination.as_local()
+            {
+                assignments[local] += 1;
+            }
+        }
+        let roots = mir
+            .local_decls
+            .indices()
+            .map(|local| {
+                let mut root = local;
+                // The chains of copies can't be longer than the number of locals, unless they loop.
+                for _ in 0..mir.local_decls.len() {
+                    match copied[root] {
+                        Some(source) if assignments[root] == 1 => root = source,
+                        _ => break,
+                    }
+                }
+                root
+            })
+            .collect();
+        Self { reclaims, roots }
+    }
+
+    /// Whether `local` holds a pointer which may have been reclaimed, given the state of the
+    /// analysis.
+    pub fn is_reclaimed(&self, state: &BitSet<Local>, local: Local) -> bool {
+        state.contains(self.roots[local])
+    }
+
+    fn reassign(&self, trans: &mut impl GenKill<Local>, local: Local) {
+        if self.roots[local] == local {
+            trans.kill(local);
+        }
+    }
+}
+
+impl<'tcx, F: Fn(DefId) -> bool> AnalysisDomain<'tcx> for MaybeReclaimedPointers<F> {
+    type Domain = BitSet<Local>;
+    const NAME: &'static str = "maybe_reclaimed_pointers";
+
+    fn bottom_value(&self, mir: &Body<'tcx>) -> Self::Domain {
+        BitSet::new_empty(mir.local_decls.len())
+    }
+
+    fn initialize_start_block(&self, _: &Body<'tcx>, _: &mut Self::Domain) {}
+}
+
+impl<'tcx, F: Fn(DefId) -> bool> GenKillAnalysis<'tcx> for MaybeReclaimedPointers<F> {
+    type Idx = Local;
+
+    fn domain_size(&self, mir: &Body<'tcx>) -> usize {
+        mir.local_decls.len()
+    }
+
+    fn statement_effect(&mut self, trans: &mut impl GenKill<Local>, statement: &Statement<'tcx>, _: Location) {
+        if let StatementKind::Assign(box (place, _)) = &statement.kind
+            && let Some(local) = place.as_local()
+        {
+            self.reassign(trans, local);
+        }
+    }
+
+    fn terminator_effect<'mir>(
+        &mut self,
+        trans: &mut Self::Domain,
+        terminator: &'mir Terminator<'tcx>,
+        _: Location,
+    ) -> TerminatorEdges<'mir, 'tcx> {
+        if let TerminatorKind::Call { func, args, .. } = &terminator.kind
+            && let Some((callee, _)) = func.const_fn_def()
+            && (self.reclaims)(callee)
+            && let Some(pointer) = args.first().and_then(|arg| arg.node.place()?.as_local())
+        {
+            trans.gen(self.roots[pointer]);
+        }
+        terminator.edges()
+    }
+
+    fn call_return_effect(
+        &mut self,
+        trans: &mut Self::Domain,
+        _: BasicBlock,
+        return_places: CallReturnPlaces<'_, 'tcx>,
+    ) {
+        return_places.for_each(|place| {
+            if let Some(local) = place.as_local() {
+                self.reassign(trans, local);
+            }
+        });
+    }
+}
+
+/// The locals which may hold the values assigned at the source locations, a reference to them or
+/// a value containing them. The results of the calls taking such a local as argument are
+/// considered to hold them too.
+pub struct MaybeHoldsValue {
+    sources: Vec<Location>,
+}
+
+impl MaybeHoldsValue {
+    /// `sources` are the locations of the statements and calls assigning the tracked values.
+    pub fn new(sources: Vec<Location>) -> Self {
+        Self { sources }
+    }
+}
+
+/// Whether a place read by `rvalue` may hold the value, or the value of `rvalue` is unrelated.
+fn rvalue_holds(rvalue: &Rvalue<'_>, state: &BitSet<Local>) -> bool {
+    let operand_holds = |operand: &Operand<'_>| operand.place().is_some_and(|place| state.contains(place.local));
+    match rvalue {
+        Rvalue::Use(operand) | Rvalue::Cast(_, operand, _) | Rvalue::ShallowInitBox(operand, _) => {
+            operand_holds(operand)
+        },
+        Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place) | Rvalue::CopyForDeref(place) => {
+            state.contains(place.local)
+        },
+        Rvalue::Aggregate(_, operands) => operands.iter().any(operand_holds),
+        _ => false,
+    }
+}
+
+impl<'tcx> AnalysisDomain<'tcx> for MaybeHoldsValue {
+    type Domain = BitSet<Local>;
+    const NAME: &'static str = "maybe_holds_value";
+
+    fn bottom_value(&self, mir: &Body<'tcx>) -> Self::Domain {
+        BitSet::new_empty(mir.local_decls.len())
+    }
+
+    fn initialize_start_block(&self, _: &Body<'tcx>, _: &mut Self::Domain) {}
+}
+
+impl<'tcx> Analysis<'tcx> for MaybeHoldsValue {
+    fn apply_statement_effect(&mut self, state: &mut Self::Domain, statement: &Statement<'tcx>, location: Location) {
+        match &statement.kind {
+            StatementKind::Assign(box (place, rvalue)) => {
+                let holds = self.sources.contains(&location) || rvalue_holds(rvalue, state);
+                if holds {
+                    state.insert(place.local);
+                } else if let Some(local) = place.as_local() {
+                    state.remove(local);
+                }
+            },
+            StatementKind::StorageDead(local) => {
+                state.remove(*local);
+            },
+            _ => {},
+        }
+    }
+
+    fn apply_terminator_effect<'mir>(
+        &mut self,
+        state: &mut Self::Domain,
+        terminator: &'mir Terminator<'tcx>,
+        location: Location,
+    ) -> TerminatorEdges<'mir, 'tcx> {
+        match &terminator.kind {
+            TerminatorKind::Call { args, destination, .. } => {
+                // The result is assigned here rather than on return, which only makes the
+                // analysis less precise on the unwind edges.
+                let holds = self.sources.contains(&location)
+                    || args
+                        .iter()
+                        .any(|arg| arg.node.place().is_some_and(|place| state.contains(place.local)));
+                if holds {
+                    state.insert(destination.local);
+                } else if let Some(local) = destination.as_local() {
+                    state.remove(local);
+                }
+            },
+            TerminatorKind::Drop { place, .. } => {
+                if let Some(local) = place.as_local() {
+                    state.remove(local);
+                }
+            },
+            _ => {},
+        }
+        terminator.edges()
+    }
+
+    fn apply_call_return_effect(&mut self, _: &mut Self::Domain, _: BasicBlock, _: CallReturnPlaces<'_, 'tcx>) {}
+}
+
+/// Returns the locations of the statements and terminators of `mir` with the span `span`.
+fn locations_with_span(mir: &Body<'_>, span: Span) -> Vec<Location> {
+    let mut locations = Vec::new();
+    for (block, data) in mir.basic_blocks.iter_enumerated() {
+        for (statement_index, statement) in data.statements.iter().enumerate() {
+            if statement.source_info.span == span {
+                locations.push(Location { block, statement_index });
+            }
+        }
+        if data.terminator().source_info.span == span {
+            locations.push(mir.terminator_loc(block));
+        }
+    }
+    locations
+}
+
+/// Collects the locals read at a location.
+struct ReadLocals(BitSet<Local>);
+
+impl<'tcx> Visitor<'tcx> for ReadLocals {
+    fn visit_operand(&mut self, operand: &Operand<'tcx>, _: Location) {
+        if let Some(place) = operand.place() {
+            self.0.insert(place.local);
+        }
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
+        if let Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place) | Rvalue::CopyForDeref(place) = rvalue {
+            self.0.insert(place.local);
+        }
+        self.super_rvalue(rvalue, location);
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        if let TerminatorKind::Drop { place, .. } = &terminator.kind {
+            self.0.insert(place.local);
+        }
+        self.super_terminator(terminator, location);
+    }
+}
+
+/// Returns whether the value of `source` may flow to `sink` in the function containing them: the
+/// MIR of `sink` reads a local which may hold the value, a reference to it or a value containing
+/// it. Always `false` if they are in different bodies.
+pub fn expr_flows_to(cx: &LateContext<'_>, source: &Expr<'_>, sink: &Expr<'_>) -> bool {
+    let hir = cx.tcx.hir();
+    if hir.enclosing_body_owner(source.hir_id) != hir.enclosing_body_owner(sink.hir_id) {
+        return false;
+    }
+    let Some(mir) = enclosing_mir(cx.tcx, source.hir_id) else {
+        return false;
+    };
+    let sources: Vec<_> = locations_with_span(mir, source.span)
+        .into_iter()
+        .filter(|&location| {
+            let data = &mir.basic_blocks[location.block];
+            match data.statements.get(location.statement_index) {
+                Some(statement) => matches!(statement.kind, StatementKind::Assign(..)),
+                None => matches!(data.terminator().kind, TerminatorKind::Call { .. }),
+            }
+        })
+        .collect();
+    if sources.is_empty() {
+        return false;
+    }
+    let mut cursor = run(cx.tcx, mir, MaybeHoldsValue::new(sources));
+    locations_with_span(mir, sink.span).into_iter().any(|location| {
+        let mut read = ReadLocals(BitSet::new_empty(mir.local_decls.len()));
+        let data = &mir.basic_blocks[location.block];
+        match data.statements.get(location.statement_index) {
+            Some(statement) => read.visit_statement(statement, location),
+            None => read.visit_terminator(data.terminator(), location),
+        }
+        cursor.seek_before_primary_effect(location);
+        read.0.iter().any(|local| cursor.get().contains(local))
+    })
+}
@@ -6,6 +6,8 @@ use rustc_middle::mir::{
 };
 use rustc_middle::ty::TyCtxt;
 
+pub mod dataflow;
+
 mod possible_borrower;
 pub use possible_borrower::PossibleBorrowerMap;
 
@@ -0,0 +1,326 @@
+//! Whether a panic can be reached from an expression or a function of the local crate.
+//!
+//! Panics are found syntactically: the panicking macros, and outside of the other macros `unwrap`
+//! and `expect`, indexing, integer division, and the bodies of the functions and methods of the
+//! local crate which are called, up to a given depth. The callees are looked up in the
+//! [`call_graph`](crate::call_graph), so calls to other crates, to trait methods which can't be
+//! resolved to an implementation of the local crate and to closures are assumed not to panic. The
+//! results of [`fn_may_panic`] are cached for each configuration.
+
+use crate::call_graph::{call_graph, CallGraph};
+use crate::consts::{constant, Constant};
+use crate::macros::{is_panic, root_macro_call_first_node};
+use crate::sext;
+use crate::ty::is_type_diagnostic_item;
+use crate::visitors::Visitable;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::intravisit::{walk_expr, Visitor};
+use rustc_hir::{AnonConst, BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::hir::nested_filter;
+use rustc_middle::ty::{self, TypeckResults};
+use rustc_span::{sym, Span};
+use std::rc::Rc;
+use std::sync::Mutex;
+
+/// The kinds of panics which can be looked for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PanicKind {
+    /// `panic!`
+    Explicit,
+    /// `assert!`, `assert_eq!` and `assert_ne!`, but not their `debug_` variants.
+    Assert,
+    /// `unreachable!`
+    Unreachable,
+    /// `todo!`
+    Todo,
+    /// `unimplemented!`
+    Unimplemented,
+    /// `unwrap` and `expect` on an `Option`, or on a `Result` whose error type is inhabited.
+    Unwrap,
+    /// Indexing, except of an array by a constant in bounds.
+    Indexing,
+    /// Integer division and remainder, except by a constant which is neither zero nor -1.
+    Division,
+}
+
+impl PanicKind {
+    pub const ALL: &'static [Self] = &[
+        Self::Explicit,
+        Self::Assert,
+        Self::Unreachable,
+        Self::Todo,
+        Self::Unimplemented,
+        Self::Unwrap,
+        Self::Indexing,
+        Self::Division,
+    ];
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// The kinds of panics looked for, and how deep the calls are followed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PanicConfig {
+    kinds: u8,
+    depth: u32,
+}
+
+impl PanicConfig {
+    /// Looks for the given kinds of panics, following the calls to the functions of the local
+    /// crate `depth` levels deep. With a depth of 0, only the expression itself is looked at.
+    pub const fn new(kinds: &[PanicKind], depth: u32) -> Self {
+        let mut bits = 0;
+        let mut i = 0;
+        while i < kinds.len() {
+            bits |= kinds[i].bit();
+            i += 1;
+        }
+        Self { kinds: bits, depth }
+    }
+
+    pub fn includes(self, kind: PanicKind) -> bool {
+        self.kinds & kind.bit() != 0
+    }
+}
+
+/// A panic which can be reached.
+#[derive(Clone, Debug)]
+pub struct PanicSite {
+    pub kind: PanicKind,
+    /// The span of the panicking expression or macro call.
+    pub span: Span,
+    /// The spans of the calls through which the panic is reached, starting with the one in the
+    /// analyzed expression. Empty if the panic is in the analyzed expression itself.
+    pub calls: Vec<Span>,
+}
+
+impl PanicSite {
+    /// Returns the span in the analyzed expression leading to the panic: the first call, or the
+    /// panic itself.
+    pub fn first_span(&self) -> Span {
+        self.calls.first().copied().unwrap_or(self.span)
+    }
+}
+
+/// Returns the first panic which can be reached from `node`, with the typeck results of its body.
+/// Panics in constants are ignored, since they fail the compilation.
+pub fn find_panic<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    node: impl Visitable<'tcx>,
+    config: PanicConfig,
+) -> Option<PanicSite> {
+    visit_panics(cx, typeck_results, node, config, true).pop()
+}
+
+/// Returns all the panics which can be reached from `node`, with the typeck results of its body,
+/// in the order they appear in. Only the first panic reached through each call is returned, and the
+/// panics nested in the arguments of a panicking macro aren't, unlike the ones nested in another
+/// panicking expression, e.g. `a[b[0]]`.
+pub fn panic_sites<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    node: impl Visitable<'tcx>,
+    config: PanicConfig,
+) -> Vec<PanicSite> {
+    visit_panics(cx, typeck_results, node, config, false)
+}
+
+fn visit_panics<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    node: impl Visitable<'tcx>,
+    config: PanicConfig,
+    first_only: bool,
+) -> Vec<PanicSite> {
+    let mut finder = FindPanic {
+        cx,
+        typeck_results,
+        call_graph: (config.depth > 0).then(|| call_graph(cx)),
+        config,
+        first_only,
+        found: Vec::new(),
+    };
+    node.visit(&mut finder);
+    finder.found
+}
+
+type FnCache = FxHashMap<(LocalDefId, PanicConfig), Option<PanicSite>>;
+
+/// Whether indexing `base` with `index` can panic, i.e. unless `base` is an array and `index` a
+/// constant in its bounds.
+pub(crate) fn index_may_panic<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck_results: &TypeckResults<'tcx>,
+    base: &Expr<'_>,
+    index: &Expr<'_>,
+) -> bool {
+    let param_env = cx.tcx.param_env(typeck_results.hir_owner);
+    if let ty::Array(_, len) = typeck_results.expr_ty_adjusted(base).peel_refs().kind()
+        && let Some(len) = len.try_eval_target_usize(cx.tcx, param_env)
+        && let Some(Constant::Int(index)) = constant(cx, typeck_results, index)
+    {
+        index >= u128::from(len)
+    } else {
+        true
+    }
+}
+
+/// Whether an integer division or remainder by `divisor` can panic.
+pub(crate) fn division_may_panic<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck_results: &TypeckResults<'tcx>,
+    divisor: &Expr<'_>,
+) -> bool {
+    let ty = typeck_results.expr_ty(divisor);
+    if !ty.is_integral() {
+        return false;
+    }
+    match (constant(cx, typeck_results, divisor), ty.kind()) {
+        (Some(Constant::Int(value)), ty::Int(ity)) => matches!(sext(cx.tcx, value, *ity), 0 | -1),
+        (Some(Constant::Int(value)), _) => value == 0,
+        _ => true,
+    }
+}
+
+static FN_CACHE: Mutex<Option<FnCache>> = Mutex::new(None);
+
+/// Returns the first panic which can be reached from the body of the function `def_id`, if it has
+/// one.
+pub fn fn_may_panic(cx: &LateContext<'_>, def_id: LocalDefId, config: PanicConfig) -> Option<PanicSite> {
+    if let Some(cached) = FN_CACHE
+        .lock()
+        .unwrap()
+        .get_or_insert_with(FxHashMap::default)
+        .get(&(def_id, config))
+    {
+        return cached.clone();
+    }
+    // The lock isn't held while the body is analyzed, since it calls this function again.
+    let site = cx
+        .tcx
+        .hir()
+        .maybe_body_owned_by(def_id)
+        .and_then(|body_id| find_panic(cx, cx.tcx.typeck(def_id), cx.tcx.hir().body(body_id).value, config));
+    FN_CACHE
+        .lock()
+        .unwrap()
+        .get_or_insert_with(FxHashMap::default)
+        .insert((def_id, config), site.clone());
+    site
+}
+
+struct FindPanic<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    /// Only needed to look into the called functions
+    call_graph: Option<Rc<CallGraph>>,
+    config: PanicConfig,
+    /// Whether to stop at the first panic found
+    first_only: bool,
+    found: Vec<PanicSite>,
+}
+
+impl<'a, 'tcx> FindPanic<'a, 'tcx> {
+    fn panic_kind(&self, expr: &Expr<'_>) -> Option<PanicKind> {
+        if let Some(macro_call) = root_macro_call_first_node(self.cx, expr) {
+            if is_panic(self.cx, macro_call.def_id) {
+                return Some(PanicKind::Explicit);
+            }
+            match self.cx.tcx.get_diagnostic_name(macro_call.def_id) {
+                Some(sym::assert_macro | sym::assert_eq_macro | sym::assert_ne_macro) => {
+                    return Some(PanicKind::Assert);
+                },
+                Some(sym::unreachable_macro) => return Some(PanicKind::Unreachable),
+                Some(sym::todo_macro) => return Some(PanicKind::Todo),
+                Some(sym::unimplemented_macro) => return Some(PanicKind::Unimplemented),
+                _ => {},
+            }
+        }
+        // The panics of the code expanded from the other macros are considered to be up to them.
+        if expr.span.from_expansion() {
+            return None;
+        }
+        match expr.kind {
+            ExprKind::MethodCall(path, receiver, ..)
+                if matches!(path.ident.as_str(), "unwrap" | "expect") && self.is_fallible(receiver) =>
+            {
+                Some(PanicKind::Unwrap)
+            },
+            ExprKind::Index(base, index, _) if index_may_panic(self.cx, self.typeck_results, base, index) => {
+                Some(PanicKind::Indexing)
+            },
+            ExprKind::Binary(op, _, divisor) | ExprKind::AssignOp(op, _, divisor)
+                if matches!(op.node, BinOpKind::Div | BinOpKind::Rem)
+                    && division_may_panic(self.cx, self.typeck_results, divisor) =>
+            {
+                Some(PanicKind::Division)
+            },
+            _ => None,
+        }
+    }
+
+    fn param_env(&self) -> ty::ParamEnv<'tcx> {
+        self.cx.tcx.param_env(self.typeck_results.hir_owner)
+    }
+
+    /// Whether `receiver` is an `Option`, or a `Result` which can be an error.
+    fn is_fallible(&self, receiver: &Expr<'_>) -> bool {
+        let ty = self.typeck_results.expr_ty(receiver).peel_refs();
+        if is_type_diagnostic_item(self.cx, ty, sym::Option) {
+            return true;
+        }
+        is_type_diagnostic_item(self.cx, ty, sym::Result)
+            && match ty.kind() {
+                ty::Adt(_, args) => !args.type_at(1).is_privately_uninhabited(self.cx.tcx, self.param_env()),
+                _ => true,
+            }
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for FindPanic<'a, 'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'_>) {
+        if self.first_only && !self.found.is_empty() {
+            return;
+        }
+        if let Some(kind) = self.panic_kind(expr)
+            && self.config.includes(kind)
+        {
+            let macro_call = root_macro_call_first_node(self.cx, expr);
+            self.found.push(PanicSite {
+                kind,
+                span: macro_call.as_ref().map_or(expr.span, |macro_call| macro_call.span),
+                calls: Vec::new(),
+            });
+            if self.first_only || macro_call.is_some() {
+                return;
+            }
+        }
+        if let Some(call_graph) = &self.call_graph
+            && let Some(def_id) = call_graph.callee(expr.hir_id)
+        {
+            let config = PanicConfig {
+                depth: self.config.depth - 1,
+                ..self.config
+            };
+            if let Some(mut site) = fn_may_panic(self.cx, def_id, config) {
+                site.calls.insert(0, expr.span);
+                self.found.push(site);
+            }
+        }
+        walk_expr(self, expr);
+    }
+
+    // Panics in const blocks will cause compilation to fail.
+    fn visit_anon_const(&mut self, _: &'tcx AnonConst) {}
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.cx.tcx.hir()
+    }
+}
@@ -0,0 +1,28 @@
+//! Support for the external lint passes loaded from the `plugins` configuration.
+//!
+//! This is unstable: a plugin is a `dylib` crate which has to be built with the same toolchain as
+//! Clippy, and with the version of `clippy_utils` of that Clippy release.
+
+/// Declares the function which registers the lints and lint passes of a plugin, e.g.
+///
+/// ```ignore
+/// #![feature(rustc_private)]
+///
+/// extern crate rustc_lint;
+///
+/// clippy_utils::declare_clippy_plugin!(register);
+///
+/// fn register(store: &mut rustc_lint::LintStore) {
+///     store.register_lints(&[MY_LINT]);
+///     store.register_late_pass(|_| Box::new(MyLint));
+/// }
+/// ```
+#[macro_export]
+macro_rules! declare_clippy_plugin {
+    ($register:path) => {
+        #[no_mangle]
+        pub fn clippy_plugin_register(store: &mut rustc_lint::LintStore) {
+            $register(store);
+        }
+    };
+}
@@ -2,9 +2,11 @@
 
 #![allow(clippy::module_name_repetitions)]
 
+use crate::tokenize_with_text;
 use rustc_data_structures::sync::Lrc;
 use rustc_errors::Applicability;
 use rustc_hir::{BlockCheckMode, Expr, ExprKind, UnsafeSource};
+use rustc_lexer::TokenKind;
 use rustc_lint::{LateContext, LintContext};
 use rustc_session::Session;
 use rustc_span::source_map::{original_sp, SourceMap};
@@ -500,6 +502,97 @@ pub fn expand_past_previous_comma(cx: &LateContext<'_>, span: Span) -> Span {
     extended.with_lo(extended.lo() - BytePos(1))
 }
 
+/// Gets the parts of the source text of `span` which aren't covered by any of the `kept` spans,
+/// i.e. the code a suggestion replacing `span` with a rewrite of the `kept` parts would delete.
+/// The `kept` spans are walked up to the context of `span`; those which can't be, or which lie
+/// outside of `span`, are ignored.
+///
+/// Returns `None` if the source text isn't available.
+fn removed_source(cx: &impl LintContext, span: Span, kept: &[Span]) -> Option<Vec<String>> {
+    let src = get_source_text(cx, span)?;
+    let text = src.as_str()?;
+    let lo = span.lo();
+    let mut kept: Vec<Range<usize>> = kept
+        .iter()
+        .filter_map(|&sp| walk_span_to_context(sp, span.ctxt()))
+        .filter(|sp| span.contains(*sp))
+        .map(|sp| (sp.lo() - lo).to_usize()..(sp.hi() - lo).to_usize())
+        .collect();
+    kept.sort_by_key(|r| r.start);
+
+    let mut removed = Vec::with_capacity(kept.len() + 1);
+    let mut pos = 0;
+    for range in kept {
+        if range.start > pos {
+            removed.push(text.get(pos..range.start)?.to_owned());
+        }
+        pos = pos.max(range.end);
+    }
+    removed.push(text.get(pos..)?.to_owned());
+    Some(removed)
+}
+
+/// Gets the comments a suggestion replacing `span` with a rewrite of the `kept` spans would
+/// delete. Comments inside the `kept` spans are expected to be carried over by their snippets
+/// and are not included.
+///
+/// Returns `None` if the source text isn't available.
+pub fn removed_comments(cx: &impl LintContext, span: Span, kept: &[Span]) -> Option<Vec<String>> {
+    Some(
+        removed_source(cx, span, kept)?
+            .iter()
+            .flat_map(|text| tokenize_with_text(text))
+            .filter(|(t, _)| matches!(t, TokenKind::BlockComment { .. } | TokenKind::LineComment { .. }))
+            .map(|(_, s)| s.to_owned())
+            .collect(),
+    )
+}
+
+/// Checks if the code a suggestion replacing `span` with a rewrite of the `kept` spans would
+/// delete contains a `#[cfg(..)]` attribute. Whatever the attribute was placed on may have been
+/// stripped before linting, so a suggestion built from the HIR would silently drop it, or not
+/// compile, on other configurations.
+///
+/// Returns `true` if the source text isn't available.
+pub fn removed_source_contains_cfg(cx: &impl LintContext, span: Span, kept: &[Span]) -> bool {
+    removed_source(cx, span, kept).map_or(true, |removed| removed.iter().any(|text| text_contains_cfg(text)))
+}
+
+/// Checks if the text contains the token sequence [`#`, `[`, `cfg`], ignoring whitespace and
+/// comments between the tokens.
+pub(crate) fn text_contains_cfg(text: &str) -> bool {
+    let mut iter = tokenize_with_text(text);
+    while iter.any(|(t, _)| matches!(t, TokenKind::Pound)) {
+        let mut iter = iter.by_ref().skip_while(|(t, _)| {
+            matches!(
+                t,
+                TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. }
+            )
+        });
+        if matches!(iter.next(), Some((TokenKind::OpenBracket, _)))
+            && matches!(iter.next(), Some((TokenKind::Ident, "cfg")))
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Puts the comments before the replacement for `span`, each on its own line and indented like
+/// the first line of `span`. Meant to be used with [`removed_comments`] so that replacing code
+/// doesn't delete the comments attached to it.
+pub fn with_leading_comments<T: LintContext>(cx: &T, comments: &[String], replacement: &str, span: Span) -> String {
+    let indent = snippet_indent(cx, span).unwrap_or_default();
+    let mut res = String::new();
+    for comment in comments {
+        res.push_str(comment);
+        res.push('\n');
+        res.push_str(&indent);
+    }
+    res.push_str(replacement);
+    res
+}
+
 #[cfg(test)]
 mod test {
     use super::{reindent_multiline, without_block_comments};
@@ -289,6 +289,29 @@ pub fn to_camel_case(item_name: &str) -> String {
     s
 }
 
+/// Checks whether `text` matches `pattern`, where `*` matches any number of characters and `?`
+/// a single character.
+/// ```no_run
+/// use clippy_utils::str_utils::matches_wildcard;
+/// assert!(matches_wildcard("AWS_*", "AWS_SECRET_KEY"));
+/// assert!(matches_wildcard("KEY_?", "KEY_1"));
+/// assert!(!matches_wildcard("KEY_?", "KEY_12"));
+/// ```
+pub fn matches_wildcard(pattern: &str, text: &str) -> bool {
+    fn matches(pattern: &[char], text: &[char]) -> bool {
+        match pattern.split_first() {
+            None => text.is_empty(),
+            Some(('*', rest)) => (0..=text.len()).any(|i| matches(rest, &text[i..])),
+            Some(('?', rest)) => !text.is_empty() && matches(rest, &text[1..]),
+            Some((c, rest)) => text.first() == Some(c) && matches(rest, &text[1..]),
+        }
+    }
+
+    let pattern: Vec<_> = pattern.chars().collect();
+    let text: Vec<_> = text.chars().collect();
+    matches(&pattern, &text)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
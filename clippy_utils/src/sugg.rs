@@ -1,13 +1,13 @@
 //! Contains utility functions to generate suggestions.
 #![deny(clippy::missing_docs_in_private_items)]
 
-use crate::source::{snippet, snippet_opt, snippet_with_applicability, snippet_with_context};
+use crate::source::{snippet, snippet_opt, snippet_with_applicability, snippet_with_context, walk_span_to_context};
 use crate::ty::expr_sig;
-use crate::{get_parent_expr_for_hir, higher};
+use crate::{get_parent_expr, get_parent_expr_for_hir, higher};
 use rustc_ast::util::parser::AssocOp;
 use rustc_ast::{ast, token};
 use rustc_ast_pretty::pprust::token_kind_to_string;
-use rustc_errors::Applicability;
+use rustc_errors::{Applicability, SubdiagMessage};
 use rustc_hir as hir;
 use rustc_hir::{Closure, ExprKind, HirId, MutTy, TyKind};
 use rustc_hir_typeck::expr_use_visitor::{Delegate, ExprUseVisitor, PlaceBase, PlaceWithHirId};
@@ -16,7 +16,7 @@ use rustc_lint::{EarlyContext, LateContext, LintContext};
 use rustc_middle::hir::place::ProjectionKind;
 use rustc_middle::mir::{FakeReadCause, Mutability};
 use rustc_middle::ty;
-use rustc_span::{BytePos, CharPos, Pos, Span, SyntaxContext};
+use rustc_span::{BytePos, CharPos, DesugaringKind, Pos, Span, SyntaxContext};
 use std::borrow::Cow;
 use std::fmt::{self, Display, Write as _};
 use std::ops::{Add, Neg, Not, Sub};
@@ -538,31 +538,6 @@ pub fn make_unop(op: &str, expr: Sugg<'_>) -> Sugg<'static> {
 /// often confusing so
 /// parenthesis will always be added for a mix of these.
 pub fn make_assoc(op: AssocOp, lhs: &Sugg<'_>, rhs: &Sugg<'_>) -> Sugg<'static> {
-    /// Returns `true` if the operator is a shift operator `<<` or `>>`.
-    fn is_shift(op: AssocOp) -> bool {
-        matches!(op, AssocOp::ShiftLeft | AssocOp::ShiftRight)
-    }
-
-    /// Returns `true` if the operator is an arithmetic operator
-    /// (i.e., `+`, `-`, `*`, `/`, `%`).
-    fn is_arith(op: AssocOp) -> bool {
-        matches!(
-            op,
-            AssocOp::Add | AssocOp::Subtract | AssocOp::Multiply | AssocOp::Divide | AssocOp::Modulus
-        )
-    }
-
-    /// Returns `true` if the operator `op` needs parenthesis with the operator
-    /// `other` in the direction `dir`.
-    fn needs_paren(op: AssocOp, other: AssocOp, dir: Associativity) -> bool {
-        other.precedence() < op.precedence()
-            || (other.precedence() == op.precedence()
-                && ((op != other && associativity(op) != dir)
-                    || (op == other && associativity(op) != Associativity::Both)))
-            || is_shift(op) && is_arith(other)
-            || is_shift(other) && is_arith(op)
-    }
-
     let lhs_paren = if let Sugg::BinOp(lop, _, _) = *lhs {
         needs_paren(op, lop, Associativity::Left)
     } else {
@@ -580,6 +555,30 @@ pub fn make_assoc(op: AssocOp, lhs: &Sugg<'_>, rhs: &Sugg<'_>) -> Sugg<'static>
     Sugg::BinOp(op, lhs.into(), rhs.into())
 }
 
+/// Returns `true` if the operator is a shift operator `<<` or `>>`.
+fn is_shift(op: AssocOp) -> bool {
+    matches!(op, AssocOp::ShiftLeft | AssocOp::ShiftRight)
+}
+
+/// Returns `true` if the operator is an arithmetic operator
+/// (i.e., `+`, `-`, `*`, `/`, `%`).
+fn is_arith(op: AssocOp) -> bool {
+    matches!(
+        op,
+        AssocOp::Add | AssocOp::Subtract | AssocOp::Multiply | AssocOp::Divide | AssocOp::Modulus
+    )
+}
+
+/// Returns `true` if the operator `op` needs parenthesis with the operator
+/// `other` in the direction `dir`.
+fn needs_paren(op: AssocOp, other: AssocOp, dir: Associativity) -> bool {
+    other.precedence() < op.precedence()
+        || (other.precedence() == op.precedence()
+            && ((op != other && associativity(op) != dir) || (op == other && associativity(op) != Associativity::Both)))
+        || is_shift(op) && is_arith(other)
+        || is_shift(other) && is_arith(op)
+}
+
 /// Convenience wrapper around `make_assoc` and `AssocOp::from_ast_binop`.
 pub fn make_binop(op: ast::BinOpKind, lhs: &Sugg<'_>, rhs: &Sugg<'_>) -> Sugg<'static> {
     make_assoc(AssocOp::from_ast_binop(op), lhs, rhs)
@@ -792,6 +791,171 @@ impl<T: LintContext> DiagExt<T> for rustc_errors::Diag<'_, ()> {
     }
 }
 
+/// A builder of a suggestion made of several edits, possibly far apart, which are applied
+/// together: removals, insertions, replacements and parentheses.
+///
+/// The edits are made in the syntax context given to [`MultipartSugg::new`], usually the one of
+/// the linted expression. The spans of the macro calls in that context are walked up to the calls.
+/// If an edit would be inside of a macro expansion, or two edits overlap, no suggestion is made.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // `x.map(f).unwrap_or(a)` -> `x.map_or(a, f)`
+/// let mut sugg = MultipartSugg::new(cx, expr.span.ctxt());
+/// sugg.replace(map_name_span, "map_or")
+///     .insert_before(f.span, format!("{a}, "))
+///     .remove(map_call.span.between(expr.span.shrink_to_hi()));
+/// sugg.emit(diag, "use `map_or` instead", Applicability::MachineApplicable);
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct MultipartSugg<'a, 'tcx> {
+    /// The context used to look for the parent expressions.
+    cx: &'a LateContext<'tcx>,
+    /// The syntax context of the edits.
+    ctxt: SyntaxContext,
+    /// The edits made so far.
+    edits: Vec<(Span, String)>,
+    /// Whether an edit couldn't be made in the syntax context.
+    in_macro: bool,
+}
+
+impl<'a, 'tcx> MultipartSugg<'a, 'tcx> {
+    /// Starts a suggestion whose edits are made in the syntax context `ctxt`.
+    pub fn new(cx: &'a LateContext<'tcx>, ctxt: SyntaxContext) -> Self {
+        Self {
+            cx,
+            ctxt,
+            edits: Vec::new(),
+            in_macro: false,
+        }
+    }
+
+    /// Returns `span` walked up to the syntax context, or records that the suggestion can't be
+    /// made if it's in a macro expansion.
+    fn walk(&mut self, span: Span) -> Option<Span> {
+        match walk_span_to_context(span, self.ctxt) {
+            Some(span) if !span.from_expansion() => Some(span),
+            _ => {
+                self.in_macro = true;
+                None
+            },
+        }
+    }
+
+    /// Replaces the code of `span` with `text`.
+    pub fn replace(&mut self, span: Span, text: impl Into<String>) -> &mut Self {
+        if let Some(span) = self.walk(span) {
+            self.edits.push((span, text.into()));
+        }
+        self
+    }
+
+    /// Removes the code of `span`.
+    pub fn remove(&mut self, span: Span) -> &mut Self {
+        self.replace(span, String::new())
+    }
+
+    /// Inserts `text` before the code of `span`.
+    pub fn insert_before(&mut self, span: Span, text: impl Into<String>) -> &mut Self {
+        if let Some(span) = self.walk(span) {
+            self.edits.push((span.shrink_to_lo(), text.into()));
+        }
+        self
+    }
+
+    /// Inserts `text` after the code of `span`.
+    pub fn insert_after(&mut self, span: Span, text: impl Into<String>) -> &mut Self {
+        if let Some(span) = self.walk(span) {
+            self.edits.push((span.shrink_to_hi(), text.into()));
+        }
+        self
+    }
+
+    /// Wraps the code of `span` in parentheses.
+    pub fn wrap_in_parens(&mut self, span: Span) -> &mut Self {
+        self.insert_before(span, "(").insert_after(span, ")")
+    }
+
+    /// Replaces `expr` with `sugg`, in parentheses if the precedence of `sugg` is too low for the
+    /// position of `expr` in its parent expression.
+    pub fn replace_expr(&mut self, expr: &hir::Expr<'_>, sugg: Sugg<'_>) -> &mut Self {
+        let sugg = if needs_parens_in_parent(self.cx, expr, &sugg) {
+            sugg.maybe_par()
+        } else {
+            sugg
+        };
+        self.replace(expr.span, sugg.into_string())
+    }
+
+    /// Returns the edits, sorted by position, unless one of them is in a macro expansion or two
+    /// of them overlap.
+    pub fn build(self) -> Option<Vec<(Span, String)>> {
+        if self.in_macro || self.edits.is_empty() {
+            return None;
+        }
+        let mut edits = self.edits;
+        edits.sort_by_key(|(span, _)| (span.lo(), span.hi()));
+        edits.dedup();
+        if edits.windows(2).any(|pair| pair[0].0.hi() > pair[1].0.lo()) {
+            return None;
+        }
+        Some(edits)
+    }
+
+    /// Adds the suggestion to `diag`, if it can be made. Returns whether it was.
+    pub fn emit(
+        self,
+        diag: &mut rustc_errors::Diag<'_, ()>,
+        msg: impl Into<SubdiagMessage>,
+        applicability: Applicability,
+    ) -> bool {
+        if let Some(edits) = self.build() {
+            diag.multipart_suggestion(msg, edits, applicability);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Returns `true` if `sugg` needs parentheses to replace `expr` in its parent expression.
+fn needs_parens_in_parent(cx: &LateContext<'_>, expr: &hir::Expr<'_>, sugg: &Sugg<'_>) -> bool {
+    let Some(parent) = get_parent_expr(cx, expr) else {
+        return false;
+    };
+    let is_operand = |e: &hir::Expr<'_>| e.hir_id == expr.hir_id;
+    // `x?` and `x.await`
+    let is_postfix_desugaring =
+        parent.span.is_desugaring(DesugaringKind::QuestionMark) || parent.span.is_desugaring(DesugaringKind::Await);
+    match (sugg, parent.kind) {
+        (Sugg::NonParen(_), _) => false,
+        _ if is_postfix_desugaring => true,
+        // `x.y`, `x[y]`, `x()` and `x.y()`
+        (
+            _,
+            ExprKind::Field(base, _)
+            | ExprKind::Index(base, ..)
+            | ExprKind::Call(base, _)
+            | ExprKind::MethodCall(_, base, ..),
+        ) => is_operand(base),
+        (Sugg::BinOp(..), ExprKind::Unary(..) | ExprKind::AddrOf(..)) => true,
+        (&Sugg::BinOp(op, ..), ExprKind::Cast(..)) => needs_paren(AssocOp::As, op, Associativity::Left),
+        (&Sugg::BinOp(op, ..), ExprKind::Binary(parent_op, lhs, _)) => {
+            let dir = if is_operand(lhs) {
+                Associativity::Left
+            } else {
+                Associativity::Right
+            };
+            needs_paren(AssocOp::from_ast_binop(parent_op.node), op, dir)
+        },
+        (&Sugg::BinOp(op, ..), ExprKind::Assign(lhs, ..) | ExprKind::AssignOp(_, lhs, _)) => {
+            is_operand(lhs) || needs_paren(AssocOp::Assign, op, Associativity::Right)
+        },
+        _ => false,
+    }
+}
+
 /// Suggestion results for handling closure
 /// args dereferencing and borrowing
 pub struct DerefClosure {
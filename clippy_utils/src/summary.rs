@@ -0,0 +1,73 @@
+//! Counting of the warnings of each lint, for `cargo clippy --summary`.
+//!
+//! The diagnostics emitted through [`crate::diagnostics`] are counted, as well as the ones which
+//! are suppressed by `#[allow]`, `#[expect]` or `-A`. A pass registered after all the others
+//! reports the counts of the crate in a note starting with [`NOTE_PREFIX`], which `cargo clippy`
+//! collects from all the crates and prints as tables at the end of the run.
+
+use rustc_errors::{DiagInner, Level};
+use rustc_lint::{LateContext, LateLintPass, Lint, LintStore};
+use rustc_session::impl_lint_pass;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// The start of the note with the counts of a crate, followed by `lint=warnings/suppressed` for
+/// each counted lint, separated by spaces.
+pub const NOTE_PREFIX: &str = "clippy summary:";
+
+/// The number of warnings and of suppressed warnings of each lint by its lowercase name without the
+/// `clippy::` prefix, if the summary is enabled.
+static COUNTS: Mutex<Option<BTreeMap<String, (u64, u64)>>> = Mutex::new(None);
+
+/// Enables the counting. This has to be called after all the passes are registered.
+pub fn enable(store: &mut LintStore) {
+    *COUNTS.lock().unwrap() = Some(BTreeMap::new());
+    store.register_late_pass(|_| Box::new(Summary));
+}
+
+/// Counts a diagnostic emitted by one of the functions of [`crate::diagnostics`], or replayed by
+/// the lint cache.
+pub(crate) fn count(diag: &DiagInner, lint: &'static Lint) {
+    match diag.level() {
+        Level::Warning | Level::ForceWarning(_) | Level::Error => add(lint, 1, 0),
+        Level::Expect(_) => add(lint, 0, 1),
+        _ => {},
+    }
+}
+
+/// Counts a diagnostic which isn't emitted since its lint is allowed, unless the lint is allowed by
+/// default. `level` is only called if the summary is enabled.
+pub(crate) fn count_allowed(lint: &'static Lint, level: impl FnOnce() -> rustc_lint::Level) {
+    if lint.default_level != rustc_lint::Level::Allow
+        && COUNTS.lock().unwrap().is_some()
+        && level() == rustc_lint::Level::Allow
+    {
+        add(lint, 0, 1);
+    }
+}
+
+fn add(lint: &'static Lint, warnings: u64, suppressed: u64) {
+    if let Some(counts) = &mut *COUNTS.lock().unwrap() {
+        let name = lint.name_lower();
+        let name = name.strip_prefix("clippy::").unwrap_or(&name);
+        let count = counts.entry(name.to_string()).or_default();
+        count.0 += warnings;
+        count.1 += suppressed;
+    }
+}
+
+struct Summary;
+
+impl_lint_pass!(Summary => []);
+
+impl LateLintPass<'_> for Summary {
+    fn check_crate_post(&mut self, cx: &LateContext<'_>) {
+        let counts = COUNTS.lock().unwrap().replace(BTreeMap::new()).unwrap_or_default();
+        let mut note = NOTE_PREFIX.to_string();
+        for (name, (warnings, suppressed)) in counts {
+            let _ = write!(note, " {name}={warnings}/{suppressed}");
+        }
+        cx.tcx.dcx().note(note);
+    }
+}
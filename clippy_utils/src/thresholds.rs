@@ -0,0 +1,276 @@
+//! Resolution of the thresholds used by lints such as `too_many_lines`.
+//!
+//! A threshold is taken from, in order of precedence:
+//! 1. the nearest `#[clippy::limit(name = N)]` or `#[clippy::max_*(N)]` attribute on the node or
+//!    any of its parents,
+//! 2. the first `threshold-overrides` entry of `clippy.toml` whose path glob matches the source
+//!    file,
+//! 3. the global configuration value.
+
+use crate::attrs::parse_attrs;
+use crate::str_utils::matches_wildcard;
+use clippy_config::types::ThresholdOverride;
+use rustc_ast::{ast, LitKind};
+use rustc_hir::HirId;
+use rustc_lint::{LateContext, LintContext};
+use rustc_session::Session;
+use rustc_span::{sym, FileName, Span, Symbol};
+use std::path::{Component, PathBuf};
+use std::{env, slice};
+
+/// The kinds of thresholds which can be overridden.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThresholdKind {
+    CognitiveComplexity,
+    TooManyArguments,
+    TooManyLines,
+    TypeComplexity,
+    StructExcessiveBools,
+    FnParamsExcessiveBools,
+}
+
+impl ThresholdKind {
+    pub const ALL: [Self; 6] = [
+        Self::CognitiveComplexity,
+        Self::TooManyArguments,
+        Self::TooManyLines,
+        Self::TypeComplexity,
+        Self::StructExcessiveBools,
+        Self::FnParamsExcessiveBools,
+    ];
+
+    /// The name used for this threshold in `#[clippy::limit(..)]`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CognitiveComplexity => "cognitive_complexity",
+            Self::TooManyArguments => "too_many_arguments",
+            Self::TooManyLines => "too_many_lines",
+            Self::TypeComplexity => "type_complexity",
+            Self::StructExcessiveBools => "struct_excessive_bools",
+            Self::FnParamsExcessiveBools => "fn_params_excessive_bools",
+        }
+    }
+
+    /// The name of the attribute setting only this threshold, as in `#[clippy::max_lines(300)]`.
+    pub fn attr_name(self) -> &'static str {
+        match self {
+            Self::CognitiveComplexity => "max_cognitive_complexity",
+            Self::TooManyArguments => "max_args",
+            Self::TooManyLines => "max_lines",
+            Self::TypeComplexity => "max_type_complexity",
+            Self::StructExcessiveBools => "max_struct_bools",
+            Self::FnParamsExcessiveBools => "max_fn_params_bools",
+        }
+    }
+
+    fn override_value(self, conf: &ThresholdOverride) -> Option<u64> {
+        match self {
+            Self::CognitiveComplexity => conf.cognitive_complexity_threshold,
+            Self::TooManyArguments => conf.too_many_arguments_threshold,
+            Self::TooManyLines => conf.too_many_lines_threshold,
+            Self::TypeComplexity => conf.type_complexity_threshold,
+            Self::StructExcessiveBools => conf.max_struct_bools,
+            Self::FnParamsExcessiveBools => conf.max_fn_params_bools,
+        }
+    }
+}
+
+/// A threshold whose value may depend on where in the crate it is checked.
+pub struct Threshold {
+    kind: ThresholdKind,
+    default: u64,
+    /// The path globs, split into components, and the value which applies to matching files.
+    overrides: Vec<(Vec<String>, u64)>,
+    /// The directory override paths are relative to.
+    root: Option<PathBuf>,
+}
+
+impl Threshold {
+    pub fn new(kind: ThresholdKind, default: u64, overrides: &[ThresholdOverride]) -> Self {
+        let overrides: Vec<_> = overrides
+            .iter()
+            .filter_map(|conf| {
+                let value = kind.override_value(conf)?;
+                let glob = conf
+                    .path
+                    .split('/')
+                    .filter(|segment| !segment.is_empty() && *segment != ".")
+                    .map(String::from)
+                    .collect();
+                Some((glob, value))
+            })
+            .collect();
+        let root = if overrides.is_empty() {
+            None
+        } else {
+            env::var_os("CARGO_MANIFEST_DIR")
+                .map(PathBuf::from)
+                .or_else(|| env::current_dir().ok())
+        };
+        Self {
+            kind,
+            default,
+            overrides,
+            root,
+        }
+    }
+
+    /// Returns the threshold which applies to the node `hir_id`, whose source is at `span`.
+    pub fn resolve(&self, cx: &LateContext<'_>, hir_id: HirId, span: Span) -> u64 {
+        let hir = cx.tcx.hir();
+        std::iter::once(hir_id)
+            .chain(hir.parent_id_iter(hir_id))
+            .find_map(|id| self.attrs_value(hir.attrs(id)))
+            .or_else(|| self.path_value(cx, span))
+            .unwrap_or(self.default)
+    }
+
+    /// Returns the threshold set by the last attribute of `attrs` setting it. The malformed
+    /// attributes are skipped, [`check_attr`] reports them.
+    fn attrs_value(&self, attrs: &[ast::Attribute]) -> Option<u64> {
+        attrs
+            .iter()
+            .flat_map(|attr| parse_attr(attr, |_, _| {}))
+            .filter_map(|(kind, value)| (kind == self.kind).then_some(value))
+            .last()
+    }
+
+    fn path_value(&self, cx: &LateContext<'_>, span: Span) -> Option<u64> {
+        if self.overrides.is_empty() {
+            return None;
+        }
+        let FileName::Real(name) = cx.sess().source_map().span_to_filename(span) else {
+            return None;
+        };
+        let path = name.local_path()?;
+        let path = self
+            .root
+            .as_deref()
+            .and_then(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path);
+        let components: Vec<_> = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+        self.overrides
+            .iter()
+            .find(|(glob, _)| matches_glob(glob, &components))
+            .map(|&(_, value)| value)
+    }
+}
+
+/// Reports the errors of `attr` if it is a malformed threshold attribute.
+///
+/// This must be called once for each attribute of the crate, as resolving a [`Threshold`] reads
+/// the attributes of every parent of the checked nodes, without reporting anything.
+pub fn check_attr(sess: &Session, attr: &ast::Attribute) {
+    // Also reports the unknown and deprecated `clippy::` attributes, as `LimitStack` did when it
+    // was read by `cognitive_complexity` for every node.
+    parse_attrs(sess, slice::from_ref(attr), "cognitive_complexity", |_| {});
+    parse_attr(attr, |span, msg| {
+        sess.dcx().span_err(span, msg);
+    });
+}
+
+/// Returns the name of a `#[clippy::name ..]` attribute.
+fn clippy_attr_name(attr: &ast::Attribute) -> Option<Symbol> {
+    if let ast::AttrKind::Normal(normal) = &attr.kind
+        && let [tool, name] = &*normal.item.path.segments
+        && tool.ident.name == sym::clippy
+    {
+        Some(name.ident.name)
+    } else {
+        None
+    }
+}
+
+/// Returns the thresholds set by `attr`, calling `err` with the span and message of each
+/// malformed part of it.
+fn parse_attr(attr: &ast::Attribute, mut err: impl FnMut(Span, String)) -> Vec<(ThresholdKind, u64)> {
+    let Some(name) = clippy_attr_name(attr) else {
+        return Vec::new();
+    };
+    let name = name.as_str();
+    if name == "cognitive_complexity" {
+        // `#[clippy::cognitive_complexity = "N"]` predates `#[clippy::limit(..)]`, `parse_attrs`
+        // reports it.
+        attr.value_str()
+            .and_then(|value| value.as_str().parse().ok())
+            .map(|value| vec![(ThresholdKind::CognitiveComplexity, value)])
+            .unwrap_or_default()
+    } else if name == "limit" {
+        let Some(items) = attr.meta_item_list() else {
+            err(attr.span, "bad clippy attribute".into());
+            return Vec::new();
+        };
+        items
+            .iter()
+            .filter_map(|item| {
+                if let Some(meta) = item.meta_item()
+                    && let Some(ident) = meta.ident()
+                    && let Some(kind) = ThresholdKind::ALL
+                        .into_iter()
+                        .find(|kind| ident.name.as_str() == kind.name())
+                    && let Some(lit) = meta.name_value_literal()
+                    && let LitKind::Int(value, _) = lit.kind
+                    && let Ok(value) = u64::try_from(value.get())
+                {
+                    Some((kind, value))
+                } else {
+                    err(item.span(), "expected `name = N` with a known limit name".into());
+                    None
+                }
+            })
+            .collect()
+    } else if let Some(kind) = ThresholdKind::ALL.into_iter().find(|kind| name == kind.attr_name()) {
+        if let Some([item]) = attr.meta_item_list().as_deref()
+            && let Some(lit) = item.lit()
+            && let LitKind::Int(value, _) = lit.kind
+            && let Ok(value) = u64::try_from(value.get())
+        {
+            vec![(kind, value)]
+        } else {
+            err(attr.span, format!("expected `#[clippy::{name}(N)]`"));
+            Vec::new()
+        }
+    } else {
+        Vec::new()
+    }
+}
+
+/// Checks whether the path components match the glob components. `**` matches any number of
+/// components, `*` any number of characters within a component and `?` a single character.
+fn matches_glob(glob: &[String], path: &[&str]) -> bool {
+    match glob.split_first() {
+        None => path.is_empty(),
+        Some((segment, rest)) if segment == "**" => (0..=path.len()).any(|i| matches_glob(rest, &path[i..])),
+        Some((segment, rest)) => path
+            .split_first()
+            .is_some_and(|(component, path)| matches_wildcard(segment, component) && matches_glob(rest, path)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::matches_glob;
+
+    fn matches(glob: &str, path: &str) -> bool {
+        let glob: Vec<_> = glob.split('/').map(String::from).collect();
+        let path: Vec<_> = path.split('/').collect();
+        matches_glob(&glob, &path)
+    }
+
+    #[test]
+    fn glob() {
+        assert!(matches("src/parser/**", "src/parser/mod.rs"));
+        assert!(matches("src/parser/**", "src/parser/expr/binary.rs"));
+        assert!(!matches("src/parser/**", "src/lexer.rs"));
+        assert!(matches("src/*.rs", "src/lib.rs"));
+        assert!(!matches("src/*.rs", "src/parser/mod.rs"));
+        assert!(matches("**/generated_?.rs", "src/a/generated_1.rs"));
+        assert!(!matches("**/generated_?.rs", "src/a/generated_12.rs"));
+    }
+}
@@ -8,11 +8,11 @@ use rustc_ast::ast::Mutability;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorKind, CtorOf, DefKind, Res};
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, DefIdSet};
 use rustc_hir::{Expr, FnDecl, LangItem, TyKind, Unsafety};
 use rustc_infer::infer::type_variable::TypeVariableOrigin;
 use rustc_infer::infer::TyCtxtInferExt;
-use rustc_lint::LateContext;
+use rustc_lint::{LateContext, LintContext};
 use rustc_middle::mir::interpret::Scalar;
 use rustc_middle::mir::ConstValue;
 use rustc_middle::traits::EvaluationResult;
@@ -32,7 +32,8 @@ use std::assert_matches::debug_assert_matches;
 use std::collections::hash_map::Entry;
 use std::iter;
 
-use crate::{def_path_def_ids, match_def_path, path_res};
+use crate::attrs::get_attr;
+use crate::{def_path_def_ids, match_def_path, path_res, paths};
 
 mod type_certainty;
 pub use type_certainty::expr_type_is_certain;
@@ -538,6 +539,133 @@ pub fn needs_ordered_drop<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> bool {
     needs_ordered_drop_inner(cx, ty, &mut FxHashSet::default())
 }
 
+/// Why dropping a value has effects other code can observe, as opposed to only freeing memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignificantDropKind {
+    /// Unlocks a `Mutex` or `RwLock`.
+    Lock,
+    /// Ends a borrow of a `RefCell`.
+    RefCellBorrow,
+    /// Closes a file.
+    File,
+    /// Disconnects one end of a channel.
+    Channel,
+    /// Detaches a thread.
+    JoinHandle,
+    /// The type is marked with `#[clippy::has_significant_drop]`.
+    Attribute,
+    /// The type is listed in the `significant-drop-types` configuration.
+    Configured,
+}
+
+impl SignificantDropKind {
+    /// Describes a value of this kind, for use in diagnostics.
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::Lock => "a lock guard",
+            Self::RefCellBorrow => "a `RefCell` borrow",
+            Self::File => "a file",
+            Self::Channel => "a channel endpoint",
+            Self::JoinHandle => "a thread handle",
+            Self::Attribute | Self::Configured => "a type with a significant drop",
+        }
+    }
+}
+
+/// A type whose drop has observable effects, found by [`significant_drop`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignificantDrop {
+    pub kind: SignificantDropKind,
+    /// The ADT with the significant drop. This is either the type given, or a type it contains.
+    pub def_id: DefId,
+}
+
+/// Classifies an ADT whose own drop has observable effects. `configured` holds the types resolved
+/// from the `significant-drop-types` configuration.
+///
+/// Unlike [`significant_drop`], this doesn't look at the fields or generic arguments of the type.
+pub fn own_significant_drop_kind(
+    cx: &LateContext<'_>,
+    did: DefId,
+    configured: &DefIdSet,
+) -> Option<SignificantDropKind> {
+    let kind = match cx.tcx.get_diagnostic_name(did) {
+        Some(sym::MutexGuard | sym::RwLockReadGuard | sym::RwLockWriteGuard) => SignificantDropKind::Lock,
+        Some(sym::RefCellRef | sym::RefCellRefMut) => SignificantDropKind::RefCellBorrow,
+        Some(sym::File) => SignificantDropKind::File,
+        Some(sym::Receiver) => SignificantDropKind::Channel,
+        _ if match_def_path(cx, did, &paths::PARKING_LOT_MUTEX_GUARD)
+            || match_def_path(cx, did, &paths::PARKING_LOT_RWLOCK_READ_GUARD)
+            || match_def_path(cx, did, &paths::PARKING_LOT_RWLOCK_WRITE_GUARD) =>
+        {
+            SignificantDropKind::Lock
+        },
+        _ if match_def_path(cx, did, &paths::MPSC_SENDER) || match_def_path(cx, did, &paths::MPSC_SYNC_SENDER) => {
+            SignificantDropKind::Channel
+        },
+        _ if match_def_path(cx, did, &paths::THREAD_JOIN_HANDLE) => SignificantDropKind::JoinHandle,
+        _ if has_significant_drop_attr(cx, did) => SignificantDropKind::Attribute,
+        _ if configured.contains(&did) => SignificantDropKind::Configured,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// Checks if the type is marked with `#[clippy::has_significant_drop]`.
+pub fn has_significant_drop_attr(cx: &LateContext<'_>, did: DefId) -> bool {
+    get_attr(cx.sess(), cx.tcx.get_attrs_unchecked(did), "has_significant_drop")
+        .next()
+        .is_some()
+}
+
+/// Finds a type with an observable drop, such as a lock guard, a file or a channel, which
+/// dropping a value of the given type drops. The fields and generic arguments of ADTs are
+/// searched, but not the targets of references. `configured` holds the types resolved from the
+/// `significant-drop-types` configuration.
+pub fn significant_drop<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>, configured: &DefIdSet) -> Option<SignificantDrop> {
+    fn f<'tcx>(
+        cx: &LateContext<'tcx>,
+        ty: Ty<'tcx>,
+        configured: &DefIdSet,
+        seen: &mut FxHashSet<Ty<'tcx>>,
+    ) -> Option<SignificantDrop> {
+        if !seen.insert(ty) {
+            return None;
+        }
+        match *ty.kind() {
+            ty::Adt(adt, args) => {
+                if let Some(kind) = own_significant_drop_kind(cx, adt.did(), configured) {
+                    return Some(SignificantDrop {
+                        kind,
+                        def_id: adt.did(),
+                    });
+                }
+                adt.all_fields()
+                    .map(|field| field.ty(cx.tcx, args))
+                    .chain(args.types())
+                    .find_map(|ty| f(cx, ty, configured, seen))
+            },
+            ty::Tuple(tys) => tys.iter().find_map(|ty| f(cx, ty, configured, seen)),
+            ty::Array(ty, _) | ty::Slice(ty) => f(cx, ty, configured, seen),
+            _ => None,
+        }
+    }
+    f(cx, ty, configured, &mut FxHashSet::default())
+}
+
+/// Checks if dropping a value of the type has effects which other code can observe, e.g.
+/// releasing a lock, closing a file or disconnecting a channel. See [`significant_drop`].
+pub fn has_significant_drop_effects<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>, configured: &DefIdSet) -> bool {
+    significant_drop(cx, ty, configured).is_some()
+}
+
+/// Checks if the type contains a lifetime other than `'static`, i.e. if a value of the type may
+/// hold a borrow. Note the lifetimes in the types of the typeck results are all erased.
+pub fn contains_non_static_region(ty: Ty<'_>) -> bool {
+    ty.walk()
+        .any(|arg| matches!(arg.unpack(), GenericArgKind::Lifetime(r) if !r.is_static()))
+}
+
 /// Peels off all references on the type. Returns the underlying type and the number of references
 /// removed.
 pub fn peel_mid_ty_refs(ty: Ty<'_>) -> (Ty<'_>, usize) {
@@ -971,7 +1099,7 @@ impl AdtVariantInfo {
                     .fields
                     .iter()
                     .enumerate()
-                    .map(|(i, f)| (i, approx_ty_size(cx, f.ty(cx.tcx, subst))))
+                    .map(|(i, f)| (i, layout_of_or_estimate(cx, f.ty(cx.tcx, subst)).size))
                     .collect::<Vec<_>>();
                 fields_size.sort_by(|(_, a_size), (_, b_size)| (a_size.cmp(b_size)));
 
@@ -1015,56 +1143,116 @@ pub fn adt_and_variant_of_res<'tcx>(cx: &LateContext<'tcx>, res: Res) -> Option<
     }
 }
 
-/// Comes up with an "at least" guesstimate for the type's size, not taking into
-/// account the layout of type parameters.
-pub fn approx_ty_size<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> u64 {
+/// The size, alignment and niche of a type, as computed by [`layout_of_or_estimate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LayoutEstimate {
+    /// The size in bytes.
+    pub size: u64,
+    /// The alignment in bytes.
+    pub align: u64,
+    /// Whether the type has invalid bit patterns which an enum can use for its discriminant, so
+    /// that e.g. `Option<T>` has the size of `T`.
+    pub has_niche: bool,
+    /// Whether this is the layout computed by the compiler. Otherwise, `size` and `align` are
+    /// lower bounds and `has_niche` is only `true` if every instance of the type has a niche.
+    pub exact: bool,
+}
+
+impl LayoutEstimate {
+    const UNKNOWN: Self = Self {
+        size: 0,
+        align: 1,
+        has_niche: false,
+        exact: false,
+    };
+}
+
+/// Returns the layout of the type, or an estimate if it can't be computed because the type
+/// depends on generic parameters.
+///
+/// The estimate is a lower bound which counts the parts of the type which are known: the fields of
+/// tuples, structs and enum variants, rounded up to their alignment, and arrays of a known length.
+/// Type parameters, projections and enum discriminants count as zero bytes aligned to one byte.
+pub fn layout_of_or_estimate<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> LayoutEstimate {
     use rustc_middle::ty::layout::LayoutOf;
     if !is_normalizable(cx, cx.param_env, ty) {
-        return 0;
+        return LayoutEstimate::UNKNOWN;
+    }
+    if let Ok(layout) = cx.layout_of(ty) {
+        return LayoutEstimate {
+            size: layout.size.bytes(),
+            align: layout.align.abi.bytes(),
+            has_niche: layout.largest_niche.is_some(),
+            exact: true,
+        };
     }
-    match (cx.layout_of(ty).map(|layout| layout.size.bytes()), ty.kind()) {
-        (Ok(size), _) => size,
-        (Err(_), ty::Tuple(list)) => list.iter().map(|t| approx_ty_size(cx, t)).sum(),
-        (Err(_), ty::Array(t, n)) => {
-            n.try_eval_target_usize(cx.tcx, cx.param_env).unwrap_or_default() * approx_ty_size(cx, *t)
+    match ty.kind() {
+        ty::Tuple(list) => estimate_fields(list.iter().map(|t| layout_of_or_estimate(cx, t))),
+        ty::Array(t, n) => {
+            let Some(len) = n.try_eval_target_usize(cx.tcx, cx.param_env) else {
+                return LayoutEstimate::UNKNOWN;
+            };
+            let element = layout_of_or_estimate(cx, *t);
+            LayoutEstimate {
+                size: element.size.saturating_mul(len),
+                align: element.align,
+                has_niche: len > 0 && element.has_niche,
+                exact: false,
+            }
         },
-        (Err(_), ty::Adt(def, subst)) if def.is_struct() => def
-            .variants()
-            .iter()
-            .map(|v| {
-                v.fields
-                    .iter()
-                    .map(|field| approx_ty_size(cx, field.ty(cx.tcx, subst)))
-                    .sum::<u64>()
-            })
-            .sum(),
-        (Err(_), ty::Adt(def, subst)) if def.is_enum() => def
-            .variants()
-            .iter()
-            .map(|v| {
-                v.fields
-                    .iter()
-                    .map(|field| approx_ty_size(cx, field.ty(cx.tcx, subst)))
-                    .sum::<u64>()
-            })
-            .max()
-            .unwrap_or_default(),
-        (Err(_), ty::Adt(def, subst)) if def.is_union() => def
-            .variants()
-            .iter()
-            .map(|v| {
-                v.fields
-                    .iter()
-                    .map(|field| approx_ty_size(cx, field.ty(cx.tcx, subst)))
-                    .max()
-                    .unwrap_or_default()
-            })
-            .max()
-            .unwrap_or_default(),
-        (Err(_), _) => 0,
+        ty::Adt(def, subst) if def.is_struct() => estimate_fields(
+            def.non_enum_variant()
+                .fields
+                .iter()
+                .map(|field| layout_of_or_estimate(cx, field.ty(cx.tcx, subst))),
+        ),
+        ty::Adt(def, subst) if def.is_enum() => {
+            let variants = def.variants().iter().map(|v| {
+                estimate_fields(
+                    v.fields
+                        .iter()
+                        .map(|field| layout_of_or_estimate(cx, field.ty(cx.tcx, subst))),
+                )
+            });
+            estimate_alternatives(variants)
+        },
+        ty::Adt(def, subst) if def.is_union() => estimate_alternatives(
+            def.non_enum_variant()
+                .fields
+                .iter()
+                .map(|field| layout_of_or_estimate(cx, field.ty(cx.tcx, subst))),
+        ),
+        _ => LayoutEstimate::UNKNOWN,
+    }
+}
+
+/// Estimates the layout of fields laid out one after the other. The size of a type is a multiple
+/// of its alignment, which is at least the one of each field.
+fn estimate_fields(fields: impl Iterator<Item = LayoutEstimate>) -> LayoutEstimate {
+    let (size, align, has_niche) = fields.fold((0u64, 1, false), |(size, align, has_niche), field| {
+        (
+            size.saturating_add(field.size),
+            align.max(field.align),
+            has_niche || field.has_niche,
+        )
+    });
+    LayoutEstimate {
+        size: size.div_ceil(align).saturating_mul(align),
+        align,
+        has_niche,
+        exact: false,
     }
 }
 
+/// Estimates the layout of overlapping alternatives, i.e. enum variants or union fields.
+fn estimate_alternatives(alternatives: impl Iterator<Item = LayoutEstimate>) -> LayoutEstimate {
+    alternatives.fold(LayoutEstimate::UNKNOWN, |acc, alternative| LayoutEstimate {
+        size: acc.size.max(alternative.size),
+        align: acc.align.max(alternative.align),
+        ..acc
+    })
+}
+
 /// Asserts that the given arguments match the generic parameters of the given item.
 #[allow(dead_code)]
 fn assert_generic_args_match<'tcx>(tcx: TyCtxt<'tcx>, did: DefId, args: &[GenericArg<'tcx>]) {
@@ -1,3 +1,4 @@
+use crate::ty::contains_non_static_region;
 use crate::visitors::{for_each_expr, for_each_expr_with_closures, Descend, Visitable};
 use crate::{self as utils, get_enclosing_loop_or_multi_call_closure};
 use core::ops::ControlFlow;
@@ -9,7 +10,8 @@ use rustc_infer::infer::TyCtxtInferExt;
 use rustc_lint::LateContext;
 use rustc_middle::hir::nested_filter;
 use rustc_middle::mir::FakeReadCause;
-use rustc_middle::ty;
+use rustc_middle::ty::{self, GenericArg, GenericArgKind};
+use std::iter;
 
 /// Returns a set of mutated local variable IDs, or `None` if mutations could not be determined.
 pub fn mutated_variables<'tcx>(expr: &'tcx Expr<'_>, cx: &LateContext<'tcx>) -> Option<HirIdSet> {
@@ -208,3 +210,216 @@ pub fn local_used_after_expr(cx: &LateContext<'_>, local_id: HirId, after: &Expr
     })
     .is_some()
 }
+
+/// How a value borrowing from the current function escapes it, see [`borrow_escape`].
+#[derive(Clone, Copy, Debug)]
+pub enum BorrowEscape<'tcx> {
+    /// The value is returned, from the function or from a closure, or yielded. Holds the returned
+    /// expression.
+    Returned(&'tcx Expr<'tcx>),
+    /// The value is stored in a place which isn't a local, or passed to a function alongside a
+    /// mutable reference it could be stored in. Holds the assignment or the call.
+    Stored(&'tcx Expr<'tcx>),
+    /// The value is captured by a closure, or an `async` block, which escapes itself. Holds the
+    /// closure.
+    Captured(&'tcx Expr<'tcx>),
+    /// The value is used in a way which isn't understood. Holds the expression whose value is used.
+    Unknown(&'tcx Expr<'tcx>),
+}
+
+/// Checks if the value of the expression, e.g. a reference or an iterator borrowing a local, can
+/// outlive the function it's created in, i.e. whether it's returned, stored somewhere other than a
+/// local or captured by a closure which does either.
+///
+/// The value is followed through locals, through the values built from it and through the results
+/// of the calls it's passed to when the signature of the callee allows them to borrow from it. The
+/// type of the expression itself isn't checked, so this can also be used to find whether a value
+/// which doesn't borrow yet would escape if it was replaced by one which does.
+///
+/// This is conservative: an escape is returned whenever the value might outlive the function.
+pub fn borrow_escape<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> Option<BorrowEscape<'tcx>> {
+    BorrowEscapeFinder {
+        cx,
+        seen: HirIdSet::default(),
+    }
+    .escape(e)
+}
+
+struct BorrowEscapeFinder<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    /// The locals and closures which were already checked.
+    seen: HirIdSet,
+}
+
+impl<'tcx> BorrowEscapeFinder<'_, 'tcx> {
+    fn escape(&mut self, mut e: &'tcx Expr<'tcx>) -> Option<BorrowEscape<'tcx>> {
+        let typeck = self.cx.typeck_results();
+        loop {
+            let Some(ControlFlow::Continue((parent, child_id))) =
+                utils::walk_to_expr_usage(self.cx, e, |_, _, _| ControlFlow::<!>::Continue(()))
+            else {
+                return None;
+            };
+            if let hir::Node::Expr(child) = self.cx.tcx.hir_node(child_id) {
+                e = child;
+            }
+            let next = match parent {
+                hir::Node::Expr(parent) => match parent.kind {
+                    ExprKind::Ret(_) | ExprKind::Yield(..) | ExprKind::Closure(_) => {
+                        return Some(BorrowEscape::Returned(e));
+                    },
+                    ExprKind::AddrOf(..)
+                    | ExprKind::Array(_)
+                    | ExprKind::Tup(_)
+                    | ExprKind::Struct(..)
+                    | ExprKind::Repeat(..) => parent,
+                    ExprKind::Call(callee, args) => {
+                        if callee.hir_id == e.hir_id {
+                            parent
+                        } else {
+                            let index = args.iter().position(|arg| arg.hir_id == e.hir_id)?;
+                            match self.call_arg(parent, args.iter(), index) {
+                                ControlFlow::Continue(call) => call,
+                                ControlFlow::Break(escape) => return escape,
+                            }
+                        }
+                    },
+                    ExprKind::MethodCall(_, receiver, args, _) => {
+                        let index = args.iter().position(|arg| arg.hir_id == e.hir_id).map_or(0, |i| i + 1);
+                        match self.call_arg(parent, iter::once(receiver).chain(args), index) {
+                            ControlFlow::Continue(call) => call,
+                            ControlFlow::Break(escape) => return escape,
+                        }
+                    },
+                    ExprKind::Match(_, arms, _) => {
+                        return arms.iter().find_map(|arm| self.pat_escape(arm.pat));
+                    },
+                    ExprKind::Let(let_expr) => return self.pat_escape(let_expr.pat),
+                    ExprKind::Assign(lhs, ..) | ExprKind::AssignOp(_, lhs, _) if lhs.hir_id == e.hir_id => {
+                        return None;
+                    },
+                    ExprKind::AssignOp(_, lhs, _) if !contains_non_static_region(typeck.expr_ty(lhs)) => return None,
+                    ExprKind::Assign(lhs, ..) | ExprKind::AssignOp(_, lhs, _) => {
+                        return match utils::path_to_local(lhs) {
+                            Some(id) => self.local_escape(id),
+                            None => Some(BorrowEscape::Stored(parent)),
+                        };
+                    },
+                    ExprKind::InlineAsm(_) => return Some(BorrowEscape::Unknown(e)),
+                    _ if contains_non_static_region(typeck.expr_ty(parent)) => parent,
+                    _ => return None,
+                },
+                hir::Node::ExprField(field) => match self.cx.tcx.parent_hir_node(field.hir_id) {
+                    hir::Node::Expr(parent) => parent,
+                    _ => return Some(BorrowEscape::Unknown(e)),
+                },
+                hir::Node::LetStmt(local) => return self.pat_escape(local.pat),
+                hir::Node::Stmt(_) | hir::Node::Arm(_) => return None,
+                hir::Node::Item(_) | hir::Node::ImplItem(_) | hir::Node::TraitItem(_) => {
+                    return Some(BorrowEscape::Returned(e));
+                },
+                _ => return Some(BorrowEscape::Unknown(e)),
+            };
+            e = next;
+        }
+    }
+
+    /// Checks the use of a value passed to a call as its `index`th argument, counting the receiver
+    /// of a method call as the first one. Continues with the call if its result may borrow from the
+    /// value.
+    fn call_arg(
+        &self,
+        call: &'tcx Expr<'tcx>,
+        args: impl Iterator<Item = &'tcx Expr<'tcx>>,
+        index: usize,
+    ) -> ControlFlow<Option<BorrowEscape<'tcx>>, &'tcx Expr<'tcx>> {
+        let typeck = self.cx.typeck_results();
+        // The value could be stored in the place another argument mutably borrows.
+        let stored = args.enumerate().any(|(i, arg)| {
+            i != index
+                && match *typeck.expr_ty_adjusted(arg).kind() {
+                    ty::Ref(_, pointee, mutbl) => {
+                        contains_non_static_region(pointee)
+                            && (mutbl.is_mut() || !pointee.is_freeze(self.cx.tcx, self.cx.param_env))
+                    },
+                    _ => false,
+                }
+        });
+        if stored {
+            return ControlFlow::Break(Some(BorrowEscape::Stored(call)));
+        }
+
+        let result_borrows = match utils::fn_def_id(self.cx, call) {
+            Some(def_id) => {
+                let sig = self.cx.tcx.fn_sig(def_id).instantiate_identity().skip_binder();
+                sig.inputs().get(index).map_or(true, |&input| {
+                    let params: Vec<_> = input.walk().filter(|&arg| is_param(arg)).collect();
+                    sig.output().walk().any(|arg| params.contains(&arg))
+                })
+            },
+            None => true,
+        };
+        if result_borrows || contains_non_static_region(typeck.expr_ty(call)) {
+            ControlFlow::Continue(call)
+        } else {
+            ControlFlow::Break(None)
+        }
+    }
+
+    /// Checks the bindings of a pattern matched against the value. A binding which doesn't bind the
+    /// whole value is only checked if its type can borrow.
+    fn pat_escape(&mut self, pat: &'tcx hir::Pat<'tcx>) -> Option<BorrowEscape<'tcx>> {
+        if let hir::PatKind::Binding(_, id, _, None) = pat.kind {
+            return self.local_escape(id);
+        }
+        let typeck = self.cx.typeck_results();
+        let mut ids = Vec::new();
+        pat.each_binding_or_first(&mut |_, id, _, _| {
+            if contains_non_static_region(typeck.node_type(id)) {
+                ids.push(id);
+            }
+        });
+        ids.into_iter().find_map(|id| self.local_escape(id))
+    }
+
+    /// Checks each use of a local holding the value, and the closures capturing it.
+    fn local_escape(&mut self, id: HirId) -> Option<BorrowEscape<'tcx>> {
+        if !self.seen.insert(id) {
+            return None;
+        }
+        let hir = self.cx.tcx.hir();
+        let body = hir.body(hir.body_owned_by(hir.enclosing_body_owner(id)));
+        let mut uses = Vec::new();
+        for_each_expr_with_closures(self.cx, body.value, |e| {
+            if utils::path_to_local_id(e, id) {
+                uses.push(e);
+            }
+            ControlFlow::<!>::Continue(())
+        });
+        uses.into_iter().find_map(|e| {
+            let captured = hir
+                .parent_iter(e.hir_id)
+                .take_while(|&(id, _)| id != body.value.hir_id)
+                .find_map(|(_, node)| match node {
+                    hir::Node::Expr(
+                        closure @ Expr {
+                            kind: ExprKind::Closure(_),
+                            ..
+                        },
+                    ) if self.seen.insert(closure.hir_id) && self.escape(closure).is_some() => {
+                        Some(BorrowEscape::Captured(closure))
+                    },
+                    _ => None,
+                });
+            captured.or_else(|| self.escape(e))
+        })
+    }
+}
+
+fn is_param(arg: GenericArg<'_>) -> bool {
+    match arg.unpack() {
+        GenericArgKind::Type(ty) => matches!(ty.kind(), ty::Param(_)),
+        GenericArgKind::Lifetime(r) => matches!(r.kind(), ty::ReEarlyParam(_) | ty::ReBound(..)),
+        GenericArgKind::Const(_) => false,
+    }
+}
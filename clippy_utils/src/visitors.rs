@@ -13,6 +13,7 @@ use rustc_middle::hir::nested_filter;
 use rustc_middle::ty::adjustment::Adjust;
 use rustc_middle::ty::{self, Ty, TyCtxt, TypeckResults};
 use rustc_span::Span;
+use std::mem;
 
 mod internal {
     /// Trait for visitor functions to control whether or not to descend to child nodes. Implemented
@@ -179,6 +180,68 @@ pub fn for_each_expr_with_closures<'tcx, B, C: Continue>(
     v.res
 }
 
+/// Calls the given function once for each expression contained, with its type and the typeck
+/// results of the body containing it. This will enter closures, inline constants and the other
+/// nested bodies, switching to their typeck results, but not nested items.
+///
+/// `typeck_results` are the ones of the body containing `node`.
+pub fn for_each_typed_expr<'tcx, B, C: Continue>(
+    cx: &LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    node: impl Visitable<'tcx>,
+    f: impl FnMut(&'tcx Expr<'tcx>, Ty<'tcx>, &'tcx TypeckResults<'tcx>) -> ControlFlow<B, C>,
+) -> Option<B> {
+    struct V<'tcx, B, F> {
+        tcx: TyCtxt<'tcx>,
+        typeck_results: &'tcx TypeckResults<'tcx>,
+        f: F,
+        res: Option<B>,
+    }
+    impl<'tcx, B, C: Continue, F: FnMut(&'tcx Expr<'tcx>, Ty<'tcx>, &'tcx TypeckResults<'tcx>) -> ControlFlow<B, C>>
+        Visitor<'tcx> for V<'tcx, B, F>
+    {
+        type NestedFilter = nested_filter::OnlyBodies;
+        fn nested_visit_map(&mut self) -> Self::Map {
+            self.tcx.hir()
+        }
+
+        fn visit_nested_body(&mut self, body_id: BodyId) {
+            if self.res.is_some() {
+                return;
+            }
+            let typeck_results = mem::replace(&mut self.typeck_results, self.tcx.typeck_body(body_id));
+            self.visit_body(self.tcx.hir().body(body_id));
+            self.typeck_results = typeck_results;
+        }
+
+        fn visit_expr(&mut self, e: &'tcx Expr<'tcx>) {
+            if self.res.is_some() {
+                return;
+            }
+            match (self.f)(e, self.typeck_results.expr_ty(e), self.typeck_results) {
+                ControlFlow::Continue(c) if c.descend() => walk_expr(self, e),
+                ControlFlow::Break(b) => self.res = Some(b),
+                ControlFlow::Continue(_) => (),
+            }
+        }
+
+        // Avoid unnecessary `walk_*` calls.
+        fn visit_ty(&mut self, _: &'tcx hir::Ty<'tcx>) {}
+        fn visit_pat(&mut self, _: &'tcx Pat<'tcx>) {}
+        fn visit_qpath(&mut self, _: &'tcx QPath<'tcx>, _: HirId, _: Span) {}
+        // Avoid monomorphising all `visit_*` functions.
+        fn visit_nested_item(&mut self, _: ItemId) {}
+    }
+    let mut v = V {
+        tcx: cx.tcx,
+        typeck_results,
+        f,
+        res: None,
+    };
+    node.visit(&mut v);
+    v.res
+}
+
 /// returns `true` if expr contains match expr desugared from try
 fn contains_try(expr: &Expr<'_>) -> bool {
     for_each_expr(expr, |e| {
@@ -208,7 +271,7 @@ where
 
     impl<F> RetFinder<F> {
         fn inside_stmt(&mut self, in_stmt: bool) -> WithStmtGuard<'_, F> {
-            let prev_in_stmt = std::mem::replace(&mut self.in_stmt, in_stmt);
+            let prev_in_stmt = mem::replace(&mut self.in_stmt, in_stmt);
             WithStmtGuard {
                 val: self,
                 prev_in_stmt,
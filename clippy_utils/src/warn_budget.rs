@@ -0,0 +1,63 @@
+//! Counting of the warnings of each lint, for the budgets set with `cargo clippy --warn-budget` or
+//! the `warn-budget` configuration.
+//!
+//! The diagnostics emitted through [`crate::diagnostics`] are counted, and a pass registered after
+//! all the others fails the crate if a lint was emitted more often than its budget allows.
+
+use rustc_errors::{DiagInner, Level};
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_lint::{LateContext, LateLintPass, Lint, LintStore};
+use rustc_session::impl_lint_pass;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// The number of warnings of each lint by its lowercase name without the `clippy::` prefix, if
+/// budgets are enabled.
+static COUNTS: Mutex<Option<BTreeMap<String, u64>>> = Mutex::new(None);
+
+/// Enables the budgets, by the lowercase lint names without the `clippy::` prefix. This has to be
+/// called after all the passes are registered.
+pub fn enable(store: &mut LintStore, budgets: BTreeMap<String, u64>) {
+    *COUNTS.lock().unwrap() = Some(BTreeMap::new());
+    store.register_late_pass(move |_| {
+        Box::new(WarnBudget {
+            budgets: budgets.clone(),
+        })
+    });
+}
+
+/// Counts a diagnostic emitted by one of the functions of [`crate::diagnostics`], or replayed by
+/// the lint cache.
+pub(crate) fn count(diag: &DiagInner, lint: &'static Lint) {
+    // Allowed lints aren't emitted at all, and expected ones are only emitted to be fulfilled
+    if !matches!(diag.level(), Level::Warning | Level::ForceWarning(_) | Level::Error) {
+        return;
+    }
+    if let Some(counts) = &mut *COUNTS.lock().unwrap() {
+        let name = lint.name_lower();
+        let name = name.strip_prefix("clippy::").unwrap_or(&name);
+        *counts.entry(name.to_string()).or_default() += 1;
+    }
+}
+
+struct WarnBudget {
+    budgets: BTreeMap<String, u64>,
+}
+
+impl_lint_pass!(WarnBudget => []);
+
+impl LateLintPass<'_> for WarnBudget {
+    fn check_crate_post(&mut self, cx: &LateContext<'_>) {
+        let counts = COUNTS.lock().unwrap().replace(BTreeMap::new()).unwrap_or_default();
+        for (name, &budget) in &self.budgets {
+            let count = counts.get(name).copied().unwrap_or(0);
+            if count > budget {
+                cx.tcx.dcx().err(format!(
+                    "`{}` has {count} warning{} of `clippy::{name}`, exceeding its budget of {budget}",
+                    cx.tcx.crate_name(LOCAL_CRATE),
+                    if count == 1 { "" } else { "s" },
+                ));
+            }
+        }
+    }
+}
@@ -28,6 +28,7 @@ struct ClippyLint {
     name: Ident,
     category: Ident,
     description: LitStr,
+    version: Option<LitStr>,
 }
 
 impl Parse for ClippyLint {
@@ -73,6 +74,7 @@ impl Parse for ClippyLint {
             name,
             category,
             description,
+            version,
         })
     }
 }
@@ -128,6 +130,7 @@ pub fn declare_clippy_lint(input: TokenStream) -> TokenStream {
         name,
         category,
         description,
+        version,
     } = parse_macro_input!(input as ClippyLint);
 
     let mut category = category.to_string();
@@ -143,6 +146,10 @@ pub fn declare_clippy_lint(input: TokenStream) -> TokenStream {
     );
 
     let info_name = format_ident!("{name}_INFO");
+    let version = match version {
+        Some(version) => quote!(Some(#version)),
+        None => quote!(None),
+    };
 
     (&mut category[0..1]).make_ascii_uppercase();
     let category_variant = format_ident!("{category}");
@@ -160,6 +167,7 @@ pub fn declare_clippy_lint(input: TokenStream) -> TokenStream {
             lint: &#name,
             category: crate::LintCategory::#category_variant,
             explanation: #explanation,
+            version: #version,
         };
     };
 
@@ -0,0 +1,185 @@
+//! Merging of the identical diagnostics of the compilations of a workspace, for `--aggregate`.
+//!
+//! The driver notes the cfgs of the crate on each diagnostic, see `clippy_utils::cfg_notes`. The
+//! diagnostics are held back until cargo is done, merged when they only differ by that note, and
+//! annotated with the targets and cfgs they were emitted with, e.g. when a warning is only emitted
+//! in the unit tests.
+
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+/// The start of the note of the driver, see `clippy_utils::cfg_notes::NOTE_PREFIX`.
+const NOTE_PREFIX: &str = "clippy cfgs:";
+
+struct Merged {
+    /// The first message of the diagnostic.
+    message: Value,
+    /// The targets and cfgs the diagnostic was emitted with.
+    units: BTreeSet<String>,
+}
+
+/// Collects the diagnostics to merge them.
+#[derive(Default)]
+pub struct Aggregation {
+    merged: Vec<Merged>,
+    /// The index of each diagnostic in `merged` by rendered text.
+    indices: HashMap<String, usize>,
+    /// The targets and cfgs which each package was compiled with.
+    packages: HashMap<String, BTreeSet<String>>,
+}
+
+impl Aggregation {
+    /// Holds back a message of the output of cargo, if it's a diagnostic with the note of the
+    /// driver. Returns whether the message was held back.
+    pub fn add_message(&mut self, message: &Value) -> bool {
+        let Some(children) = message["message"]["children"]
+            .as_array()
+            .filter(|_| message["reason"] == "compiler-message")
+        else {
+            return false;
+        };
+        let Some(cfgs) = children.iter().find_map(|child| {
+            let text = child["message"].as_str()?.strip_prefix(NOTE_PREFIX)?;
+            Some(text.trim_start())
+        }) else {
+            return false;
+        };
+        let target = &message["target"];
+        let mut unit = format!("`{}`", target["name"].as_str().unwrap_or_default());
+        if let Some(kind) = target["kind"][0].as_str() {
+            let _ = write!(unit, " ({kind})");
+        }
+        if !cfgs.is_empty() {
+            let _ = write!(unit, " with {cfgs}");
+        }
+        self.packages
+            .entry(message["package_id"].as_str().unwrap_or_default().to_string())
+            .or_default()
+            .insert(unit.clone());
+
+        let key = diagnostic_key(&message["message"]);
+        if let Some(&index) = self.indices.get(&key) {
+            self.merged[index].units.insert(unit);
+        } else {
+            self.indices.insert(key, self.merged.len());
+            self.merged.push(Merged {
+                message: message.clone(),
+                units: BTreeSet::from([unit]),
+            });
+        }
+        true
+    }
+
+    /// Returns the merged messages, with a note listing the targets and cfgs they were emitted
+    /// with if they were emitted several times, or if their package was compiled with several
+    /// targets and cfgs.
+    pub fn into_messages(self) -> impl Iterator<Item = Value> {
+        let packages = self.packages;
+        self.merged.into_iter().map(move |Merged { mut message, units }| {
+            let package = message["package_id"].as_str().unwrap_or_default();
+            if units.len() < 2 && packages.get(package).map_or(0, BTreeSet::len) < 2 {
+                replace_note(&mut message["message"], None);
+                return message;
+            }
+            let units: Vec<_> = units.into_iter().collect();
+            let note = format!("emitted for {}", units.join(", "));
+            replace_note(&mut message["message"], Some(&note));
+            message
+        })
+    }
+}
+
+/// The rendered text of a diagnostic without the note of the driver, which identifies the
+/// diagnostic.
+fn diagnostic_key(diagnostic: &Value) -> String {
+    diagnostic["rendered"]
+        .as_str()
+        .unwrap_or_default()
+        .split_inclusive('\n')
+        .filter(|line| !line.contains(NOTE_PREFIX))
+        .collect()
+}
+
+/// Replaces the note of the driver, in the children and in the rendered text which may contain
+/// ANSI escape codes, or removes it.
+fn replace_note(diagnostic: &mut Value, note: Option<&str>) {
+    if let Some(children) = diagnostic["children"].as_array_mut() {
+        children.retain_mut(|child| {
+            if !child["message"]
+                .as_str()
+                .is_some_and(|text| text.starts_with(NOTE_PREFIX))
+            {
+                return true;
+            }
+            if let Some(note) = note {
+                child["message"] = note.into();
+            }
+            note.is_some()
+        });
+    }
+    if let Some(rendered) = diagnostic["rendered"].as_str() {
+        let mut replaced = String::new();
+        for line in rendered.split_inclusive('\n') {
+            match (line.find(NOTE_PREFIX), note) {
+                (None, _) => replaced.push_str(line),
+                (Some(start), Some(note)) => {
+                    let end = line[start..].find(['\x1b', '\n']).map_or(line.len(), |end| start + end);
+                    replaced.push_str(&line[..start]);
+                    replaced.push_str(note);
+                    replaced.push_str(&line[end..]);
+                },
+                (Some(_), None) => {},
+            }
+        }
+        diagnostic["rendered"] = replaced.into();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Aggregation;
+    use serde_json::{json, Value};
+
+    fn message(package: &str, kind: &str, cfgs: &str, text: &str) -> Value {
+        let note = format!("clippy cfgs:{cfgs}");
+        json!({
+            "reason": "compiler-message",
+            "package_id": package,
+            "target": { "name": "dt", "kind": [kind] },
+            "message": {
+                "message": text,
+                "level": "warning",
+                "children": [{ "message": note, "level": "note", "children": [], "spans": [] }],
+                "rendered": format!("warning: {text}\n   = \u{1b}[1mnote\u{1b}[0m: {note}\u{1b}[0m\n\n"),
+            },
+        })
+    }
+
+    #[test]
+    fn merge() {
+        let mut aggregation = Aggregation::default();
+        assert!(aggregation.add_message(&message("a", "lib", "", "unused")));
+        assert!(aggregation.add_message(&message("a", "lib", " test", "unused")));
+        assert!(aggregation.add_message(&message("a", "lib", " test", "test only")));
+        assert!(aggregation.add_message(&message("c", "lib", "", "other")));
+        assert!(aggregation.add_message(&message("b", "bin", "", "unused")));
+        assert!(!aggregation.add_message(&json!({ "reason": "build-finished", "success": true })));
+        let messages: Vec<_> = aggregation.into_messages().collect();
+        assert_eq!(messages.len(), 3);
+
+        let note = "emitted for `dt` (bin), `dt` (lib), `dt` (lib) with test";
+        assert_eq!(messages[0]["message"]["children"][0]["message"], note);
+        assert_eq!(
+            messages[0]["message"]["rendered"],
+            format!("warning: unused\n   = \u{1b}[1mnote\u{1b}[0m: {note}\u{1b}[0m\n\n")
+        );
+        assert_eq!(
+            messages[1]["message"]["children"][0]["message"],
+            "emitted for `dt` (lib) with test"
+        );
+        // The package was only compiled once
+        assert_eq!(messages[2]["message"]["children"], json!([]));
+        assert_eq!(messages[2]["message"]["rendered"], "warning: other\n\n");
+    }
+}
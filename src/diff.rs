@@ -0,0 +1,252 @@
+//! Filtering of the diagnostics to the lines changed since a git revision, for `--diff-base`.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The lines of each file which were changed since the base revision.
+#[derive(Debug, Default)]
+pub struct ChangedLines {
+    files: HashMap<PathBuf, Vec<Range<u64>>>,
+    /// The files which were added, or are untracked.
+    new_files: Vec<PathBuf>,
+}
+
+impl ChangedLines {
+    /// Computes the lines changed in the working tree since `base`.
+    pub fn from_git(base: &str) -> Result<Self, String> {
+        let toplevel = PathBuf::from(git(&["rev-parse", "--show-toplevel"])?.trim_end());
+        let diff = git(&[
+            "diff",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+            "--no-renames",
+            base,
+            "--",
+        ])?;
+        let mut changed = Self::parse_diff(&toplevel, &diff);
+        let untracked = git(&["ls-files", "--others", "--exclude-standard", "--full-name"])?;
+        changed
+            .new_files
+            .extend(untracked.lines().map(|file| canonicalize(&toplevel.join(file))));
+        Ok(changed)
+    }
+
+    /// Parses the output of `git diff --unified=0`, with the paths relative to `toplevel`.
+    fn parse_diff(toplevel: &Path, diff: &str) -> Self {
+        let mut changed = Self::default();
+        let mut file = None;
+        let mut is_new = false;
+        for line in diff.lines() {
+            if line.starts_with("diff --git ") {
+                is_new = false;
+            } else if line.starts_with("new file mode") {
+                is_new = true;
+            } else if let Some(path) = line.strip_prefix("+++ ") {
+                file = path.strip_prefix("b/").map(|path| canonicalize(&toplevel.join(path)));
+                if let (true, Some(file)) = (is_new, &file) {
+                    changed.new_files.push(file.clone());
+                }
+            } else if let (Some(hunk), Some(file)) = (line.strip_prefix("@@ "), &file) {
+                if let Some(range) = added_lines(hunk) {
+                    changed.files.entry(file.clone()).or_default().push(range);
+                }
+            }
+        }
+        changed
+    }
+
+    /// Checks whether any line from `first` to `last` of `file` was changed.
+    fn contains(&self, file: &Path, first: u64, last: u64) -> bool {
+        self.new_files.iter().any(|new_file| new_file == file)
+            || self
+                .files
+                .get(file)
+                .is_some_and(|ranges| ranges.iter().any(|range| range.start <= last && first < range.end))
+    }
+}
+
+/// Returns the lines of the new file of a hunk header like `-10,2 +12,3 @@ fn main() {`.
+fn added_lines(hunk: &str) -> Option<Range<u64>> {
+    let (_, added) = hunk.split_once(" +")?;
+    let added = added.split(' ').next()?;
+    let (start, len) = match added.split_once(',') {
+        Some((start, len)) => (start.parse::<u64>().ok()?, len.parse::<u64>().ok()?),
+        None => (added.parse().ok()?, 1),
+    };
+    // Hunks which only remove lines don't change any line of the new file.
+    (len != 0).then(|| start..start + len)
+}
+
+fn git(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|err| format!("could not run git: {err}"))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(format!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        ))
+    }
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Decides which of the diagnostics in the JSON output of cargo are shown.
+pub struct Filter {
+    changed: ChangedLines,
+    /// The directory which the paths of the diagnostics are relative to.
+    workspace_root: PathBuf,
+    /// Whether the lints without a location in a source file are shown, e.g. the lints of the
+    /// `cargo` group.
+    include_crate_level: bool,
+}
+
+impl Filter {
+    pub fn new(changed: ChangedLines, workspace_root: PathBuf, include_crate_level: bool) -> Self {
+        Self {
+            changed,
+            workspace_root,
+            include_crate_level,
+        }
+    }
+
+    /// Checks whether a diagnostic of the JSON output is shown. Errors are always shown, since
+    /// they fail the build.
+    pub fn keep(&self, diagnostic: &Value) -> bool {
+        if matches!(
+            diagnostic["level"].as_str(),
+            Some("error" | "error: internal compiler error")
+        ) {
+            return true;
+        }
+        let Some(span) = diagnostic["spans"]
+            .as_array()
+            .and_then(|spans| spans.iter().find(|span| span["is_primary"] == true))
+        else {
+            // Summaries like "2 warnings emitted" don't have a code.
+            return self.include_crate_level && !diagnostic["code"].is_null();
+        };
+        if self.include_crate_level
+            && span["file_name"]
+                .as_str()
+                .is_some_and(|file| Path::new(file).extension().map_or(true, |ext| ext != "rs"))
+        {
+            return true;
+        }
+        // A diagnostic in a macro is shown if any of the calls leading to it was changed.
+        std::iter::successors(Some(span), |span| {
+            let call_site = &span["expansion"]["span"];
+            (!call_site.is_null()).then_some(call_site)
+        })
+        .any(|span| self.contains_span(span))
+    }
+
+    fn contains_span(&self, span: &Value) -> bool {
+        let (Some(file), Some(start), Some(end)) = (
+            span["file_name"].as_str(),
+            span["line_start"].as_u64(),
+            span["line_end"].as_u64(),
+        ) else {
+            return false;
+        };
+        self.changed
+            .contains(&canonicalize(&self.workspace_root.join(file)), start, end)
+    }
+}
+
+/// Returns the root directory of the workspace of the current directory.
+pub fn workspace_root() -> Result<PathBuf, String> {
+    let cargo = std::env::var("CARGO").unwrap_or("cargo".into());
+    let output = Command::new(cargo)
+        .args(["locate-project", "--workspace", "--message-format", "plain"])
+        .output()
+        .map_err(|err| format!("could not run cargo: {err}"))?;
+    let manifest = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim_end());
+    match manifest.parent() {
+        Some(root) if output.status.success() => Ok(root.to_path_buf()),
+        _ => Err(String::from_utf8_lossy(&output.stderr).trim_end().to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{added_lines, ChangedLines, Filter};
+    use serde_json::json;
+    use std::path::Path;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3 +3 @@ fn a() {
+-    old();
++    new();
+@@ -10,2 +10,0 @@ fn b() {
+@@ -20,0 +19,3 @@ fn c() {
++    x();
++    y();
++    z();
+diff --git a/src/new.rs b/src/new.rs
+new file mode 100644
+--- /dev/null
++++ b/src/new.rs
+@@ -0,0 +1 @@
++fn new() {}
+";
+
+    fn diagnostic(level: &str, file: &str, lines: (u64, u64)) -> serde_json::Value {
+        json!({
+            "level": level,
+            "code": { "code": "clippy::needless_return" },
+            "spans": [{
+                "file_name": file,
+                "line_start": lines.0,
+                "line_end": lines.1,
+                "is_primary": true,
+                "expansion": null,
+            }],
+        })
+    }
+
+    #[test]
+    fn hunks() {
+        assert_eq!(added_lines("-3 +3 @@ fn a() {"), Some(3..4));
+        assert_eq!(added_lines("-10,2 +10,0 @@"), None);
+        assert_eq!(added_lines("-20,0 +19,3 @@"), Some(19..22));
+    }
+
+    #[test]
+    fn filter() {
+        let root = Path::new("/ws");
+        let filter = Filter::new(ChangedLines::parse_diff(root, DIFF), root.to_path_buf(), false);
+        assert!(filter.keep(&diagnostic("warning", "src/lib.rs", (3, 3))));
+        assert!(filter.keep(&diagnostic("warning", "src/lib.rs", (15, 20))));
+        assert!(!filter.keep(&diagnostic("warning", "src/lib.rs", (10, 10))));
+        assert!(!filter.keep(&diagnostic("warning", "src/main.rs", (3, 3))));
+        assert!(filter.keep(&diagnostic("warning", "src/new.rs", (7, 7))));
+        assert!(filter.keep(&diagnostic("error", "src/main.rs", (3, 3))));
+        assert!(!filter.keep(&diagnostic("warning", "Cargo.toml", (1, 1))));
+
+        let mut in_macro = diagnostic("warning", "src/macros.rs", (1, 1));
+        in_macro["spans"][0]["expansion"] = json!({
+            "span": { "file_name": "src/lib.rs", "line_start": 20, "line_end": 20, "expansion": null },
+        });
+        assert!(filter.keep(&in_macro));
+
+        let filter = Filter::new(ChangedLines::parse_diff(root, DIFF), root.to_path_buf(), true);
+        assert!(filter.keep(&diagnostic("warning", "Cargo.toml", (1, 1))));
+        assert!(filter.keep(&json!({ "level": "warning", "code": { "code": "clippy::x" }, "spans": [] })));
+        assert!(!filter.keep(&json!({ "level": "warning", "code": null, "spans": [] })));
+    }
+}
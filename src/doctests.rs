@@ -0,0 +1,193 @@
+//! `cargo clippy --doctests`: the Rust code blocks of the documentation of a library, which are
+//! collected while it's linted, are linted afterwards the same way `rustdoc --test` compiles them.
+//! Each code block is passed to a new `clippy-driver` process as a binary crate depending on the
+//! library, and only the Clippy lints of its diagnostics are passed on.
+
+use crate::arg_value;
+use clippy_lints::Doctest;
+use std::env;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+/// The lints of the `doctest-lints` configuration, all the lints are run if it's empty.
+pub static LINTS: OnceLock<Vec<String>> = OnceLock::new();
+
+/// The arguments of the compiler which are passed on to the code blocks, followed by a value.
+const PASSED_ARGS: &[&str] = &["-L", "--extern", "--sysroot", "--target", "--json"];
+
+/// Lints the code blocks of the crate compiled with `args`, after it was linted successfully.
+pub fn lint(args: &[String], clippy_args_var: &str, doctests: Vec<Doctest>) {
+    // Only libraries have doctests, which are linted once rather than again with `--test`
+    if arg_value(args, "--crate-type", |ty| ty == "lib" || ty == "rlib").is_none()
+        || args.iter().any(|arg| arg == "--test")
+    {
+        return;
+    }
+    let (Some(name), Some(out_dir)) = (
+        arg_value(args, "--crate-name", |_| true),
+        arg_value(args, "--out-dir", |_| true),
+    ) else {
+        return;
+    };
+    let extra_filename = arg_value(args, "-C", |value| value.starts_with("extra-filename="))
+        .map_or("", |value| &value["extra-filename=".len()..]);
+    let metadata = Path::new(out_dir).join(format!("lib{name}{extra_filename}.rmeta"));
+    if !metadata.exists() {
+        return;
+    }
+
+    let mut common_args = vec![
+        "-".to_string(),
+        "--crate-type=bin".into(),
+        "--crate-name=rust_out".into(),
+        "--cfg=doctest".into(),
+        "--emit=metadata".into(),
+        "--error-format=json".into(),
+        "--out-dir".into(),
+        Path::new(out_dir).join("clippy-doctests").display().to_string(),
+        "--extern".into(),
+        format!("{name}={}", metadata.display()),
+    ];
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if PASSED_ARGS.contains(&arg.as_str()) {
+            common_args.extend([arg.clone(), iter.next().cloned().unwrap_or_default()]);
+        } else if PASSED_ARGS
+            .iter()
+            .any(|flag| arg.strip_prefix(flag).is_some_and(|value| value.starts_with('=')))
+        {
+            common_args.push(arg.clone());
+        }
+    }
+    let json = arg_value(args, "--error-format", |format| format == "json").is_some();
+
+    let mut clippy_args: Vec<_> = clippy_args_var
+        .split("__CLIPPY_HACKERY__")
+        .filter(|arg| !matches!(*arg, "" | "--doctests" | "--lint-cache" | "--summary"))
+        .map(String::from)
+        .collect();
+    if let Some(lints) = LINTS.get().filter(|lints| !lints.is_empty()) {
+        clippy_args.push(format!("--only={}", lints.join(",")));
+    }
+    let clippy_args = clippy_args.join("__CLIPPY_HACKERY__");
+
+    for doctest in doctests {
+        let (source, offset) = source(&doctest, name);
+        let mut child = match Command::new(env::current_exe().expect("current executable path invalid"))
+            .args(&common_args)
+            .arg(format!("--edition={}", doctest.edition))
+            .env("CLIPPY_ARGS", &clippy_args)
+            .env("UNSTABLE_RUSTDOC_TEST_PATH", &doctest.file)
+            .env("UNSTABLE_RUSTDOC_TEST_LINE", offset.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(err) => {
+                eprintln!("warning: could not lint the code blocks of `{name}`: {err}");
+                return;
+            },
+        };
+        let _ = child.stdin.take().unwrap().write_all(source.as_bytes());
+        let Ok(output) = child.wait_with_output() else {
+            continue;
+        };
+        for line in String::from_utf8_lossy(&output.stderr).lines() {
+            if let Some(diagnostic) = clippy_diagnostic(line, &doctest.file, offset) {
+                let mut stderr = io::stderr().lock();
+                if json {
+                    let _ = writeln!(stderr, "{diagnostic}");
+                } else if let Some(rendered) = diagnostic["rendered"].as_str() {
+                    let _ = write!(stderr, "{rendered}");
+                }
+            }
+        }
+    }
+}
+
+/// Returns the source of the crate of a code block and the offset of its lines from the lines of
+/// the file, like `rustdoc --test` does.
+///
+/// The lines of the code block keep their line and column in the crate, so that only the offset of
+/// the lines has to be applied to the spans: the hidden lines starting with `#` are shown, the
+/// crate attributes are moved before the code, and the code is indented like in the file.
+fn source(doctest: &Doctest, crate_name: &str) -> (String, isize) {
+    let mut prefix = String::from("#![allow(unused)]\n");
+    let mut body = String::new();
+    let mut in_attributes = true;
+    for line in doctest.code.lines() {
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+        let line = if trimmed.starts_with("##") {
+            format!("{indent} {}", &trimmed[1..])
+        } else if trimmed == "#" {
+            String::new()
+        } else if let Some(rest) = trimmed.strip_prefix("# ").or_else(|| trimmed.strip_prefix("#\t")) {
+            format!("{indent}  {rest}")
+        } else {
+            line.to_string()
+        };
+        in_attributes &= line.trim().is_empty() || line.trim_start().starts_with("#![");
+        if in_attributes {
+            prefix.push_str(&line);
+            prefix.push('\n');
+            body.push('\n');
+        } else if line.is_empty() {
+            body.push('\n');
+        } else {
+            body.push_str(&format!("{:1$}{line}\n", "", doctest.column));
+        }
+    }
+    if doctest.edition == "2015" && !body.contains("extern crate") && body.contains(crate_name) {
+        prefix.push_str(&format!("extern crate {crate_name};\n"));
+    }
+    if !body.contains("fn main") {
+        prefix.push_str("fn main() {\n");
+        body.push_str("}\n");
+    }
+    let prefix_lines = isize::try_from(prefix.lines().count()).unwrap_or(0);
+    let line = isize::try_from(doctest.line).unwrap_or(0);
+    (prefix + &body, line - prefix_lines - 1)
+}
+
+/// Parses a line of the JSON output of the compiler, returning it without its suggestions if it's
+/// a diagnostic of a Clippy lint. The suggestions are removed since their byte offsets are the ones
+/// of the code block rather than of the file.
+///
+/// Unlike the rendered diagnostic, the lines of the spans aren't offset by the compiler, so
+/// `offset` is applied to the spans in `file`.
+fn clippy_diagnostic(line: &str, file: &str, offset: isize) -> Option<serde_json::Value> {
+    let mut diagnostic = serde_json::from_str::<serde_json::Value>(line).ok()?;
+    if !diagnostic["code"]["code"]
+        .as_str()
+        .is_some_and(|code| code.starts_with("clippy::"))
+    {
+        return None;
+    }
+    for child in diagnostic["children"].as_array_mut().into_iter().flatten() {
+        for span in child["spans"].as_array_mut().into_iter().flatten() {
+            span["suggested_replacement"] = serde_json::Value::Null;
+            span["suggestion_applicability"] = serde_json::Value::Null;
+            offset_lines(span, file, offset);
+        }
+    }
+    for span in diagnostic["spans"].as_array_mut().into_iter().flatten() {
+        offset_lines(span, file, offset);
+    }
+    Some(diagnostic)
+}
+
+fn offset_lines(span: &mut serde_json::Value, file: &str, offset: isize) {
+    if span["file_name"] != file {
+        return;
+    }
+    for key in ["line_start", "line_end"] {
+        if let Some(line) = span[key].as_i64() {
+            span[key] = (line + offset as i64).into();
+        }
+    }
+}
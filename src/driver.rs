@@ -14,6 +14,7 @@
 // (Currently there is no way to opt into sysroot crates without `extern crate`.)
 extern crate rustc_driver;
 extern crate rustc_interface;
+extern crate rustc_lint;
 extern crate rustc_session;
 extern crate rustc_span;
 
@@ -23,14 +24,18 @@ use rustc_session::parse::ParseSess;
 use rustc_session::EarlyDiagCtxt;
 use rustc_span::symbol::Symbol;
 
-use std::env;
 use std::fs::read_to_string;
 use std::ops::Deref;
 use std::path::Path;
 use std::process::exit;
+use std::sync::Arc;
+use std::{env, mem};
 
 use anstream::println;
 
+mod doctests;
+mod plugins;
+
 /// If a command-line option matches `find_arg`, then apply the predicate `pred` on its value. If
 /// true, then return it. The parameter is assumed to be either `--arg=value` or `--arg value`.
 fn arg_value<'a, T: Deref<Target = str>>(
@@ -103,6 +108,36 @@ fn track_files(psess: &mut ParseSess) {
     }
 }
 
+/// Returns the cfgs passed to the compiler by cargo, like `test` or `feature="std"`.
+fn crate_cfgs(args: &[String]) -> Vec<String> {
+    let mut cfgs = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--test" {
+            cfgs.push("test".into());
+        } else if arg == "--cfg" {
+            cfgs.extend(args.next().cloned());
+        } else if let Some(cfg) = arg.strip_prefix("--cfg=") {
+            cfgs.push(cfg.into());
+        }
+    }
+    cfgs
+}
+
+/// Reports a crash as a JSON diagnostic for `cargo clippy --severity-exit-codes`, which can't tell
+/// it apart from a compilation error otherwise, as the report of the crash isn't in JSON.
+fn report_ice_as_json() {
+    let severity_exit_codes = env::var("CLIPPY_ARGS").is_ok_and(|args| {
+        args.split("__CLIPPY_HACKERY__")
+            .any(|arg| arg == "--severity-exit-codes")
+    });
+    if severity_exit_codes && env::args().any(|arg| arg.starts_with("--error-format=json")) {
+        eprintln!(
+            r#"{{"$message_type":"diagnostic","message":"Clippy crashed","code":null,"level":"error: internal compiler error","spans":[],"children":[],"rendered":"error: internal compiler error: Clippy crashed, see the report above\n"}}"#
+        );
+    }
+}
+
 struct DefaultCallbacks;
 impl rustc_driver::Callbacks for DefaultCallbacks {}
 
@@ -121,17 +156,44 @@ impl rustc_driver::Callbacks for RustcCallbacks {
     }
 }
 
+#[expect(clippy::struct_excessive_bools)]
 struct ClippyCallbacks {
     clippy_args_var: Option<String>,
+    lint_cache: bool,
+    lint_profile: bool,
+    /// The lints passed to `--only`.
+    only: Option<Vec<String>>,
+    /// The budgets passed to `--warn-budget`, by lint name.
+    warn_budget: Vec<(String, u64)>,
+    collapse_macro_diagnostics: bool,
+    summary: bool,
+    doctests: bool,
+    /// The cfgs of the crate to note on its diagnostics, for `--aggregate`.
+    note_cfgs: Option<Vec<String>>,
+    /// The lints passed to `--annotate-allows`.
+    annotate_allows: Vec<String>,
+    /// The `key=value` pairs passed to `--clippy-config`.
+    conf_overrides: Vec<String>,
+    /// The number of threads passed to `--lint-threads`.
+    lint_threads: Option<usize>,
 }
 
 impl rustc_driver::Callbacks for ClippyCallbacks {
-    // JUSTIFICATION: necessary in clippy driver to set `mir_opt_level`
-    #[allow(rustc::bad_opt_access)]
     fn config(&mut self, config: &mut interface::Config) {
         let conf_path = clippy_config::lookup_conf_file();
         let previous = config.register_lints.take();
         let clippy_args_var = self.clippy_args_var.take();
+        let lint_cache = self.lint_cache;
+        let lint_profile = self.lint_profile;
+        let only = self.only.take();
+        let warn_budget = mem::take(&mut self.warn_budget);
+        let collapse_macro_diagnostics = self.collapse_macro_diagnostics;
+        let summary = self.summary;
+        let doctests = self.doctests;
+        let note_cfgs = self.note_cfgs.take();
+        let annotate_allows = mem::take(&mut self.annotate_allows);
+        let conf_overrides = mem::take(&mut self.conf_overrides);
+        let lint_threads = self.lint_threads;
         config.psess_created = Some(Box::new(move |psess| {
             track_clippy_args(psess, &clippy_args_var);
             track_files(psess);
@@ -142,6 +204,20 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
                 Symbol::intern("CLIPPY_CONF_DIR"),
                 env::var("CLIPPY_CONF_DIR").ok().map(|dir| Symbol::intern(&dir)),
             ));
+            // Trigger a rebuild if the plugins are allowed or disallowed
+            psess.env_depinfo.get_mut().insert((
+                Symbol::intern(plugins::ALLOW_ENV_VAR),
+                env::var(plugins::ALLOW_ENV_VAR)
+                    .ok()
+                    .map(|allow| Symbol::intern(&allow)),
+            ));
+            // Trigger a rebuild if the selected profile changes
+            psess.env_depinfo.get_mut().insert((
+                Symbol::intern(clippy_config::PROFILE_ENV_VAR),
+                env::var(clippy_config::PROFILE_ENV_VAR)
+                    .ok()
+                    .map(|profile| Symbol::intern(&profile)),
+            ));
         }));
         config.register_lints = Some(Box::new(move |sess, lint_store| {
             // technically we're ~guaranteed that this is none but might as well call anything that
@@ -150,20 +226,56 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
                 (previous)(sess, lint_store);
             }
 
-            let conf = clippy_config::Conf::read(sess, &conf_path);
-            clippy_lints::register_lints(lint_store, conf);
-            clippy_lints::register_pre_expansion_lints(lint_store, conf);
-            clippy_lints::register_renamed(lint_store);
+            let conf = clippy_config::Conf::read(sess, &conf_path, &conf_overrides);
+            let first_early_pass = lint_store.early_passes.len();
+            let first_late_pass = lint_store.late_passes.len();
+            clippy_lints::register_with_config(lint_store, Arc::clone(conf));
+            if let Ok((Some(conf_file), _)) = &conf_path
+                && let Some(conf_dir) = conf_file.parent()
+            {
+                plugins::register(sess, lint_store, conf_dir, &conf.plugins);
+            }
+            if let Some(only) = &only {
+                clippy_lints::retain_lint_passes(lint_store, first_early_pass, first_late_pass, only);
+            }
+            // `cargo clippy` rejects the other flags which report at the end of the crate
+            if lint_threads.is_some() {
+                if conf.warn_budget.is_empty() {
+                    clippy_lints::run_stateless_passes_per_module(lint_store, first_late_pass);
+                } else {
+                    sess.dcx()
+                        .warn("`--lint-threads` is ignored with the `warn-budget` configuration");
+                }
+            }
+            // The timed passes keep the names which the lint cache relies on, so they are wrapped first
+            if lint_profile {
+                clippy_lints::enable_lint_profile(lint_store, first_late_pass);
+            }
+            if lint_cache {
+                clippy_lints::enable_lint_cache(lint_store, first_late_pass);
+            }
+            clippy_lints::enable_warn_budget(lint_store, sess, conf, &warn_budget);
+            if collapse_macro_diagnostics {
+                clippy_lints::collapse_macro_diagnostics(lint_store);
+            }
+            if summary {
+                clippy_lints::enable_summary(lint_store);
+            }
+            if doctests {
+                let _ = doctests::LINTS.set(clippy_lints::collect_doctests(conf));
+            }
+            if let Some(cfgs) = &note_cfgs {
+                clippy_lints::note_cfgs(cfgs);
+            }
+            if !annotate_allows.is_empty() {
+                clippy_lints::annotate_allows(lint_store, &annotate_allows);
+            }
         }));
 
-        // FIXME: #4825; This is required, because Clippy lints that are based on MIR have to be
-        // run on the unoptimized MIR. On the other hand this results in some false negatives. If
-        // MIR passes can be enabled / disabled separately, we should figure out, what passes to
-        // use for Clippy.
-        config.opts.unstable_opts.mir_opt_level = Some(0);
-
-        // Disable flattening and inlining of format_args!(), so the HIR matches with the AST.
-        config.opts.unstable_opts.flatten_format_args = false;
+        clippy_lints::set_compiler_options(&mut config.opts);
+        if let Some(threads) = self.lint_threads {
+            clippy_lints::set_threads(&mut config.opts, threads);
+        }
     }
 }
 
@@ -187,6 +299,7 @@ pub fn main() {
         // accept a generic closure.
         let version_info = rustc_tools_util::get_version_info!();
         handler.note(format!("Clippy version: {version_info}"));
+        report_ice_as_json();
     });
 
     exit(rustc_driver::catch_with_exit_code(move || {
@@ -259,17 +372,85 @@ pub fn main() {
         pass_sysroot_env_if_given(&mut args, sys_root_env);
 
         let mut no_deps = false;
+        let mut lint_cache = false;
+        let mut lint_profile = false;
+        let mut only = None;
+        let mut warn_budget = Vec::new();
+        let mut collapse_macro_diagnostics = false;
+        let mut summary = false;
+        let mut doctests = false;
+        let mut aggregate = false;
+        let mut annotate_allows = Vec::new();
+        let mut conf_overrides = Vec::new();
+        let mut lint_threads = None;
         let clippy_args_var = env::var("CLIPPY_ARGS").ok();
         let clippy_args = clippy_args_var
             .as_deref()
             .unwrap_or_default()
             .split("__CLIPPY_HACKERY__")
             .filter_map(|s| match s {
-                "" => None,
+                // `--severity-exit-codes` is only used by `report_ice_as_json`
+                "" | "--severity-exit-codes" => None,
                 "--no-deps" => {
                     no_deps = true;
                     None
                 },
+                "--lint-cache" => {
+                    lint_cache = true;
+                    None
+                },
+                "--lint-profile" => {
+                    lint_profile = true;
+                    None
+                },
+                "--collapse-macro-diagnostics" => {
+                    collapse_macro_diagnostics = true;
+                    None
+                },
+                "--summary" => {
+                    summary = true;
+                    None
+                },
+                "--doctests" => {
+                    doctests = true;
+                    None
+                },
+                "--aggregate" => {
+                    aggregate = true;
+                    None
+                },
+                _ if s.starts_with("--warn-budget=") => {
+                    warn_budget.extend(s["--warn-budget=".len()..].split(',').filter_map(|budget| {
+                        let (name, budget) = budget.split_once('=')?;
+                        Some((name.to_string(), budget.parse().ok()?))
+                    }));
+                    None
+                },
+                _ if s.starts_with("--fix-maybe-incorrect=") => {
+                    clippy_lints::set_fix_maybe_incorrect(
+                        s["--fix-maybe-incorrect=".len()..]
+                            .split(',')
+                            .map(String::from)
+                            .collect(),
+                    );
+                    None
+                },
+                _ if s.starts_with("--annotate-allows=") => {
+                    annotate_allows.extend(s["--annotate-allows=".len()..].split(',').map(String::from));
+                    None
+                },
+                _ if s.starts_with("--lint-threads=") => {
+                    lint_threads = s["--lint-threads=".len()..].parse().ok();
+                    None
+                },
+                _ if s.starts_with("--clippy-config=") => {
+                    conf_overrides.push(s["--clippy-config=".len()..].to_string());
+                    None
+                },
+                _ if s.starts_with("--only=") => {
+                    only = Some(s["--only=".len()..].split(',').map(String::from).collect::<Vec<_>>());
+                    None
+                },
                 _ => Some(s.to_string()),
             })
             // FIXME: remove this line in 1.79 to only keep `--cfg clippy`.
@@ -288,10 +469,55 @@ pub fn main() {
 
         let clippy_enabled = !cap_lints_allow && (!no_deps || in_primary_package);
         if clippy_enabled {
+            // The lint levels of the selected profile and of the kind of target come before the flags
+            // passed to `cargo clippy`, so that these can override them
+            let conf_path = clippy_config::lookup_conf_file();
+            if let Ok(profile) = env::var(clippy_config::PROFILE_ENV_VAR)
+                && !profile.is_empty()
+            {
+                args.extend(clippy_config::profile_lint_flags(&conf_path, &profile));
+            }
+            args.extend(clippy_config::target_kind_lint_flags(
+                &conf_path,
+                clippy_config::TargetKind::from_args(&orig_args),
+            ));
             args.extend(clippy_args);
-            rustc_driver::RunCompiler::new(&args, &mut ClippyCallbacks { clippy_args_var })
-                .set_using_internal_features(using_internal_features)
-                .run()
+            // The lint passes which `--only` keeps may emit other lints too, and the expectations of
+            // the skipped lints can't be fulfilled
+            if let Some(only) = &only {
+                args.push("-Aunfulfilled_lint_expectations".into());
+                args.extend(
+                    clippy_lints::lint_names_except(only)
+                        .iter()
+                        .map(|name| format!("-Aclippy::{name}")),
+                );
+            }
+            let doctest_clippy_args = doctests.then(|| clippy_args_var.clone().unwrap_or_default());
+            let result = rustc_driver::RunCompiler::new(
+                &args,
+                &mut ClippyCallbacks {
+                    clippy_args_var,
+                    lint_cache,
+                    lint_profile,
+                    only,
+                    warn_budget,
+                    collapse_macro_diagnostics,
+                    summary,
+                    doctests,
+                    note_cfgs: aggregate.then(|| crate_cfgs(&orig_args)),
+                    annotate_allows,
+                    conf_overrides,
+                    lint_threads,
+                },
+            )
+            .set_using_internal_features(using_internal_features)
+            .run();
+            if let Some(clippy_args) = doctest_clippy_args
+                && result.is_ok()
+            {
+                doctests::lint(&args, &clippy_args, clippy_lints::take_doctests());
+            }
+            result
         } else {
             rustc_driver::RunCompiler::new(&args, &mut RustcCallbacks { clippy_args_var })
                 .set_using_internal_features(using_internal_features)
@@ -0,0 +1,295 @@
+//! The export of the suggestions of the diagnostics to a JSON file, for `--export-fixes`, so that
+//! editors and other tools can apply them without computing the edits from the spans.
+//!
+//! Each fix is a suggestion of a diagnostic, with all of its edits, which have to be applied
+//! together. The edits are given as byte ranges of the files with the replaced text, and the
+//! changed lines of each file are rendered before and after applying them.
+
+use crate::review::changed_lines;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// The version of the format of the exported file.
+const VERSION: u64 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Edit {
+    /// The path of the file as reported by cargo, relative to the workspace root.
+    file_name: String,
+    range: Range<usize>,
+    /// The lines and columns of the start and of the end, starting at 1.
+    start: (u64, u64),
+    end: (u64, u64),
+    replacement: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Fix {
+    lint: Option<String>,
+    level: String,
+    message: String,
+    help: String,
+    applicability: String,
+    edits: Vec<Edit>,
+}
+
+impl Fix {
+    /// Returns the fix of a child of a diagnostic, if it suggests replacements.
+    fn from_child(diagnostic: &Value, child: &Value) -> Option<Self> {
+        let spans = child["spans"].as_array()?;
+        let mut edits = Vec::new();
+        for span in spans {
+            let Some(replacement) = span["suggested_replacement"].as_str() else {
+                continue;
+            };
+            let position =
+                |line: &str, column: &str| (span[line].as_u64().unwrap_or(0), span[column].as_u64().unwrap_or(0));
+            let start = usize::try_from(span["byte_start"].as_u64()?).ok()?;
+            let end = usize::try_from(span["byte_end"].as_u64()?).ok()?;
+            edits.push(Edit {
+                file_name: span["file_name"].as_str()?.to_string(),
+                range: start..end,
+                start: position("line_start", "column_start"),
+                end: position("line_end", "column_end"),
+                replacement: replacement.to_string(),
+            });
+        }
+        if edits.is_empty() {
+            return None;
+        }
+        edits.sort_by(|a, b| {
+            (&a.file_name, a.range.start, a.range.end).cmp(&(&b.file_name, b.range.start, b.range.end))
+        });
+        Some(Self {
+            lint: diagnostic["code"]["code"].as_str().map(String::from),
+            level: diagnostic["level"].as_str().unwrap_or_default().to_string(),
+            message: diagnostic["message"].as_str().unwrap_or_default().to_string(),
+            help: child["message"].as_str().unwrap_or_default().to_string(),
+            applicability: spans
+                .iter()
+                .find_map(|span| span["suggestion_applicability"].as_str())
+                .unwrap_or("Unspecified")
+                .to_string(),
+            edits,
+        })
+    }
+
+    /// Returns the JSON of the fix, with the original text of each edit and the changed lines of
+    /// each file, given the contents of the files.
+    fn to_json(&self, contents: &BTreeMap<&str, String>) -> Value {
+        let mut changes = Vec::new();
+        let mut edits = self.edits.as_slice();
+        while let Some(first) = edits.first() {
+            let count = edits
+                .iter()
+                .take_while(|edit| edit.file_name == first.file_name)
+                .count();
+            let (file_edits, rest) = edits.split_at(count);
+            edits = rest;
+            let content = contents.get(first.file_name.as_str()).map_or("", String::as_str);
+            let replacements: Vec<_> = file_edits
+                .iter()
+                .map(|edit| (edit.range.clone(), edit.replacement.clone()))
+                .collect();
+            let (lines, after) = changed_lines(content, &replacements).unwrap_or_default();
+            changes.push(json!({
+                "file": first.file_name,
+                "line_start": content[..lines.start].matches('\n').count() + 1,
+                "line_end": content[..lines.end].matches('\n').count() + 1,
+                "before": &content[lines],
+                "after": after,
+                "edits": file_edits.iter().map(|edit| json!({
+                    "byte_start": edit.range.start,
+                    "byte_end": edit.range.end,
+                    "line_start": edit.start.0,
+                    "column_start": edit.start.1,
+                    "line_end": edit.end.0,
+                    "column_end": edit.end.1,
+                    "original": content.get(edit.range.clone()),
+                    "replacement": edit.replacement,
+                })).collect::<Vec<_>>(),
+            }));
+        }
+        json!({
+            "lint": self.lint,
+            "level": self.level,
+            "message": self.message,
+            "help": self.help,
+            "applicability": self.applicability,
+            "changes": changes,
+        })
+    }
+}
+
+/// Collects the suggestions of the compiler messages to export them.
+pub struct Fixes {
+    /// The directory which the paths of the diagnostics are relative to.
+    workspace_root: PathBuf,
+    collected: Vec<Fix>,
+    seen: HashSet<Fix>,
+}
+
+impl Fixes {
+    pub fn new(workspace_root: PathBuf) -> Self {
+        Self {
+            workspace_root,
+            collected: Vec::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Adds the suggestions of a line of the output of cargo in the files of the workspace,
+    /// ignoring other messages.
+    pub fn add_message(&mut self, message: &Value) {
+        if message["reason"] != "compiler-message" {
+            return;
+        }
+        let diagnostic = &message["message"];
+        for child in diagnostic["children"].as_array().into_iter().flatten() {
+            let Some(fix) = Fix::from_child(diagnostic, child) else {
+                continue;
+            };
+            // The paths outside of the workspace, like the ones of dependencies, are absolute.
+            if fix.edits.iter().any(|edit| {
+                !self
+                    .workspace_root
+                    .join(&edit.file_name)
+                    .starts_with(&self.workspace_root)
+            }) {
+                continue;
+            }
+            // The same diagnostic is emitted for each target which contains the code.
+            if self.seen.insert(fix.clone()) {
+                self.collected.push(fix);
+            }
+        }
+    }
+
+    /// Writes the fixes to `path`.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut contents = BTreeMap::new();
+        for edit in self.collected.iter().flat_map(|fix| &fix.edits) {
+            if !contents.contains_key(edit.file_name.as_str()) {
+                let file = self.workspace_root.join(&edit.file_name);
+                let content =
+                    fs::read_to_string(&file).map_err(|err| format!("could not read `{}`: {err}", file.display()))?;
+                contents.insert(edit.file_name.as_str(), content);
+            }
+        }
+        fs::write(path, format!("{:#}\n", self.to_json(&contents)))
+            .map_err(|err| format!("could not write the fixes to `{}`: {err}", path.display()))
+    }
+
+    fn to_json(&self, contents: &BTreeMap<&str, String>) -> Value {
+        let mut fixes: Vec<_> = self.collected.iter().collect();
+        fixes.sort_by(|a, b| {
+            let key = |fix: &Fix| (fix.edits[0].file_name.clone(), fix.edits[0].range.start);
+            key(a).cmp(&key(b))
+        });
+        let fixes: Vec<_> = fixes.iter().map(|fix| fix.to_json(contents)).collect();
+        json!({ "version": VERSION, "fixes": fixes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Fixes;
+    use serde_json::{json, Value};
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn span(file: &str, start: u64, end: u64, replacement: Option<&str>) -> Value {
+        json!({
+            "file_name": file,
+            "byte_start": start,
+            "byte_end": end,
+            "line_start": 2,
+            "column_start": start.saturating_sub(14),
+            "line_end": 2,
+            "column_end": end.saturating_sub(14),
+            "suggested_replacement": replacement,
+            "suggestion_applicability": replacement.map(|_| "MachineApplicable"),
+        })
+    }
+
+    #[test]
+    fn export() {
+        let message = json!({
+            "reason": "compiler-message",
+            "message": {
+                "message": "unneeded `return` statement",
+                "code": { "code": "clippy::needless_return" },
+                "level": "warning",
+                "children": [
+                    { "message": "the span", "spans": [span("src/lib.rs", 19, 28, None)] },
+                    {
+                        "message": "remove `return`",
+                        "spans": [span("src/lib.rs", 27, 28, Some("")), span("src/lib.rs", 19, 26, Some(""))],
+                    },
+                ],
+            },
+        });
+        let outside = json!({
+            "reason": "compiler-message",
+            "message": {
+                "message": "message",
+                "code": null,
+                "level": "error",
+                "children": [{ "message": "help", "spans": [span("/registry/src/lib.rs", 0, 1, Some("x"))] }],
+            },
+        });
+        let mut fixes = Fixes::new(PathBuf::from("/ws"));
+        fixes.add_message(&message);
+        fixes.add_message(&message);
+        fixes.add_message(&outside);
+        assert_eq!(fixes.collected.len(), 1);
+
+        let contents = BTreeMap::from([("src/lib.rs", "fn f() -> u8 {\n    return 1;\n}\n".to_string())]);
+        let exported = fixes.to_json(&contents);
+        assert_eq!(
+            exported,
+            json!({
+                "version": 1,
+                "fixes": [{
+                    "lint": "clippy::needless_return",
+                    "level": "warning",
+                    "message": "unneeded `return` statement",
+                    "help": "remove `return`",
+                    "applicability": "MachineApplicable",
+                    "changes": [{
+                        "file": "src/lib.rs",
+                        "line_start": 2,
+                        "line_end": 2,
+                        "before": "    return 1;",
+                        "after": "    1",
+                        "edits": [
+                            {
+                                "byte_start": 19,
+                                "byte_end": 26,
+                                "line_start": 2,
+                                "column_start": 5,
+                                "line_end": 2,
+                                "column_end": 12,
+                                "original": "return ",
+                                "replacement": "",
+                            },
+                            {
+                                "byte_start": 27,
+                                "byte_end": 28,
+                                "line_start": 2,
+                                "column_start": 13,
+                                "line_end": 2,
+                                "column_end": 14,
+                                "original": ";",
+                                "replacement": "",
+                            },
+                        ],
+                    }],
+                }],
+            })
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Conversion of the JSON messages emitted by `cargo --message-format=json` to
+//! [GitHub Actions workflow commands](https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions),
+//! which show the diagnostics as annotations of the changed files.
+
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Turns the diagnostics of the compiler messages into annotations.
+#[derive(Default)]
+pub struct Annotations {
+    /// The rendered diagnostics, as the same diagnostic can be emitted for several targets.
+    seen: HashSet<String>,
+}
+
+impl Annotations {
+    /// Returns the annotation of the diagnostic of a line of the output of cargo, if it's a new
+    /// diagnostic with a location.
+    pub fn annotation(&mut self, line: &str) -> Option<String> {
+        let message = serde_json::from_str::<Value>(line).ok()?;
+        if message["reason"] != "compiler-message" {
+            return None;
+        }
+        let diagnostic = &message["message"];
+        let span = diagnostic["spans"]
+            .as_array()?
+            .iter()
+            .find(|span| span["is_primary"] == true)?;
+        if !self
+            .seen
+            .insert(diagnostic["rendered"].as_str().unwrap_or_default().to_string())
+        {
+            return None;
+        }
+
+        let command = match diagnostic["level"].as_str() {
+            Some("error" | "error: internal compiler error") => "error",
+            Some("warning") => "warning",
+            _ => "notice",
+        };
+        let mut text = diagnostic["message"].as_str().unwrap_or_default().to_string();
+        let mut title = String::from("clippy");
+        if let Some(code) = diagnostic["code"]["code"].as_str() {
+            title = code.to_string();
+            if let Some((name, (group, _))) = code
+                .strip_prefix("clippy::")
+                .and_then(|name| Some((name, clippy_lints::lint_group_and_description(name)?)))
+            {
+                let _ = write!(title, " ({group})");
+                let _ = write!(
+                    text,
+                    "\nhttps://rust-lang.github.io/rust-clippy/master/index.html#{name}"
+                );
+            }
+        }
+        Some(format!(
+            "::{command} file={},line={},endLine={},col={},endColumn={},title={}::{}",
+            escape_property(span["file_name"].as_str().unwrap_or_default()),
+            span["line_start"],
+            span["line_end"],
+            span["column_start"],
+            span["column_end"],
+            escape_property(&title),
+            escape_data(&text),
+        ))
+    }
+}
+
+fn escape_data(data: &str) -> String {
+    data.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_property(property: &str) -> String {
+    escape_data(property).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Annotations;
+    use serde_json::json;
+
+    fn message(code: &str) -> String {
+        json!({
+            "reason": "compiler-message",
+            "message": {
+                "message": "unneeded `return` statement",
+                "code": { "code": code, "explanation": null },
+                "level": "warning",
+                "spans": [{
+                    "file_name": "src/main.rs",
+                    "line_start": 2,
+                    "line_end": 3,
+                    "column_start": 5,
+                    "column_end": 17,
+                    "is_primary": true,
+                }],
+                "children": [],
+                "rendered": format!("{code} rendered"),
+            },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn annotations() {
+        let mut annotations = Annotations::default();
+        assert_eq!(
+            annotations.annotation(&message("clippy::needless_return")).unwrap(),
+            "::warning file=src/main.rs,line=2,endLine=3,col=5,endColumn=17,title=clippy%3A%3Aneedless_return (style)\
+             ::unneeded `return` statement%0Ahttps://rust-lang.github.io/rust-clippy/master/index.html#needless_return"
+        );
+        assert_eq!(annotations.annotation(&message("clippy::needless_return")), None);
+        assert_eq!(
+            annotations.annotation(&message("dead_code")).unwrap(),
+            "::warning file=src/main.rs,line=2,endLine=3,col=5,endColumn=17,title=dead_code::unneeded `return` statement"
+        );
+        assert_eq!(
+            annotations.annotation(r#"{"reason":"build-finished","success":true}"#),
+            None
+        );
+    }
+}
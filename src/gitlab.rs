@@ -0,0 +1,139 @@
+//! Conversion of the JSON messages emitted by `cargo --message-format=json` to a
+//! [GitLab code quality report](https://docs.gitlab.com/ee/ci/testing/code_quality.html#implement-a-custom-tool),
+//! which shows the diagnostics in merge requests.
+
+use crate::snapshot::fnv1a;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+/// Collects the diagnostics of the compiler messages and turns them into a code quality report.
+#[derive(Default)]
+pub struct Report {
+    issues: Vec<Value>,
+    /// The fingerprints of the issues, as the same diagnostic can be emitted for several targets.
+    fingerprints: HashSet<String>,
+}
+
+impl Report {
+    /// Adds the diagnostic of a line of the output of cargo, ignoring other messages.
+    pub fn add_message(&mut self, line: &str) {
+        let Ok(message) = serde_json::from_str::<Value>(line) else {
+            return;
+        };
+        if message["reason"] != "compiler-message" {
+            return;
+        }
+        let diagnostic = &message["message"];
+        let Some(span) = diagnostic["spans"]
+            .as_array()
+            .and_then(|spans| spans.iter().find(|span| span["is_primary"] == true))
+        else {
+            // Summaries like "aborting due to 2 previous errors".
+            return;
+        };
+        let fingerprint = format!("{:016x}", fnv1a(diagnostic["rendered"].as_str().unwrap_or_default()));
+        if !self.fingerprints.insert(fingerprint.clone()) {
+            return;
+        }
+
+        let code = diagnostic["code"]["code"].as_str();
+        let mut description = diagnostic["message"].as_str().unwrap_or_default().to_string();
+        let mut issue = json!({
+            "type": "issue",
+            "check_name": code.unwrap_or("clippy"),
+            "severity": match diagnostic["level"].as_str() {
+                Some("error" | "error: internal compiler error") => "major",
+                Some("warning") => "minor",
+                _ => "info",
+            },
+            "fingerprint": fingerprint,
+            "location": {
+                "path": span["file_name"],
+                "lines": { "begin": span["line_start"], "end": span["line_end"] },
+            },
+        });
+        if let Some((name, (group, _))) = code
+            .and_then(|code| code.strip_prefix("clippy::"))
+            .and_then(|name| Some((name, clippy_lints::lint_group_and_description(name)?)))
+        {
+            description.push_str(&format!(
+                " (clippy::{name}, {group}: https://rust-lang.github.io/rust-clippy/master/index.html#{name})"
+            ));
+            issue["categories"] = json!([category(group)]);
+        }
+        issue["description"] = description.into();
+        self.issues.push(issue);
+    }
+
+    pub fn into_value(self) -> Value {
+        self.issues.into()
+    }
+}
+
+/// The code quality category of a lint group.
+fn category(group: &str) -> &'static str {
+    match group {
+        "correctness" | "suspicious" => "Bug Risk",
+        "complexity" => "Complexity",
+        "perf" => "Performance",
+        "style" => "Style",
+        "cargo" => "Compatibility",
+        _ => "Clarity",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Report;
+    use serde_json::json;
+
+    fn message(code: &str, level: &str) -> String {
+        json!({
+            "reason": "compiler-message",
+            "message": {
+                "message": "unneeded `return` statement",
+                "code": { "code": code, "explanation": null },
+                "level": level,
+                "spans": [{
+                    "file_name": "src/main.rs",
+                    "line_start": 2,
+                    "line_end": 3,
+                    "column_start": 5,
+                    "column_end": 17,
+                    "is_primary": true,
+                }],
+                "children": [],
+                "rendered": format!("{code} rendered"),
+            },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn convert() {
+        let mut report = Report::default();
+        report.add_message(&message("clippy::needless_return", "warning"));
+        report.add_message(&message("clippy::needless_return", "warning"));
+        report.add_message(&message("dead_code", "error"));
+        report.add_message(r#"{"reason":"build-finished","success":true}"#);
+        let report = report.into_value();
+        let issues = report.as_array().unwrap();
+        assert_eq!(issues.len(), 2);
+
+        assert_eq!(issues[0]["check_name"], "clippy::needless_return");
+        assert_eq!(issues[0]["severity"], "minor");
+        assert_eq!(issues[0]["categories"], json!(["Style"]));
+        assert_eq!(
+            issues[0]["description"],
+            "unneeded `return` statement (clippy::needless_return, style: \
+             https://rust-lang.github.io/rust-clippy/master/index.html#needless_return)"
+        );
+        assert_eq!(issues[0]["location"]["path"], "src/main.rs");
+        assert_eq!(issues[0]["location"]["lines"], json!({ "begin": 2, "end": 3 }));
+
+        assert_eq!(issues[1]["severity"], "major");
+        assert_eq!(issues[1]["description"], "unneeded `return` statement");
+        assert!(issues[1].get("categories").is_none());
+        assert_ne!(issues[0]["fingerprint"], issues[1]["fingerprint"]);
+    }
+}
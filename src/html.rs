@@ -0,0 +1,524 @@
+//! Rendering of the JSON messages emitted by `cargo --message-format=json` as a standalone HTML
+//! report, for `--message-format=html`.
+
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+use std::ops::Range;
+use std::path::Path;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em auto; max-width: 70em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: left; }
+td.count { text-align: right; }
+.diagnostic { border-left: 4px solid #e0a000; margin: 1em 0; padding-left: 1em; }
+.diagnostic.error { border-color: #d02020; }
+.diagnostic.note, .diagnostic.help { border-color: #2060d0; }
+.level { font-weight: bold; }
+.location { color: #666; }
+pre { background: #f6f6f6; padding: 0.5em; overflow-x: auto; }
+.line-number { color: #999; user-select: none; }
+.added { background: #e6ffe6; }
+.removed { background: #ffe6e6; }
+mark { background: #fff0a0; }
+.keyword { color: #8020a0; }
+.string { color: #208020; }
+.number, .lifetime { color: #b05000; }
+.comment { color: #888; font-style: italic; }
+.macro, .type { color: #2060b0; }
+";
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+];
+
+/// A diagnostic of the report.
+struct Diagnostic {
+    level: String,
+    message: String,
+    /// The lint, like `clippy::needless_return`, or an empty string for errors without a code.
+    lint: String,
+    module: String,
+    file: String,
+    line: u64,
+    column: u64,
+    /// The highlighted source code of the primary span.
+    snippet: String,
+    /// The message and the highlighted diff of each suggestion.
+    suggestions: Vec<(String, String)>,
+}
+
+/// Collects the diagnostics of the compiler messages and renders them as an HTML report.
+#[derive(Default)]
+pub struct Report {
+    diagnostics: Vec<Diagnostic>,
+    /// The rendered diagnostics, as the same diagnostic can be emitted for several targets.
+    seen: HashSet<String>,
+}
+
+impl Report {
+    /// Adds the diagnostic of a line of the output of cargo, ignoring other messages.
+    pub fn add_message(&mut self, line: &str) {
+        let Ok(message) = serde_json::from_str::<Value>(line) else {
+            return;
+        };
+        if message["reason"] != "compiler-message" {
+            return;
+        }
+        let diagnostic = &message["message"];
+        let Some(span) = diagnostic["spans"]
+            .as_array()
+            .and_then(|spans| spans.iter().find(|span| span["is_primary"] == true))
+        else {
+            // Summaries like "aborting due to 2 previous errors".
+            return;
+        };
+        if !self
+            .seen
+            .insert(diagnostic["rendered"].as_str().unwrap_or_default().to_string())
+        {
+            return;
+        }
+        let file = span["file_name"].as_str().unwrap_or_default();
+        let suggestions = diagnostic["children"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|child| Some((str(&child["message"]), suggestion_diff(child)?)))
+            .collect();
+        self.diagnostics.push(Diagnostic {
+            level: str(&diagnostic["level"]),
+            message: str(&diagnostic["message"]),
+            lint: str(&diagnostic["code"]["code"]),
+            module: module(message["target"]["name"].as_str().unwrap_or_default(), file),
+            file: file.to_string(),
+            line: span["line_start"].as_u64().unwrap_or_default(),
+            column: span["column_start"].as_u64().unwrap_or_default(),
+            snippet: snippet(span),
+            suggestions,
+        });
+    }
+
+    pub fn into_html(mut self) -> String {
+        self.diagnostics.sort_by(|a, b| {
+            (&a.lint, &a.module, &a.file, a.line, a.column).cmp(&(&b.lint, &b.module, &b.file, b.line, b.column))
+        });
+        let mut by_lint: BTreeMap<&str, Vec<&Diagnostic>> = BTreeMap::new();
+        let mut by_module: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut by_level: BTreeMap<&str, usize> = BTreeMap::new();
+        for diagnostic in &self.diagnostics {
+            by_lint.entry(&diagnostic.lint).or_default().push(diagnostic);
+            *by_module.entry(&diagnostic.module).or_default() += 1;
+            *by_level.entry(&diagnostic.level).or_default() += 1;
+        }
+        let mut lints: Vec<_> = by_lint.into_iter().collect();
+        lints.sort_by(|(_, a), (_, b)| b.len().cmp(&a.len()));
+
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Clippy report</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+             <h1>Clippy report</h1>\n<p>{} diagnostics, Clippy {}</p>\n",
+            self.diagnostics.len(),
+            env!("CARGO_PKG_VERSION")
+        );
+
+        html.push_str("<h2>By severity</h2>\n<table>\n<tr><th>Severity</th><th>Diagnostics</th></tr>\n");
+        for (level, count) in by_level {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"count\">{count}</td></tr>",
+                escape(level)
+            );
+        }
+        html.push_str(
+            "</table>\n<h2>By lint</h2>\n<table>\n<tr><th>Lint</th><th>Group</th><th>Diagnostics</th></tr>\n",
+        );
+        for (lint, diagnostics) in &lints {
+            let _ = writeln!(
+                html,
+                "<tr><td><a href=\"#{}\">{}</a></td><td>{}</td><td class=\"count\">{}</td></tr>",
+                anchor(lint),
+                lint_name(lint),
+                group(lint),
+                diagnostics.len()
+            );
+        }
+        html.push_str("</table>\n<h2>By module</h2>\n<table>\n<tr><th>Module</th><th>Diagnostics</th></tr>\n");
+        for (module, count) in by_module {
+            let _ = writeln!(
+                html,
+                "<tr><td><code>{}</code></td><td class=\"count\">{count}</td></tr>",
+                escape(module)
+            );
+        }
+        html.push_str("</table>\n");
+
+        for (lint, diagnostics) in lints {
+            let _ = write!(html, "<h2 id=\"{}\">{}", anchor(lint), lint_name(lint));
+            if let Some(name) = lint.strip_prefix("clippy::") {
+                let _ = write!(
+                    html,
+                    " <small>{} (<a href=\"https://rust-lang.github.io/rust-clippy/master/index.html#{name}\">documentation</a>)</small>",
+                    group(lint)
+                );
+            }
+            html.push_str("</h2>\n");
+            for diagnostic in diagnostics {
+                render_diagnostic(&mut html, diagnostic);
+            }
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn render_diagnostic(html: &mut String, diagnostic: &Diagnostic) {
+    let _ = write!(
+        html,
+        "<div class=\"diagnostic {level}\">\n<p><span class=\"level\">{level}</span>: {}<br>\n\
+         <span class=\"location\">{}:{}:{} in <code>{}</code></span></p>\n<pre>{}</pre>\n",
+        escape(&diagnostic.message),
+        escape(&diagnostic.file),
+        diagnostic.line,
+        diagnostic.column,
+        escape(&diagnostic.module),
+        diagnostic.snippet,
+        level = escape(&diagnostic.level),
+    );
+    for (message, diff) in &diagnostic.suggestions {
+        let _ = write!(html, "<p>help: {}</p>\n<pre>{diff}</pre>\n", escape(message));
+    }
+    html.push_str("</div>\n");
+}
+
+fn str(value: &Value) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}
+
+fn lint_name(lint: &str) -> String {
+    if lint.is_empty() {
+        "Errors without a lint".into()
+    } else {
+        format!("<code>{}</code>", escape(lint))
+    }
+}
+
+fn anchor(lint: &str) -> String {
+    if lint.is_empty() {
+        "no-lint".into()
+    } else {
+        escape(&lint.replace("::", "-"))
+    }
+}
+
+fn group(lint: &str) -> &'static str {
+    match lint.strip_prefix("clippy::") {
+        Some(name) => clippy_lints::lint_group_and_description(name).map_or("", |(group, _)| group),
+        None if lint.is_empty() => "",
+        None => "rustc",
+    }
+}
+
+/// Returns the module of a source file of the crate `krate`, like `my_crate::parser::lexer` for
+/// `src/parser/lexer.rs`.
+fn module(krate: &str, file: &str) -> String {
+    let path = Path::new(file).with_extension("");
+    let components: Vec<_> = path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let start = components
+        .iter()
+        .rposition(|component| matches!(component.as_str(), "src" | "tests" | "benches" | "examples"))
+        .map_or(0, |index| index + 1);
+    let mut components = &components[start..];
+    if let [rest @ .., last] = components {
+        if matches!(last.as_str(), "lib" | "main" | "mod") || (rest.is_empty() && *last == krate) {
+            components = rest;
+        }
+    }
+    std::iter::once(krate)
+        .chain(components.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Renders the source lines of a span, with the span marked.
+fn snippet(span: &Value) -> String {
+    let first_line = span["line_start"].as_u64().unwrap_or_default();
+    let mut html = String::new();
+    for (line_number, line) in (first_line..).zip(span["text"].as_array().into_iter().flatten()) {
+        let mark = char_range(line);
+        let _ = writeln!(
+            html,
+            "<span class=\"line-number\">{line_number:>5} </span>{}",
+            highlight(line["text"].as_str().unwrap_or_default(), Some(mark))
+        );
+    }
+    html
+}
+
+/// Returns the range of characters of a line of a span which the span covers.
+fn char_range(line: &Value) -> Range<usize> {
+    let column = |key: &str| {
+        usize::try_from(line[key].as_u64().unwrap_or(1))
+            .unwrap_or(1)
+            .saturating_sub(1)
+    };
+    column("highlight_start")..column("highlight_end")
+}
+
+/// Renders the suggested replacements of a child of a diagnostic as a diff, if it has any. The
+/// replacements in the same lines are applied together.
+fn suggestion_diff(child: &Value) -> Option<String> {
+    // The replaced lines by their file and number, and the replacements with their location
+    let mut lines: BTreeMap<(&str, u64), &str> = BTreeMap::new();
+    let mut replacements = Vec::new();
+    for span in child["spans"].as_array()? {
+        let (Some(file), Some(replacement), Some(first_line)) = (
+            span["file_name"].as_str(),
+            span["suggested_replacement"].as_str(),
+            span["line_start"].as_u64(),
+        ) else {
+            continue;
+        };
+        let text = span["text"].as_array()?;
+        for (number, line) in (first_line..).zip(text) {
+            lines.insert((file, number), line["text"].as_str()?);
+        }
+        let start = (first_line, char_range(text.first()?).start);
+        let end = (first_line + text.len() as u64 - 1, char_range(text.last()?).end);
+        replacements.push((file, start, end, replacement));
+    }
+    replacements.sort_by_key(|&(file, start, ..)| (file, start));
+
+    let mut html = String::new();
+    let mut replacements = replacements.into_iter().peekable();
+    while let Some((file, start, mut end, replacement)) = replacements.next() {
+        let mut group = vec![(start, end, replacement)];
+        while let Some(&(next_file, next_start, next_end, next_replacement)) = replacements.peek() {
+            if next_file != file || next_start.0 > end.0 {
+                break;
+            }
+            group.push((next_start, next_end, next_replacement));
+            end = end.max(next_end);
+            replacements.next();
+        }
+        let original: Vec<&str> = (start.0..=end.0)
+            .filter_map(|line| lines.get(&(file, line)).copied())
+            .collect();
+        let mut replaced: Vec<char> = original.join("\n").chars().collect();
+        // The offset in the joined lines of a line and column
+        let offset = |(line, column): (u64, usize)| {
+            let previous: usize = original
+                .iter()
+                .take(usize::try_from(line - start.0).unwrap_or(usize::MAX))
+                .map(|line| line.chars().count() + 1)
+                .sum();
+            previous + column
+        };
+        // Replacing from the end keeps the offsets of the earlier replacements valid
+        for &(start, end, replacement) in group.iter().rev() {
+            let start = offset(start).min(replaced.len());
+            let end = offset(end).clamp(start, replaced.len());
+            replaced.splice(start..end, replacement.chars());
+        }
+        for line in original {
+            let _ = writeln!(html, "<span class=\"removed\">- {}</span>", highlight(line, None));
+        }
+        for line in replaced.into_iter().collect::<String>().lines() {
+            let _ = writeln!(html, "<span class=\"added\">+ {}</span>", highlight(line, None));
+        }
+    }
+    (!html.is_empty()).then_some(html)
+}
+
+/// Highlights a line of Rust code, marking the characters in `mark`.
+fn highlight(line: &str, mark: Option<Range<usize>>) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mark = mark.filter(|mark| mark.start < mark.end && mark.start < chars.len());
+    let mut html = String::new();
+    for (range, class) in tokens(&chars) {
+        // Split the token where the mark starts and ends, so the elements are nested properly.
+        let mut cuts = vec![range.start, range.end];
+        if let Some(mark) = &mark {
+            cuts.extend(
+                [mark.start, mark.end]
+                    .into_iter()
+                    .filter(|&cut| range.contains(&cut) && cut != range.start),
+            );
+        }
+        cuts.sort_unstable();
+        for piece in cuts.windows(2) {
+            let (start, end) = (piece[0], piece[1]);
+            if mark.as_ref().is_some_and(|mark| mark.start == start) {
+                html.push_str("<mark>");
+            }
+            let text = escape(&chars[start..end].iter().collect::<String>());
+            match class {
+                Some(class) => {
+                    let _ = write!(html, "<span class=\"{class}\">{text}</span>");
+                },
+                None => html.push_str(&text),
+            }
+            if mark.as_ref().is_some_and(|mark| mark.end.min(chars.len()) == end) {
+                html.push_str("</mark>");
+            }
+        }
+    }
+    html
+}
+
+/// Splits a line of Rust code into tokens, with the CSS class of each token.
+fn tokens(line: &[char]) -> Vec<(Range<usize>, Option<&'static str>)> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let skip_while = |mut i: usize, f: &dyn Fn(char) -> bool| {
+        while i < line.len() && f(line[i]) {
+            i += 1;
+        }
+        i
+    };
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < line.len() {
+        let start = i;
+        let c = line[i];
+        let class = if c == '/' && line.get(i + 1) == Some(&'/') {
+            i = line.len();
+            Some("comment")
+        } else if c == '"' {
+            i += 1;
+            while i < line.len() && line[i] != '"' {
+                i += if line[i] == '\\' { 2 } else { 1 };
+            }
+            i = (i + 1).min(line.len());
+            Some("string")
+        } else if c == '\'' && (line.get(i + 1) == Some(&'\\') || line.get(i + 2) == Some(&'\'')) {
+            i = skip_while(i + 2, &|c| c != '\'');
+            i = (i + 1).min(line.len());
+            Some("string")
+        } else if c == '\'' {
+            i = skip_while(i + 1, &is_ident);
+            Some("lifetime")
+        } else if c.is_ascii_digit() {
+            i = skip_while(i, &|c| is_ident(c) || c == '.');
+            Some("number")
+        } else if is_ident(c) {
+            i = skip_while(i, &is_ident);
+            let word: String = line[start..i].iter().collect();
+            if KEYWORDS.contains(&word.as_str()) {
+                Some("keyword")
+            } else if line.get(i) == Some(&'!') {
+                Some("macro")
+            } else if c.is_uppercase() {
+                Some("type")
+            } else {
+                None
+            }
+        } else {
+            i += 1;
+            None
+        };
+        tokens.push((start..i, class));
+    }
+    tokens
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{highlight, module, suggestion_diff, Report};
+    use serde_json::json;
+
+    #[test]
+    fn modules() {
+        assert_eq!(module("my_crate", "src/lib.rs"), "my_crate");
+        assert_eq!(module("my_crate", "src/parser/lexer.rs"), "my_crate::parser::lexer");
+        assert_eq!(module("my_crate", "crates/a/src/parser/mod.rs"), "my_crate::parser");
+        assert_eq!(module("integration", "tests/integration.rs"), "integration");
+        assert_eq!(module("integration", "tests/common/mod.rs"), "integration::common");
+    }
+
+    #[test]
+    fn highlighting() {
+        assert_eq!(
+            highlight("let x = \"a<b\";", Some(8..14)),
+            "<span class=\"keyword\">let</span> x = <mark><span class=\"string\">&quot;a&lt;b&quot;</span>;</mark>"
+        );
+        assert_eq!(
+            highlight("fn f<'a>() {}", Some(3..4)),
+            "<span class=\"keyword\">fn</span> <mark>f</mark>&lt;<span class=\"lifetime\">'a</span>&gt;() {}"
+        );
+        assert_eq!(
+            highlight("vec![1]", Some(1..2)),
+            "<span class=\"macro\">v</span><mark><span class=\"macro\">e</span></mark><span class=\"macro\">c</span>![<span class=\"number\">1</span>]"
+        );
+    }
+
+    #[test]
+    fn suggestions() {
+        let span = |start: u64, end: u64, replacement: &str| {
+            json!({
+                "file_name": "src/lib.rs",
+                "line_start": 2,
+                "suggested_replacement": replacement,
+                "text": [{ "text": "    return 0;", "highlight_start": start, "highlight_end": end }],
+            })
+        };
+        let diff = suggestion_diff(&json!({ "spans": [span(13, 14, ""), span(5, 12, "")] }));
+        assert_eq!(
+            diff.as_deref(),
+            Some(
+                "<span class=\"removed\">-     <span class=\"keyword\">return</span> <span class=\"number\">0</span>;</span>\n\
+                 <span class=\"added\">+     <span class=\"number\">0</span></span>\n"
+            )
+        );
+        assert_eq!(suggestion_diff(&json!({ "spans": [] })), None);
+    }
+
+    #[test]
+    fn report() {
+        let span = |replacement: Option<&str>| {
+            json!({
+                "file_name": "src/lib.rs",
+                "line_start": 2,
+                "column_start": 5,
+                "is_primary": true,
+                "suggested_replacement": replacement,
+                "text": [{ "text": "    return 0;", "highlight_start": 5, "highlight_end": 14 }],
+            })
+        };
+        let message = json!({
+            "reason": "compiler-message",
+            "target": { "name": "my_crate" },
+            "message": {
+                "message": "unneeded `return` statement",
+                "code": { "code": "clippy::needless_return" },
+                "level": "warning",
+                "spans": [span(None)],
+                "children": [{ "message": "remove `return`", "spans": [span(Some("0"))] }],
+                "rendered": "rendered",
+            },
+        })
+        .to_string();
+        let mut report = Report::default();
+        report.add_message(&message);
+        report.add_message(&message);
+        report.add_message(r#"{"reason":"build-finished","success":true}"#);
+        let html = report.into_html();
+        assert!(html.contains("<p>1 diagnostics, Clippy"));
+        assert!(html.contains("<h2 id=\"clippy-needless_return\"><code>clippy::needless_return</code> <small>style"));
+        assert!(html.contains("src/lib.rs:2:5 in <code>my_crate</code>"));
+        assert!(html.contains("<span class=\"removed\">-     <span class=\"keyword\">return</span> <span class=\"number\">0</span>;</span>"));
+        assert!(html.contains("<span class=\"added\">+     <span class=\"number\">0</span></span>"));
+    }
+}
@@ -3,10 +3,23 @@
 #![warn(rust_2018_idioms, unused_lifetimes)]
 
 use std::env;
-use std::path::PathBuf;
-use std::process::{self, Command};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
 
-use anstream::println;
+use anstream::{eprint, eprintln, println};
+
+mod aggregate;
+mod diff;
+mod fixes;
+mod github;
+mod gitlab;
+mod html;
+mod review;
+mod sarif;
+mod severity;
+mod snapshot;
+mod summary;
 
 #[allow(clippy::ignored_unit_patterns)]
 fn show_help() {
@@ -31,6 +44,25 @@ pub fn main() {
         return;
     }
 
+    if env::args().any(|a| a == "--dump-config-schema") {
+        println!("{:#}", clippy_config::get_configuration_schema());
+        return;
+    }
+
+    if let Some(pos) = env::args().position(|a| a == "--dump-lints" || a.starts_with("--dump-lints=")) {
+        let format = env::args()
+            .nth(pos)
+            .and_then(|arg| arg.strip_prefix("--dump-lints=").map(String::from))
+            .or_else(|| env::args().nth(pos + 1));
+        if format.as_deref() == Some("json") {
+            println!("{:#}", lints_json());
+        } else {
+            eprintln!("`--dump-lints` expects the format `json`");
+            process::exit(1);
+        }
+        return;
+    }
+
     if let Some(pos) = env::args().position(|a| a == "--explain") {
         if let Some(mut lint) = env::args().nth(pos + 1) {
             lint.make_ascii_lowercase();
@@ -48,10 +80,43 @@ pub fn main() {
     }
 }
 
+#[allow(clippy::struct_excessive_bools)]
 struct ClippyCmd {
     cargo_subcommand: &'static str,
     args: Vec<String>,
     clippy_args: Vec<String>,
+    profile: Option<String>,
+    /// The `--message-format` which was passed, which is handled by Clippy if the output of cargo
+    /// is processed.
+    message_format: Option<String>,
+    /// The git revision passed to `--diff-base`.
+    diff_base: Option<String>,
+    diff_include_crate_level: bool,
+    /// The lints passed to `--only`, without the `clippy::` prefix.
+    only: Vec<String>,
+    /// The lists passed to `--warn-budget`, like `clippy::todo=5,clippy::unwrap_used=0`.
+    warn_budget: Vec<String>,
+    /// Whether `--interactive` was passed, to review the suggestions of `--fix`.
+    interactive: bool,
+    /// The lints passed to `--fix-maybe-incorrect`, without the `clippy::` prefix.
+    fix_maybe_incorrect: Vec<String>,
+    /// Whether `--summary` was passed, to print the number of warnings of each lint at the end.
+    summary: bool,
+    /// The file passed to `--save-snapshot`.
+    save_snapshot: Option<String>,
+    /// The snapshot passed to `--deny-new`, whose warnings are hidden.
+    deny_new: Option<String>,
+    /// Whether `--aggregate` was passed, to merge the identical diagnostics of the crates.
+    aggregate: bool,
+    /// The lints passed to `--annotate-allows`, without the `clippy::` prefix.
+    annotate_allows: Vec<String>,
+    /// Whether `--severity-exit-codes` was passed, to exit with a code depending on the most severe
+    /// diagnostic.
+    severity_exit_codes: bool,
+    /// The number of threads passed to `--lint-threads`.
+    lint_threads: Option<String>,
+    /// The file passed to `--export-fixes`.
+    export_fixes: Option<String>,
 }
 
 impl ClippyCmd {
@@ -62,33 +127,175 @@ impl ClippyCmd {
         let mut cargo_subcommand = "check";
         let mut args = vec![];
         let mut clippy_args: Vec<String> = vec![];
+        let mut profile = None;
+        let mut message_format = None;
+        let mut diff_base = None;
+        let mut diff_include_crate_level = false;
+        let mut only = Vec::new();
+        let mut warn_budget = Vec::new();
+        let mut interactive = false;
+        let mut fix_maybe_incorrect = Vec::new();
+        let mut annotate_allows = Vec::new();
+        let mut save_snapshot = None;
+        let mut deny_new = None;
+        let mut lint_threads = None;
+        let mut export_fixes = None;
 
-        for arg in old_args.by_ref() {
+        while let Some(arg) = old_args.next() {
+            if let Some(value) = flag_value(&arg, "--clippy-profile", &mut old_args) {
+                profile = Some(value);
+                continue;
+            } else if let Some(value) = flag_value(&arg, "--message-format", &mut old_args) {
+                message_format = Some(value);
+                continue;
+            } else if let Some(value) = flag_value(&arg, "--diff-base", &mut old_args) {
+                diff_base = Some(value);
+                continue;
+            } else if let Some(value) = flag_value(&arg, "--only", &mut old_args) {
+                only.extend(parse_lint_list(&value));
+                continue;
+            } else if let Some(value) = flag_value(&arg, "--fix-maybe-incorrect", &mut old_args) {
+                fix_maybe_incorrect.extend(parse_lint_list(&value));
+                continue;
+            } else if let Some(value) = flag_value(&arg, "--annotate-allows", &mut old_args) {
+                annotate_allows.extend(parse_lint_list(&value));
+                continue;
+            } else if let Some(value) = flag_value(&arg, "--warn-budget", &mut old_args) {
+                warn_budget.push(value);
+                continue;
+            } else if let Some(value) = flag_value(&arg, "--save-snapshot", &mut old_args) {
+                save_snapshot = Some(value);
+                continue;
+            } else if let Some(value) = flag_value(&arg, "--deny-new", &mut old_args) {
+                deny_new = Some(value);
+                continue;
+            } else if let Some(value) = flag_value(&arg, "--lint-threads", &mut old_args) {
+                lint_threads = Some(value);
+                continue;
+            } else if let Some(value) = flag_value(&arg, "--export-fixes", &mut old_args) {
+                export_fixes = Some(value);
+                continue;
+            }
             match arg.as_str() {
-                "--fix" => {
-                    cargo_subcommand = "fix";
-                    continue;
-                },
-                "--no-deps" => {
-                    clippy_args.push("--no-deps".into());
-                    continue;
-                },
+                "--fix" => cargo_subcommand = "fix",
+                "--no-deps" => clippy_args.push("--no-deps".into()),
+                "--interactive" => interactive = true,
+                "--summary"
+                | "--lint-cache"
+                | "--lint-profile"
+                | "--collapse-macro-diagnostics"
+                | "--doctests"
+                | "--aggregate"
+                | "--severity-exit-codes" => clippy_args.push(arg),
+                "--diff-include-crate-level" => diff_include_crate_level = true,
                 "--" => break,
-                _ => {},
+                _ => args.push(arg),
             }
-
-            args.push(arg);
         }
 
-        clippy_args.append(&mut (old_args.collect()));
-        if cargo_subcommand == "fix" && !clippy_args.iter().any(|arg| arg == "--no-deps") {
-            clippy_args.push("--no-deps".into());
+        // The configuration overrides are passed after `--`, like the lint levels
+        while let Some(arg) = old_args.next() {
+            match flag_value(&arg, "--clippy-config", &mut old_args) {
+                Some(value) => clippy_args.push(format!("--clippy-config={value}")),
+                None => clippy_args.push(arg),
+            }
         }
 
-        Self {
+        let summary = clippy_args.iter().any(|arg| arg == "--summary");
+        let aggregate = clippy_args.iter().any(|arg| arg == "--aggregate");
+        let severity_exit_codes = clippy_args.iter().any(|arg| arg == "--severity-exit-codes");
+        let mut cmd = Self {
             cargo_subcommand,
             args,
             clippy_args,
+            profile,
+            message_format,
+            diff_base,
+            diff_include_crate_level,
+            only,
+            warn_budget,
+            interactive,
+            fix_maybe_incorrect,
+            summary,
+            save_snapshot,
+            deny_new,
+            aggregate,
+            annotate_allows,
+            severity_exit_codes,
+            lint_threads,
+            export_fixes,
+        };
+        cmd.forward_lint_flags();
+        cmd
+    }
+
+    /// Passes the flags about the lints to the driver.
+    fn forward_lint_flags(&mut self) {
+        // The allows are inserted by `cargo fix`, and only the passes of the annotated lints run.
+        // The lints are warned about, as they may be allowed by default.
+        if !self.annotate_allows.is_empty() {
+            self.cargo_subcommand = "fix";
+            self.only.extend(self.annotate_allows.iter().cloned());
+            self.clippy_args
+                .extend(self.annotate_allows.iter().map(|lint| format!("-Wclippy::{lint}")));
+            self.clippy_args
+                .push(format!("--annotate-allows={}", self.annotate_allows.join(",")));
+        }
+        if self.cargo_subcommand == "fix" && !self.clippy_args.iter().any(|arg| arg == "--no-deps") {
+            self.clippy_args.push("--no-deps".into());
+        }
+        if !self.only.is_empty() {
+            self.clippy_args.push(format!("--only={}", self.only.join(",")));
+        }
+        if !self.fix_maybe_incorrect.is_empty() {
+            self.clippy_args
+                .push(format!("--fix-maybe-incorrect={}", self.fix_maybe_incorrect.join(",")));
+        }
+    }
+
+    fn is_sarif(&self) -> bool {
+        self.message_format.as_deref() == Some("sarif")
+    }
+
+    fn is_html(&self) -> bool {
+        self.message_format.as_deref() == Some("html")
+    }
+
+    fn is_github(&self) -> bool {
+        self.message_format.as_deref() == Some("github")
+    }
+
+    fn is_gitlab(&self) -> bool {
+        self.message_format.as_deref() == Some("gitlab")
+    }
+
+    /// Whether the JSON output of cargo is processed by Clippy, rather than printed directly.
+    fn processes_output(&self) -> bool {
+        self.is_sarif()
+            || self.is_html()
+            || self.is_github()
+            || self.is_gitlab()
+            || self.interactive
+            || self.summary
+            || self.diff_base.is_some()
+            || self.save_snapshot.is_some()
+            || self.deny_new.is_some()
+            || self.aggregate
+            || self.severity_exit_codes
+            || self.export_fixes.is_some()
+    }
+
+    /// Returns the `--message-format` passed to cargo.
+    fn cargo_message_format(&self) -> Option<String> {
+        let format = self.message_format.as_deref();
+        if !self.processes_output() || format.is_some_and(|format| format.starts_with("json")) {
+            format.map(String::from)
+        } else if format == Some("short") {
+            Some("json-diagnostic-short,json-diagnostic-rendered-ansi".into())
+        } else if self.is_sarif() || self.is_html() || self.is_github() || self.is_gitlab() {
+            Some("json".into())
+        } else {
+            Some("json-diagnostic-rendered-ansi".into())
         }
     }
 
@@ -116,35 +323,422 @@ impl ClippyCmd {
 
         cmd.env("RUSTC_WORKSPACE_WRAPPER", Self::path())
             .env("CLIPPY_ARGS", clippy_args)
-            .env("CLIPPY_TERMINAL_WIDTH", terminal_width.to_string())
-            .arg(self.cargo_subcommand)
-            .args(&self.args);
+            .env("CLIPPY_TERMINAL_WIDTH", terminal_width.to_string());
+
+        // The suggestions to review are collected from `cargo check` and applied by Clippy. Like
+        // `cargo fix`, all the targets are checked by default.
+        if self.interactive {
+            cmd.arg("check").args(&self.args);
+            if !review::selects_targets(&self.args) {
+                cmd.arg("--all-targets");
+            }
+        } else {
+            cmd.arg(self.cargo_subcommand).args(&self.args);
+        }
+
+        if let Some(format) = self.cargo_message_format() {
+            cmd.arg(format!("--message-format={format}"));
+        }
+
+        if let Some(profile) = &self.profile {
+            cmd.env(clippy_config::PROFILE_ENV_VAR, profile);
+        }
 
         cmd
     }
 }
 
+/// Returns the value of `arg` if it's the flag `name`, given either as `name=value` or as `name`
+/// followed by the value.
+fn flag_value(arg: &str, name: &str, args: &mut impl Iterator<Item = String>) -> Option<String> {
+    if arg == name {
+        Some(args.next().unwrap_or_default())
+    } else {
+        arg.strip_prefix(name)?.strip_prefix('=').map(String::from)
+    }
+}
+
+/// Normalizes a lint name like `clippy::needless-return` to `needless_return`.
+fn normalize_lint_name(lint: &str) -> String {
+    let lint = lint.trim().to_ascii_lowercase().replace('-', "_");
+    lint.strip_prefix("clippy::").map(String::from).unwrap_or(lint)
+}
+
+/// Parses a comma separated list of lints like `clippy::needless-return,len_zero`.
+fn parse_lint_list(lints: &str) -> Vec<String> {
+    lints
+        .split(',')
+        .map(normalize_lint_name)
+        .filter(|lint| !lint.is_empty())
+        .collect()
+}
+
+/// Parses a comma separated list of lint budgets like `clippy::todo=5,clippy::unwrap_used=0`.
+fn parse_warn_budget(budgets: &str) -> Result<Vec<(String, u64)>, String> {
+    budgets
+        .split(',')
+        .filter(|budget| !budget.trim().is_empty())
+        .map(|budget| {
+            let Some((lint, count)) = budget.split_once('=') else {
+                return Err(format!(
+                    "`--warn-budget` expects budgets like `clippy::todo=5`, found `{budget}`"
+                ));
+            };
+            let lint = normalize_lint_name(lint);
+            if clippy_lints::lint_group_and_description(&lint).is_none() {
+                return Err(format!("unknown lint `clippy::{lint}` passed to `--warn-budget`"));
+            }
+            match count.trim().parse() {
+                Ok(count) => Ok((lint, count)),
+                Err(_) => Err(format!("invalid budget `{count}` for `clippy::{lint}`")),
+            }
+        })
+        .collect()
+}
+
+/// Checks the arguments which Clippy handles, and passes the lint budgets to the driver.
+fn check_args(cmd: &mut ClippyCmd) -> Result<(), String> {
+    for (lints, flag) in [
+        (&cmd.annotate_allows, "--annotate-allows"),
+        (&cmd.only, "--only"),
+        (&cmd.fix_maybe_incorrect, "--fix-maybe-incorrect"),
+    ] {
+        if let Some(unknown) = lints
+            .iter()
+            .find(|lint| clippy_lints::lint_group_and_description(lint).is_none())
+        {
+            return Err(format!("unknown lint `clippy::{unknown}` passed to `{flag}`"));
+        }
+    }
+    if let Some(threads) = &cmd.lint_threads {
+        check_lint_threads(cmd, threads)?;
+        cmd.clippy_args.push(format!("--lint-threads={threads}"));
+    }
+    if !cmd.warn_budget.is_empty() {
+        let budgets: Vec<_> = parse_warn_budget(&cmd.warn_budget.join(","))?
+            .iter()
+            .map(|(lint, count)| format!("{lint}={count}"))
+            .collect();
+        cmd.clippy_args.push(format!("--warn-budget={}", budgets.join(",")));
+    }
+    if cmd.interactive && cmd.cargo_subcommand != "fix" {
+        return Err("`--interactive` can only be used with `--fix`".into());
+    }
+    if !cmd.fix_maybe_incorrect.is_empty() && cmd.cargo_subcommand != "fix" {
+        return Err("`--fix-maybe-incorrect` can only be used with `--fix`".into());
+    }
+    // The suggestions of the collapsed warnings would be lost, and the ones of the code blocks
+    // can't be applied to the doc comments
+    for flag in ["--collapse-macro-diagnostics", "--doctests"] {
+        if cmd.cargo_subcommand == "fix" && cmd.clippy_args.iter().any(|arg| arg == flag) {
+            return Err(format!("`{flag}` can't be used with `--fix`"));
+        }
+    }
+    Ok(())
+}
+
+fn check_lint_threads(cmd: &ClippyCmd, threads: &str) -> Result<(), String> {
+    if !threads.parse::<usize>().is_ok_and(|threads| threads > 0) {
+        return Err(format!(
+            "`--lint-threads` expects a positive number of threads, found `{threads}`"
+        ));
+    }
+    // These report at the end of each crate, which can be done before its modules are
+    let flags = [
+        "--lint-cache",
+        "--lint-profile",
+        "--summary",
+        "--collapse-macro-diagnostics",
+    ];
+    if let Some(flag) = flags.iter().find(|&flag| cmd.clippy_args.iter().any(|arg| arg == flag)) {
+        return Err(format!("`{flag}` can't be used with `--lint-threads`"));
+    }
+    for (lints, flag) in [
+        (&cmd.warn_budget, "--warn-budget"),
+        (&cmd.annotate_allows, "--annotate-allows"),
+    ] {
+        if !lints.is_empty() {
+            return Err(format!("`{flag}` can't be used with `--lint-threads`"));
+        }
+    }
+    Ok(())
+}
+
+/// A format of `--message-format` which Clippy converts the diagnostics to.
+enum Report {
+    Sarif(sarif::Log),
+    Html(html::Report),
+    Github(github::Annotations),
+    Gitlab(gitlab::Report),
+}
+
+impl Report {
+    fn new(cmd: &ClippyCmd) -> Option<Self> {
+        match cmd.message_format.as_deref()? {
+            "sarif" => Some(Self::Sarif(sarif::Log::default())),
+            "html" => Some(Self::Html(html::Report::default())),
+            "github" => Some(Self::Github(github::Annotations::default())),
+            "gitlab" => Some(Self::Gitlab(gitlab::Report::default())),
+            _ => None,
+        }
+    }
+
+    /// Adds a line of the output of cargo, and its parsed `message`.
+    fn add_message(&mut self, line: &str, message: &serde_json::Value) {
+        match self {
+            Self::Sarif(log) => log.add_message(line),
+            Self::Html(report) => report.add_message(line),
+            Self::Gitlab(report) => report.add_message(line),
+            // The annotations are shown in the files, and the usual output in the log
+            Self::Github(annotations) => {
+                if let Some(annotation) = annotations.annotation(line) {
+                    println!("{annotation}");
+                }
+                if let Some(rendered) = message["message"]["rendered"]
+                    .as_str()
+                    .filter(|_| message["reason"] == "compiler-message")
+                {
+                    eprint!("{rendered}");
+                }
+            },
+        }
+    }
+
+    /// Prints the report once cargo is done.
+    fn finish(self) {
+        match self {
+            Self::Sarif(log) => println!("{:#}", log.into_value()),
+            Self::Html(report) => print!("{}", report.into_html()),
+            Self::Gitlab(report) => println!("{:#}", report.into_value()),
+            Self::Github(_) => {},
+        }
+    }
+}
+
+/// The processing of the JSON output of cargo, by the flags handled by Clippy.
+struct Processors {
+    summary: Option<summary::Summary>,
+    aggregation: Option<aggregate::Aggregation>,
+    filter: Option<diff::Filter>,
+    tracker: Option<snapshot::Tracker>,
+    report: Option<Report>,
+    review: Option<review::Review>,
+    fixes: Option<fixes::Fixes>,
+    /// The most severe diagnostic which was shown, for `--severity-exit-codes`.
+    severity: Option<severity::Severity>,
+    /// Whether the JSON messages are printed, for `--message-format=json`.
+    json: bool,
+}
+
+impl Processors {
+    fn new(cmd: &ClippyCmd) -> Result<Self, String> {
+        let review = if cmd.interactive {
+            Some(review::Review::new(diff::workspace_root()?))
+        } else {
+            None
+        };
+        let filter = match &cmd.diff_base {
+            Some(base) => Some(diff::Filter::new(
+                diff::ChangedLines::from_git(base)?,
+                diff::workspace_root()?,
+                cmd.diff_include_crate_level,
+            )),
+            None => None,
+        };
+        let fixes = if cmd.export_fixes.is_some() {
+            Some(fixes::Fixes::new(diff::workspace_root()?))
+        } else {
+            None
+        };
+        let tracker = if cmd.deny_new.is_some() || cmd.save_snapshot.is_some() {
+            Some(snapshot::Tracker::new(cmd.deny_new.clone(), cmd.save_snapshot.clone())?)
+        } else {
+            None
+        };
+        let report = Report::new(cmd);
+        let json = report.is_none()
+            && cmd
+                .message_format
+                .as_deref()
+                .is_some_and(|format| format.starts_with("json"));
+        Ok(Self {
+            summary: cmd.summary.then(summary::Summary::default),
+            aggregation: cmd.aggregate.then(aggregate::Aggregation::default),
+            filter,
+            tracker,
+            report,
+            review,
+            fixes,
+            severity: cmd.severity_exit_codes.then(severity::Severity::default),
+            json,
+        })
+    }
+
+    /// Processes a line of the output of cargo.
+    fn add_line(&mut self, line: &str) {
+        let message = serde_json::from_str::<serde_json::Value>(line).unwrap_or_default();
+        if self
+            .summary
+            .as_mut()
+            .is_some_and(|summary| summary.add_message(&message))
+            || self
+                .aggregation
+                .as_mut()
+                .is_some_and(|aggregation| aggregation.add_message(&message))
+        {
+            return;
+        }
+        self.output(line, &message);
+    }
+
+    fn output(&mut self, line: &str, message: &serde_json::Value) {
+        let is_diagnostic = message["reason"] == "compiler-message";
+        if is_diagnostic
+            && (self
+                .filter
+                .as_ref()
+                .is_some_and(|filter| !filter.keep(&message["message"]))
+                || self
+                    .tracker
+                    .as_mut()
+                    .is_some_and(|tracker| !tracker.keep(&message["message"])))
+        {
+            return;
+        }
+        if let Some(fixes) = &mut self.fixes {
+            fixes.add_message(message);
+        }
+        if let Some(severity) = self.severity.as_mut().filter(|_| is_diagnostic) {
+            *severity = (*severity).max(severity::Severity::of(&message["message"]));
+        }
+        if let Some(report) = &mut self.report {
+            report.add_message(line, message);
+        } else if let Some(review) = &mut self.review {
+            review.add_message(message);
+            // Only the errors are shown, the warnings are reviewed afterwards
+            if let Some(rendered) = message["message"]["rendered"]
+                .as_str()
+                .filter(|_| is_diagnostic && message["message"]["level"] == "error")
+            {
+                eprint!("{rendered}");
+            }
+        } else if self.json {
+            println!("{line}");
+        } else if let Some(rendered) = message["message"]["rendered"].as_str().filter(|_| is_diagnostic) {
+            eprint!("{rendered}");
+        }
+    }
+
+    /// Prints the diagnostics which were held back, the report and the summary once cargo is done.
+    fn finish_output(&mut self) {
+        if let Some(aggregation) = self.aggregation.take() {
+            for message in aggregation.into_messages() {
+                self.output(&message.to_string(), &message);
+            }
+        }
+        if let Some(report) = self.report.take() {
+            report.finish();
+        }
+        if let Some(summary) = &self.summary {
+            eprint!("{}", summary.render());
+        }
+    }
+}
+
 fn process<I>(old_args: I) -> Result<(), i32>
 where
     I: Iterator<Item = String>,
 {
-    let cmd = ClippyCmd::new(old_args);
+    let mut cmd = ClippyCmd::new(old_args);
 
+    if let Err(err) = check_args(&mut cmd) {
+        eprintln!("error: {err}");
+        return Err(1);
+    }
+    let mut processors = match Processors::new(&cmd) {
+        Ok(processors) => processors,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return Err(1);
+        },
+    };
+    let processes_output = cmd.processes_output();
+    let export_fixes = cmd.export_fixes.clone();
     let mut cmd = cmd.into_std_cmd();
 
-    let exit_status = cmd
-        .spawn()
-        .expect("could not run cargo")
-        .wait()
-        .expect("failed to wait for cargo?");
+    let exit_status = if processes_output {
+        let mut child = cmd.stdout(Stdio::piped()).spawn().expect("could not run cargo");
+        for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+            processors.add_line(&line.expect("failed to read the output of cargo"));
+        }
+        processors.finish_output();
+        child.wait()
+    } else {
+        cmd.spawn().expect("could not run cargo").wait()
+    }
+    .expect("failed to wait for cargo?");
+
+    if let Some(review) = processors.review.filter(|_| exit_status.success()) {
+        if let Err(err) = review.run() {
+            eprintln!("error: could not apply the suggestions: {err}");
+            return Err(1);
+        }
+    }
+
+    if let (Some(fixes), Some(path)) = (&processors.fixes, &export_fixes) {
+        if let Err(err) = fixes.save(Path::new(path)) {
+            eprintln!("error: {err}");
+            return Err(1);
+        }
+    }
+
+    // A failed build would be missing warnings
+    if let Some(tracker) = processors.tracker.filter(|_| exit_status.success()) {
+        if let Err(err) = tracker.finish() {
+            eprintln!("error: {err}");
+            return Err(1);
+        }
+    }
 
-    if exit_status.success() {
+    if let Some(severity) = processors.severity {
+        severity.exit_code(exit_status)
+    } else if exit_status.success() {
         Ok(())
     } else {
         Err(exit_status.code().unwrap_or(-1))
     }
 }
 
+/// Returns the documentation of all the lints, for `--dump-lints=json`.
+fn lints_json() -> serde_json::Value {
+    clippy_lints::lints_metadata()
+        .into_iter()
+        .map(|lint| {
+            let configuration: Vec<_> = lint
+                .configuration
+                .iter()
+                .map(|conf| {
+                    serde_json::json!({
+                        "name": conf.name,
+                        "type": conf.ty,
+                        "default": conf.default,
+                        "doc": conf.doc.replace("\n    ", "\n"),
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "name": lint.name,
+                "group": lint.group,
+                "level": lint.default_level,
+                "version": lint.version,
+                "description": lint.description,
+                "docs": lint.explanation,
+                "configuration": configuration,
+            })
+        })
+        .collect()
+}
+
 #[must_use]
 pub fn help_message() -> &'static str {
     color_print::cstr!(
@@ -156,9 +750,34 @@ pub fn help_message() -> &'static str {
 <green,bold>Common options:</>
     <cyan,bold>--no-deps</>                Run Clippy only on the given crate, without linting the dependencies
     <cyan,bold>--fix</>                    Automatically apply lint suggestions. This flag implies <cyan>--no-deps</> and <cyan>--all-targets</>
+    <cyan,bold>--interactive</>            With <cyan>--fix</>, review each suggestion before applying it
+    <cyan,bold>--annotate-allows</> <cyan><<LINTS>></>  Allow the current warnings of the given lints on their items, like <cyan>--fix</>
+    <cyan,bold>--fix-maybe-incorrect</> <cyan><<LINTS>></>  With <cyan>--fix</>, also apply the suggestions of the given lints which may be incorrect
+    <cyan,bold>--clippy-profile</> <cyan><<NAME>></>  Use the lint levels and configuration of a profile from <cyan>clippy.toml</>
     <cyan,bold>-h</>, <cyan,bold>--help</>               Print this message
     <cyan,bold>-V</>, <cyan,bold>--version</>            Print version info and exit
-    <cyan,bold>--explain [LINT]</>         Print the documentation for a given lint
+    <cyan,bold>--explain [LINT]</>         Print the documentation, group and default level of a given lint
+    <cyan,bold>--dump-config-schema</>     Print the JSON schema of <cyan>clippy.toml</>
+    <cyan,bold>--dump-lints=json</>        Print the documentation, group, level and configuration of every lint as JSON
+    <cyan,bold>--message-format=sarif</>   Print the diagnostics as a SARIF log, e.g. for code scanning
+    <cyan,bold>--message-format=html</>    Print the diagnostics as a standalone HTML report, grouped by lint
+    <cyan,bold>--message-format=github</>  Also print the diagnostics as GitHub Actions annotations
+    <cyan,bold>--message-format=gitlab</>  Print the diagnostics as a GitLab code quality report
+    <cyan,bold>--diff-base</> <cyan><<REV>></>        Only show the diagnostics on the lines changed since the git revision <cyan><<REV>></>
+    <cyan,bold>--diff-include-crate-level</>  With <cyan>--diff-base</>, also show lints outside of Rust files, e.g. in <cyan>Cargo.toml</>
+    <cyan,bold>--lint-cache</>             Reuse the diagnostics of the functions which didn't change since the last run
+    <cyan,bold>--lint-profile</>           Report the time spent in the slowest lint passes
+    <cyan,bold>--warn-budget</> <cyan><<BUDGETS>></>  Fail when a lint is emitted more often than its budget, e.g. <cyan>clippy::todo=5,clippy::unwrap_used=0</>
+    <cyan,bold>--only</> <cyan><<LINTS>></>           Only run the lint passes of the given comma separated lints, and only report these lints
+    <cyan,bold>--doctests</>               Also lint the Rust code blocks of the documentation of libraries
+    <cyan,bold>--save-snapshot</> <cyan><<FILE>></>  Save the warnings to a JSON snapshot, for <cyan>--deny-new</>
+    <cyan,bold>--deny-new</> <cyan><<FILE>></>       Hide the warnings of a snapshot, failing only on new warnings and reporting the fixed ones
+    <cyan,bold>--export-fixes</> <cyan><<FILE>></>  Write the suggestions with their edits to a JSON file, e.g. for editors
+    <cyan,bold>--summary</>                Print the number of warnings of each lint, lint group and crate at the end
+    <cyan,bold>--aggregate</>              Merge the identical diagnostics of the crates, noting the targets and cfgs they were emitted with
+    <cyan,bold>--severity-exit-codes</>    Exit with 2 for warnings, 3 for <cyan>correctness</> and <cyan>suspicious</> lints and 4 for crashes
+    <cyan,bold>--lint-threads</> <cyan><<N>></>     Lint the modules of each crate on <cyan><<N>></> threads
+    <cyan,bold>--collapse-macro-diagnostics</>  Report a warning of a lint in the expansions of a macro only once, with the call sites
 
 See all options with <cyan,bold>cargo check --help</>.
 
@@ -171,6 +790,9 @@ To allow or deny a lint from the command line you can use <cyan,bold>cargo clipp
     <cyan,bold>-D</> / <cyan,bold>--deny</> <cyan>[LINT]</>       Set lint denied
     <cyan,bold>-F</> / <cyan,bold>--forbid</> <cyan>[LINT]</>     Set lint forbidden
 
+The keys of <cyan>clippy.toml</> can be overridden for a single run in the same way, e.g.
+<cyan,bold>cargo clippy -- --clippy-config msrv=1.70 --clippy-config too-many-arguments-threshold=10</>
+
 You can use tool lints to allow or deny lints from your code, e.g.:
 
     <yellow,bold>#[allow(clippy::needless_lifetimes)]</>
@@ -184,7 +806,7 @@ You can use tool lints to allow or deny lints from your code, e.g.:
 }
 #[cfg(test)]
 mod tests {
-    use super::ClippyCmd;
+    use super::{check_args, parse_warn_budget, ClippyCmd};
 
     #[test]
     fn fix() {
@@ -210,6 +832,264 @@ mod tests {
         assert_eq!(cmd.clippy_args.iter().filter(|arg| *arg == "--no-deps").count(), 1);
     }
 
+    #[test]
+    fn profile() {
+        for args in ["cargo clippy --clippy-profile ci", "cargo clippy --clippy-profile=ci"] {
+            let cmd = ClippyCmd::new(args.split_whitespace().map(ToString::to_string));
+            assert_eq!(cmd.profile.as_deref(), Some("ci"));
+            assert!(!cmd.args.iter().any(|arg| arg.contains("clippy-profile")));
+        }
+    }
+
+    #[test]
+    fn sarif() {
+        for args in [
+            "cargo clippy --message-format=sarif",
+            "cargo clippy --message-format sarif",
+        ] {
+            let cmd = ClippyCmd::new(args.split_whitespace().map(ToString::to_string));
+            assert!(cmd.is_sarif());
+            assert!(!cmd.args.iter().any(|arg| arg.contains("message-format")));
+            assert_eq!(cmd.cargo_message_format().as_deref(), Some("json"));
+        }
+        let cmd = ClippyCmd::new(
+            "cargo clippy --message-format short"
+                .split_whitespace()
+                .map(ToString::to_string),
+        );
+        assert!(!cmd.is_sarif());
+        assert_eq!(cmd.cargo_message_format().as_deref(), Some("short"));
+    }
+
+    #[test]
+    fn interactive() {
+        let cmd = ClippyCmd::new("--fix --interactive --lib".split_whitespace().map(ToString::to_string));
+        assert!(cmd.interactive && cmd.processes_output());
+        assert_eq!(cmd.cargo_subcommand, "fix");
+        assert!(cmd.clippy_args.iter().any(|arg| arg == "--no-deps"));
+        let std_cmd = cmd.into_std_cmd();
+        let args: Vec<_> = std_cmd.get_args().collect();
+        assert_eq!(args[..2], ["check", "--lib"]);
+        assert!(!args.contains(&"--all-targets".as_ref()));
+    }
+
+    #[test]
+    fn fix_maybe_incorrect() {
+        let cmd = ClippyCmd::new(
+            "--fix --fix-maybe-incorrect clippy::option-if-let-else,manual_let_else"
+                .split_whitespace()
+                .map(ToString::to_string),
+        );
+        assert_eq!(cmd.fix_maybe_incorrect, ["option_if_let_else", "manual_let_else"]);
+        assert!(
+            cmd.clippy_args
+                .iter()
+                .any(|arg| arg == "--fix-maybe-incorrect=option_if_let_else,manual_let_else")
+        );
+    }
+
+    #[test]
+    fn collapse_macro_diagnostics() {
+        let mut cmd = ClippyCmd::new(["--collapse-macro-diagnostics".to_string()].into_iter());
+        assert_eq!(cmd.clippy_args, ["--collapse-macro-diagnostics"]);
+        assert!(check_args(&mut cmd).is_ok());
+        let mut cmd = ClippyCmd::new(
+            "--fix --collapse-macro-diagnostics"
+                .split_whitespace()
+                .map(ToString::to_string),
+        );
+        assert!(check_args(&mut cmd).is_err());
+    }
+
+    #[test]
+    fn doctests() {
+        let mut cmd = ClippyCmd::new("--doctests --lib".split_whitespace().map(ToString::to_string));
+        assert_eq!(cmd.args, ["--lib"]);
+        assert_eq!(cmd.clippy_args, ["--doctests"]);
+        assert!(check_args(&mut cmd).is_ok());
+        let mut cmd = ClippyCmd::new("--fix --doctests".split_whitespace().map(ToString::to_string));
+        assert!(check_args(&mut cmd).is_err());
+    }
+
+    #[test]
+    fn summary() {
+        let cmd = ClippyCmd::new("--summary -p foo".split_whitespace().map(ToString::to_string));
+        assert!(cmd.summary && cmd.processes_output());
+        assert_eq!(cmd.args, ["-p", "foo"]);
+        assert_eq!(cmd.clippy_args, ["--summary"]);
+    }
+
+    #[test]
+    fn html() {
+        let cmd = ClippyCmd::new(
+            "cargo clippy --message-format=html"
+                .split_whitespace()
+                .map(ToString::to_string),
+        );
+        assert!(cmd.is_html() && cmd.processes_output());
+        assert_eq!(cmd.cargo_message_format().as_deref(), Some("json"));
+    }
+
+    #[test]
+    fn ci_formats() {
+        for format in ["github", "gitlab"] {
+            let cmd = ClippyCmd::new(
+                format!("cargo clippy --message-format {format}")
+                    .split_whitespace()
+                    .map(ToString::to_string),
+            );
+            assert!(cmd.processes_output());
+            assert_eq!(cmd.cargo_message_format().as_deref(), Some("json"));
+        }
+    }
+
+    #[test]
+    fn snapshots() {
+        let args = "--deny-new=snapshot.json --save-snapshot new.json -p foo";
+        let cmd = ClippyCmd::new(args.split_whitespace().map(ToString::to_string));
+        assert_eq!(cmd.deny_new.as_deref(), Some("snapshot.json"));
+        assert_eq!(cmd.save_snapshot.as_deref(), Some("new.json"));
+        assert!(cmd.processes_output());
+        assert_eq!(cmd.args, ["-p", "foo"]);
+    }
+
+    #[test]
+    fn annotate_allows() {
+        let args = "--annotate-allows clippy::unwrap_used,Needless-Return -- -D warnings";
+        let mut cmd = ClippyCmd::new(args.split_whitespace().map(ToString::to_string));
+        assert_eq!(cmd.cargo_subcommand, "fix");
+        assert_eq!(cmd.annotate_allows, ["unwrap_used", "needless_return"]);
+        assert_eq!(cmd.only, ["unwrap_used", "needless_return"]);
+        assert!(check_args(&mut cmd).is_ok());
+        assert_eq!(
+            cmd.clippy_args,
+            [
+                "-D",
+                "warnings",
+                "-Wclippy::unwrap_used",
+                "-Wclippy::needless_return",
+                "--annotate-allows=unwrap_used,needless_return",
+                "--no-deps",
+                "--only=unwrap_used,needless_return"
+            ]
+        );
+
+        let mut cmd = ClippyCmd::new(["--annotate-allows=nope".to_string()].into_iter());
+        assert_eq!(
+            check_args(&mut cmd),
+            Err("unknown lint `clippy::nope` passed to `--annotate-allows`".into())
+        );
+    }
+
+    #[test]
+    fn lint_threads() {
+        let mut cmd = ClippyCmd::new(
+            "--lint-threads 4 --workspace"
+                .split_whitespace()
+                .map(ToString::to_string),
+        );
+        assert!(check_args(&mut cmd).is_ok());
+        assert_eq!(cmd.clippy_args, ["--lint-threads=4"]);
+        assert_eq!(cmd.args, ["--workspace"]);
+
+        let mut cmd = ClippyCmd::new(["--lint-threads=0".to_string()].into_iter());
+        assert_eq!(
+            check_args(&mut cmd),
+            Err("`--lint-threads` expects a positive number of threads, found `0`".into())
+        );
+        let mut cmd = ClippyCmd::new("--lint-threads=2 --summary".split_whitespace().map(ToString::to_string));
+        assert_eq!(
+            check_args(&mut cmd),
+            Err("`--summary` can't be used with `--lint-threads`".into())
+        );
+    }
+
+    #[test]
+    fn severity_exit_codes() {
+        let cmd = ClippyCmd::new(
+            "--severity-exit-codes --workspace"
+                .split_whitespace()
+                .map(ToString::to_string),
+        );
+        assert!(cmd.severity_exit_codes && cmd.processes_output());
+        assert_eq!(cmd.clippy_args, ["--severity-exit-codes"]);
+        assert_eq!(cmd.args, ["--workspace"]);
+    }
+
+    #[test]
+    fn clippy_config() {
+        let args = "--all-targets -- --clippy-config msrv=1.70 -D warnings --clippy-config=too-many-lines-threshold=10";
+        let cmd = ClippyCmd::new(args.split_whitespace().map(ToString::to_string));
+        assert_eq!(cmd.args, ["--all-targets"]);
+        assert_eq!(
+            cmd.clippy_args,
+            [
+                "--clippy-config=msrv=1.70",
+                "-D",
+                "warnings",
+                "--clippy-config=too-many-lines-threshold=10"
+            ]
+        );
+    }
+
+    #[test]
+    fn aggregate() {
+        let cmd = ClippyCmd::new("--aggregate --all-targets".split_whitespace().map(ToString::to_string));
+        assert!(cmd.aggregate && cmd.processes_output());
+        assert_eq!(cmd.clippy_args, ["--aggregate"]);
+        assert_eq!(cmd.args, ["--all-targets"]);
+    }
+
+    #[test]
+    fn diff_base() {
+        let message_format = |args: &str| {
+            let cmd = ClippyCmd::new(args.split_whitespace().map(ToString::to_string));
+            assert_eq!(cmd.diff_base.as_deref(), Some("main"));
+            assert!(
+                !cmd.args
+                    .iter()
+                    .any(|arg| arg.contains("diff-base") || arg.contains("message-format"))
+            );
+            cmd.cargo_message_format()
+        };
+        assert_eq!(
+            message_format("cargo clippy --diff-base main").as_deref(),
+            Some("json-diagnostic-rendered-ansi")
+        );
+        assert_eq!(
+            message_format("cargo clippy --diff-base=main --message-format short").as_deref(),
+            Some("json-diagnostic-short,json-diagnostic-rendered-ansi")
+        );
+        assert_eq!(
+            message_format("cargo clippy --diff-base main --message-format=json").as_deref(),
+            Some("json")
+        );
+    }
+
+    #[test]
+    fn only() {
+        let args = "cargo clippy --only clippy::needless-return,Len_Zero -- -D warnings";
+        let cmd = ClippyCmd::new(args.split_whitespace().map(ToString::to_string));
+        assert_eq!(cmd.only, ["needless_return", "len_zero"]);
+        assert!(
+            cmd.clippy_args
+                .iter()
+                .any(|arg| arg == "--only=needless_return,len_zero")
+        );
+        assert!(!cmd.args.iter().any(|arg| arg.contains("only")));
+    }
+
+    #[test]
+    fn warn_budget() {
+        assert_eq!(
+            parse_warn_budget("clippy::todo=5, Unwrap-Used=0"),
+            Ok(vec![("todo".into(), 5), ("unwrap_used".into(), 0)])
+        );
+        assert!(parse_warn_budget("clippy::todo").is_err());
+        assert!(parse_warn_budget("clippy::todo=five").is_err());
+        assert!(parse_warn_budget("clippy::nope=1").is_err());
+    }
+
     #[test]
     fn check() {
         let args = "cargo clippy".split_whitespace().map(ToString::to_string);
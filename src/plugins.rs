@@ -0,0 +1,59 @@
+//! Loading of the external lint passes listed in the `plugins` configuration, which is unstable.
+//!
+//! A plugin is a dynamic library exporting the function declared by
+//! `clippy_utils::declare_clippy_plugin!`, which registers its lints and lint passes. As the
+//! library links to the `rustc_driver` of the toolchain, it can only be loaded by a Clippy built
+//! with the same toolchain.
+//!
+//! Loading a plugin runs its code in Clippy, so the plugins are only loaded when
+//! [`ALLOW_ENV_VAR`] is set to `1`, and the configuration files of the crates being linted can't
+//! opt in on their own.
+
+use rustc_lint::LintStore;
+use rustc_session::Session;
+use rustc_span::symbol::Symbol;
+use std::env;
+use std::path::Path;
+
+/// The environment variable which allows the plugins to be loaded.
+pub const ALLOW_ENV_VAR: &str = "CLIPPY_ALLOW_PLUGINS";
+
+/// The name of the function declared by `clippy_utils::declare_clippy_plugin!`.
+const REGISTER_SYMBOL: &[u8] = b"clippy_plugin_register";
+
+/// Loads the plugins, given relative to `conf_dir`, and registers their lint passes.
+pub fn register(sess: &Session, lint_store: &mut LintStore, conf_dir: &Path, plugins: &[String]) {
+    if plugins.is_empty() {
+        return;
+    }
+    if env::var(ALLOW_ENV_VAR).as_deref() != Ok("1") {
+        sess.dcx().err(format!(
+            "the Clippy plugins are only loaded with `{ALLOW_ENV_VAR}=1` set"
+        ));
+        return;
+    }
+    for plugin in plugins {
+        let path = conf_dir.join(plugin);
+        // Trigger a rebuild when a plugin is rebuilt
+        if let Some(path) = path.to_str() {
+            sess.psess.file_depinfo.borrow_mut().insert(Symbol::intern(path));
+        }
+        // SAFETY: the plugin is trusted like a build script or a proc macro, and is required to be
+        // built with the same toolchain, so the Rust ABI of the function matches
+        let result = unsafe {
+            libloading::Library::new(&path).and_then(|library| {
+                let register = *library.get::<fn(&mut LintStore)>(REGISTER_SYMBOL)?;
+                // The lint passes live in the library, so it's never unloaded
+                std::mem::forget(library);
+                Ok(register)
+            })
+        };
+        match result {
+            Ok(register) => register(lint_store),
+            Err(err) => {
+                sess.dcx()
+                    .err(format!("could not load the Clippy plugin `{}`: {err}", path.display()));
+            },
+        }
+    }
+}
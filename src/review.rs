@@ -0,0 +1,367 @@
+//! The review of the suggestions of `cargo clippy --fix --interactive`. The machine applicable
+//! suggestions are collected from the JSON output of cargo, shown one by one, and only the
+//! accepted ones are written to the files.
+
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use std::path::PathBuf;
+
+/// A suggestion of a diagnostic, with all of its replacements in a file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Suggestion {
+    lint: String,
+    message: String,
+    help: String,
+    file: PathBuf,
+    /// The path of the file as reported by cargo, relative to the workspace root.
+    file_name: String,
+    line: u64,
+    /// The replaced byte ranges of the file, with their replacement.
+    replacements: Vec<(Range<usize>, String)>,
+}
+
+/// What the user decided for a suggestion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Answer {
+    Accept,
+    Skip,
+    /// Accepts the suggestion and the following ones of the same lint.
+    AcceptLint,
+    Quit,
+}
+
+/// Collects the machine applicable suggestions of the compiler messages to review them.
+pub struct Review {
+    /// The directory which the paths of the diagnostics are relative to.
+    workspace_root: PathBuf,
+    suggestions: Vec<Suggestion>,
+    seen: HashSet<Suggestion>,
+}
+
+impl Review {
+    pub fn new(workspace_root: PathBuf) -> Self {
+        Self {
+            workspace_root,
+            suggestions: Vec::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Adds the suggestions of a line of the output of cargo, ignoring other messages.
+    pub fn add_message(&mut self, message: &Value) {
+        if message["reason"] != "compiler-message" {
+            return;
+        }
+        let diagnostic = &message["message"];
+        for child in diagnostic["children"].as_array().into_iter().flatten() {
+            let Some(suggestion) = self.suggestion(diagnostic, child) else {
+                continue;
+            };
+            // The same diagnostic is emitted for each target which contains the code.
+            if self.seen.insert(suggestion.clone()) {
+                self.suggestions.push(suggestion);
+            }
+        }
+    }
+
+    /// Returns the suggestion of a child of a diagnostic, if all of its replacements are machine
+    /// applicable and in the same file.
+    fn suggestion(&self, diagnostic: &Value, child: &Value) -> Option<Suggestion> {
+        let spans = child["spans"].as_array().filter(|spans| !spans.is_empty())?;
+        let file_name = spans[0]["file_name"].as_str()?;
+        let mut replacements = Vec::new();
+        for span in spans {
+            if span["suggestion_applicability"] != "MachineApplicable" || span["file_name"] != file_name {
+                return None;
+            }
+            let start = usize::try_from(span["byte_start"].as_u64()?).ok()?;
+            let end = usize::try_from(span["byte_end"].as_u64()?).ok()?;
+            replacements.push((start..end, span["suggested_replacement"].as_str()?.to_string()));
+        }
+        replacements.sort_by_key(|(range, _)| (range.start, range.end));
+        // The paths outside of the workspace, like the ones of dependencies, are absolute.
+        let file = self.workspace_root.join(file_name);
+        if !file.starts_with(&self.workspace_root) {
+            return None;
+        }
+        Some(Suggestion {
+            lint: diagnostic["code"]["code"].as_str().unwrap_or_default().to_string(),
+            message: diagnostic["message"].as_str().unwrap_or_default().to_string(),
+            help: child["message"].as_str().unwrap_or_default().to_string(),
+            file,
+            file_name: file_name.to_string(),
+            line: spans[0]["line_start"].as_u64().unwrap_or_default(),
+            replacements,
+        })
+    }
+
+    /// Asks the user about each suggestion and writes the accepted ones to the files.
+    pub fn run(mut self) -> io::Result<()> {
+        self.suggestions
+            .sort_by_key(|suggestion| (suggestion.file.clone(), suggestion.replacements[0].0.start));
+        let mut contents = BTreeMap::new();
+        for suggestion in &self.suggestions {
+            if !contents.contains_key(&suggestion.file) {
+                contents.insert(suggestion.file.clone(), fs::read_to_string(&suggestion.file)?);
+            }
+        }
+        if self.suggestions.is_empty() {
+            anstream::eprintln!("No machine applicable suggestions to review");
+            return Ok(());
+        }
+
+        let accepted = ask(&self.suggestions, &contents, io::stdin().lock(), anstream::stdout())?;
+        let mut applied = 0;
+        let mut files = 0;
+        for (file, content) in &contents {
+            let replacements: Vec<_> = accepted
+                .iter()
+                .filter(|suggestion| suggestion.file == *file)
+                .flat_map(|suggestion| {
+                    suggestion
+                        .replacements
+                        .iter()
+                        .map(|(range, text)| (range.clone(), text.as_str()))
+                })
+                .collect();
+            if replacements.is_empty() {
+                continue;
+            }
+            let (fixed, count) = apply(content, &replacements);
+            fs::write(file, fixed)?;
+            applied += count;
+            files += 1;
+        }
+        anstream::eprintln!(
+            "Applied {applied} of {} replacements to {files} files",
+            accepted
+                .iter()
+                .map(|suggestion| suggestion.replacements.len())
+                .sum::<usize>()
+        );
+        Ok(())
+    }
+}
+
+/// Shows each suggestion and reads the answer of the user, returning the accepted suggestions.
+fn ask<'a>(
+    suggestions: &'a [Suggestion],
+    contents: &BTreeMap<PathBuf, String>,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<Vec<&'a Suggestion>> {
+    let mut accepted = Vec::new();
+    let mut accepted_lints = HashSet::new();
+    for (index, suggestion) in suggestions.iter().enumerate() {
+        if accepted_lints.contains(&suggestion.lint) {
+            accepted.push(suggestion);
+            continue;
+        }
+        writeln!(
+            output,
+            "{}",
+            color_print::cformat!(
+                "\n<bold>[{}/{}] {}</><bold>: {}</>\n  <blue>--></> {}:{}\n  <cyan>help</>: {}",
+                index + 1,
+                suggestions.len(),
+                if suggestion.lint.is_empty() {
+                    "suggestion"
+                } else {
+                    &suggestion.lint
+                },
+                suggestion.message,
+                suggestion.file_name,
+                suggestion.line,
+                suggestion.help,
+            )
+        )?;
+        if let Some(content) = contents.get(&suggestion.file) {
+            write_diff(&mut output, content, &suggestion.replacements)?;
+        }
+        let answer = loop {
+            write!(
+                output,
+                "Apply this suggestion? [y]es, [n]o, [a]ll of `{}`, [q]uit: ",
+                suggestion.lint
+            )?;
+            output.flush()?;
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                break Answer::Quit;
+            }
+            match line.trim() {
+                "y" | "yes" => break Answer::Accept,
+                "n" | "no" => break Answer::Skip,
+                "a" | "all" => break Answer::AcceptLint,
+                "q" | "quit" => break Answer::Quit,
+                _ => {},
+            }
+        };
+        match answer {
+            Answer::Accept => accepted.push(suggestion),
+            Answer::Skip => {},
+            Answer::AcceptLint => {
+                accepted.push(suggestion);
+                accepted_lints.insert(suggestion.lint.clone());
+            },
+            Answer::Quit => break,
+        }
+    }
+    Ok(accepted)
+}
+
+/// Writes the lines changed by the replacements, before and after applying them.
+fn write_diff(output: &mut impl Write, content: &str, replacements: &[(Range<usize>, String)]) -> io::Result<()> {
+    let Some((lines, new)) = changed_lines(content, replacements) else {
+        return Ok(());
+    };
+    for line in content[lines].lines() {
+        writeln!(output, "{}", color_print::cformat!("<red>- {}</>", line))?;
+    }
+    for line in new.lines() {
+        writeln!(output, "{}", color_print::cformat!("<green>+ {}</>", line))?;
+    }
+    Ok(())
+}
+
+/// Returns the byte range of the lines changed by the replacements, without the last line break,
+/// and these lines after applying them.
+pub fn changed_lines(content: &str, replacements: &[(Range<usize>, String)]) -> Option<(Range<usize>, String)> {
+    let start = replacements.iter().map(|(range, _)| range.start).min()?;
+    let end = replacements.iter().map(|(range, _)| range.end).max()?;
+    let (start, end) = (start.min(content.len()), end.min(content.len()));
+    let line_start = content[..start].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = content[end..].find('\n').map_or(content.len(), |index| end + index);
+    let shifted: Vec<_> = replacements
+        .iter()
+        .map(|(range, text)| (range.start - line_start..range.end - line_start, text.as_str()))
+        .collect();
+    let (new, _) = apply(&content[line_start..line_end], &shifted);
+    Some((line_start..line_end, new))
+}
+
+/// Applies the replacements to `content`, skipping the ones which overlap a previous one or don't
+/// fit in `content`. Returns the new content and the number of applied replacements.
+fn apply(content: &str, replacements: &[(Range<usize>, &str)]) -> (String, usize) {
+    let mut replacements = replacements.to_vec();
+    replacements.sort_by_key(|(range, _)| (range.start, range.end));
+    let mut fixed = String::with_capacity(content.len());
+    let mut position = 0;
+    let mut count = 0;
+    for (range, text) in replacements {
+        if range.start < position || range.end < range.start || content.get(range.clone()).is_none() {
+            continue;
+        }
+        fixed.push_str(&content[position..range.start]);
+        fixed.push_str(text);
+        position = range.end;
+        count += 1;
+    }
+    fixed.push_str(&content[position..]);
+    (fixed, count)
+}
+
+/// Returns whether the cargo arguments select the targets to check.
+pub fn selects_targets(args: &[String]) -> bool {
+    args.iter().any(|arg| {
+        let name = arg.split('=').next().unwrap_or(arg);
+        matches!(
+            name,
+            "--lib"
+                | "--bin"
+                | "--bins"
+                | "--example"
+                | "--examples"
+                | "--test"
+                | "--tests"
+                | "--bench"
+                | "--benches"
+                | "--all-targets"
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, ask, Review, Suggestion};
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use std::io::Cursor;
+    use std::path::PathBuf;
+
+    fn suggestion(lint: &str, start: usize) -> Suggestion {
+        Suggestion {
+            lint: lint.into(),
+            message: "message".into(),
+            help: "help".into(),
+            file: PathBuf::from("src/lib.rs"),
+            file_name: "src/lib.rs".into(),
+            line: 1,
+            replacements: vec![(start..start, "x".into())],
+        }
+    }
+
+    #[test]
+    fn applies_without_overlaps() {
+        let replacements = [(0..3, "x"), (1..2, "y"), (4..4, "z"), (5..99, "w")];
+        assert_eq!(apply("abc def", &replacements), ("x zdef".into(), 2));
+    }
+
+    #[test]
+    fn answers() {
+        let suggestions = [
+            suggestion("clippy::a", 0),
+            suggestion("clippy::b", 1),
+            suggestion("clippy::a", 2),
+            suggestion("clippy::b", 3),
+            suggestion("clippy::a", 4),
+        ];
+        let contents = BTreeMap::from([(PathBuf::from("src/lib.rs"), "abcdef\n".to_string())]);
+        let mut output = Vec::new();
+        let accepted = ask(&suggestions, &contents, Cursor::new("a\nwhat\nn\ny\n"), &mut output).unwrap();
+        assert_eq!(
+            accepted,
+            [&suggestions[0], &suggestions[2], &suggestions[3], &suggestions[4]]
+        );
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("Apply this suggestion?").count(), 4);
+        assert!(output.contains("- abcdef") && output.contains("+ xabcdef"));
+
+        let accepted = ask(&suggestions, &contents, Cursor::new("y\nq\n"), Vec::new()).unwrap();
+        assert_eq!(accepted, [&suggestions[0]]);
+    }
+
+    #[test]
+    fn collects() {
+        let span = |applicability: &str| {
+            json!({
+                "file_name": "src/lib.rs",
+                "byte_start": 4,
+                "byte_end": 11,
+                "line_start": 2,
+                "suggested_replacement": "",
+                "suggestion_applicability": applicability,
+            })
+        };
+        let message = |applicability: &str| {
+            json!({
+                "reason": "compiler-message",
+                "message": {
+                    "message": "unneeded `return` statement",
+                    "code": { "code": "clippy::needless_return" },
+                    "children": [{ "message": "remove `return`", "spans": [span(applicability)] }],
+                },
+            })
+        };
+        let mut review = Review::new(PathBuf::from("/ws"));
+        review.add_message(&message("MachineApplicable"));
+        review.add_message(&message("MachineApplicable"));
+        review.add_message(&message("MaybeIncorrect"));
+        assert_eq!(review.suggestions.len(), 1);
+        assert_eq!(review.suggestions[0].file, PathBuf::from("/ws/src/lib.rs"));
+        assert_eq!(review.suggestions[0].replacements, [(4..11, String::new())]);
+    }
+}
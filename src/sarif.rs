@@ -0,0 +1,233 @@
+//! Conversion of the JSON messages emitted by `cargo --message-format=json` to a
+//! [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) log.
+
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Collects the diagnostics of the compiler messages and turns them into a SARIF log.
+#[derive(Default)]
+pub struct Log {
+    /// The index in `rules` of each `ruleId`.
+    rule_indices: BTreeMap<String, usize>,
+    rules: Vec<Value>,
+    results: Vec<Value>,
+    /// The rendered diagnostics, as the same diagnostic can be emitted for several targets.
+    seen: HashSet<String>,
+}
+
+impl Log {
+    /// Adds the diagnostic of a line of the output of cargo, ignoring other messages.
+    pub fn add_message(&mut self, line: &str) {
+        let Ok(message) = serde_json::from_str::<Value>(line) else {
+            return;
+        };
+        if message["reason"] != "compiler-message" {
+            return;
+        }
+        let diagnostic = &message["message"];
+        let rendered = diagnostic["rendered"].as_str().unwrap_or_default();
+        let Some(location) = primary_span(diagnostic).map(location) else {
+            // Summaries like "aborting due to 2 previous errors".
+            return;
+        };
+        if !self.seen.insert(rendered.to_string()) {
+            return;
+        }
+
+        let mut result = json!({
+            "level": level(diagnostic["level"].as_str().unwrap_or_default()),
+            "message": { "text": diagnostic["message"] },
+            "locations": [location],
+        });
+        if let Some(code) = diagnostic["code"]["code"].as_str() {
+            result["ruleId"] = code.into();
+            result["ruleIndex"] = self.rule_index(code).into();
+        }
+        let fixes: Vec<_> = diagnostic["children"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(fix)
+            .collect();
+        if !fixes.is_empty() {
+            result["fixes"] = fixes.into();
+        }
+        self.results.push(result);
+    }
+
+    fn rule_index(&mut self, code: &str) -> usize {
+        if let Some(&index) = self.rule_indices.get(code) {
+            return index;
+        }
+        let mut rule = json!({ "id": code });
+        if let Some((name, (group, description))) = code
+            .strip_prefix("clippy::")
+            .and_then(|name| Some((name, clippy_lints::lint_group_and_description(name)?)))
+        {
+            rule["shortDescription"] = json!({ "text": description });
+            rule["helpUri"] = format!("https://rust-lang.github.io/rust-clippy/master/index.html#{name}").into();
+            rule["properties"] = json!({ "tags": [group] });
+        }
+        let index = self.rules.len();
+        self.rules.push(rule);
+        self.rule_indices.insert(code.to_string(), index);
+        index
+    }
+
+    pub fn into_value(self) -> Value {
+        json!({
+            "$schema": SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "clippy",
+                        "informationUri": "https://github.com/rust-lang/rust-clippy",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": self.rules,
+                    },
+                },
+                "columnKind": "unicodeCodePoints",
+                "results": self.results,
+            }],
+        })
+    }
+}
+
+fn level(level: &str) -> &'static str {
+    match level {
+        "error" | "error: internal compiler error" => "error",
+        "warning" => "warning",
+        _ => "note",
+    }
+}
+
+fn primary_span(diagnostic: &Value) -> Option<&Value> {
+    diagnostic["spans"]
+        .as_array()?
+        .iter()
+        .find(|span| span["is_primary"] == true)
+}
+
+fn region(span: &Value) -> Value {
+    json!({
+        "startLine": span["line_start"],
+        "startColumn": span["column_start"],
+        "endLine": span["line_end"],
+        "endColumn": span["column_end"],
+    })
+}
+
+fn artifact_location(span: &Value) -> Value {
+    json!({ "uri": span["file_name"], "uriBaseId": "%SRCROOT%" })
+}
+
+fn location(span: &Value) -> Value {
+    json!({
+        "physicalLocation": {
+            "artifactLocation": artifact_location(span),
+            "region": region(span),
+        },
+    })
+}
+
+/// Turns a child of a diagnostic with suggested replacements into a fix.
+fn fix(child: &Value) -> Option<Value> {
+    // The replacements of each file, as a fix has a single change per file.
+    let mut replacements: BTreeMap<&str, (&Value, Vec<Value>)> = BTreeMap::new();
+    for span in child["spans"].as_array()? {
+        if let (Some(file), Some(replacement)) = (span["file_name"].as_str(), span["suggested_replacement"].as_str()) {
+            replacements.entry(file).or_insert((span, Vec::new())).1.push(json!({
+                "deletedRegion": region(span),
+                "insertedContent": { "text": replacement },
+            }));
+        }
+    }
+    (!replacements.is_empty()).then(|| {
+        let changes: Vec<_> = replacements
+            .into_values()
+            .map(|(span, replacements)| {
+                json!({
+                    "artifactLocation": artifact_location(span),
+                    "replacements": replacements,
+                })
+            })
+            .collect();
+        json!({
+            "description": { "text": child["message"] },
+            "artifactChanges": changes,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Log;
+    use serde_json::json;
+
+    fn span(replacement: Option<&str>) -> serde_json::Value {
+        json!({
+            "file_name": "src/main.rs",
+            "line_start": 2,
+            "line_end": 2,
+            "column_start": 5,
+            "column_end": 17,
+            "is_primary": true,
+            "suggested_replacement": replacement,
+        })
+    }
+
+    fn message(code: &str) -> String {
+        json!({
+            "reason": "compiler-message",
+            "message": {
+                "message": "unneeded `return` statement",
+                "code": { "code": code, "explanation": null },
+                "level": "warning",
+                "spans": [span(None)],
+                "children": [{
+                    "message": "remove `return`",
+                    "code": null,
+                    "level": "help",
+                    "spans": [span(Some("x"))],
+                    "children": [],
+                }],
+                "rendered": format!("{code} rendered"),
+            },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn convert() {
+        let mut log = Log::default();
+        log.add_message(&message("clippy::needless_return"));
+        log.add_message(&message("clippy::needless_return"));
+        log.add_message(&message("dead_code"));
+        log.add_message(r#"{"reason":"build-finished","success":true}"#);
+        let log = log.into_value();
+        let run = &log["runs"][0];
+
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "clippy::needless_return");
+        assert_eq!(rules[0]["properties"]["tags"][0], "style");
+        assert_eq!(
+            rules[0]["helpUri"],
+            "https://rust-lang.github.io/rust-clippy/master/index.html#needless_return"
+        );
+        assert_eq!(rules[1], json!({ "id": "dead_code" }));
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(results[1]["ruleIndex"], 1);
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/main.rs");
+        assert_eq!(location["region"]["startColumn"], 5);
+        let change = &results[0]["fixes"][0]["artifactChanges"][0];
+        assert_eq!(change["replacements"][0]["insertedContent"]["text"], "x");
+    }
+}
@@ -0,0 +1,111 @@
+//! The exit codes of `--severity-exit-codes`, by the most severe diagnostic of the run.
+
+use serde_json::Value;
+use std::process::ExitStatus;
+
+/// The exit code when there are only warnings of other lint groups, or of the compiler.
+const MINOR_EXIT_CODE: i32 = 2;
+/// The exit code when there are warnings of the `correctness` or `suspicious` groups.
+const MAJOR_EXIT_CODE: i32 = 3;
+/// The exit code when the compiler or Clippy crashed.
+const INTERNAL_EXIT_CODE: i32 = 4;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    #[default]
+    None,
+    Minor,
+    Major,
+    /// An error which isn't emitted by a lint, e.g. a type error.
+    CompileError,
+    Internal,
+}
+
+impl Severity {
+    /// The severity of a diagnostic of the JSON output.
+    pub fn of(diagnostic: &Value) -> Self {
+        let level = diagnostic["level"].as_str().unwrap_or_default();
+        if level == "error: internal compiler error" {
+            return Self::Internal;
+        }
+        // Summaries like "aborting due to 2 previous errors"
+        if diagnostic["spans"].as_array().map_or(true, Vec::is_empty) || !matches!(level, "error" | "warning") {
+            return Self::None;
+        }
+        // The errors of the compiler have codes like `E0308`, while the lints have their names
+        let lint = diagnostic["code"]["code"].as_str().filter(|code| {
+            !code
+                .strip_prefix('E')
+                .is_some_and(|number| number.chars().all(|c| c.is_ascii_digit()))
+        });
+        match lint.and_then(|lint| lint.strip_prefix("clippy::")) {
+            Some(name)
+                if clippy_lints::lint_group_and_description(name)
+                    .is_some_and(|(group, _)| matches!(group, "correctness" | "suspicious")) =>
+            {
+                Self::Major
+            },
+            _ if lint.is_none() && level == "error" => Self::CompileError,
+            _ => Self::Minor,
+        }
+    }
+
+    /// The result of the run, given the exit status of cargo. A failure of cargo caused by a lint
+    /// denied by the user is reported like a warning of that lint.
+    pub fn exit_code(self, cargo: ExitStatus) -> Result<(), i32> {
+        match self {
+            Self::Internal => Err(INTERNAL_EXIT_CODE),
+            Self::Major => Err(MAJOR_EXIT_CODE),
+            Self::Minor => Err(MINOR_EXIT_CODE),
+            Self::CompileError | Self::None if !cargo.success() => Err(cargo.code().unwrap_or(-1)),
+            Self::CompileError | Self::None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Severity;
+    use serde_json::{json, Value};
+
+    fn diagnostic(level: &str, code: Option<&str>) -> Value {
+        json!({
+            "message": "message",
+            "code": code.map(|code| json!({ "code": code, "explanation": null })),
+            "level": level,
+            "spans": [{ "file_name": "src/main.rs", "is_primary": true }],
+            "children": [],
+        })
+    }
+
+    #[test]
+    fn severity() {
+        assert_eq!(
+            Severity::of(&diagnostic("warning", Some("clippy::needless_return"))),
+            Severity::Minor
+        );
+        assert_eq!(
+            Severity::of(&diagnostic("error", Some("clippy::absurd_extreme_comparisons"))),
+            Severity::Major
+        );
+        assert_eq!(
+            Severity::of(&diagnostic("warning", Some("clippy::suspicious_map"))),
+            Severity::Major
+        );
+        assert_eq!(Severity::of(&diagnostic("warning", Some("dead_code"))), Severity::Minor);
+        assert_eq!(
+            Severity::of(&diagnostic("error", Some("E0308"))),
+            Severity::CompileError
+        );
+        assert_eq!(Severity::of(&diagnostic("error", None)), Severity::CompileError);
+        assert_eq!(Severity::of(&diagnostic("note", None)), Severity::None);
+        assert_eq!(
+            Severity::of(&json!({ "message": "aborting due to 1 previous error", "level": "error", "spans": [] })),
+            Severity::None
+        );
+        assert_eq!(
+            Severity::of(&json!({ "message": "panicked", "level": "error: internal compiler error", "spans": [] })),
+            Severity::Internal
+        );
+    }
+}
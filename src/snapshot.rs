@@ -0,0 +1,330 @@
+//! Snapshots of the warnings of a workspace, for `--save-snapshot` and `--deny-new`.
+//!
+//! A warning is identified by a fingerprint of its lint, file and highlighted source code, without
+//! the line numbers, so that it survives unrelated changes to the file. Identical warnings in the
+//! same file share a fingerprint, and are counted.
+
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// The version of the format of the snapshot files.
+const VERSION: u64 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Finding {
+    lint: String,
+    file: String,
+    /// The highlighted code of the primary span, with the whitespace normalized.
+    snippet: String,
+    message: String,
+    count: u64,
+}
+
+impl Finding {
+    /// Returns the finding of a warning of a lint, or `None` for the errors and other messages.
+    fn from_diagnostic(diagnostic: &Value) -> Option<Self> {
+        if diagnostic["level"] != "warning" {
+            return None;
+        }
+        let lint = diagnostic["code"]["code"].as_str()?;
+        let span = diagnostic["spans"]
+            .as_array()?
+            .iter()
+            .find(|span| span["is_primary"] == true)?;
+        let snippet: Vec<String> = span["text"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|text| {
+                let start = usize::try_from(text["highlight_start"].as_u64()?).ok()?;
+                let end = usize::try_from(text["highlight_end"].as_u64()?).ok()?;
+                let highlighted: String = text["text"]
+                    .as_str()?
+                    .chars()
+                    .skip(start.saturating_sub(1))
+                    .take(end.saturating_sub(start))
+                    .collect();
+                Some(highlighted)
+            })
+            .collect();
+        Some(Self {
+            lint: lint.to_string(),
+            file: span["file_name"].as_str()?.replace('\\', "/"),
+            snippet: snippet.join(" ").split_whitespace().collect::<Vec<_>>().join(" "),
+            message: diagnostic["message"].as_str().unwrap_or_default().to_string(),
+            count: 1,
+        })
+    }
+
+    fn fingerprint(&self) -> String {
+        format!(
+            "{:016x}",
+            fnv1a(&format!("{}\0{}\0{}", self.lint, self.file, self.snippet))
+        )
+    }
+
+    fn describe(&self) -> String {
+        let mut description = format!("{} in {}: `{}`", self.lint, self.file, self.snippet);
+        if self.count > 1 {
+            let _ = write!(description, " ({} times)", self.count);
+        }
+        description
+    }
+}
+
+/// The warnings of a run, by fingerprint.
+#[derive(Debug, Default)]
+pub struct Snapshot {
+    findings: BTreeMap<String, Finding>,
+}
+
+impl Snapshot {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents =
+            std::fs::read_to_string(path).map_err(|err| format!("could not read the snapshot `{path}`: {err}"))?;
+        Self::from_json(&contents).map_err(|err| format!("invalid snapshot `{path}`: {err}"))
+    }
+
+    fn from_json(contents: &str) -> Result<Self, String> {
+        let value: Value = serde_json::from_str(contents).map_err(|err| err.to_string())?;
+        if value["version"] != VERSION {
+            return Err(format!("expected version {VERSION}, found {}", value["version"]));
+        }
+        let mut snapshot = Self::default();
+        for finding in value["findings"].as_array().ok_or("missing `findings`")? {
+            let field = |name: &str| {
+                finding[name]
+                    .as_str()
+                    .map(String::from)
+                    .ok_or_else(|| format!("missing `{name}` in a finding"))
+            };
+            snapshot.findings.insert(
+                field("fingerprint")?,
+                Finding {
+                    lint: field("lint")?,
+                    file: field("file")?,
+                    snippet: field("snippet")?,
+                    message: field("message")?,
+                    count: finding["count"].as_u64().unwrap_or(1),
+                },
+            );
+        }
+        Ok(snapshot)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, format!("{:#}\n", self.to_json()))
+            .map_err(|err| format!("could not write the snapshot `{path}`: {err}"))
+    }
+
+    fn to_json(&self) -> Value {
+        let mut findings: Vec<_> = self.findings.iter().collect();
+        // Sorted by location, to keep the diffs of a snapshot checked into a repository readable
+        findings.sort_by(|(_, a), (_, b)| (&a.file, &a.lint, &a.snippet).cmp(&(&b.file, &b.lint, &b.snippet)));
+        let findings: Vec<_> = findings
+            .into_iter()
+            .map(|(fingerprint, finding)| {
+                json!({
+                    "fingerprint": fingerprint,
+                    "lint": finding.lint,
+                    "file": finding.file,
+                    "snippet": finding.snippet,
+                    "message": finding.message,
+                    "count": finding.count,
+                })
+            })
+            .collect();
+        json!({ "version": VERSION, "findings": findings })
+    }
+
+    fn count(&self, fingerprint: &str) -> u64 {
+        self.findings.get(fingerprint).map_or(0, |finding| finding.count)
+    }
+
+    fn len(&self) -> u64 {
+        self.findings.values().map(|finding| finding.count).sum()
+    }
+}
+
+/// Compares the warnings of the JSON output of cargo to the ones of a stored snapshot.
+#[derive(Default)]
+pub struct Tracker {
+    /// The path and the snapshot passed to `--deny-new`.
+    baseline: Option<(String, Snapshot)>,
+    /// The path passed to `--save-snapshot`.
+    save: Option<String>,
+    current: Snapshot,
+    /// Whether each rendered warning is new, as the same warning can be emitted for several
+    /// targets.
+    seen: HashMap<String, bool>,
+    new: u64,
+}
+
+impl Tracker {
+    pub fn new(deny_new: Option<String>, save_snapshot: Option<String>) -> Result<Self, String> {
+        let baseline = match deny_new {
+            Some(path) => {
+                let snapshot = Snapshot::load(&path)?;
+                Some((path, snapshot))
+            },
+            None => None,
+        };
+        Ok(Self {
+            baseline,
+            save: save_snapshot,
+            ..Self::default()
+        })
+    }
+
+    /// Records a diagnostic of the JSON output, and checks whether it's shown: the warnings of
+    /// the snapshot are hidden, while the new warnings and the errors are shown.
+    pub fn keep(&mut self, diagnostic: &Value) -> bool {
+        let Some(finding) = Finding::from_diagnostic(diagnostic) else {
+            return true;
+        };
+        let rendered = diagnostic["rendered"].as_str().unwrap_or_default();
+        if let Some(&is_new) = self.seen.get(rendered) {
+            return is_new;
+        }
+        let fingerprint = finding.fingerprint();
+        let current = self
+            .current
+            .findings
+            .entry(fingerprint.clone())
+            .or_insert(Finding { count: 0, ..finding });
+        current.count += 1;
+        let count = current.count;
+        let is_new = self
+            .baseline
+            .as_ref()
+            .map_or(true, |(_, baseline)| count > baseline.count(&fingerprint));
+        self.new += u64::from(is_new);
+        self.seen.insert(rendered.to_string(), is_new);
+        is_new
+    }
+
+    /// Prints the comparison and saves the snapshot once cargo is done. Fails if there are new
+    /// warnings.
+    pub fn finish(&self) -> Result<(), String> {
+        eprint!("{}", self.render());
+        if let Some(path) = &self.save {
+            self.current.save(path)?;
+        }
+        match &self.baseline {
+            Some((path, _)) if self.new > 0 => Err(format!(
+                "{} new warning{} not in the snapshot `{path}`",
+                self.new,
+                if self.new == 1 { "" } else { "s" }
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the report of the comparison, listing the warnings of the snapshot which were
+    /// fixed.
+    fn render(&self) -> String {
+        let Some((_, baseline)) = &self.baseline else {
+            return String::new();
+        };
+        let mut out = String::new();
+        let mut fixed: Vec<_> = baseline
+            .findings
+            .iter()
+            .filter_map(|(fingerprint, finding)| {
+                let count = finding.count.saturating_sub(self.current.count(fingerprint));
+                (count > 0).then(|| Finding {
+                    count,
+                    ..finding.clone()
+                })
+            })
+            .collect();
+        fixed.sort_by(|a, b| (&a.file, &a.lint, &a.snippet).cmp(&(&b.file, &b.lint, &b.snippet)));
+        for finding in &fixed {
+            let _ = writeln!(out, "fixed: {}", finding.describe());
+        }
+        let fixed: u64 = fixed.iter().map(|finding| finding.count).sum();
+        let _ = writeln!(
+            out,
+            "clippy snapshot: {} new, {} known, {fixed} fixed warnings",
+            self.new,
+            self.current.len() - self.new,
+        );
+        out
+    }
+}
+
+/// The 64-bit FNV-1a hash of `text`, which is stable across runs and Rust versions, unlike the
+/// hashers of the standard library.
+pub fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Snapshot, Tracker};
+    use serde_json::{json, Value};
+
+    fn warning(code: &str, line: u64, text: &str) -> Value {
+        json!({
+            "message": "unneeded `return` statement",
+            "code": { "code": code, "explanation": null },
+            "level": "warning",
+            "spans": [{
+                "file_name": "src/main.rs",
+                "line_start": line,
+                "line_end": line,
+                "is_primary": true,
+                "text": [{ "text": text, "highlight_start": 5, "highlight_end": text.trim_end().len() + 1 }],
+            }],
+            "children": [],
+            "rendered": format!("{code} at {line}"),
+        })
+    }
+
+    #[test]
+    fn compare() {
+        let mut tracker = Tracker::default();
+        assert!(tracker.keep(&warning("clippy::needless_return", 2, "    return x;")));
+        assert!(tracker.keep(&warning("clippy::needless_return", 8, "    return  x;")));
+        assert!(tracker.keep(&warning("clippy::needless_return", 8, "    return  x;")));
+        assert!(tracker.keep(&warning("clippy::todo", 4, "    todo!()   ")));
+        let snapshot = tracker.current.to_json();
+        assert_eq!(snapshot["findings"].as_array().unwrap().len(), 2);
+        assert_eq!(snapshot["findings"][0]["snippet"], "return x;");
+        assert_eq!(snapshot["findings"][0]["count"], 2);
+
+        // The warnings moved to other lines are still known
+        let mut tracker = Tracker {
+            baseline: Some((
+                "snapshot.json".into(),
+                Snapshot::from_json(&snapshot.to_string()).unwrap(),
+            )),
+            ..Tracker::default()
+        };
+        assert!(!tracker.keep(&warning("clippy::needless_return", 12, "    return x;")));
+        assert!(tracker.keep(&warning("clippy::unwrap_used", 13, "    x.unwrap();")));
+        let mut error = warning("clippy::todo", 4, "    todo!()   ");
+        error["level"] = "error".into();
+        assert!(tracker.keep(&error));
+        assert_eq!(
+            tracker.finish(),
+            Err("1 new warning not in the snapshot `snapshot.json`".into())
+        );
+        assert_eq!(
+            tracker.render(),
+            "fixed: clippy::needless_return in src/main.rs: `return x;`\n\
+             fixed: clippy::todo in src/main.rs: `todo!()`\n\
+             clippy snapshot: 1 new, 1 known, 2 fixed warnings\n"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Snapshot::from_json("{}").is_err());
+        assert!(Snapshot::from_json(r#"{"version":1,"findings":[{"lint":"x"}]}"#).is_err());
+        assert!(Snapshot::from_json(r#"{"version":1,"findings":[]}"#).is_ok());
+    }
+}
@@ -0,0 +1,156 @@
+//! The summary of `cargo clippy --summary`. The driver reports the number of warnings of each lint
+//! in a note at the end of each crate, which is collected from the JSON output of cargo, and the
+//! counts are printed as tables at the end of the run.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// The start of the note of the driver, see `clippy_utils::summary::NOTE_PREFIX`.
+const NOTE_PREFIX: &str = "clippy summary:";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Counts {
+    warnings: u64,
+    suppressed: u64,
+}
+
+impl Counts {
+    fn add(&mut self, other: Self) {
+        self.warnings += other.warnings;
+        self.suppressed += other.suppressed;
+    }
+}
+
+/// Collects the counts of the crates to print them at the end of the run.
+#[derive(Default)]
+pub struct Summary {
+    /// The counts by crate and lint name without the `clippy::` prefix.
+    crates: BTreeMap<String, BTreeMap<String, Counts>>,
+}
+
+impl Summary {
+    /// Collects the counts of a line of the output of cargo. Returns whether the line is the note
+    /// with the counts, which isn't shown.
+    pub fn add_message(&mut self, message: &Value) -> bool {
+        let Some(note) = message["message"]["message"]
+            .as_str()
+            .filter(|_| message["reason"] == "compiler-message" && message["message"]["level"] == "note")
+            .and_then(|text| text.strip_prefix(NOTE_PREFIX))
+        else {
+            return false;
+        };
+        let target = &message["target"];
+        let mut krate = target["name"].as_str().unwrap_or_default().to_string();
+        if let Some(kind) = target["kind"][0].as_str().filter(|kind| !kind.ends_with("lib")) {
+            let _ = write!(krate, " ({kind})");
+        }
+        let lints = self.crates.entry(krate).or_default();
+        for count in note.split_whitespace() {
+            let Some((lint, count)) = count.split_once('=') else {
+                continue;
+            };
+            let Some((warnings, suppressed)) = count.split_once('/') else {
+                continue;
+            };
+            // A library is linted again with its unit tests, so the larger counts are kept
+            let counts = lints.entry(lint.to_string()).or_default();
+            counts.warnings = counts.warnings.max(warnings.parse().unwrap_or(0));
+            counts.suppressed = counts.suppressed.max(suppressed.parse().unwrap_or(0));
+        }
+        true
+    }
+
+    /// Returns the tables of the counts by lint, by lint group and, if several crates were
+    /// linted, by crate.
+    pub fn render(&self) -> String {
+        let mut lints = BTreeMap::<&str, Counts>::new();
+        let mut groups = BTreeMap::<&str, Counts>::new();
+        let mut crates = Vec::new();
+        let mut total = Counts::default();
+        for (krate, counts) in &self.crates {
+            let mut crate_total = Counts::default();
+            for (lint, &count) in counts {
+                let group = clippy_lints::lint_group_and_description(lint).map_or("unknown", |(group, _)| group);
+                lints.entry(lint).or_default().add(count);
+                groups.entry(group).or_default().add(count);
+                crate_total.add(count);
+            }
+            crates.push((krate.as_str(), crate_total));
+            total.add(crate_total);
+        }
+
+        let mut out = String::from("\nClippy summary\n");
+        write_table(&mut out, "lint", "clippy::", lints.into_iter().collect());
+        write_table(&mut out, "group", "clippy::", groups.into_iter().collect());
+        if crates.len() > 1 {
+            write_table(&mut out, "crate", "", crates);
+        }
+        let _ = writeln!(
+            out,
+            "\n{} warning{} and {} suppressed in total",
+            total.warnings,
+            if total.warnings == 1 { "" } else { "s" },
+            total.suppressed,
+        );
+        out
+    }
+}
+
+/// Writes a table of counts, sorted by the number of warnings.
+fn write_table(out: &mut String, title: &str, prefix: &str, mut rows: Vec<(&str, Counts)>) {
+    rows.sort_by(|(a_name, a), (b_name, b)| {
+        (b.warnings, b.suppressed, a_name).cmp(&(a.warnings, a.suppressed, b_name))
+    });
+    let _ = writeln!(out, "\n  warnings  suppressed  {title}");
+    for (name, counts) in rows {
+        let _ = writeln!(
+            out,
+            "  {:>8}  {:>10}  {prefix}{name}",
+            counts.warnings, counts.suppressed
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Summary;
+    use serde_json::json;
+
+    fn note(name: &str, kind: &str, counts: &str) -> serde_json::Value {
+        json!({
+            "reason": "compiler-message",
+            "target": { "name": name, "kind": [kind] },
+            "message": { "message": format!("clippy summary:{counts}"), "level": "note" },
+        })
+    }
+
+    #[test]
+    fn summary() {
+        let mut summary = Summary::default();
+        assert!(summary.add_message(&note("a", "lib", " needless_return=2/1 unnecessary_cast=0/3")));
+        assert!(summary.add_message(&note("a", "lib", " needless_return=3/0")));
+        assert!(summary.add_message(&note("a", "bin", " len_zero=1/0")));
+        assert!(!summary.add_message(&json!({
+            "reason": "compiler-message",
+            "message": { "message": "unneeded `return` statement", "level": "warning" },
+        })));
+
+        let out = summary.render();
+        let lines: Vec<_> = out.lines().map(str::trim).collect();
+        assert_eq!(
+            lines[4..7],
+            [
+                "3           1  clippy::needless_return",
+                "1           0  clippy::len_zero",
+                "0           3  clippy::unnecessary_cast",
+            ]
+        );
+        assert_eq!(
+            lines[9..11],
+            ["4           1  clippy::style", "0           3  clippy::complexity"]
+        );
+        assert_eq!(lines[13..15], ["3           4  a", "1           0  a (bin)"]);
+        assert_eq!(lines[16], "4 warnings and 4 suppressed in total");
+    }
+}
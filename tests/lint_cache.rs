@@ -0,0 +1,164 @@
+#![feature(lazy_cell)]
+
+//! Tests of `cargo clippy --lint-cache`. The messages of the cached diagnostics are marked, so
+//! that the replayed diagnostics can be told apart from the ones emitted by linting again.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+const MESSAGE: &str = "unneeded `return` statement";
+const CACHED_MESSAGE: &str = "unneeded `return` statement (cached)";
+
+const MANIFEST: &str = r#"[package]
+name = "lint_cache_test"
+version = "0.1.0"
+edition = "2021"
+
+[workspace]
+"#;
+
+const SOURCE: &str = "pub fn first() -> u32 {
+    return 1;
+}
+
+pub fn second() -> u32 {
+    return 2;
+}
+";
+
+/// Creates the crate of the test `name` in a fresh directory.
+fn setup(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("lint_cache_test")
+        .join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("Cargo.toml"), MANIFEST).unwrap();
+    fs::write(dir.join("src/lib.rs"), SOURCE).unwrap();
+    dir
+}
+
+/// Runs Clippy with the lint cache, and returns its diagnostics. The source is written again
+/// first, so that Cargo doesn't reuse the diagnostics of the previous run.
+fn clippy(dir: &Path) -> String {
+    let source = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+    fs::write(dir.join("src/lib.rs"), source).unwrap();
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(dir)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", dir.join("target"))
+        .env_remove("CLIPPY_CONF_DIR")
+        .arg("clippy")
+        .arg("--lint-cache")
+        .arg("--")
+        .arg("-Cdebuginfo=0") // disable debuginfo to generate less data in the target dir
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    println!("status: {}", output.status);
+    println!("stderr: {stderr}");
+    assert!(output.status.success());
+    stderr
+}
+
+fn cache_file(dir: &Path) -> PathBuf {
+    fs::read_dir(dir.join("target/debug/clippy-cache"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .expect("no cache was written")
+}
+
+/// Lints the crate, and marks the messages of the diagnostics stored in the cache.
+fn lint_and_mark_cache(dir: &Path) {
+    let stderr = clippy(dir);
+    assert_eq!(stderr.matches(MESSAGE).count(), 2);
+    let path = cache_file(dir);
+    let cache = fs::read_to_string(&path).unwrap();
+    assert_eq!(cache.matches(MESSAGE).count(), 2);
+    fs::write(&path, cache.replace(MESSAGE, CACHED_MESSAGE)).unwrap();
+}
+
+#[test]
+fn replays_unchanged_functions() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let dir = setup("replays_unchanged_functions");
+    lint_and_mark_cache(&dir);
+
+    let stderr = clippy(&dir);
+    assert_eq!(stderr.matches(CACHED_MESSAGE).count(), 2);
+}
+
+#[test]
+fn relints_changed_bodies() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let dir = setup("relints_changed_bodies");
+    lint_and_mark_cache(&dir);
+
+    fs::write(dir.join("src/lib.rs"), SOURCE.replace("return 2;", "return 3;")).unwrap();
+    let stderr = clippy(&dir);
+    // Only the diagnostic of `first` is replayed.
+    assert_eq!(stderr.matches(CACHED_MESSAGE).count(), 1);
+    assert_eq!(stderr.matches(MESSAGE).count(), 2);
+}
+
+#[test]
+fn invalidated_by_configuration() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let dir = setup("invalidated_by_configuration");
+    lint_and_mark_cache(&dir);
+
+    fs::write(dir.join("clippy.toml"), "too-many-lines-threshold = 50\n").unwrap();
+    let stderr = clippy(&dir);
+    assert!(!stderr.contains(CACHED_MESSAGE));
+    assert_eq!(stderr.matches(MESSAGE).count(), 2);
+}
+
+#[test]
+fn invalidated_by_clippy_version() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let dir = setup("invalidated_by_clippy_version");
+    lint_and_mark_cache(&dir);
+
+    // Pretend that the cache was written by another version of Clippy.
+    let path = cache_file(&dir);
+    let cache = fs::read_to_string(&path).unwrap();
+    let version = cache.split(r#""version":""#).nth(1).unwrap().split('"').next().unwrap();
+    fs::write(&path, cache.replace(version, "0.0.0")).unwrap();
+    let stderr = clippy(&dir);
+    assert!(!stderr.contains(CACHED_MESSAGE));
+    assert_eq!(stderr.matches(MESSAGE).count(), 2);
+    assert!(!fs::read_to_string(&path).unwrap().contains("0.0.0"));
+}
+
+#[test]
+fn recovers_from_corrupt_cache() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let dir = setup("recovers_from_corrupt_cache");
+    clippy(&dir);
+
+    let path = cache_file(&dir);
+    fs::write(&path, "{ not json").unwrap();
+    let stderr = clippy(&dir);
+    assert_eq!(stderr.matches(MESSAGE).count(), 2);
+    // The cache is written again, and used by the next run.
+    assert_eq!(fs::read_to_string(&path).unwrap().matches(MESSAGE).count(), 2);
+    lint_and_mark_cache(&dir);
+    let stderr = clippy(&dir);
+    assert_eq!(stderr.matches(CACHED_MESSAGE).count(), 2);
+}
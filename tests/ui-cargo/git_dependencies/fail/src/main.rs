@@ -0,0 +1,3 @@
+#![warn(clippy::git_dependencies)]
+
+fn main() {}
@@ -0,0 +1,5 @@
+#![warn(clippy::naming_conventions)]
+
+fn main() {
+    // test code goes here
+}
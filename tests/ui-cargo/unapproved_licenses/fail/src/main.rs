@@ -0,0 +1,3 @@
+#![warn(clippy::unapproved_licenses)]
+
+fn main() {}
@@ -0,0 +1,44 @@
+#![deny(clippy::internal)]
+#![allow(
+    clippy::missing_clippy_version_attribute,
+    clippy::collapsible_span_lint_calls,
+    clippy::unguarded_span_lint
+)]
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+
+use rustc_hir::{Expr, Item};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+
+declare_tool_lint! {
+    pub clippy::TEST_LINT,
+    Warn,
+    "",
+    report_in_external_macro: true
+}
+
+declare_lint_pass!(Pass => [TEST_LINT]);
+
+impl<'tcx> LateLintPass<'tcx> for Pass {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, _: &'tcx Expr<'_>) {
+        let _ = cx.tcx.hir().items().count();
+        //~^ ERROR: the whole crate is walked each time `check_expr` is called
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'tcx>, _: &'tcx Item<'_>) {
+        for _ in cx.tcx.hir().body_owners() {}
+        //~^ ERROR: the whole crate is walked each time `check_item` is called
+    }
+
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        // Called once
+        let _ = cx.tcx.hir().items().count();
+    }
+}
+
+fn main() {}
@@ -0,0 +1,83 @@
+#![deny(clippy::inconsistent_configuration_lints)]
+#![feature(rustc_private)]
+
+extern crate clippy_config;
+extern crate rustc_lint;
+extern crate rustc_session;
+
+use clippy_config::Conf;
+use rustc_lint::{LateLintPass, LintStore};
+use rustc_session::{declare_lint_pass, declare_tool_lint, impl_lint_pass};
+
+declare_tool_lint! {
+    /// ### What it does
+    /// Checks for functions with more lines than `too-many-lines-threshold`, or the threshold of
+    /// their path in `threshold-overrides`.
+    #[clippy::version = "1.99.0"]
+    pub clippy::TOO_MANY_LINES,
+    Warn,
+    "",
+    report_in_external_macro: true
+}
+
+declare_tool_lint! {
+    /// ### What it does
+    /// Checks for functions with too many arguments, which can be overridden by path with
+    /// `threshold-overrides`.
+    #[clippy::version = "1.99.0"]
+    pub clippy::TOO_MANY_ARGUMENTS,
+    //~^ ERROR: but its documentation doesn't mention it
+    Warn,
+    "",
+    report_in_external_macro: true
+}
+
+declare_tool_lint! {
+    /// ### What it does
+    /// Checks for complex types, without mentioning the configuration, as older lints can.
+    #[clippy::version = "pre 1.29.0"]
+    pub clippy::TYPE_COMPLEXITY,
+    Warn,
+    "",
+    report_in_external_macro: true
+}
+
+declare_tool_lint! {
+    pub clippy::TEST_LINT,
+    Warn,
+    "",
+    report_in_external_macro: true
+}
+
+struct TooManyLines(u64);
+impl_lint_pass!(TooManyLines => [TOO_MANY_LINES]);
+impl LateLintPass<'_> for TooManyLines {}
+
+struct TooManyArguments(u64);
+impl_lint_pass!(TooManyArguments => [TOO_MANY_ARGUMENTS]);
+impl LateLintPass<'_> for TooManyArguments {}
+
+declare_lint_pass!(TypeComplexity => [TYPE_COMPLEXITY]);
+impl LateLintPass<'_> for TypeComplexity {}
+
+struct Test(u64);
+impl_lint_pass!(Test => [TEST_LINT]);
+impl LateLintPass<'_> for Test {}
+
+pub fn register_lints(store: &mut LintStore, conf: &'static Conf) {
+    let Conf {
+        too_many_lines_threshold,
+        too_many_arguments_threshold,
+        type_complexity_threshold,
+        //~^ ERROR: but its lint pass doesn't read it
+        ..
+    } = *conf;
+
+    store.register_late_pass(move |_| Box::new(TooManyLines(too_many_lines_threshold)));
+    store.register_late_pass(move |_| Box::new(TooManyArguments(too_many_arguments_threshold)));
+    store.register_late_pass(move |_| Box::new(Test(too_many_arguments_threshold)));
+    //~^ ERROR: `Test` reads the configuration `too-many-arguments-threshold`
+    store.register_late_pass(|_| Box::new(TypeComplexity));
+}
+
+fn main() {}
@@ -0,0 +1,104 @@
+#![deny(clippy::internal)]
+#![allow(clippy::missing_clippy_version_attribute, clippy::collapsible_span_lint_calls)]
+#![feature(rustc_private, let_chains)]
+
+extern crate clippy_utils;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+
+use clippy_utils::diagnostics::span_lint;
+use rustc_hir::{Expr, ExprKind, Item};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+
+declare_tool_lint! {
+    pub clippy::TEST_LINT,
+    Warn,
+    "",
+    report_in_external_macro: true
+}
+
+declare_lint_pass!(Pass => [TEST_LINT]);
+
+impl<'tcx> LateLintPass<'tcx> for Pass {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if let ExprKind::Lit(_) = expr.kind {
+            span_lint(cx, TEST_LINT, expr.span, "lint message");
+            //~^ ERROR: `span_lint*` is called without checking whether the code comes from a macro expansion
+        }
+    }
+}
+
+declare_lint_pass!(Guarded => [TEST_LINT]);
+
+impl<'tcx> LateLintPass<'tcx> for Guarded {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if let ExprKind::Lit(_) = expr.kind
+            && !in_external_macro(cx.sess(), expr.span)
+        {
+            span_lint(cx, TEST_LINT, expr.span, "lint message");
+        }
+        if expr.span.from_expansion() {
+            return;
+        }
+        span_lint(cx, TEST_LINT, expr.span, "lint message");
+    }
+
+    fn check_expr_post(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        let Some(lit) = lit_of(expr) else {
+            return;
+        };
+        match lit.kind {
+            ExprKind::Lit(_) if !lit.span.from_expansion() => span_lint(cx, TEST_LINT, lit.span, "lint message"),
+            _ => {},
+        }
+        if lit.hir_id == expr.hir_id || in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+        let span = if expr.span.from_expansion() {
+            return;
+        } else {
+            expr.span
+        };
+        span_lint(cx, TEST_LINT, span, "lint message");
+    }
+
+    // Items are rarely produced by macros.
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        span_lint(cx, TEST_LINT, item.span, "lint message");
+    }
+}
+
+declare_lint_pass!(NotDominated => [TEST_LINT]);
+
+impl<'tcx> LateLintPass<'tcx> for NotDominated {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if let ExprKind::Lit(_) = expr.kind {
+            if !expr.span.from_expansion() {
+                span_lint(cx, TEST_LINT, expr.span, "lint message");
+            }
+        } else {
+            span_lint(cx, TEST_LINT, expr.span, "lint message");
+            //~^ ERROR: `span_lint*` is called without checking whether the code comes from a macro expansion
+        }
+    }
+
+    fn check_expr_post(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        // Getting the context of a span doesn't check it
+        let ctxt = expr.span.ctxt();
+        if matches!(expr.kind, ExprKind::Lit(_)) && in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+        span_lint(cx, TEST_LINT, expr.span.with_ctxt(ctxt), "lint message");
+        //~^ ERROR: `span_lint*` is called without checking whether the code comes from a macro
+    }
+}
+
+fn lit_of<'tcx>(expr: &'tcx Expr<'tcx>) -> Option<&'tcx Expr<'tcx>> {
+    Some(expr)
+}
+
+fn main() {}
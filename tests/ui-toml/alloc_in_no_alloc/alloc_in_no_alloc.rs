@@ -0,0 +1,33 @@
+#![warn(clippy::alloc_in_no_alloc)]
+
+mod dsp {
+    pub fn gain(samples: &mut [f32], gain: f32) {
+        for sample in samples {
+            *sample *= gain;
+        }
+    }
+
+    pub fn history(samples: &[f32]) -> Vec<f32> {
+        samples.to_vec()
+        //~^ alloc_in_no_alloc
+    }
+
+    pub mod filters {
+        pub fn label(cutoff: u32) -> String {
+            crate::ui::describe(cutoff)
+            //~^ alloc_in_no_alloc
+        }
+    }
+}
+
+mod ui {
+    pub fn describe(cutoff: u32) -> String {
+        format!("{cutoff} Hz")
+    }
+
+    pub fn history(samples: &[f32]) -> Vec<f32> {
+        samples.to_vec()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+#![warn(clippy::ambient_randomness)]
+
+mod entropy {
+    pub fn seed() -> u64 {
+        4
+    }
+}
+
+mod setup {
+    pub fn seed() -> u64 {
+        crate::entropy::seed()
+    }
+
+    mod nested {
+        fn seeds() -> Vec<u64> {
+            std::iter::repeat_with(crate::entropy::seed).take(2).collect()
+        }
+    }
+}
+
+fn main() {
+    let _ = entropy::seed();
+    //~^ ERROR: use of the ambient source of randomness `crate::entropy::seed`
+    let _: Vec<u64> = std::iter::repeat_with(entropy::seed).take(2).collect();
+    //~^ ERROR: use of the ambient source of randomness `crate::entropy::seed`
+
+    let _ = setup::seed();
+}
@@ -0,0 +1,113 @@
+#![warn(clippy::arithmetic_side_effects)]
+
+use std::ops::{Add, Div, Mul, Neg};
+use std::time::Duration;
+
+mod num {
+    #[derive(Clone, Copy)]
+    pub struct Saturated(pub u32);
+
+    impl std::ops::Add for Saturated {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self {
+            Self(self.0.saturating_add(rhs.0))
+        }
+    }
+
+    impl std::ops::Div<u32> for Saturated {
+        type Output = Self;
+        fn div(self, rhs: u32) -> Self {
+            Self(self.0.checked_div(rhs).unwrap_or(u32::MAX))
+        }
+    }
+}
+
+type Sat = num::Saturated;
+
+#[derive(Clone, Copy)]
+struct Meters(f64);
+
+impl Mul<f64> for Meters {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self(self.0 * rhs)
+    }
+}
+
+impl Mul<Meters> for Meters {
+    type Output = Self;
+    fn mul(self, rhs: Meters) -> Self {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl Add for Meters {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Div<f64> for Meters {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        Self(self.0 / rhs)
+    }
+}
+
+impl Neg for Meters {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Area(f64);
+
+impl Add for Area {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Mul for Area {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0)
+    }
+}
+
+fn duration(mut a: Duration, b: Duration) {
+    let _ = a + b;
+    let _ = a - b;
+    a += b;
+    let _ = a * 2;
+    //~^ ERROR: arithmetic operation that can potentially result in unexpected side-effects
+}
+
+fn meters(m: Meters, n: f64) {
+    let _ = m * n;
+    let _ = -m;
+    let _ = m * m;
+    //~^ ERROR: arithmetic operation that can potentially result in unexpected side-effects
+    let _ = m + m;
+    //~^ ERROR: arithmetic operation that can potentially result in unexpected side-effects
+    let _ = m / n;
+    //~^ ERROR: arithmetic operation that can potentially result in unexpected side-effects
+}
+
+// `Area` is allowed, but its configured operators take precedence
+fn area(a: Area, b: Area) {
+    let _ = a + b;
+    let _ = a * b;
+    //~^ ERROR: arithmetic operation that can potentially result in unexpected side-effects
+}
+
+fn wrappers(a: num::Saturated, b: Sat, n: u32) {
+    let _ = a + b;
+    let _ = b / n;
+}
+
+fn main() {}
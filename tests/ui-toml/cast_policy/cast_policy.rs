@@ -0,0 +1,22 @@
+//@no-rustfix
+#![warn(clippy::cast_policy)]
+#![allow(clippy::cast_lossless, clippy::cast_possible_truncation)]
+
+fn allowed(a: u8, b: u64, c: *const u8) {
+    let _ = a as u32;
+    let _ = c as usize;
+    let _ = c as *const i8;
+    // Lossless with 64-bit pointers
+    let _ = b as usize;
+}
+
+fn linted(a: i32, b: u64, c: f64) {
+    let _ = a as u32;
+    //~^ ERROR: sign-changing cast from `i32` to `u32`
+    let _ = b as u32;
+    //~^ ERROR: truncating cast from `u64` to `u32`
+    let _ = c as i64;
+    //~^ ERROR: cast between a float and an integer from `f64` to `i64`
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+//@compile-flags: --cfg feature=\"ffi\"
+#![warn(clippy::disallowed_types)]
+
+use std::collections::HashMap;
+//~^ ERROR: `std::collections::HashMap` is not allowed according to config
+use std::ffi::CString;
+//~^ ERROR: `std::ffi::CString` is not allowed according to config
+use std::time::Instant;
+
+fn main() {
+    let _: Option<String> = None;
+}
@@ -0,0 +1,6 @@
+//@no-rustfix
+//@error-in-other-file: invalid cfg
+//@error-in-other-file: conditional sections cannot be nested
+//@error-in-other-file: invalid type: integer `42`, expected a sequence
+
+fn main() {}
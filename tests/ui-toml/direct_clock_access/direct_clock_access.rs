@@ -0,0 +1,44 @@
+#![warn(clippy::direct_clock_access)]
+
+use std::time::{Instant, SystemTime};
+
+mod fake_chrono {
+    pub struct Utc;
+
+    impl Utc {
+        pub fn now() -> u64 {
+            0
+        }
+    }
+}
+
+mod time {
+    use std::time::SystemTime;
+
+    pub trait Clock {
+        fn now(&self) -> SystemTime;
+    }
+
+    pub struct SystemClock;
+
+    impl Clock for SystemClock {
+        fn now(&self) -> SystemTime {
+            SystemTime::now()
+        }
+    }
+
+    mod nested {
+        fn now() -> u64 {
+            crate::fake_chrono::Utc::now()
+        }
+    }
+}
+
+fn main() {
+    let _ = Instant::now();
+    //~^ ERROR: direct read of the clock with `std::time::Instant::now`
+    let _ = SystemTime::now();
+    //~^ ERROR: direct read of the clock with `std::time::SystemTime::now`
+    let _ = fake_chrono::Utc::now();
+    //~^ ERROR: direct read of the clock with `crate::fake_chrono::Utc::now`
+}
@@ -0,0 +1,56 @@
+#![warn(clippy::disallowed_attributes)]
+
+#[inline(always)]
+//~^ ERROR: use of a disallowed attribute `inline(always)`
+fn hot() {}
+
+#[inline]
+fn warm() {}
+
+#[allow(unused, dead_code)]
+//~^ ERROR: use of a disallowed attribute `allow(dead_code)`
+fn unused() {}
+
+#[allow(clippy::needless_return)]
+fn allowed() {}
+
+#[derive(Clone, Debug, Hash)]
+//~^ ERROR: use of a disallowed attribute `derive(core::fmt::Debug)`
+//~| ERROR: use of a disallowed attribute `derive(Hash)`
+struct Point {
+    #[must_use = "unused"]
+    //~^ ERROR: use of a disallowed attribute `must_use`
+    x: u8,
+}
+
+#[must_use]
+//~^ ERROR: use of a disallowed attribute `must_use`
+fn value() -> u8 {
+    0
+}
+
+#[no_mangle]
+extern "C" fn exported() {}
+
+mod domain {
+    #[no_mangle]
+    //~^ ERROR: use of a disallowed attribute `no_mangle`
+    extern "C" fn leaked() {}
+
+    mod nested {
+        #[no_mangle]
+        //~^ ERROR: use of a disallowed attribute `no_mangle`
+        extern "C" fn leaked_nested() {}
+    }
+}
+
+macro_rules! local {
+    () => {
+        #[inline(always)]
+        //~^ ERROR: use of a disallowed attribute `inline(always)`
+        fn generated() {}
+    };
+}
+local!();
+
+fn main() {}
@@ -0,0 +1,48 @@
+#![warn(clippy::disallowed_env_vars)]
+
+use std::env;
+
+const KEY: &str = "SECRET_KEY";
+
+mod config {
+    pub fn load() {
+        let _ = std::env::var("SECRET_KEY");
+        let _ = env!("PATH");
+
+        mod nested {
+            fn load() {
+                let _ = std::env::var_os(super::super::KEY);
+            }
+        }
+    }
+}
+
+mod build {
+    fn path() {
+        let _ = option_env!("PATH");
+        let _ = std::env::var("SECRET_KEY");
+        //~^ ERROR: read of the environment variable `SECRET_KEY`
+    }
+}
+
+fn main() {
+    let _ = env::var("SECRET_KEY");
+    //~^ ERROR: read of the environment variable `SECRET_KEY`
+    let _ = env::var_os(KEY);
+    //~^ ERROR: read of the environment variable `SECRET_KEY`
+    let _ = env::var("AWS_ACCESS_KEY_ID");
+    //~^ ERROR: read of the environment variable `AWS_ACCESS_KEY_ID`
+    let _ = env!("PATH");
+    //~^ ERROR: read of the environment variable `PATH`
+    let _ = option_env!("PATH");
+    //~^ ERROR: read of the environment variable `PATH`
+    let _ = option_env!("AWS_REGION");
+    //~^ ERROR: read of the environment variable `AWS_REGION`
+
+    // Not configured.
+    let _ = env::var("HOME");
+    let _ = option_env!("PATHS");
+    // Unknown names.
+    let name = String::from("SECRET_KEY");
+    let _ = env::var(name);
+}
@@ -0,0 +1,42 @@
+#![warn(clippy::disallowed_imports)]
+#![allow(unused_imports, clippy::single_component_path_imports)]
+
+extern crate alloc;
+//~^ ERROR: importing `alloc` is not allowed according to config
+
+mod legacy {
+    pub fn old() {}
+    pub mod nested {
+        pub struct Old;
+    }
+}
+
+mod storage {
+    pub fn new() {}
+}
+
+use std::sync::mpsc;
+//~^ ERROR: importing `std::sync::mpsc::*` is not allowed according to config
+use std::sync::mpsc::channel;
+//~^ ERROR: importing `std::sync::mpsc::*` is not allowed according to config
+use std::sync::mpsc::*;
+//~^ ERROR: importing `std::sync::mpsc::*` is not allowed according to config
+use std::sync::mpsc::Sender;
+//~^ ERROR: importing `std::sync::mpsc::*` is not allowed according to config
+use std::sync::Arc;
+
+use std::rc;
+//~^ ERROR: importing `std::rc` is not allowed according to config
+use std::rc::Rc;
+
+use crate::legacy::nested::Old;
+//~^ ERROR: importing `crate::legacy::*` is not allowed according to config
+//~| NOTE: use `crate::storage` instead (from clippy.toml)
+use legacy::old;
+//~^ ERROR: importing `crate::legacy::*` is not allowed according to config
+//~| NOTE: use `crate::storage` instead (from clippy.toml)
+use storage::new;
+
+fn main() {
+    let _ = std::sync::mpsc::channel::<u8>();
+}
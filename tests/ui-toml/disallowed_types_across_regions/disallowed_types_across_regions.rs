@@ -0,0 +1,111 @@
+#![warn(clippy::disallowed_types_across_regions)]
+
+use std::cell::RefCell;
+
+struct Transaction;
+
+impl Transaction {
+    fn begin() -> Self {
+        Transaction
+    }
+    fn commit(self) {}
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {}
+}
+
+fn render() {}
+
+extern "C" {
+    fn callback();
+}
+
+fn bad_call() {
+    let tx = Transaction::begin();
+    //~^ ERROR: `disallowed_types_across_regions::Transaction` may not be held across a call to `render`
+    render();
+    tx.commit();
+}
+
+fn good_call() {
+    let tx = Transaction::begin();
+    tx.commit();
+    render();
+}
+
+fn good_call_dropped() {
+    let tx = Transaction::begin();
+    drop(tx);
+    render();
+}
+
+fn good_call_moved_in_branch(early: bool) {
+    let tx = Transaction::begin();
+    if early {
+        // `tx` is still dropped at the end of the function on the other branch
+        tx.commit();
+        render();
+    }
+}
+
+fn bad_call_in_other_branch(early: bool) {
+    let tx = Transaction::begin();
+    //~^ ERROR: `disallowed_types_across_regions::Transaction` may not be held across a call to `render`
+    if early {
+        tx.commit();
+    } else {
+        render();
+    }
+}
+
+fn bad_ffi(cell: &RefCell<u32>) {
+    let mut borrow = cell.borrow_mut();
+    //~^ ERROR: `std::cell::RefMut` may not be held across a call to a foreign function
+    unsafe { callback() };
+    *borrow += 1;
+}
+
+fn good_ffi(cell: &RefCell<u32>) {
+    {
+        let mut borrow = cell.borrow_mut();
+        *borrow += 1;
+    }
+    unsafe { callback() };
+    // `String` may only not be held across `await`
+    let s = String::new();
+    render();
+    drop(s);
+}
+
+async fn bad_await() {
+    let tx = Transaction::begin();
+    //~^ ERROR: `disallowed_types_across_regions::Transaction` may not be held across an `await` point
+    baz().await;
+    tx.commit();
+}
+
+fn bad_ffi_string() {
+    let s = String::new();
+    //~^ ERROR: `std::string::String` may not be held across a call to a foreign function
+    unsafe { callback() };
+    drop(s);
+}
+
+async fn bad_await_string() {
+    let s = String::new();
+    //~^ ERROR: `std::string::String` may not be held across an `await` point
+    baz().await;
+    drop(s);
+}
+
+async fn good_await() {
+    {
+        let _tx = Transaction::begin();
+    }
+    baz().await;
+}
+
+async fn baz() {}
+
+fn main() {}
@@ -0,0 +1,46 @@
+#![warn(clippy::env_access_outside_config)]
+
+use std::env;
+
+const LOG: &str = "RUST_LOG";
+
+mod config {
+    pub fn load() {
+        let _ = std::env::var("DATABASE_URL");
+        let _ = std::env::vars();
+        let _ = option_env!("PROFILE");
+
+        mod nested {
+            fn load() {
+                let _ = std::env::var_os("DATABASE_URL");
+            }
+        }
+    }
+}
+
+mod cli {
+    fn args() {
+        let name = String::from("TERM");
+        let _ = std::env::var(name);
+    }
+}
+
+mod server {
+    fn bind() {
+        let _ = std::env::var("PORT");
+        //~^ ERROR: read of the environment variable `PORT` outside of the configuration modules
+    }
+}
+
+fn main() {
+    let _ = env!("CARGO_PKG_VERSION");
+    let _ = env::var(LOG);
+    let _ = env::var_os("RUST_LOG");
+
+    let _ = env::var("RUST_LOGS");
+    //~^ ERROR: read of the environment variable `RUST_LOGS` outside of the configuration modules
+    let _ = env!("CARGO_MANIFEST_DIR");
+    //~^ ERROR: read of the environment variable `CARGO_MANIFEST_DIR`
+    let _ = env::vars_os();
+    //~^ ERROR: read of the whole environment outside of the configuration modules
+}
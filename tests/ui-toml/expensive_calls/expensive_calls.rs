@@ -0,0 +1,43 @@
+#![warn(clippy::or_fun_call, clippy::unnecessary_lazy_evaluations)]
+
+const fn build_table(seed: u8) -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = seed.wrapping_mul(i as u8);
+        i += 1;
+    }
+    table
+}
+
+const fn build_small_table(seed: u8) -> [u8; 256] {
+    [seed; 256]
+}
+
+struct Table([u8; 256]);
+
+impl Table {
+    const fn checksum(&self) -> u8 {
+        let mut sum = 0u8;
+        let mut i = 0;
+        while i < 256 {
+            sum = sum.wrapping_add(self.0[i]);
+            i += 1;
+        }
+        sum
+    }
+}
+
+fn lookup(table: Option<[u8; 256]>, checksum: Option<u8>, seed: u8) {
+    let _ = table.unwrap_or(build_table(seed));
+    //~^ ERROR: use of `unwrap_or` followed by a function call
+    let _ = table.unwrap_or_else(|| build_table(1));
+    let _ = table.unwrap_or(build_small_table(seed));
+
+    let t = Table(build_table(seed));
+    let _ = checksum.unwrap_or(t.checksum());
+    //~^ ERROR: use of `unwrap_or` followed by a function call
+    let _ = checksum.unwrap_or_else(|| t.checksum());
+}
+
+fn main() {}
@@ -0,0 +1,53 @@
+#![warn(clippy::implicit_field_init)]
+
+mod tls {
+    #[derive(Default)]
+    pub struct TlsConfig {
+        pub verify_certificates: bool,
+        pub min_version: u8,
+        pub ciphers: Vec<String>,
+    }
+}
+
+#[derive(Default, Clone)]
+struct Limits {
+    connections: u32,
+    requests: u32,
+}
+
+#[derive(Default)]
+struct Other {
+    a: u32,
+    b: u32,
+}
+
+fn main() {
+    let _ = tls::TlsConfig {
+        verify_certificates: true,
+        ..Default::default()
+        //~^ ERROR: struct update of `TlsConfig`, whose fields must all be initialized explicitly
+        //~| NOTE: the fields `min_version` and `ciphers` are initialized implicitly
+    };
+    let base = Limits::default();
+    let _ = Limits {
+        connections: 10,
+        ..base.clone()
+        //~^ ERROR: struct update of `Limits`, whose fields must all be initialized explicitly
+        //~| NOTE: the field `requests` is initialized implicitly
+    };
+    let _ = Limits { ..base };
+    //~^ ERROR: struct update of `Limits`, whose fields must all be initialized explicitly
+    //~| NOTE: the fields `connections` and `requests` are initialized implicitly
+
+    // All the fields are initialized explicitly
+    let _ = tls::TlsConfig {
+        verify_certificates: true,
+        min_version: 3,
+        ciphers: Vec::new(),
+    };
+    // Not configured
+    let _ = Other {
+        a: 1,
+        ..Default::default()
+    };
+}
@@ -0,0 +1,16 @@
+#![warn(clippy::match_bool_tuple)]
+#![allow(clippy::match_like_matches_macro)]
+
+fn main() {
+    let (a, b, c) = (true, false, true);
+    let _ = match (a, b) {
+        //~^ ERROR: you seem to be trying to match on a tuple of booleans
+        (true, true) => true,
+        _ => false,
+    };
+    // Too many booleans
+    let _ = match (a, b, c) {
+        (true, true, true) => true,
+        _ => false,
+    };
+}
@@ -0,0 +1,32 @@
+//@no-rustfix
+#![warn(clippy::match_same_arms)]
+
+use std::mem;
+
+mod a {
+    pub const LIMIT: u32 = 1;
+}
+
+mod b {
+    pub const LIMIT: u32 = 2;
+}
+
+fn take(v: &mut Vec<u32>, n: u8) -> Vec<u32> {
+    match n {
+        0 => mem::take(v),
+        1 => std::mem::take(v),
+        //~^ ERROR: this match arm has an identical body to another arm
+        _ => Vec::new(),
+    }
+}
+
+fn limits(n: u8) -> u32 {
+    // Different items with the same name
+    match n {
+        0 => a::LIMIT,
+        1 => b::LIMIT,
+        _ => 0,
+    }
+}
+
+fn main() {}
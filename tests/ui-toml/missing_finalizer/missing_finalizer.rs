@@ -0,0 +1,102 @@
+#![warn(clippy::missing_finalizer)]
+#![allow(clippy::new_without_default)]
+
+pub mod db {
+    pub struct Transaction;
+
+    impl Transaction {
+        pub fn begin() -> Self {
+            Self
+        }
+        pub fn execute(&mut self, _: &str) {}
+        pub fn commit(self) {}
+        pub fn rollback(self) {}
+    }
+}
+
+pub struct Builder(u32);
+
+impl Builder {
+    pub fn new() -> Self {
+        Self(0)
+    }
+    pub fn value(self, value: u32) -> Self {
+        Self(value)
+    }
+    pub fn build(self) -> u32 {
+        self.0
+    }
+}
+
+pub struct Span;
+pub struct Entered;
+
+impl Span {
+    pub fn enter(&self) -> Entered {
+        Entered
+    }
+}
+
+fn committed() {
+    let mut tx = db::Transaction::begin();
+    tx.execute("insert");
+    tx.commit();
+}
+
+fn early_return(cond: bool) {
+    let mut tx = db::Transaction::begin();
+    //~^ ERROR: the result of `missing_finalizer::db::Transaction::begin` may be dropped without calling
+    tx.execute("insert");
+    if cond {
+        return;
+    }
+    tx.commit();
+}
+
+fn every_branch(cond: bool) {
+    let tx = db::Transaction::begin();
+    if cond { tx.commit() } else { tx.rollback() }
+}
+
+fn loops(n: u32) {
+    let mut tx = db::Transaction::begin();
+    for _ in 0..n {
+        tx.execute("insert");
+    }
+    tx.commit();
+}
+
+fn returned() -> db::Transaction {
+    db::Transaction::begin()
+}
+
+fn moved_elsewhere() {
+    let tx = db::Transaction::begin();
+    drop_it(tx);
+}
+
+fn drop_it(tx: db::Transaction) {
+    tx.rollback();
+}
+
+fn built() -> u32 {
+    Builder::new().value(1).build()
+}
+
+fn not_built() {
+    let _builder = Builder::new().value(1);
+    //~^ ERROR: the result of `missing_finalizer::Builder::new` may be dropped without calling
+}
+
+fn entered(span: &Span) {
+    let _guard = span.enter();
+}
+
+fn not_entered(span: &Span) {
+    let _ = span.enter();
+    //~^ ERROR: the result of `missing_finalizer::Span::enter` is dropped right away
+    span.enter();
+    //~^ ERROR: the result of `missing_finalizer::Span::enter` is dropped right away
+}
+
+fn main() {}
@@ -0,0 +1,48 @@
+#![warn(clippy::missing_required_const_fn, clippy::missing_const_for_fn)]
+
+mod tables {
+    pub fn crc_entry(index: u32) -> u32 {
+        //~^ missing_required_const_fn
+        index.rotate_left(8) ^ 0xEDB8_8320
+    }
+
+    mod nested {
+        pub(crate) unsafe fn entry(table: *const u8) -> u8 {
+            //~^ missing_required_const_fn
+            *table
+        }
+    }
+
+    pub fn allocating() -> Vec<u32> {
+        vec![0; 256]
+    }
+}
+
+mod codec {
+    pub mod consts {
+        pub struct Header(u8);
+
+        impl Header {
+            pub fn new(version: u8) -> Self {
+                //~^ missing_required_const_fn
+                Self(version)
+            }
+        }
+    }
+
+    pub fn header_len() -> usize {
+        //~^ missing_const_for_fn
+        4
+    }
+}
+
+mod msrv {
+    pub mod consts {
+        #[clippy::msrv = "1.60"]
+        pub fn bounded<T: Copy>(value: &T) -> T {
+            *value
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,87 @@
+//@compile-flags: --test
+#![warn(clippy::module_layering)]
+
+mod http {
+    pub struct StatusCode(pub u16);
+
+    pub fn respond() -> StatusCode {
+        // Modules may depend on themselves.
+        crate::http::StatusCode(200)
+    }
+
+    pub mod client {
+        pub fn get() {}
+    }
+}
+
+mod test_support {
+    pub fn fixture() -> u32 {
+        42
+    }
+}
+
+mod domain {
+    use crate::http::StatusCode;
+    //~^ ERROR: `crate::domain` may not depend on `crate::http` per `clippy.toml`
+    use crate::http::{self, client};
+    //~^ ERROR: `crate::domain` may not depend on `crate::http` per `clippy.toml`
+    //~| ERROR: `crate::domain` may not depend on `crate::http` per `clippy.toml`
+
+    // Names brought into scope by a `use` item are only linted at the `use` item.
+    pub fn validate() -> Result<(), StatusCode> {
+        client::get();
+        Err(http::respond())
+    }
+
+    pub fn status() -> u16 {
+        crate::http::respond().0
+        //~^ ERROR: `crate::domain` may not depend on `crate::http` per `clippy.toml`
+    }
+
+    pub mod rules {
+        pub fn check() {
+            super::super::http::client::get();
+            //~^ ERROR: `crate::domain` may not depend on `crate::http` per `clippy.toml`
+        }
+    }
+
+    pub fn seed() -> u32 {
+        crate::test_support::fixture()
+        //~^ ERROR: `crate::*` may not depend on `crate::test_support` per `clippy.toml`
+    }
+
+    #[cfg(test)]
+    mod tests {
+        #[test]
+        fn seeded() {
+            assert_eq!(crate::test_support::fixture(), 42);
+        }
+    }
+}
+
+mod app {
+    // `crate::app` may depend on `crate::http`.
+    use crate::http::StatusCode;
+
+    pub fn run() -> StatusCode {
+        crate::domain::rules::check();
+        let _ = test_support_free();
+        StatusCode(204)
+    }
+
+    fn test_support_free() -> u32 {
+        0
+    }
+}
+
+fn main() {
+    let _ = test_support::fixture();
+    //~^ ERROR: `crate::*` may not depend on `crate::test_support` per `clippy.toml`
+    let _ = app::run();
+    let _ = domain::validate();
+}
+
+#[test]
+fn uses_fixture() {
+    assert_eq!(test_support::fixture(), 42);
+}
@@ -0,0 +1,66 @@
+#![warn(clippy::must_use_types)]
+
+mod db {
+    pub struct Transaction;
+
+    impl Transaction {
+        pub fn begin() -> Self {
+            //~^ ERROR: this function returns `crate::db::Transaction` but isn't `#[must_use]`
+            Transaction
+        }
+
+        #[must_use]
+        pub fn nested(&self) -> Self {
+            Transaction
+        }
+
+        pub fn commit(self) {}
+    }
+}
+
+pub struct Guard;
+
+#[must_use]
+pub struct Annotated;
+
+fn lock() -> Guard {
+    //~^ ERROR: this function returns `crate::Guard` but isn't `#[must_use]`
+    Guard
+}
+
+// Types which are already `#[must_use]` are checked by the compiler.
+fn annotated() -> Annotated {
+    Annotated
+}
+
+trait Locker {
+    fn lock(&self) -> Guard;
+    //~^ ERROR: this function returns `crate::Guard` but isn't `#[must_use]`
+}
+
+impl Locker for () {
+    // Checked at the trait.
+    fn lock(&self) -> Guard {
+        Guard
+    }
+}
+
+fn main() {
+    lock();
+    //~^ ERROR: unused value of type `crate::Guard`
+    ().lock();
+    //~^ ERROR: unused value of type `crate::Guard`
+    let _ = lock();
+    //~^ ERROR: unused value of type `crate::Guard`
+    let _guard = lock();
+
+    let tx = db::Transaction::begin();
+    // Already linted by the compiler.
+    #[allow(unused_must_use)]
+    tx.nested();
+    let _ = tx.nested();
+    //~^ ERROR: unused value of type `crate::db::Transaction`
+    tx.commit();
+
+    let _ = annotated();
+}
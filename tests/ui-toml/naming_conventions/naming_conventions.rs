@@ -0,0 +1,59 @@
+//@compile-flags: --test
+#![warn(clippy::naming_conventions)]
+#![allow(dead_code, non_camel_case_types, non_snake_case)]
+
+struct HttpClient;
+struct HTTP_Client;
+//~^ ERROR: type name `HTTP_Client` does not match the configured pattern `[A-Z][A-Za-z0-9]*`
+enum Level2 {}
+type Alias_ = u8;
+//~^ ERROR: type name `Alias_` does not match the configured pattern `[A-Z][A-Za-z0-9]*`
+
+trait Readable {
+    type Output_;
+    //~^ ERROR: type name `Output_` does not match the configured pattern `[A-Z][A-Za-z0-9]*`
+    const MAX_LEN: usize;
+    fn read_all(&self);
+    fn read2(&self);
+    //~^ ERROR: function name `read2` does not match the configured pattern `[a-z]+(_[a-z]+)*`
+}
+trait ReadAll {}
+//~^ ERROR: trait name `ReadAll` does not match the configured pattern `[A-Z][a-z]+`
+
+impl HttpClient {
+    const DEFAULT_PORT: u16 = 80;
+    #[allow(non_upper_case_globals)]
+    const Timeout: u16 = 30;
+    //~^ ERROR: constant name `Timeout` does not match the configured pattern `[A-Z]+(_[A-Z]+)*`
+    fn send(&self) {}
+}
+
+// The names of trait items are checked on the trait.
+impl Readable for HttpClient {
+    type Output_ = ();
+    const MAX_LEN: usize = 8;
+    fn read_all(&self) {}
+    fn read2(&self) {}
+}
+
+const LIMIT: u32 = 1;
+static COUNTER_2: u32 = 0;
+//~^ ERROR: constant name `COUNTER_2` does not match the configured pattern `[A-Z]+(_[A-Z]+)*`
+const _: () = ();
+
+fn parse_args() {}
+fn parseArgs() {}
+//~^ ERROR: function name `parseArgs` does not match the configured pattern `[a-z]+(_[a-z]+)*`
+
+macro_rules! make_fn {
+    () => {
+        fn generatedFn() {}
+    };
+}
+make_fn!();
+
+#[test]
+fn test_parse() {}
+#[test]
+fn parses() {}
+//~^ ERROR: test function name `parses` does not match the configured pattern `test_[a-z_]+`
@@ -0,0 +1,4 @@
+//@error-in-other-file: invalid regex `[A-Z`: unclosed character class
+#![warn(clippy::naming_conventions)]
+
+fn main() {}
@@ -0,0 +1,64 @@
+#![warn(clippy::panic_in_production)]
+#![allow(clippy::diverging_sub_expression, clippy::unnecessary_literal_unwrap)]
+
+use std::collections::HashMap;
+
+fn unwraps(option: Option<u32>, result: Result<u32, String>) -> u32 {
+    let a = option.unwrap();
+    //~^ ERROR: unwrapping in production code
+    let b = result.expect("valid");
+    //~^ ERROR: unwrapping in production code
+    let c = option.as_ref().map(|x| x + 1).unwrap();
+    //~^ ERROR: unwrapping in production code
+    a + b + c
+}
+
+fn infallible(result: Result<u32, std::convert::Infallible>) -> u32 {
+    // Can't panic
+    result.unwrap()
+}
+
+fn macros(value: u32) -> u32 {
+    match value {
+        0 => panic!("zero"),
+        //~^ ERROR: `panic!` in production code
+        1 => todo!(),
+        //~^ ERROR: `todo!` in production code
+        2 => core::unimplemented!("two"),
+        //~^ ERROR: `unimplemented!` in production code
+        _ => unreachable!(),
+    }
+}
+
+fn renamed_macros(value: u32) -> u32 {
+    use core::unreachable as never;
+    use std::todo as later;
+    match value {
+        0 => later!(),
+        //~^ ERROR: `todo!` in production code
+        _ => never!(),
+    }
+}
+
+fn indexing(slice: &[u8], array: [u8; 4], map: &HashMap<u8, u8>) -> u8 {
+    let a = slice[0];
+    //~^ ERROR: indexing or slicing in production code
+    let b = slice[1..][0];
+    //~^ ERROR: indexing or slicing in production code
+    //~| ERROR: indexing or slicing in production code
+    let c = map[&0];
+    //~^ ERROR: indexing or slicing in production code
+    // An array indexed by a constant in its bounds can't panic
+    let d = array[3];
+    a + b + c + d
+}
+
+fn in_closure(values: &[Option<u32>]) -> Vec<u32> {
+    values.iter().map(|value| value.unwrap()).collect()
+    //~^ ERROR: unwrapping in production code
+}
+
+// Panics in constants fail the compilation
+const FIRST: u8 = [1, 2][0];
+
+fn main() {}
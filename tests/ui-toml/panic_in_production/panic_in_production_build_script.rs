@@ -0,0 +1,10 @@
+//@compile-flags: --crate-name=build_script_build
+#![warn(clippy::panic_in_production)]
+
+// Build scripts aren't linted
+fn main() {
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    if out_dir.is_empty() {
+        panic!("`OUT_DIR` is empty");
+    }
+}
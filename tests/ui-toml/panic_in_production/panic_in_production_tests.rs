@@ -0,0 +1,12 @@
+//@compile-flags: --test
+#![warn(clippy::panic_in_production)]
+
+// Crates built with the test harness aren't linted
+fn helper(option: Option<u32>) -> u32 {
+    option.unwrap()
+}
+
+#[test]
+fn test() {
+    assert_eq!(helper(Some(1)), [1][0]);
+}
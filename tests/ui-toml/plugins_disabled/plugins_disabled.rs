@@ -0,0 +1,4 @@
+// the plugins are refused without `CLIPPY_ALLOW_PLUGINS=1`, before trying to load them
+//@error-in-other-file: the Clippy plugins are only loaded with `CLIPPY_ALLOW_PLUGINS=1` set
+
+fn main() {}
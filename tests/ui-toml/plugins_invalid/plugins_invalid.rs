@@ -0,0 +1,7 @@
+//@rustc-env:CLIPPY_ALLOW_PLUGINS=1
+// the error of the dynamic loader differs between platforms
+//@ignore-target-windows
+//@ignore-target-apple
+//@error-in-other-file: could not load the Clippy plugin
+
+fn main() {}
@@ -0,0 +1,10 @@
+//@no-rustfix
+//@compile-flags: --crate-type=lib
+#![warn(clippy::print_in_library)]
+
+pub fn load(path: &str) {
+    println!("loading {path}");
+    //~^ ERROR: use of `println!` in a library
+    eprintln!("can't read {}", path);
+    //~^ ERROR: use of `eprintln!` in a library
+}
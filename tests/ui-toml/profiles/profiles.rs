@@ -0,0 +1,25 @@
+//@revisions: ci dev
+//@[ci] rustc-env:CLIPPY_PROFILE=ci
+//@[dev] rustc-env:CLIPPY_PROFILE=dev
+#![warn(clippy::too_many_lines)]
+
+fn lines() {
+    //~[ci]^ ERROR: this function has too many lines (3/2)
+    let a = 1;
+    let b = 2;
+    let _ = a + b;
+}
+
+fn options(x: Option<u32>) -> u32 {
+    let _ = x.expect("x");
+    //~[dev]^ ERROR: used `expect()` on an `Option` value
+    x.unwrap()
+    //~[ci]^ ERROR: used `unwrap()` on an `Option` value
+}
+
+fn zeroed() -> u8 {
+    unsafe { std::mem::zeroed() }
+    //~[ci]^ ERROR: usage of an `unsafe` block
+}
+
+fn main() {}
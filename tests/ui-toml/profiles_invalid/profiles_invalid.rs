@@ -0,0 +1,6 @@
+//@rustc-env:CLIPPY_PROFILE=release
+//@error-in-other-file: lint levels can only be set in a `profile` or `target-kind` section
+//@error-in-other-file: data did not match any variant of untagged enum LintConfig
+//@error-in-other-file: the Clippy profile `release` is not defined in the configuration file
+
+fn main() {}
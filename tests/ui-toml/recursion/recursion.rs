@@ -0,0 +1,80 @@
+#![warn(clippy::recursion)]
+
+fn factorial(n: u64) -> u64 {
+    //~^ recursion
+    if n == 0 { 1 } else { n * factorial(n - 1) }
+}
+
+fn is_even(n: u32) -> bool {
+    //~^ recursion
+    n == 0 || is_odd(n - 1)
+}
+
+fn is_odd(n: u32) -> bool {
+    //~^ recursion
+    n != 0 && is_even(n - 1)
+}
+
+// Not part of the cycle, only calls into it
+fn parity(n: u32) -> &'static str {
+    if is_even(n) { "even" } else { "odd" }
+}
+
+pub struct Tree {
+    children: Vec<Tree>,
+}
+
+impl Tree {
+    fn len(&self) -> usize {
+        //~^ recursion
+        1 + self.children_len()
+    }
+
+    fn children_len(&self) -> usize {
+        //~^ recursion
+        let mut len = 0;
+        for child in &self.children {
+            len += child.len();
+        }
+        len
+    }
+
+    fn audited_len(&self) -> usize {
+        let mut len = 1;
+        for child in &self.children {
+            len += child.audited_len();
+        }
+        len
+    }
+}
+
+mod parser {
+    pub fn parse_expr(input: &str) -> usize {
+        match input.strip_prefix('(') {
+            Some(rest) => parse_paren(rest),
+            None => 0,
+        }
+    }
+
+    // The cycle goes through the audited `parse_expr`
+    fn parse_paren(input: &str) -> usize {
+        1 + parse_expr(input)
+    }
+}
+
+trait Visit {
+    fn visit(&self, depth: u32);
+}
+
+struct Leaf;
+
+impl Visit for Leaf {
+    fn visit(&self, depth: u32) {
+        //~^ recursion
+        if depth > 0 {
+            self.visit(depth - 1);
+        }
+    }
+}
+
+fn main() {}
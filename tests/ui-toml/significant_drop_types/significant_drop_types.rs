@@ -0,0 +1,22 @@
+#![warn(clippy::let_underscore_lock, clippy::mem_forget)]
+
+mod pool {
+    pub struct Connection;
+
+    pub fn connect() -> Connection {
+        Connection
+    }
+}
+
+struct Unlisted;
+
+fn main() {
+    let _ = pool::connect();
+    //~^ ERROR: non-binding `let` on a type with a significant drop
+    let _ = (Unlisted, Some(pool::connect()));
+    //~^ ERROR: non-binding `let` on a type with a significant drop
+    let _ = Unlisted;
+
+    std::mem::forget(pool::connect());
+    //~^ ERROR: usage of `mem::forget` on a type with a significant drop, whose drop is never run
+}
@@ -23,7 +23,7 @@ const fn idx4() -> usize {
 fn main() {
     let x = [1, 2, 3, 4];
     let index: usize = 1;
-    x[index];
+    x[index]; // Ok, the index is known to be in bounds.
     x[4]; // Ok, let rustc's `unconditional_panic` lint handle `usize` indexing on arrays.
     x[1 << 3]; // Ok, let rustc's `unconditional_panic` lint handle `usize` indexing on arrays.
 
@@ -0,0 +1,23 @@
+//@revisions: bin test
+//@[test] compile-flags: --test
+#![warn(clippy::too_many_lines)]
+
+fn lines() {
+    //~[test]^ ERROR: this function has too many lines (3/2)
+    let a = 1;
+    let b = 2;
+    let _ = a + b;
+}
+
+fn options(x: Option<u32>) -> u32 {
+    x.unwrap()
+    //~[bin]^ ERROR: used `unwrap()` on an `Option` value
+}
+
+#[test]
+fn print() {
+    println!("print");
+    //~[test]^ ERROR: use of `println!`
+}
+
+fn main() {}
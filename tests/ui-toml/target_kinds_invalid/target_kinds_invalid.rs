@@ -0,0 +1,3 @@
+//@error-in-other-file: invalid target kind `tests`
+
+fn main() {}
@@ -0,0 +1,18 @@
+//@no-rustfix
+#![warn(clippy::pointer_width_dependent_cast)]
+
+fn main() {}
+
+fn casts(pos: u64, count: u32, small: u16, byte: u8) {
+    let _ = pos as usize;
+    //~^ ERROR: casting `u64` to `usize` may truncate the value on targets with 16-bit wide pointers
+    let _ = count as usize;
+    //~^ ERROR: casting `u32` to `usize` may truncate the value on targets with 16-bit wide pointers
+    let _ = small as isize;
+    //~^ ERROR: casting `u16` to `isize` may wrap around the value on targets with 16-bit wide pointers
+
+    // Ok: lossless on every configured width
+    let _ = small as usize;
+    let _ = byte as isize;
+    let _ = (count & 0xff) as isize;
+}
@@ -0,0 +1,4 @@
+//@error-in-other-file: invalid pointer width `48`, expected one of 16, 32 or 64
+#![warn(clippy::pointer_width_dependent_cast)]
+
+fn main() {}
@@ -0,0 +1,37 @@
+#![feature(register_tool)]
+#![register_tool(my)]
+#![warn(clippy::unwrap_used)]
+
+macro_rules! check {
+    ($e:expr) => {{
+        let value: Option<u8> = $e;
+        value.unwrap()
+    }};
+}
+
+#[my::test]
+fn tool_attribute(x: Option<u8>) {
+    x.unwrap();
+}
+
+#[my::other]
+fn other_tool_attribute(x: Option<u8>) {
+    x.unwrap();
+    //~^ ERROR: used `unwrap()` on an `Option` value
+}
+
+mod outer {
+    #[my::test]
+    mod inner {
+        fn in_module(x: Option<u8>) {
+            x.unwrap();
+        }
+    }
+}
+
+fn main() {
+    let x = std::env::args().next().map(|_| 1);
+    check!(x);
+    x.unwrap();
+    //~^ ERROR: used `unwrap()` on an `Option` value
+}
@@ -0,0 +1,12 @@
+#![warn(clippy::too_many_arguments)]
+
+fn below_override(_: u8, _: u8, _: u8, _: u8) {}
+
+fn above_override(_: u8, _: u8, _: u8, _: u8, _: u8) {}
+//~^ ERROR: this function has too many arguments (5/4)
+
+#[clippy::limit(too_many_arguments = 3)]
+fn attribute_takes_precedence(_: u8, _: u8, _: u8, _: u8) {}
+//~^ ERROR: this function has too many arguments (4/3)
+
+fn main() {}
@@ -0,0 +1,8 @@
+#![warn(clippy::too_many_arguments)]
+
+fn below_global(_: u8, _: u8) {}
+
+fn above_global(_: u8, _: u8, _: u8) {}
+//~^ ERROR: this function has too many arguments (3/2)
+
+fn main() {}
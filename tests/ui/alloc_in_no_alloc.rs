@@ -0,0 +1,91 @@
+#![warn(clippy::alloc_in_no_alloc)]
+#![allow(clippy::useless_vec)]
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[clippy::no_alloc]
+fn direct(buffer: &mut Vec<u8>, name: &str) -> usize {
+    let boxed = Box::new(1);
+    //~^ alloc_in_no_alloc
+    let numbers = vec![1, 2, 3];
+    //~^ alloc_in_no_alloc
+    let message = format!("hello {name}");
+    //~^ alloc_in_no_alloc
+    let owned = name.to_string();
+    //~^ alloc_in_no_alloc
+    let doubled: Vec<u8> = buffer.iter().map(|x| x * 2).collect();
+    //~^ alloc_in_no_alloc
+    buffer.push(1);
+    //~^ alloc_in_no_alloc
+    buffer.len() + numbers.len() + message.len() + owned.len() + doubled.len() + *boxed
+}
+
+#[clippy::no_alloc]
+fn no_allocation(buffer: &mut [u8], shared: &Rc<u8>, map: &HashMap<u8, u8>, maybe: &mut Option<Vec<u8>>) -> u8 {
+    // `const fn`s can't allocate
+    let _empty: Vec<u8> = Vec::new();
+    let _shared = Rc::clone(shared);
+    let _taken = maybe.take().unwrap_or_default().len();
+    buffer.sort_unstable();
+    let total: u8 = buffer.iter().sum();
+    total + map.get(&0).copied().unwrap_or(0)
+}
+
+#[clippy::no_alloc]
+fn audited() -> Vec<u8> {
+    #[allow(clippy::alloc_in_no_alloc)]
+    let buffer = Vec::with_capacity(16);
+    buffer
+}
+
+fn make_name(id: u32) -> String {
+    format!("#{id}")
+}
+
+fn describe(id: u32) -> usize {
+    make_name(id).len()
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0, |acc, byte| acc ^ byte)
+}
+
+#[clippy::no_alloc]
+fn through_calls(id: u32, bytes: &[u8]) -> usize {
+    let len = describe(id);
+    //~^ alloc_in_no_alloc
+    let name = make_name(id);
+    //~^ alloc_in_no_alloc
+    // Checked on their own
+    let _ = audited();
+    let _ = direct(&mut Vec::new(), "");
+    len + name.len() + usize::from(checksum(bytes))
+}
+
+struct Queue {
+    items: Vec<u32>,
+}
+
+#[clippy::no_alloc]
+impl Queue {
+    fn enqueue(&mut self, item: u32) {
+        self.items.push(item);
+        //~^ alloc_in_no_alloc
+    }
+
+    fn peek(&self) -> Option<u32> {
+        self.items.first().copied()
+    }
+}
+
+#[clippy::no_alloc]
+mod realtime {
+    pub fn render(samples: &[f32]) -> Box<[f32]> {
+        samples.to_vec().into_boxed_slice()
+        //~^ alloc_in_no_alloc
+        //~| alloc_in_no_alloc
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+//@aux-build:rand.rs
+#![warn(clippy::ambient_randomness)]
+
+extern crate rand;
+
+use rand::{Rng, StdRng};
+
+fn roll(rng: &mut impl Rng) -> u64 {
+    rng.gen() % 6 + 1
+}
+
+fn main() {
+    let _ = roll(&mut rand::thread_rng());
+    //~^ ERROR: use of the ambient source of randomness `rand::thread_rng`
+    let _ = roll(&mut rand::rngs::thread_rng());
+    //~^ ERROR: use of the ambient source of randomness `rand::thread_rng`
+    let _ = roll(&mut rand::rng());
+    //~^ ERROR: use of the ambient source of randomness `rand::rng`
+    let _: u32 = rand::random();
+    //~^ ERROR: use of the ambient source of randomness `rand::random`
+    let _: Vec<_> = std::iter::repeat_with(rand::rng).take(2).collect();
+    //~^ ERROR: use of the ambient source of randomness `rand::rng`
+
+    // A seeded generator.
+    let _ = roll(&mut StdRng(42));
+}
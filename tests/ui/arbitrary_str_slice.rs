@@ -0,0 +1,92 @@
+#![warn(clippy::arbitrary_str_slice)]
+#![allow(clippy::redundant_slicing, clippy::single_match, clippy::string_slice)]
+
+fn arbitrary(s: &str, owned: String, n: usize) {
+    let _ = &s[..2];
+    //~^ ERROR: slicing a string with bounds which may not be on a char boundary
+    let _ = &s[n..];
+    //~^ ERROR: slicing a string with bounds which may not be on a char boundary
+    let _ = &owned[1..n];
+    //~^ ERROR: slicing a string with bounds which may not be on a char boundary
+    let _ = &"héllo"[..2];
+    //~^ ERROR: slicing a string with bounds which may not be on a char boundary
+    let _ = &"hello"[..=4];
+    let _ = &"héllo"[..=1];
+    //~^ ERROR: slicing a string with bounds which may not be on a char boundary
+    if let Some(i) = s.find(':') {
+        let _ = &s[i + 2..];
+        //~^ ERROR: slicing a string with bounds which may not be on a char boundary
+        let _ = &s[..=i];
+        //~^ ERROR: slicing a string with bounds which may not be on a char boundary
+        let _ = &owned[i..];
+        //~^ ERROR: slicing a string with bounds which may not be on a char boundary
+    }
+    if s.is_ascii() || n > 2 {
+        let _ = &s[..2];
+        //~^ ERROR: slicing a string with bounds which may not be on a char boundary
+    }
+    if owned.is_ascii() {
+        let _ = &s[..2];
+        //~^ ERROR: slicing a string with bounds which may not be on a char boundary
+    }
+}
+
+fn boundaries(s: &str, owned: String) {
+    let _ = &s[..];
+    let _ = &s[0..s.len()];
+    let _ = &owned[..owned.len()];
+    let _ = &"héllo"[..3];
+    const GREETING: &str = "héllo";
+    let _ = &GREETING[3..];
+
+    if let Some(i) = s.find(':') {
+        let _ = &s[..i];
+        let _ = &s[i + 1..];
+        let _ = &s[i + ':'.len_utf8()..];
+    }
+    if let Some(i) = s.rfind("::") {
+        let _ = &s[i + 2..];
+        let _ = &s[i + "::".len()..];
+    }
+    let sep = "=>";
+    let start = s.find(sep).unwrap();
+    let _ = &s[start + sep.len()..];
+    for (i, _) in s.char_indices() {
+        let _ = &s[i..];
+    }
+    match s.find('é') {
+        Some(i) => {
+            let _ = &s[..i];
+        },
+        None => {},
+    }
+}
+
+fn ascii(s: &str, n: usize) {
+    if s.is_ascii() {
+        let _ = &s[..n];
+    }
+    if n < s.len() && s.is_ascii() {
+        let _ = &s[n..];
+    }
+    let _ = s.is_ascii() && &s[..n] == "ab";
+    {
+        assert!(s.is_ascii());
+        let _ = &s[..n];
+    }
+    {
+        debug_assert!(s.is_ascii(), "not ASCII");
+        let _ = &s[..n];
+    }
+    if !s.is_ascii() {
+        return;
+    }
+    let _ = &s[n..];
+}
+
+fn find_or_return(s: &str) -> Option<&str> {
+    let i = s.find(',')?;
+    Some(&s[i + 1..])
+}
+
+fn main() {}
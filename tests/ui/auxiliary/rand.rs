@@ -0,0 +1,37 @@
+//! The functions of `rand` linted by `ambient_randomness`.
+
+pub struct ThreadRng;
+
+pub trait Rng {
+    fn gen(&mut self) -> u64;
+}
+
+impl Rng for ThreadRng {
+    fn gen(&mut self) -> u64 {
+        4
+    }
+}
+
+pub struct StdRng(pub u64);
+
+impl Rng for StdRng {
+    fn gen(&mut self) -> u64 {
+        self.0
+    }
+}
+
+pub mod rngs {
+    pub fn thread_rng() -> super::ThreadRng {
+        super::ThreadRng
+    }
+}
+
+pub use rngs::thread_rng;
+
+pub fn rng() -> ThreadRng {
+    ThreadRng
+}
+
+pub fn random<T: Default>() -> T {
+    T::default()
+}
@@ -498,3 +498,28 @@ fn issue12721() {
     (255 % 999999u64) as u8;
     //~^ ERROR: casting `u64` to `u8` may truncate the value
 }
+
+fn bounded_casts(x: u64, y: i32, z: usize) {
+    // Don't lint, the values fit in the target type.
+    let _ = (x % 256) as u8;
+    let _ = (x >> 56) as u8;
+    let _ = x.min(255) as u8;
+    let _ = y.clamp(-128, 127) as i8;
+    if (-128..128).contains(&y) {
+        let _ = y as i8;
+    }
+    if !(-128..=127).contains(&y) {
+        return;
+    }
+    let _ = y as i8;
+    let _ = (z & 0xffff_ffff) as u32;
+    let small = x % 100;
+    let _ = small as u8;
+
+    let _ = (x % 257) as u8;
+    //~^ ERROR: casting `u64` to `u8` may truncate the value
+    let _ = (x >> 55) as u8;
+    //~^ ERROR: casting `u64` to `u8` may truncate the value
+    let _ = y.clamp(-129, 127) as i8;
+    //~^ ERROR: casting `i32` to `i8` may truncate the value
+}
@@ -0,0 +1,28 @@
+#![warn(clippy::cast_policy)]
+#![allow(clippy::cast_lossless)]
+
+fn widening(a: u8, b: i16, c: bool, d: f32, e: char) {
+    let _ = a as u32;
+    //~^ ERROR: widening cast from `u8` to `u32`
+    let _ = b as i64;
+    //~^ ERROR: widening cast from `i16` to `i64`
+    let _ = a as i16;
+    //~^ ERROR: widening cast from `u8` to `i16`
+    let _ = c as u8;
+    //~^ ERROR: widening cast from `bool` to `u8`
+    let _ = d as f64;
+    //~^ ERROR: widening cast from `f32` to `f64`
+    let _ = e as u32;
+    //~^ ERROR: widening cast from `char` to `u32`
+    let _ = a as char;
+    //~^ ERROR: widening cast from `u8` to `char`
+    let _ = (a + 1) as u64;
+    //~^ ERROR: widening cast from `u8` to `u64`
+}
+
+fn float_int(a: u32) {
+    let _ = a as f64;
+    //~^ ERROR: cast between a float and an integer from `u32` to `f64`
+}
+
+fn main() {}
@@ -0,0 +1,68 @@
+//@no-rustfix
+#![warn(clippy::cast_policy)]
+#![allow(clippy::unnecessary_cast, clippy::cast_lossless, dead_code)]
+
+fn widening(a: char) {
+    // `From<char>` isn't implemented for `i32`
+    let _ = a as i32;
+    //~^ ERROR: widening cast from `char` to `i32`
+}
+
+fn sign_change(a: i32, b: u32, c: i8) {
+    let _ = a as u32;
+    //~^ ERROR: sign-changing cast from `i32` to `u32`
+    let _ = b as i32;
+    //~^ ERROR: sign-changing cast from `u32` to `i32`
+    let _ = c as u64;
+    //~^ ERROR: sign-changing cast from `i8` to `u64`
+}
+
+fn truncation(a: u64, b: i64, c: f64, d: char) {
+    let _ = a as u32;
+    //~^ ERROR: truncating cast from `u64` to `u32`
+    let _ = b as u8;
+    //~^ ERROR: truncating cast from `i64` to `u8`
+    let _ = c as f32;
+    //~^ ERROR: truncating cast from `f64` to `f32`
+    let _ = d as u8;
+    //~^ ERROR: truncating cast from `char` to `u8`
+    let _ = a as usize;
+    //~^ ERROR: truncating cast from `u64` to `usize`
+    let _: u16 = a as _;
+    //~^ ERROR: truncating cast from `u64` to `u16`
+}
+
+fn float_int(a: f64, b: u64) {
+    let _ = a as i32;
+    //~^ ERROR: cast between a float and an integer from `f64` to `i32`
+    let _ = b as f64;
+    //~^ ERROR: cast between a float and an integer from `u64` to `f64`
+}
+
+fn pointer(a: *const u8, b: &u32, c: fn()) {
+    let _ = a as *const i8;
+    //~^ ERROR: pointer cast from `*const u8` to `*const i8`
+    let _ = b as *const u32;
+    //~^ ERROR: pointer cast from `&u32` to `*const u32`
+    let _ = a as usize;
+    //~^ ERROR: pointer cast from `*const u8` to `usize`
+    let _ = c as usize;
+    //~^ ERROR: pointer cast from `fn()` to `usize`
+}
+
+#[derive(Clone, Copy)]
+enum Level {
+    Low,
+    High,
+}
+
+fn not_categorized(level: Level, a: u32) {
+    let _ = level as u8;
+    let _ = a as u32;
+}
+
+// `From` can't be called in constants
+const WIDE: u64 = 1u8 as u64;
+//~^ ERROR: widening cast from `u8` to `u64`
+
+fn main() {}
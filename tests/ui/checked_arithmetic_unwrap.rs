@@ -0,0 +1,56 @@
+#![warn(clippy::checked_arithmetic_unwrap)]
+
+fn main() {}
+
+fn operators(a: u32, b: u32) {
+    let _ = a.checked_add(b).unwrap();
+    //~^ ERROR: `checked_add` is immediately unwrapped
+    let _ = a.checked_sub(b * 2).unwrap();
+    //~^ ERROR: `checked_sub` is immediately unwrapped
+    let _ = (a + 1).checked_mul(b).unwrap();
+    //~^ ERROR: `checked_mul` is immediately unwrapped
+    let _ = a.checked_div(b).unwrap();
+    //~^ ERROR: `checked_div` is immediately unwrapped
+    let _ = 7_i64.checked_rem(-2).unwrap();
+    //~^ ERROR: `checked_rem` is immediately unwrapped
+
+    // Ok: the overflow is handled
+    let _ = a.checked_add(b).unwrap_or(0);
+    let _ = a.checked_add(b).expect("the sum of two small numbers fits in a `u32`");
+    if let Some(sum) = a.checked_add(b) {
+        let _ = sum;
+    }
+}
+
+fn in_option(a: u8, b: u8) -> Option<u8> {
+    let sum = a.checked_add(b).unwrap();
+    //~^ ERROR: `checked_add` is immediately unwrapped
+    Some(sum)
+}
+
+fn in_closure(values: &[u16]) -> Vec<u16> {
+    let _ = values
+        .iter()
+        .map(|&v| -> Option<u16> { Some(v.checked_mul(2).unwrap() + 1) });
+    //~^ ERROR: `checked_mul` is immediately unwrapped
+    values.iter().map(|v| v.checked_mul(2).unwrap()).collect()
+    //~^ ERROR: `checked_mul` is immediately unwrapped
+}
+
+fn in_result(a: i32, b: i32) -> Result<i32, String> {
+    Ok(a.checked_sub(b).unwrap())
+    //~^ ERROR: `checked_sub` is immediately unwrapped
+}
+
+struct Meters(u32);
+
+impl Meters {
+    fn checked_add(&self, other: u32) -> Option<u32> {
+        self.0.checked_add(other)
+    }
+}
+
+fn not_an_integer(m: Meters) {
+    // Ok: not the integer method
+    let _ = m.checked_add(1).unwrap();
+}
@@ -0,0 +1,39 @@
+#![warn(clippy::too_many_lines, clippy::too_many_arguments, clippy::type_complexity)]
+#![allow(clippy::cognitive_complexity)]
+
+#[clippy::limit(too_many_lines = 2)]
+fn too_long() {
+    //~^ ERROR: this function has too many lines (3/2)
+    println!("1");
+    println!("2");
+    println!("3");
+}
+
+#[clippy::limit(too_many_lines = 3)]
+fn not_too_long() {
+    println!("1");
+    println!("2");
+    println!("3");
+}
+
+#[clippy::limit(too_many_arguments = 1, type_complexity = 10)]
+mod inherited {
+    fn two_args(_: u8, _: u8) {}
+    //~^ ERROR: this function has too many arguments (2/1)
+
+    fn complex(_: Vec<Vec<(u8, u8)>>) {}
+    //~^ ERROR: very complex type used. Consider factoring parts into `type` definitions
+
+    #[clippy::limit(too_many_arguments = 2)]
+    fn nearest_wins(_: u8, _: u8) {}
+}
+
+#[clippy::limit(too_many_lines)]
+//~^ ERROR: expected `name = N` with a known limit name
+#[clippy::limit(unknown_limit = 1)]
+//~^ ERROR: expected `name = N` with a known limit name
+#[clippy::limit = "1"]
+//~^ ERROR: bad clippy attribute
+fn malformed() {}
+
+fn main() {}
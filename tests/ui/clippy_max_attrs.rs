@@ -0,0 +1,57 @@
+#![warn(
+    clippy::too_many_lines,
+    clippy::too_many_arguments,
+    clippy::struct_excessive_bools,
+    clippy::fn_params_excessive_bools
+)]
+
+#[clippy::max_lines(2)]
+fn too_long() {
+    //~^ ERROR: this function has too many lines (3/2)
+    println!("1");
+    println!("2");
+    println!("3");
+}
+
+#[clippy::max_args(1)]
+mod strict {
+    fn two_args(_: u8, _: u8) {}
+    //~^ ERROR: this function has too many arguments (2/1)
+
+    #[clippy::max_struct_bools(1)]
+    struct Flags {
+        //~^ ERROR: more than 1 bools in a struct
+        a: bool,
+        b: bool,
+    }
+}
+
+struct Parser;
+
+#[clippy::max_args(8)]
+impl Parser {
+    fn many_args(&self, _: u8, _: u8, _: u8, _: u8, _: u8, _: u8, _: u8) {}
+
+    #[clippy::max_args(2)]
+    fn nearest_wins(&self, _: u8, _: u8) {}
+    //~^ ERROR: this function has too many arguments (3/2)
+
+    #[clippy::max_fn_params_bools(1)]
+    fn flags(&self, _: bool, _: bool) {}
+    //~^ ERROR: more than 1 bools in function parameters
+}
+
+// The shorthand and `#[clippy::limit(..)]` set the same thresholds.
+#[clippy::limit(too_many_arguments = 1)]
+#[clippy::max_args(3)]
+fn shorthand_after_limit(_: u8, _: u8, _: u8) {}
+
+#[clippy::max_lines]
+//~^ ERROR: expected `#[clippy::max_lines(N)]`
+#[clippy::max_lines(1, 2)]
+//~^ ERROR: expected `#[clippy::max_lines(N)]`
+#[clippy::max_lines("1")]
+//~^ ERROR: expected `#[clippy::max_lines(N)]`
+fn malformed() {}
+
+fn main() {}
@@ -0,0 +1,121 @@
+#![warn(clippy::closed_dyn_trait)]
+#![allow(dead_code)]
+
+mod shapes {
+    pub(crate) trait Shape {
+        //~^ ERROR: this trait is only used as a trait object, and has a few known implementors
+        fn area(&self) -> f64;
+    }
+
+    pub(crate) struct Circle(pub f64);
+    pub(crate) struct Square(pub f64);
+
+    impl Shape for Circle {
+        fn area(&self) -> f64 {
+            3.0 * self.0 * self.0
+        }
+    }
+
+    impl Shape for Square {
+        fn area(&self) -> f64 {
+            self.0 * self.0
+        }
+    }
+
+    pub(crate) fn total_area(shapes: &[Box<dyn Shape>]) -> f64 {
+        shapes.iter().map(|shape| shape.area()).sum()
+    }
+
+    pub(crate) fn largest(a: &dyn Shape, b: &dyn Shape) -> f64 {
+        a.area().max(b.area())
+    }
+}
+
+// Used as a bound
+trait Named {
+    fn name(&self) -> &str;
+}
+
+struct A;
+struct B;
+
+impl Named for A {
+    fn name(&self) -> &str {
+        "a"
+    }
+}
+
+impl Named for B {
+    fn name(&self) -> &str {
+        "b"
+    }
+}
+
+fn names(named: &[Box<dyn Named>]) -> Vec<&str> {
+    named.iter().map(|named| named.name()).collect()
+}
+
+fn name(named: &impl Named) -> &str {
+    named.name()
+}
+
+// Implemented for all types
+trait Blanket {
+    fn blanket(&self) {}
+}
+
+impl<T> Blanket for T {}
+
+fn blanket(b: &dyn Blanket) {
+    b.blanket();
+}
+
+// A single implementor
+trait Single {
+    fn single(&self) {}
+}
+
+impl Single for A {}
+
+fn single(s: Box<dyn Single>) {
+    s.single();
+}
+
+// Generic implementor
+struct Wrapper<T>(T);
+
+trait Wrapped {
+    fn wrapped(&self) {}
+}
+
+impl<T> Wrapped for Wrapper<T> {}
+impl Wrapped for A {}
+
+fn wrapped(w: &dyn Wrapped) {
+    w.wrapped();
+}
+
+// Exported
+pub trait Exported {
+    fn exported(&self) {}
+}
+
+impl Exported for A {}
+impl Exported for B {}
+
+pub fn exported(e: &dyn Exported) {
+    e.exported();
+}
+
+// Not used as a trait object
+trait Unused {
+    fn unused(&self) {}
+}
+
+impl Unused for A {}
+impl Unused for B {}
+
+fn main() {
+    let shapes: Vec<Box<dyn shapes::Shape>> = vec![Box::new(shapes::Circle(1.0)), Box::new(shapes::Square(2.0))];
+    shapes::total_area(&shapes);
+}
@@ -0,0 +1,18 @@
+#![warn(clippy::direct_clock_access)]
+
+use std::time::{Duration, Instant, SystemTime};
+
+fn main() {
+    let start = Instant::now();
+    //~^ ERROR: direct read of the clock with `std::time::Instant::now`
+    let _ = SystemTime::now();
+    //~^ ERROR: direct read of the clock with `std::time::SystemTime::now`
+    let _ = std::time::SystemTime::now();
+    //~^ ERROR: direct read of the clock with `std::time::SystemTime::now`
+    let _ = None.unwrap_or_else(Instant::now);
+    //~^ ERROR: direct read of the clock with `std::time::Instant::now`
+
+    // Not a read of the clock.
+    let _ = start.elapsed() > Duration::from_secs(1);
+    let _ = SystemTime::UNIX_EPOCH;
+}
@@ -0,0 +1,89 @@
+#![warn(clippy::display_forwarding_to_debug)]
+#![allow(clippy::uninlined_format_args)]
+
+use std::fmt::{self, Debug, Display};
+
+#[derive(Debug)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        //~^ ERROR: this `Display` implementation forwards to `Debug`
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug)]
+struct Inline(u32);
+
+impl Display for Inline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        //~^ ERROR: this `Display` implementation forwards to `Debug`
+        write!(f, "{self:?}")
+    }
+}
+
+#[derive(Debug)]
+struct Call;
+
+impl Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        //~^ ERROR: this `Display` implementation forwards to `Debug`
+        Debug::fmt(self, f)
+    }
+}
+
+struct Fields {
+    name: String,
+    tags: Vec<String>,
+}
+
+impl Display for Fields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        //~^ ERROR: this `Display` implementation formats every field with `Debug`
+        writeln!(f, "{:?} {:?}", self.name, &self.tags)
+    }
+}
+
+#[derive(Debug)]
+struct MyError;
+
+impl Display for MyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        //~^ ERROR: this `Display` implementation forwards to `Debug`
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for MyError {}
+
+// Human-readable
+impl Debug for Fields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.name)
+    }
+}
+
+struct Mixed {
+    name: String,
+    tags: Vec<String>,
+}
+
+impl Display for Mixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:?}", self.name, self.tags)
+    }
+}
+
+struct Other(Vec<u8>);
+
+impl Display for Other {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bytes: {:?}", self.0.len())
+    }
+}
+
+fn main() {}
@@ -0,0 +1,101 @@
+#![warn(clippy::dropped_error_source)]
+
+use std::error::Error;
+use std::{fmt, io, num};
+
+#[derive(Debug)]
+struct ConfigError(io::Error);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("can't read the configuration")
+    }
+}
+
+impl Error for ConfigError {}
+//~^ ERROR: this error type holds another error, but doesn't return it from `source`
+
+#[derive(Debug)]
+enum AppError {
+    Io(io::Error),
+    Other(Box<dyn Error + Send + Sync>),
+    Message(String),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("application error")
+    }
+}
+
+impl Error for AppError {}
+//~^ ERROR: this error type holds another error, but doesn't return it from `source`
+
+#[derive(Debug)]
+struct ParseError {
+    message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<num::ParseIntError> for ParseError {
+    fn from(e: num::ParseIntError) -> Self {
+        //~^ ERROR: this conversion drops the error it converts
+        Self { message: e.to_string() }
+    }
+}
+
+impl From<num::ParseFloatError> for ParseError {
+    fn from(e: num::ParseFloatError) -> Self {
+        //~^ ERROR: this conversion drops the error it converts
+        Self {
+            message: format!("invalid float: {e}"),
+        }
+    }
+}
+
+impl From<fmt::Error> for ParseError {
+    fn from(_: fmt::Error) -> Self {
+        //~^ ERROR: this conversion drops the error it converts
+        Self { message: String::new() }
+    }
+}
+
+// Keeps the source
+#[derive(Debug)]
+struct Wrapped(io::Error);
+
+impl fmt::Display for Wrapped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "io error: {}", self.0)
+    }
+}
+
+impl Error for Wrapped {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl From<io::Error> for Wrapped {
+    fn from(e: io::Error) -> Self {
+        Self(e)
+    }
+}
+
+// Not an error type
+struct Message(String);
+
+impl From<io::Error> for Message {
+    fn from(e: io::Error) -> Self {
+        Self(e.to_string())
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+#![warn(clippy::env_access_outside_config)]
+
+use std::env;
+
+const NAME: &str = "HOME";
+
+fn main() {
+    let _ = env::var("HOME");
+    //~^ ERROR: read of the environment variable `HOME` outside of the configuration modules
+    let _ = env::var_os(NAME);
+    //~^ ERROR: read of the environment variable `HOME` outside of the configuration modules
+    let _ = env::vars();
+    //~^ ERROR: read of the whole environment outside of the configuration modules
+    let _ = env::vars_os();
+    //~^ ERROR: read of the whole environment outside of the configuration modules
+    let _ = env!("PATH");
+    //~^ ERROR: read of the environment variable `PATH` outside of the configuration modules
+    let _ = option_env!("CARGO");
+    //~^ ERROR: read of the environment variable `CARGO` outside of the configuration modules
+    let name = String::from("HOME");
+    let _ = env::var(name);
+    //~^ ERROR: read of an environment variable outside of the configuration modules
+
+    // Not a read of the environment.
+    let _ = env::current_dir();
+}
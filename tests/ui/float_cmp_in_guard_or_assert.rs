@@ -0,0 +1,78 @@
+//@no-rustfix
+#![warn(clippy::float_cmp_in_guard_or_assert)]
+#![allow(clippy::float_cmp)]
+
+fn guards(ratio: f64, pair: (f32, f32)) {
+    match ratio {
+        x if x == 0.1 => {},
+        //~^ ERROR: strict comparison of `f32` or `f64` in a `match` guard
+        x if x != 0.5 && x > 1.0 => {},
+        //~^ ERROR: strict comparison of `f32` or `f64` in a `match` guard
+        x if !(x == ratio * 2.0) => {},
+        //~^ ERROR: strict comparison of `f32` or `f64` in a `match` guard
+        _ => {},
+    }
+    match pair {
+        (a, b) if a == b => {},
+        //~^ ERROR: strict comparison of `f32` or `f64` in a `match` guard
+        _ => {},
+    }
+
+    // Ok: comparisons with zero and infinities are exact
+    match ratio {
+        x if x == 0.0 => {},
+        x if x == f64::INFINITY => {},
+        x if x.signum() == ratio.signum() => {},
+        _ => {},
+    }
+
+    // Ok: not a guard
+    match ratio {
+        x if x > 1.0 => {
+            let _ = x == 0.1;
+        },
+        _ => {},
+    }
+}
+
+fn asserts(computed: f64, other: f32) {
+    assert_eq!(computed, 0.3);
+    //~^ ERROR: strict comparison of `f32` or `f64` in `assert_eq!`
+    assert_ne!(computed, 0.3);
+    //~^ ERROR: strict comparison of `f32` or `f64` in `assert_ne!`
+    debug_assert_eq!(other, 1.5);
+    //~^ ERROR: strict comparison of `f32` or `f64` in `debug_assert_eq!`
+    assert_eq!(computed * 2.0, computed + computed, "doubling is exact");
+    //~^ ERROR: strict comparison of `f32` or `f64` in `assert_eq!`
+    assert_eq!([computed, 1.0], [0.3, 1.0]);
+    //~^ ERROR: strict comparison of `f32` or `f64` in `assert_eq!`
+
+    // Ok: comparisons with zero and infinities are exact
+    assert_eq!(computed, 0.0);
+    assert_ne!(other, f32::NEG_INFINITY);
+    // Ok: not floats
+    assert_eq!(computed as i64, 0);
+}
+
+macro_rules! assert_ratio {
+    ($x:expr) => {
+        assert_eq!($x, 0.25)
+    };
+}
+
+fn from_macro(ratio: f64) {
+    // Ok: the assertion is written in the macro
+    assert_ratio!(ratio);
+}
+
+#[test]
+fn in_test() {
+    assert_eq!(0.1 + 0.2, 0.3);
+}
+
+#[cfg(test)]
+mod tests {
+    fn helper(x: f64) {
+        assert_eq!(x, 0.3);
+    }
+}
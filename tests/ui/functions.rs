@@ -127,4 +127,10 @@ impl Bar {
     }
 }
 
+pub fn nested_bodies(p: *const u8) {
+    let _ = [0u8; { 3u8.wrapping_add(1) as usize }];
+    let _ = || unsafe { std::ptr::read(p) };
+    //~^ ERROR: this public function might dereference a raw pointer but is not marked
+}
+
 fn main() {}
@@ -0,0 +1,90 @@
+#![warn(clippy::hash_eq_field_mismatch)]
+#![allow(clippy::inconsistent_hash_and_eq)]
+
+use std::hash::{Hash, Hasher};
+
+#[derive(PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+impl Hash for Point {
+    //~^ ERROR: this `Hash` implementation doesn't hash fields which `PartialEq` compares
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+    }
+}
+
+struct Manual {
+    id: u32,
+    name: String,
+}
+
+impl PartialEq for Manual {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.name == other.name
+    }
+}
+
+impl Hash for Manual {
+    //~^ ERROR: this `Hash` implementation doesn't hash fields which `PartialEq` compares
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Self { id, .. } = self;
+        id.hash(state);
+    }
+}
+
+// Same fields
+struct Same(u32, u32);
+
+impl PartialEq for Same {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1 == other.1
+    }
+}
+
+impl Hash for Same {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.0, self.1).hash(state);
+    }
+}
+
+// The fields `Hash` reads aren't known
+#[derive(PartialEq)]
+struct Opaque {
+    a: u32,
+    b: u32,
+}
+
+fn hash_opaque<H: Hasher>(opaque: &Opaque, state: &mut H) {
+    opaque.a.hash(state);
+    opaque.b.hash(state);
+}
+
+impl Hash for Opaque {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_opaque(self, state);
+    }
+}
+
+// Hashing a field which isn't compared is linted by `inconsistent_hash_and_eq` instead
+struct Both {
+    a: u32,
+    b: u32,
+}
+
+impl PartialEq for Both {
+    fn eq(&self, other: &Self) -> bool {
+        self.a == other.a
+    }
+}
+
+impl Hash for Both {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.b.hash(state);
+    }
+}
+
+fn main() {}
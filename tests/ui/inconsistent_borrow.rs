@@ -0,0 +1,89 @@
+#![warn(clippy::inconsistent_borrow)]
+#![allow(clippy::inconsistent_hash_and_eq)]
+
+use std::borrow::Borrow;
+use std::hash::{Hash, Hasher};
+
+#[derive(PartialEq, Eq, Hash)]
+struct User {
+    name: String,
+    age: u32,
+}
+
+impl Borrow<str> for User {
+    //~^ ERROR: this `Borrow` implementation doesn't return fields which `Hash` or `PartialEq` read
+    fn borrow(&self) -> &str {
+        &self.name
+    }
+}
+
+struct Key {
+    id: u32,
+    generation: u32,
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Key {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl Borrow<u32> for Key {
+    //~^ ERROR: this `Borrow` implementation doesn't return fields which `Hash` or `PartialEq` read
+    fn borrow(&self) -> &u32 {
+        &self.id
+    }
+}
+
+// Consistent
+struct Name {
+    name: String,
+    cached_len: usize,
+}
+
+impl PartialEq for Name {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Name {}
+
+impl Hash for Name {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl Borrow<str> for Name {
+    fn borrow(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+// Borrows the whole value
+#[derive(PartialEq, Eq, Hash)]
+struct Wrapper {
+    inner: String,
+}
+
+fn as_str(wrapper: &Wrapper) -> &str {
+    &wrapper.inner
+}
+
+impl Borrow<str> for Wrapper {
+    fn borrow(&self) -> &str {
+        as_str(self)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,99 @@
+#![warn(clippy::inconsistent_hash_and_eq)]
+
+use std::hash::{Hash, Hasher};
+
+struct Entry {
+    id: u32,
+    hits: u32,
+    name: String,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for Entry {
+    //~^ ERROR: this `Hash` implementation hashes fields which `PartialEq` doesn't compare
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.hits.hash(state);
+        self.name.hash(state);
+    }
+}
+
+struct Destructured(u32, u32);
+
+impl PartialEq for Destructured {
+    fn eq(&self, other: &Self) -> bool {
+        let Self(a, _) = self;
+        let Self(b, _) = other;
+        a == b
+    }
+}
+
+impl Hash for Destructured {
+    //~^ ERROR: this `Hash` implementation hashes fields which `PartialEq` doesn't compare
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Self(a, b) = self;
+        (a, b).hash(state);
+    }
+}
+
+// Hashing fewer fields than are compared is fine
+struct Subset {
+    id: u32,
+    hits: u32,
+}
+
+impl PartialEq for Subset {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.hits == other.hits
+    }
+}
+
+impl Hash for Subset {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+// `PartialEq` passes `self` to another function, the fields it compares aren't known
+struct Opaque {
+    id: u32,
+    hits: u32,
+}
+
+fn key(o: &Opaque) -> u32 {
+    o.id ^ o.hits
+}
+
+impl PartialEq for Opaque {
+    fn eq(&self, other: &Self) -> bool {
+        key(self) == key(other)
+    }
+}
+
+impl Hash for Opaque {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.hits.hash(state);
+    }
+}
+
+// Derived `PartialEq` compares every field
+#[derive(PartialEq)]
+struct Derived {
+    id: u32,
+    hits: u32,
+}
+
+impl Hash for Derived {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.hits.hash(state);
+    }
+}
+
+fn main() {}
@@ -25,8 +25,8 @@ const fn idx4() -> usize {
 fn main() {
     let x = [1, 2, 3, 4];
     let index: usize = 1;
+    // Ok, the index is known to be in bounds.
     x[index];
-    //~^ ERROR: indexing may panic
     // Ok, let rustc's `unconditional_panic` lint handle `usize` indexing on arrays.
     x[4];
     // Ok, let rustc's `unconditional_panic` lint handle `usize` indexing on arrays.
@@ -77,3 +77,40 @@ fn main() {
     let slice = &x;
     let _ = x[4];
 }
+
+#[allow(clippy::needless_range_loop)]
+fn bounded_index(x: [u8; 4], i: usize, j: usize, n: u8) {
+    // Ok, the indices are known to be in bounds.
+    x[i % 4];
+    x[i & 3];
+    x[(i >> 62) & 3];
+    x[(n >> 6) as usize];
+    x[i.min(3)];
+    if j < 4 {
+        x[j];
+    }
+    if j > 3 {
+        return;
+    }
+    x[j];
+    for k in 0..4 {
+        x[k];
+    }
+    match i {
+        0..=3 => x[i],
+        _ => 0,
+    };
+
+    x[i];
+    //~^ ERROR: indexing may panic
+    x[i % 5];
+    //~^ ERROR: indexing may panic
+    for k in 0..=4 {
+        x[k];
+        //~^ ERROR: indexing may panic
+    }
+    let mut m = 0;
+    m += i;
+    x[m % 8];
+    //~^ ERROR: indexing may panic
+}
@@ -103,3 +103,10 @@ fn issue_12586() {
     let y = vec![proc_macros::make_it_big!([x; 10])];
     let y = vec![create_then_move![x; 5]; 5];
 }
+
+fn generic<T: Copy>(t: T) {
+    // The size of `T` is unknown, but the array is at least this large.
+    let _ = [(t, [0u8; 1000]); 1000];
+    //~^ ERROR: allocating a local array larger than 512000 bytes
+    let _ = [t; 1_000_000];
+}
@@ -0,0 +1,85 @@
+#![warn(clippy::legacy_ref_pattern)]
+#![allow(
+    clippy::needless_borrowed_reference,
+    clippy::single_match,
+    clippy::match_single_binding
+)]
+
+struct Pair {
+    a: String,
+    b: Option<String>,
+}
+
+fn redundant_ref(x: Option<(u32, String)>, pair: &Pair) {
+    match &x {
+        Some((_, ref s)) => println!("{s}"),
+        //~^ ERROR: `ref` is redundant when the value is matched through a reference
+        None => {},
+    }
+    if let Some((ref n, ref s)) = &x {
+        //~^ ERROR: `ref` is redundant when the value is matched through a reference
+        println!("{n} {s}");
+    }
+    let Pair { ref a, b: Some(ref b) } = pair else {
+        //~^ ERROR: `ref` is redundant when the value is matched through a reference
+        return;
+    };
+    println!("{a} {b}");
+}
+
+fn explicit_deref(x: &Option<(u32, String)>, pairs: &[(String, (u32, String))]) {
+    if let &Some((ref n, ref s)) = x {
+        //~^ ERROR: dereferencing a pattern whose bindings all take a reference
+        println!("{n} {s}");
+    }
+    for &(ref key, (_, ref value)) in pairs {
+        //~^ ERROR: dereferencing a pattern whose bindings all take a reference
+        println!("{key} {value}");
+    }
+    // Linted by `needless_borrowed_reference`
+    let _ = x.as_ref().map(|&(ref n, _)| n);
+    let &(ref key, ref value) = &pairs[0];
+    println!("{key} {value:?}");
+}
+
+fn mixed_modes(x: &Option<(u32, String)>, y: &mut Option<String>, z: Option<(u32, String)>) {
+    // `n` is copied
+    if let &Some((n, ref s)) = x {
+        println!("{n} {s}");
+    }
+    // `ref` changes `&mut String` into `&String`
+    if let Some(ref s) = y {
+        println!("{s}");
+    }
+    // `ref mut` isn't redundant
+    if let &mut Some(ref mut s) = y {
+        s.push('a');
+    }
+    // Matched by value
+    if let Some((_, ref s)) = z {
+        println!("{s}");
+    }
+    // The binding modes of the alternatives must match
+    match x {
+        &Some((0, ref s)) | &Some((_, ref s)) => println!("{s}"),
+        _ => {},
+    }
+    // A reference inside, which would be matched by reference too
+    if let &Some((ref n, &ref s)) = &Some((1, &String::new())) {
+        println!("{n} {s}");
+    }
+}
+
+macro_rules! ref_pattern {
+    ($e:expr) => {
+        if let Some(ref s) = $e {
+            println!("{s}");
+        }
+    };
+}
+
+fn macros(x: &Option<String>) {
+    ref_pattern!(x);
+}
+
+fn main() {}
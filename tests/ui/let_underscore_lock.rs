@@ -43,3 +43,25 @@ fn uplifted() {
     let _ = m;
     let _ = rw;
 }
+
+#[clippy::has_significant_drop]
+struct Connection;
+
+fn connect() -> Connection {
+    Connection
+}
+
+fn significant_drop() {
+    let _ = connect();
+    //~^ ERROR: non-binding `let` on a type with a significant drop
+    let _ = Some(connect());
+    //~^ ERROR: non-binding `let` on a type with a significant drop
+
+    // A reference doesn't drop the value
+    let c = connect();
+    let _ = &c;
+
+    // Not linted by this lint
+    let _ = std::fs::File::open("foo");
+    let _ = std::cell::RefCell::new(0).borrow_mut();
+}
@@ -0,0 +1,119 @@
+#![warn(clippy::lossy_from)]
+#![allow(dead_code)]
+
+enum Event {
+    Click { x: i32, y: i32 },
+    Key(char),
+    Scroll(i32),
+    Resize(u32, u32),
+}
+
+enum Kind {
+    Click,
+    Other,
+}
+
+impl From<Event> for Kind {
+    fn from(event: Event) -> Self {
+        //~^ ERROR: this conversion ignores a part of the `Event`
+        match event {
+            Event::Click { .. } => Kind::Click,
+            _ => Kind::Other,
+        }
+    }
+}
+
+struct Position(i32);
+
+impl From<Event> for Position {
+    fn from(event: Event) -> Self {
+        //~^ ERROR: this conversion ignores a part of the `Event`
+        match event {
+            Event::Click { x, y: _ } => Position(x),
+            Event::Key(_) | Event::Scroll(..) => Position(0),
+            Event::Resize(w, ..) => Position(w as i32),
+        }
+    }
+}
+
+struct User {
+    name: String,
+    age: u32,
+    email: String,
+}
+
+struct Name(String);
+
+impl From<User> for Name {
+    fn from(user: User) -> Self {
+        //~^ ERROR: this conversion ignores a part of the `User`
+        Name(user.name)
+    }
+}
+
+impl From<User> for (String, u32) {
+    fn from(User { name, age, .. }: User) -> Self {
+        //~^ ERROR: this conversion ignores a part of the `User`
+        (name, age)
+    }
+}
+
+struct Unit;
+
+impl From<Event> for Unit {
+    fn from(_event: Event) -> Self {
+        //~^ ERROR: this conversion ignores a part of the `Event`
+        Unit
+    }
+}
+
+// Lossless
+struct Full {
+    name: String,
+    age: u32,
+    email: String,
+}
+
+impl From<User> for Full {
+    fn from(user: User) -> Self {
+        let User { name, age, email } = user;
+        Full { name, age, email }
+    }
+}
+
+enum Mirror {
+    Click(i32, i32),
+    Key(char),
+    Scroll(i32),
+    Resize(u32, u32),
+}
+
+impl From<Event> for Mirror {
+    fn from(event: Event) -> Self {
+        match event {
+            Event::Click { x, y } => Mirror::Click(x, y),
+            Event::Key(c) => Mirror::Key(c),
+            Event::Scroll(d) => Mirror::Scroll(d),
+            Event::Resize(w, h) => Mirror::Resize(w, h),
+        }
+    }
+}
+
+// The value is used as a whole
+struct Wrapper(Event);
+
+impl From<Event> for Wrapper {
+    fn from(event: Event) -> Self {
+        Wrapper(event)
+    }
+}
+
+// Acknowledged
+#[allow(clippy::lossy_from)]
+impl From<User> for u32 {
+    fn from(user: User) -> Self {
+        user.age
+    }
+}
+
+fn main() {}
@@ -93,3 +93,17 @@ fn issue12505() {
         };
     }
 }
+
+fn comments_and_cfg(a: u8) {
+    // Comments inside the `panic!` are kept by the suggestion itself
+    if a > 2 {
+        // removed
+        panic!("panic with comment" /* arg */)
+    }
+    // The statement is removed by `#[cfg]`, so don't lint
+    if a > 3 {
+        #[cfg(any())]
+        println!("not compiled");
+        panic!("panic with cfg")
+    }
+}
@@ -1,5 +1,5 @@
 #![warn(clippy::manual_retain)]
-#![allow(unused, clippy::redundant_clone)]
+#![allow(unused, clippy::clone_on_copy, clippy::redundant_clone)]
 use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
 
 fn main() {
@@ -17,6 +17,8 @@ fn main() {
 
     issue_10393();
     issue_12081();
+    map_iter_retain();
+    chars_retain();
 }
 
 fn binary_heap_retain() {
@@ -293,11 +295,11 @@ fn issue_10393() {
 }
 
 fn issue_11457() {
-    // Do not lint, as we need to modify the closure
+    // Do lint, the closure is adapted to the reference passed by `retain`
     let mut vals = vec![1, 2, 3, 4];
     vals = vals.iter().filter(|v| **v != 1).cloned().collect();
 
-    // Do not lint, as we need to modify the closure
+    // Do lint, the closure is adapted to the char passed by `retain`
     let mut s = String::from("foobar");
     s = s.chars().filter(|c| *c != 'o').to_owned().collect();
 }
@@ -315,3 +317,114 @@ fn issue_12081() {
     vec = vec.iter().filter(|&x| *x == 0).cloned().collect();
     vec = vec.into_iter().filter(|x| *x == 0).collect();
 }
+
+fn map_iter_retain() {
+    let mut hash_map: HashMap<i8, i8> = (0..8).map(|x| (x, x * 10)).collect();
+    // Do lint.
+    hash_map = hash_map
+        .iter()
+        .filter(|(k, _)| **k % 2 == 0)
+        .map(|(k, v)| (*k, *v))
+        .collect();
+    hash_map = hash_map
+        .iter()
+        .filter(|&(_, &v)| v > 10)
+        .map(|(&k, &v)| (k, v))
+        .collect();
+    hash_map = hash_map
+        .iter()
+        .filter(|(k, v)| k.is_positive() && **v != 0)
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+
+    let mut btree_map: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    let keep = |_: &String, _: &Vec<u8>| true;
+    // Do lint.
+    btree_map = btree_map
+        .iter()
+        .filter(|(k, v)| k.len() > 1 && keep(k, v) && !v.is_empty())
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    btree_map = btree_map
+        .iter()
+        .filter(|&(k, _)| k != "a")
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+
+    let map = &mut btree_map;
+    // Do lint.
+    *map = map
+        .iter()
+        .filter(|(k, _)| !k.is_empty())
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+
+    let mut str_map: HashMap<&str, u8> = HashMap::new();
+    // Do lint.
+    str_map = str_map
+        .iter()
+        .filter(|(k, _)| k.starts_with('a'))
+        .map(|(k, v)| (*k, *v))
+        .collect();
+
+    // Do not lint, because the entries are changed.
+    hash_map = hash_map
+        .iter()
+        .filter(|(k, _)| **k % 2 == 0)
+        .map(|(k, v)| (*k, *v + 1))
+        .collect();
+    hash_map = hash_map
+        .iter()
+        .filter(|(k, _)| **k % 2 == 0)
+        .map(|(k, v)| (*v, *k))
+        .collect();
+    // Do not lint, because the closure takes the whole entry.
+    hash_map = hash_map
+        .iter()
+        .filter(|e| *e.0 % 2 == 0)
+        .map(|(k, v)| (*k, *v))
+        .collect();
+    // Do not lint, because the binding is used in a macro.
+    hash_map = hash_map
+        .iter()
+        .filter(|(k, _)| {
+            println!("{}", k);
+            **k % 2 == 0
+        })
+        .map(|(k, v)| (*k, *v))
+        .collect();
+    // Do not lint, because it is an assignment to a different variable.
+    let other: HashMap<i8, i8> = HashMap::new();
+    hash_map = other
+        .iter()
+        .filter(|(k, _)| **k % 2 == 0)
+        .map(|(k, v)| (*k, *v))
+        .collect();
+}
+
+fn chars_retain() {
+    let mut s = String::from("foobar");
+    // Do lint.
+    s = s.chars().filter(|&c| c != 'o').collect();
+    s = s.chars().filter(|c| *c != 'o').collect();
+    s = s.chars().filter(|c| c.is_alphabetic()).collect();
+    s = s.chars().filter(|c| ['a', 'b'].contains(c)).collect();
+
+    let t = &mut s;
+    // Do lint.
+    *t = t.chars().filter(|c| c.is_ascii()).collect();
+
+    // Do not lint, because it is an assignment to a different variable.
+    let bar = String::new();
+    s = bar.chars().filter(|&c| c != 'o').collect();
+}
+
+#[clippy::msrv = "1.52"]
+fn _msrv_152() {
+    let mut btree_map: BTreeMap<i8, i8> = (0..8).map(|x| (x, x * 10)).collect();
+    btree_map = btree_map
+        .iter()
+        .filter(|(k, _)| **k % 2 == 0)
+        .map(|(k, v)| (*k, *v))
+        .collect();
+}
@@ -0,0 +1,59 @@
+//@no-rustfix
+#![warn(clippy::manual_slice_pattern)]
+#![allow(clippy::needless_if, clippy::ptr_arg)]
+
+fn f(_: u32, _: u32) {}
+
+fn lint(v: Vec<u32>, s: &[String], r: &Vec<(u32, u32)>) {
+    if v.len() == 2 {
+        //~^ ERROR: this length check is followed by indexing with constant indices
+        f(v[0], v[1]);
+    }
+    if 3 == s.len() {
+        //~^ ERROR: this length check is followed by indexing with constant indices
+        println!("{} {}", s[0].len(), &s[2]);
+    } else {
+        println!("{}", s.len());
+    }
+    if let 1 = r.len() {
+        //~^ ERROR: this length check is followed by indexing with constant indices
+        let _ = r[0].1;
+        let _ = || r[0].0;
+    }
+}
+
+fn no_lint(mut v: Vec<u32>, s: &[u32], i: usize) {
+    // Used otherwise
+    if v.len() == 2 {
+        f(v[0], v.iter().sum());
+    }
+    // Not a constant index
+    if s.len() == 2 {
+        f(s[0], s[i]);
+    }
+    // Index out of the checked length
+    if s.len() == 2 {
+        f(s[0], s[2]);
+    }
+    // Modified
+    if v.len() == 2 {
+        v[0] = 1;
+        f(v[0], v[1]);
+    }
+    if v.len() == 1 {
+        let x = &mut v[0];
+        *x += 1;
+    }
+    // Longer than `max-suggested-slice-pattern-length`
+    if s.len() == 4 {
+        f(s[0], s[3]);
+    }
+    // Not indexed
+    if s.len() == 2 {}
+    // Other comparisons
+    if s.len() >= 2 {
+        f(s[0], s[1]);
+    }
+}
+
+fn main() {}
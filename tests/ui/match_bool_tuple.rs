@@ -0,0 +1,100 @@
+#![warn(clippy::match_bool_tuple)]
+#![allow(clippy::needless_bool, clippy::nonminimal_bool, clippy::match_like_matches_macro)]
+
+fn foo() {}
+fn bar() {}
+fn baz() {}
+
+struct S {
+    a: bool,
+    b: bool,
+}
+
+fn bool_exprs(a: bool, b: bool, c: bool, s: &S) {
+    let _ = match (a, b) {
+        //~^ ERROR: you seem to be trying to match on a tuple of booleans
+        (true, true) => true,
+        _ => false,
+    };
+    let _ = match (a, b) {
+        //~^ ERROR: you seem to be trying to match on a tuple of booleans
+        (false, false) => false,
+        _ => true,
+    };
+    let _ = match (a, b, c) {
+        //~^ ERROR: you seem to be trying to match on a tuple of booleans
+        (true, false, _) => true,
+        (_, _, _) => false,
+    };
+    let _ = match (s.a, s.b) {
+        //~^ ERROR: you seem to be trying to match on a tuple of booleans
+        (true, true) | (false, false) => true,
+        (true, false) | (false, true) => false,
+    };
+    let _ = match (a, b) {
+        //~^ ERROR: you seem to be trying to match on a tuple of booleans
+        (true, false) => true,
+        (false, true) => true,
+        _ => false,
+    };
+    let _ = !match (a, b) {
+        //~^ ERROR: you seem to be trying to match on a tuple of booleans
+        (true, _) => true,
+        (_, true) => true,
+        _ => false,
+    };
+    // No boolean expression
+    let _ = match (a, b, c) {
+        //~^ ERROR: you seem to be trying to match on a tuple of booleans
+        (true, true, _) => true,
+        (_, _, true) => true,
+        _ => false,
+    };
+}
+
+fn if_chains(a: bool, b: bool, c: bool) {
+    match (a, b) {
+        //~^ ERROR: you seem to be trying to match on a tuple of booleans
+        (true, false) => foo(),
+        _ => bar(),
+    }
+    match (a, b, c) {
+        //~^ ERROR: you seem to be trying to match on a tuple of booleans
+        (false, true, _) | (false, _, true) => {
+            bar();
+            baz();
+        },
+        _ => {},
+    }
+    let _ = match (a.then_some(1).is_some(), b) {
+        //~^ ERROR: you seem to be trying to match on a tuple of booleans
+        (true, true) => 1,
+        _ => 2,
+    } + 1;
+}
+
+fn no_lint(a: bool, b: bool, x: u8) {
+    // Too many booleans
+    match (a, b, a, b) {
+        (true, true, true, true) => foo(),
+        _ => bar(),
+    }
+    // Not only booleans
+    match (a, x) {
+        (true, 1) => foo(),
+        _ => bar(),
+    }
+    // A decision table
+    match (a, b) {
+        (true, true) => foo(),
+        (false, _) => bar(),
+        (true, false) => baz(),
+    }
+    // A binding
+    match (a, b) {
+        (true, b) if b => foo(),
+        _ => bar(),
+    }
+}
+
+fn main() {}
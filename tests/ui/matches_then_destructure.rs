@@ -0,0 +1,101 @@
+#![warn(clippy::matches_then_destructure)]
+#![allow(
+    clippy::manual_let_else,
+    clippy::redundant_pattern_matching,
+    clippy::single_match_else
+)]
+
+enum E {
+    A(u8, String),
+    B { x: u8, y: u8 },
+    C,
+}
+
+fn let_else(x: Option<u8>, e: &E) {
+    if matches!(x, Some(_)) {
+        //~^ ERROR: this `matches!` is followed by a destructuring of the same value
+        let Some(y) = x else { unreachable!() };
+        println!("{y}");
+    }
+
+    if matches!(e, E::A(1, _)) {
+        //~^ ERROR: this `matches!` is followed by a destructuring of the same value
+        let E::A(1, s) = e else {
+            panic!("not an `A`");
+        };
+        println!("{s}");
+    } else {
+        println!("other");
+    }
+
+    if matches!(*e, E::B { x: 0, .. }) {
+        //~^ ERROR: this `matches!` is followed by a destructuring of the same value
+        let E::B { x: 0, y } = *e else { unreachable!() };
+        println!("{y}");
+    }
+}
+
+fn if_let_or_match(x: Result<u8, ()>, e: &E) {
+    if matches!(x, Ok(_)) {
+        //~^ ERROR: this `matches!` is followed by a destructuring of the same value
+        let y = if let Ok(y) = x { y } else { unreachable!() };
+        println!("{y}");
+    }
+
+    if matches!(e, E::A(..)) {
+        //~^ ERROR: this `matches!` is followed by a destructuring of the same value
+        let n = match e {
+            E::A(n, _) => *n,
+            _ => panic!(),
+        };
+        println!("{n}");
+    }
+}
+
+fn no_lint(x: Option<u8>, y: Option<u8>, e: &E, mut it: std::vec::IntoIter<u8>) {
+    // Not the same value
+    if matches!(x, Some(_)) {
+        let Some(y) = y else { unreachable!() };
+        println!("{y}");
+    }
+
+    // Not the same pattern
+    if matches!(x, Some(_)) {
+        let Some(1) = x else { unreachable!() };
+    }
+    if matches!(e, E::A(1, _)) {
+        let E::A(_, s) = e else { unreachable!() };
+        println!("{s}");
+    }
+    if matches!(e, E::A(..) | E::C) {
+        let E::A(_, s) = e else { unreachable!() };
+        println!("{s}");
+    }
+
+    // The scrutinee has side effects
+    if matches!(it.next(), Some(_)) {
+        let Some(y) = it.next() else { unreachable!() };
+        println!("{y}");
+    }
+
+    // A guard
+    if matches!(x, Some(n) if n > 1) {
+        let Some(y) = x else { unreachable!() };
+        println!("{y}");
+    }
+
+    // The other case doesn't panic
+    if matches!(x, Some(_)) {
+        let Some(y) = x else { return };
+        println!("{y}");
+    }
+
+    // Not the first statement
+    if matches!(x, Some(_)) {
+        println!("some");
+        let Some(y) = x else { unreachable!() };
+        println!("{y}");
+    }
+}
+
+fn main() {}
@@ -31,4 +31,14 @@ fn main() {
     //~| NOTE: argument has type `std::string::String`
 
     std::mem::forget(7);
+
+    let m = std::sync::Mutex::new(0);
+    std::mem::forget(m.lock().unwrap());
+    //~^ ERROR: usage of `mem::forget` on a lock guard, whose drop is never run
+    //~| NOTE: argument has type `std::sync::MutexGuard<'_, i32>`
+
+    let (tx, _rx) = std::sync::mpsc::channel::<i32>();
+    std::mem::forget(Some(tx));
+    //~^ ERROR: usage of `mem::forget` on a type containing a channel endpoint
+    //~| NOTE: argument has type `std::option::Option<std::sync::mpsc::Sender<i32>>`
 }
@@ -165,3 +165,35 @@ union U {
 fn h(u: U) -> u32 {
     unsafe { u.f }
 }
+
+// Do not lint because these features are only stable in `const fn` since a later version
+mod msrv_const_fn_features {
+    #[clippy::msrv = "1.60"]
+    fn trait_bound<T: Copy>(t: &T) -> T {
+        *t
+    }
+
+    struct Wrapper<T>(T);
+
+    #[clippy::msrv = "1.60"]
+    impl<T: Copy> Wrapper<T> {
+        fn get(&self) -> T {
+            self.0
+        }
+    }
+
+    #[clippy::msrv = "1.57"]
+    unsafe fn read_raw(ptr: *const u8) -> u8 {
+        *ptr
+    }
+
+    #[clippy::msrv = "1.53"]
+    fn as_slice(array: &[u8; 4]) -> &[u8] {
+        array
+    }
+
+    #[clippy::msrv = "1.61"]
+    extern "C" fn c_abi() -> i32 {
+        0
+    }
+}
@@ -99,6 +99,42 @@ fn msrv_1_46() -> i32 {
     46
 }
 
+mod msrv_const_fn_features {
+    #[clippy::msrv = "1.61"]
+    fn trait_bound<T: Copy>(t: &T) -> T {
+        //~^ ERROR: this could be a `const fn`
+        *t
+    }
+
+    struct Wrapper<T>(T);
+
+    #[clippy::msrv = "1.61"]
+    impl<T: Copy> Wrapper<T> {
+        fn get(&self) -> T {
+            //~^ ERROR: this could be a `const fn`
+            self.0
+        }
+    }
+
+    #[clippy::msrv = "1.58"]
+    unsafe fn read_raw(ptr: *const u8) -> u8 {
+        //~^ ERROR: this could be a `const fn`
+        *ptr
+    }
+
+    #[clippy::msrv = "1.54"]
+    fn as_slice(array: &[u8; 4]) -> &[u8] {
+        //~^ ERROR: this could be a `const fn`
+        array
+    }
+
+    #[clippy::msrv = "1.62"]
+    extern "C" fn c_abi() -> i32 {
+        //~^ ERROR: this could be a `const fn`
+        0
+    }
+}
+
 // Should not be const
 fn main() {}
 
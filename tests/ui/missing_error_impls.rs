@@ -0,0 +1,118 @@
+#![warn(clippy::missing_error_impls)]
+#![allow(clippy::result_unit_err, dead_code, private_interfaces)]
+
+use std::error::Error;
+use std::fmt;
+
+pub struct NoImpls;
+//~^ ERROR: public error type `NoImpls` doesn't implement `Debug`, `Display` and `std::error::Error`
+
+pub fn no_impls() -> Result<(), NoImpls> {
+    Err(NoImpls)
+}
+
+#[derive(Debug)]
+pub enum OnlyDebug {
+    //~^ ERROR: doesn't implement `Display` and `std::error::Error`
+    Empty,
+    Invalid(char),
+}
+
+pub struct Parser;
+
+impl Parser {
+    pub fn parse(&self, input: &str) -> Result<u32, OnlyDebug> {
+        input
+            .chars()
+            .next()
+            .map_or(Err(OnlyDebug::Empty), |c| Err(OnlyDebug::Invalid(c)))
+    }
+}
+
+#[derive(Debug)]
+pub struct NoError;
+//~^ ERROR: public error type `NoError` doesn't implement `std::error::Error`
+
+impl fmt::Display for NoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("no error")
+    }
+}
+
+pub trait Source {
+    fn read(&mut self) -> Result<Vec<u8>, NoError>;
+}
+
+#[derive(Debug)]
+pub struct Generic<T>(T);
+//~^ ERROR: public error type `Generic` doesn't implement `Display` and `std::error::Error`
+
+pub type GenericResult<T> = Result<T, Generic<T>>;
+
+pub fn generic<T>(value: T) -> GenericResult<T> {
+    Err(Generic(value))
+}
+
+// Implements all the traits
+#[derive(Debug)]
+pub struct Complete;
+
+impl fmt::Display for Complete {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("complete")
+    }
+}
+
+impl Error for Complete {}
+
+pub fn complete() -> Result<(), Complete> {
+    Err(Complete)
+}
+
+// Generic types implementing the traits for some parameters
+#[derive(Debug)]
+pub struct Wrapper<T>(T);
+
+impl<T: fmt::Display> fmt::Display for Wrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> Error for Wrapper<T> {}
+
+pub fn wrapper<T>(value: T) -> Result<(), Wrapper<T>> {
+    Err(Wrapper(value))
+}
+
+// Not returned by a public function
+pub struct Unused;
+
+fn private() -> Result<(), Unused> {
+    Err(Unused)
+}
+
+// Not public
+struct Private;
+
+pub fn private_error() -> Result<(), Private> {
+    Err(Private)
+}
+
+// Trait impls are linted at the trait
+pub struct FromStrError;
+
+impl std::str::FromStr for Parser {
+    type Err = FromStrError;
+
+    fn from_str(_: &str) -> Result<Self, FromStrError> {
+        Err(FromStrError)
+    }
+}
+
+// Foreign types can't be changed
+pub fn foreign() -> Result<(), std::fmt::Error> {
+    Ok(())
+}
+
+fn main() {}
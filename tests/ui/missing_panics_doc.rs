@@ -191,3 +191,9 @@ fn from_declared_macro_should_lint_at_macrosite() {
     // Not here.
     some_macro_that_panics!()
 }
+
+/// This is okay because the error type is uninhabited
+pub fn infallible_unwrap(s: &str) -> String {
+    let result: Result<String, std::convert::Infallible> = s.parse();
+    result.unwrap()
+}
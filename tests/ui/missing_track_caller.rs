@@ -0,0 +1,124 @@
+#![warn(clippy::missing_track_caller)]
+#![allow(clippy::manual_assert)]
+
+fn percent(value: u32) -> f64 {
+    //~^ ERROR: this function panics on invalid arguments, but isn't `#[track_caller]`
+    assert!(value <= 100, "not a percentage: {value}");
+    f64::from(value) / 100.0
+}
+
+pub fn same_len(a: &[u8], b: &[u8]) -> usize {
+    //~^ ERROR: this function panics on invalid arguments, but isn't `#[track_caller]`
+    assert_eq!(a.len(), b.len());
+    a.len()
+}
+
+fn non_zero(value: i32) -> i32 {
+    //~^ ERROR: this function panics on invalid arguments, but isn't `#[track_caller]`
+    if value == 0 {
+        panic!("zero");
+    }
+    value
+}
+
+fn required(value: Option<&str>) -> &str {
+    //~^ ERROR: this function panics on invalid arguments, but isn't `#[track_caller]`
+    value.expect("missing value")
+}
+
+struct Buffer {
+    data: Vec<u8>,
+}
+
+impl Buffer {
+    fn set(&mut self, index: usize, value: u8) {
+        //~^ ERROR: this function panics on invalid arguments, but isn't `#[track_caller]`
+        assert!(index < self.data.len());
+        assert_ne!(value, 0);
+        self.data[index] = value;
+    }
+
+    #[track_caller]
+    fn get(&self, index: usize) -> u8 {
+        assert!(index < self.data.len());
+        self.data[index]
+    }
+}
+
+// The assertion checks a computed value, not an argument.
+fn checks_result(a: u32, b: u32) -> u32 {
+    let sum = a + b;
+    assert!(sum < 100);
+    sum
+}
+
+// Other panics.
+fn other_panic(value: u32) -> u32 {
+    assert!(value < 10);
+    if value == 5 {
+        unreachable!();
+    }
+    value
+}
+
+fn unwraps_other(value: u32) -> u32 {
+    assert!(value < 10);
+    "1".parse::<u32>().unwrap() + value
+}
+
+fn in_closure(values: &[u32]) -> Vec<u32> {
+    values
+        .iter()
+        .map(|&v| {
+            assert!(v < 10);
+            v
+        })
+        .collect()
+}
+
+// No panics.
+fn no_panic(value: u32) -> u32 {
+    value + 1
+}
+
+// Too long for a helper.
+fn long(value: u32) -> u32 {
+    assert!(value < 10);
+    let a = value + 1;
+    let b = a + 1;
+    let c = b + 1;
+    let d = c + 1;
+    let e = d + 1;
+    let f = e + 1;
+    let g = f + 1;
+    let h = g + 1;
+    h + 1
+}
+
+// A `Result` is handled, not checked.
+fn handle(result: Result<u32, String>) -> u32 {
+    result.unwrap()
+}
+
+// Trait methods.
+trait Check {
+    fn check(&self, value: u32) {
+        assert!(value > 0);
+    }
+}
+
+impl Check for Buffer {
+    fn check(&self, value: u32) {
+        assert!(value < 10);
+    }
+}
+
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        assert!(self.data.is_empty());
+    }
+}
+
+fn main() {
+    assert!(std::env::args().count() > 0);
+}
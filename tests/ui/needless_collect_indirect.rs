@@ -322,3 +322,23 @@ mod issue_8553 {
         w.len();
     }
 }
+
+mod escaping_borrow {
+    fn returned(v: Vec<i32>) -> impl Iterator<Item = i32> {
+        // Do not lint, the iterator borrowing `v` can't be returned
+        let w: Vec<i32> = v.iter().map(|i| i * 2).collect();
+        w.into_iter()
+    }
+
+    fn returned_owned(v: Vec<i32>) -> impl Iterator<Item = i32> {
+        let w: Vec<i32> = v.into_iter().map(|i| i * 2).collect();
+        //~^ ERROR: avoid using `collect()` when not needed
+        w.into_iter()
+    }
+
+    fn not_returned(v: Vec<i32>) -> i32 {
+        let w: Vec<i32> = v.iter().map(|i| i * 2).collect();
+        //~^ ERROR: avoid using `collect()` when not needed
+        w.into_iter().sum()
+    }
+}
@@ -0,0 +1,184 @@
+#![warn(clippy::nested_match_pyramid)]
+#![allow(
+    clippy::collapsible_match,
+    clippy::manual_let_else,
+    clippy::manual_map,
+    clippy::manual_unwrap_or_default,
+    clippy::needless_return,
+    clippy::question_mark,
+    clippy::single_match_else
+)]
+
+fn first_even_len(v: &[Option<&str>]) -> Option<usize> {
+    if let Some(first) = v.first() {
+        //~^ ERROR: these nested matches only continue on the happy path
+        match first {
+            Some(s) => {
+                let len = s.len();
+                (len % 2 == 0).then_some(len)
+            },
+            None => None,
+        }
+    } else {
+        None
+    }
+}
+
+fn parse_sum(a: &str, b: &str) -> Result<i32, ()> {
+    match a.parse::<i32>() {
+        //~^ ERROR: these nested matches only continue on the happy path
+        Ok(a) => match b.parse::<i32>() {
+            Ok(b) => Ok(a + b),
+            Err(_) => Err(()),
+        },
+        Err(_) => Err(()),
+    }
+}
+
+fn three_levels(x: Option<Option<Option<u8>>>) -> u8 {
+    if let Some(x) = x {
+        //~^ ERROR: these nested matches only continue on the happy path
+        if let Some(x) = x {
+            match x {
+                None => 0,
+                Some(x) => {
+                    println!("{x}");
+                    x
+                },
+            }
+        } else {
+            return 1;
+        }
+    } else {
+        0
+    }
+}
+
+fn in_loop(v: &[Option<Result<u8, ()>>]) {
+    for x in v {
+        if let Some(x) = x {
+            //~^ ERROR: these nested matches only continue on the happy path
+            if let Ok(x) = x {
+                println!("{x}");
+            } else {
+                continue;
+            }
+        } else {
+            break;
+        }
+    }
+}
+
+fn in_statement(x: Option<Option<u8>>) {
+    let _ = 1;
+    match x {
+        //~^ ERROR: these nested matches only continue on the happy path
+        Some(x) => {
+            if let Some(x) = x {
+                println!("{x}");
+            } else {
+                return;
+            }
+        },
+        _ => return,
+    };
+}
+
+// Only one level
+fn one_level(x: Option<u8>) -> Option<u8> {
+    if let Some(x) = x { Some(x + 1) } else { None }
+}
+
+// The next level isn't the last expression of the happy path
+fn not_last(x: Option<Option<u8>>) -> u8 {
+    if let Some(x) = x {
+        let y = if let Some(x) = x {
+            x
+        } else {
+            return 0;
+        };
+        y + 1
+    } else {
+        0
+    }
+}
+
+// The other case isn't trivial
+fn not_trivial(x: Option<Option<u8>>) -> u8 {
+    if let Some(x) = x {
+        if let Some(x) = x {
+            x
+        } else {
+            println!("none");
+            0
+        }
+    } else {
+        0
+    }
+}
+
+// The values of the other cases can't be returned, since the pyramid isn't the value of the
+// function.
+fn not_fn_value(x: Option<Option<u8>>) -> u8 {
+    let y = if let Some(x) = x {
+        if let Some(x) = x { x } else { 0 }
+    } else {
+        0
+    };
+    y + 1
+}
+
+// The pyramid isn't the last statement of its block.
+fn not_last_statement(x: Option<Option<u8>>) {
+    if let Some(x) = x {
+        if let Some(x) = x {
+            println!("{x}");
+        } else {
+            return;
+        }
+    } else {
+        return;
+    }
+    println!("done");
+}
+
+// Part of an `else if` chain
+fn else_if(b: bool, x: Option<Option<u8>>) -> u8 {
+    if b {
+        1
+    } else if let Some(x) = x {
+        if let Some(x) = x { x } else { return 0 }
+    } else {
+        return 0;
+    }
+}
+
+// The other case of the `match` isn't a failure case.
+fn other_pattern(x: Option<Option<u8>>) -> u8 {
+    match x {
+        Some(Some(x)) => x,
+        Some(None) => 0,
+        None => 0,
+    }
+}
+
+#[clippy::msrv = "1.64"]
+fn msrv_1_64(x: Option<Option<u8>>) -> u8 {
+    if let Some(x) = x {
+        if let Some(x) = x { x } else { return 0 }
+    } else {
+        0
+    }
+}
+
+#[clippy::msrv = "1.65"]
+fn msrv_1_65(x: Option<Option<u8>>) -> u8 {
+    if let Some(x) = x {
+        //~^ ERROR: these nested matches only continue on the happy path
+        if let Some(x) = x { x } else { return 0 }
+    } else {
+        0
+    }
+}
+
+fn main() {}
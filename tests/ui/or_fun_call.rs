@@ -311,4 +311,44 @@ mod lazy {
     }
 }
 
+mod costs {
+    const fn repeat(n: u32) -> [u32; 4] {
+        [n; 4]
+    }
+
+    fn expensive() -> String {
+        "expensive".repeat(100)
+    }
+
+    fn f(opt: Option<[u32; 4]>, n: u32) {
+        // `const fn`s are cheap.
+        let _ = opt.unwrap_or(repeat(n));
+
+        let mut opt = None;
+        opt.get_or_insert(String::from("value"));
+        //~^ ERROR: use of `get_or_insert` followed by a function call
+        opt.get_or_insert(String::new());
+
+        let mut map = std::collections::HashMap::<u64, String>::new();
+        map.entry(1).or_insert(expensive());
+        //~^ ERROR: use of `or_insert` followed by a function call
+    }
+
+    fn effects(opt: Option<u32>, len: Option<usize>, a: u32, b: u32, s: &str, v: &mut Vec<u32>) {
+        // Pure calls are cheap.
+        let _ = opt.unwrap_or(a.min(b));
+        let _ = len.unwrap_or(s.trim().len());
+
+        // The calls which allocate, may panic, do I/O or mutate something aren't.
+        let _ = len.unwrap_or(s.to_uppercase().len());
+        //~^ ERROR: use of `unwrap_or` followed by a function call
+        let _ = opt.unwrap_or(s.parse().unwrap());
+        //~^ ERROR: use of `unwrap_or` followed by a function call
+        let _ = len.unwrap_or(std::env::args().count());
+        //~^ ERROR: use of `unwrap_or` followed by a function call
+        let _ = opt.unwrap_or(v.pop().unwrap_or(0));
+        //~^ ERROR: use of `unwrap_or` followed by a function call
+    }
+}
+
 fn main() {}
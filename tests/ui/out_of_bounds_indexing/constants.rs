@@ -0,0 +1,45 @@
+#![warn(clippy::out_of_bounds_indexing)]
+#![allow(clippy::no_effect, clippy::unnecessary_operation)]
+
+const SLICE: &[u8] = &[1, 2, 3];
+const STR: &str = "abc";
+const BOUNDS: (usize, usize) = (1, 4);
+
+struct Range {
+    start: usize,
+    end: usize,
+}
+
+const RANGE: Range = Range { start: 2, end: 5 };
+
+enum Index {
+    First,
+    Last = 4,
+}
+
+fn main() {
+    let x = [1, 2, 3, 4];
+
+    SLICE[3];
+    //~^ ERROR: index is out of bounds
+    //~| NOTE: `-D clippy::out-of-bounds-indexing` implied by `-D warnings`
+    &SLICE[..4];
+    //~^ ERROR: range is out of bounds
+    &STR[1..4];
+    //~^ ERROR: range is out of bounds
+    &x[BOUNDS.0..BOUNDS.1 + 1];
+    //~^ ERROR: range is out of bounds
+    &x[RANGE.start..RANGE.end];
+    //~^ ERROR: range is out of bounds
+    &x[Index::Last as usize + 1..];
+    //~^ ERROR: range is out of bounds
+    &x[..SLICE.len() + 2];
+    //~^ ERROR: range is out of bounds
+
+    SLICE[2]; // Ok, should not produce stderr.
+    &SLICE[..3]; // Ok, should not produce stderr.
+    &STR[..3]; // Ok, should not produce stderr.
+    &x[BOUNDS.0..BOUNDS.1]; // Ok, should not produce stderr.
+    &x[Index::First as usize..Index::Last as usize]; // Ok, should not produce stderr.
+    &x[..SLICE.len() + 1]; // Ok, should not produce stderr.
+}
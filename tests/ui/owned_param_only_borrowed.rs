@@ -0,0 +1,108 @@
+#![warn(clippy::owned_param_only_borrowed)]
+#![allow(
+    clippy::needless_pass_by_value,
+    clippy::needless_lifetimes,
+    clippy::ptr_arg,
+    clippy::useless_vec,
+    dead_code
+)]
+
+use std::path::{Path, PathBuf};
+
+fn takes_str(_: &str) {}
+fn takes_slice(_: &[u8]) {}
+fn takes_string(_: String) {}
+
+fn greet(name: String) {
+    //~^ ERROR: this `String` is passed by value, but only used as a `&str`
+    println!("hello {}", name.trim());
+    takes_str(&name);
+    let _ = name.as_str().len();
+    println!("{name:?}");
+}
+
+fn sum(values: Vec<u8>, scale: u8) -> u8 {
+    //~^ ERROR: this `Vec` is passed by value, but only used as a `&[_]`
+    takes_slice(&values);
+    values.iter().map(|v| v * scale).sum::<u8>() + values[0]
+}
+
+fn exists(path: PathBuf) -> bool {
+    //~^ ERROR: this `PathBuf` is passed by value, but only used as a `&Path`
+    let copy = path.clone();
+    path.is_absolute() && copy.exists()
+}
+
+// `needless_pass_by_value` defers to this lint
+#[warn(clippy::needless_pass_by_value)]
+fn both(name: String) -> bool {
+    //~^ ERROR: this `String` is passed by value, but only used as a `&str`
+    name.is_empty()
+}
+
+struct Greeter;
+
+impl Greeter {
+    fn greet(&self, name: String) -> usize {
+        //~^ ERROR: this `String` is passed by value, but only used as a `&str`
+        name.len()
+    }
+}
+
+fn callers(owned: String, path: &PathBuf) {
+    greet(owned.clone());
+    greet("world".to_string());
+    greet(String::from("world"));
+    greet(format!("{owned}!"));
+    let _ = sum(vec![1, 2], 2);
+    let _ = exists(path.clone());
+    let _ = Greeter.greet(owned.clone());
+    takes_string(owned);
+}
+
+// Ok: the argument is moved
+fn moved(name: String) {
+    takes_string(name);
+}
+
+// Ok: the argument is moved into the return value
+fn returned(name: String) -> String {
+    name
+}
+
+// Ok: `String` methods
+fn capacity(name: String) -> usize {
+    name.capacity()
+}
+
+// Ok: mutated
+fn mutated(mut values: Vec<u8>) -> usize {
+    values.push(1);
+    values.len()
+}
+
+// Ok: moved into a closure
+fn captured(name: String) -> impl Fn() -> usize {
+    move || name.len()
+}
+
+// Ok: the return type borrows from the arguments
+fn first<'a>(values: &'a [u8], name: String) -> &'a u8 {
+    takes_str(&name);
+    &values[0]
+}
+
+// Ok: passed to a generic function
+fn generic(name: String) {
+    fn show(_: impl AsRef<str>) {}
+    show(name);
+}
+
+trait Named {
+    // Ok: trait methods
+    fn named(&self, name: String) -> usize {
+        name.len()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,133 @@
+#![warn(clippy::panicking_iterator_next)]
+
+struct Items<'a> {
+    items: &'a [u32],
+    pos: usize,
+}
+
+impl Iterator for Items<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        //~^ ERROR: this `next` implementation never returns `None`, but can panic
+        let item = self.items[self.pos];
+        self.pos += 1;
+        Some(item)
+    }
+}
+
+struct Stack(Vec<u32>);
+
+impl Iterator for Stack {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        //~^ ERROR: this `next` implementation never returns `None`, but can panic
+        Some(self.0.pop().unwrap())
+    }
+}
+
+struct Countdown(u32);
+
+impl Iterator for Countdown {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        //~^ ERROR: this `next` implementation never returns `None`, but can panic
+        if self.0 == 0 {
+            panic!("exhausted");
+        }
+        self.0 -= 1;
+        Some(self.0)
+    }
+}
+
+// Returns `None` when exhausted
+struct Checked<'a> {
+    items: &'a [u32],
+    pos: usize,
+}
+
+impl Iterator for Checked<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.pos == self.items.len() {
+            return None;
+        }
+        let item = self.items[self.pos];
+        self.pos += 1;
+        Some(item)
+    }
+}
+
+struct Question<'a> {
+    items: &'a [u32],
+    pos: usize,
+}
+
+impl Iterator for Question<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let item = *self.items.get(self.pos)?;
+        self.pos += 1;
+        Some(item + self.items[0])
+    }
+}
+
+struct Forward(std::vec::IntoIter<u32>);
+
+impl Iterator for Forward {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let x = self.0.as_slice()[0];
+        self.0.next().map(|y| x + y)
+    }
+}
+
+// Infinite, without panics
+struct Counter(u32);
+
+impl Iterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.0 = self.0.wrapping_add(1);
+        Some(self.0)
+    }
+}
+
+// Infinite, cycling over a buffer
+struct Cycle<'a> {
+    buf: &'a [u32],
+    i: usize,
+}
+
+impl Iterator for Cycle<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let item = self.buf[self.i % self.buf.len()];
+        self.i += 1;
+        Some(item)
+    }
+}
+
+struct Ring<'a> {
+    buf: &'a [u32],
+    i: usize,
+}
+
+impl Iterator for Ring<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let item = self.buf[self.i];
+        self.i = (self.i + 1).rem_euclid(self.buf.len());
+        Some(item)
+    }
+}
+
+fn main() {}
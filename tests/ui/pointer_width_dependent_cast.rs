@@ -0,0 +1,32 @@
+//@no-rustfix
+#![warn(clippy::pointer_width_dependent_cast)]
+#![allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap, clippy::cast_lossless)]
+
+fn main() {}
+
+fn casts(pos: u64, len: usize, offset: i64, count: u32) {
+    let _ = pos as usize;
+    //~^ ERROR: casting `u64` to `usize` may truncate the value on targets with 32-bit wide pointers
+    let _ = offset as isize;
+    //~^ ERROR: casting `i64` to `isize` may truncate the value on targets with 32-bit wide pointers
+    let _ = count as isize;
+    //~^ ERROR: casting `u32` to `isize` may wrap around the value on targets with 32-bit wide pointers
+    let _ = len as u64;
+    let _ = len as i128;
+    let _: usize = pos as _;
+    //~^ ERROR: casting `u64` to `usize` may truncate the value on targets with 32-bit wide pointers
+
+    // Ok: lossless on every configured width
+    let _ = count as usize;
+    let _ = (count as u16) as isize;
+    let _ = (pos & 0xffff) as usize;
+    let _ = len as isize as usize;
+
+    // Ok: lossy on the current target, see `cast_possible_truncation`
+    let _ = len as u32;
+    let _ = len as i32;
+
+    // Constants are still linted
+    let _ = 42_u64 as usize;
+    //~^ ERROR: casting `u64` to `usize` may truncate the value on targets with 32-bit wide pointers
+}
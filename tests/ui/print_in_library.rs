@@ -0,0 +1,16 @@
+//@no-rustfix
+#![warn(clippy::print_in_library)]
+#![allow(clippy::print_literal)]
+
+pub fn load(path: &str) {
+    println!("loading {path}");
+    //~^ ERROR: use of `println!` in a library
+    print!("{}", 1);
+    //~^ ERROR: use of `print!` in a library
+    eprintln!("can't read {}", path);
+    //~^ ERROR: use of `eprintln!` in a library
+    eprint!("{path}");
+    //~^ ERROR: use of `eprint!` in a library
+    println!();
+    //~^ ERROR: use of `println!` in a library
+}
@@ -0,0 +1,7 @@
+#![warn(clippy::print_in_library)]
+
+// Binaries may print.
+fn main() {
+    println!("hello");
+    eprintln!("world");
+}
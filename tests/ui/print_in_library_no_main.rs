@@ -0,0 +1,8 @@
+#![warn(clippy::print_in_library)]
+#![no_main]
+
+// `#![no_main]` crates are binaries, even when they are built without `--crate-type=bin`.
+#[no_mangle]
+pub extern "C" fn start() {
+    println!("hello");
+}
@@ -0,0 +1,11 @@
+#![warn(clippy::print_in_library)]
+
+// A library built with the test harness.
+pub fn load(path: &str) {
+    println!("loading {path}");
+}
+
+#[test]
+fn test_load() {
+    println!("in a test");
+}
@@ -0,0 +1,100 @@
+#![warn(clippy::returned_vec_only_iterated)]
+#![allow(clippy::needless_pass_by_value)]
+
+fn evens(n: u32) -> Vec<u32> {
+    //~^ ERROR: this function returns a `Vec` which every caller only iterates
+    (0..n).filter(|x| x % 2 == 0).collect()
+}
+
+fn incremented(values: &[u32]) -> Vec<u32> {
+    //~^ ERROR: this function returns a `Vec` which every caller only iterates
+    values.iter().map(|v| v + 1).collect()
+}
+
+fn doubled(values: Vec<u32>, factor: u32) -> Vec<u32> {
+    //~^ ERROR: this function returns a `Vec` which every caller only iterates
+    values.into_iter().map(move |v| v * factor).collect()
+}
+
+struct Numbers;
+
+impl Numbers {
+    fn squares(&self, n: u32) -> Vec<u32> {
+        //~^ ERROR: this function returns a `Vec` which every caller only iterates
+        (0..n).map(|x| x * x).collect()
+    }
+}
+
+fn callers(words: &[&str]) -> u32 {
+    for x in evens(10) {
+        println!("{x}");
+    }
+    let total: u32 = evens(4).into_iter().sum();
+    let longest = incremented(&[1, 2]).into_iter().max();
+    let _ = lengths(words).into_iter().max();
+    for v in doubled(vec![1, 2], 3).into_iter().filter(|v| *v > 2) {
+        println!("{v} {longest:?}");
+    }
+    total + Numbers.squares(3).into_iter().map(|x| x + 1).sum::<u32>()
+}
+
+// Ok: the lifetime of the iterator can't be elided
+fn lengths(words: &[&str]) -> Vec<usize> {
+    words.iter().map(|w| w.len()).collect()
+}
+
+// Ok: the result is kept
+fn kept(n: u32) -> Vec<u32> {
+    (0..n).collect()
+}
+
+// Ok: the iterator is reversed
+fn reversed(n: u32) -> Vec<u32> {
+    (0..n).collect()
+}
+
+// Ok: the iterator would borrow a local
+fn borrows_local(n: u32) -> Vec<u32> {
+    let offset = n / 2;
+    (0..n).map(|x| x + offset).collect()
+}
+
+// Ok: the iterator would borrow an argument
+fn borrows_argument(values: Vec<u32>) -> Vec<u32> {
+    values.iter().map(|v| v + 1).collect()
+}
+
+// Ok: passed as a function
+fn as_value(n: u32) -> Vec<u32> {
+    (0..n).collect()
+}
+
+// Ok: exported
+pub fn exported(n: u32) -> Vec<u32> {
+    (0..n).collect()
+}
+
+// Ok: not called
+fn not_called(n: u32) -> Vec<u32> {
+    (0..n).collect()
+}
+
+fn other_callers() {
+    let v = kept(3);
+    println!("{}", v.len());
+    for x in reversed(3).into_iter().rev() {
+        println!("{x}");
+    }
+    for x in borrows_local(3).into_iter().chain(borrows_argument(vec![1])) {
+        println!("{x}");
+    }
+    let _ = [1].map(as_value);
+    for x in exported(2) {
+        println!("{x}");
+    }
+}
+
+fn main() {
+    println!("{}", callers(&["a"]));
+    other_callers();
+}
@@ -0,0 +1,51 @@
+#![warn(clippy::shift_amount_overflow)]
+#![allow(clippy::identity_op)]
+
+fn main() {}
+
+fn scaled(x: u64, byte_count: u8) {
+    let bits = u32::from(byte_count) * 8;
+    let _ = x >> bits;
+    //~^ ERROR: this shift amount can reach the bit width of `u64`
+    let _ = 1_u32 << (bits + 1);
+    //~^ ERROR: this shift amount can reach the bit width of `u32`
+}
+
+fn bounded(mut x: u64, y: i16, n: u32) {
+    for i in 0..=64 {
+        let _ = x << i;
+        //~^ ERROR: this shift amount can reach the bit width of `u64`
+    }
+    let amount = n % 17;
+    let _ = y >> amount;
+    //~^ ERROR: this shift amount can reach the bit width of `i16`
+    if n <= 100 {
+        x <<= n;
+        //~^ ERROR: this shift amount can reach the bit width of `u64`
+    }
+}
+
+fn ok(x: u64, n: u32, bytes: u8, byte_count: usize) {
+    // within the bit width
+    for i in 0..64 {
+        let _ = x << i;
+    }
+    let _ = x << (n % 64);
+    let _ = x << (n & 63);
+    let _ = x >> (u32::from(bytes & 7) * 8);
+    let _ = x >> (u32::from(bytes % 8).min(7) * 8);
+    let amount: u32 = (bytes % 8).into();
+    let _ = x >> amount;
+    if n < 64 {
+        let _ = x << n;
+    }
+    // nothing is known about the amount
+    let _ = x << n;
+    let _ = x << (byte_count * 8);
+    let _ = 1_u64 << (n + 1);
+    for i in 0..n {
+        let _ = x >> i;
+    }
+    // constant amounts are checked by rustc
+    let _ = x << 4;
+}
@@ -0,0 +1,71 @@
+#![warn(clippy::trim_matches_instead_of_strip)]
+#![allow(clippy::manual_strip)]
+
+use std::str::FromStr;
+
+fn parsed(s: &str, owned: String) {
+    let _ = u32::from_str_radix(s.trim_start_matches("0x"), 16);
+    //~^ ERROR: `trim_start_matches` removes all the repetitions of the pattern, not only the first one
+    let _: Result<u32, _> = s.trim_end_matches("px").parse();
+    //~^ ERROR: `trim_end_matches` removes all the repetitions of the pattern, not only the first one
+    let _ = u32::from_str(owned.trim_start_matches("v:"));
+    //~^ ERROR: `trim_start_matches` removes all the repetitions of the pattern, not only the first one
+    let digits = s.trim_start_matches("0b");
+    //~^ ERROR: `trim_start_matches` removes all the repetitions of the pattern, not only the first one
+    println!("{digits}");
+    let _ = u8::from_str_radix(digits, 2);
+}
+
+fn checked(s: &str) {
+    if s.starts_with("--") && s.len() > 2 {
+        let _ = s.trim_start_matches("--");
+        //~^ ERROR: `trim_start_matches` removes all the repetitions of the pattern, not only the first one
+    }
+    if s.ends_with(".rs") {
+        println!("{}", s.trim_end_matches(".rs"));
+        //~^ ERROR: `trim_end_matches` removes all the repetitions of the pattern, not only the first one
+    }
+}
+
+fn no_lint(s: &str) {
+    // The same character, or a single one
+    let _: Result<u32, _> = s.trim_start_matches("00").parse();
+    if s.starts_with("-") {
+        let _ = s.trim_start_matches("-");
+    }
+    let _: Result<u32, _> = s.trim_start_matches('0').parse();
+    // Not a literal
+    let prefix = "0x";
+    let _ = u32::from_str_radix(s.trim_start_matches(prefix), 16);
+    // Neither parsed nor checked
+    let _ = s.trim_start_matches("ab");
+    if !s.starts_with("--") {
+        let _ = s.trim_start_matches("--");
+    }
+    if s.starts_with("--") || s.is_empty() {
+        let _ = s.trim_start_matches("--");
+    }
+    // Another pattern or string
+    if s.starts_with("--") {
+        let _ = s.trim_start_matches("-+");
+        let _ = "--x".trim_start_matches("--");
+    }
+    // In the `else` branch
+    if s.starts_with("--") {
+    } else {
+        let _ = s.trim_start_matches("--");
+    }
+}
+
+#[clippy::msrv = "1.44"]
+fn msrv_1_44(s: &str) {
+    let _ = u32::from_str_radix(s.trim_start_matches("0x"), 16);
+}
+
+#[clippy::msrv = "1.45"]
+fn msrv_1_45(s: &str) {
+    let _ = u32::from_str_radix(s.trim_start_matches("0x"), 16);
+    //~^ ERROR: `trim_start_matches` removes all the repetitions of the pattern
+}
+
+fn main() {}
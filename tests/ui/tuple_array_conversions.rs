@@ -1,9 +1,11 @@
 //@aux-build:proc_macros.rs
+//@aux-build:macro_rules.rs
 #![allow(clippy::no_effect, clippy::useless_vec, unused)]
 #![warn(clippy::tuple_array_conversions)]
 
 #[macro_use]
 extern crate proc_macros;
+extern crate macro_rules;
 
 fn main() {
     let x = [1, 2];
@@ -52,6 +54,15 @@ fn main() {
         let v1: Vec<[u32; 2]> = t1.iter().map(|&(a, b)| [a, b]).collect();
         let t2: Vec<(u32, u32)> = v1.iter().map(|&[a, b]| (a, b)).collect();
     }
+    macro_rules! local_conversion {
+        () => {
+            let t = (1, 2);
+            let _ = [t.0, t.1];
+        };
+    }
+    local_conversion!();
+    // The local macro is expanded by an external macro
+    macro_rules::call_macro!(local_conversion);
     // FP #11082; needs discussion
     let (a, b) = (1.0f64, 2.0f64);
     let _: &[f64] = &[a, b];
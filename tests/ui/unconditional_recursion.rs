@@ -60,10 +60,12 @@ enum Foo4 {
 
 impl PartialEq for Foo4 {
     fn ne(&self, other: &Self) -> bool {
-        self.eq(other) // no error
+        //~^ ERROR: function cannot return without recursing
+        self.eq(other)
     }
     fn eq(&self, other: &Self) -> bool {
-        self.ne(other) // no error
+        //~^ ERROR: function cannot return without recursing
+        self.ne(other)
     }
 }
 
@@ -243,6 +245,7 @@ struct S12;
 
 impl std::default::Default for S12 {
     fn default() -> Self {
+        //~^ ERROR: function cannot return without recursing
         Self::new()
     }
 }
@@ -398,4 +401,45 @@ impl From<&BadFromTy4> for i32 {
     }
 }
 
+// `eq` and `ne` calling each other
+struct MutualEq;
+impl PartialEq for MutualEq {
+    fn eq(&self, other: &Self) -> bool {
+        //~^ ERROR: function cannot return without recursing
+        !self.ne(other)
+    }
+    fn ne(&self, other: &Self) -> bool {
+        //~^ ERROR: function cannot return without recursing
+        !self.eq(other)
+    }
+}
+
+// Through a function of the crate
+struct MutualDisplay;
+impl std::fmt::Display for MutualDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        //~^ ERROR: function cannot return without recursing
+        write_mutual_display(self, f)
+    }
+}
+fn write_mutual_display(value: &MutualDisplay, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    std::fmt::Display::fmt(value, f)
+}
+
+// The recursion ends when `done` is `true`
+struct ConditionalMutual {
+    done: bool,
+}
+impl PartialEq for ConditionalMutual {
+    fn eq(&self, other: &Self) -> bool {
+        conditional_mutual_eq(self, other)
+    }
+}
+fn conditional_mutual_eq(left: &ConditionalMutual, right: &ConditionalMutual) -> bool {
+    if left.done {
+        return true;
+    }
+    left == right || left.eq(right)
+}
+
 fn main() {}
@@ -0,0 +1,60 @@
+#![warn(clippy::unguarded_time_subtraction)]
+#![allow(clippy::unchecked_duration_subtraction, clippy::nonminimal_bool)]
+
+use std::time::{Duration, Instant};
+
+fn instants(start: Instant, end: Instant) {
+    let _ = end - start;
+    //~^ ERROR: this subtraction of `Instant`s assumes their order
+
+    // Ok: the order is established
+    if end >= start {
+        let _ = end - start;
+    }
+    if start < end && true {
+        let _ = end - start;
+    }
+}
+
+fn durations(timeout: Duration, elapsed: Duration, mut left: Duration) {
+    let _ = timeout - elapsed;
+    //~^ ERROR: this subtraction of `Duration`s can underflow
+    left -= elapsed;
+    //~^ ERROR: this subtraction of `Duration`s can underflow
+
+    // Ok: guarded by an early return
+    if timeout < elapsed {
+        return;
+    }
+    let _ = timeout - elapsed;
+}
+
+fn asserted(timeout: Duration, elapsed: Duration) {
+    assert!(timeout >= elapsed);
+    let _ = timeout - elapsed;
+}
+
+fn wrong_guard(timeout: Duration, elapsed: Duration) {
+    if timeout <= elapsed {
+        let _ = timeout - elapsed;
+        //~^ ERROR: this subtraction of `Duration`s can underflow
+    }
+}
+
+fn constructors(deadline: u64, now: u64, nanos: u32, signed: i64) {
+    let _ = Duration::from_secs(deadline - now);
+    //~^ ERROR: this subtraction can underflow, creating a huge `Duration` in release builds
+    let _ = Duration::new(deadline, nanos - 1);
+    //~^ ERROR: this subtraction can underflow, creating a huge `Duration` in release builds
+
+    // Ok: guarded
+    if !(deadline < now) {
+        let _ = Duration::from_secs(deadline - now);
+    }
+    // Ok: constant
+    let _ = Duration::from_secs(10 - 5);
+    // Ok: not a `Duration` constructor
+    let _ = signed - 1;
+}
+
+fn main() {}
@@ -0,0 +1,108 @@
+#![warn(clippy::unused_trait_bound)]
+#![allow(dead_code, clippy::needless_pass_by_value)]
+
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+fn largest<T: PartialOrd + Clone>(values: &[T]) -> Option<&T> {
+    //~^ ERROR: this trait bound is never required
+    values.iter().reduce(|a, b| if b > a { b } else { a })
+}
+
+fn show<T: Debug + Display + Hash>(value: T) -> String {
+    //~^ ERROR: these trait bounds are never required
+    format!("{value}")
+}
+
+fn only_unused<T: Clone>(value: &T) -> usize {
+    //~^ ERROR: this trait bound is never required
+    std::mem::size_of_val(value)
+}
+
+fn in_where<T, U>(t: T, u: U) -> String
+where
+    T: Display,
+    U: Clone,
+    //~^ ERROR: this trait bound is never required
+{
+    drop(u);
+    t.to_string()
+}
+
+fn closure<F: Fn(u8) -> u8, G: Clone>(f: F, _g: G) -> u8 {
+    //~^ ERROR: this trait bound is never required
+    f(1)
+}
+
+// Needed to infer the type of the closures of the callers
+fn unused_closure<F: Fn(u8) -> bool>(_f: F) {}
+
+// `Ord` implies `PartialOrd`, only one of them is removed
+fn implied<T: PartialOrd + Ord>(a: T, b: T) -> bool {
+    //~^ ERROR: this trait bound is never required
+    a < b
+}
+
+struct Wrapper<T>(T);
+
+impl<T: Clone + Debug> Wrapper<T> {
+    //~^ ERROR: this trait bound is never required
+    fn get(&self) -> T {
+        self.0.clone()
+    }
+
+    fn print<U: Display + Clone>(&self, u: U) {
+        //~^ ERROR: this trait bound is never required
+        println!("{u}");
+    }
+}
+
+struct Bounded<T: Clone>(T);
+
+// Required by the self type
+impl<T: Clone> Bounded<T> {
+    fn new(value: T) -> Self {
+        Self(value)
+    }
+}
+
+// Used through the trait bounds of the other functions
+fn used_by_call<T: Clone + Debug>(value: &T) -> T {
+    largest_clone(value)
+}
+
+fn largest_clone<T: Clone + Debug>(value: &T) -> T {
+    println!("{value:?}");
+    value.clone()
+}
+
+// Used through the operators and traits
+fn add<T: std::ops::Add<Output = T> + Copy>(a: T, b: T) -> T {
+    a + b
+}
+
+fn deref<T: std::ops::Deref<Target = str>>(s: T) -> usize {
+    s.len()
+}
+
+fn boxed<T: Debug + 'static>(value: T) -> Box<dyn Debug> {
+    Box::new(value)
+}
+
+fn returns_impl<T: Debug>(value: T) -> impl Sized {
+    value
+}
+
+fn sized_copy<T: Copy + Sized>(value: &T) -> (T, T) {
+    (*value, *value)
+}
+
+trait Tr {
+    fn f<T: Clone>(&self, t: T);
+}
+
+impl Tr for () {
+    fn f<T: Clone>(&self, _t: T) {}
+}
+
+fn main() {}
@@ -119,3 +119,10 @@ fn f() {
     v.push((0i32, 0i32));
     let y = v[0].0.abs();
 }
+
+fn g() {
+    let mut v = Vec::new();
+    //~^ ERROR: calls to `push` immediately after creation
+    v.push((0i32, 0i32));
+    v[0].0 += 1;
+}